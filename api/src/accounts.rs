@@ -366,7 +366,7 @@ impl Account {
                     &self.latest_ledger_info,
                     BasicResponseStatus::Ok,
                 ))
-                .map(|v| v.with_cursor(next_state_key))
+                .map(|v| v.with_cursor(next_state_key.map(StateKeyWrapper::from)))
             },
             AcceptType::Bcs => {
                 // Put resources in a BTreeMap to ensure they're ordered the same every time
@@ -376,7 +376,7 @@ impl Account {
                     &self.latest_ledger_info,
                     BasicResponseStatus::Ok,
                 ))
-                .map(|v| v.with_cursor(next_state_key))
+                .map(|v| v.with_cursor(next_state_key.map(StateKeyWrapper::from)))
             },
         }
     }
@@ -438,7 +438,7 @@ impl Account {
                     &self.latest_ledger_info,
                     BasicResponseStatus::Ok,
                 ))
-                .map(|v| v.with_cursor(next_state_key))
+                .map(|v| v.with_cursor(next_state_key.map(StateKeyWrapper::from)))
             },
             AcceptType::Bcs => {
                 // Sort modules by name
@@ -451,7 +451,7 @@ impl Account {
                     &self.latest_ledger_info,
                     BasicResponseStatus::Ok,
                 ))
-                .map(|v| v.with_cursor(next_state_key))
+                .map(|v| v.with_cursor(next_state_key.map(StateKeyWrapper::from)))
             },
         }
     }
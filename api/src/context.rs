@@ -15,7 +15,7 @@ use aptos_api_types::{
     AptosErrorCode, AsConverter, BcsBlock, GasEstimation, LedgerInfo, ResourceGroup,
     TransactionOnChainData,
 };
-use aptos_config::config::{NodeConfig, RoleType};
+use aptos_config::config::{ApiConfig, NodeConfig, RoleType};
 use aptos_crypto::HashValue;
 use aptos_gas_schedule::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_logger::{error, warn};
@@ -47,7 +47,7 @@ use aptos_utils::aptos_try;
 use aptos_vm::data_cache::AsMoveResolver;
 use futures::{channel::oneshot, SinkExt};
 use move_core_types::{
-    language_storage::{ModuleId, StructTag},
+    language_storage::{ModuleId, StructTag, TypeTag},
     resolver::ModuleResolver,
 };
 use std::{
@@ -56,6 +56,7 @@ use std::{
     sync::{Arc, RwLock, RwLockWriteGuard},
     time::Instant,
 };
+use tokio::sync::watch;
 
 // Context holds application scope context
 #[derive(Clone)]
@@ -64,6 +65,9 @@ pub struct Context {
     pub db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
     pub node_config: NodeConfig,
+    /// The latest API config. Unlike the API config in `node_config`, it is
+    /// updated when the node config is reloaded.
+    api_config: watch::Receiver<ApiConfig>,
     gas_schedule_cache: Arc<RwLock<GasScheduleCache>>,
     gas_estimation_cache: Arc<RwLock<GasEstimationCache>>,
     gas_limit_cache: Arc<RwLock<GasLimitCache>>,
@@ -82,11 +86,13 @@ impl Context {
         mp_sender: MempoolClientSender,
         node_config: NodeConfig,
     ) -> Self {
+        let (_, api_config) = watch::channel(node_config.api.clone());
         Self {
             chain_id,
             db,
             mp_sender,
             node_config,
+            api_config,
             gas_schedule_cache: Arc::new(RwLock::new(GasScheduleCache {
                 last_updated_epoch: None,
                 gas_schedule_params: None,
@@ -104,20 +110,27 @@ impl Context {
         }
     }
 
+    /// Applies the given API config updates (e.g., from node config reloads).
+    /// Only the page and batch size limits are read from the latest config.
+    pub fn with_api_config_updates(mut self, api_config: watch::Receiver<ApiConfig>) -> Self {
+        self.api_config = api_config;
+        self
+    }
+
     pub fn max_transactions_page_size(&self) -> u16 {
-        self.node_config.api.max_transactions_page_size
+        self.api_config.borrow().max_transactions_page_size
     }
 
     pub fn max_events_page_size(&self) -> u16 {
-        self.node_config.api.max_events_page_size
+        self.api_config.borrow().max_events_page_size
     }
 
     pub fn max_account_resources_page_size(&self) -> u16 {
-        self.node_config.api.max_account_resources_page_size
+        self.api_config.borrow().max_account_resources_page_size
     }
 
     pub fn max_account_modules_page_size(&self) -> u16 {
-        self.node_config.api.max_account_modules_page_size
+        self.api_config.borrow().max_account_modules_page_size
     }
 
     pub fn latest_state_view(&self) -> Result<DbStateView> {
@@ -166,12 +179,19 @@ impl Context {
         self.node_config.api.content_length_limit()
     }
 
+    /// Whether some ledger history is kept past the prune window, either the history of the
+    /// retained accounts and event types, or everything in cold storage
+    pub fn retains_ledger_history(&self) -> bool {
+        !self.node_config.storage.ledger_retention.is_empty()
+            || self.node_config.storage.ledger_cold_storage.enable
+    }
+
     pub fn failpoints_enabled(&self) -> bool {
         self.node_config.api.failpoints_enabled
     }
 
     pub fn max_submit_transaction_batch_size(&self) -> usize {
-        self.node_config.api.max_submit_transaction_batch_size
+        self.api_config.borrow().max_submit_transaction_batch_size
     }
 
     pub async fn submit_transaction(&self, txn: SignedTransaction) -> Result<SubmissionStatus> {
@@ -564,7 +584,7 @@ impl Context {
 
         // We can only get the max_transactions page size
         let max_txns = std::cmp::min(
-            self.node_config.api.max_transactions_page_size,
+            self.max_transactions_page_size(),
             (last_version - first_version + 1) as u16,
         );
         let txns = if with_transactions {
@@ -759,6 +779,48 @@ impl Context {
             .map_err(|err| E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info))
     }
 
+    /// Gets the transactions involving an account, starting at version `start`, or the latest
+    /// ones if `start` isn't given.
+    pub fn get_account_involved_transactions<E: InternalError>(
+        &self,
+        address: AccountAddress,
+        start: Option<u64>,
+        limit: u16,
+        ledger_version: u64,
+        ledger_info: &LedgerInfo,
+    ) -> Result<Vec<TransactionOnChainData>, E> {
+        let (start, order) = match start {
+            Some(start) => (start, Order::Ascending),
+            None => (u64::MAX, Order::Descending),
+        };
+        let mut txns = self
+            .db
+            .get_account_involved_transactions(
+                address,
+                start,
+                order,
+                limit as u64,
+                true,
+                ledger_version,
+            )
+            .context("Failed to retrieve account involved transactions")
+            .map_err(|err| E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info))?
+            .into_inner();
+        if order == Order::Descending {
+            txns.reverse();
+        }
+        txns.into_iter()
+            .map(|t| self.convert_into_transaction_on_chain_data(t))
+            .collect::<Result<Vec<_>>>()
+            .context("Failed to parse account involved transactions")
+            .map_err(|err| E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info))
+    }
+
+    /// Whether the storage indexes the transactions by the accounts involved in them
+    pub fn account_involvement_index_enabled(&self) -> bool {
+        self.node_config.storage.enable_account_involvement_index
+    }
+
     pub fn get_transaction_by_hash(
         &self,
         hash: HashValue,
@@ -845,6 +907,37 @@ impl Context {
         }
     }
 
+    pub fn get_events_by_type(
+        &self,
+        event_type: &TypeTag,
+        start: Option<(u64, u64)>,
+        limit: u16,
+        ledger_version: u64,
+    ) -> Result<Vec<(u64, EventWithVersion)>> {
+        if let Some(start) = start {
+            self.db.get_events_by_type(
+                event_type,
+                start,
+                Order::Ascending,
+                limit as u64,
+                ledger_version,
+            )
+        } else {
+            self.db
+                .get_events_by_type(
+                    event_type,
+                    (u64::MAX, u64::MAX),
+                    Order::Descending,
+                    limit as u64,
+                    ledger_version,
+                )
+                .map(|mut result| {
+                    result.reverse();
+                    result
+                })
+        }
+    }
+
     fn next_bucket(&self, gas_unit_price: u64) -> u64 {
         match self
             .node_config
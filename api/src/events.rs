@@ -16,11 +16,12 @@ use crate::{
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    verify_field_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper, LedgerInfo,
-    MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
+    verify_field_identifier, Address, AptosErrorCode, AsConverter, EventCursor, IdentifierWrapper,
+    LedgerInfo, MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
 };
-use aptos_types::event::EventKey;
+use aptos_types::{contract_event::EventWithVersion, event::EventKey};
 use aptos_vm::data_cache::AsMoveResolver;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use poem_openapi::{
     param::{Path, Query},
     OpenApi,
@@ -151,6 +152,69 @@ impl EventsApi {
         })
         .await
     }
+
+    /// Get module events by event type
+    ///
+    /// Module events have no event handle, so they are identified by their
+    /// type. This API returns the module events of the given type, in the order
+    /// they were emitted.
+    ///
+    /// The response has an X-Aptos-Cursor header with the position right after
+    /// the last returned event. Pass it as `start` to get the following events.
+    #[oai(
+        path = "/events/by_type/:event_type",
+        method = "get",
+        operation_id = "get_events_by_type",
+        tag = "ApiTags::Events"
+    )]
+    async fn get_events_by_type(
+        &self,
+        accept_type: AcceptType,
+        /// Name of the event struct e.g. `0x1::transaction_fee::FeeStatement`
+        event_type: Path<MoveStructTag>,
+        /// Position to start retrieving events from.
+        ///
+        /// Either a ledger version, to start from the first event of that
+        /// version, or the cursor returned in the X-Aptos-Cursor header of a
+        /// previous response.
+        ///
+        /// If unspecified, by default will retrieve the most recent events
+        start: Query<Option<EventCursor>>,
+        /// Max number of events to retrieve.
+        ///
+        /// If unspecified, defaults to default page size
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
+        event_type
+            .0
+            .verify(0)
+            .context("'event_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        let event_type: StructTag = event_type
+            .0
+            .try_into()
+            .context("'event_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_get_events_by_type")?;
+        self.context
+            .check_api_output_enabled("Get events by type", &accept_type)?;
+        let page = Page::new(None, limit.0, self.context.max_events_page_size());
+
+        let api = self.clone();
+        api_spawn_blocking(move || {
+            api.list_by_type(
+                accept_type,
+                start.0,
+                page,
+                TypeTag::Struct(Box::new(event_type)),
+            )
+        })
+        .await
+    }
 }
 
 impl EventsApi {
@@ -180,6 +244,51 @@ impl EventsApi {
                 )
             })?;
 
+        self.render_events(latest_ledger_info, accept_type, events)
+    }
+
+    /// List module events of the given type, starting at the `start` cursor
+    fn list_by_type(
+        &self,
+        accept_type: AcceptType,
+        start: Option<EventCursor>,
+        page: Page,
+        event_type: TypeTag,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let ledger_version = latest_ledger_info.version();
+        let events = self
+            .context
+            .get_events_by_type(
+                &event_type,
+                start.map(Into::into),
+                page.limit(&latest_ledger_info)?,
+                ledger_version,
+            )
+            .context(format!("Failed to find events by type {}", event_type))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &latest_ledger_info,
+                )
+            })?;
+
+        let cursor = events
+            .last()
+            .map(|(idx, event)| EventCursor::new(event.transaction_version, idx + 1));
+        let events = events.into_iter().map(|(_, event)| event).collect();
+        self.render_events(latest_ledger_info, accept_type, events)
+            .map(|response| response.with_cursor(cursor))
+    }
+
+    /// Renders the events in the requested format
+    fn render_events(
+        &self,
+        latest_ledger_info: LedgerInfo,
+        accept_type: AcceptType,
+        events: Vec<EventWithVersion>,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
         match accept_type {
             AcceptType::Json => {
                 let events = self
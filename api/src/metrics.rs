@@ -62,3 +62,22 @@ pub static REQUEST_SOURCE_CLIENT: Lazy<IntCounterVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static RATE_LIMITED_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_rate_limited_requests",
+        "API requests rejected by the rate limiter, grouped by client (ip or API key name) and route",
+        &["client", "route"]
+    )
+    .unwrap()
+});
+
+pub static RATE_LIMITER_REQUESTS_PER_CLIENT: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_api_rate_limiter_requests_per_client",
+        "API requests of a client between refills of its rate limit, grouped by rate limit and result (allowed or throttled)",
+        &["rate_limit", "result"],
+        exponential_buckets(/*start=*/ 1.0, /*factor=*/ 2.0, /*count=*/ 16).unwrap()
+    )
+    .unwrap()
+});
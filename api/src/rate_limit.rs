@@ -0,0 +1,396 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::{RATE_LIMITED_REQUESTS, RATE_LIMITER_REQUESTS_PER_CLIENT};
+use aptos_api_types::{AptosError, AptosErrorCode};
+use aptos_config::config::{ApiRateLimitConfig, RateLimits, TokenBucketConfig};
+use aptos_rate_limiter::rate_limit::TokenBucketRateLimiter;
+use hyper::Method;
+use poem::{
+    http::{header, HeaderValue, StatusCode},
+    Endpoint, IntoResponse, Middleware, Request, Response,
+};
+use poem_openapi::payload::Json;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::Hash,
+    net::IpAddr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::{Duration, Instant},
+};
+
+const X_FORWARDED_FOR: &str = "x-forwarded-for";
+
+/// How often the buckets of idle clients are dropped, so the per-IP budgets don't grow
+/// without bound
+const GARBAGE_COLLECTION_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Rate limit labels, for logs and metrics, of the budgets of IP addresses
+const IP_LABELS: RouteLabels = RouteLabels {
+    all: "api_ip_all",
+    view: "api_ip_view",
+    simulate: "api_ip_simulate",
+    submit: "api_ip_submit",
+};
+/// Rate limit labels, for logs and metrics, of the budgets of API keys
+const API_KEY_LABELS: RouteLabels = RouteLabels {
+    all: "api_key_all",
+    view: "api_key_view",
+    simulate: "api_key_simulate",
+    submit: "api_key_submit",
+};
+
+/// Routes that are expensive to serve, and have their own budget on top of the budget for all
+/// requests
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ExpensiveRoute {
+    View,
+    Simulate,
+    Submit,
+}
+
+impl ExpensiveRoute {
+    fn from_request(req: &Request) -> Option<Self> {
+        if req.method() != Method::POST {
+            return None;
+        }
+        match req.uri().path().trim_end_matches('/') {
+            "/v1/view" => Some(Self::View),
+            "/v1/transactions/simulate" => Some(Self::Simulate),
+            "/v1/transactions" | "/v1/transactions/batch" => Some(Self::Submit),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::View => "view",
+            Self::Simulate => "simulate",
+            Self::Submit => "submit",
+        }
+    }
+}
+
+struct RouteLabels {
+    all: &'static str,
+    view: &'static str,
+    simulate: &'static str,
+    submit: &'static str,
+}
+
+/// The budgets of a kind of client, with a token bucket per client `Key`
+struct ClientRateLimiters<Key: Eq + Hash + Clone + Debug> {
+    all: TokenBucketRateLimiter<Key>,
+    view: Option<TokenBucketRateLimiter<Key>>,
+    simulate: Option<TokenBucketRateLimiter<Key>>,
+    submit: Option<TokenBucketRateLimiter<Key>>,
+}
+
+impl<Key: Eq + Hash + Clone + Debug> ClientRateLimiters<Key> {
+    fn new(labels: &RouteLabels, log_info: &str, limits: &RateLimits) -> Self {
+        let rate_limiter = |label, bucket: &TokenBucketConfig| {
+            TokenBucketRateLimiter::new(
+                label,
+                log_info.to_string(),
+                100,
+                bucket.bucket_size,
+                bucket.fill_rate,
+                Some(RATE_LIMITER_REQUESTS_PER_CLIENT.clone()),
+            )
+        };
+        Self {
+            all: rate_limiter(labels.all, &limits.all),
+            view: limits
+                .view
+                .as_ref()
+                .map(|bucket| rate_limiter(labels.view, bucket)),
+            simulate: limits
+                .simulate
+                .as_ref()
+                .map(|bucket| rate_limiter(labels.simulate, bucket)),
+            submit: limits
+                .submit
+                .as_ref()
+                .map(|bucket| rate_limiter(labels.submit, bucket)),
+        }
+    }
+
+    fn route_rate_limiter(&self, route: ExpensiveRoute) -> Option<&TokenBucketRateLimiter<Key>> {
+        match route {
+            ExpensiveRoute::View => self.view.as_ref(),
+            ExpensiveRoute::Simulate => self.simulate.as_ref(),
+            ExpensiveRoute::Submit => self.submit.as_ref(),
+        }
+    }
+
+    /// Drops the buckets of clients that haven't used any of their budgets recently
+    fn garbage_collect(&self) {
+        self.all.garbage_collect_full_buckets();
+        for rate_limiter in [&self.view, &self.simulate, &self.submit]
+            .into_iter()
+            .flatten()
+        {
+            rate_limiter.garbage_collect_full_buckets();
+        }
+    }
+
+    /// Takes a request out of the budgets of `key`, or returns when it can be retried
+    fn acquire(&self, key: Key, route: Option<ExpensiveRoute>) -> Result<(), Option<Instant>> {
+        let all = self.all.bucket(key.clone());
+        all.lock().acquire_all_tokens(1)?;
+
+        if let Some(rate_limiter) = route.and_then(|route| self.route_rate_limiter(route)) {
+            if let Err(retry_at) = rate_limiter.bucket(key).lock().acquire_all_tokens(1) {
+                // The request is rejected, so it doesn't count against the budget for all requests
+                all.lock().return_tokens(1);
+                return Err(retry_at);
+            }
+        }
+        Ok(())
+    }
+}
+
+struct ApiKeyRateLimiters {
+    name: String,
+    rate_limiters: ClientRateLimiters<()>,
+}
+
+struct RateLimitState {
+    per_ip: ClientRateLimiters<IpAddr>,
+    /// Rate limiters by API key
+    api_keys: HashMap<String, ApiKeyRateLimiters>,
+    api_key_header: String,
+    use_x_forwarded_for: bool,
+    created_at: Instant,
+    /// When the per-IP buckets are next garbage collected, in milliseconds since `created_at`
+    next_garbage_collection_ms: AtomicU64,
+}
+
+impl RateLimitState {
+    fn new(config: &ApiRateLimitConfig) -> Self {
+        let api_keys = config
+            .api_keys
+            .iter()
+            .map(|api_key| {
+                (api_key.key.clone(), ApiKeyRateLimiters {
+                    name: api_key.name.clone(),
+                    rate_limiters: ClientRateLimiters::new(
+                        &API_KEY_LABELS,
+                        &api_key.name,
+                        &api_key.limits,
+                    ),
+                })
+            })
+            .collect();
+        Self {
+            per_ip: ClientRateLimiters::new(&IP_LABELS, "", &config.per_ip),
+            api_keys,
+            api_key_header: config.api_key_header.clone(),
+            use_x_forwarded_for: config.use_x_forwarded_for,
+            created_at: Instant::now(),
+            next_garbage_collection_ms: AtomicU64::new(
+                GARBAGE_COLLECTION_INTERVAL.as_millis() as u64
+            ),
+        }
+    }
+
+    /// Garbage collects the per-IP buckets if it's been long enough since the last time.
+    /// Only the request that claims the collection does the work.
+    fn maybe_garbage_collect(&self) {
+        let now_ms = self.created_at.elapsed().as_millis() as u64;
+        let next_ms = self.next_garbage_collection_ms.load(Ordering::Relaxed);
+        if now_ms >= next_ms
+            && self
+                .next_garbage_collection_ms
+                .compare_exchange(
+                    next_ms,
+                    now_ms + GARBAGE_COLLECTION_INTERVAL.as_millis() as u64,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                )
+                .is_ok()
+        {
+            self.per_ip.garbage_collect();
+        }
+    }
+
+    fn api_key(&self, req: &Request) -> Option<&ApiKeyRateLimiters> {
+        let api_key = req.headers().get(self.api_key_header.as_str())?;
+        self.api_keys.get(api_key.to_str().ok()?)
+    }
+
+    fn client_ip(&self, req: &Request) -> Option<IpAddr> {
+        if self.use_x_forwarded_for {
+            let forwarded_ip = req
+                .headers()
+                .get(X_FORWARDED_FOR)
+                .and_then(|value| value.to_str().ok())
+                // The proxy appends the address it got the request from, while the entries
+                // before it come from the client and can be spoofed
+                .and_then(|value| value.rsplit(',').next())
+                .and_then(|ip| ip.trim().parse().ok());
+            if forwarded_ip.is_some() {
+                return forwarded_ip;
+            }
+        }
+        req.remote_addr().as_socket_addr().map(|addr| addr.ip())
+    }
+
+    /// Takes the request out of the budgets of its client, or returns the client label and
+    /// when the request can be retried
+    fn acquire(
+        &self,
+        req: &Request,
+        route: Option<ExpensiveRoute>,
+    ) -> Result<(), (&str, Option<Instant>)> {
+        if let Some(api_key) = self.api_key(req) {
+            return api_key
+                .rate_limiters
+                .acquire((), route)
+                .map_err(|retry_at| (api_key.name.as_str(), retry_at));
+        }
+        self.maybe_garbage_collect();
+        match self.client_ip(req) {
+            Some(ip) => self
+                .per_ip
+                .acquire(ip, route)
+                .map_err(|retry_at| ("ip", retry_at)),
+            // Requests from clients without an address (e.g. over a unix socket) aren't limited
+            None => Ok(()),
+        }
+    }
+}
+
+/// This middleware limits the rate of requests of each client, identified by its API key, or
+/// by its IP address for requests without a known API key. Requests over the limit get a 429
+/// response with a `Retry-After` header.
+pub struct RateLimit {
+    state: Option<Arc<RateLimitState>>,
+}
+
+impl RateLimit {
+    /// Creates the middleware, which lets all requests through if rate limiting is disabled
+    pub fn new(config: &ApiRateLimitConfig) -> Self {
+        Self {
+            state: config
+                .enabled
+                .then(|| Arc::new(RateLimitState::new(config))),
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for RateLimit {
+    type Output = RateLimitEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        RateLimitEndpoint {
+            inner: ep,
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// Endpoint for RateLimit middleware.
+pub struct RateLimitEndpoint<E> {
+    inner: E,
+    state: Option<Arc<RateLimitState>>,
+}
+
+#[async_trait::async_trait]
+impl<E: Endpoint> Endpoint for RateLimitEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> poem::Result<Self::Output> {
+        if let Some(state) = &self.state {
+            let route = ExpensiveRoute::from_request(&req);
+            if let Err((client, retry_at)) = state.acquire(&req, route) {
+                RATE_LIMITED_REQUESTS
+                    .with_label_values(&[client, route.map_or("all", |route| route.as_str())])
+                    .inc();
+                return Ok(too_many_requests(retry_at));
+            }
+        }
+
+        self.inner.call(req).await.map(IntoResponse::into_response)
+    }
+}
+
+fn too_many_requests(retry_at: Option<Instant>) -> Response {
+    // Retry-After is in whole seconds, so round the wait up
+    let retry_after_secs = retry_at
+        .map_or(1, |retry_at| {
+            let wait = retry_at.saturating_duration_since(Instant::now());
+            (wait.as_millis() as u64 + 999) / 1000
+        })
+        .max(1);
+
+    let mut response = Json(AptosError::new_with_error_code(
+        format!(
+            "Too many requests, retry after {} seconds",
+            retry_after_secs
+        ),
+        AptosErrorCode::RateLimited,
+    ))
+    .into_response();
+    response.set_status(StatusCode::TOO_MANY_REQUESTS);
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_route_budget_is_separate() {
+        let limits = RateLimits {
+            all: TokenBucketConfig::new(3, 1),
+            view: Some(TokenBucketConfig::new(1, 1)),
+            simulate: None,
+            submit: None,
+        };
+        let rate_limiters = ClientRateLimiters::new(&IP_LABELS, "", &limits);
+
+        // The view budget only allows a single view request
+        rate_limiters
+            .acquire(1, Some(ExpensiveRoute::View))
+            .unwrap();
+        rate_limiters
+            .acquire(1, Some(ExpensiveRoute::View))
+            .unwrap_err();
+
+        // The rejected view request doesn't use up the budget for all requests
+        rate_limiters.acquire(1, None).unwrap();
+        rate_limiters
+            .acquire(1, Some(ExpensiveRoute::Simulate))
+            .unwrap();
+        rate_limiters.acquire(1, None).unwrap_err();
+
+        // Other clients have their own budgets
+        rate_limiters
+            .acquire(2, Some(ExpensiveRoute::View))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_client_ip_from_x_forwarded_for() {
+        let config = ApiRateLimitConfig {
+            enabled: true,
+            use_x_forwarded_for: true,
+            ..ApiRateLimitConfig::default()
+        };
+        let state = RateLimitState::new(&config);
+
+        // The client can prepend anything, only the address added by the proxy counts
+        let req = Request::builder()
+            .header(X_FORWARDED_FOR, "1.1.1.1, 2.2.2.2")
+            .finish();
+        assert_eq!(state.client_ip(&req), Some("2.2.2.2".parse().unwrap()));
+    }
+}
@@ -461,11 +461,11 @@ macro_rules! generate_success_response {
                )))
             }
 
-            pub fn with_cursor(mut self, new_cursor: Option<aptos_types::state_store::state_key::StateKey>) -> Self {
+            pub fn with_cursor<C: std::fmt::Display>(mut self, new_cursor: Option<C>) -> Self {
                 match self {
                     $(
                     [<$enum_name>]::$name(_, _, _, _, _, _, _, _, ref mut cursor) => {
-                        *cursor = new_cursor.map(|c| aptos_api_types::StateKeyWrapper::from(c).to_string());
+                        *cursor = new_cursor.map(|c| c.to_string());
                     }
                     )*
                 }
@@ -612,6 +612,17 @@ pub fn version_pruned<E: GoneError>(ledger_version: u64, ledger_info: &LedgerInf
     )
 }
 
+pub fn version_not_retained<E: GoneError>(ledger_version: u64, ledger_info: &LedgerInfo) -> E {
+    E::gone_with_code(
+        format!(
+            "Ledger version({}) has been pruned, and is neither retained nor in cold storage",
+            ledger_version
+        ),
+        AptosErrorCode::VersionPruned,
+        ledger_info,
+    )
+}
+
 pub fn account_not_found<E: NotFoundError>(
     address: Address,
     ledger_version: u64,
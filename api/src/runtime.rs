@@ -5,8 +5,8 @@
 use crate::{
     accounts::AccountsApi, basic::BasicApi, blocks::BlocksApi, check_size::PostSizeLimit,
     context::Context, error_converter::convert_error, events::EventsApi, index::IndexApi,
-    log::middleware_log, set_failpoints, state::StateApi, transactions::TransactionsApi,
-    view_function::ViewFunctionApi,
+    log::middleware_log, rate_limit::RateLimit, set_failpoints, state::StateApi,
+    transactions::TransactionsApi, view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::X_APTOS_CLIENT;
@@ -25,7 +25,10 @@ use poem::{
 };
 use poem_openapi::{ContactObject, LicenseObject, OpenApiService};
 use std::{net::SocketAddr, sync::Arc};
-use tokio::runtime::{Handle, Runtime};
+use tokio::{
+    runtime::{Handle, Runtime},
+    sync::watch,
+};
 
 const VERSION: &str = include_str!("../doc/.version");
 
@@ -35,11 +38,13 @@ pub fn bootstrap(
     chain_id: ChainId,
     db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
+    api_config_updates: watch::Receiver<ApiConfig>,
 ) -> anyhow::Result<Runtime> {
     let max_runtime_workers = get_max_runtime_workers(&config.api);
     let runtime = aptos_runtimes::spawn_named_runtime("api".into(), Some(max_runtime_workers));
 
-    let context = Context::new(chain_id, db, mp_sender, config.clone());
+    let context = Context::new(chain_id, db, mp_sender, config.clone())
+        .with_api_config_updates(api_config_updates);
 
     attach_poem_to_runtime(runtime.handle(), context, config, false)
         .context("Failed to attach poem to runtime")?;
@@ -120,6 +125,12 @@ pub fn attach_poem_to_runtime(
     let context = Arc::new(context);
 
     let size_limit = context.content_length_limit();
+    let rate_limit = RateLimit::new(&config.api.rate_limit);
+    let api_key_header = config
+        .api
+        .rate_limit
+        .enabled
+        .then(|| config.api.rate_limit.api_key_header.clone());
 
     let api_service = get_api_service(context.clone());
 
@@ -166,7 +177,7 @@ pub fn attach_poem_to_runtime(
         .as_socket_addr()
         .context("Failed to get socket addr from local addr for Poem webserver")?;
     runtime_handle.spawn(async move {
-        let cors = Cors::new()
+        let mut cors = Cors::new()
             // To allow browsers to use cookies (for cookie-based sticky
             // routing in the LB) we must enable this:
             // https://stackoverflow.com/a/24689738/3846032
@@ -177,6 +188,9 @@ pub fn attach_poem_to_runtime(
                 header::CONTENT_TYPE,
                 header::ACCEPT,
             ]);
+        if let Some(api_key_header) = api_key_header {
+            cors = cors.allow_header(api_key_header.as_str());
+        }
 
         // Build routes for the API
         let route = Route::new()
@@ -194,6 +208,8 @@ pub fn attach_poem_to_runtime(
                         poem::get(set_failpoints::set_failpoint_poem).data(context.clone()),
                     ),
             )
+            // NOTE: Keep this before CORS, so responses to rate limited requests have CORS headers.
+            .with(rate_limit)
             .with(cors)
             .with(PostSizeLimit::new(size_limit))
             // NOTE: Make sure to keep this after all the `with` middleware.
@@ -299,11 +315,13 @@ mod tests {
         let context = runtime.block_on(new_test_context_async(
             "test_bootstrap_jsonprc_and_api_configured_at_different_port".to_string(),
         ));
+        let (_, api_config_updates) = tokio::sync::watch::channel(cfg.api.clone());
         let ret = bootstrap(
             &cfg,
             ChainId::test(),
             context.db.clone(),
             context.mempool.ac_client.clone(),
+            api_config_updates,
         );
         assert!(ret.is_ok());
 
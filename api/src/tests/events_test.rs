@@ -5,7 +5,7 @@
 use super::new_test_context;
 use aptos_api_test_context::{current_function_name, TestContext};
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
-use serde_json::json;
+use serde_json::{json, Value};
 use std::path::PathBuf;
 
 static ACCOUNT_ADDRESS: &str = "0xa550c18";
@@ -180,6 +180,132 @@ async fn test_module_events() {
         .map_or(false, |t| t.as_str().unwrap() == "0x0"))));
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_type() {
+    let mut context = new_test_context(current_function_name!());
+
+    // Prepare accounts
+    let mut user = context.create_account().await;
+
+    let user_addr = user.address();
+    // Publish packages
+    let named_addresses = vec![("event".to_string(), user_addr)];
+    let txn = futures::executor::block_on(async move {
+        let path = PathBuf::from(std::env!("CARGO_MANIFEST_DIR"))
+            .join("../aptos-move/move-examples/event");
+        TestContext::build_package(path, named_addresses)
+    });
+    context.publish_package(&mut user, txn).await;
+
+    // Emit 5 events, then 2 more in another transaction
+    for num in ["5", "2"] {
+        context
+            .api_execute_entry_function(
+                &mut user,
+                &format!("0x{}::event::emit", user_addr.to_hex()),
+                json!([]),
+                json!([num]),
+            )
+            .await;
+    }
+    let resp = context
+        .get(format!("/accounts/{}/transactions", user.address()).as_str())
+        .await;
+    let first_version: u64 = resp.as_array().unwrap()[1]["version"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let second_version: u64 = resp.as_array().unwrap()[2]["version"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+
+    let path = format!(
+        "/events/by_type/{}",
+        utf8_percent_encode(
+            &format!("0x{}::event::MyEvent", user_addr.to_hex()),
+            NON_ALPHANUMERIC
+        )
+    );
+    let get_page = |start: Option<String>| {
+        let path = match start {
+            Some(start) => format!("{}?limit=3&start={}", path, start),
+            None => format!("{}?limit=3", path),
+        };
+        let context = &context;
+        async move {
+            let resp = context
+                .reply(
+                    warp::test::request()
+                        .method("GET")
+                        .path(&context.prepend_path(&path)),
+                )
+                .await;
+            assert_eq!(resp.status(), 200);
+            let cursor = resp
+                .headers()
+                .get("X-Aptos-Cursor")
+                .map(|cursor| cursor.to_str().unwrap().to_string());
+            let events: Vec<Value> = serde_json::from_slice(resp.body()).unwrap();
+            let events: Vec<_> = events
+                .iter()
+                .map(|event| {
+                    (
+                        event["version"].as_str().unwrap().parse::<u64>().unwrap(),
+                        event["data"]["seq"].as_str().unwrap().to_string(),
+                    )
+                })
+                .collect();
+            (events, cursor)
+        }
+    };
+    let event = |version: u64, seq: u64| (version, seq.to_string());
+
+    // Pages are capped at the limit, even in the middle of the events of a transaction
+    let (events, cursor) = get_page(Some(first_version.to_string())).await;
+    assert_eq!(events, vec![
+        event(first_version, 0),
+        event(first_version, 1),
+        event(first_version, 2)
+    ]);
+    assert_eq!(cursor, Some(format!("{}:3", first_version)));
+
+    // The cursor continues right after the last returned event
+    let (events, cursor) = get_page(cursor).await;
+    assert_eq!(events, vec![
+        event(first_version, 3),
+        event(first_version, 4),
+        event(second_version, 0)
+    ]);
+    assert_eq!(cursor, Some(format!("{}:1", second_version)));
+
+    let (events, cursor) = get_page(cursor).await;
+    assert_eq!(events, vec![event(second_version, 1)]);
+    assert_eq!(cursor, Some(format!("{}:2", second_version)));
+
+    // There are no events after the last one yet
+    let (events, cursor) = get_page(cursor).await;
+    assert!(events.is_empty());
+    assert_eq!(cursor, None);
+
+    // Without a start, the most recent events are returned in the order they were emitted
+    let (events, cursor) = get_page(None).await;
+    assert_eq!(events, vec![
+        event(first_version, 4),
+        event(second_version, 0),
+        event(second_version, 1)
+    ]);
+    assert_eq!(cursor, Some(format!("{}:2", second_version)));
+
+    // Invalid cursors are rejected
+    context
+        .expect_status_code(400)
+        .get(format!("{}?start={}:", path, first_version).as_str())
+        .await;
+}
+
 // until we have generics in the genesis
 #[ignore]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
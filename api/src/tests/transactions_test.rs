@@ -5,13 +5,16 @@
 use super::new_test_context;
 use crate::tests::new_test_context_with_config;
 use aptos_api_test_context::{assert_json, current_function_name, pretty, TestContext};
-use aptos_config::config::{GasEstimationStaticOverride, NodeConfig};
+use aptos_config::config::{
+    GasEstimationStaticOverride, LedgerPrunerConfig, LedgerRetentionConfig, NodeConfig,
+};
 use aptos_crypto::{
     ed25519::Ed25519PrivateKey,
     multi_ed25519::{MultiEd25519PrivateKey, MultiEd25519PublicKey},
     PrivateKey, SigningKey, Uniform,
 };
 use aptos_sdk::types::LocalAccount;
+use aptos_storage_interface::DbReader;
 use aptos_types::{
     account_address::AccountAddress,
     transaction::{
@@ -532,6 +535,75 @@ async fn test_get_transaction_by_version() {
     assert_json(resp, txns[0].clone())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_retained_transaction_by_version_after_prune() {
+    let retained_account = LocalAccount::generate(&mut thread_rng());
+    let mut node_config = NodeConfig::default();
+    node_config
+        .storage
+        .storage_pruner_config
+        .ledger_pruner_config = LedgerPrunerConfig {
+        enable: true,
+        prune_window: 2,
+        batch_size: 1,
+        user_pruning_window_offset: 0,
+    };
+    node_config.storage.ledger_retention = LedgerRetentionConfig {
+        accounts: vec![retained_account.address()],
+        event_types: vec![],
+    };
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+
+    let retained_txn = context.create_user_account(&retained_account).await;
+    context.commit_block(&vec![retained_txn.clone()]).await;
+    let other_account = context.gen_account();
+    let other_txn = context.create_user_account(&other_account).await;
+    context.commit_block(&vec![other_txn.clone()]).await;
+
+    let mut versions = vec![];
+    for txn in [&retained_txn, &other_txn] {
+        let resp = context
+            .get(&format!(
+                "/transactions/by_hash/{}",
+                txn.committed_hash().to_hex_literal()
+            ))
+            .await;
+        versions.push(resp["version"].as_str().unwrap().parse::<u64>().unwrap());
+    }
+
+    // Push both transactions out of the prune window, and wait for the pruner to delete the
+    // transactions which are not retained.
+    let mut root_account = context.root_account().await;
+    for _ in 0..3 {
+        let account = context.gen_account();
+        let txn = context.create_user_account_by(&mut root_account, &account);
+        context.commit_block(&vec![txn]).await;
+    }
+    let ledger_version = context.get_latest_ledger_info().version();
+    assert!(context.get_latest_ledger_info().oldest_version() > versions[1]);
+    // Until the pruner catches up, the transaction is still indexed by its hash.
+    while !matches!(
+        context
+            .db
+            .get_transaction_by_hash(other_txn.committed_hash(), ledger_version, false),
+        Ok(None)
+    ) {
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    let resp = context
+        .get(&format!("/transactions/by_version/{}", versions[0]))
+        .await;
+    assert_eq!(
+        resp["hash"].as_str().unwrap(),
+        retained_txn.committed_hash().to_hex_literal()
+    );
+    context
+        .expect_status_code(410)
+        .get(&format!("/transactions/by_version/{}", versions[1]))
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_pending_transaction_by_hash() {
     let mut context = new_test_context(current_function_name!());
@@ -12,9 +12,9 @@ use crate::{
     page::Page,
     response::{
         api_disabled, api_forbidden, transaction_not_found_by_hash,
-        transaction_not_found_by_version, version_pruned, BadRequestError, BasicError,
-        BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResult, BasicResultWith404,
-        InsufficientStorageError, InternalError,
+        transaction_not_found_by_version, version_not_retained, version_pruned, BadRequestError,
+        BasicError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResult,
+        BasicResultWith404, InsufficientStorageError, InternalError,
     },
     ApiTags,
 };
@@ -211,7 +211,9 @@ impl TransactionsApi {
     /// Get transaction by version
     ///
     /// Retrieves a transaction by a given version. If the version has been
-    /// pruned, a 410 will be returned.
+    /// pruned, a 410 will be returned, unless the node retains the history of
+    /// the transaction's accounts or event types, or keeps pruned transactions
+    /// in cold storage.
     #[oai(
         path = "/transactions/by_version/:txn_version",
         method = "get",
@@ -274,6 +276,52 @@ impl TransactionsApi {
         api_spawn_blocking(move || api.list_by_account(&accept_type, page, address.0)).await
     }
 
+    /// Get account involved transactions
+    ///
+    /// Retrieves on-chain committed transactions involving an account: the
+    /// transactions it signed (as the sender, a secondary signer or the fee
+    /// payer), the transactions writing to its resources or modules, and the
+    /// transactions emitting events on its event handles or transferring
+    /// objects from or to it.
+    ///
+    /// This requires the node to have the account involvement index enabled,
+    /// otherwise a 403 will be returned. Transactions older than the prune
+    /// window are not returned.
+    #[oai(
+        path = "/accounts/:address/involved_transactions",
+        method = "get",
+        operation_id = "get_account_involved_transactions",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_account_involved_transactions(
+        &self,
+        accept_type: AcceptType,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+        /// Ledger version to start list of transactions
+        ///
+        /// If not provided, defaults to showing the latest transactions
+        start: Query<Option<U64>>,
+        /// Max number of transactions to retrieve.
+        ///
+        /// If not provided, defaults to default page size
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<Transaction>> {
+        fail_point_poem("endpoint_get_account_involved_transactions")?;
+        if !self.context.account_involvement_index_enabled() {
+            return Err(api_disabled("Get account involved transactions"));
+        }
+        self.context
+            .check_api_output_enabled("Get account involved transactions", &accept_type)?;
+        let page = Page::new(
+            start.0.map(|v| v.0),
+            limit.0,
+            self.context.max_transactions_page_size(),
+        );
+        let api = self.clone();
+        api_spawn_blocking(move || api.list_involving_account(&accept_type, page, address.0)).await
+    }
+
     /// Submit transaction
     ///
     /// This endpoint accepts transaction submissions in two formats.
@@ -734,6 +782,9 @@ impl TransactionsApi {
                 Err(transaction_not_found_by_version(version.0, &ledger_info))
             },
             GetByVersionResponse::VersionTooOld => Err(version_pruned(version.0, &ledger_info)),
+            GetByVersionResponse::VersionNotRetained => {
+                Err(version_not_retained(version.0, &ledger_info))
+            },
         }
     }
 
@@ -797,7 +848,14 @@ impl TransactionsApi {
             return Ok(GetByVersionResponse::VersionTooNew);
         }
         if version < ledger_info.oldest_version() {
-            return Ok(GetByVersionResponse::VersionTooOld);
+            if !self.context.retains_ledger_history() {
+                return Ok(GetByVersionResponse::VersionTooOld);
+            }
+            // Past the prune window, only the transactions of the retained accounts and event
+            // types, and the ones moved to cold storage are kept
+            if !self.context.db.is_pruned_transaction_kept(version)? {
+                return Ok(GetByVersionResponse::VersionNotRetained);
+            }
         }
         Ok(GetByVersionResponse::Found(
             self.context
@@ -865,6 +923,34 @@ impl TransactionsApi {
         }
     }
 
+    /// List all transactions involving an account
+    fn list_involving_account(
+        &self,
+        accept_type: &AcceptType,
+        page: Page,
+        address: Address,
+    ) -> BasicResultWith404<Vec<Transaction>> {
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let data = self.context.get_account_involved_transactions(
+            address.into(),
+            page.start_option(),
+            page.limit(&latest_ledger_info)?,
+            latest_ledger_info.version(),
+            &latest_ledger_info,
+        )?;
+        match accept_type {
+            AcceptType::Json => BasicResponse::try_from_json((
+                self.context
+                    .render_transactions_non_sequential(&latest_ledger_info, data)?,
+                &latest_ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((data, &latest_ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+    }
+
     /// Parses a single signed transaction
     fn get_signed_transaction(
         &self,
@@ -1361,5 +1447,6 @@ fn override_gas_parameters(
 enum GetByVersionResponse {
     VersionTooNew,
     VersionTooOld,
+    VersionNotRetained,
     Found(TransactionData),
 }
@@ -117,8 +117,28 @@ pub fn new_test_context(
     let (validator_identity, _, _, _) = validators[0].get_key_objects(None).unwrap();
     let validator_owner = validator_identity.account_address.unwrap();
 
+    let storage_config = &node_config.storage;
     let (db, db_rw) = if use_db_with_indexer {
         DbReaderWriter::wrap(AptosDB::new_for_test_with_indexer(&tmp_dir))
+    } else if !storage_config.ledger_retention.is_empty()
+        || storage_config.ledger_cold_storage.enable
+    {
+        // Keeping ledger history past the prune window only matters when the ledger is pruned.
+        DbReaderWriter::wrap(
+            AptosDB::open_with_ledger_history(
+                StorageDirPaths::from_path(&tmp_dir),
+                false, /* readonly */
+                storage_config.storage_pruner_config,
+                &storage_config.ledger_retention,
+                &storage_config.ledger_cold_storage,
+                RocksdbConfigs::default(),
+                false, /* indexer */
+                storage_config.enable_account_involvement_index,
+                BUFFERED_STATE_TARGET_ITEMS,
+                DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+            )
+            .unwrap(),
+        )
     } else {
         DbReaderWriter::wrap(
             AptosDB::open(
@@ -20,8 +20,8 @@
 
 use crate::{
     move_types::{MoveAbility, MoveStructValue},
-    Address, EntryFunctionId, HashValue, HexEncodedBytes, IdentifierWrapper, MoveModuleId,
-    MoveStructTag, MoveType, StateKeyWrapper, U128, U256, U64,
+    Address, EntryFunctionId, EventCursor, HashValue, HexEncodedBytes, IdentifierWrapper,
+    MoveModuleId, MoveStructTag, MoveType, StateKeyWrapper, U128, U256, U64,
 };
 use aptos_openapi::{impl_poem_parameter, impl_poem_type};
 use indoc::indoc;
@@ -231,6 +231,19 @@ impl_poem_type!(
     )
 );
 
+impl_poem_type!(
+    EventCursor,
+    "string",
+    (
+        example = Some(serde_json::Value::String("32425224034:2".to_string())),
+        description = Some(indoc! {"
+          Position of a module event, as `<version>:<event_index>`. This is used for cursor
+          based pagination of events by type. A bare `<version>` starts at the first event of
+          that version.
+        "})
+    )
+);
+
 impl_poem_type!(
     StateKeyWrapper,
     "string",
@@ -293,6 +306,7 @@ impl_poem_type!(
 
 impl_poem_parameter!(
     Address,
+    EventCursor,
     HashValue,
     IdentifierWrapper,
     HexEncodedBytes,
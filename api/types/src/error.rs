@@ -110,6 +110,8 @@ pub enum AptosErrorCode {
     BcsNotSupported = 602,
     /// API Disabled
     ApiDisabled = 603,
+    /// The client made too many requests, and should retry later
+    RateLimited = 604,
 }
 
 impl AptosErrorCode {
@@ -15,7 +15,7 @@ use anyhow::{bail, Context};
 use aptos_types::{event::EventKey, state_store::state_key::StateKey};
 use move_core_types::identifier::{IdentStr, Identifier};
 use poem_openapi::Object;
-use serde::{Deserialize, Serialize};
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
 use std::{convert::From, fmt, ops::Deref, str::FromStr};
 
 /// A wrapper of a Move identifier
@@ -151,3 +151,70 @@ impl From<StateKeyWrapper> for StateKey {
         value.0
     }
 }
+
+/// Position of a module event, as the version of the transaction that emitted it and its index
+/// among the events of that transaction. This is used for cursor based pagination of events by
+/// type, as `<version>:<event_index>`. A bare `<version>` starts at the first event of that
+/// version.
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct EventCursor {
+    pub version: u64,
+    pub event_index: u64,
+}
+
+impl EventCursor {
+    pub fn new(version: u64, event_index: u64) -> Self {
+        Self {
+            version,
+            event_index,
+        }
+    }
+}
+
+impl fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.version, self.event_index)
+    }
+}
+
+impl FromStr for EventCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self, anyhow::Error> {
+        let (version, event_index) = match s.split_once(':') {
+            Some((version, event_index)) => (
+                version,
+                event_index
+                    .parse()
+                    .context("Failed to parse event index of cursor")?,
+            ),
+            None => (s, 0),
+        };
+        let version = version
+            .parse()
+            .context("Failed to parse version of cursor")?;
+        Ok(Self::new(version, event_index))
+    }
+}
+
+impl Serialize for EventCursor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_string().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for EventCursor {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = <String>::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+impl From<EventCursor> for (u64, u64) {
+    fn from(value: EventCursor) -> (u64, u64) {
+        (value.version, value.event_index)
+    }
+}
@@ -0,0 +1,238 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, Result};
+use aptos_debugger::{AptosDebugger, ExecutionTrace, TraceEvent, TraceStep};
+use aptos_rest_client::Client;
+use aptos_types::transaction::Transaction;
+use clap::{Parser, Subcommand};
+use std::{
+    io::{self, BufRead, Write},
+    path::PathBuf,
+};
+use url::Url;
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Re-execute a committed user transaction and record its execution trace as JSON.
+    Record {
+        /// Full node's rest api to fetch the transaction and the state from.
+        #[clap(long)]
+        endpoint: String,
+
+        /// Version of the transaction to trace.
+        #[clap(long)]
+        version: u64,
+
+        /// File to write the trace to.
+        #[clap(long)]
+        output: PathBuf,
+    },
+    /// Walk through a recorded trace step by step.
+    View {
+        trace: PathBuf,
+
+        /// Print all steps at once instead of stepping interactively.
+        #[clap(long)]
+        all: bool,
+    },
+}
+
+#[derive(Parser)]
+pub struct Argument {
+    #[clap(subcommand)]
+    cmd: Command,
+}
+
+fn format_step(idx: usize, step: &TraceStep) -> String {
+    let indent = "  ".repeat(step.depth);
+    let location = match step.offset {
+        Some(offset) => format!("{}@{}", step.function, offset),
+        None => step.function.clone(),
+    };
+    let event = match &step.event {
+        TraceEvent::Instruction { op, ty, values } => {
+            let mut s = op.clone();
+            if let Some(ty) = ty {
+                s.push_str(&format!("<{}>", ty));
+            }
+            if !values.is_empty() {
+                s.push_str(&format!(" {}", serde_json::to_string(values).unwrap()));
+            }
+            s
+        },
+        TraceEvent::Call {
+            function,
+            ty_args,
+            args,
+        } => format!(
+            "call {}<{}>({})",
+            function,
+            ty_args.join(", "),
+            serde_json::to_string(args).unwrap()
+        ),
+        TraceEvent::NativeReturn { return_values } => format!(
+            "native returned {}",
+            serde_json::to_string(return_values).unwrap()
+        ),
+        TraceEvent::Return { locals } => {
+            format!("return, locals {}", serde_json::to_string(locals).unwrap())
+        },
+        TraceEvent::LoadResource {
+            address,
+            ty,
+            value,
+            bytes,
+        } => format!(
+            "load {} from {} ({} bytes): {}",
+            ty,
+            address.to_hex_literal(),
+            bytes,
+            serde_json::to_string(value).unwrap()
+        ),
+    };
+    format!(
+        "[{}] {}{} | {} (gas {})",
+        idx, indent, location, event, step.gas
+    )
+}
+
+/// Steps through `trace` following the commands read from `input`.
+fn view(trace: &ExecutionTrace, mut input: impl BufRead, mut output: impl Write) -> Result<()> {
+    if trace.steps.is_empty() {
+        writeln!(output, "The trace is empty.")?;
+        return Ok(());
+    }
+
+    writeln!(
+        output,
+        "{} steps. <enter>: next, p: previous, <number>: jump to step, q: quit",
+        trace.steps.len()
+    )?;
+    let mut idx = 0;
+    loop {
+        writeln!(output, "{}", format_step(idx, &trace.steps[idx]))?;
+        write!(output, "> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(());
+        }
+        match line.trim() {
+            "" | "n" => idx = (idx + 1).min(trace.steps.len() - 1),
+            "p" => idx = idx.saturating_sub(1),
+            "q" => return Ok(()),
+            other => match other.parse::<usize>() {
+                Ok(step) if step < trace.steps.len() => idx = step,
+                _ => writeln!(output, "Unknown command or step out of range: {}", other)?,
+            },
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Argument::parse();
+
+    match args.cmd {
+        Command::Record {
+            endpoint,
+            version,
+            output,
+        } => {
+            let debugger = AptosDebugger::rest_client(Client::new(Url::parse(&endpoint)?))?;
+            let (mut txns, _) = debugger.get_committed_transactions(version, 1).await?;
+            let txn = match txns.pop() {
+                Some(Transaction::UserTransaction(txn)) => txn,
+                _ => bail!(
+                    "Transaction at version {} is not a user transaction",
+                    version
+                ),
+            };
+
+            let (status, _, trace) =
+                debugger.execute_transaction_at_version_with_tracer(version, txn)?;
+            println!("Status: {:?}", status);
+            println!(
+                "Recorded {} steps to {}",
+                trace.steps.len(),
+                output.display()
+            );
+            std::fs::write(output, serde_json::to_vec_pretty(&trace)?)?;
+        },
+        Command::View { trace, all } => {
+            let trace: ExecutionTrace = serde_json::from_slice(&std::fs::read(trace)?)?;
+            if all {
+                for (idx, step) in trace.steps.iter().enumerate() {
+                    println!("{}", format_step(idx, step));
+                }
+            } else {
+                view(&trace, io::stdin().lock(), io::stdout())?;
+            }
+        },
+    }
+
+    Ok(())
+}
+
+#[test]
+fn verify_tool() {
+    use clap::CommandFactory;
+    Argument::command().debug_assert()
+}
+
+#[test]
+fn test_view_recorded_trace() {
+    use aptos_debugger::ExecutionTracer;
+    use move_core_types::{identifier::Identifier, language_storage::ModuleId};
+    use move_vm_types::{
+        gas::{GasMeter, SimpleInstruction, UnmeteredGasMeter},
+        values::Value,
+    };
+
+    let module_id = ModuleId::new(
+        move_core_types::account_address::AccountAddress::ONE,
+        Identifier::new("m").unwrap(),
+    );
+    let mut tracer = ExecutionTracer::new_function(
+        UnmeteredGasMeter,
+        module_id,
+        Identifier::new("f").unwrap(),
+        vec![],
+    );
+    tracer
+        .charge_simple_instr(SimpleInstruction::LdU64)
+        .unwrap();
+    tracer.charge_br_false(Some(3)).unwrap();
+    tracer.charge_simple_instr(SimpleInstruction::Ret).unwrap();
+    tracer
+        .charge_drop_frame(std::iter::empty::<Value>())
+        .unwrap();
+
+    // Traces are read back from the JSON they are recorded to
+    let trace: ExecutionTrace =
+        serde_json::from_slice(&serde_json::to_vec(&tracer.finish()).unwrap()).unwrap();
+    assert_eq!(trace.steps.len(), 5);
+    assert_eq!(
+        trace
+            .steps
+            .iter()
+            .map(|step| step.offset)
+            .collect::<Vec<_>>(),
+        vec![None, Some(0), Some(1), Some(3), None]
+    );
+
+    let mut output = vec![];
+    view(&trace, "\n\np\n3\n9\nq\n".as_bytes(), &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    let shown_steps: Vec<_> = output
+        .lines()
+        .filter_map(|line| line.trim_start_matches("> ").strip_prefix('['))
+        .map(|line| line.split(']').next().unwrap().parse::<usize>().unwrap())
+        .collect();
+    assert_eq!(shown_steps, vec![0, 1, 2, 1, 3, 3]);
+    assert!(output.contains("[3] 0x1::m::f@3 | RET"));
+    assert!(!output.contains("[4] "));
+    assert!(output.contains("Unknown command or step out of range: 9"));
+}
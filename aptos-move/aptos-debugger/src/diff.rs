@@ -0,0 +1,290 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    contract_event::ContractEvent,
+    state_store::state_key::StateKey,
+    transaction::{TransactionOutput, TransactionStatus, Version},
+    write_set::WriteOp,
+};
+use serde::Serialize;
+use std::{collections::BTreeSet, fmt};
+
+/// A single difference between the recorded output of a transaction and the replayed one.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub enum OutputDiff {
+    Status {
+        expected: TransactionStatus,
+        actual: TransactionStatus,
+    },
+    GasUsed {
+        expected: u64,
+        actual: u64,
+    },
+    WriteOp {
+        state_key: StateKey,
+        expected: Option<WriteOp>,
+        actual: Option<WriteOp>,
+    },
+    Event {
+        index: usize,
+        expected: Option<ContractEvent>,
+        actual: Option<ContractEvent>,
+    },
+}
+
+/// All differences found for a single transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TransactionDiff {
+    pub version: Version,
+    pub diffs: Vec<OutputDiff>,
+}
+
+impl TransactionDiff {
+    /// Compares the `actual` (replayed) output against the `expected` (recorded) one.
+    pub fn new(version: Version, expected: &TransactionOutput, actual: &TransactionOutput) -> Self {
+        let mut diffs = vec![];
+
+        if expected.status() != actual.status() {
+            diffs.push(OutputDiff::Status {
+                expected: expected.status().clone(),
+                actual: actual.status().clone(),
+            });
+        }
+
+        if expected.gas_used() != actual.gas_used() {
+            diffs.push(OutputDiff::GasUsed {
+                expected: expected.gas_used(),
+                actual: actual.gas_used(),
+            });
+        }
+
+        let state_keys = expected
+            .write_set()
+            .iter()
+            .chain(actual.write_set().iter())
+            .map(|(state_key, _)| state_key)
+            .collect::<BTreeSet<_>>();
+        for state_key in state_keys {
+            let expected_op = expected.write_set().get(state_key);
+            let actual_op = actual.write_set().get(state_key);
+            if expected_op != actual_op {
+                diffs.push(OutputDiff::WriteOp {
+                    state_key: state_key.clone(),
+                    expected: expected_op.cloned(),
+                    actual: actual_op.cloned(),
+                });
+            }
+        }
+
+        let num_events = expected.events().len().max(actual.events().len());
+        for index in 0..num_events {
+            let expected_event = expected.events().get(index);
+            let actual_event = actual.events().get(index);
+            if expected_event != actual_event {
+                diffs.push(OutputDiff::Event {
+                    index,
+                    expected: expected_event.cloned(),
+                    actual: actual_event.cloned(),
+                });
+            }
+        }
+
+        Self { version, diffs }
+    }
+
+    pub fn is_match(&self) -> bool {
+        self.diffs.is_empty()
+    }
+}
+
+/// Summary of a replay, comparing every replayed output against the recorded one.
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct ReplayReport {
+    pub begin_version: Version,
+    pub num_transactions: u64,
+    pub num_status_mismatches: u64,
+    pub num_gas_mismatches: u64,
+    pub num_write_set_mismatches: u64,
+    pub num_event_mismatches: u64,
+    pub expected_gas_used: u64,
+    pub actual_gas_used: u64,
+    /// Only transactions with at least one difference are kept.
+    pub mismatches: Vec<TransactionDiff>,
+}
+
+impl ReplayReport {
+    pub fn new(begin_version: Version) -> Self {
+        Self {
+            begin_version,
+            ..Self::default()
+        }
+    }
+
+    pub fn add(
+        &mut self,
+        version: Version,
+        expected: &TransactionOutput,
+        actual: &TransactionOutput,
+    ) {
+        self.num_transactions += 1;
+        self.expected_gas_used += expected.gas_used();
+        self.actual_gas_used += actual.gas_used();
+
+        let diff = TransactionDiff::new(version, expected, actual);
+        if diff.is_match() {
+            return;
+        }
+        let has = |f: fn(&OutputDiff) -> bool| diff.diffs.iter().any(f);
+        if has(|d| matches!(d, OutputDiff::Status { .. })) {
+            self.num_status_mismatches += 1;
+        }
+        if has(|d| matches!(d, OutputDiff::GasUsed { .. })) {
+            self.num_gas_mismatches += 1;
+        }
+        if has(|d| matches!(d, OutputDiff::WriteOp { .. })) {
+            self.num_write_set_mismatches += 1;
+        }
+        if has(|d| matches!(d, OutputDiff::Event { .. })) {
+            self.num_event_mismatches += 1;
+        }
+        self.mismatches.push(diff);
+    }
+
+    pub fn num_mismatched_transactions(&self) -> u64 {
+        self.mismatches.len() as u64
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "=============")?;
+        writeln!(f, "Replay Report")?;
+        writeln!(f, "=============")?;
+        writeln!(
+            f,
+            "Versions: {}..{}",
+            self.begin_version,
+            self.begin_version + self.num_transactions
+        )?;
+        writeln!(
+            f,
+            "Mismatched transactions: {}/{}",
+            self.num_mismatched_transactions(),
+            self.num_transactions
+        )?;
+        writeln!(f, "  status:    {}", self.num_status_mismatches)?;
+        writeln!(f, "  gas used:  {}", self.num_gas_mismatches)?;
+        writeln!(f, "  write set: {}", self.num_write_set_mismatches)?;
+        writeln!(f, "  events:    {}", self.num_event_mismatches)?;
+        writeln!(
+            f,
+            "Total gas used: {} (recorded: {}, delta: {:+})",
+            self.actual_gas_used,
+            self.expected_gas_used,
+            self.actual_gas_used as i128 - self.expected_gas_used as i128
+        )?;
+        for diff in &self.mismatches {
+            writeln!(f, "Version {}:", diff.version)?;
+            for d in &diff.diffs {
+                match d {
+                    OutputDiff::Status { expected, actual } => {
+                        writeln!(f, "  status: {:?} -> {:?}", expected, actual)?
+                    },
+                    OutputDiff::GasUsed { expected, actual } => {
+                        writeln!(f, "  gas used: {} -> {}", expected, actual)?
+                    },
+                    OutputDiff::WriteOp {
+                        state_key,
+                        expected,
+                        actual,
+                    } => writeln!(
+                        f,
+                        "  write op {:?}: {:?} -> {:?}",
+                        state_key, expected, actual
+                    )?,
+                    OutputDiff::Event {
+                        index,
+                        expected,
+                        actual,
+                    } => writeln!(f, "  event #{}: {:?} -> {:?}", index, expected, actual)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{
+        transaction::ExecutionStatus,
+        write_set::{WriteSet, WriteSetMut},
+    };
+
+    fn output(write_set: Vec<(StateKey, WriteOp)>, gas_used: u64) -> TransactionOutput {
+        TransactionOutput::new(
+            WriteSetMut::new(write_set).freeze().unwrap(),
+            vec![],
+            gas_used,
+            TransactionStatus::Keep(ExecutionStatus::Success),
+        )
+    }
+
+    #[test]
+    fn test_identical_outputs_match() {
+        let key = StateKey::raw(vec![1]);
+        let expected = output(
+            vec![(key.clone(), WriteOp::Modification(vec![1].into()))],
+            10,
+        );
+        let actual = output(vec![(key, WriteOp::Modification(vec![1].into()))], 10);
+
+        let mut report = ReplayReport::new(100);
+        report.add(100, &expected, &actual);
+        assert_eq!(report.num_transactions, 1);
+        assert!(report.mismatches.is_empty());
+    }
+
+    #[test]
+    fn test_mismatched_outputs() {
+        let key_1 = StateKey::raw(vec![1]);
+        let key_2 = StateKey::raw(vec![2]);
+        let expected = output(
+            vec![(key_1.clone(), WriteOp::Modification(vec![1].into()))],
+            10,
+        );
+        let actual = TransactionOutput::new(
+            WriteSet::default(),
+            vec![],
+            12,
+            TransactionStatus::Keep(ExecutionStatus::OutOfGas),
+        );
+        let actual_with_write = output(vec![(key_2.clone(), WriteOp::Deletion)], 10);
+
+        let mut report = ReplayReport::new(100);
+        report.add(100, &expected, &actual);
+        report.add(101, &expected, &actual_with_write);
+
+        assert_eq!(report.num_mismatched_transactions(), 2);
+        assert_eq!(report.num_status_mismatches, 1);
+        assert_eq!(report.num_gas_mismatches, 1);
+        assert_eq!(report.num_write_set_mismatches, 2);
+        assert_eq!(report.num_event_mismatches, 0);
+        assert_eq!(report.expected_gas_used, 20);
+        assert_eq!(report.actual_gas_used, 22);
+        assert_eq!(report.mismatches[1].diffs, vec![
+            OutputDiff::WriteOp {
+                state_key: key_1,
+                expected: Some(WriteOp::Modification(vec![1].into())),
+                actual: None,
+            },
+            OutputDiff::WriteOp {
+                state_key: key_2,
+                expected: None,
+                actual: Some(WriteOp::Deletion),
+            },
+        ]);
+    }
+}
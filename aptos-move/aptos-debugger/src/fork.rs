@@ -0,0 +1,705 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::run_session;
+use anyhow::{bail, ensure, format_err, Context, Result};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey};
+use aptos_rest_client::Client;
+use aptos_state_view::{StateViewId, TStateView};
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_config::{AccountResource, CoinStoreResource, CORE_CODE_ADDRESS},
+    chain_id::ChainId,
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+    },
+    transaction::{
+        authenticator::AuthenticationKey, RawTransaction, SignedTransaction, Transaction,
+        TransactionOutput, TransactionStatus, Version,
+    },
+    utility_coin::APTOS_COIN_TYPE,
+    write_set::{TransactionWrite, WriteOp, WriteSetMut},
+};
+use aptos_validator_interface::{
+    AptosValidatorInterface, CachedDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
+};
+use aptos_vm::{AptosVM, VMExecutor};
+use move_core_types::{
+    ident_str,
+    language_storage::{ModuleId, StructTag},
+    move_resource::MoveStructType,
+    value::MoveValue,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use url::Url;
+
+const CONFIG_FILE: &str = "fork.json";
+const STATE_FILE: &str = "state.bcs";
+const CACHE_DIR: &str = "cache";
+
+/// The key impersonated transactions are signed with. The sender's authentication key is
+/// swapped for this key's while the transaction runs, so it never needs to be kept secret.
+const IMPERSONATION_KEY_SEED: [u8; 32] = [0x1F; 32];
+
+/// Where a fork was created from.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ForkConfig {
+    /// REST endpoint of the network the state is read from.
+    pub rest_url: Url,
+    pub chain_id: ChainId,
+    /// The version of the remote network the fork starts at; its state is the state after this
+    /// version was committed.
+    pub fork_version: Version,
+}
+
+/// A transaction executed on the fork, together with its output.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ForkedTransaction {
+    pub version: Version,
+    pub transaction: SignedTransaction,
+    /// Whether the transaction was run on behalf of the sender without their signature.
+    pub impersonated: bool,
+    pub output: TransactionOutput,
+}
+
+/// Everything that happened locally since the fork was created.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct LocalState {
+    /// The latest local value of every key written on the fork, `None` for deletions.
+    writes: BTreeMap<StateKey, Option<StateValue>>,
+    transactions: Vec<ForkedTransaction>,
+}
+
+/// A local chain forked off a remote network at a given version.
+///
+/// State is read lazily from the remote network through its REST API and kept in an on-disk
+/// cache, so only what the local transactions touch is ever downloaded. Everything written
+/// locally (transactions, funding) is kept in the fork directory on top of that state, so a
+/// fork can be reopened and extended later on.
+pub struct ForkedChain {
+    dir: PathBuf,
+    config: ForkConfig,
+    /// The state of the remote network at the fork version.
+    remote: Box<dyn TStateView<Key = StateKey> + Send + Sync>,
+    local: LocalState,
+}
+
+impl ForkedChain {
+    /// Creates a new fork in `dir` of the network behind `rest_url`, at `version` or at the
+    /// latest version of the network if none is given.
+    pub async fn create<P: AsRef<Path>>(
+        dir: P,
+        rest_url: Url,
+        version: Option<Version>,
+    ) -> Result<Self> {
+        let dir = dir.as_ref();
+        ensure!(
+            !Self::exists(dir),
+            "A fork already exists in {}",
+            dir.display()
+        );
+
+        let index = Client::new(rest_url.clone())
+            .get_index()
+            .await?
+            .into_inner();
+        let latest_version = index.ledger_version.0;
+        let fork_version = version.unwrap_or(latest_version);
+        ensure!(
+            fork_version <= latest_version,
+            "Cannot fork at version {}, the latest version of the network is {}",
+            fork_version,
+            latest_version
+        );
+
+        let config = ForkConfig {
+            rest_url,
+            chain_id: ChainId::new(index.chain_id),
+            fork_version,
+        };
+        fs::create_dir_all(dir)?;
+        fs::write(dir.join(CONFIG_FILE), serde_json::to_vec_pretty(&config)?)?;
+        Self::open(dir)
+    }
+
+    /// Whether a fork was created in `dir`.
+    pub fn exists<P: AsRef<Path>>(dir: P) -> bool {
+        dir.as_ref().join(CONFIG_FILE).exists()
+    }
+
+    /// Opens a fork previously created in `dir`.
+    pub fn open<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        let config: ForkConfig = serde_json::from_slice(
+            &fs::read(dir.join(CONFIG_FILE))
+                .with_context(|| format!("No fork found in {}", dir.display()))?,
+        )?;
+        let local = match fs::read(dir.join(STATE_FILE)) {
+            Ok(bytes) => bcs::from_bytes(&bytes)
+                .with_context(|| format!("Corrupted fork state in {}", dir.display()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => LocalState::default(),
+            Err(err) => return Err(err.into()),
+        };
+
+        let interface: Arc<dyn AptosValidatorInterface + Send> =
+            Arc::new(CachedDebuggerInterface::open(
+                dir.join(CACHE_DIR),
+                config.chain_id,
+                Some(Arc::new(RestDebuggerInterface::new(Client::new(
+                    config.rest_url.clone(),
+                )))),
+            )?);
+        // The debugger state view reads the state before the given version.
+        let remote = DebuggerStateView::new(interface, config.fork_version + 1);
+
+        Ok(Self {
+            dir,
+            config,
+            remote: Box::new(remote),
+            local,
+        })
+    }
+
+    pub fn config(&self) -> &ForkConfig {
+        &self.config
+    }
+
+    /// The version of the last transaction executed on the fork.
+    pub fn version(&self) -> Version {
+        self.config.fork_version + self.local.transactions.len() as Version
+    }
+
+    /// The transactions executed on the fork, in order.
+    pub fn transactions(&self) -> &[ForkedTransaction] {
+        &self.local.transactions
+    }
+
+    /// A view of the current state of the fork.
+    pub fn state_view(&self) -> ForkedStateView<'_> {
+        ForkedStateView {
+            remote: self.remote.as_ref(),
+            writes: &self.local.writes,
+            overrides: BTreeMap::new(),
+        }
+    }
+
+    pub fn get_resource(
+        &self,
+        address: AccountAddress,
+        tag: &StructTag,
+    ) -> Result<Option<Vec<u8>>> {
+        let key = StateKey::access_path(AccessPath::resource_access_path(address, tag.clone())?);
+        Ok(self
+            .state_view()
+            .get_state_value_bytes(&key)?
+            .map(|bytes| bytes.to_vec()))
+    }
+
+    /// The sequence number of `address` on the fork, if the account exists.
+    pub fn sequence_number(&self, address: AccountAddress) -> Result<Option<u64>> {
+        Ok(self
+            .get_account_resource(address)?
+            .map(|account| account.sequence_number()))
+    }
+
+    /// The APT balance of `address` on the fork, if it has a coin store.
+    pub fn balance(&self, address: AccountAddress) -> Result<Option<u64>> {
+        Ok(self
+            .get_coin_store(address)?
+            .map(|coin_store| coin_store.coin()))
+    }
+
+    /// Executes a signed transaction on top of the fork. The transaction is committed to the
+    /// fork if it is kept, i.e. even if it aborted.
+    pub fn execute_transaction(&mut self, txn: SignedTransaction) -> Result<TransactionOutput> {
+        let state_view = self.state_view();
+        let output = execute(&state_view, &txn, self.config.chain_id)?;
+        self.commit(txn, false, output)
+    }
+
+    /// Executes a transaction on behalf of its sender, who doesn't have to sign it.
+    ///
+    /// The sender's authentication key is replaced by a throwaway key for the duration of the
+    /// transaction, which is then signed with that key. The original authentication key is put
+    /// back into the output, so it's as if the sender themselves submitted the transaction,
+    /// unless it reads or rotates the authentication key.
+    pub fn execute_impersonated(&mut self, raw_txn: RawTransaction) -> Result<TransactionOutput> {
+        let sender = raw_txn.sender();
+        let account_key = account_resource_key(sender)?;
+        let account_bytes = self
+            .state_view()
+            .get_state_value_bytes(&account_key)?
+            .ok_or_else(|| format_err!("Account {} doesn't exist on the fork", sender))?;
+
+        let private_key = Ed25519PrivateKey::try_from(&IMPERSONATION_KEY_SEED[..])?;
+        let public_key = private_key.public_key();
+        let impersonation_auth_key = AuthenticationKey::ed25519(&public_key).to_vec();
+        let original_auth_key = authentication_key(&account_bytes)?;
+
+        let mut state_view = self.state_view();
+        state_view.overrides.insert(
+            account_key.clone(),
+            StateValue::new_legacy(
+                replace_authentication_key(&account_bytes, &impersonation_auth_key)?.into(),
+            ),
+        );
+        let txn = raw_txn
+            .sign(&private_key, public_key)
+            .map_err(|err| format_err!("Failed to sign impersonated transaction: {:?}", err))?
+            .into_inner();
+        let output = execute(&state_view, &txn, self.config.chain_id)?;
+
+        // The impersonation key must not leak into the fork's state.
+        let (write_set, events, gas_used, status) = output.unpack();
+        let mut write_set: WriteSetMut = write_set.into_iter().collect();
+        if let Some(op) = write_set.as_inner_mut().get_mut(&account_key) {
+            if let Some(bytes) = op.bytes() {
+                let restored =
+                    with_bytes(op, replace_authentication_key(bytes, &original_auth_key)?);
+                *op = restored;
+            }
+        }
+        let output = TransactionOutput::new(write_set.freeze()?, events, gas_used, status);
+        self.commit(txn, true, output)
+    }
+
+    /// Mints `amount` APT (in octas) into `address`, creating the account and its coin store if
+    /// needed, and returns the new balance. The total supply of APT is not updated.
+    pub fn fund(&mut self, address: AccountAddress, amount: u64) -> Result<u64> {
+        if self.get_coin_store(address)?.is_none() {
+            let account_exists = self.get_account_resource(address)?.is_some();
+            let change_set = run_session(&self.state_view(), self.config.chain_id, |session| {
+                if account_exists {
+                    session.execute_function_bypass_visibility(
+                        &ModuleId::new(CORE_CODE_ADDRESS, ident_str!("coin").to_owned()),
+                        ident_str!("register"),
+                        vec![APTOS_COIN_TYPE.clone()],
+                        vec![MoveValue::Signer(address).simple_serialize().unwrap()],
+                        &mut move_vm_types::gas::UnmeteredGasMeter,
+                    )?;
+                } else {
+                    session.execute_function_bypass_visibility(
+                        &ModuleId::new(CORE_CODE_ADDRESS, ident_str!("aptos_account").to_owned()),
+                        ident_str!("create_account"),
+                        vec![],
+                        vec![MoveValue::Address(address).simple_serialize().unwrap()],
+                        &mut move_vm_types::gas::UnmeteredGasMeter,
+                    )?;
+                }
+                Ok(())
+            })?;
+            let change_set = change_set
+                .try_into_storage_change_set()
+                .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+            for (key, op) in change_set.write_set() {
+                self.local.writes.insert(key.clone(), op.as_state_value());
+            }
+        }
+
+        let coin_store = self
+            .get_coin_store(address)?
+            .ok_or_else(|| format_err!("Failed to create a coin store for {}", address))?;
+        let balance = coin_store
+            .coin()
+            .checked_add(amount)
+            .ok_or_else(|| format_err!("Balance of {} would overflow", address))?;
+        let coin_store = CoinStoreResource::new(
+            balance,
+            coin_store.frozen(),
+            coin_store.deposit_events().clone(),
+            coin_store.withdraw_events().clone(),
+        );
+        self.local.writes.insert(
+            coin_store_key(address)?,
+            Some(StateValue::new_legacy(bcs::to_bytes(&coin_store)?.into())),
+        );
+        self.save()?;
+        Ok(balance)
+    }
+
+    fn get_account_resource(&self, address: AccountAddress) -> Result<Option<AccountResource>> {
+        self.state_view()
+            .get_state_value_bytes(&account_resource_key(address)?)?
+            .map(|bytes| bcs::from_bytes(&bytes))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    fn get_coin_store(&self, address: AccountAddress) -> Result<Option<CoinStoreResource>> {
+        self.state_view()
+            .get_state_value_bytes(&coin_store_key(address)?)?
+            .map(|bytes| bcs::from_bytes(&bytes))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    fn commit(
+        &mut self,
+        transaction: SignedTransaction,
+        impersonated: bool,
+        output: TransactionOutput,
+    ) -> Result<TransactionOutput> {
+        if let TransactionStatus::Keep(_) = output.status() {
+            for (key, op) in output.write_set() {
+                self.local.writes.insert(key.clone(), op.as_state_value());
+            }
+            self.local.transactions.push(ForkedTransaction {
+                version: self.version() + 1,
+                transaction,
+                impersonated,
+                output: output.clone(),
+            });
+            self.save()?;
+        }
+        Ok(output)
+    }
+
+    /// Writes the local state atomically, so a fork is never left half written.
+    fn save(&self) -> Result<()> {
+        let tmp_path = self.dir.join(format!("{}.tmp", STATE_FILE));
+        fs::write(&tmp_path, bcs::to_bytes(&self.local)?)?;
+        fs::rename(tmp_path, self.dir.join(STATE_FILE))?;
+        Ok(())
+    }
+}
+
+/// The state of a fork: the local writes on top of the remote state at the fork version.
+pub struct ForkedStateView<'a> {
+    remote: &'a (dyn TStateView<Key = StateKey> + Sync),
+    writes: &'a BTreeMap<StateKey, Option<StateValue>>,
+    /// Values which only apply to this view, e.g. while impersonating an account.
+    overrides: BTreeMap<StateKey, StateValue>,
+}
+
+impl<'a> TStateView for ForkedStateView<'a> {
+    type Key = StateKey;
+
+    fn id(&self) -> StateViewId {
+        self.remote.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        if let Some(value) = self.overrides.get(state_key) {
+            return Ok(Some(value.clone()));
+        }
+        match self.writes.get(state_key) {
+            Some(value) => Ok(value.clone()),
+            None => self.remote.get_state_value(state_key),
+        }
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.remote.get_usage()
+    }
+}
+
+fn execute(
+    state_view: &ForkedStateView,
+    txn: &SignedTransaction,
+    chain_id: ChainId,
+) -> Result<TransactionOutput> {
+    ensure!(
+        txn.chain_id() == chain_id,
+        "Transaction is for chain {}, but the fork is of chain {}",
+        txn.chain_id(),
+        chain_id
+    );
+    let txn = Transaction::UserTransaction(txn.clone());
+    let mut outputs = AptosVM::execute_block(&[txn.into()], state_view, None)
+        .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+    outputs
+        .pop()
+        .ok_or_else(|| format_err!("No output for the transaction"))
+}
+
+fn account_resource_key(address: AccountAddress) -> Result<StateKey> {
+    Ok(StateKey::access_path(AccessPath::resource_access_path(
+        address,
+        AccountResource::struct_tag(),
+    )?))
+}
+
+fn coin_store_key(address: AccountAddress) -> Result<StateKey> {
+    Ok(StateKey::access_path(AccessPath::resource_access_path(
+        address,
+        CoinStoreResource::struct_tag(),
+    )?))
+}
+
+/// The authentication key is the first field of the account resource: a BCS vector of 32 bytes,
+/// i.e. a length byte followed by the key.
+fn authentication_key(account_bytes: &[u8]) -> Result<Vec<u8>> {
+    if account_bytes.len() < 33 || account_bytes[0] != 32 {
+        bail!("Unexpected account resource layout");
+    }
+    Ok(account_bytes[1..33].to_vec())
+}
+
+/// Swaps the authentication key in a serialized account resource, leaving all other fields
+/// untouched.
+fn replace_authentication_key(account_bytes: &[u8], auth_key: &[u8]) -> Result<Vec<u8>> {
+    authentication_key(account_bytes)?;
+    ensure!(auth_key.len() == 32, "Authentication keys are 32 bytes");
+    let mut bytes = account_bytes.to_vec();
+    bytes[1..33].copy_from_slice(auth_key);
+    Ok(bytes)
+}
+
+fn with_bytes(op: &WriteOp, data: Vec<u8>) -> WriteOp {
+    match op {
+        WriteOp::Creation(_) => WriteOp::Creation(data.into()),
+        WriteOp::Modification(_) => WriteOp::Modification(data.into()),
+        WriteOp::CreationWithMetadata { metadata, .. } => WriteOp::CreationWithMetadata {
+            data: data.into(),
+            metadata: metadata.clone(),
+        },
+        WriteOp::ModificationWithMetadata { metadata, .. } => WriteOp::ModificationWithMetadata {
+            data: data.into(),
+            metadata: metadata.clone(),
+        },
+        WriteOp::Deletion | WriteOp::DeletionWithMetadata { .. } => op.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_cached_packages::aptos_stdlib;
+    use aptos_language_e2e_tests::{
+        account::{Account, AccountData},
+        executor::FakeExecutor,
+    };
+    use aptos_temppath::TempPath;
+    use aptos_types::{
+        event::{EventHandle, EventKey},
+        transaction::ExecutionStatus,
+    };
+
+    /// A fork of a genesis state with a single funded account, which is returned with it.
+    fn fork_with_account(dir: &TempPath) -> (ForkedChain, AccountData) {
+        let mut executor = FakeExecutor::from_head_genesis();
+        let sender = executor.create_raw_account_data(1_000_000_000, 0);
+        executor.add_account_data(&sender);
+        dir.create_as_dir().unwrap();
+
+        let fork = ForkedChain {
+            dir: dir.path().to_path_buf(),
+            config: ForkConfig {
+                rest_url: Url::parse("http://localhost:8080").unwrap(),
+                chain_id: ChainId::test(),
+                fork_version: 10,
+            },
+            remote: Box::new(executor.data_store().clone()),
+            local: LocalState::default(),
+        };
+        (fork, sender)
+    }
+
+    #[test]
+    fn test_execute_transaction() {
+        let dir = TempPath::new();
+        let (mut fork, sender) = fork_with_account(&dir);
+        let receiver = Account::new();
+
+        let txn = sender
+            .account()
+            .transaction()
+            .payload(aptos_stdlib::aptos_account_transfer(
+                *receiver.address(),
+                1000,
+            ))
+            .sequence_number(0)
+            .gas_unit_price(100)
+            .sign();
+        let output = fork.execute_transaction(txn.clone()).unwrap();
+        assert_eq!(
+            output.status(),
+            &TransactionStatus::Keep(ExecutionStatus::Success)
+        );
+
+        // The writes of the transaction become the state of the fork.
+        let receiver_coin_store = coin_store_key(*receiver.address()).unwrap();
+        assert!(output
+            .write_set()
+            .iter()
+            .any(|(key, _)| key == &receiver_coin_store));
+        assert_eq!(fork.balance(*receiver.address()).unwrap(), Some(1000));
+        assert_eq!(fork.sequence_number(*sender.address()).unwrap(), Some(1));
+        assert_eq!(
+            fork.balance(*sender.address()).unwrap(),
+            Some(1_000_000_000 - 1000 - output.gas_used() * 100)
+        );
+        // The remote state is left untouched.
+        assert!(fork
+            .remote
+            .get_state_value(&receiver_coin_store)
+            .unwrap()
+            .is_none());
+
+        assert_eq!(fork.version(), 11);
+        let forked_txn = fork.transactions().last().unwrap();
+        assert_eq!(forked_txn.version, 11);
+        assert_eq!(forked_txn.transaction, txn);
+        assert!(!forked_txn.impersonated);
+
+        // Everything is kept when the fork is reopened.
+        let local: LocalState =
+            bcs::from_bytes(&fs::read(dir.path().join(STATE_FILE)).unwrap()).unwrap();
+        assert_eq!(local.transactions.len(), 1);
+        assert_eq!(local.writes, fork.local.writes);
+    }
+
+    #[test]
+    fn test_execute_transaction_wrong_chain() {
+        let dir = TempPath::new();
+        let (mut fork, sender) = fork_with_account(&dir);
+
+        let txn = sender
+            .account()
+            .transaction()
+            .payload(aptos_stdlib::aptos_account_transfer(AccountAddress::ONE, 1))
+            .sequence_number(0)
+            .gas_unit_price(100)
+            .chain_id(ChainId::new(42))
+            .sign();
+        assert!(fork.execute_transaction(txn).is_err());
+        assert!(fork.transactions().is_empty());
+    }
+
+    #[test]
+    fn test_execute_impersonated() {
+        let dir = TempPath::new();
+        let (mut fork, sender) = fork_with_account(&dir);
+        let receiver = Account::new();
+
+        // The transaction is run as the sender, but not signed with their key.
+        let raw_txn = sender
+            .account()
+            .transaction()
+            .payload(aptos_stdlib::aptos_account_transfer(
+                *receiver.address(),
+                1000,
+            ))
+            .sequence_number(0)
+            .gas_unit_price(100)
+            .raw();
+        let output = fork.execute_impersonated(raw_txn).unwrap();
+        assert_eq!(
+            output.status(),
+            &TransactionStatus::Keep(ExecutionStatus::Success)
+        );
+        assert_eq!(fork.balance(*receiver.address()).unwrap(), Some(1000));
+        assert_eq!(fork.sequence_number(*sender.address()).unwrap(), Some(1));
+
+        // The account resource written by the transaction keeps the sender's own key.
+        let account_key = account_resource_key(*sender.address()).unwrap();
+        let (_, op) = output
+            .write_set()
+            .iter()
+            .find(|(key, _)| key == &account_key)
+            .unwrap();
+        assert_eq!(
+            authentication_key(op.bytes().unwrap()).unwrap(),
+            sender.account().auth_key()
+        );
+        let account_bytes = fork
+            .state_view()
+            .get_state_value_bytes(&account_key)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            authentication_key(&account_bytes).unwrap(),
+            sender.account().auth_key()
+        );
+        assert!(fork.transactions().last().unwrap().impersonated);
+    }
+
+    #[test]
+    fn test_fund() {
+        let dir = TempPath::new();
+        let (mut fork, sender) = fork_with_account(&dir);
+        let new_account = AccountAddress::random();
+
+        assert_eq!(fork.fund(*sender.address(), 5).unwrap(), 1_000_000_005);
+        assert_eq!(fork.fund(new_account, 5).unwrap(), 5);
+        assert_eq!(fork.sequence_number(new_account).unwrap(), Some(0));
+        assert_eq!(fork.balance(new_account).unwrap(), Some(5));
+        // Funding doesn't run a transaction.
+        assert_eq!(fork.version(), 10);
+    }
+
+    #[test]
+    fn test_state_view_precedence() {
+        let dir = TempPath::new();
+        let (mut fork, sender) = fork_with_account(&dir);
+        let account_key = account_resource_key(*sender.address()).unwrap();
+        let coin_store_key = coin_store_key(*sender.address()).unwrap();
+        let remote_account = fork.remote.get_state_value(&account_key).unwrap();
+
+        // A local deletion hides the remote value.
+        fork.local.writes.insert(coin_store_key.clone(), None);
+        let mut state_view = fork.state_view();
+        assert!(state_view
+            .get_state_value(&coin_store_key)
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            state_view.get_state_value(&account_key).unwrap(),
+            remote_account
+        );
+
+        // Overrides win over both the local writes and the remote state.
+        let value = StateValue::new_legacy(vec![1, 2, 3].into());
+        state_view
+            .overrides
+            .insert(coin_store_key.clone(), value.clone());
+        state_view
+            .overrides
+            .insert(account_key.clone(), value.clone());
+        assert_eq!(
+            state_view.get_state_value(&coin_store_key).unwrap(),
+            Some(value.clone())
+        );
+        assert_eq!(
+            state_view.get_state_value(&account_key).unwrap(),
+            Some(value)
+        );
+        // But only apply to the view they were set on.
+        assert_eq!(
+            fork.state_view().get_state_value(&account_key).unwrap(),
+            remote_account
+        );
+    }
+
+    #[test]
+    fn test_replace_authentication_key() {
+        let account = AccountResource::new(
+            7,
+            vec![1; 32],
+            EventHandle::new(EventKey::new(0, AccountAddress::ONE), 0),
+            EventHandle::new(EventKey::new(1, AccountAddress::ONE), 0),
+        );
+        let bytes = bcs::to_bytes(&account).unwrap();
+        assert_eq!(authentication_key(&bytes).unwrap(), vec![1; 32]);
+
+        let replaced = replace_authentication_key(&bytes, &[2; 32]).unwrap();
+        let replaced_account: AccountResource = bcs::from_bytes(&replaced).unwrap();
+        assert_eq!(replaced_account.authentication_key(), &[2; 32]);
+        assert_eq!(replaced_account.sequence_number(), 7);
+        assert_eq!(
+            replaced_account.coin_register_events(),
+            account.coin_register_events()
+        );
+
+        let restored = replace_authentication_key(&replaced, &[1; 32]).unwrap();
+        assert_eq!(restored, bytes);
+        assert!(replace_authentication_key(&bytes, &[2; 16]).is_err());
+    }
+}
@@ -1,7 +1,18 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{format_err, Result};
+mod diff;
+mod fork;
+mod overrides;
+mod tracer;
+
+pub use crate::{
+    diff::{OutputDiff, ReplayReport, TransactionDiff},
+    fork::{ForkConfig, ForkedChain, ForkedStateView, ForkedTransaction},
+    overrides::{OverriddenStateView, ReplayOverrides},
+    tracer::{ExecutionTrace, ExecutionTracer, TraceEvent, TraceStep},
+};
+use anyhow::{bail, format_err, Result};
 use aptos_gas_meter::{StandardGasAlgebra, StandardGasMeter};
 use aptos_gas_profiling::{GasProfiler, TransactionGasLog};
 use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters, LATEST_GAS_FEATURE_VERSION};
@@ -13,6 +24,7 @@ use aptos_types::{
     account_address::AccountAddress,
     chain_id::ChainId,
     on_chain_config::{Features, OnChainConfig, TimedFeaturesBuilder},
+    state_store::state_key::StateKey,
     transaction::{
         signature_verified_transaction::SignatureVerifiedTransaction, SignedTransaction,
         Transaction, TransactionInfo, TransactionOutput, TransactionPayload, Version,
@@ -20,7 +32,8 @@ use aptos_types::{
     vm_status::VMStatus,
 };
 use aptos_validator_interface::{
-    AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
+    AptosValidatorInterface, CachedDebuggerInterface, DBDebuggerInterface, DebuggerStateView,
+    RestDebuggerInterface,
 };
 use aptos_vm::{
     data_cache::AsMoveResolver,
@@ -45,6 +58,27 @@ impl AptosDebugger {
         Ok(Self::new(Arc::new(RestDebuggerInterface::new(rest_client))))
     }
 
+    /// Like `rest_client`, but keeps everything fetched from the node in a persistent cache under
+    /// `cache_dir`, so that later sessions replaying the same versions are fast.
+    pub fn rest_client_with_cache<P: AsRef<Path>>(
+        rest_client: Client,
+        cache_dir: P,
+        chain_id: ChainId,
+    ) -> Result<Self> {
+        Ok(Self::new(Arc::new(CachedDebuggerInterface::open(
+            cache_dir,
+            chain_id,
+            Some(Arc::new(RestDebuggerInterface::new(rest_client))),
+        )?)))
+    }
+
+    /// Replays purely from a cache previously populated by `rest_client_with_cache`.
+    pub fn offline<P: AsRef<Path>>(cache_dir: P, chain_id: ChainId) -> Result<Self> {
+        Ok(Self::new(Arc::new(CachedDebuggerInterface::open(
+            cache_dir, chain_id, None,
+        )?)))
+    }
+
     pub fn db<P: AsRef<Path> + Clone>(db_root_path: P) -> Result<Self> {
         Ok(Self::new(Arc::new(DBDebuggerInterface::open(
             db_root_path,
@@ -63,6 +97,24 @@ impl AptosDebugger {
             .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))
     }
 
+    /// Executes the given transactions on top of the state at `version`, with `overrides`
+    /// (e.g. a locally built framework) applied to that state.
+    pub fn execute_transactions_at_version_with_overrides(
+        &self,
+        version: Version,
+        txns: Vec<Transaction>,
+        overrides: &ReplayOverrides,
+    ) -> Result<Vec<TransactionOutput>> {
+        let sig_verified_txns: Vec<SignatureVerifiedTransaction> =
+            txns.into_iter().map(|x| x.into()).collect::<Vec<_>>();
+        let state_view = OverriddenStateView::with_overrides(
+            DebuggerStateView::new(self.debugger.clone(), version),
+            overrides,
+        )?;
+        AptosVM::execute_block(&sig_verified_txns, &state_view, None)
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))
+    }
+
     pub fn execute_transaction_at_version_with_gas_profiler(
         &self,
         version: Version,
@@ -108,6 +160,69 @@ impl AptosDebugger {
         Ok((status, output, gas_profiler.finish()))
     }
 
+    /// Re-executes `txn` on top of the state at `version`, recording a step-by-step trace of
+    /// the executed instructions, calls, values and global storage accesses of its payload.
+    pub fn execute_transaction_at_version_with_tracer(
+        &self,
+        version: Version,
+        txn: SignedTransaction,
+    ) -> Result<(VMStatus, VMOutput, ExecutionTrace)> {
+        match txn.payload() {
+            TransactionPayload::Script(_) | TransactionPayload::EntryFunction(_) => (),
+            TransactionPayload::ModuleBundle(..) => bail!("Module bundles are not supported"),
+            TransactionPayload::Multisig(..) => {
+                bail!("Tracing multisig transactions is not supported yet")
+            },
+        }
+
+        let state_view = DebuggerStateView::new(self.debugger.clone(), version);
+        let log_context = AdapterLogSchema::new(state_view.id(), 0);
+        let txn = txn
+            .check_signature()
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+
+        let vm = AptosVM::new_from_state_view(&state_view);
+        let resolver = state_view.as_move_resolver();
+
+        let (status, output, tracer) = vm.execute_user_transaction_with_custom_gas_meter(
+            &resolver,
+            &txn,
+            &log_context,
+            |gas_feature_version, gas_params, storage_gas_params, balance| {
+                let gas_meter =
+                    MemoryTrackedGasMeter::new(StandardGasMeter::new(StandardGasAlgebra::new(
+                        gas_feature_version,
+                        gas_params,
+                        storage_gas_params,
+                        balance,
+                    )));
+                let tracer = match txn.payload() {
+                    TransactionPayload::Script(_) => ExecutionTracer::new_script(gas_meter),
+                    TransactionPayload::EntryFunction(entry_func) => ExecutionTracer::new_function(
+                        gas_meter,
+                        entry_func.module().clone(),
+                        entry_func.function().to_owned(),
+                        entry_func.ty_args().to_vec(),
+                    ),
+                    TransactionPayload::ModuleBundle(..) | TransactionPayload::Multisig(..) => {
+                        unreachable!("rejected before execution")
+                    },
+                };
+                Ok(tracer)
+            },
+        )?;
+
+        Ok((status, output, tracer.finish()))
+    }
+
+    pub async fn get_committed_transactions(
+        &self,
+        begin: Version,
+        limit: u64,
+    ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
+        self.debugger.get_committed_transactions(begin, limit).await
+    }
+
     pub async fn execute_past_transactions(
         &self,
         mut begin: Version,
@@ -127,6 +242,14 @@ impl AptosDebugger {
             let mut epoch_result = self
                 .execute_transactions_by_epoch(begin, txns.clone())
                 .await?;
+            if epoch_result.is_empty() || epoch_result.len() > txn_infos.len() {
+                bail!(
+                    "Executed {} transactions at version {}, but {} transaction infos are left",
+                    epoch_result.len(),
+                    begin,
+                    txn_infos.len()
+                );
+            }
             begin += epoch_result.len() as u64;
             limit -= epoch_result.len() as u64;
             txns = txns.split_off(epoch_result.len());
@@ -138,6 +261,64 @@ impl AptosDebugger {
         Ok(ret)
     }
 
+    /// Replays `limit` transactions starting at `begin` with the given overrides applied to the
+    /// on-chain state, and diffs every resulting output against the recorded one.
+    ///
+    /// Overrides are re-applied at the start of every epoch, so a locally built framework stays
+    /// in place even if the replayed range contains a framework upgrade.
+    pub async fn replay_past_transactions_with_overrides(
+        &self,
+        mut begin: Version,
+        mut limit: u64,
+        overrides: &ReplayOverrides,
+    ) -> Result<ReplayReport> {
+        let (mut txns, _, mut expected_outputs) = self
+            .debugger
+            .get_committed_transactions_with_outputs(begin, limit)
+            .await?;
+
+        let mut report = ReplayReport::new(begin);
+        while limit != 0 {
+            println!(
+                "Starting epoch execution at {:?}, {:?} transactions remaining",
+                begin, limit
+            );
+            let results = self.execute_transactions_at_version_with_overrides(
+                begin,
+                txns.clone(),
+                overrides,
+            )?;
+            let epoch_result = truncate_at_reconfiguration(results);
+            if epoch_result.is_empty() {
+                break;
+            }
+            if epoch_result.len() > expected_outputs.len() {
+                bail!(
+                    "Executed {} transactions at version {}, but {} recorded outputs are left",
+                    epoch_result.len(),
+                    begin,
+                    expected_outputs.len()
+                );
+            }
+
+            let epoch_expected_outputs = expected_outputs
+                .drain(0..epoch_result.len())
+                .collect::<Vec<_>>();
+            for (idx, (expected, actual)) in epoch_expected_outputs
+                .iter()
+                .zip(epoch_result.iter())
+                .enumerate()
+            {
+                report.add(begin + idx as Version, expected, actual);
+            }
+
+            begin += epoch_result.len() as u64;
+            limit -= epoch_result.len() as u64;
+            txns = txns.split_off(epoch_result.len());
+        }
+        Ok(report)
+    }
+
     fn print_mismatches(
         txn_outputs: &[TransactionOutput],
         expected_txn_infos: &[TransactionInfo],
@@ -159,19 +340,7 @@ impl AptosDebugger {
         txns: Vec<Transaction>,
     ) -> Result<Vec<TransactionOutput>> {
         let results = self.execute_transactions_at_version(begin, txns)?;
-        let mut ret = vec![];
-        let mut is_reconfig = false;
-
-        for result in results.into_iter() {
-            if is_reconfig {
-                continue;
-            }
-            if is_reconfiguration(&result) {
-                is_reconfig = true;
-            }
-            ret.push(result)
-        }
-        Ok(ret)
+        Ok(truncate_at_reconfiguration(results))
     }
 
     pub async fn annotate_account_state_at_version(
@@ -229,28 +398,72 @@ impl AptosDebugger {
         F: FnOnce(&mut SessionExt) -> VMResult<()>,
     {
         let state_view = DebuggerStateView::new(self.debugger.clone(), version);
-        let state_view_storage = state_view.as_move_resolver();
-        let features = Features::fetch_config(&state_view_storage).unwrap_or_default();
-        let move_vm = MoveVmExt::new(
-            NativeGasParameters::zeros(),
-            MiscGasParameters::zeros(),
-            LATEST_GAS_FEATURE_VERSION,
-            ChainId::test().id(),
-            features,
-            TimedFeaturesBuilder::enable_all().build(),
-            &state_view_storage,
+        run_session(&state_view, ChainId::test(), f)
+    }
+}
+
+/// Runs `f` in a new, unmetered session on top of `state_view` and returns its changes.
+pub fn run_session<F>(
+    state_view: &impl TStateView<Key = StateKey>,
+    chain_id: ChainId,
+    f: F,
+) -> Result<VMChangeSet>
+where
+    F: FnOnce(&mut SessionExt) -> VMResult<()>,
+{
+    run_session_with_id(state_view, chain_id, SessionId::Void, f)
+}
+
+/// Like `run_session`, but the session is created with the given id, so the transaction context
+/// seen by Move code (e.g. the script hash) can match the one of a real transaction.
+pub fn run_session_with_id<F>(
+    state_view: &impl TStateView<Key = StateKey>,
+    chain_id: ChainId,
+    session_id: SessionId,
+    f: F,
+) -> Result<VMChangeSet>
+where
+    F: FnOnce(&mut SessionExt) -> VMResult<()>,
+{
+    let state_view_storage = state_view.as_move_resolver();
+    let features = Features::fetch_config(&state_view_storage).unwrap_or_default();
+    let move_vm = MoveVmExt::new(
+        NativeGasParameters::zeros(),
+        MiscGasParameters::zeros(),
+        LATEST_GAS_FEATURE_VERSION,
+        chain_id.id(),
+        features,
+        TimedFeaturesBuilder::enable_all().build(),
+        &state_view_storage,
+    )
+    .unwrap();
+    let mut session = move_vm.new_session(&state_view_storage, session_id);
+    f(&mut session).map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+    let change_set = session
+        .finish(
+            &mut (),
+            &ChangeSetConfigs::unlimited_at_gas_feature_version(LATEST_GAS_FEATURE_VERSION),
         )
-        .unwrap();
-        let mut session = move_vm.new_session(&state_view_storage, SessionId::Void);
-        f(&mut session).map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
-        let change_set = session
-            .finish(
-                &mut (),
-                &ChangeSetConfigs::unlimited_at_gas_feature_version(LATEST_GAS_FEATURE_VERSION),
-            )
-            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
-        Ok(change_set)
+        .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))?;
+    Ok(change_set)
+}
+
+/// Drops all outputs after the first reconfiguration, since those transactions have to be
+/// re-executed in the next epoch.
+fn truncate_at_reconfiguration(results: Vec<TransactionOutput>) -> Vec<TransactionOutput> {
+    let mut ret = vec![];
+    let mut is_reconfig = false;
+
+    for result in results.into_iter() {
+        if is_reconfig {
+            continue;
+        }
+        if is_reconfiguration(&result) {
+            is_reconfig = true;
+        }
+        ret.push(result)
     }
+    ret
 }
 
 fn is_reconfiguration(vm_output: &TransactionOutput) -> bool {
@@ -1,20 +1,36 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Result;
-use aptos_debugger::AptosDebugger;
+use anyhow::{bail, Result};
+use aptos_debugger::{AptosDebugger, ReplayOverrides};
+use aptos_framework::ReleaseBundle;
 use aptos_rest_client::Client;
+use aptos_types::chain_id::ChainId;
+use aptos_validator_interface::{CachedDebuggerInterface, RestDebuggerInterface};
 use aptos_vm::AptosVM;
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 use url::Url;
 
 #[derive(Subcommand)]
 pub enum Target {
     /// Use full node's rest api as query endpoint.
-    Rest { endpoint: String },
+    Rest {
+        endpoint: String,
+        /// Persist everything fetched from the endpoint in this directory and reuse it in
+        /// later runs.
+        #[clap(long)]
+        cache_dir: Option<PathBuf>,
+    },
     /// Use a local db instance to serve as query endpoint.
     DB { path: PathBuf },
+    /// Use only a cache directory populated by earlier `rest --cache-dir` runs, without network.
+    Offline {
+        cache_dir: PathBuf,
+        /// The chain id of the network the cache was populated from.
+        #[clap(long)]
+        chain_id: u8,
+    },
 }
 #[derive(Parser)]
 pub struct Argument {
@@ -29,6 +45,34 @@ pub struct Argument {
 
     #[clap(long, default_value_t = 1)]
     concurrency_level: usize,
+
+    /// Replay with the framework from this release bundle (e.g. a locally built `head.mrb`)
+    /// instead of the code on chain. Implies `--diff`.
+    #[clap(long)]
+    framework_bundle: Option<PathBuf>,
+
+    /// Feature flags (by numeric id) to enable on top of the on-chain features. Implies `--diff`.
+    #[clap(long, value_delimiter = ',')]
+    enable_features: Vec<u64>,
+
+    /// Feature flags (by numeric id) to disable on top of the on-chain features. Implies `--diff`.
+    #[clap(long, value_delimiter = ',')]
+    disable_features: Vec<u64>,
+
+    /// Compare every replayed output with the recorded one and print a summary report instead
+    /// of the raw outputs.
+    #[clap(long)]
+    diff: bool,
+
+    /// Also write the full report, including every mismatch, as JSON to this file.
+    #[clap(long)]
+    report_path: Option<PathBuf>,
+
+    /// Only populate the cache of `rest --cache-dir` for a later `offline` run: fetch the
+    /// transactions of the range and replay them once to fetch the state they read, without
+    /// printing the outputs.
+    #[clap(long)]
+    prefetch: bool,
 }
 
 #[tokio::main]
@@ -38,18 +82,72 @@ async fn main() -> Result<()> {
     AptosVM::set_concurrency_level_once(args.concurrency_level);
 
     let debugger = match args.target {
-        Target::Rest { endpoint } => {
-            AptosDebugger::rest_client(Client::new(Url::parse(&endpoint)?))?
+        Target::Rest {
+            endpoint,
+            cache_dir,
+        } => {
+            let client = Client::new(Url::parse(&endpoint)?);
+            match cache_dir {
+                Some(cache_dir) => {
+                    let chain_id = client.get_ledger_information().await?.into_inner().chain_id;
+                    let cache = CachedDebuggerInterface::open(
+                        cache_dir,
+                        ChainId::new(chain_id),
+                        Some(Arc::new(RestDebuggerInterface::new(client))),
+                    )?;
+                    if args.prefetch {
+                        cache.prefetch(args.begin_version, args.limit).await?;
+                    }
+                    AptosDebugger::new(Arc::new(cache))
+                },
+                None if args.prefetch => bail!("--prefetch requires `rest --cache-dir`"),
+                None => AptosDebugger::rest_client(client)?,
+            }
+        },
+        Target::DB { .. } | Target::Offline { .. } if args.prefetch => {
+            bail!("--prefetch requires `rest --cache-dir`")
         },
         Target::DB { path } => AptosDebugger::db(path)?,
+        Target::Offline {
+            cache_dir,
+            chain_id,
+        } => AptosDebugger::offline(cache_dir, ChainId::new(chain_id))?,
     };
 
-    println!(
-        "{:#?}",
+    if args.prefetch {
+        // Replaying reads, and so caches, all the state the transactions need.
         debugger
             .execute_past_transactions(args.begin_version, args.limit)
-            .await?
-    );
+            .await?;
+        println!(
+            "Cached {} transactions starting at version {}",
+            args.limit, args.begin_version
+        );
+        return Ok(());
+    }
+
+    let overrides = ReplayOverrides {
+        framework: args.framework_bundle.map(ReleaseBundle::read).transpose()?,
+        enable_features: args.enable_features,
+        disable_features: args.disable_features,
+    };
+
+    if args.diff || !overrides.is_empty() || args.report_path.is_some() {
+        let report = debugger
+            .replay_past_transactions_with_overrides(args.begin_version, args.limit, &overrides)
+            .await?;
+        println!("{}", report);
+        if let Some(report_path) = args.report_path {
+            std::fs::write(report_path, serde_json::to_vec_pretty(&report)?)?;
+        }
+    } else {
+        println!(
+            "{:#?}",
+            debugger
+                .execute_past_transactions(args.begin_version, args.limit)
+                .await?
+        );
+    }
 
     Ok(())
 }
@@ -0,0 +1,159 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{format_err, Result};
+use aptos_framework::{natives::code::PackageRegistry, ReleaseBundle};
+use aptos_state_view::{StateViewId, TStateView};
+use aptos_types::{
+    access_path::AccessPath,
+    on_chain_config::{Features, OnChainConfig},
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+    },
+};
+use move_binary_format::access::ModuleAccess;
+use std::collections::HashMap;
+
+/// Changes applied on top of the on-chain state when replaying transactions, e.g. to test a
+/// framework upgrade against historical traffic before it is proposed.
+#[derive(Clone, Debug, Default)]
+pub struct ReplayOverrides {
+    /// A locally built framework whose modules (and package metadata) replace the ones on chain.
+    pub framework: Option<ReleaseBundle>,
+    /// Feature flags (by their numeric id) to enable on top of the on-chain `Features`.
+    pub enable_features: Vec<u64>,
+    /// Feature flags (by their numeric id) to disable on top of the on-chain `Features`.
+    pub disable_features: Vec<u64>,
+}
+
+impl ReplayOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.framework.is_none()
+            && self.enable_features.is_empty()
+            && self.disable_features.is_empty()
+    }
+
+    /// Computes the state values which have to be replaced in `base` for these overrides to
+    /// take effect.
+    pub fn state_overrides(
+        &self,
+        base: &impl TStateView<Key = StateKey>,
+    ) -> Result<HashMap<StateKey, StateValue>> {
+        let mut overrides = HashMap::new();
+
+        if let Some(bundle) = &self.framework {
+            for package in &bundle.packages {
+                let mut address = None;
+                for (code, module) in package.sorted_code_and_modules() {
+                    address = Some(*module.self_id().address());
+                    overrides.insert(
+                        StateKey::access_path(AccessPath::code_access_path(module.self_id())),
+                        StateValue::new_legacy(code.to_vec().into()),
+                    );
+                }
+                let address = match address {
+                    Some(address) => address,
+                    None => continue,
+                };
+
+                // Keep the package registry in sync with the code, so upgrades and dependency
+                // checks performed during the replay see the local packages.
+                let registry_key = StateKey::access_path(AccessPath::resource_access_path(
+                    address,
+                    PackageRegistry::struct_tag(),
+                )?);
+                let mut registry = match overrides.get(&registry_key) {
+                    Some(value) => bcs::from_bytes::<PackageRegistry>(value.bytes())?,
+                    None => match base.get_state_value_bytes(&registry_key)? {
+                        Some(bytes) => bcs::from_bytes::<PackageRegistry>(&bytes)?,
+                        None => PackageRegistry { packages: vec![] },
+                    },
+                };
+                let metadata = package.package_metadata().clone();
+                match registry
+                    .packages
+                    .iter_mut()
+                    .find(|p| p.name == metadata.name)
+                {
+                    Some(existing) => *existing = metadata,
+                    None => registry.packages.push(metadata),
+                }
+                overrides.insert(
+                    registry_key,
+                    StateValue::new_legacy(bcs::to_bytes(&registry)?.into()),
+                );
+            }
+        }
+
+        if !self.enable_features.is_empty() || !self.disable_features.is_empty() {
+            let features_key = StateKey::access_path(Features::access_path()?);
+            let mut features = match base.get_state_value_bytes(&features_key)? {
+                Some(bytes) => Features::deserialize_into_config(&bytes)?,
+                None => Features::default(),
+            };
+            for flag in &self.enable_features {
+                set_feature(&mut features, *flag, true);
+            }
+            for flag in &self.disable_features {
+                set_feature(&mut features, *flag, false);
+            }
+            overrides.insert(
+                features_key,
+                StateValue::new_legacy(bcs::to_bytes(&features)?.into()),
+            );
+        }
+
+        Ok(overrides)
+    }
+}
+
+fn set_feature(features: &mut Features, flag: u64, enabled: bool) {
+    let byte_index = (flag / 8) as usize;
+    let bit_mask = 1 << (flag % 8);
+    if byte_index >= features.features.len() {
+        features.features.resize(byte_index + 1, 0);
+    }
+    if enabled {
+        features.features[byte_index] |= bit_mask;
+    } else {
+        features.features[byte_index] &= !bit_mask;
+    }
+}
+
+/// A state view which serves the given overrides and falls back to `base` for everything else.
+pub struct OverriddenStateView<S> {
+    base: S,
+    overrides: HashMap<StateKey, StateValue>,
+}
+
+impl<S: TStateView<Key = StateKey>> OverriddenStateView<S> {
+    pub fn new(base: S, overrides: HashMap<StateKey, StateValue>) -> Self {
+        Self { base, overrides }
+    }
+
+    pub fn with_overrides(base: S, overrides: &ReplayOverrides) -> Result<Self> {
+        let overrides = overrides
+            .state_overrides(&base)
+            .map_err(|err| format_err!("Failed to compute state overrides: {:?}", err))?;
+        Ok(Self::new(base, overrides))
+    }
+}
+
+impl<S: TStateView<Key = StateKey>> TStateView for OverriddenStateView<S> {
+    type Key = StateKey;
+
+    fn id(&self) -> StateViewId {
+        self.base.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        match self.overrides.get(state_key) {
+            Some(value) => Ok(Some(value.clone())),
+            None => self.base.get_state_value(state_key),
+        }
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.base.get_usage()
+    }
+}
@@ -0,0 +1,758 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_gas_algebra::{Fee, FeePerGasUnit, InternalGas, NumArgs, NumBytes};
+use aptos_gas_meter::AptosGasMeter;
+use aptos_gas_profiling::{delegate, delegate_mut};
+use aptos_types::{
+    contract_event::ContractEvent, state_store::state_key::StateKey, write_set::WriteOp,
+};
+use aptos_vm_types::change_set::GroupWrite;
+use move_binary_format::{
+    errors::{PartialVMResult, VMResult},
+    file_format::CodeOffset,
+    file_format_common::Opcodes,
+};
+use move_core_types::{
+    account_address::AccountAddress,
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+    u256::U256,
+};
+use move_vm_types::{
+    gas::{GasMeter, SimpleInstruction},
+    views::{TypeView, ValueView, ValueVisitor},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A step-by-step record of the execution of a transaction payload, as observed through the
+/// gas meter callbacks of the Move VM.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ExecutionTrace {
+    pub steps: Vec<TraceStep>,
+}
+
+/// A single step of an `ExecutionTrace`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TraceStep {
+    /// Depth of the call stack, with 0 being the entry function or script.
+    pub depth: usize,
+    /// The function executing the step, e.g. `0x1::coin::transfer`.
+    pub function: String,
+    /// The bytecode offset of the instruction within `function`. Not available for steps which
+    /// are not instructions, such as returning from a native function.
+    pub offset: Option<CodeOffset>,
+    /// Internal gas charged for the step.
+    pub gas: u64,
+    pub event: TraceEvent,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TraceEvent {
+    /// An executed instruction, along with the values it consumed or produced (when these are
+    /// exposed to the gas meter), and the type it operated on for global storage and vector
+    /// instructions.
+    Instruction {
+        op: String,
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        ty: Option<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty", default)]
+        values: Vec<Value>,
+    },
+    /// A call to a Move or native function, with the arguments it got called with.
+    Call {
+        function: String,
+        ty_args: Vec<String>,
+        args: Vec<Value>,
+    },
+    /// A native function returned.
+    NativeReturn { return_values: Vec<Value> },
+    /// A function returned, with the (non-reference) values left in its locals.
+    Return { locals: Vec<Value> },
+    /// A resource was loaded from global storage.
+    LoadResource {
+        address: AccountAddress,
+        ty: String,
+        value: Option<Value>,
+        bytes: u64,
+    },
+}
+
+struct TraceFrame {
+    function: String,
+    pc: CodeOffset,
+}
+
+impl TraceFrame {
+    fn new(function: String) -> Self {
+        Self { function, pc: 0 }
+    }
+}
+
+/// A gas meter adapter which records an `ExecutionTrace` while delegating all charges to the
+/// underlying gas meter.
+///
+/// The VM does not expose the program counter to the gas meter, so bytecode offsets are tracked
+/// by following the control flow: every charged instruction advances the offset by one, while
+/// branches and calls are followed explicitly.
+pub struct ExecutionTracer<G> {
+    base: G,
+    frames: Vec<TraceFrame>,
+    steps: Vec<TraceStep>,
+}
+
+impl<G> ExecutionTracer<G> {
+    pub fn new_script(base: G) -> Self {
+        Self {
+            base,
+            frames: vec![TraceFrame::new("script".to_string())],
+            steps: vec![],
+        }
+    }
+
+    pub fn new_function(
+        base: G,
+        module_id: ModuleId,
+        func_name: Identifier,
+        ty_args: Vec<TypeTag>,
+    ) -> Self {
+        let function = function_name(&module_id, func_name.as_str());
+        let mut tracer = Self {
+            base,
+            frames: vec![TraceFrame::new(function.clone())],
+            steps: vec![],
+        };
+        tracer.push_step(None, 0.into(), TraceEvent::Call {
+            function,
+            ty_args: ty_args.iter().map(|ty| ty.to_string()).collect(),
+            args: vec![],
+        });
+        tracer
+    }
+
+    pub fn finish(self) -> ExecutionTrace {
+        ExecutionTrace { steps: self.steps }
+    }
+
+    fn push_step(&mut self, offset: Option<CodeOffset>, gas: InternalGas, event: TraceEvent) {
+        let (depth, function) = match self.frames.last() {
+            Some(frame) => (self.frames.len() - 1, frame.function.clone()),
+            None => (0, String::new()),
+        };
+        self.steps.push(TraceStep {
+            depth,
+            function,
+            offset,
+            gas: gas.into(),
+            event,
+        });
+    }
+
+    /// Records an instruction at the current offset and moves on to the next one.
+    fn record_instruction(
+        &mut self,
+        op: Opcodes,
+        ty: Option<TypeTag>,
+        values: Vec<Value>,
+        gas: InternalGas,
+    ) {
+        let offset = self.frames.last().map(|frame| frame.pc);
+        self.push_step(offset, gas, TraceEvent::Instruction {
+            op: format!("{:?}", op),
+            ty: ty.map(|ty| ty.to_string()),
+            values,
+        });
+        if let Some(frame) = self.frames.last_mut() {
+            frame.pc += 1;
+        }
+    }
+
+    fn jump(&mut self, target_offset: Option<CodeOffset>) {
+        if let (Some(offset), Some(frame)) = (target_offset, self.frames.last_mut()) {
+            frame.pc = offset;
+        }
+    }
+
+    fn record_call(
+        &mut self,
+        op: Opcodes,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: Vec<TypeTag>,
+        args: Vec<Value>,
+        gas: InternalGas,
+    ) {
+        let function = function_name(module_id, func_name);
+        self.record_instruction(op, None, vec![], gas);
+        self.push_step(None, 0.into(), TraceEvent::Call {
+            function: function.clone(),
+            ty_args: ty_args.iter().map(|ty| ty.to_string()).collect(),
+            args,
+        });
+        self.frames.push(TraceFrame::new(function));
+    }
+}
+
+impl<G> ExecutionTracer<G>
+where
+    G: GasMeter,
+{
+    /// Delegate the charging call to the base gas meter and measure variation in balance.
+    fn delegate_charge<F, R>(&mut self, charge: F) -> (InternalGas, R)
+    where
+        F: FnOnce(&mut G) -> R,
+    {
+        let old = self.base.balance_internal();
+        let res = charge(&mut self.base);
+        let new = self.base.balance_internal();
+        let cost = old.checked_sub(new).unwrap_or_else(|| 0.into());
+
+        (cost, res)
+    }
+}
+
+fn function_name(module_id: &ModuleId, func_name: &str) -> String {
+    format!(
+        "{}::{}::{}",
+        module_id.address().to_hex_literal(),
+        module_id.name(),
+        func_name
+    )
+}
+
+fn render(val: impl ValueView) -> Value {
+    let mut renderer = ValueRenderer::default();
+    val.visit(&mut renderer);
+    renderer.finish()
+}
+
+fn render_all(vals: impl Iterator<Item = impl ValueView>) -> Vec<Value> {
+    vals.map(render).collect()
+}
+
+/// Renders a Move value as JSON. Structs and vectors become arrays (field names are not
+/// available to the gas meter), `vector<u8>` becomes a hex string, integers wider than 64 bits
+/// become strings and references are wrapped as `{"ref": ..}`.
+#[derive(Default)]
+struct ValueRenderer {
+    /// Containers which are still being visited, with the number of elements left to visit.
+    stack: Vec<(Vec<Value>, usize, bool)>,
+    result: Option<Value>,
+}
+
+impl ValueRenderer {
+    fn push_container(&mut self, len: usize, is_ref: bool) {
+        if len == 0 {
+            self.add(Value::Array(vec![]));
+        } else {
+            self.stack.push((Vec::with_capacity(len), len, is_ref));
+        }
+    }
+
+    fn add(&mut self, val: Value) {
+        let mut val = val;
+        loop {
+            match self.stack.last_mut() {
+                None => {
+                    self.result = Some(val);
+                    return;
+                },
+                Some((elems, remaining, _)) => {
+                    elems.push(val);
+                    *remaining -= 1;
+                    if *remaining != 0 {
+                        return;
+                    }
+                },
+            }
+            let (mut elems, _, is_ref) = self.stack.pop().expect("container must exist");
+            val = if is_ref {
+                serde_json::json!({ "ref": elems.pop().expect("reference must have a target") })
+            } else {
+                Value::Array(elems)
+            };
+        }
+    }
+
+    fn finish(self) -> Value {
+        self.result.unwrap_or(Value::Null)
+    }
+}
+
+impl ValueVisitor for ValueRenderer {
+    fn visit_u8(&mut self, _depth: usize, val: u8) {
+        self.add(val.into());
+    }
+
+    fn visit_u16(&mut self, _depth: usize, val: u16) {
+        self.add(val.into());
+    }
+
+    fn visit_u32(&mut self, _depth: usize, val: u32) {
+        self.add(val.into());
+    }
+
+    fn visit_u64(&mut self, _depth: usize, val: u64) {
+        self.add(val.into());
+    }
+
+    fn visit_u128(&mut self, _depth: usize, val: u128) {
+        self.add(val.to_string().into());
+    }
+
+    fn visit_u256(&mut self, _depth: usize, val: U256) {
+        self.add(val.to_string().into());
+    }
+
+    fn visit_bool(&mut self, _depth: usize, val: bool) {
+        self.add(val.into());
+    }
+
+    fn visit_address(&mut self, _depth: usize, val: AccountAddress) {
+        self.add(val.to_hex_literal().into());
+    }
+
+    fn visit_struct(&mut self, _depth: usize, len: usize) -> bool {
+        self.push_container(len, false);
+        true
+    }
+
+    fn visit_vec(&mut self, _depth: usize, len: usize) -> bool {
+        self.push_container(len, false);
+        true
+    }
+
+    fn visit_ref(&mut self, _depth: usize, _is_global: bool) -> bool {
+        self.push_container(1, true);
+        true
+    }
+
+    fn visit_vec_u8(&mut self, _depth: usize, vals: &[u8]) {
+        self.add(format!("0x{}", hex::encode(vals)).into());
+    }
+
+    fn visit_vec_u16(&mut self, _depth: usize, vals: &[u16]) {
+        self.add(vals.into());
+    }
+
+    fn visit_vec_u32(&mut self, _depth: usize, vals: &[u32]) {
+        self.add(vals.into());
+    }
+
+    fn visit_vec_u64(&mut self, _depth: usize, vals: &[u64]) {
+        self.add(vals.into());
+    }
+
+    fn visit_vec_u128(&mut self, _depth: usize, vals: &[u128]) {
+        self.add(
+            vals.iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .into(),
+        );
+    }
+
+    fn visit_vec_u256(&mut self, _depth: usize, vals: &[U256]) {
+        self.add(
+            vals.iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .into(),
+        );
+    }
+
+    fn visit_vec_bool(&mut self, _depth: usize, vals: &[bool]) {
+        self.add(vals.into());
+    }
+
+    fn visit_vec_address(&mut self, _depth: usize, vals: &[AccountAddress]) {
+        self.add(
+            vals.iter()
+                .map(|v| v.to_hex_literal())
+                .collect::<Vec<_>>()
+                .into(),
+        );
+    }
+}
+
+macro_rules! trace_instruction {
+    ($(
+        [$op: expr, $ty: expr, $values: expr]
+        fn $fn: ident $(<$($lt: lifetime),*>)? (&mut self $(, $arg: ident : $arg_ty: ty)* $(,)?) -> PartialVMResult<()>;
+    )*) => {
+        $(fn $fn $(<$($lt)*>)? (&mut self, $($arg: $arg_ty),*) -> PartialVMResult<()> {
+            #[allow(unused)]
+            use Opcodes::*;
+
+            let op = $op;
+            let ty: Option<TypeTag> = $ty;
+            let values: Vec<Value> = $values;
+            let (cost, res) = self.delegate_charge(|base| base.$fn($($arg),*));
+            self.record_instruction(op, ty, values, cost);
+
+            res
+        })*
+    };
+}
+
+impl<G> GasMeter for ExecutionTracer<G>
+where
+    G: GasMeter,
+{
+    delegate_mut! {
+        fn charge_ld_const_after_deserialization(&mut self, val: impl ValueView)
+            -> PartialVMResult<()>;
+
+        fn charge_native_function_before_execution(
+            &mut self,
+            ty_args: impl ExactSizeIterator<Item = impl TypeView> + Clone,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+    }
+
+    trace_instruction! {
+        [POP, None, vec![render(&popped_val)]]
+        fn charge_pop(&mut self, popped_val: impl ValueView) -> PartialVMResult<()>;
+
+        [LD_CONST, None, vec![]]
+        fn charge_ld_const(&mut self, size: NumBytes) -> PartialVMResult<()>;
+
+        [COPY_LOC, None, vec![render(&val)]]
+        fn charge_copy_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        [MOVE_LOC, None, vec![render(&val)]]
+        fn charge_move_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        [ST_LOC, None, vec![render(&val)]]
+        fn charge_store_loc(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        [if is_generic { PACK_GENERIC } else { PACK }, None, render_all(args.clone())]
+        fn charge_pack(
+            &mut self,
+            is_generic: bool,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        [if is_generic { UNPACK_GENERIC } else { UNPACK }, None, render_all(args.clone())]
+        fn charge_unpack(
+            &mut self,
+            is_generic: bool,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        [READ_REF, None, vec![render(&val)]]
+        fn charge_read_ref(&mut self, val: impl ValueView) -> PartialVMResult<()>;
+
+        [WRITE_REF, None, vec![render(&new_val), render(&old_val)]]
+        fn charge_write_ref(
+            &mut self,
+            new_val: impl ValueView,
+            old_val: impl ValueView,
+        ) -> PartialVMResult<()>;
+
+        [EQ, None, vec![render(&lhs), render(&rhs)]]
+        fn charge_eq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()>;
+
+        [NEQ, None, vec![render(&lhs), render(&rhs)]]
+        fn charge_neq(&mut self, lhs: impl ValueView, rhs: impl ValueView) -> PartialVMResult<()>;
+
+        [
+            match (is_mut, is_generic) {
+                (false, false) => IMM_BORROW_GLOBAL,
+                (false, true) => IMM_BORROW_GLOBAL_GENERIC,
+                (true, false) => MUT_BORROW_GLOBAL,
+                (true, true) => MUT_BORROW_GLOBAL_GENERIC
+            },
+            Some(ty.to_type_tag()),
+            vec![is_success.into()]
+        ]
+        fn charge_borrow_global(
+            &mut self,
+            is_mut: bool,
+            is_generic: bool,
+            ty: impl TypeView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        [
+            if is_generic { EXISTS_GENERIC } else { EXISTS },
+            Some(ty.to_type_tag()),
+            vec![exists.into()]
+        ]
+        fn charge_exists(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            exists: bool,
+        ) -> PartialVMResult<()>;
+
+        [
+            if is_generic { MOVE_FROM_GENERIC } else { MOVE_FROM },
+            Some(ty.to_type_tag()),
+            val.as_ref().map(render).into_iter().collect()
+        ]
+        fn charge_move_from(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            val: Option<impl ValueView>,
+        ) -> PartialVMResult<()>;
+
+        [
+            if is_generic { MOVE_TO_GENERIC } else { MOVE_TO },
+            Some(ty.to_type_tag()),
+            vec![render(&val), is_success.into()]
+        ]
+        fn charge_move_to(
+            &mut self,
+            is_generic: bool,
+            ty: impl TypeView,
+            val: impl ValueView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        [VEC_PACK, Some(ty.to_type_tag()), render_all(args.clone())]
+        fn charge_vec_pack<'a>(
+            &mut self,
+            ty: impl TypeView + 'a,
+            args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        [VEC_LEN, Some(ty.to_type_tag()), vec![]]
+        fn charge_vec_len(&mut self, ty: impl TypeView) -> PartialVMResult<()>;
+
+        [
+            if is_mut { VEC_MUT_BORROW } else { VEC_IMM_BORROW },
+            Some(ty.to_type_tag()),
+            vec![is_success.into()]
+        ]
+        fn charge_vec_borrow(
+            &mut self,
+            is_mut: bool,
+            ty: impl TypeView,
+            is_success: bool,
+        ) -> PartialVMResult<()>;
+
+        [VEC_PUSH_BACK, Some(ty.to_type_tag()), vec![render(&val)]]
+        fn charge_vec_push_back(
+            &mut self,
+            ty: impl TypeView,
+            val: impl ValueView,
+        ) -> PartialVMResult<()>;
+
+        [
+            VEC_POP_BACK,
+            Some(ty.to_type_tag()),
+            val.as_ref().map(render).into_iter().collect()
+        ]
+        fn charge_vec_pop_back(
+            &mut self,
+            ty: impl TypeView,
+            val: Option<impl ValueView>,
+        ) -> PartialVMResult<()>;
+
+        [VEC_UNPACK, Some(ty.to_type_tag()), render_all(elems.clone())]
+        fn charge_vec_unpack(
+            &mut self,
+            ty: impl TypeView,
+            expect_num_elements: NumArgs,
+            elems: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        ) -> PartialVMResult<()>;
+
+        [VEC_SWAP, Some(ty.to_type_tag()), vec![]]
+        fn charge_vec_swap(&mut self, ty: impl TypeView) -> PartialVMResult<()>;
+    }
+
+    fn balance_internal(&self) -> InternalGas {
+        self.base.balance_internal()
+    }
+
+    fn charge_simple_instr(&mut self, instr: SimpleInstruction) -> PartialVMResult<()> {
+        let (cost, res) = self.delegate_charge(|base| base.charge_simple_instr(instr));
+        self.record_instruction(instr.to_opcode(), None, vec![], cost);
+        res
+    }
+
+    fn charge_br_true(&mut self, target_offset: Option<CodeOffset>) -> PartialVMResult<()> {
+        let (cost, res) = self.delegate_charge(|base| base.charge_br_true(target_offset));
+        self.record_instruction(Opcodes::BR_TRUE, None, vec![], cost);
+        self.jump(target_offset);
+        res
+    }
+
+    fn charge_br_false(&mut self, target_offset: Option<CodeOffset>) -> PartialVMResult<()> {
+        let (cost, res) = self.delegate_charge(|base| base.charge_br_false(target_offset));
+        self.record_instruction(Opcodes::BR_FALSE, None, vec![], cost);
+        self.jump(target_offset);
+        res
+    }
+
+    fn charge_branch(&mut self, target_offset: CodeOffset) -> PartialVMResult<()> {
+        let (cost, res) = self.delegate_charge(|base| base.charge_branch(target_offset));
+        self.record_instruction(Opcodes::BRANCH, None, vec![], cost);
+        self.jump(Some(target_offset));
+        res
+    }
+
+    fn charge_call(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        let rendered_args = render_all(args.clone());
+        let (cost, res) =
+            self.delegate_charge(|base| base.charge_call(module_id, func_name, args, num_locals));
+        self.record_call(
+            Opcodes::CALL,
+            module_id,
+            func_name,
+            vec![],
+            rendered_args,
+            cost,
+        );
+        res
+    }
+
+    fn charge_call_generic(
+        &mut self,
+        module_id: &ModuleId,
+        func_name: &str,
+        ty_args: impl ExactSizeIterator<Item = impl TypeView> + Clone,
+        args: impl ExactSizeIterator<Item = impl ValueView> + Clone,
+        num_locals: NumArgs,
+    ) -> PartialVMResult<()> {
+        let ty_tags = ty_args
+            .clone()
+            .map(|ty| ty.to_type_tag())
+            .collect::<Vec<_>>();
+        let rendered_args = render_all(args.clone());
+        let (cost, res) = self.delegate_charge(|base| {
+            base.charge_call_generic(module_id, func_name, ty_args, args, num_locals)
+        });
+        self.record_call(
+            Opcodes::CALL_GENERIC,
+            module_id,
+            func_name,
+            ty_tags,
+            rendered_args,
+            cost,
+        );
+        res
+    }
+
+    fn charge_native_function(
+        &mut self,
+        amount: InternalGas,
+        ret_vals: Option<impl ExactSizeIterator<Item = impl ValueView> + Clone>,
+    ) -> PartialVMResult<()> {
+        let return_values = ret_vals
+            .as_ref()
+            .map(|vals| render_all(vals.clone()))
+            .unwrap_or_default();
+        let (cost, res) =
+            self.delegate_charge(|base| base.charge_native_function(amount, ret_vals));
+        self.push_step(None, cost, TraceEvent::NativeReturn { return_values });
+        self.frames.pop();
+        res
+    }
+
+    fn charge_drop_frame(
+        &mut self,
+        locals: impl Iterator<Item = impl ValueView> + Clone,
+    ) -> PartialVMResult<()> {
+        let rendered_locals = render_all(locals.clone());
+        let (cost, res) = self.delegate_charge(|base| base.charge_drop_frame(locals));
+        self.push_step(None, cost, TraceEvent::Return {
+            locals: rendered_locals,
+        });
+        self.frames.pop();
+        res
+    }
+
+    fn charge_load_resource(
+        &mut self,
+        addr: AccountAddress,
+        ty: impl TypeView,
+        val: Option<impl ValueView>,
+        bytes_loaded: NumBytes,
+    ) -> PartialVMResult<()> {
+        let ty_tag = ty.to_type_tag();
+        let value = val.as_ref().map(render);
+        let (cost, res) =
+            self.delegate_charge(|base| base.charge_load_resource(addr, ty, val, bytes_loaded));
+        self.push_step(None, cost, TraceEvent::LoadResource {
+            address: addr,
+            ty: ty_tag.to_string(),
+            value,
+            bytes: bytes_loaded.into(),
+        });
+        res
+    }
+}
+
+impl<G> AptosGasMeter for ExecutionTracer<G>
+where
+    G: AptosGasMeter,
+{
+    type Algebra = G::Algebra;
+
+    delegate! {
+        fn algebra(&self) -> &Self::Algebra;
+
+        fn storage_fee_for_state_slot(&self, op: &WriteOp) -> Fee;
+
+        fn storage_fee_refund_for_state_slot(&self, op: &WriteOp) -> Fee;
+
+        fn storage_fee_for_state_bytes(&self, key: &StateKey, maybe_value_size: Option<u64>) -> Fee;
+
+        fn storage_fee_per_event(&self, event: &ContractEvent) -> Fee;
+
+        fn storage_discount_for_events(&self, total_cost: Fee) -> Fee;
+
+        fn storage_fee_for_transaction_storage(&self, txn_size: NumBytes) -> Fee;
+    }
+
+    delegate_mut! {
+        fn algebra_mut(&mut self) -> &mut Self::Algebra;
+
+        fn charge_io_gas_for_write(&mut self, key: &StateKey, op: &WriteOp) -> VMResult<()>;
+
+        fn charge_io_gas_for_group_write(&mut self, key: &StateKey, group_write: &GroupWrite) -> VMResult<()>;
+
+        fn charge_storage_fee(
+            &mut self,
+            amount: Fee,
+            gas_unit_price: FeePerGasUnit,
+        ) -> PartialVMResult<()>;
+
+        fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()>;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use move_vm_types::values::{Struct, Value as MoveValue};
+
+    #[test]
+    fn test_render_values() {
+        assert_eq!(render(&MoveValue::u64(7)), serde_json::json!(7));
+        assert_eq!(render(&MoveValue::u128(7)), serde_json::json!("7"));
+        assert_eq!(
+            render(&MoveValue::vector_u8(vec![0xAB, 0xCD])),
+            serde_json::json!("0xabcd")
+        );
+        assert_eq!(
+            render(&MoveValue::struct_(Struct::pack(vec![
+                MoveValue::bool(true),
+                MoveValue::struct_(Struct::pack(vec![])),
+                MoveValue::address(AccountAddress::ONE),
+            ]))),
+            serde_json::json!([true, [], "0x1"])
+        );
+    }
+}
@@ -39,6 +39,8 @@ pub struct GasProfiler<G> {
 }
 
 // TODO: consider switching to a library like https://docs.rs/delegate/latest/delegate/.
+/// Implements methods of a gas meter wrapper by forwarding them to its `base` gas meter.
+#[macro_export]
 macro_rules! delegate {
     ($(
         fn $fn: ident $(<$($lt: lifetime),*>)? (&self $(, $arg: ident : $ty: ty)* $(,)?) -> $ret_ty: ty;
@@ -49,6 +51,8 @@ macro_rules! delegate {
     };
 }
 
+/// Same as `delegate!`, for methods taking `&mut self`.
+#[macro_export]
 macro_rules! delegate_mut {
     ($(
         fn $fn: ident $(<$($lt: lifetime),*>)? (&mut self $(, $arg: ident : $ty: ty)* $(,)?) -> $ret_ty: ty;
@@ -0,0 +1,857 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Dry runs of a release against the state of a real network.
+//!
+//! The proposal scripts of the release are generated and compiled as they would be for
+//! submission, and are then executed one by one on top of a DB checkpoint (or the state served
+//! by a REST endpoint) without ever committing anything. For multi-step proposals, a governance
+//! proposal with the hash of the first script is created and voted through directly in the
+//! state, so the scripts go through the real `aptos_governance::resolve_multi_step_proposal`
+//! path (including the execution hash chain and the reconfigurations they trigger).
+
+use crate::{
+    aptos_framework_path,
+    components::{feature_flags::FeatureFlag, Proposal},
+    validate::get_proposal_script_paths,
+    ExecutionMode, ReleaseConfig,
+};
+use anyhow::{anyhow, Context, Result};
+use aptos::governance::GenerateExecutionHash;
+use aptos_crypto::HashValue;
+use aptos_debugger::run_session_with_id;
+use aptos_framework::natives::code::{PackageRegistry, PublishRequest};
+use aptos_rest_client::Client;
+use aptos_state_view::{StateViewId, TStateView};
+use aptos_temppath::TempPath;
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_config::CORE_CODE_ADDRESS,
+    chain_id::ChainId,
+    contract_event::ContractEvent,
+    on_chain_config::{
+        new_epoch_event_key, ConfigurationResource, CurrentTimeMicroseconds,
+        FeatureFlag as AptosFeatureFlag, Features, GasScheduleV2, OnChainConfig,
+        OnChainConsensusConfig, OnChainExecutionConfig, Version as OnChainVersion,
+    },
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+    },
+    transaction::Version,
+    vm_status::VMStatus,
+    write_set::TransactionWrite,
+};
+use aptos_validator_interface::{
+    AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
+};
+use aptos_vm::{
+    data_cache::AsMoveResolver,
+    move_vm_ext::{SessionExt, SessionId},
+};
+use move_binary_format::{compatibility::Compatibility, errors::VMResult};
+use move_core_types::{
+    ident_str,
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag, TypeTag},
+    move_resource::MoveStructType,
+    value::{MoveStruct, MoveValue},
+};
+use move_vm_types::gas::UnmeteredGasMeter;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use strum::IntoEnumIterator;
+use url::Url;
+
+/// The addresses the framework packages are published under.
+const FRAMEWORK_PACKAGE_ADDRESSES: [&str; 3] = ["0x1", "0x3", "0x4"];
+
+/// The address of the core resources account, which signs root signer (testnet) proposals.
+const CORE_RESOURCES_ADDRESS: &str = "0xa550c18";
+
+/// The time (in microseconds) between two steps, as if each ran in its own block.
+const STEP_INTERVAL_USECS: u64 = 1_000_000;
+
+/// The state a dry run is executed against.
+#[derive(Clone, Debug)]
+pub enum DryRunSource {
+    /// A DB checkpoint (or a stopped node's DB) of the network.
+    Db(PathBuf),
+    /// The state served by the REST endpoint of a node of the network.
+    Rest(Url),
+}
+
+/// The outcome of a single proposal script.
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunStep {
+    pub proposal: String,
+    pub script: String,
+    /// The status the script aborted with, if it failed.
+    pub abort: Option<String>,
+    /// Whether the script started a new epoch.
+    pub reconfigured: bool,
+}
+
+/// A change to one of the on-chain configs, as YAML.
+#[derive(Clone, Debug, Serialize)]
+pub struct ConfigChange {
+    pub config: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+/// A change to an entry of the gas schedule.
+#[derive(Clone, Debug, Serialize)]
+pub struct GasScheduleChange {
+    pub name: String,
+    pub before: Option<u64>,
+    pub after: Option<u64>,
+}
+
+/// The changes to a framework package.
+#[derive(Clone, Debug, Serialize)]
+pub struct PackageChange {
+    pub address: AccountAddress,
+    pub name: String,
+    pub upgrade_number_before: Option<u64>,
+    pub upgrade_number_after: Option<u64>,
+    pub added_modules: Vec<String>,
+    pub removed_modules: Vec<String>,
+    pub modified_modules: Vec<String>,
+}
+
+/// Everything a release changed (or failed to change) during a dry run.
+#[derive(Clone, Debug, Serialize)]
+pub struct DryRunReport {
+    /// The version of the state the dry run was executed on top of.
+    pub version: Version,
+    pub epoch_before: u64,
+    pub epoch_after: u64,
+    /// The executed scripts, in order. Execution stops at the first abort.
+    pub steps: Vec<DryRunStep>,
+    pub config_changes: Vec<ConfigChange>,
+    pub enabled_features: Vec<FeatureFlag>,
+    pub disabled_features: Vec<FeatureFlag>,
+    pub gas_feature_version_before: Option<u64>,
+    pub gas_feature_version_after: Option<u64>,
+    pub gas_schedule_changes: Vec<GasScheduleChange>,
+    pub package_changes: Vec<PackageChange>,
+}
+
+impl DryRunReport {
+    /// The first step that aborted, if any.
+    pub fn first_abort(&self) -> Option<&DryRunStep> {
+        self.steps.iter().find(|step| step.abort.is_some())
+    }
+}
+
+impl fmt::Display for DryRunReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Dry run on top of version {}", self.version)?;
+        writeln!(f, "Epoch: {} -> {}", self.epoch_before, self.epoch_after)?;
+
+        writeln!(f, "\nSteps:")?;
+        for step in &self.steps {
+            let outcome = match &step.abort {
+                Some(abort) => format!("ABORTED: {}", abort),
+                None if step.reconfigured => "OK (reconfiguration)".to_string(),
+                None => "OK".to_string(),
+            };
+            writeln!(f, "  {}/{}: {}", step.proposal, step.script, outcome)?;
+        }
+
+        if !self.config_changes.is_empty() {
+            writeln!(f, "\nOn-chain config changes:")?;
+            for change in &self.config_changes {
+                writeln!(f, "  {}", change.config)?;
+                writeln!(
+                    f,
+                    "    before: {}",
+                    change.before.as_deref().unwrap_or("none")
+                )?;
+                writeln!(
+                    f,
+                    "    after: {}",
+                    change.after.as_deref().unwrap_or("none")
+                )?;
+            }
+        }
+
+        if !self.enabled_features.is_empty() || !self.disabled_features.is_empty() {
+            writeln!(f, "\nFeature flags:")?;
+            for feature in &self.enabled_features {
+                writeln!(f, "  + {:?}", feature)?;
+            }
+            for feature in &self.disabled_features {
+                writeln!(f, "  - {:?}", feature)?;
+            }
+        }
+
+        if self.gas_feature_version_before != self.gas_feature_version_after
+            || !self.gas_schedule_changes.is_empty()
+        {
+            writeln!(
+                f,
+                "\nGas schedule (feature version {:?} -> {:?}):",
+                self.gas_feature_version_before, self.gas_feature_version_after
+            )?;
+            for change in &self.gas_schedule_changes {
+                writeln!(
+                    f,
+                    "  {}: {:?} -> {:?}",
+                    change.name, change.before, change.after
+                )?;
+            }
+        }
+
+        if !self.package_changes.is_empty() {
+            writeln!(f, "\nFramework packages:")?;
+            for change in &self.package_changes {
+                writeln!(
+                    f,
+                    "  {}::{} (upgrade number {:?} -> {:?})",
+                    change.address,
+                    change.name,
+                    change.upgrade_number_before,
+                    change.upgrade_number_after
+                )?;
+                for module in &change.added_modules {
+                    writeln!(f, "    + {}", module)?;
+                }
+                for module in &change.removed_modules {
+                    writeln!(f, "    - {}", module)?;
+                }
+                for module in &change.modified_modules {
+                    writeln!(f, "    ~ {}", module)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Generates the proposal scripts of the release and executes them against `source`, at
+/// `version` or at the latest version of the source if none is given. Nothing is committed.
+pub async fn dry_run_release(
+    release_config: &ReleaseConfig,
+    source: DryRunSource,
+    version: Option<Version>,
+) -> Result<DryRunReport> {
+    let interface: Arc<dyn AptosValidatorInterface + Send> = match source {
+        DryRunSource::Db(path) => Arc::new(DBDebuggerInterface::open(path)?),
+        DryRunSource::Rest(url) => Arc::new(RestDebuggerInterface::new(Client::new(url))),
+    };
+    let version = match version {
+        Some(version) => version,
+        None => interface.get_latest_version().await?,
+    };
+
+    // Generate and compile the proposal scripts
+    let scripts_path = TempPath::new();
+    scripts_path.create_as_dir()?;
+    release_config.generate_release_proposal_scripts(scripts_path.path())?;
+
+    // The debugger state view reads the state before the given version.
+    let mut state = DryRunState::new(DebuggerStateView::new(interface, version + 1))?;
+    let snapshot_before = ReleaseSnapshot::take(&state.state_view)?;
+
+    let mut steps = vec![];
+    'proposals: for proposal in &release_config.proposals {
+        let script_paths =
+            get_proposal_script_paths(scripts_path.path(), &release_config.name, proposal)?;
+        let scripts = script_paths
+            .iter()
+            .map(|path| compile_script(path))
+            .collect::<Result<Vec<_>>>()?;
+        let first_script_hash = match scripts.first() {
+            Some((_, hash)) => *hash,
+            None => continue,
+        };
+
+        // Multi-step proposals are approved with the hash of their first script
+        let proposal_id = match proposal.execution_mode {
+            ExecutionMode::MultiStep => match state.create_approved_proposal(first_script_hash)? {
+                Ok(proposal_id) => Some(proposal_id),
+                Err(status) => {
+                    steps.push(DryRunStep {
+                        proposal: proposal.name.clone(),
+                        script: "create_proposal".to_string(),
+                        abort: Some(format!("{:?}", status)),
+                        reconfigured: false,
+                    });
+                    break 'proposals;
+                },
+            },
+            ExecutionMode::RootSigner => None,
+        };
+
+        for (path, (bytecode, hash)) in script_paths.iter().zip(scripts) {
+            let step = state.execute_script(proposal, path, bytecode, hash, proposal_id)?;
+            let aborted = step.abort.is_some();
+            steps.push(step);
+            if aborted {
+                break 'proposals;
+            }
+        }
+    }
+
+    let snapshot_after = ReleaseSnapshot::take(&state.state_view)?;
+    Ok(snapshot_before.diff(&snapshot_after, version, steps))
+}
+
+/// Compiles the script at `path`, returning its bytecode and hash.
+fn compile_script(path: &Path) -> Result<(Vec<u8>, HashValue)> {
+    GenerateExecutionHash {
+        script_path: Some(path.to_path_buf()),
+        framework_local_dir: Some(aptos_framework_path()),
+    }
+    .generate_hash()
+    .map_err(|err| anyhow!("Failed to compile {}: {:?}", path.display(), err))
+}
+
+/// The (uncommitted) state of a dry run.
+struct DryRunState {
+    state_view: DryRunStateView,
+    chain_id: ChainId,
+    next_step: u64,
+}
+
+impl DryRunState {
+    fn new(base: DebuggerStateView) -> Result<Self> {
+        let chain_id = ChainId::fetch_config(&base.as_move_resolver())
+            .ok_or_else(|| anyhow!("Failed to fetch the chain id"))?;
+        Ok(Self {
+            state_view: DryRunStateView {
+                base,
+                writes: HashMap::new(),
+            },
+            chain_id,
+            next_step: 0,
+        })
+    }
+
+    /// Creates a governance proposal for the given execution hash, which can be resolved (i.e.,
+    /// it has passed voting) once the time advances. Returns the id of the proposal.
+    fn create_approved_proposal(
+        &mut self,
+        execution_hash: HashValue,
+    ) -> Result<Result<u64, VMStatus>> {
+        let now_secs = self.now_microseconds()? / 1_000_000;
+        let proposal_type = TypeTag::Struct(Box::new(StructTag {
+            address: CORE_CODE_ADDRESS,
+            module: ident_str!("governance_proposal").to_owned(),
+            name: ident_str!("GovernanceProposal").to_owned(),
+            type_params: vec![],
+        }));
+        let voting = ModuleId::new(CORE_CODE_ADDRESS, ident_str!("voting").to_owned());
+        // Structs without fields, options set to none and empty maps all serialize the same way.
+        let empty_struct = MoveValue::Struct(MoveStruct::new(vec![MoveValue::Bool(false)]));
+        let empty_container = MoveValue::Struct(MoveStruct::new(vec![MoveValue::Vector(vec![])]));
+
+        let mut return_value = None;
+        let outcome = self.execute_session(SessionId::Void, |session| {
+            let return_values = session.execute_function_bypass_visibility(
+                &voting,
+                ident_str!("create_proposal_v2"),
+                vec![proposal_type.clone()],
+                serialize_values(vec![
+                    MoveValue::Address(CORE_CODE_ADDRESS),
+                    MoveValue::Address(CORE_CODE_ADDRESS),
+                    empty_struct.clone(),
+                    MoveValue::vector_u8(execution_hash.to_vec()),
+                    MoveValue::U128(0),
+                    MoveValue::U64(now_secs),
+                    empty_container.clone(),
+                    empty_container,
+                    MoveValue::Bool(true),
+                ]),
+                &mut UnmeteredGasMeter,
+            )?;
+            return_value = return_values
+                .return_values
+                .into_iter()
+                .next()
+                .map(|(bytes, _)| bytes);
+            Ok(())
+        })?;
+        if let Err(status) = outcome {
+            return Ok(Err(status));
+        }
+        let proposal_id: u64 = bcs::from_bytes(
+            &return_value.ok_or_else(|| anyhow!("create_proposal_v2 returned no proposal id"))?,
+        )
+        .context("Failed to decode the proposal id returned by create_proposal_v2")?;
+
+        // A single vote is enough, as the minimum vote threshold is zero
+        let outcome = self.execute_session(SessionId::Void, |session| {
+            session.execute_function_bypass_visibility(
+                &voting,
+                ident_str!("vote"),
+                vec![proposal_type],
+                serialize_values(vec![
+                    empty_struct,
+                    MoveValue::Address(CORE_CODE_ADDRESS),
+                    MoveValue::U64(proposal_id),
+                    MoveValue::U64(1),
+                    MoveValue::Bool(true),
+                ]),
+                &mut UnmeteredGasMeter,
+            )?;
+            Ok(())
+        })?;
+        Ok(outcome.map(|_| proposal_id))
+    }
+
+    /// Executes one proposal script in its own block. Multi-step proposal scripts are resolved
+    /// against `proposal_id`, while root signer scripts are signed by the core resources account.
+    fn execute_script(
+        &mut self,
+        proposal: &Proposal,
+        path: &Path,
+        bytecode: Vec<u8>,
+        hash: HashValue,
+        proposal_id: Option<u64>,
+    ) -> Result<DryRunStep> {
+        let script_name = path
+            .file_stem()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| anyhow!("Invalid script path {}", path.display()))?
+            .to_string();
+        let script_arg = match proposal_id {
+            Some(proposal_id) => MoveValue::U64(proposal_id),
+            None => MoveValue::Signer(AccountAddress::from_hex_literal(CORE_RESOURCES_ADDRESS)?),
+        };
+        let timestamp = self.now_microseconds()? + STEP_INTERVAL_USECS;
+        let treat_friend_as_private = Features::fetch_config(&self.state_view.as_move_resolver())
+            .unwrap_or_default()
+            .is_enabled(AptosFeatureFlag::TREAT_FRIEND_AS_PRIVATE);
+
+        let session_id = SessionId::Txn {
+            sender: CORE_CODE_ADDRESS,
+            sequence_number: self.next_step,
+            script_hash: hash.to_vec(),
+        };
+        self.next_step += 1;
+        let outcome = self.execute_session(session_id, |session| {
+            session.execute_function_bypass_visibility(
+                &ModuleId::new(CORE_CODE_ADDRESS, ident_str!("timestamp").to_owned()),
+                ident_str!("update_global_time"),
+                vec![],
+                serialize_values(vec![
+                    MoveValue::Signer(AccountAddress::ZERO),
+                    MoveValue::Address(CORE_CODE_ADDRESS),
+                    MoveValue::U64(timestamp),
+                ]),
+                &mut UnmeteredGasMeter,
+            )?;
+            session.execute_script(
+                bytecode,
+                vec![],
+                serialize_values(vec![script_arg]),
+                &mut UnmeteredGasMeter,
+            )?;
+
+            // Framework upgrades publish their packages through the code module, which only
+            // registers a request that has to be resolved after the script.
+            if let Some(PublishRequest {
+                destination,
+                bundle,
+                ..
+            }) = session.extract_publish_request()
+            {
+                session.publish_module_bundle_with_compat_config(
+                    bundle.into_inner(),
+                    destination,
+                    &mut UnmeteredGasMeter,
+                    Compatibility::new(true, true, !treat_friend_as_private),
+                )?;
+            }
+            Ok(())
+        })?;
+
+        let new_epoch_event_key = new_epoch_event_key();
+        let (abort, reconfigured) = match outcome {
+            Ok(events) => (
+                None,
+                events
+                    .iter()
+                    .any(|event| event.event_key() == Some(&new_epoch_event_key)),
+            ),
+            Err(status) => (Some(format!("{:?}", status)), false),
+        };
+        Ok(DryRunStep {
+            proposal: proposal.name.clone(),
+            script: script_name,
+            abort,
+            reconfigured,
+        })
+    }
+
+    /// Runs `f` in a new session and keeps its changes, unless it failed, in which case the
+    /// status it failed with is returned.
+    fn execute_session<F>(
+        &mut self,
+        session_id: SessionId,
+        f: F,
+    ) -> Result<Result<Vec<ContractEvent>, VMStatus>>
+    where
+        F: FnOnce(&mut SessionExt) -> VMResult<()>,
+    {
+        let mut failure = None;
+        let change_set =
+            run_session_with_id(&self.state_view, self.chain_id, session_id, |session| {
+                if let Err(error) = f(session) {
+                    failure = Some(error.into_vm_status());
+                }
+                Ok(())
+            })?;
+        if let Some(status) = failure {
+            return Ok(Err(status));
+        }
+
+        let (write_set, events) = change_set
+            .try_materialize_aggregator_v1_delta_set(&self.state_view.as_move_resolver())
+            .and_then(|change_set| change_set.try_into_storage_change_set())
+            .map_err(|status| anyhow!("Failed to materialize the changes: {:?}", status))?
+            .into_inner();
+        for (key, op) in &write_set {
+            self.state_view
+                .writes
+                .insert(key.clone(), op.as_state_value());
+        }
+        Ok(Ok(events))
+    }
+
+    fn now_microseconds(&self) -> Result<u64> {
+        CurrentTimeMicroseconds::fetch_config(&self.state_view.as_move_resolver())
+            .map(|time| time.microseconds)
+            .ok_or_else(|| anyhow!("Failed to fetch the current time"))
+    }
+}
+
+fn serialize_values(values: Vec<MoveValue>) -> Vec<Vec<u8>> {
+    values
+        .into_iter()
+        .map(|value| value.simple_serialize().unwrap())
+        .collect()
+}
+
+/// The changes of a dry run on top of the state it was started from.
+struct DryRunStateView {
+    base: DebuggerStateView,
+    /// The latest value of every key written during the dry run, `None` for deletions.
+    writes: HashMap<StateKey, Option<StateValue>>,
+}
+
+impl TStateView for DryRunStateView {
+    type Key = StateKey;
+
+    fn id(&self) -> StateViewId {
+        self.base.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        match self.writes.get(state_key) {
+            Some(value) => Ok(value.clone()),
+            None => self.base.get_state_value(state_key),
+        }
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.base.get_usage()
+    }
+}
+
+/// A framework package, with the hashes of its modules' bytecode.
+struct PackageSnapshot {
+    upgrade_number: u64,
+    modules: BTreeMap<String, Option<HashValue>>,
+}
+
+/// The parts of the on-chain state a release is expected to change.
+struct ReleaseSnapshot {
+    epoch: u64,
+    configs: BTreeMap<&'static str, Option<String>>,
+    features: Option<Features>,
+    gas_schedule: Option<GasScheduleV2>,
+    packages: BTreeMap<(AccountAddress, String), PackageSnapshot>,
+}
+
+impl ReleaseSnapshot {
+    fn take(state_view: &impl TStateView<Key = StateKey>) -> Result<Self> {
+        let resolver = state_view.as_move_resolver();
+        let epoch = ConfigurationResource::fetch_config(&resolver)
+            .map(|configuration| configuration.epoch())
+            .context("Failed to fetch the current epoch")?;
+
+        let mut configs = BTreeMap::new();
+        configs.insert(
+            "OnChainConsensusConfig",
+            to_yaml(OnChainConsensusConfig::fetch_config(&resolver))?,
+        );
+        configs.insert(
+            "OnChainExecutionConfig",
+            to_yaml(OnChainExecutionConfig::fetch_config(&resolver))?,
+        );
+        configs.insert("Version", to_yaml(OnChainVersion::fetch_config(&resolver))?);
+
+        let mut packages = BTreeMap::new();
+        for address in FRAMEWORK_PACKAGE_ADDRESSES {
+            let address = AccountAddress::from_hex_literal(address)?;
+            let registry_key = StateKey::access_path(AccessPath::resource_access_path(
+                address,
+                PackageRegistry::struct_tag(),
+            )?);
+            let registry = match state_view.get_state_value_bytes(&registry_key)? {
+                Some(bytes) => bcs::from_bytes::<PackageRegistry>(&bytes)?,
+                None => continue,
+            };
+            for package in registry.packages {
+                let mut modules = BTreeMap::new();
+                for module in &package.modules {
+                    let module_id = ModuleId::new(address, Identifier::new(module.name.as_str())?);
+                    let code = state_view.get_state_value_bytes(&StateKey::access_path(
+                        AccessPath::code_access_path(module_id),
+                    ))?;
+                    modules.insert(
+                        module.name.clone(),
+                        code.map(|code| HashValue::sha3_256_of(&code)),
+                    );
+                }
+                packages.insert((address, package.name), PackageSnapshot {
+                    upgrade_number: package.upgrade_number,
+                    modules,
+                });
+            }
+        }
+
+        Ok(Self {
+            epoch,
+            configs,
+            features: Features::fetch_config(&resolver),
+            gas_schedule: GasScheduleV2::fetch_config(&resolver),
+            packages,
+        })
+    }
+
+    fn diff(&self, after: &Self, version: Version, steps: Vec<DryRunStep>) -> DryRunReport {
+        let config_changes = self
+            .configs
+            .iter()
+            .filter_map(|(config, before)| {
+                let after = after.configs.get(config).cloned().flatten();
+                (before != &after).then(|| ConfigChange {
+                    config: config.to_string(),
+                    before: before.clone(),
+                    after,
+                })
+            })
+            .collect();
+
+        let is_enabled = |features: &Option<Features>, flag: &FeatureFlag| {
+            features.as_ref().map_or(false, |features| {
+                features.is_enabled(AptosFeatureFlag::from(flag.clone()))
+            })
+        };
+        let (mut enabled_features, mut disabled_features) = (vec![], vec![]);
+        for flag in FeatureFlag::iter() {
+            match (
+                is_enabled(&self.features, &flag),
+                is_enabled(&after.features, &flag),
+            ) {
+                (false, true) => enabled_features.push(flag),
+                (true, false) => disabled_features.push(flag),
+                _ => (),
+            }
+        }
+
+        let gas_entries = |gas_schedule: &Option<GasScheduleV2>| -> BTreeMap<String, u64> {
+            gas_schedule
+                .as_ref()
+                .map(|gas_schedule| gas_schedule.entries.iter().cloned().collect())
+                .unwrap_or_default()
+        };
+        let (entries_before, entries_after) = (
+            gas_entries(&self.gas_schedule),
+            gas_entries(&after.gas_schedule),
+        );
+        let gas_schedule_changes = entries_before
+            .keys()
+            .chain(entries_after.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|name| {
+                let (before, after) = (
+                    entries_before.get(name).copied(),
+                    entries_after.get(name).copied(),
+                );
+                (before != after).then(|| GasScheduleChange {
+                    name: name.clone(),
+                    before,
+                    after,
+                })
+            })
+            .collect();
+
+        let package_changes = self
+            .packages
+            .keys()
+            .chain(after.packages.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter_map(|key| {
+                let (before, after) = (self.packages.get(key), after.packages.get(key));
+                let no_modules = BTreeMap::new();
+                let modules_before = before.map_or(&no_modules, |package| &package.modules);
+                let modules_after = after.map_or(&no_modules, |package| &package.modules);
+                let change = PackageChange {
+                    address: key.0,
+                    name: key.1.clone(),
+                    upgrade_number_before: before.map(|package| package.upgrade_number),
+                    upgrade_number_after: after.map(|package| package.upgrade_number),
+                    added_modules: modules_after
+                        .keys()
+                        .filter(|module| !modules_before.contains_key(*module))
+                        .cloned()
+                        .collect(),
+                    removed_modules: modules_before
+                        .keys()
+                        .filter(|module| !modules_after.contains_key(*module))
+                        .cloned()
+                        .collect(),
+                    modified_modules: modules_before
+                        .iter()
+                        .filter(|(module, hash)| {
+                            modules_after
+                                .get(*module)
+                                .map_or(false, |hash_after| hash_after != *hash)
+                        })
+                        .map(|(module, _)| module.clone())
+                        .collect(),
+                };
+                let changed = change.upgrade_number_before != change.upgrade_number_after
+                    || !change.added_modules.is_empty()
+                    || !change.removed_modules.is_empty()
+                    || !change.modified_modules.is_empty();
+                changed.then_some(change)
+            })
+            .collect();
+
+        DryRunReport {
+            version,
+            epoch_before: self.epoch,
+            epoch_after: after.epoch,
+            steps,
+            config_changes,
+            enabled_features,
+            disabled_features,
+            gas_feature_version_before: self
+                .gas_schedule
+                .as_ref()
+                .map(|gas_schedule| gas_schedule.feature_version),
+            gas_feature_version_after: after
+                .gas_schedule
+                .as_ref()
+                .map(|gas_schedule| gas_schedule.feature_version),
+            gas_schedule_changes,
+            package_changes,
+        }
+    }
+}
+
+fn to_yaml<T: Serialize>(config: Option<T>) -> Result<Option<String>> {
+    config
+        .map(|config| serde_yaml::to_string(&config).map(|yaml| yaml.trim_end().to_string()))
+        .transpose()
+        .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(
+        gas_entries: Vec<(&str, u64)>,
+        modules: Vec<(&str, &[u8])>,
+        upgrade_number: u64,
+    ) -> ReleaseSnapshot {
+        let modules = modules
+            .into_iter()
+            .map(|(name, code)| (name.to_string(), Some(HashValue::sha3_256_of(code))))
+            .collect();
+        ReleaseSnapshot {
+            epoch: upgrade_number,
+            configs: BTreeMap::from([("Version", Some(format!("major: {}", upgrade_number)))]),
+            features: Some(Features::default()),
+            gas_schedule: Some(GasScheduleV2 {
+                feature_version: upgrade_number,
+                entries: gas_entries
+                    .into_iter()
+                    .map(|(name, value)| (name.to_string(), value))
+                    .collect(),
+            }),
+            packages: BTreeMap::from([(
+                (AccountAddress::ONE, "AptosFramework".to_string()),
+                PackageSnapshot {
+                    upgrade_number,
+                    modules,
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_snapshot_diff() {
+        let before = snapshot(
+            vec![("txn.gas_unit_scaling_factor", 1), ("txn.min_price", 100)],
+            vec![("coin", b"coin"), ("account", b"account")],
+            1,
+        );
+        let mut after = snapshot(
+            vec![("txn.gas_unit_scaling_factor", 2), ("txn.max_price", 200)],
+            vec![
+                ("coin", b"coin_v2"),
+                ("account", b"account"),
+                ("object", b"object"),
+            ],
+            2,
+        );
+        // MODULE_EVENT is bit 26, which is off by default
+        after.features.as_mut().unwrap().features[3] |= 1 << 2;
+
+        let report = before.diff(&after, 10, vec![]);
+        assert_eq!((report.epoch_before, report.epoch_after), (1, 2));
+        assert_eq!(report.config_changes.len(), 1);
+        assert_eq!(report.config_changes[0].config, "Version");
+        assert_eq!(report.enabled_features, vec![FeatureFlag::ModuleEvent]);
+        assert!(report.disabled_features.is_empty());
+
+        let gas_changes: Vec<_> = report
+            .gas_schedule_changes
+            .iter()
+            .map(|change| (change.name.as_str(), change.before, change.after))
+            .collect();
+        assert_eq!(gas_changes, vec![
+            ("txn.gas_unit_scaling_factor", Some(1), Some(2)),
+            ("txn.max_price", None, Some(200)),
+            ("txn.min_price", Some(100), None),
+        ]);
+
+        assert_eq!(report.package_changes.len(), 1);
+        let package_change = &report.package_changes[0];
+        assert_eq!(package_change.added_modules, vec!["object".to_string()]);
+        assert!(package_change.removed_modules.is_empty());
+        assert_eq!(package_change.modified_modules, vec!["coin".to_string()]);
+
+        // Nothing changes between identical snapshots
+        let report = before.diff(&before, 10, vec![]);
+        assert!(report.config_changes.is_empty());
+        assert!(report.gas_schedule_changes.is_empty());
+        assert!(report.package_changes.is_empty());
+    }
+}
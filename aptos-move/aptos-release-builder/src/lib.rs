@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod components;
+pub mod dry_run;
 mod utils;
 pub mod validate;
 
@@ -1,11 +1,12 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::Context;
+use anyhow::{bail, Context};
 use aptos_crypto::{ed25519::Ed25519PrivateKey, ValidCryptoMaterialStringExt};
 use aptos_framework::natives::code::PackageRegistry;
 use aptos_release_builder::{
     components::fetch_config,
+    dry_run::{dry_run_release, DryRunSource},
     initialize_aptos_core_path,
     validate::{DEFAULT_RESOLUTION_TIME, FAST_RESOLUTION_TIME},
 };
@@ -53,6 +54,26 @@ pub enum Commands {
         #[clap(long)]
         mint_to_validator: bool,
     },
+    /// Execute the governance proposals generated from a given release config against the state
+    /// of a real network, without submitting anything, and report what they change.
+    DryRunProposals {
+        /// Path to the config to be released.
+        #[clap(short, long)]
+        release_config: PathBuf,
+        /// Path to a DB checkpoint of the network to run the proposals against.
+        #[clap(long, group = "source")]
+        db_dir: Option<PathBuf>,
+        /// Url endpoint of the network to run the proposals against, whose state is forked
+        /// lazily. e.g: https://fullnode.mainnet.aptoslabs.com/v1.
+        #[clap(long, group = "source")]
+        endpoint: Option<url::Url>,
+        /// Version of the network state to run the proposals on top of. Defaults to the latest.
+        #[clap(long)]
+        version: Option<u64>,
+        /// Set this value to also write the report as JSON.
+        #[clap(long)]
+        output_path: Option<PathBuf>,
+    },
     /// Print out current values of on chain configs.
     PrintConfigs {
         /// Url endpoint for the desired network. e.g: https://fullnode.mainnet.aptoslabs.com/v1.
@@ -197,6 +218,31 @@ async fn main() -> anyhow::Result<()> {
                 .await?;
             Ok(())
         },
+        Commands::DryRunProposals {
+            release_config,
+            db_dir,
+            endpoint,
+            version,
+            output_path,
+        } => {
+            let config =
+                aptos_release_builder::ReleaseConfig::load_config(release_config.as_path())?;
+            let source = match (db_dir, endpoint) {
+                (Some(db_dir), _) => DryRunSource::Db(db_dir),
+                (None, Some(endpoint)) => DryRunSource::Rest(endpoint),
+                (None, None) => bail!("Either --db-dir or --endpoint must be provided"),
+            };
+
+            let report = dry_run_release(&config, source, version).await?;
+            println!("{}", report);
+            if let Some(output_path) = output_path {
+                std::fs::write(output_path, serde_json::to_string_pretty(&report)?)?;
+            }
+            if let Some(step) = report.first_abort() {
+                bail!("Proposal {} aborted at {}", step.proposal, step.script);
+            }
+            Ok(())
+        },
         Commands::PrintConfigs {
             endpoint,
             print_gas_schedule,
@@ -1,7 +1,11 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{aptos_framework_path, components::ProposalMetadata, ExecutionMode, ReleaseConfig};
+use crate::{
+    aptos_framework_path,
+    components::{Proposal, ProposalMetadata},
+    ExecutionMode, ReleaseConfig,
+};
 use anyhow::Result;
 use aptos::{
     common::types::CliCommand,
@@ -405,24 +409,8 @@ async fn execute_release(
 
     // Execute proposals
     for proposal in &release_config.proposals {
-        let mut proposal_path = proposal_folder.to_path_buf();
-        proposal_path.push("sources");
-        proposal_path.push(&release_config.name);
-        proposal_path.push(proposal.name.as_str());
-
-        let mut script_paths: Vec<PathBuf> = std::fs::read_dir(proposal_path.as_path())?
-            .filter_map(|entry| entry.ok())
-            .filter_map(|entry| {
-                let path = entry.path();
-                if path.extension().map(|s| s == "move").unwrap_or(false) {
-                    Some(path)
-                } else {
-                    None
-                }
-            })
-            .collect();
-
-        script_paths.sort();
+        let script_paths =
+            get_proposal_script_paths(proposal_folder, &release_config.name, proposal)?;
 
         match proposal.execution_mode {
             ExecutionMode::MultiStep => {
@@ -472,6 +460,34 @@ async fn execute_release(
     Ok(())
 }
 
+/// Returns the paths of the scripts generated for the proposal (under `proposal_folder`), in
+/// the order they have to be executed.
+pub(crate) fn get_proposal_script_paths(
+    proposal_folder: &Path,
+    release_name: &str,
+    proposal: &Proposal,
+) -> Result<Vec<PathBuf>> {
+    let mut proposal_path = proposal_folder.to_path_buf();
+    proposal_path.push("sources");
+    proposal_path.push(release_name);
+    proposal_path.push(proposal.name.as_str());
+
+    let mut script_paths: Vec<PathBuf> = std::fs::read_dir(proposal_path.as_path())?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().map(|s| s == "move").unwrap_or(false) {
+                Some(path)
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    script_paths.sort();
+    Ok(script_paths)
+}
+
 pub async fn validate_config(
     release_config: ReleaseConfig,
     network_config: NetworkConfig,
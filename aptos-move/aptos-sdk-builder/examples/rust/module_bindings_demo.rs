@@ -0,0 +1,45 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+mod bindings;
+
+use bindings::{coin::CoinInfo, option::Option, MoveVector, U128, U64};
+
+fn demo_json() {
+    // Resources as returned by the REST API in JSON
+    let coin_info: CoinInfo = serde_json::from_str(
+        r#"{ "name": "Aptos Coin", "decimals": 8, "supply": { "vec": ["18446744073709551616"] } }"#,
+    )
+    .unwrap();
+    assert_eq!(coin_info.name, "Aptos Coin");
+    assert_eq!(coin_info.decimals, 8);
+    assert_eq!(coin_info.supply.vec, MoveVector(vec![U128(1 << 64)]));
+
+    let numbers: Option<U64> = serde_json::from_str(r#"{ "vec": ["5"] }"#).unwrap();
+    assert_eq!(numbers.vec, MoveVector(vec![U64(5)]));
+
+    // A vector of a type parameter instantiated with u8 is a hex string
+    let bytes: Option<u8> = serde_json::from_str(r#"{ "vec": "0x0102" }"#).unwrap();
+    assert_eq!(bytes.vec, MoveVector(vec![1, 2]));
+    assert_eq!(
+        serde_json::to_string(&bytes).unwrap(),
+        r#"{"vec":"0x0102"}"#
+    );
+}
+
+fn demo_bcs() {
+    let bytes = Option {
+        vec: MoveVector(vec![1u8, 2]),
+    };
+    let output = bcs::to_bytes(&bytes).unwrap();
+    assert_eq!(bcs::from_bytes::<Option<u8>>(&output).unwrap(), bytes);
+    for o in output {
+        print!("{} ", o);
+    }
+    println!();
+}
+
+fn main() {
+    demo_json();
+    demo_bcs();
+}
@@ -2,24 +2,27 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::module_abi::{ModuleAbi, ModuleAbiFile};
+use anyhow::bail;
 use aptos_types::transaction::EntryABI;
-use std::{ffi::OsStr, fs, io::Read, path::Path};
+use std::{collections::BTreeSet, ffi::OsStr, fs, io::Read, path::Path};
 
 pub mod golang;
+pub mod module_abi;
 pub mod rust;
 
 /// Internals shared between languages.
 mod common;
 
-fn get_abi_paths(dir: &Path) -> std::io::Result<Vec<String>> {
+fn get_paths_with_extension(dir: &Path, extension: &str) -> std::io::Result<Vec<String>> {
     let mut abi_paths = Vec::new();
     if dir.is_dir() {
         for entry in fs::read_dir(dir)? {
             let entry = entry?;
             let path = entry.path();
             if path.is_dir() {
-                abi_paths.append(&mut get_abi_paths(&path)?);
-            } else if let Some("abi") = path.extension().and_then(OsStr::to_str) {
+                abi_paths.append(&mut get_paths_with_extension(&path, extension)?);
+            } else if Some(extension) == path.extension().and_then(OsStr::to_str) {
                 abi_paths.push(path.to_str().unwrap().to_string());
             }
         }
@@ -31,7 +34,7 @@ fn get_abi_paths(dir: &Path) -> std::io::Result<Vec<String>> {
 pub fn read_abis(dir_paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<EntryABI>> {
     let mut abis = Vec::<EntryABI>::new();
     for dir in dir_paths.iter() {
-        for path in get_abi_paths(dir.as_ref())? {
+        for path in get_paths_with_extension(dir.as_ref(), "abi")? {
             let mut buffer = Vec::new();
             let mut f = std::fs::File::open(path)?;
             f.read_to_end(&mut buffer)?;
@@ -56,6 +59,33 @@ pub fn read_abis(dir_paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<EntryABI>
     Ok(abis)
 }
 
+/// Read all module ABI files in JSON, as returned by the REST API, in the specified directories.
+/// A file may contain a single module or a list of modules, with or without their bytecode.
+pub fn read_module_abis(dir_paths: &[impl AsRef<Path>]) -> anyhow::Result<Vec<ModuleAbi>> {
+    let mut abis = Vec::new();
+    for dir in dir_paths.iter() {
+        for path in get_paths_with_extension(dir.as_ref(), "json")? {
+            let content = fs::read_to_string(&path)?;
+            let file: ModuleAbiFile = serde_json::from_str(&content).map_err(|e| {
+                anyhow::format_err!("Failed to parse module ABIs in {}: {}", path, e)
+            })?;
+            abis.append(&mut file.into_abis());
+        }
+    }
+
+    // Sort modules by (address, name) lexicographical order, modules are generated as Rust
+    // modules named after them, so their names must be unique
+    abis.sort_by(|a, b| (a.address, &a.name).cmp(&(b.address, &b.name)));
+    abis.dedup_by(|a, b| a.address == b.address && a.name == b.name);
+    let mut names = BTreeSet::new();
+    for abi in &abis {
+        if !names.insert(&abi.name) {
+            bail!("Found several modules named {}", abi.name);
+        }
+    }
+    Ok(abis)
+}
+
 /// How to copy ABI-generated source code for a given language.
 pub trait SourceInstaller {
     type Error;
@@ -56,12 +56,25 @@ struct Options {
     /// Optional package name (Python) or module path (Go) of the `aptos_types` dependency.
     #[clap(long)]
     package_name: Option<String>,
+
+    /// Path to a directory containing module ABIs in JSON, as returned by the REST API.
+    /// Typed bindings for the structs and view functions of these modules are written to
+    /// `bindings.rs` in the `target_source_dir` (Rust only). The file isn't added to the
+    /// generated crates, it is meant to be included with `mod bindings;` in a crate depending on
+    /// `aptos-rest-client`.
+    #[clap(long)]
+    module_abi_directories: Vec<PathBuf>,
 }
 
 fn main() {
     let options = Options::parse();
     let abis = aptos_sdk_builder::read_abis(&options.abi_directories)
         .expect("Failed to read ABI in directory");
+    let module_abis = aptos_sdk_builder::read_module_abis(&options.module_abi_directories)
+        .expect("Failed to read module ABI in directory");
+    if !module_abis.is_empty() && !matches!(options.language, Language::Rust) {
+        panic!("Bindings for module ABIs are only generated in Rust");
+    }
 
     let install_dir = match options.target_source_dir {
         None => {
@@ -71,7 +84,15 @@ fn main() {
             match options.language {
                 Language::Rust => {
                     aptos_sdk_builder::rust::output(&mut out, &abis, /* local types */ true)
-                        .unwrap()
+                        .unwrap();
+                    if !module_abis.is_empty() {
+                        aptos_sdk_builder::rust::output_module_bindings_in_mod(
+                            &mut out,
+                            &module_abis,
+                            "bindings",
+                        )
+                        .unwrap();
+                    }
                 },
                 Language::Go => {
                     aptos_sdk_builder::golang::output(
@@ -89,6 +110,14 @@ fn main() {
         Some(dir) => dir,
     };
 
+    // Module bindings
+    if !module_abis.is_empty() {
+        std::fs::create_dir_all(&install_dir).unwrap();
+        let mut file = std::fs::File::create(install_dir.join("bindings.rs"))
+            .expect("Failed to create bindings file");
+        aptos_sdk_builder::rust::output_module_bindings(&mut file, &module_abis).unwrap();
+    }
+
     // Aptos types
     if let Some(registry_file) = options.with_aptos_types {
         let installer: Box<dyn serdegen::SourceInstaller<Error = Box<dyn std::error::Error>>> =
@@ -0,0 +1,289 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Module ABIs in the JSON representation of the REST API, i.e. the `abi` of the modules
+//! returned by `/accounts/{address}/module/{name}` and `/accounts/{address}/modules`.
+
+use anyhow::{bail, ensure, format_err};
+use move_core_types::account_address::AccountAddress;
+use serde::{de::Error, Deserialize, Deserializer};
+use std::{fmt, str::FromStr};
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ModuleAbi {
+    pub address: AccountAddress,
+    pub name: String,
+    pub exposed_functions: Vec<FunctionAbi>,
+    pub structs: Vec<StructAbi>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct StructAbi {
+    pub name: String,
+    pub is_native: bool,
+    pub abilities: Vec<String>,
+    pub generic_type_params: Vec<StructTypeParamAbi>,
+    pub fields: Vec<FieldAbi>,
+}
+
+impl StructAbi {
+    pub fn has_ability(&self, ability: &str) -> bool {
+        self.abilities.iter().any(|a| a == ability)
+    }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct StructTypeParamAbi {
+    pub is_phantom: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct FieldAbi {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_: AbiType,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct FunctionAbi {
+    pub name: String,
+    pub is_entry: bool,
+    #[serde(default)]
+    pub is_view: bool,
+    pub generic_type_params: Vec<FunctionTypeParamAbi>,
+    pub params: Vec<AbiType>,
+    #[serde(rename = "return")]
+    pub return_: Vec<AbiType>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct FunctionTypeParamAbi {
+    pub constraints: Vec<String>,
+}
+
+/// A Move type, as displayed by the REST API, e.g. `vector<0x1::coin::Coin<T0>>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AbiType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    U256,
+    Address,
+    Signer,
+    Vector(Box<AbiType>),
+    Struct {
+        address: AccountAddress,
+        module: String,
+        name: String,
+        type_args: Vec<AbiType>,
+    },
+    TypeParam(u16),
+    Reference {
+        mutable: bool,
+        to: Box<AbiType>,
+    },
+}
+
+impl AbiType {
+    /// Parses a type at the start of `s`, returning it along with the rest of `s`.
+    fn parse_prefix(s: &str) -> anyhow::Result<(AbiType, &str)> {
+        let s = s.trim_start();
+        if let Some(rest) = s.strip_prefix("&mut ") {
+            let (to, rest) = Self::parse_prefix(rest)?;
+            return Ok((
+                AbiType::Reference {
+                    mutable: true,
+                    to: Box::new(to),
+                },
+                rest,
+            ));
+        }
+        if let Some(rest) = s.strip_prefix('&') {
+            let (to, rest) = Self::parse_prefix(rest)?;
+            return Ok((
+                AbiType::Reference {
+                    mutable: false,
+                    to: Box::new(to),
+                },
+                rest,
+            ));
+        }
+
+        let end = s.find(['<', '>', ',']).unwrap_or(s.len());
+        let (token, rest) = (s[..end].trim(), &s[end..]);
+        let (type_args, rest) = match rest.strip_prefix('<') {
+            Some(mut rest) => {
+                let mut type_args = vec![];
+                loop {
+                    let (ty, remaining) = Self::parse_prefix(rest)?;
+                    type_args.push(ty);
+                    let remaining = remaining.trim_start();
+                    if let Some(remaining) = remaining.strip_prefix(',') {
+                        rest = remaining;
+                    } else if let Some(remaining) = remaining.strip_prefix('>') {
+                        break (type_args, remaining);
+                    } else {
+                        bail!("Expected ',' or '>' in type arguments of {}", token);
+                    }
+                }
+            },
+            None => (vec![], rest),
+        };
+
+        if token != "vector" && !token.contains("::") {
+            ensure!(
+                type_args.is_empty(),
+                "Unexpected type arguments for {}",
+                token
+            );
+        }
+        let ty = match token {
+            "bool" => AbiType::Bool,
+            "u8" => AbiType::U8,
+            "u16" => AbiType::U16,
+            "u32" => AbiType::U32,
+            "u64" => AbiType::U64,
+            "u128" => AbiType::U128,
+            "u256" => AbiType::U256,
+            "address" => AbiType::Address,
+            "signer" => AbiType::Signer,
+            "vector" => {
+                ensure!(
+                    type_args.len() == 1,
+                    "Expected one type argument for vector"
+                );
+                AbiType::Vector(Box::new(type_args.into_iter().next().unwrap()))
+            },
+            _ => {
+                if let Some(index) = token.strip_prefix('T').and_then(|i| i.parse().ok()) {
+                    AbiType::TypeParam(index)
+                } else {
+                    let parts: Vec<_> = token.split("::").collect();
+                    ensure!(parts.len() == 3, "Invalid type {}", token);
+                    AbiType::Struct {
+                        address: AccountAddress::from_str(parts[0])?,
+                        module: parts[1].to_string(),
+                        name: parts[2].to_string(),
+                        type_args,
+                    }
+                }
+            },
+        };
+        Ok((ty, rest))
+    }
+}
+
+impl FromStr for AbiType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let (ty, rest) = Self::parse_prefix(s)?;
+        if !rest.trim().is_empty() {
+            return Err(format_err!("Unexpected trailing characters in type {}", s));
+        }
+        Ok(ty)
+    }
+}
+
+impl fmt::Display for AbiType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbiType::Bool => write!(f, "bool"),
+            AbiType::U8 => write!(f, "u8"),
+            AbiType::U16 => write!(f, "u16"),
+            AbiType::U32 => write!(f, "u32"),
+            AbiType::U64 => write!(f, "u64"),
+            AbiType::U128 => write!(f, "u128"),
+            AbiType::U256 => write!(f, "u256"),
+            AbiType::Address => write!(f, "address"),
+            AbiType::Signer => write!(f, "signer"),
+            AbiType::Vector(ty) => write!(f, "vector<{}>", ty),
+            AbiType::Struct {
+                address,
+                module,
+                name,
+                type_args,
+            } => {
+                write!(f, "{}::{}::{}", address.to_hex_literal(), module, name)?;
+                if !type_args.is_empty() {
+                    let type_args: Vec<_> = type_args.iter().map(|ty| ty.to_string()).collect();
+                    write!(f, "<{}>", type_args.join(", "))?;
+                }
+                Ok(())
+            },
+            AbiType::TypeParam(index) => write!(f, "T{}", index),
+            AbiType::Reference { mutable, to } => {
+                if *mutable {
+                    write!(f, "&mut {}", to)
+                } else {
+                    write!(f, "&{}", to)
+                }
+            },
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AbiType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(D::Error::custom)
+    }
+}
+
+/// The contents of a module ABI file: either the `abi` alone, a module as returned by the API,
+/// or a list of either.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ModuleAbiFile {
+    Modules(Vec<ModuleAbiEntry>),
+    Module(ModuleAbiEntry),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum ModuleAbiEntry {
+    Abi(ModuleAbi),
+    Bytecode { abi: Option<ModuleAbi> },
+}
+
+impl ModuleAbiFile {
+    pub(crate) fn into_abis(self) -> Vec<ModuleAbi> {
+        let entries = match self {
+            ModuleAbiFile::Modules(entries) => entries,
+            ModuleAbiFile::Module(entry) => vec![entry],
+        };
+        entries
+            .into_iter()
+            .filter_map(|entry| match entry {
+                ModuleAbiEntry::Abi(abi) => Some(abi),
+                ModuleAbiEntry::Bytecode { abi } => abi,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_abi_type() {
+        for s in [
+            "u64",
+            "vector<u8>",
+            "&signer",
+            "&mut 0x1::coin::CoinStore<T0>",
+            "0x1::option::Option<vector<0x1::string::String>>",
+            "0x1::simple_map::SimpleMap<address, 0x1::object::Object<T1>>",
+        ] {
+            assert_eq!(AbiType::from_str(s).unwrap().to_string(), s);
+        }
+        assert!(AbiType::from_str("0x1::option::Option<u8, >").is_err());
+        assert!(AbiType::from_str("vector<u8").is_err());
+        assert!(AbiType::from_str("u8<u8>").is_err());
+        assert!(AbiType::from_str("coin::Coin").is_err());
+    }
+}
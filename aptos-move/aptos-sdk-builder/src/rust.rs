@@ -2,7 +2,11 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::common;
+use crate::{
+    common,
+    module_abi::{AbiType, FunctionAbi, ModuleAbi, StructAbi},
+};
+use anyhow::{ensure, format_err};
 use aptos_types::transaction::{
     ArgumentABI, EntryABI, EntryFunctionABI, TransactionScriptABI, TypeArgumentABI,
 };
@@ -18,7 +22,7 @@ use serde_generate::{
 };
 use serde_reflection::ContainerFormat;
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
     io::{Result, Write},
     path::PathBuf,
     str::FromStr,
@@ -913,6 +917,531 @@ fn decode_{}_argument(arg: TransactionArgument) -> Option<{}> {{
     }
 }
 
+/// Output typed Rust bindings for the structs and view functions of the given modules.
+/// The generated types (de)serialize the way the REST API represents Move values, in BCS as
+/// well as in JSON, so that resources and view function results decode into them directly.
+/// Phantom type parameters don't affect the representation of a struct, so they are left out
+/// of the generated types.
+pub fn output_module_bindings(out: &mut dyn Write, modules: &[ModuleAbi]) -> anyhow::Result<()> {
+    output_module_bindings_impl(out, modules, None)
+}
+
+/// Same as [`output_module_bindings`], but the bindings are wrapped in `pub mod <mod_name> { .. }`,
+/// so that they can follow other code in the same file: the bindings start with inner attributes,
+/// which must come before any item.
+pub fn output_module_bindings_in_mod(
+    out: &mut dyn Write,
+    modules: &[ModuleAbi],
+    mod_name: &str,
+) -> anyhow::Result<()> {
+    output_module_bindings_impl(out, modules, Some(mod_name))
+}
+
+fn output_module_bindings_impl(
+    out: &mut dyn Write,
+    modules: &[ModuleAbi],
+    mod_name: Option<&str>,
+) -> anyhow::Result<()> {
+    let structs = modules
+        .iter()
+        .flat_map(|module| {
+            module
+                .structs
+                .iter()
+                .map(move |s| ((module.address, module.name.as_str(), s.name.as_str()), s))
+        })
+        .collect();
+    let mut emitter = BindingsEmitter {
+        out: IndentedWriter::new(out, IndentConfig::Space(4)),
+        structs,
+    };
+
+    if let Some(mod_name) = mod_name {
+        writeln!(emitter.out, "pub mod {} {{", quote_ident(mod_name))?;
+        emitter.out.indent();
+    }
+    writeln!(emitter.out, "{}", BINDINGS_PREAMBLE)?;
+    for module in modules {
+        emitter.output_module(module)?;
+    }
+    if mod_name.is_some() {
+        emitter.out.unindent();
+        writeln!(emitter.out, "}}")?;
+    }
+    Ok(())
+}
+
+const BINDINGS_PREAMBLE: &str = r#"// Typed bindings for Move structs and view functions.
+//
+// This code was generated from module ABIs, as returned by the REST API, with the tool
+// `aptos-sdk-builder`. Structs decode from resources fetched with either
+// `Client::get_account_resource_bcs` or `Client::get_account_resource`.
+
+#![allow(dead_code)]
+#![allow(unused_imports)]
+#![allow(clippy::too_many_arguments)]
+
+use ::serde::{de::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! impl_number_serde {
+    ($name:ident) => {
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    self.0.to_string().serialize(serializer)
+                } else {
+                    self.0.serialize(serializer)
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    let s = String::deserialize(deserializer)?;
+                    s.parse().map($name).map_err(D::Error::custom)
+                } else {
+                    Deserialize::deserialize(deserializer).map($name)
+                }
+            }
+        }
+    };
+}
+
+/// A Move `u64`, represented as a string in JSON.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U64(pub u64);
+impl_number_serde!(U64);
+
+/// A Move `u128`, represented as a string in JSON.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U128(pub u128);
+impl_number_serde!(U128);
+
+/// A Move `u256`, represented as a string in JSON.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct U256(pub ::move_core_types::u256::U256);
+impl_number_serde!(U256);
+
+/// A Move `vector<u8>`, represented as a hex string in JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HexBytes(pub Vec<u8>);
+
+impl Serialize for HexBytes {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let hex: String = self.0.iter().map(|b| format!("{:02x}", b)).collect();
+            format!("0x{}", hex).serialize(serializer)
+        } else {
+            self.0.serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for HexBytes {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let s = String::deserialize(deserializer)?;
+            let hex = s
+                .strip_prefix("0x")
+                .ok_or_else(|| D::Error::custom("expected a 0x prefixed hex string"))?;
+            if hex.len() % 2 != 0 {
+                return Err(D::Error::custom("expected an even number of hex digits"));
+            }
+            (0..hex.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(D::Error::custom))
+                .collect::<Result<_, _>>()
+                .map(HexBytes)
+        } else {
+            Deserialize::deserialize(deserializer).map(HexBytes)
+        }
+    }
+}
+
+/// A Move `vector<T>` of a type parameter `T`. It is represented as a hex string in JSON when `T`
+/// is `u8`, as `vector<u8>` always is, and as an array otherwise.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MoveVector<T>(pub Vec<T>);
+
+impl<T: Serialize + 'static> Serialize for MoveVector<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match (&self.0 as &dyn ::std::any::Any).downcast_ref::<Vec<u8>>() {
+            Some(bytes) if serializer.is_human_readable() => {
+                HexBytes(bytes.clone()).serialize(serializer)
+            },
+            _ => self.0.serialize(serializer),
+        }
+    }
+}
+
+impl<'de, T: Deserialize<'de> + 'static> Deserialize<'de> for MoveVector<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable()
+            && ::std::any::TypeId::of::<T>() == ::std::any::TypeId::of::<u8>()
+        {
+            let bytes: Box<dyn ::std::any::Any> = Box::new(HexBytes::deserialize(deserializer)?.0);
+            Ok(MoveVector(*bytes.downcast::<Vec<T>>().expect("T is u8")))
+        } else {
+            Deserialize::deserialize(deserializer).map(MoveVector)
+        }
+    }
+}
+
+/// Decodes the BCS encoded value at `index` in the values returned by a view function.
+pub fn decode_view_value<T: ::serde::de::DeserializeOwned>(
+    values: &[Vec<u8>],
+    index: usize,
+) -> Result<T, ::aptos_rest_client::error::RestError> {
+    let value = values.get(index).ok_or_else(|| {
+        ::anyhow::format_err!("Expected at least {} values, got {}", index + 1, values.len())
+    })?;
+    Ok(::bcs::from_bytes(value)?)
+}"#;
+
+/// Shared state for the generator of Rust bindings.
+struct BindingsEmitter<'a, T> {
+    /// Writer.
+    out: IndentedWriter<T>,
+    /// Structs of all the modules, by address, module name and struct name.
+    structs: BTreeMap<(AccountAddress, &'a str, &'a str), &'a StructAbi>,
+}
+
+impl<'a, T> BindingsEmitter<'a, T>
+where
+    T: Write,
+{
+    fn output_module(&mut self, module: &ModuleAbi) -> anyhow::Result<()> {
+        writeln!(
+            self.out,
+            "\n/// Bindings for the module `{}::{}`.",
+            module.address.to_hex_literal(),
+            module.name
+        )?;
+        writeln!(self.out, "pub mod {} {{", quote_ident(&module.name))?;
+        self.out.indent();
+        for struct_abi in module.structs.iter().filter(|s| !s.is_native) {
+            self.output_struct(module, struct_abi)?;
+        }
+        for function in module.exposed_functions.iter().filter(|f| f.is_view) {
+            self.output_view_function(module, function)?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    fn output_struct(&mut self, module: &ModuleAbi, struct_abi: &StructAbi) -> anyhow::Result<()> {
+        let mut used_type_params = BTreeSet::new();
+        let fields = struct_abi
+            .fields
+            .iter()
+            .map(|field| {
+                Ok((
+                    &field.name,
+                    self.quote_type(&field.type_, &mut used_type_params)?,
+                ))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let type_params: Vec<_> = struct_abi
+            .generic_type_params
+            .iter()
+            .enumerate()
+            .filter(|(_, param)| !param.is_phantom)
+            .map(|(index, _)| index as u16)
+            .collect();
+        // Non phantom type parameters may still be unused
+        let unused_type_params: Vec<_> = type_params
+            .iter()
+            .filter(|index| !used_type_params.contains(*index))
+            .map(|index| format!("T{}", index))
+            .collect();
+
+        writeln!(
+            self.out,
+            "\n/// The struct `{}::{}::{}`.",
+            module.address.to_hex_literal(),
+            module.name,
+            struct_abi.name
+        )?;
+        writeln!(
+            self.out,
+            "#[derive(Clone, Debug, PartialEq, Eq, ::serde::Serialize, ::serde::Deserialize)]"
+        )?;
+        // Vectors of type parameters need to know if they are `u8`, see `MoveVector`
+        if !used_type_params.is_empty() {
+            let bounds = |bound: &str| {
+                used_type_params
+                    .iter()
+                    .map(|index| format!("T{}: {} + 'static", index, bound))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            writeln!(
+                self.out,
+                "#[serde(bound(serialize = \"{}\", deserialize = \"{}\"))]",
+                bounds("::serde::Serialize"),
+                bounds("::serde::Deserialize<'de>")
+            )?;
+        }
+        writeln!(
+            self.out,
+            "pub struct {}{} {{",
+            quote_ident(&struct_abi.name),
+            quote_generics(type_params.iter().map(|index| format!("T{}", index)))
+        )?;
+        self.out.indent();
+        for (name, type_) in fields {
+            // Serde strips the prefix of raw identifiers, but not the suffix of others
+            let ident = quote_ident(name);
+            if ident.ends_with('_') && !name.ends_with('_') {
+                writeln!(self.out, "#[serde(rename = \"{}\")]", name)?;
+            }
+            writeln!(self.out, "pub {}: {},", ident, type_)?;
+        }
+        if !unused_type_params.is_empty() {
+            writeln!(self.out, "#[serde(skip)]")?;
+            writeln!(
+                self.out,
+                "pub _phantom: ::std::marker::PhantomData<({},)>,",
+                unused_type_params.join(", ")
+            )?;
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+
+        // The struct tag depends on all type arguments, including the phantom ones
+        if struct_abi.generic_type_params.is_empty() {
+            writeln!(
+                self.out,
+                "\nimpl ::move_core_types::move_resource::MoveStructType for {} {{",
+                quote_ident(&struct_abi.name)
+            )?;
+            self.out.indent();
+            writeln!(
+                self.out,
+                "const ADDRESS: ::move_core_types::account_address::AccountAddress =\n    ::move_core_types::account_address::AccountAddress::new({:?});",
+                module.address.into_bytes()
+            )?;
+            writeln!(
+                self.out,
+                "const MODULE_NAME: &'static ::move_core_types::identifier::IdentStr =\n    ::move_core_types::ident_str!(\"{}\");",
+                module.name
+            )?;
+            writeln!(
+                self.out,
+                "const STRUCT_NAME: &'static ::move_core_types::identifier::IdentStr =\n    ::move_core_types::ident_str!(\"{}\");",
+                struct_abi.name
+            )?;
+            self.out.unindent();
+            writeln!(self.out, "}}")?;
+            if struct_abi.has_ability("key") {
+                writeln!(
+                    self.out,
+                    "\nimpl ::move_core_types::move_resource::MoveResource for {} {{}}",
+                    quote_ident(&struct_abi.name)
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    fn output_view_function(
+        &mut self,
+        module: &ModuleAbi,
+        function: &FunctionAbi,
+    ) -> anyhow::Result<()> {
+        let mut used_type_params = BTreeSet::new();
+        let params = function
+            .params
+            .iter()
+            .map(|ty| self.quote_type(ty, &mut used_type_params))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let returns = function
+            .return_
+            .iter()
+            .map(|ty| self.quote_type(ty, &mut used_type_params))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        let return_type = match returns.len() {
+            1 => returns[0].clone(),
+            _ => format!("({})", returns.join(", ")),
+        };
+        let function_id = format!(
+            "{}::{}::{}",
+            module.address.to_hex_literal(),
+            module.name,
+            function.name
+        );
+
+        writeln!(
+            self.out,
+            "\n/// Calls the view function `{}`, at the given ledger version or the latest one.",
+            function_id
+        )?;
+        writeln!(
+            self.out,
+            "pub async fn {}{}(",
+            quote_ident(&function.name),
+            quote_generics(used_type_params.iter().map(|index| {
+                format!(
+                    "T{}: ::serde::Serialize + ::serde::de::DeserializeOwned + 'static",
+                    index
+                )
+            }))
+        )?;
+        self.out.indent();
+        writeln!(self.out, "client: &::aptos_rest_client::Client,")?;
+        if !function.generic_type_params.is_empty() {
+            writeln!(
+                self.out,
+                "type_args: ::std::vec::Vec<::move_core_types::language_storage::TypeTag>,"
+            )?;
+        }
+        for (index, param) in params.iter().enumerate() {
+            writeln!(self.out, "arg{}: {},", index, param)?;
+        }
+        writeln!(self.out, "version: ::std::option::Option<u64>,")?;
+        self.out.unindent();
+        writeln!(
+            self.out,
+            ") -> ::std::result::Result<\n    ::aptos_rest_client::Response<{}>,\n    ::aptos_rest_client::error::RestError,\n> {{",
+            return_type
+        )?;
+        self.out.indent();
+        writeln!(
+            self.out,
+            "let request = ::aptos_rest_client::aptos_api_types::ViewRequest {{"
+        )?;
+        self.out.indent();
+        writeln!(self.out, "function: \"{}\".parse()?,", function_id)?;
+        if function.generic_type_params.is_empty() {
+            writeln!(self.out, "type_arguments: vec![],")?;
+        } else {
+            writeln!(
+                self.out,
+                "type_arguments: type_args.iter().map(::std::convert::Into::into).collect(),"
+            )?;
+        }
+        let arguments: Vec<_> = (0..params.len())
+            .map(|index| format!("::serde_json::to_value(&arg{})?", index))
+            .collect();
+        writeln!(self.out, "arguments: vec![{}],", arguments.join(", "))?;
+        self.out.unindent();
+        writeln!(self.out, "}};")?;
+        writeln!(
+            self.out,
+            "let response = client.view_bcs(&request, version).await?;"
+        )?;
+        match returns.len() {
+            0 => writeln!(self.out, "::std::result::Result::Ok(response.map(|_| ()))")?,
+            1 => writeln!(
+                self.out,
+                "response.and_then(|values| super::decode_view_value(&values, 0))"
+            )?,
+            _ => {
+                let values: Vec<_> = (0..returns.len())
+                    .map(|index| format!("super::decode_view_value(&values, {})?", index))
+                    .collect();
+                writeln!(
+                    self.out,
+                    "response.and_then(|values| ::std::result::Result::Ok(({})))",
+                    values.join(", ")
+                )?
+            },
+        }
+        self.out.unindent();
+        writeln!(self.out, "}}")?;
+        Ok(())
+    }
+
+    /// Quotes the Rust type of a Move type, relative to the Rust module of a Move module, and
+    /// records the type parameters it uses.
+    fn quote_type(&self, ty: &AbiType, type_params: &mut BTreeSet<u16>) -> anyhow::Result<String> {
+        Ok(match ty {
+            AbiType::Bool => "bool".into(),
+            AbiType::U8 => "u8".into(),
+            AbiType::U16 => "u16".into(),
+            AbiType::U32 => "u32".into(),
+            AbiType::U64 => "super::U64".into(),
+            AbiType::U128 => "super::U128".into(),
+            AbiType::U256 => "super::U256".into(),
+            AbiType::Address | AbiType::Signer => {
+                "::move_core_types::account_address::AccountAddress".into()
+            },
+            AbiType::Vector(ty) if **ty == AbiType::U8 => "super::HexBytes".into(),
+            // The type parameter may be instantiated with `u8`
+            AbiType::Vector(ty) if matches!(**ty, AbiType::TypeParam(_)) => {
+                format!("super::MoveVector<{}>", self.quote_type(ty, type_params)?)
+            },
+            AbiType::Vector(ty) => {
+                format!("::std::vec::Vec<{}>", self.quote_type(ty, type_params)?)
+            },
+            AbiType::Struct {
+                address,
+                module,
+                name,
+                type_args,
+            } => {
+                // The API represents strings as such
+                if *address == AccountAddress::ONE && module == "string" && name == "String" {
+                    return Ok("::std::string::String".into());
+                }
+                let struct_abi = self
+                    .structs
+                    .get(&(*address, module.as_str(), name.as_str()))
+                    .ok_or_else(|| {
+                        format_err!("Struct {} is not defined in the given module ABIs", ty)
+                    })?;
+                ensure!(
+                    type_args.len() == struct_abi.generic_type_params.len(),
+                    "Wrong number of type arguments in {}",
+                    ty
+                );
+                let type_args = type_args
+                    .iter()
+                    .zip(&struct_abi.generic_type_params)
+                    .filter(|(_, param)| !param.is_phantom)
+                    .map(|(ty, _)| self.quote_type(ty, type_params))
+                    .collect::<anyhow::Result<Vec<_>>>()?;
+                format!(
+                    "super::{}::{}{}",
+                    quote_ident(module),
+                    quote_ident(name),
+                    quote_generics(type_args)
+                )
+            },
+            AbiType::TypeParam(index) => {
+                type_params.insert(*index);
+                format!("T{}", index)
+            },
+            AbiType::Reference { to, .. } => self.quote_type(to, type_params)?,
+        })
+    }
+}
+
+fn quote_generics(params: impl IntoIterator<Item = String>) -> String {
+    let params: Vec<_> = params.into_iter().collect();
+    if params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", params.join(", "))
+    }
+}
+
+/// Quotes a Move identifier as a Rust one, Move identifiers may be Rust keywords.
+fn quote_ident(name: &str) -> String {
+    match name {
+        "self" | "Self" | "super" | "crate" => format!("{}_", name),
+        "as" | "async" | "await" | "box" | "break" | "const" | "continue" | "do" | "dyn"
+        | "else" | "enum" | "extern" | "false" | "fn" | "for" | "if" | "impl" | "in" | "let"
+        | "loop" | "match" | "mod" | "move" | "mut" | "pub" | "ref" | "return" | "static"
+        | "struct" | "trait" | "true" | "try" | "type" | "unsafe" | "use" | "where" | "while"
+        | "yield" | "abstract" | "become" | "final" | "macro" | "override" | "priv" | "typeof"
+        | "unsized" | "virtual" => format!("r#{}", name),
+        _ => name.to_string(),
+    }
+}
+
 pub struct Installer {
     install_dir: PathBuf,
     aptos_types_version: String,
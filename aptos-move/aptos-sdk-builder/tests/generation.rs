@@ -93,3 +93,215 @@ fn test_that_rust_entry_fun_code_compiles() {
         EXPECTED_SCRIPT_FUN_OUTPUT,
     );
 }
+
+const MODULE_ABIS: &str = r#"[
+  {
+    "bytecode": "0x",
+    "abi": {
+      "address": "0x1",
+      "name": "coin",
+      "friends": [],
+      "exposed_functions": [
+        {
+          "name": "balance",
+          "visibility": "public",
+          "is_entry": false,
+          "is_view": true,
+          "generic_type_params": [{ "constraints": [] }],
+          "params": ["address"],
+          "return": ["u64"]
+        },
+        {
+          "name": "supply",
+          "visibility": "public",
+          "is_entry": false,
+          "is_view": true,
+          "generic_type_params": [{ "constraints": [] }],
+          "params": [],
+          "return": ["0x1::option::Option<u128>"]
+        },
+        {
+          "name": "transfer",
+          "visibility": "public",
+          "is_entry": true,
+          "is_view": false,
+          "generic_type_params": [{ "constraints": [] }],
+          "params": ["&signer", "address", "u64"],
+          "return": []
+        }
+      ],
+      "structs": [
+        {
+          "name": "Coin",
+          "is_native": false,
+          "abilities": ["store"],
+          "generic_type_params": [{ "constraints": [], "is_phantom": true }],
+          "fields": [{ "name": "value", "type": "u64" }]
+        },
+        {
+          "name": "CoinInfo",
+          "is_native": false,
+          "abilities": ["key"],
+          "generic_type_params": [{ "constraints": [], "is_phantom": true }],
+          "fields": [
+            { "name": "name", "type": "0x1::string::String" },
+            { "name": "decimals", "type": "u8" },
+            { "name": "supply", "type": "0x1::option::Option<u128>" }
+          ]
+        },
+        {
+          "name": "Supply",
+          "is_native": false,
+          "abilities": ["key"],
+          "generic_type_params": [{ "constraints": [], "is_phantom": true }],
+          "fields": [
+            { "name": "type", "type": "vector<u8>" },
+            { "name": "coins", "type": "vector<0x1::coin::Coin<T0>>" }
+          ]
+        }
+      ]
+    }
+  },
+  {
+    "address": "0x1",
+    "name": "option",
+    "friends": [],
+    "exposed_functions": [],
+    "structs": [
+      {
+        "name": "Option",
+        "is_native": false,
+        "abilities": ["copy", "drop", "store"],
+        "generic_type_params": [{ "constraints": [], "is_phantom": false }],
+        "fields": [{ "name": "vec", "type": "vector<T0>" }]
+      }
+    ]
+  }
+]"#;
+
+#[test]
+fn test_rust_module_bindings() {
+    let dir = tempdir().unwrap();
+    std::fs::write(dir.path().join("modules.json"), MODULE_ABIS).unwrap();
+    let module_abis = buildgen::read_module_abis(&[dir.path()]).unwrap();
+    assert_eq!(module_abis.len(), 2);
+
+    // Structs of other modules can only be referred to with their ABI
+    let mut out = Vec::new();
+    assert!(buildgen::rust::output_module_bindings(&mut out, &module_abis[..1]).is_err());
+
+    let mut out = Vec::new();
+    buildgen::rust::output_module_bindings(&mut out, &module_abis).unwrap();
+    let out = String::from_utf8(out).unwrap();
+
+    // Phantom type parameters are left out, strings and options use the API representation
+    for expected in [
+        "pub mod coin {",
+        "pub struct Coin {",
+        "pub value: super::U64,",
+        "pub name: ::std::string::String,",
+        "pub supply: super::option::Option<super::U128>,",
+        "pub r#type: super::HexBytes,",
+        "pub coins: ::std::vec::Vec<super::coin::Coin>,",
+        "impl ::move_core_types::move_resource::MoveResource for CoinInfo {}",
+        "pub struct Option<T0> {",
+        "#[serde(bound(serialize = \"T0: ::serde::Serialize + 'static\", deserialize = \"T0: ::serde::Deserialize<'de> + 'static\"))]",
+        "pub vec: super::MoveVector<T0>,",
+        "pub async fn balance(",
+        "type_args: ::std::vec::Vec<::move_core_types::language_storage::TypeTag>,",
+        "arg0: ::move_core_types::account_address::AccountAddress,",
+        "::aptos_rest_client::Response<super::option::Option<super::U128>>,",
+        "function: \"0x1::coin::balance\".parse()?,",
+    ] {
+        assert!(out.contains(expected), "Missing {} in:\n{}", expected, out);
+    }
+    assert!(!out.contains("fn transfer"));
+
+    // The inner attributes of the bindings stay at the start of the wrapping module
+    let mut out = Vec::new();
+    buildgen::rust::output_module_bindings_in_mod(&mut out, &module_abis, "bindings").unwrap();
+    let out = String::from_utf8(out).unwrap();
+    assert!(out.starts_with("pub mod bindings {\n"));
+    assert!(out.contains("    #![allow(dead_code)]"));
+    assert!(out.contains("    pub mod coin {"));
+    assert!(out.trim_end().ends_with('}'));
+}
+
+const EXPECTED_MODULE_BINDINGS_OUTPUT: &str = "2 1 2 \n";
+
+/// Builds the bindings the way `aptos-sdk-builder` writes them to `bindings.rs`, in a crate
+/// with the dependencies listed in the README, and runs a demo using them.
+#[test]
+fn test_that_rust_module_bindings_compile() {
+    let dir = tempdir().unwrap();
+    std::fs::write(dir.path().join("modules.json"), MODULE_ABIS).unwrap();
+    let module_abis = buildgen::read_module_abis(&[dir.path()]).unwrap();
+
+    let repo_dir = std::env::current_dir()
+        .unwrap()
+        .join("../..")
+        .canonicalize()
+        .unwrap();
+    let crate_dir = dir.path().join("bindings");
+    std::fs::create_dir_all(crate_dir.join("src")).unwrap();
+
+    // The crates of the repository rely on its patches, and are built with its lock file
+    let manifest = std::fs::read_to_string(repo_dir.join("Cargo.toml")).unwrap();
+    let patches = &manifest[manifest.find("[patch.crates-io]").unwrap()..];
+    let patches = match patches[1..].find("\n[") {
+        Some(end) => &patches[..end + 1],
+        None => patches,
+    };
+    std::fs::copy(repo_dir.join("Cargo.lock"), crate_dir.join("Cargo.lock")).unwrap();
+    let mut cargo = std::fs::File::create(crate_dir.join("Cargo.toml")).unwrap();
+    write!(
+        cargo,
+        r#"[package]
+name = "bindings_demo"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+anyhow = "1.0.71"
+aptos-rest-client = {{ path = "{}" }}
+bcs = {{ git = "https://github.com/aptos-labs/bcs.git", rev = "d31fab9d81748e2594be5cd5cdf845786a30562d" }}
+move-core-types = {{ path = "{}" }}
+serde = {{ version = "1.0", features = ["derive"] }}
+serde_json = "1.0"
+
+{}
+"#,
+        repo_dir.join("crates/aptos-rest-client").display(),
+        repo_dir.join("third_party/move/move-core/types").display(),
+        patches,
+    )
+    .unwrap();
+
+    let mut source = std::fs::File::create(crate_dir.join("src/bindings.rs")).unwrap();
+    buildgen::rust::output_module_bindings(&mut source, &module_abis).unwrap();
+    std::fs::copy(
+        "examples/rust/module_bindings_demo.rs",
+        crate_dir.join("src/main.rs"),
+    )
+    .unwrap();
+
+    // Use a stable `target` dir to avoid downloading and recompiling crates everytime.
+    let target_dir = std::env::current_dir().unwrap().join("../../target");
+    let status = Command::new("cargo")
+        .current_dir(&crate_dir)
+        .arg("build")
+        .arg("--target-dir")
+        .arg(target_dir.clone())
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let output = Command::new(target_dir.join("debug/bindings_demo"))
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        std::str::from_utf8(&output.stdout).unwrap(),
+        EXPECTED_MODULE_BINDINGS_OUTPUT
+    );
+}
@@ -0,0 +1,573 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::AptosValidatorInterface;
+use anyhow::{anyhow, bail, Result};
+use aptos_types::{
+    account_address::AccountAddress,
+    account_state::AccountState,
+    chain_id::ChainId,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{Transaction, TransactionInfo, TransactionOutput, Version},
+    write_set::TransactionWrite,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::{File, OpenOptions},
+    io::{BufReader, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+const ACCOUNT_STATES_FILE: &str = "account_states.log";
+const STATE_VALUES_FILE: &str = "state_values.log";
+const TRANSACTIONS_FILE: &str = "transactions.log";
+
+#[derive(Deserialize, Serialize)]
+struct StateValueRecord {
+    state_key: StateKey,
+    version: Version,
+    value: Option<StateValue>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct AccountStateRecord {
+    account: AccountAddress,
+    version: Version,
+    state: Option<AccountState>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct TransactionRecord {
+    version: Version,
+    transaction: Transaction,
+    info: TransactionInfo,
+    output: TransactionOutput,
+}
+
+/// An append-only file of length-prefixed BCS records.
+struct RecordLog {
+    file: File,
+}
+
+impl RecordLog {
+    /// Opens (or creates) the log at `path` and returns all complete records in it. A partially
+    /// written record at the end, e.g. after the process was killed, is discarded.
+    fn open<T: DeserializeOwned>(path: &Path) -> Result<(Self, Vec<T>)> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+
+        let mut records = vec![];
+        let mut valid_len = 0u64;
+        {
+            let mut reader = BufReader::new(&mut file);
+            loop {
+                let mut len_bytes = [0u8; 4];
+                match reader.read_exact(&mut len_bytes) {
+                    Ok(()) => (),
+                    Err(err) if err.kind() == ErrorKind::UnexpectedEof => break,
+                    Err(err) => return Err(err.into()),
+                }
+                let mut bytes = vec![0u8; u32::from_le_bytes(len_bytes) as usize];
+                match reader.read_exact(&mut bytes) {
+                    Ok(()) => (),
+                    Err(err) if err.kind() == ErrorKind::UnexpectedEof => break,
+                    Err(err) => return Err(err.into()),
+                }
+                match bcs::from_bytes(&bytes) {
+                    Ok(record) => records.push(record),
+                    Err(_) => break,
+                }
+                valid_len += 4 + bytes.len() as u64;
+            }
+        }
+        file.set_len(valid_len)?;
+
+        Ok((Self { file }, records))
+    }
+
+    fn append<T: Serialize>(&mut self, records: &[T]) -> Result<()> {
+        let mut buf = vec![];
+        for record in records {
+            let bytes = bcs::to_bytes(record)?;
+            buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+            buf.extend_from_slice(&bytes);
+        }
+        self.file.write_all(&buf)?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+struct PersistentCache {
+    /// State values fetched from the remote, by the exact version they were requested at.
+    state_values: HashMap<(StateKey, Version), Option<StateValue>>,
+    /// Fetched transactions together with their recorded outputs.
+    transactions: BTreeMap<Version, TransactionRecord>,
+    /// Contiguous ranges of cached transactions, from first to last (inclusive) version.
+    ranges: BTreeMap<Version, Version>,
+    /// Every write found in the cached transactions, by state key and version.
+    writes: HashMap<StateKey, BTreeMap<Version, Option<StateValue>>>,
+    /// Account states fetched from the remote, by the exact version they were requested at.
+    account_states: HashMap<(AccountAddress, Version), Option<AccountState>>,
+    account_state_log: RecordLog,
+    state_value_log: RecordLog,
+    transaction_log: RecordLog,
+}
+
+/// The result of looking up a state value in the cache.
+enum Lookup {
+    Found(Option<StateValue>),
+    /// The value is not known, but equals the value of the same key at the given version.
+    Missing(Version),
+}
+
+impl PersistentCache {
+    fn open(dir: &Path) -> Result<Self> {
+        std::fs::create_dir_all(dir)?;
+        let (state_value_log, state_value_records) =
+            RecordLog::open::<StateValueRecord>(&dir.join(STATE_VALUES_FILE))?;
+        let (transaction_log, transaction_records) =
+            RecordLog::open::<TransactionRecord>(&dir.join(TRANSACTIONS_FILE))?;
+        let (account_state_log, account_state_records) =
+            RecordLog::open::<AccountStateRecord>(&dir.join(ACCOUNT_STATES_FILE))?;
+
+        let mut cache = Self {
+            state_values: HashMap::new(),
+            transactions: BTreeMap::new(),
+            ranges: BTreeMap::new(),
+            writes: HashMap::new(),
+            account_states: HashMap::new(),
+            account_state_log,
+            state_value_log,
+            transaction_log,
+        };
+        for record in state_value_records {
+            cache
+                .state_values
+                .insert((record.state_key, record.version), record.value);
+        }
+        for record in transaction_records {
+            cache.index_transaction(record);
+        }
+        for record in account_state_records {
+            cache
+                .account_states
+                .insert((record.account, record.version), record.state);
+        }
+        Ok(cache)
+    }
+
+    fn index_transaction(&mut self, record: TransactionRecord) {
+        let version = record.version;
+        for (state_key, write_op) in record.output.write_set().iter() {
+            self.writes
+                .entry(state_key.clone())
+                .or_default()
+                .insert(version, write_op.as_state_value());
+        }
+        self.transactions.insert(version, record);
+
+        // Merge the new version into the adjacent ranges.
+        let mut start = version;
+        let mut end = version;
+        if let Some((&prev_start, &prev_end)) = self.ranges.range(..=version).next_back() {
+            if prev_end.saturating_add(1) >= version {
+                start = prev_start;
+                end = end.max(prev_end);
+            }
+        }
+        if let Some(&next_end) = self.ranges.get(&(end + 1)) {
+            self.ranges.remove(&(end + 1));
+            end = next_end;
+        }
+        self.ranges.insert(start, end);
+    }
+
+    fn insert_transactions(&mut self, records: Vec<TransactionRecord>) -> Result<()> {
+        let records = records
+            .into_iter()
+            .filter(|record| !self.transactions.contains_key(&record.version))
+            .collect::<Vec<_>>();
+        self.transaction_log.append(&records)?;
+        for record in records {
+            self.index_transaction(record);
+        }
+        Ok(())
+    }
+
+    fn insert_state_value(
+        &mut self,
+        state_key: StateKey,
+        version: Version,
+        value: Option<StateValue>,
+    ) -> Result<()> {
+        let record = StateValueRecord {
+            state_key,
+            version,
+            value,
+        };
+        self.state_value_log.append(std::slice::from_ref(&record))?;
+        self.state_values
+            .insert((record.state_key, record.version), record.value);
+        Ok(())
+    }
+
+    fn insert_account_state(
+        &mut self,
+        account: AccountAddress,
+        version: Version,
+        state: Option<AccountState>,
+    ) -> Result<()> {
+        let record = AccountStateRecord {
+            account,
+            version,
+            state,
+        };
+        self.account_state_log
+            .append(std::slice::from_ref(&record))?;
+        self.account_states
+            .insert((record.account, record.version), record.state);
+        Ok(())
+    }
+
+    /// Returns the cached range of transactions containing `version`, if any.
+    fn range_containing(&self, version: Version) -> Option<(Version, Version)> {
+        self.ranges
+            .range(..=version)
+            .next_back()
+            .filter(|(_, &end)| end >= version)
+            .map(|(&start, &end)| (start, end))
+    }
+
+    fn get_state_value(&self, state_key: &StateKey, version: Version) -> Lookup {
+        let mut version = version;
+        loop {
+            if let Some(value) = self.state_values.get(&(state_key.clone(), version)) {
+                return Lookup::Found(value.clone());
+            }
+            let (start, _) = match self.range_containing(version) {
+                Some(range) => range,
+                None => return Lookup::Missing(version),
+            };
+            // All writes between `start` and `version` are known, so the value is either the
+            // latest of them or, if there is none, the value right before the range.
+            if let Some((_, value)) = self
+                .writes
+                .get(state_key)
+                .and_then(|writes| writes.range(start..=version).next_back())
+            {
+                return Lookup::Found(value.clone());
+            }
+            if start == 0 {
+                return Lookup::Found(None);
+            }
+            version = start - 1;
+        }
+    }
+
+    fn get_transactions(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Option<(
+        Vec<Transaction>,
+        Vec<TransactionInfo>,
+        Vec<TransactionOutput>,
+    )> {
+        if limit == 0 {
+            return Some((vec![], vec![], vec![]));
+        }
+        let (_, end) = self.range_containing(start)?;
+        if end < start + limit - 1 {
+            return None;
+        }
+        let mut txns = Vec::with_capacity(limit as usize);
+        let mut txn_infos = Vec::with_capacity(limit as usize);
+        let mut txn_outputs = Vec::with_capacity(limit as usize);
+        for record in self
+            .transactions
+            .range(start..start + limit)
+            .map(|(_, r)| r)
+        {
+            txns.push(record.transaction.clone());
+            txn_infos.push(record.info.clone());
+            txn_outputs.push(record.output.clone());
+        }
+        Some((txns, txn_infos, txn_outputs))
+    }
+}
+
+/// Wraps another `AptosValidatorInterface` (usually `RestDebuggerInterface`) with a persistent
+/// on-disk cache of fetched state values, account states and transactions, so that repeated
+/// replays of the same versions neither need nor hammer the remote.
+///
+/// The cache is stored under `<cache_dir>/<chain_id>`. Fetched transactions come with their
+/// write sets, which also answer state reads at any version inside a cached range of
+/// transactions. Without a remote, the interface works offline and fails on cache misses.
+pub struct CachedDebuggerInterface {
+    remote: Option<Arc<dyn AptosValidatorInterface + Send>>,
+    cache: Mutex<PersistentCache>,
+}
+
+impl CachedDebuggerInterface {
+    pub fn open(
+        cache_dir: impl AsRef<Path>,
+        chain_id: ChainId,
+        remote: Option<Arc<dyn AptosValidatorInterface + Send>>,
+    ) -> Result<Self> {
+        let dir: PathBuf = cache_dir.as_ref().join(chain_id.id().to_string());
+        Ok(Self {
+            remote,
+            cache: Mutex::new(PersistentCache::open(&dir)?),
+        })
+    }
+
+    fn remote(&self) -> Result<&Arc<dyn AptosValidatorInterface + Send>> {
+        self.remote
+            .as_ref()
+            .ok_or_else(|| anyhow!("Data is not in the cache and there is no remote (offline)"))
+    }
+
+    /// Fetches and caches the transactions (with their write sets) of the given range, so that
+    /// later state reads inside the range can be answered from the cache.
+    pub async fn prefetch(&self, start: Version, limit: u64) -> Result<()> {
+        self.get_committed_transactions_with_outputs(start, limit)
+            .await
+            .map(|_| ())
+    }
+}
+
+#[async_trait::async_trait]
+impl AptosValidatorInterface for CachedDebuggerInterface {
+    async fn get_account_state_by_version(
+        &self,
+        account: AccountAddress,
+        version: Version,
+    ) -> Result<Option<AccountState>> {
+        if let Some(state) = self
+            .cache
+            .lock()
+            .unwrap()
+            .account_states
+            .get(&(account, version))
+        {
+            return Ok(state.clone());
+        }
+
+        let state = self
+            .remote()?
+            .get_account_state_by_version(account, version)
+            .await?;
+        self.cache
+            .lock()
+            .unwrap()
+            .insert_account_state(account, version, state.clone())?;
+        Ok(state)
+    }
+
+    async fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<StateValue>> {
+        let missing_version = match self
+            .cache
+            .lock()
+            .unwrap()
+            .get_state_value(state_key, version)
+        {
+            Lookup::Found(value) => return Ok(value),
+            Lookup::Missing(version) => version,
+        };
+
+        let value = self
+            .remote()?
+            .get_state_value_by_version(state_key, missing_version)
+            .await?;
+        self.cache.lock().unwrap().insert_state_value(
+            state_key.clone(),
+            missing_version,
+            value.clone(),
+        )?;
+        Ok(value)
+    }
+
+    async fn get_committed_transactions(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
+        let (txns, txn_infos, _) = self
+            .get_committed_transactions_with_outputs(start, limit)
+            .await?;
+        Ok((txns, txn_infos))
+    }
+
+    async fn get_committed_transactions_with_outputs(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<(
+        Vec<Transaction>,
+        Vec<TransactionInfo>,
+        Vec<TransactionOutput>,
+    )> {
+        if let Some(result) = self.cache.lock().unwrap().get_transactions(start, limit) {
+            return Ok(result);
+        }
+
+        let (txns, txn_infos, txn_outputs) = self
+            .remote()?
+            .get_committed_transactions_with_outputs(start, limit)
+            .await?;
+        let records = txns
+            .iter()
+            .zip(txn_infos.iter())
+            .zip(txn_outputs.iter())
+            .enumerate()
+            .map(|(idx, ((transaction, info), output))| TransactionRecord {
+                version: start + idx as Version,
+                transaction: transaction.clone(),
+                info: info.clone(),
+                output: output.clone(),
+            })
+            .collect();
+        self.cache.lock().unwrap().insert_transactions(records)?;
+        Ok((txns, txn_infos, txn_outputs))
+    }
+
+    async fn get_latest_version(&self) -> Result<Version> {
+        match &self.remote {
+            Some(remote) => remote.get_latest_version().await,
+            None => self
+                .cache
+                .lock()
+                .unwrap()
+                .transactions
+                .keys()
+                .next_back()
+                .copied()
+                .ok_or_else(|| anyhow!("No transactions in the cache")),
+        }
+    }
+
+    async fn get_version_by_account_sequence(
+        &self,
+        account: AccountAddress,
+        seq: u64,
+    ) -> Result<Option<Version>> {
+        let cached = self
+            .cache
+            .lock()
+            .unwrap()
+            .transactions
+            .values()
+            .find(|record| match &record.transaction {
+                Transaction::UserTransaction(txn) => {
+                    txn.sender() == account && txn.sequence_number() == seq
+                },
+                _ => false,
+            })
+            .map(|record| record.version);
+        if cached.is_some() {
+            return Ok(cached);
+        }
+        match &self.remote {
+            Some(remote) => remote.get_version_by_account_sequence(account, seq).await,
+            None => bail!(
+                "Transaction {} of account {} is not in the cache and there is no remote (offline)",
+                seq,
+                account
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::HashValue;
+    use aptos_temppath::TempPath;
+    use aptos_types::{
+        transaction::{ExecutionStatus, TransactionStatus},
+        write_set::{WriteOp, WriteSetMut},
+    };
+
+    fn record(version: Version, writes: Vec<(StateKey, WriteOp)>) -> TransactionRecord {
+        TransactionRecord {
+            version,
+            transaction: Transaction::StateCheckpoint(HashValue::zero()),
+            info: TransactionInfo::new_placeholder(0, None, ExecutionStatus::Success),
+            output: TransactionOutput::new(
+                WriteSetMut::new(writes).freeze().unwrap(),
+                vec![],
+                0,
+                TransactionStatus::Keep(ExecutionStatus::Success),
+            ),
+        }
+    }
+
+    fn found(lookup: Lookup) -> Option<Option<StateValue>> {
+        match lookup {
+            Lookup::Found(value) => Some(value),
+            Lookup::Missing(_) => None,
+        }
+    }
+
+    #[test]
+    fn test_state_values_from_cached_write_sets() {
+        let dir = TempPath::new();
+        let key = StateKey::raw(vec![1]);
+        let value = StateValue::new_legacy(vec![2].into());
+
+        let mut cache = PersistentCache::open(dir.path()).unwrap();
+        cache
+            .insert_transactions(vec![
+                record(10, vec![]),
+                record(11, vec![(
+                    key.clone(),
+                    WriteOp::Modification(vec![2].into()),
+                )]),
+                record(13, vec![(key.clone(), WriteOp::Deletion)]),
+                record(12, vec![]),
+            ])
+            .unwrap();
+        assert_eq!(cache.ranges, BTreeMap::from([(10, 13)]));
+
+        // Before the first write in the range, the value has to come from the remote.
+        assert!(matches!(
+            cache.get_state_value(&key, 10),
+            Lookup::Missing(9)
+        ));
+        assert_eq!(found(cache.get_state_value(&key, 12)), Some(Some(value)));
+        assert_eq!(found(cache.get_state_value(&key, 13)), Some(None));
+        assert!(matches!(
+            cache.get_state_value(&key, 14),
+            Lookup::Missing(14)
+        ));
+
+        cache.insert_state_value(key.clone(), 9, None).unwrap();
+        assert_eq!(found(cache.get_state_value(&key, 10)), Some(None));
+        let account_state = AccountState::new(AccountAddress::ONE, BTreeMap::new());
+        cache
+            .insert_account_state(AccountAddress::ONE, 10, Some(account_state.clone()))
+            .unwrap();
+        drop(cache);
+
+        // Everything survives a restart.
+        let cache = PersistentCache::open(dir.path()).unwrap();
+        assert_eq!(cache.ranges, BTreeMap::from([(10, 13)]));
+        assert_eq!(found(cache.get_state_value(&key, 10)), Some(None));
+        assert!(cache.get_transactions(10, 4).is_some());
+        assert!(cache.get_transactions(10, 5).is_none());
+        assert_eq!(
+            cache.account_states.get(&(AccountAddress::ONE, 10)),
+            Some(&Some(account_state))
+        );
+    }
+}
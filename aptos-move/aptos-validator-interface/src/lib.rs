@@ -2,10 +2,14 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod cache_interface;
 mod rest_interface;
 mod storage_interface;
 
-pub use crate::{rest_interface::RestDebuggerInterface, storage_interface::DBDebuggerInterface};
+pub use crate::{
+    cache_interface::CachedDebuggerInterface, rest_interface::RestDebuggerInterface,
+    storage_interface::DBDebuggerInterface,
+};
 use anyhow::{anyhow, Result};
 use aptos_state_view::TStateView;
 use aptos_types::{
@@ -17,7 +21,7 @@ use aptos_types::{
     state_store::{
         state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
     },
-    transaction::{Transaction, TransactionInfo, Version},
+    transaction::{Transaction, TransactionInfo, TransactionOutput, Version},
 };
 use lru::LruCache;
 use move_binary_format::file_format::CompiledModule;
@@ -46,6 +50,19 @@ pub trait AptosValidatorInterface: Sync {
         limit: u64,
     ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)>;
 
+    /// Same as `get_committed_transactions`, but additionally returns the recorded output
+    /// (write set, events, gas used and status) of every transaction, so that a replay can be
+    /// compared against what actually happened on chain.
+    async fn get_committed_transactions_with_outputs(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<(
+        Vec<Transaction>,
+        Vec<TransactionInfo>,
+        Vec<TransactionOutput>,
+    )>;
+
     async fn get_latest_version(&self) -> Result<Version>;
 
     async fn get_version_by_account_sequence(
@@ -113,19 +130,16 @@ pub trait AptosValidatorInterface: Sync {
     }
 }
 
+type StateValueSender = std::sync::mpsc::Sender<Result<Option<Vec<u8>>>>;
+
 pub struct DebuggerStateView {
-    query_sender:
-        Mutex<UnboundedSender<(StateKey, Version, std::sync::mpsc::Sender<Option<Vec<u8>>>)>>,
+    query_sender: Mutex<UnboundedSender<(StateKey, Version, StateValueSender)>>,
     version: Version,
 }
 
 async fn handler_thread<'a>(
     db: Arc<dyn AptosValidatorInterface + Send>,
-    mut thread_receiver: UnboundedReceiver<(
-        StateKey,
-        Version,
-        std::sync::mpsc::Sender<Option<Vec<u8>>>,
-    )>,
+    mut thread_receiver: UnboundedReceiver<(StateKey, Version, StateValueSender)>,
 ) {
     const M: usize = 1024 * 1024;
     let cache = Arc::new(Mutex::new(
@@ -141,18 +155,21 @@ async fn handler_thread<'a>(
             };
 
         if let Some(val) = cache.lock().unwrap().get(&(key.clone(), version)) {
-            sender.send(val.clone()).unwrap();
+            sender.send(Ok(val.clone())).unwrap();
         } else {
             assert!(version > 0, "Expecting a non-genesis version");
             let db = db.clone();
             let cache = cache.clone();
             tokio::spawn(async move {
+                // Errors, e.g. a cache miss in offline mode, are passed on instead of being
+                // treated as missing state, which would silently change the replay.
                 let val = db
                     .get_state_value_by_version(&key, version - 1)
                     .await
-                    .ok()
-                    .and_then(|v| v.map(|s| s.bytes().to_vec()));
-                cache.lock().unwrap().put((key, version), val.clone());
+                    .map(|v| v.map(|s| s.bytes().to_vec()));
+                if let Ok(val) = &val {
+                    cache.lock().unwrap().put((key, version), val.clone());
+                }
                 sender.send(val)
             });
         }
@@ -180,7 +197,7 @@ impl DebuggerStateView {
         query_handler_locked
             .send((state_key.clone(), version, tx))
             .unwrap();
-        let bytes_opt = rx.recv()?;
+        let bytes_opt = rx.recv()??;
         Ok(bytes_opt.map(|bytes| StateValue::new_legacy(bytes.into())))
     }
 }
@@ -2,8 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::AptosValidatorInterface;
-use anyhow::{anyhow, Result};
-use aptos_api_types::{AptosError, AptosErrorCode};
+use anyhow::{anyhow, bail, Result};
+use aptos_api_types::{AptosError, AptosErrorCode, TransactionOnChainData};
 use aptos_rest_client::{
     error::{AptosErrorResponse, RestError},
     Client,
@@ -12,7 +12,7 @@ use aptos_types::{
     account_address::AccountAddress,
     account_state::AccountState,
     state_store::{state_key::StateKey, state_value::StateValue},
-    transaction::{Transaction, TransactionInfo, Version},
+    transaction::{Transaction, TransactionInfo, TransactionOutput, TransactionStatus, Version},
 };
 use std::collections::BTreeMap;
 
@@ -22,6 +22,36 @@ impl RestDebuggerInterface {
     pub fn new(client: Client) -> Self {
         Self(client)
     }
+
+    /// Fetches `limit` committed transactions starting at `start`, one page at a time
+    async fn get_transactions_on_chain_data(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<Vec<TransactionOnChainData>> {
+        let mut txns = Vec::with_capacity(limit as usize);
+        while txns.len() < limit as usize {
+            let page = self
+                .0
+                .get_transactions_bcs(
+                    Some(start + txns.len() as u64),
+                    Some(limit as u16 - txns.len() as u16),
+                )
+                .await?
+                .into_inner();
+            if page.is_empty() {
+                bail!(
+                    "No transactions from version {}, only {}/{} txns were fetched",
+                    start + txns.len() as u64,
+                    txns.len(),
+                    limit
+                );
+            }
+            txns.extend(page);
+            println!("Got {}/{} txns from RestApi.", txns.len(), limit);
+        }
+        Ok(txns)
+    }
 }
 
 #[async_trait::async_trait]
@@ -70,26 +100,38 @@ impl AptosValidatorInterface for RestDebuggerInterface {
         start: Version,
         limit: u64,
     ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
+        Ok(self
+            .get_transactions_on_chain_data(start, limit)
+            .await?
+            .into_iter()
+            .map(|txn| (txn.transaction, txn.info))
+            .unzip())
+    }
+
+    async fn get_committed_transactions_with_outputs(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<(
+        Vec<Transaction>,
+        Vec<TransactionInfo>,
+        Vec<TransactionOutput>,
+    )> {
         let mut txns = Vec::with_capacity(limit as usize);
         let mut txn_infos = Vec::with_capacity(limit as usize);
-
-        while txns.len() < limit as usize {
-            self.0
-                .get_transactions_bcs(
-                    Some(start + txns.len() as u64),
-                    Some(limit as u16 - txns.len() as u16),
-                )
-                .await?
-                .into_inner()
-                .into_iter()
-                .for_each(|txn| {
-                    txns.push(txn.transaction);
-                    txn_infos.push(txn.info);
-                });
-            println!("Got {}/{} txns from RestApi.", txns.len(), limit);
+        let mut txn_outputs = Vec::with_capacity(limit as usize);
+        for txn in self.get_transactions_on_chain_data(start, limit).await? {
+            txn_outputs.push(TransactionOutput::new(
+                txn.changes,
+                txn.events,
+                txn.info.gas_used(),
+                TransactionStatus::Keep(txn.info.status().clone()),
+            ));
+            txns.push(txn.transaction);
+            txn_infos.push(txn.info);
         }
 
-        Ok((txns, txn_infos))
+        Ok((txns, txn_infos, txn_outputs))
     }
 
     async fn get_latest_version(&self) -> Result<Version> {
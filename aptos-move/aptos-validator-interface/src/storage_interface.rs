@@ -14,8 +14,9 @@ use aptos_types::{
     account_address::AccountAddress,
     account_state::AccountState,
     state_store::{state_key::StateKey, state_key_prefix::StateKeyPrefix, state_value::StateValue},
-    transaction::{Transaction, TransactionInfo, Version},
+    transaction::{Transaction, TransactionInfo, TransactionOutput, TransactionStatus, Version},
 };
+use itertools::izip;
 use std::{path::Path, sync::Arc};
 
 pub struct DBDebuggerInterface(Arc<dyn DbReader>);
@@ -79,6 +80,39 @@ impl AptosValidatorInterface for DBDebuggerInterface {
         Ok((txns, txn_infos))
     }
 
+    async fn get_committed_transactions_with_outputs(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<(
+        Vec<Transaction>,
+        Vec<TransactionInfo>,
+        Vec<TransactionOutput>,
+    )> {
+        let (txns, txn_infos) = self.get_committed_transactions(start, limit).await?;
+        let write_sets = self
+            .0
+            .get_write_set_iterator(start, limit)?
+            .collect::<Result<Vec<_>>>()?;
+        let events = self
+            .0
+            .get_events_iterator(start, limit)?
+            .collect::<Result<Vec<_>>>()?;
+        ensure!(txns.len() == write_sets.len() && txns.len() == events.len());
+
+        let txn_outputs = izip!(&txn_infos, write_sets, events)
+            .map(|(txn_info, write_set, events)| {
+                TransactionOutput::new(
+                    write_set,
+                    events,
+                    txn_info.gas_used(),
+                    TransactionStatus::Keep(txn_info.status().clone()),
+                )
+            })
+            .collect();
+        Ok((txns, txn_infos, txn_outputs))
+    }
+
     async fn get_latest_version(&self) -> Result<Version> {
         self.0.get_latest_version()
     }
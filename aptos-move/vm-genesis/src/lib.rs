@@ -5,8 +5,10 @@
 #![forbid(unsafe_code)]
 
 mod genesis_context;
+mod regenesis;
 
 use crate::genesis_context::GenesisStateView;
+pub use crate::regenesis::{encode_regenesis_change_set, encode_regenesis_transaction};
 use aptos_crypto::{
     bls12381,
     ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
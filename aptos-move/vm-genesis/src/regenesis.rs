@@ -0,0 +1,467 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Validator;
+use anyhow::{anyhow, ensure, Result};
+use aptos_crypto::HashValue;
+use aptos_framework::{natives::code::PublishRequest, ReleaseBundle, ReleasePackage};
+use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters, LATEST_GAS_FEATURE_VERSION};
+use aptos_state_view::{StateView, StateViewId, TStateView};
+use aptos_types::{
+    account_config::{events::NewEpochEvent, reserved_vm_address, CORE_CODE_ADDRESS},
+    chain_id::ChainId,
+    contract_event::ContractEvent,
+    on_chain_config::{
+        CurrentTimeMicroseconds, FeatureFlag, Features, OnChainConfig, TimedFeaturesBuilder,
+        ValidatorSet,
+    },
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+    },
+    transaction::{ChangeSet, Transaction, WriteSetPayload},
+    utility_coin::APTOS_COIN_TYPE,
+    write_set::{TransactionWrite, WriteOp, WriteSetMut},
+};
+use aptos_vm::{
+    data_cache::AsMoveResolver,
+    move_vm_ext::{MoveVmExt, SessionExt, SessionId},
+};
+use aptos_vm_types::storage::ChangeSetConfigs;
+use move_binary_format::{compatibility::Compatibility, errors::VMResult};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+    value::MoveValue,
+};
+use move_vm_types::gas::UnmeteredGasMeter;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Builds a genesis transaction on top of the state of an existing chain, see
+/// `encode_regenesis_change_set`.
+pub fn encode_regenesis_transaction(
+    state_view: &impl StateView,
+    validators: &[Validator],
+    chain_id: ChainId,
+    framework: Option<&ReleaseBundle>,
+) -> Result<Transaction> {
+    Ok(Transaction::GenesisTransaction(WriteSetPayload::Direct(
+        encode_regenesis_change_set(state_view, validators, chain_id, framework)?,
+    )))
+}
+
+/// Builds the change set of a genesis on top of the state of an existing chain (a "regenesis"),
+/// e.g., a state snapshot of mainnet, which is then used to start a new network.
+///
+/// On top of the given state, the change set:
+/// 1. upgrades the framework to the given release bundle (if any), package by package.
+/// 2. sets the chain id to `chain_id`.
+/// 3. retires all current validators, and creates, funds and stakes the given `validators`
+///    (which must not own a stake pool yet) in the same way genesis does.
+/// 4. advances the time and starts a new epoch, with the given `validators` only.
+///
+/// Executing the transaction on a DB holding the same state (i.e., the DB the snapshot was taken
+/// from) yields the waypoint new nodes bootstrap from.
+pub fn encode_regenesis_change_set(
+    state_view: &impl StateView,
+    validators: &[Validator],
+    chain_id: ChainId,
+    framework: Option<&ReleaseBundle>,
+) -> Result<ChangeSet> {
+    ensure!(!validators.is_empty(), "At least one validator is required");
+
+    let mut regenesis = Regenesis::new(state_view, chain_id)?;
+    if let Some(framework) = framework {
+        // Every package is published in its own session, so that it is loaded as a dependency
+        // (e.g., of the token packages) in its upgraded form.
+        for package in &framework.packages {
+            regenesis.publish_package(package)?;
+        }
+    }
+    regenesis.set_chain_id()?;
+    regenesis.retire_validators()?;
+    regenesis.start_epoch(validators)?;
+    regenesis.into_change_set()
+}
+
+/// The state of the existing chain, with the changes of the regenesis applied on top.
+struct RegenesisStateView<'a, S> {
+    base: &'a S,
+    write_set: WriteSetMut,
+}
+
+impl<'a, S: StateView> TStateView for RegenesisStateView<'a, S> {
+    type Key = StateKey;
+
+    fn id(&self) -> StateViewId {
+        self.base.id()
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        match self.write_set.get(state_key) {
+            Some(write_op) => Ok(write_op.as_state_value()),
+            None => self.base.get_state_value(state_key),
+        }
+    }
+
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.base.get_usage()
+    }
+}
+
+struct Regenesis<'a, S> {
+    state_view: RegenesisStateView<'a, S>,
+    events: Vec<ContractEvent>,
+    chain_id: ChainId,
+    /// The time of the existing chain, which makes the session ids (and the table handles and
+    /// object addresses derived from them) unique to the snapshot.
+    snapshot_timestamp_usecs: u64,
+    num_sessions: u64,
+}
+
+impl<'a, S: StateView> Regenesis<'a, S> {
+    fn new(base: &'a S, chain_id: ChainId) -> Result<Self> {
+        let mut regenesis = Self {
+            state_view: RegenesisStateView {
+                base,
+                write_set: WriteSetMut::default(),
+            },
+            events: vec![],
+            chain_id,
+            snapshot_timestamp_usecs: 0,
+            num_sessions: 0,
+        };
+        regenesis.snapshot_timestamp_usecs =
+            regenesis.fetch::<CurrentTimeMicroseconds>()?.microseconds;
+        Ok(regenesis)
+    }
+
+    fn publish_package(&mut self, package: &ReleasePackage) -> Result<()> {
+        let address = *package
+            .sorted_code_and_modules()
+            .first()
+            .ok_or_else(|| anyhow!("Package {} has no modules", package.name()))?
+            .1
+            .self_id()
+            .address();
+        let metadata = bcs::to_bytes(package.package_metadata())?;
+        let code = package
+            .code()
+            .into_iter()
+            .map(|module| MoveValue::vector_u8(module.to_vec()))
+            .collect();
+        let treat_friend_as_private = self
+            .fetch::<Features>()?
+            .is_enabled(FeatureFlag::TREAT_FRIEND_AS_PRIVATE);
+
+        self.run_session(|session| {
+            exec_function(session, "code", "publish_package_txn", vec![], vec![
+                MoveValue::Signer(address),
+                MoveValue::vector_u8(metadata),
+                MoveValue::Vector(code),
+            ])?;
+
+            // The code module only requests the publishing, which is resolved after the call.
+            if let Some(PublishRequest {
+                destination,
+                bundle,
+                ..
+            }) = session.extract_publish_request()
+            {
+                session.publish_module_bundle_with_compat_config(
+                    bundle.into_inner(),
+                    destination,
+                    &mut UnmeteredGasMeter,
+                    Compatibility::new(true, true, !treat_friend_as_private),
+                )?;
+            }
+            Ok(())
+        })
+        .map_err(|error| anyhow!("Failed to publish {}: {}", package.name(), error))
+    }
+
+    /// The chain id can only be set at genesis, so it is overwritten directly.
+    fn set_chain_id(&mut self) -> Result<()> {
+        self.fetch::<ChainId>()?;
+        let chain_id = self.chain_id;
+        self.write_config(&chain_id)
+    }
+
+    /// Marks all current validators as leaving and resets the voting power of the validator set,
+    /// so that the new validators can join regardless of the voting power increase limit.
+    fn retire_validators(&mut self) -> Result<()> {
+        let mut validator_set = self.fetch::<ValidatorSet>()?;
+        let mut leaving = std::mem::take(&mut validator_set.active_validators);
+        leaving.append(&mut validator_set.pending_inactive);
+        validator_set.pending_inactive = leaving;
+        validator_set.pending_active.clear();
+        validator_set.total_voting_power = 0;
+        validator_set.total_joining_power = 0;
+        self.write_config(&validator_set)
+    }
+
+    /// Joins the new validators, and starts a new epoch in which they are the only validators.
+    fn start_epoch(&mut self, validators: &[Validator]) -> Result<()> {
+        let timestamp_usecs = self.snapshot_timestamp_usecs + 1;
+        self.run_session(|session| {
+            for validator in validators {
+                create_and_initialize_validator(session, validator)?;
+            }
+            exec_function(session, "timestamp", "update_global_time", vec![], vec![
+                MoveValue::Signer(reserved_vm_address()),
+                MoveValue::Address(CORE_CODE_ADDRESS),
+                MoveValue::U64(timestamp_usecs),
+            ])?;
+            exec_function(session, "reconfiguration", "reconfigure", vec![], vec![])
+        })
+        .map_err(|error| anyhow!("Failed to replace the validator set: {}", error))?;
+
+        ensure!(
+            self.events
+                .iter()
+                .any(|event| event.event_key() == Some(&NewEpochEvent::event_key())),
+            "No new epoch was started, is reconfiguration disabled?"
+        );
+        let active_validators: BTreeSet<_> = self
+            .fetch::<ValidatorSet>()?
+            .active_validators()
+            .into_iter()
+            .collect();
+        let expected_validators: BTreeSet<_> = validators
+            .iter()
+            .map(|validator| validator.owner_address)
+            .collect();
+        ensure!(
+            active_validators == expected_validators,
+            "The validator set is {:?} instead of {:?}, is the stake of every validator within \
+             the staking config limits?",
+            active_validators,
+            expected_validators,
+        );
+        Ok(())
+    }
+
+    fn into_change_set(self) -> Result<ChangeSet> {
+        Ok(ChangeSet::new(
+            self.state_view.write_set.freeze()?,
+            self.events,
+        ))
+    }
+
+    /// Runs `f` in a new session, and applies its changes on top of the state.
+    fn run_session<F>(&mut self, f: F) -> Result<()>
+    where
+        F: FnOnce(&mut SessionExt) -> VMResult<()>,
+    {
+        let session_id = SessionId::genesis(HashValue::sha3_256_of(&bcs::to_bytes(&(
+            "regenesis",
+            self.snapshot_timestamp_usecs,
+            self.num_sessions,
+        ))?));
+        self.num_sessions += 1;
+
+        let (write_set, events) = {
+            let resolver = self.state_view.as_move_resolver();
+            let features = Features::fetch_config(&resolver).unwrap_or_default();
+            let move_vm = MoveVmExt::new(
+                NativeGasParameters::zeros(),
+                MiscGasParameters::zeros(),
+                LATEST_GAS_FEATURE_VERSION,
+                self.chain_id.id(),
+                features,
+                TimedFeaturesBuilder::enable_all().build(),
+                &resolver,
+            )
+            .map_err(|error| anyhow!("Failed to create the VM: {:?}", error))?;
+            let mut session = move_vm.new_session(&resolver, session_id);
+            f(&mut session).map_err(|error| anyhow!("{:?}", error.into_vm_status()))?;
+
+            let configs =
+                ChangeSetConfigs::unlimited_at_gas_feature_version(LATEST_GAS_FEATURE_VERSION);
+            session
+                .finish(&mut (), &configs)
+                .map_err(|error| anyhow!("{:?}", error.into_vm_status()))?
+                .try_materialize_aggregator_v1_delta_set(&resolver)
+                .and_then(|change_set| change_set.try_into_storage_change_set())
+                .map_err(|status| anyhow!("Failed to materialize the changes: {:?}", status))?
+                .into_inner()
+        };
+        self.apply(write_set.into_mut())?;
+        self.events.extend(events);
+        Ok(())
+    }
+
+    fn fetch<T: OnChainConfig>(&self) -> Result<T> {
+        T::fetch_config(&self.state_view.as_move_resolver()).ok_or_else(|| {
+            anyhow!(
+                "Failed to fetch {}::{}",
+                T::MODULE_IDENTIFIER,
+                T::TYPE_IDENTIFIER
+            )
+        })
+    }
+
+    /// Overwrites an existing on-chain config, keeping its metadata.
+    fn write_config<T: OnChainConfig + Serialize>(&mut self, config: &T) -> Result<()> {
+        let state_key = StateKey::access_path(T::access_path()?);
+        let data = bcs::to_bytes(config)?.into();
+        let write_op = match self
+            .state_view
+            .get_state_value(&state_key)?
+            .and_then(|state_value| state_value.into_metadata())
+        {
+            Some(metadata) => WriteOp::ModificationWithMetadata { data, metadata },
+            None => WriteOp::Modification(data),
+        };
+        self.apply(WriteSetMut::new(vec![(state_key, write_op)]))
+    }
+
+    fn apply(&mut self, write_set: WriteSetMut) -> Result<()> {
+        self.state_view.write_set =
+            std::mem::take(&mut self.state_view.write_set).squash(write_set)?;
+        Ok(())
+    }
+}
+
+/// Creates the accounts of a validator (where missing), mints its stake to the owner and joins
+/// its stake pool to the validator set, like `genesis::create_initialize_validator` does.
+fn create_and_initialize_validator(
+    session: &mut SessionExt,
+    validator: &Validator,
+) -> VMResult<()> {
+    let owner = validator.owner_address;
+    let operator = validator.operator_address;
+    for address in [owner, operator, validator.voter_address] {
+        exec_function(
+            session,
+            "account",
+            "create_account_if_does_not_exist",
+            vec![],
+            vec![MoveValue::Address(address)],
+        )?;
+    }
+    exec_function(
+        session,
+        "coin",
+        "register",
+        vec![APTOS_COIN_TYPE.clone()],
+        vec![MoveValue::Signer(owner)],
+    )?;
+    // The mint capability of genesis is destroyed at its end, the one used for storage refunds
+    // is left.
+    exec_function(session, "transaction_fee", "mint_and_refund", vec![], vec![
+        MoveValue::Address(owner),
+        MoveValue::U64(validator.stake_amount),
+    ])?;
+
+    exec_function(session, "stake", "initialize_stake_owner", vec![], vec![
+        MoveValue::Signer(owner),
+        MoveValue::U64(validator.stake_amount),
+        MoveValue::Address(operator),
+        MoveValue::Address(validator.voter_address),
+    ])?;
+    exec_function(session, "stake", "rotate_consensus_key", vec![], vec![
+        MoveValue::Signer(operator),
+        MoveValue::Address(owner),
+        MoveValue::vector_u8(validator.consensus_pubkey.clone()),
+        MoveValue::vector_u8(validator.proof_of_possession.clone()),
+    ])?;
+    exec_function(
+        session,
+        "stake",
+        "update_network_and_fullnode_addresses",
+        vec![],
+        vec![
+            MoveValue::Signer(operator),
+            MoveValue::Address(owner),
+            MoveValue::vector_u8(validator.network_addresses.clone()),
+            MoveValue::vector_u8(validator.full_node_network_addresses.clone()),
+        ],
+    )?;
+    exec_function(
+        session,
+        "stake",
+        "join_validator_set_internal",
+        vec![],
+        vec![MoveValue::Signer(operator), MoveValue::Address(owner)],
+    )
+}
+
+fn exec_function(
+    session: &mut SessionExt,
+    module_name: &str,
+    function_name: &str,
+    ty_args: Vec<TypeTag>,
+    args: Vec<MoveValue>,
+) -> VMResult<()> {
+    session.execute_function_bypass_visibility(
+        &ModuleId::new(CORE_CODE_ADDRESS, Identifier::new(module_name).unwrap()),
+        &Identifier::new(function_name).unwrap(),
+        ty_args,
+        args.into_iter()
+            .map(|arg| arg.simple_serialize().unwrap())
+            .collect::<Vec<_>>(),
+        &mut UnmeteredGasMeter,
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{generate_test_genesis, genesis_context::GenesisStateView, TestValidator};
+    use aptos_types::on_chain_config::ConfigurationResource;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    #[test]
+    fn test_regenesis() {
+        let framework = aptos_cached_packages::head_release_bundle();
+        let (genesis, _) = generate_test_genesis(framework, Some(4));
+        let empty_state_view = GenesisStateView::new();
+        let state_view = RegenesisStateView {
+            base: &empty_state_view,
+            write_set: genesis.write_set().clone().into_mut(),
+        };
+
+        let mut rng = StdRng::from_seed([2u8; 32]);
+        let validators: Vec<_> = (0..2)
+            .map(|_| TestValidator::gen(&mut rng, Some(100_000_000)).data)
+            .collect();
+        let chain_id = ChainId::new(42);
+        let change_set =
+            encode_regenesis_change_set(&state_view, &validators, chain_id, Some(framework))
+                .unwrap();
+
+        let regenesis_state_view = RegenesisStateView {
+            base: &state_view,
+            write_set: change_set.write_set().clone().into_mut(),
+        };
+        let resolver = regenesis_state_view.as_move_resolver();
+        assert_eq!(ChainId::fetch_config(&resolver), Some(chain_id));
+        assert_eq!(
+            ConfigurationResource::fetch_config(&resolver)
+                .unwrap()
+                .epoch(),
+            2
+        );
+        assert_eq!(
+            ValidatorSet::fetch_config(&resolver)
+                .unwrap()
+                .active_validators()
+                .into_iter()
+                .collect::<BTreeSet<_>>(),
+            validators
+                .iter()
+                .map(|validator| validator.owner_address)
+                .collect()
+        );
+        assert_eq!(
+            change_set
+                .events()
+                .iter()
+                .filter(|event| event.event_key() == Some(&NewEpochEvent::event_key()))
+                .count(),
+            1
+        );
+    }
+}
@@ -0,0 +1,194 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::anyhow;
+use aptos_config::config::{
+    ApiConfig, AptosDataPollerConfig, MempoolConfig, NodeConfig, NodeConfigChanges,
+};
+use aptos_infallible::Mutex;
+use aptos_logger::{error, info, LoggerFilterUpdater};
+use serde_yaml::Value;
+use std::{fs, path::PathBuf, sync::Arc};
+use tokio::{runtime::Runtime, sync::watch};
+
+/// Reloads the node config file at runtime, and applies the changes to the
+/// hot-reloadable fields (see `HOT_RELOADABLE_CONFIG_FIELDS`) through watch
+/// channels. Reloads that change any other fields are refused entirely.
+pub struct NodeConfigReloader {
+    config_path: Option<PathBuf>, // The path of the node config file (if any)
+    node_config: Mutex<NodeConfig>, // The last applied node config
+    api_config_sender: watch::Sender<ApiConfig>,
+    data_poller_config_sender: watch::Sender<AptosDataPollerConfig>,
+    logger_filter_updater: Option<LoggerFilterUpdater>,
+    mempool_config_sender: watch::Sender<MempoolConfig>,
+}
+
+impl NodeConfigReloader {
+    pub fn new(
+        config_path: Option<PathBuf>,
+        node_config: &NodeConfig,
+        logger_filter_updater: Option<LoggerFilterUpdater>,
+    ) -> Self {
+        let (api_config_sender, _) = watch::channel(node_config.api.clone());
+        let (data_poller_config_sender, _) =
+            watch::channel(node_config.state_sync.aptos_data_client.data_poller_config);
+        let (mempool_config_sender, _) = watch::channel(node_config.mempool.clone());
+
+        Self {
+            config_path,
+            node_config: Mutex::new(node_config.clone()),
+            api_config_sender,
+            data_poller_config_sender,
+            logger_filter_updater,
+            mempool_config_sender,
+        }
+    }
+
+    /// Returns a receiver for the API config updates
+    pub fn subscribe_to_api_config(&self) -> watch::Receiver<ApiConfig> {
+        self.api_config_sender.subscribe()
+    }
+
+    /// Returns a receiver for the data poller config updates
+    pub fn subscribe_to_data_poller_config(&self) -> watch::Receiver<AptosDataPollerConfig> {
+        self.data_poller_config_sender.subscribe()
+    }
+
+    /// Returns a receiver for the mempool config updates
+    pub fn subscribe_to_mempool_config(&self) -> watch::Receiver<MempoolConfig> {
+        self.mempool_config_sender.subscribe()
+    }
+
+    /// Re-reads the node config file and applies the changes. Returns the
+    /// changed fields if they were applied, or an error if the new config is
+    /// invalid or contains changes that require a restart.
+    pub fn reload(&self) -> anyhow::Result<Vec<String>> {
+        let config_path = self
+            .config_path
+            .as_ref()
+            .ok_or_else(|| anyhow!("The node wasn't started from a config file!"))?;
+
+        // Load the new config (this also runs the config sanitizers)
+        let mut new_node_config = NodeConfig::load_from_path(config_path).map_err(|error| {
+            anyhow!(
+                "Failed to load the node config file: {:?}. Error: {:?}",
+                config_path.display(),
+                error
+            )
+        })?;
+        let config_yaml: Value = serde_yaml::from_str(&fs::read_to_string(config_path)?)?;
+
+        // Identify the changed fields, and refuse changes that require a restart
+        let mut node_config = self.node_config.lock();
+        keep_generated_identities(&config_yaml, &node_config, &mut new_node_config);
+        let config_changes = NodeConfigChanges::new(&node_config, &new_node_config)?;
+        if config_changes.requires_restart() {
+            return Err(anyhow!(
+                "The node config was not reloaded! The following fields require a restart: {:?}",
+                config_changes.restart_required_fields
+            ));
+        }
+
+        // Apply the changes to the reloadable sections
+        self.api_config_sender
+            .send_replace(new_node_config.api.clone());
+        self.data_poller_config_sender.send_replace(
+            new_node_config
+                .state_sync
+                .aptos_data_client
+                .data_poller_config,
+        );
+        self.mempool_config_sender
+            .send_replace(new_node_config.mempool.clone());
+        if let Some(logger_filter_updater) = &self.logger_filter_updater {
+            logger_filter_updater.update_levels(
+                new_node_config.logger.level,
+                new_node_config.logger.telemetry_level,
+            );
+        }
+        *node_config = new_node_config;
+
+        info!(
+            "Reloaded the node config. Applied fields: {:?}",
+            config_changes.reloadable_fields
+        );
+        Ok(config_changes.reloadable_fields)
+    }
+}
+
+/// Networks without an identity in the config file are given a random identity
+/// whenever the file is loaded. This keeps the identities that the node is running
+/// with for those networks, so that reloading the same file isn't seen as an
+/// identity change (which would require a restart).
+fn keep_generated_identities(
+    config_yaml: &Value,
+    node_config: &NodeConfig,
+    new_node_config: &mut NodeConfig,
+) {
+    if let (Some(network), Some(new_network)) = (
+        &node_config.validator_network,
+        &mut new_node_config.validator_network,
+    ) {
+        if !has_identity(&config_yaml["validator_network"]) {
+            new_network.identity = network.identity.clone();
+        }
+    }
+
+    // The networks are loaded in the order they appear in the file
+    for (index, new_network) in new_node_config.full_node_networks.iter_mut().enumerate() {
+        let network = node_config
+            .full_node_networks
+            .iter()
+            .find(|network| network.network_id == new_network.network_id);
+        if let Some(network) = network {
+            if !has_identity(&config_yaml["full_node_networks"][index]) {
+                new_network.identity = network.identity.clone();
+            }
+        }
+    }
+}
+
+/// Returns true iff the given network config (in YAML) specifies an identity
+fn has_identity(network_yaml: &Value) -> bool {
+    match network_yaml.get("identity") {
+        Some(identity) => identity.get("type").and_then(Value::as_str) != Some("none"),
+        None => false,
+    }
+}
+
+/// Starts a runtime that reloads the node config whenever the node receives a
+/// SIGHUP signal. Returns None if the node wasn't started from a config file.
+pub fn start_config_reload_listener(config_reloader: Arc<NodeConfigReloader>) -> Option<Runtime> {
+    config_reloader.config_path.as_ref()?;
+
+    let runtime = aptos_runtimes::spawn_named_runtime("config-reload".into(), Some(1));
+    runtime.spawn(listen_for_hangup_signals(config_reloader));
+    Some(runtime)
+}
+
+/// Reloads the node config on every SIGHUP signal
+#[cfg(unix)]
+async fn listen_for_hangup_signals(config_reloader: Arc<NodeConfigReloader>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangup_signals = match signal(SignalKind::hangup()) {
+        Ok(hangup_signals) => hangup_signals,
+        Err(error) => {
+            error!("Failed to listen for SIGHUP signals! Error: {:?}", error);
+            return;
+        },
+    };
+    while hangup_signals.recv().await.is_some() {
+        info!("Received SIGHUP, reloading the node config.");
+        if let Err(error) = config_reloader.reload() {
+            error!("Failed to reload the node config! Error: {:?}", error);
+        }
+    }
+}
+
+/// SIGHUP is only supported on unix. The config can still be
+/// reloaded through the admin service.
+#[cfg(not(unix))]
+async fn listen_for_hangup_signals(_config_reloader: Arc<NodeConfigReloader>) {
+    info!("SIGHUP config reloading is not supported on this platform.");
+}
@@ -4,6 +4,7 @@
 
 #![forbid(unsafe_code)]
 
+mod config_reloader;
 mod indexer;
 mod logger;
 mod network;
@@ -15,6 +16,7 @@ pub mod utils;
 #[cfg(test)]
 mod tests;
 
+use crate::config_reloader::NodeConfigReloader;
 use anyhow::anyhow;
 use aptos_admin_service::AdminService;
 use aptos_api::bootstrap as bootstrap_api;
@@ -152,7 +154,7 @@ impl AptosNodeArgs {
             });
 
             // Start the node
-            start(config, None, true).expect("Node should start correctly");
+            start(config, Some(config_path), None, true).expect("Node should start correctly");
         };
     }
 }
@@ -167,6 +169,7 @@ pub struct AptosHandle {
     _admin_service: AdminService,
     _api_runtime: Option<Runtime>,
     _backup_runtime: Option<Runtime>,
+    _config_reload_runtime: Option<Runtime>,
     _consensus_runtime: Option<Runtime>,
     _indexer_grpc_runtime: Option<Runtime>,
     _indexer_runtime: Option<Runtime>,
@@ -177,9 +180,11 @@ pub struct AptosHandle {
     _telemetry_runtime: Option<Runtime>,
 }
 
-/// Start an Aptos node
+/// Start an Aptos node. If the config path is given, the node config
+/// can be reloaded at runtime (see `config_reloader`).
 pub fn start(
     config: NodeConfig,
+    config_path: Option<PathBuf>,
     log_file: Option<PathBuf>,
     create_global_rayon_pool: bool,
 ) -> anyhow::Result<()> {
@@ -220,8 +225,12 @@ pub fn start(
     }
 
     // Set up the node environment and start it
-    let _node_handle =
-        setup_environment_and_start_node(config, remote_log_receiver, Some(logger_filter_update))?;
+    let _node_handle = setup_environment_and_start_node(
+        config,
+        config_path,
+        remote_log_receiver,
+        Some(logger_filter_update),
+    )?;
     let term = Arc::new(AtomicBool::new(false));
     while !term.load(Ordering::Acquire) {
         thread::park();
@@ -315,7 +324,7 @@ pub fn start_test_environment_node(
     }
     println!("\nAptos is running, press ctrl-c to exit\n");
 
-    start(config, Some(log_file), false)
+    start(config, None, Some(log_file), false)
 }
 
 /// Creates a simple test environment and starts the node.
@@ -531,15 +540,29 @@ where
 /// Initializes the node environment and starts the node
 pub fn setup_environment_and_start_node(
     mut node_config: NodeConfig,
+    config_path: Option<PathBuf>,
     remote_log_rx: Option<mpsc::Receiver<TelemetryLog>>,
     logger_filter_update_job: Option<LoggerFilterUpdater>,
 ) -> anyhow::Result<AptosHandle> {
     // Log the node config at node startup
     info!("Using node config {:?}", &node_config);
 
+    // Create the config reloader (before the node config is modified below)
+    let config_reloader = Arc::new(NodeConfigReloader::new(
+        config_path,
+        &node_config,
+        logger_filter_update_job.clone(),
+    ));
+
     // Starts the admin service
     let admin_service = services::start_admin_service(&node_config);
 
+    // Reload the node config on SIGHUP, or through the admin service
+    let config_reload_runtime =
+        config_reloader::start_config_reload_listener(config_reloader.clone());
+    let reloader = config_reloader.clone();
+    admin_service.set_config_reloader(Arc::new(move || reloader.reload()));
+
     // Set up the storage database and any RocksDB checkpoints
     let (aptos_db, db_rw, backup_service, genesis_waypoint) =
         storage::initialize_database_and_checkpoints(&mut node_config)?;
@@ -600,6 +623,7 @@ pub fn setup_environment_and_start_node(
             genesis_waypoint,
             event_subscription_service,
             db_rw.clone(),
+            config_reloader.subscribe_to_data_poller_config(),
         )?;
 
     // Start the node inspection service
@@ -611,7 +635,12 @@ pub fn setup_environment_and_start_node(
 
     // Bootstrap the API and indexer
     let (mempool_client_receiver, api_runtime, indexer_runtime, indexer_grpc_runtime) =
-        services::bootstrap_api_and_indexer(&node_config, aptos_db, chain_id)?;
+        services::bootstrap_api_and_indexer(
+            &node_config,
+            aptos_db,
+            chain_id,
+            config_reloader.subscribe_to_api_config(),
+        )?;
 
     // Create mempool and get the consensus to mempool sender
     let (mempool_runtime, consensus_to_mempool_sender) =
@@ -623,6 +652,7 @@ pub fn setup_environment_and_start_node(
             mempool_listener,
             mempool_client_receiver,
             peers_and_metadata,
+            config_reloader.subscribe_to_mempool_config(),
         );
 
     // Create the consensus runtime (this blocks on state sync first)
@@ -649,6 +679,7 @@ pub fn setup_environment_and_start_node(
         _admin_service: admin_service,
         _api_runtime: api_runtime,
         _backup_runtime: backup_service,
+        _config_reload_runtime: config_reload_runtime,
         _consensus_runtime: consensus_runtime,
         _indexer_grpc_runtime: indexer_grpc_runtime,
         _indexer_runtime: indexer_runtime,
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::network::ApplicationNetworkInterfaces;
-use aptos_config::config::{NodeConfig, StateSyncConfig};
+use aptos_config::config::{AptosDataPollerConfig, NodeConfig, StateSyncConfig};
 use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_data_client::{client::AptosDataClient, poller};
 use aptos_data_streaming_service::{
@@ -34,7 +34,7 @@ use aptos_time_service::TimeService;
 use aptos_types::waypoint::Waypoint;
 use aptos_vm::AptosVM;
 use std::sync::Arc;
-use tokio::runtime::Runtime;
+use tokio::{runtime::Runtime, sync::watch};
 
 /// Creates the event subscription service and two reconfiguration
 /// notification listeners (for mempool and consensus, respectively).
@@ -80,6 +80,7 @@ pub fn start_state_sync_and_get_notification_handles(
     waypoint: Waypoint,
     event_subscription_service: EventSubscriptionService,
     db_rw: DbReaderWriter,
+    data_poller_config_updates: watch::Receiver<AptosDataPollerConfig>,
 ) -> anyhow::Result<(
     AptosDataClient,
     StateSyncRuntimes,
@@ -92,8 +93,12 @@ pub fn start_state_sync_and_get_notification_handles(
 
     // Start the data client
     let peers_and_metadata = network_client.get_peers_and_metadata();
-    let (aptos_data_client, aptos_data_client_runtime) =
-        setup_aptos_data_client(node_config, network_client, db_rw.reader.clone())?;
+    let (aptos_data_client, aptos_data_client_runtime) = setup_aptos_data_client(
+        node_config,
+        network_client,
+        db_rw.reader.clone(),
+        data_poller_config_updates,
+    )?;
 
     // Start the data streaming service
     let (streaming_service_client, streaming_service_runtime) =
@@ -186,6 +191,7 @@ fn setup_aptos_data_client(
     node_config: &NodeConfig,
     network_client: NetworkClient<StorageServiceMessage>,
     storage: Arc<dyn DbReader>,
+    data_poller_config_updates: watch::Receiver<AptosDataPollerConfig>,
 ) -> anyhow::Result<(AptosDataClient, Runtime)> {
     // Create the storage service client
     let storage_service_client = StorageServiceClient::new(network_client);
@@ -202,6 +208,7 @@ fn setup_aptos_data_client(
         storage_service_client,
         Some(aptos_data_client_runtime.handle().clone()),
     );
+    let data_summary_poller = data_summary_poller.with_config_updates(data_poller_config_updates);
     aptos_data_client_runtime.spawn(poller::start_poller(data_summary_poller));
 
     Ok((aptos_data_client, aptos_data_client_runtime))
@@ -1,15 +1,20 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{create_single_node_test_config, network};
-use aptos_config::config::{NodeConfig, WaypointConfig};
+use crate::{config_reloader::NodeConfigReloader, create_single_node_test_config, network};
+use aptos_admin_service::AdminService;
+use aptos_config::{
+    config::{NodeConfig, WaypointConfig},
+    utils::get_available_port,
+};
 use aptos_event_notifications::EventSubscriptionService;
 use aptos_infallible::RwLock;
 use aptos_storage_interface::{DbReader, DbReaderWriter, DbWriter};
 use aptos_temppath::TempPath;
 use aptos_types::{chain_id::ChainId, waypoint::Waypoint};
+use hyper::{Body, Client, Request, StatusCode};
 use rand::SeedableRng;
-use std::{fs, sync::Arc};
+use std::{fs, path::Path, sync::Arc, time::Duration};
 
 /// A mock database implementing DbReader and DbWriter
 pub struct MockDatabase;
@@ -110,3 +115,132 @@ fn test_create_single_node_test_config() {
             .bootstrapping_mode
     );
 }
+
+#[test]
+fn test_reload_config_without_identity() {
+    // Create a public fullnode config without an identity for the public network
+    let temp_dir = TempPath::new();
+    temp_dir.create_as_dir().unwrap();
+    let config_path = temp_dir.path().join("fullnode.yaml");
+    let admin_service_port = get_available_port();
+    write_fullnode_config(&config_path, temp_dir.path(), admin_service_port, 100);
+
+    // Create the config reloader for the config the node starts with
+    let node_config = NodeConfig::load_from_path(&config_path).unwrap();
+    let config_reloader = NodeConfigReloader::new(Some(config_path.clone()), &node_config, None);
+    let mut api_config_receiver = config_reloader.subscribe_to_api_config();
+
+    // Reload the unchanged config. The public network is given a new random
+    // identity on every load, but this must not be seen as a change.
+    assert!(config_reloader.reload().unwrap().is_empty());
+
+    // Change a reloadable field and verify the change is applied
+    write_fullnode_config(&config_path, temp_dir.path(), admin_service_port, 50);
+    assert_eq!(config_reloader.reload().unwrap(), vec![
+        "api.max_transactions_page_size".to_string()
+    ]);
+    assert_eq!(
+        api_config_receiver
+            .borrow_and_update()
+            .max_transactions_page_size,
+        50
+    );
+
+    // Change a field that requires a restart and verify the reload is refused
+    fs::write(
+        &config_path,
+        fs::read_to_string(&config_path)
+            .unwrap()
+            .replace("max_transactions_page_size: 50", "address: 0.0.0.0:9999"),
+    )
+    .unwrap();
+    assert!(config_reloader.reload().is_err());
+    assert_eq!(
+        api_config_receiver
+            .borrow_and_update()
+            .max_transactions_page_size,
+        50
+    );
+}
+
+#[test]
+fn test_reload_config_through_admin_service() {
+    // Create a public fullnode config without an identity for the public network
+    let temp_dir = TempPath::new();
+    temp_dir.create_as_dir().unwrap();
+    let config_path = temp_dir.path().join("fullnode.yaml");
+    let admin_service_port = get_available_port();
+    write_fullnode_config(&config_path, temp_dir.path(), admin_service_port, 100);
+
+    // Start the admin service with the config reloader
+    let node_config = NodeConfig::load_from_path(&config_path).unwrap();
+    let admin_service = AdminService::new(&node_config);
+    let config_reloader = Arc::new(NodeConfigReloader::new(
+        Some(config_path.clone()),
+        &node_config,
+        None,
+    ));
+    let reloader = config_reloader.clone();
+    admin_service.set_config_reloader(Arc::new(move || reloader.reload()));
+    let reload_config_url = format!("http://127.0.0.1:{}/reload_config", admin_service_port);
+
+    // Reload the unchanged config and verify nothing is applied
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let (status, body) = runtime.block_on(send_post_request(&reload_config_url));
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(body, "Applied config fields: []");
+
+    // Change a reloadable field and verify the change is applied
+    write_fullnode_config(&config_path, temp_dir.path(), admin_service_port, 50);
+    let (status, body) = runtime.block_on(send_post_request(&reload_config_url));
+    assert_eq!(status, StatusCode::OK, "{}", body);
+    assert_eq!(
+        body,
+        "Applied config fields: [\"api.max_transactions_page_size\"]"
+    );
+}
+
+/// Writes a public fullnode config (without a network identity) to the given path
+fn write_fullnode_config(
+    config_path: &Path,
+    data_dir: &Path,
+    admin_service_port: u16,
+    max_transactions_page_size: u16,
+) {
+    let config = format!(
+        r#"
+        base:
+            role: "full_node"
+            data_dir: "{}"
+        full_node_networks:
+            - network_id: "public"
+              discovery_method: "onchain"
+        admin_service:
+            enabled: true
+            address: "127.0.0.1"
+            port: {}
+        api:
+            max_transactions_page_size: {}
+        "#,
+        data_dir.display(),
+        admin_service_port,
+        max_transactions_page_size
+    );
+    fs::write(config_path, config).unwrap();
+}
+
+/// Sends a POST request to the given URL, and returns the response status and
+/// body. Retries until the server is up.
+async fn send_post_request(url: &str) -> (StatusCode, String) {
+    let client = Client::new();
+    for _ in 0..50 {
+        let request = Request::post(url).body(Body::empty()).unwrap();
+        if let Ok(response) = client.request(request).await {
+            let status = response.status();
+            let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+            return (status, String::from_utf8(body.to_vec()).unwrap());
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!("Failed to send a request to {}!", url);
+}
@@ -4,8 +4,9 @@
 
 use crate::{
     config::{
-        config_sanitizer::ConfigSanitizer, gas_estimation_config::GasEstimationConfig,
-        node_config_loader::NodeType, Error, NodeConfig,
+        api_rate_limit_config::ApiRateLimitConfig, config_sanitizer::ConfigSanitizer,
+        gas_estimation_config::GasEstimationConfig, node_config_loader::NodeType, Error,
+        NodeConfig,
     },
     utils,
 };
@@ -72,6 +73,8 @@ pub struct ApiConfig {
     pub runtime_worker_multiplier: usize,
     /// Configs for computing unit gas price estimation
     pub gas_estimation: GasEstimationConfig,
+    /// Configs for rate limiting requests per client
+    pub rate_limit: ApiRateLimitConfig,
 }
 
 const DEFAULT_ADDRESS: &str = "127.0.0.1";
@@ -116,6 +119,7 @@ impl Default for ApiConfig {
             max_runtime_workers: None,
             runtime_worker_multiplier: 2,
             gas_estimation: GasEstimationConfig::default(),
+            rate_limit: ApiRateLimitConfig::default(),
         }
     }
 }
@@ -164,6 +168,7 @@ impl ConfigSanitizer for ApiConfig {
         }
 
         GasEstimationConfig::sanitize(node_config, node_type, chain_id)?;
+        ApiRateLimitConfig::sanitize(node_config, node_type, chain_id)?;
 
         Ok(())
     }
@@ -0,0 +1,258 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{
+    config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, Error, NodeConfig,
+};
+use aptos_types::chain_id::ChainId;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+const DEFAULT_API_KEY_HEADER: &str = "x-api-key";
+
+/// A token bucket: `bucket_size` requests can be made in a burst, after which the bucket
+/// refills with `fill_rate` requests per second.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenBucketConfig {
+    /// Maximum number of requests in a burst
+    pub bucket_size: usize,
+    /// Number of requests per second the bucket refills with
+    pub fill_rate: usize,
+}
+
+impl TokenBucketConfig {
+    pub const fn new(bucket_size: usize, fill_rate: usize) -> Self {
+        Self {
+            bucket_size,
+            fill_rate,
+        }
+    }
+}
+
+/// The request budgets of a client. Requests to an expensive route use up both the budget
+/// for all requests and the budget for that route.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RateLimits {
+    /// Limit for all requests
+    pub all: TokenBucketConfig,
+    /// Limit for view function requests (`POST /view`)
+    pub view: Option<TokenBucketConfig>,
+    /// Limit for transaction simulation requests (`POST /transactions/simulate`)
+    pub simulate: Option<TokenBucketConfig>,
+    /// Limit for transaction submission requests (`POST /transactions` and
+    /// `POST /transactions/batch`)
+    pub submit: Option<TokenBucketConfig>,
+}
+
+impl Default for RateLimits {
+    fn default() -> RateLimits {
+        RateLimits {
+            all: TokenBucketConfig::new(200, 100),
+            view: Some(TokenBucketConfig::new(40, 20)),
+            simulate: Some(TokenBucketConfig::new(20, 10)),
+            submit: Some(TokenBucketConfig::new(40, 20)),
+        }
+    }
+}
+
+impl RateLimits {
+    fn buckets(&self) -> impl Iterator<Item = (&'static str, &TokenBucketConfig)> {
+        [
+            ("all", Some(&self.all)),
+            ("view", self.view.as_ref()),
+            ("simulate", self.simulate.as_ref()),
+            ("submit", self.submit.as_ref()),
+        ]
+        .into_iter()
+        .filter_map(|(route, bucket)| bucket.map(|bucket| (route, bucket)))
+    }
+}
+
+/// An API key and the request budgets of the clients using it
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiKeyConfig {
+    /// Name of the key, used in logs and metrics instead of the key itself
+    pub name: String,
+    /// The key, as sent by clients in the API key header
+    pub key: String,
+    /// Limits for all the requests made with the key
+    #[serde(default)]
+    pub limits: RateLimits,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiRateLimitConfig {
+    /// Enables rate limiting of API requests. Rejected requests get a 429 response with a
+    /// `Retry-After` header.
+    pub enabled: bool,
+    /// Header clients send their API key in
+    pub api_key_header: String,
+    /// Identifies clients by the last address of the `X-Forwarded-For` header, i.e. the one
+    /// appended by the load balancer, instead of the address of the connection. Only enable
+    /// this behind a single trusted load balancer that appends to the header.
+    pub use_x_forwarded_for: bool,
+    /// Limits for each client IP address, for requests without a known API key
+    pub per_ip: RateLimits,
+    /// API keys with their own limits
+    pub api_keys: Vec<ApiKeyConfig>,
+}
+
+impl Default for ApiRateLimitConfig {
+    fn default() -> ApiRateLimitConfig {
+        ApiRateLimitConfig {
+            enabled: false,
+            api_key_header: DEFAULT_API_KEY_HEADER.to_string(),
+            use_x_forwarded_for: false,
+            per_ip: RateLimits::default(),
+            api_keys: vec![],
+        }
+    }
+}
+
+impl ConfigSanitizer for ApiRateLimitConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        _node_type: NodeType,
+        _chain_id: ChainId,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let rate_limit_config = &node_config.api.rate_limit;
+
+        // If rate limiting is disabled, we don't need to do anything
+        if !rate_limit_config.enabled {
+            return Ok(());
+        }
+
+        // Verify the API key header is a valid header name (i.e., an HTTP token)
+        let api_key_header = &rate_limit_config.api_key_header;
+        let is_valid_header_name = !api_key_header.is_empty()
+            && api_key_header
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte));
+        if !is_valid_header_name {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "api_key_header {} is not a valid header name!",
+                    rate_limit_config.api_key_header
+                ),
+            ));
+        }
+
+        // Verify the API keys are unique, so every request maps to a single key
+        let mut keys = HashSet::new();
+        for api_key in &rate_limit_config.api_keys {
+            if !keys.insert(&api_key.key) {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!("API key {} is configured more than once!", api_key.name),
+                ));
+            }
+        }
+
+        // Verify the buckets can be used by the token bucket rate limiter
+        let limits = std::iter::once(("per_ip", &rate_limit_config.per_ip)).chain(
+            rate_limit_config
+                .api_keys
+                .iter()
+                .map(|api_key| (api_key.name.as_str(), &api_key.limits)),
+        );
+        for (client, limits) in limits {
+            for (route, bucket) in limits.buckets() {
+                if bucket.fill_rate == 0 || bucket.bucket_size < bucket.fill_rate {
+                    return Err(Error::ConfigSanitizerFailed(
+                        sanitizer_name,
+                        format!(
+                            "The {} limit of {} must have a fill_rate greater than 0 and a bucket_size of at least the fill_rate!",
+                            route, client
+                        ),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ApiConfig;
+
+    fn node_config_with_rate_limit(rate_limit: ApiRateLimitConfig) -> NodeConfig {
+        NodeConfig {
+            api: ApiConfig {
+                rate_limit,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_sanitize_default_limits() {
+        // Create a node config with rate limiting enabled
+        let node_config = node_config_with_rate_limit(ApiRateLimitConfig {
+            enabled: true,
+            ..Default::default()
+        });
+
+        // Sanitize the config and verify that it succeeds
+        ApiRateLimitConfig::sanitize(&node_config, NodeType::PublicFullnode, ChainId::mainnet())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_invalid_bucket() {
+        // Create a node config with a bucket smaller than its fill rate
+        let node_config = node_config_with_rate_limit(ApiRateLimitConfig {
+            enabled: true,
+            per_ip: RateLimits {
+                view: Some(TokenBucketConfig::new(5, 10)),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+
+        // Sanitize the config and verify that it fails
+        let error = ApiRateLimitConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_duplicate_api_keys() {
+        // Create a node config with the same API key twice
+        let api_key = ApiKeyConfig {
+            name: "indexer".into(),
+            key: "secret".into(),
+            limits: RateLimits::default(),
+        };
+        let node_config = node_config_with_rate_limit(ApiRateLimitConfig {
+            enabled: true,
+            api_keys: vec![api_key.clone(), ApiKeyConfig {
+                name: "wallet".into(),
+                ..api_key
+            }],
+            ..Default::default()
+        });
+
+        // Sanitize the config and verify that it fails
+        let error = ApiRateLimitConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+}
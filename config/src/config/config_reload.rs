@@ -0,0 +1,187 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::{Error, NodeConfig};
+use serde_yaml::Value;
+
+/// The node config fields that can be changed at runtime, without restarting
+/// the node. Each entry is the path of a field in the node config file.
+pub const HOT_RELOADABLE_CONFIG_FIELDS: &[&str] = &[
+    "api.max_account_modules_page_size",
+    "api.max_account_resources_page_size",
+    "api.max_events_page_size",
+    "api.max_submit_transaction_batch_size",
+    "api.max_transactions_page_size",
+    "logger.level",
+    "logger.telemetry_level",
+    "mempool.capacity",
+    "mempool.capacity_bytes",
+    "mempool.capacity_per_user",
+    "state_sync.aptos_data_client.data_poller_config.additional_polls_per_peer_bucket",
+    "state_sync.aptos_data_client.data_poller_config.max_num_in_flight_priority_polls",
+    "state_sync.aptos_data_client.data_poller_config.max_num_in_flight_regular_polls",
+    "state_sync.aptos_data_client.data_poller_config.max_polls_per_second",
+    "state_sync.aptos_data_client.data_poller_config.min_polls_per_second",
+    "state_sync.aptos_data_client.data_poller_config.peer_bucket_size",
+];
+
+/// The fields that differ between two node configs, split by whether
+/// or not they can be hot-reloaded.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct NodeConfigChanges {
+    /// The changed fields that can be applied at runtime
+    pub reloadable_fields: Vec<String>,
+    /// The changed fields that require a node restart
+    pub restart_required_fields: Vec<String>,
+}
+
+impl NodeConfigChanges {
+    /// Returns the changes from the current node config to the new one
+    pub fn new(current_config: &NodeConfig, new_config: &NodeConfig) -> Result<Self, Error> {
+        // Serialize both configs, so that every field can be compared by its path
+        let current_config = serialize_node_config(current_config)?;
+        let new_config = serialize_node_config(new_config)?;
+
+        // Identify all changed fields
+        let mut changed_fields = vec![];
+        find_changed_fields("", &current_config, &new_config, &mut changed_fields);
+
+        // Split the changed fields by whether or not they can be reloaded
+        let (reloadable_fields, restart_required_fields) = changed_fields
+            .into_iter()
+            .partition(|field| HOT_RELOADABLE_CONFIG_FIELDS.contains(&field.as_str()));
+        Ok(Self {
+            reloadable_fields,
+            restart_required_fields,
+        })
+    }
+
+    /// Returns true iff no fields changed
+    pub fn is_empty(&self) -> bool {
+        self.reloadable_fields.is_empty() && self.restart_required_fields.is_empty()
+    }
+
+    /// Returns true iff at least one changed field requires a restart
+    pub fn requires_restart(&self) -> bool {
+        !self.restart_required_fields.is_empty()
+    }
+}
+
+/// Serializes the given node config into a YAML value
+fn serialize_node_config(node_config: &NodeConfig) -> Result<Value, Error> {
+    serde_yaml::to_value(node_config)
+        .map_err(|error| Error::Yaml("Failed to serialize the node config".into(), error))
+}
+
+/// Recursively compares the given YAML values and appends the paths of
+/// all fields that differ. Maps are compared field by field, and all
+/// other values (including sequences) are compared as a whole.
+fn find_changed_fields(
+    path: &str,
+    current_value: &Value,
+    new_value: &Value,
+    changed_fields: &mut Vec<String>,
+) {
+    match (current_value, new_value) {
+        (Value::Mapping(current_map), Value::Mapping(new_map)) => {
+            // Compare all fields in the current map
+            for (key, current_field) in current_map {
+                let field_path = get_field_path(path, key);
+                match new_map.get(key) {
+                    Some(new_field) => {
+                        find_changed_fields(&field_path, current_field, new_field, changed_fields)
+                    },
+                    None => changed_fields.push(field_path),
+                }
+            }
+
+            // Identify any fields that only exist in the new map
+            for (key, _) in new_map {
+                if !current_map.contains_key(key) {
+                    changed_fields.push(get_field_path(path, key));
+                }
+            }
+        },
+        (current_value, new_value) => {
+            if current_value != new_value {
+                changed_fields.push(path.to_string());
+            }
+        },
+    }
+}
+
+/// Returns the path of the field with the given key in the map at the given path
+fn get_field_path(path: &str, key: &Value) -> String {
+    let key = match key {
+        Value::String(key) => key.clone(),
+        key => serde_yaml::to_string(key)
+            .map(|key| key.trim().to_string())
+            .unwrap_or_default(),
+    };
+    if path.is_empty() {
+        key
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NodeConfig;
+    use aptos_logger::Level;
+
+    #[test]
+    fn test_no_config_changes() {
+        // Compare identical configs
+        let node_config = NodeConfig::default();
+        let config_changes = NodeConfigChanges::new(&node_config, &node_config).unwrap();
+
+        // Verify that no changes were found
+        assert!(config_changes.is_empty());
+        assert!(!config_changes.requires_restart());
+    }
+
+    #[test]
+    fn test_reloadable_config_changes() {
+        // Change several reloadable fields
+        let node_config = NodeConfig::default();
+        let mut new_node_config = node_config.clone();
+        new_node_config.mempool.capacity += 1;
+        new_node_config.logger.level = Level::Debug;
+        new_node_config
+            .state_sync
+            .aptos_data_client
+            .data_poller_config
+            .max_num_in_flight_regular_polls += 1;
+
+        // Verify that the changes can all be reloaded
+        let config_changes = NodeConfigChanges::new(&node_config, &new_node_config).unwrap();
+        assert!(!config_changes.requires_restart());
+        assert_eq!(config_changes.reloadable_fields, vec![
+            "logger.level".to_string(),
+            "mempool.capacity".to_string(),
+            "state_sync.aptos_data_client.data_poller_config.max_num_in_flight_regular_polls"
+                .to_string(),
+        ]);
+    }
+
+    #[test]
+    fn test_restart_required_config_changes() {
+        // Change a reloadable field and a field that requires a restart
+        let node_config = NodeConfig::default();
+        let mut new_node_config = node_config.clone();
+        new_node_config.api.max_events_page_size += 1;
+        new_node_config.api.enabled = !node_config.api.enabled;
+
+        // Verify that only the reloadable field can be reloaded
+        let config_changes = NodeConfigChanges::new(&node_config, &new_node_config).unwrap();
+        assert!(config_changes.requires_restart());
+        assert_eq!(config_changes.reloadable_fields, vec![
+            "api.max_events_page_size".to_string()
+        ]);
+        assert_eq!(config_changes.restart_required_fields, vec![
+            "api.enabled".to_string()
+        ]);
+    }
+}
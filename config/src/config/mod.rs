@@ -5,8 +5,10 @@
 // All modules should be declared below
 mod admin_service_config;
 mod api_config;
+mod api_rate_limit_config;
 mod base_config;
 mod config_optimizer;
+mod config_reload;
 mod config_sanitizer;
 mod consensus_config;
 mod dag_consensus_config;
@@ -36,7 +38,9 @@ mod utils;
 // All public usage statements should be declared below
 pub use admin_service_config::*;
 pub use api_config::*;
+pub use api_rate_limit_config::*;
 pub use base_config::*;
+pub use config_reload::*;
 pub use consensus_config::*;
 pub use dag_consensus_config::*;
 pub use error::*;
@@ -295,6 +295,12 @@ impl Default for AptosDataPollerConfig {
 pub struct AptosDataClientConfig {
     /// The aptos data poller config for the data client
     pub data_poller_config: AptosDataPollerConfig,
+    /// Whether or not to hedge requests, i.e., race a slow request against a second peer
+    pub enable_request_hedging: bool,
+    /// Whether or not to select peers by their observed throughput for the request type
+    pub enable_throughput_peer_selection: bool,
+    /// The latency percentile (of recent requests of the same type) after which to hedge
+    pub hedged_request_latency_percentile: u64,
     /// The reduction factor for latency filtering when selecting peers
     pub latency_filtering_reduction_factor: u64,
     /// The interval (milliseconds) at which to refresh the latency monitor
@@ -315,6 +321,8 @@ pub struct AptosDataClientConfig {
     pub max_transaction_chunk_size: u64,
     /// Maximum number of transaction outputs per chunk
     pub max_transaction_output_chunk_size: u64,
+    /// Minimum delay (in ms) before a request is hedged
+    pub min_hedged_request_delay_ms: u64,
     /// Minimum peer ratio for latency filtering
     pub min_peer_ratio_for_latency_filtering: u64,
     /// Minimum number of peers before latency filtering can occur
@@ -333,6 +341,9 @@ impl Default for AptosDataClientConfig {
     fn default() -> Self {
         Self {
             data_poller_config: AptosDataPollerConfig::default(),
+            enable_request_hedging: false,
+            enable_throughput_peer_selection: false,
+            hedged_request_latency_percentile: 95,
             latency_filtering_reduction_factor: 2, // Only consider the best 50% of peers
             latency_monitor_loop_interval_ms: 100,
             max_epoch_chunk_size: MAX_EPOCH_CHUNK_SIZE,
@@ -343,6 +354,7 @@ impl Default for AptosDataClientConfig {
             max_subscription_lag_secs: 30, // 30 seconds
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
             max_transaction_output_chunk_size: MAX_TRANSACTION_OUTPUT_CHUNK_SIZE,
+            min_hedged_request_delay_ms: 500,
             min_peer_ratio_for_latency_filtering: 5, // Only filter if we have at least 5 potential peers per request
             min_peers_for_latency_filtering: 10, // Only filter if we have at least 10 total peers
             optimistic_fetch_timeout_ms: 5000,   // 5 seconds
@@ -360,7 +372,37 @@ impl ConfigSanitizer for StateSyncConfig {
         chain_id: ChainId,
     ) -> Result<(), Error> {
         // Sanitize the state sync driver config
-        StateSyncDriverConfig::sanitize(node_config, node_type, chain_id)
+        StateSyncDriverConfig::sanitize(node_config, node_type, chain_id)?;
+
+        // Sanitize the data client config
+        AptosDataClientConfig::sanitize(node_config, node_type, chain_id)
+    }
+}
+
+impl ConfigSanitizer for AptosDataClientConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        _node_type: NodeType,
+        _chain_id: ChainId,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let data_client_config = &node_config.state_sync.aptos_data_client;
+
+        // Verify that the hedging latency percentile is valid
+        let latency_percentile = data_client_config.hedged_request_latency_percentile;
+        if data_client_config.enable_request_hedging
+            && (latency_percentile == 0 || latency_percentile > 100)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The hedged request latency percentile must be in (0, 100], found: {}",
+                    latency_percentile
+                ),
+            ));
+        }
+
+        Ok(())
     }
 }
 
@@ -686,6 +728,28 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_hedged_request_latency_percentile() {
+        // Create a node config with request hedging enabled and an invalid percentile
+        let node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                aptos_data_client: AptosDataClientConfig {
+                    enable_request_hedging: true,
+                    hedged_request_latency_percentile: 101,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, ChainId::testnet())
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     /// Creates and returns a node config with the syncing modes set to execution
     fn create_execution_mode_config() -> NodeConfig {
         NodeConfig {
@@ -8,7 +8,7 @@ use crate::{
 };
 use anyhow::{ensure, Result};
 use aptos_logger::warn;
-use aptos_types::chain_id::ChainId;
+use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use arr_macro::arr;
 use number_range::NumberRangeOptions;
 use serde::{Deserialize, Serialize};
@@ -152,6 +152,10 @@ pub struct StorageConfig {
     pub dir: PathBuf,
     /// Storage pruning configuration
     pub storage_pruner_config: PrunerConfig,
+    /// Ledger history kept by the ledger pruner past its prune window
+    pub ledger_retention: LedgerRetentionConfig,
+    /// Cold storage tier for the ledger data pruned by the ledger pruner
+    pub ledger_cold_storage: LedgerColdStorageConfig,
     /// Subdirectory for storage in tests only
     #[serde(skip)]
     data_dir: PathBuf,
@@ -170,6 +174,11 @@ pub struct StorageConfig {
     /// since genesis. To recover operation after data loss, or to bootstrap a node in fast sync
     /// mode, the indexer db needs to be copied in from another node.
     pub enable_indexer: bool,
+    /// Index every transaction by the accounts involved in it (signers, owners of the written
+    /// resources, event handles and transferred objects), to serve an account's full transaction
+    /// history through the API. The index only covers the transactions committed while it's
+    /// enabled, and is pruned along with the ledger.
+    pub enable_account_involvement_index: bool,
     /// Fine grained control for db paths of individal databases/shards.
     /// If not specificed, will use `dir` as default.
     /// Only allowed when sharding is enabled.
@@ -213,6 +222,58 @@ pub struct LedgerPrunerConfig {
     pub user_pruning_window_offset: u64,
 }
 
+/// The ledger history the ledger pruner keeps past its prune window. The transactions, events and
+/// write sets of the transactions that touch one of the `accounts`, or emit an event of one of the
+/// `event_types`, are kept, so the API can keep serving them. Everything else is pruned as usual.
+///
+/// Note: the transaction accumulator is not pruned while anything is retained, because it's
+/// needed to prove the retained transactions.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LedgerRetentionConfig {
+    /// Accounts whose transactions are retained: the transactions sent by the account, emitting
+    /// events on one of its event handles, or writing to its resources and modules.
+    pub accounts: Vec<AccountAddress>,
+    /// Event types (e.g. `0x1::coin::DepositEvent`) whose transactions are retained.
+    pub event_types: Vec<String>,
+}
+
+impl LedgerRetentionConfig {
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.event_types.is_empty()
+    }
+}
+
+/// The cold storage tier of the ledger. Instead of being deleted, the ledger data leaving the prune
+/// window is moved into a separate, heavily compressed, append-only database, and reads of pruned
+/// versions fall back to it.
+///
+/// Note: like with `LedgerRetentionConfig`, the transaction accumulator is not pruned, because
+/// it's needed to prove the transactions in cold storage.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LedgerColdStorageConfig {
+    pub enable: bool,
+    /// Directory of the cold storage database, typically on cheaper disks than the rest of the
+    /// storage. Defaults to a `cold_ledger_db` directory in the storage directory.
+    pub dir: Option<PathBuf>,
+    pub rocksdb_config: RocksdbConfig,
+}
+
+impl Default for LedgerColdStorageConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            dir: None,
+            rocksdb_config: RocksdbConfig {
+                // Cold data is rarely read, so it doesn't need as many open files.
+                max_open_files: 1000,
+                ..Default::default()
+            },
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StateMerklePrunerConfig {
@@ -317,9 +378,12 @@ impl Default for StorageConfig {
             // conservatively safe minimal prune window. It'll take a few Gigabytes of disk space
             // depending on the size of an average account blob.
             storage_pruner_config: PrunerConfig::default(),
+            ledger_retention: LedgerRetentionConfig::default(),
+            ledger_cold_storage: LedgerColdStorageConfig::default(),
             data_dir: PathBuf::from("/opt/aptos/data"),
             rocksdb_configs: RocksdbConfigs::default(),
             enable_indexer: false,
+            enable_account_involvement_index: false,
             db_path_overrides: None,
             buffered_state_target_items: BUFFERED_STATE_TARGET_ITEMS,
             max_num_nodes_per_lru_cache_shard: DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::*;
-use crate::dag::{CertifiedNode, Extensions, Node, Vote};
+use crate::dag::{CertifiedNode, Extensions, Node, NodeMetadata, Vote};
 use aptos_consensus_types::{
     block::block_test_utils::certificate_for_genesis,
     common::{Author, Payload},
@@ -114,3 +114,64 @@ fn test_dag() {
     let vote = Vote::new(node.metadata().clone(), Signature::dummy_signature());
     test_dag_type::<DagVoteSchema, <DagVoteSchema as Schema>::Key>(node.id(), vote, &db);
 }
+
+#[test]
+fn test_equivocation_evidence() {
+    let tmp_dir = TempPath::new();
+    let db = ConsensusDB::new(&tmp_dir);
+
+    let author = Author::random();
+    let node_metadata = |digest| NodeMetadata::new_for_test(1, 2, author, 0, digest);
+    let first = EquivocationEvidence::DagNode {
+        first: node_metadata(HashValue::random()),
+        second: node_metadata(HashValue::random()),
+    };
+    let second = EquivocationEvidence::DagNode {
+        first: node_metadata(HashValue::random()),
+        second: node_metadata(HashValue::random()),
+    };
+
+    assert_eq!(db.get_equivocation_evidence().unwrap(), vec![]);
+    db.save_equivocation_evidence(&first).unwrap();
+    // only the first evidence of an equivocation is kept
+    db.save_equivocation_evidence(&second).unwrap();
+    assert_eq!(db.get_equivocation_evidence().unwrap(), vec![first.clone()]);
+
+    // the evidence can be read while the db is open
+    assert_eq!(read_equivocation_evidence(&tmp_dir).unwrap(), vec![first]);
+}
+
+#[test]
+fn test_prune_equivocation_evidence() {
+    let tmp_dir = TempPath::new();
+    let db = ConsensusDB::new(&tmp_dir);
+
+    let author = Author::random();
+    let evidence = |epoch, round| EquivocationEvidence::DagNode {
+        first: NodeMetadata::new_for_test(epoch, round, author, 0, HashValue::random()),
+        second: NodeMetadata::new_for_test(epoch, round, author, 0, HashValue::random()),
+    };
+    // epochs past 255 check that the keys are ordered numerically rather than by their bytes
+    let all_evidence = vec![
+        evidence(1, 300),
+        evidence(2, 1),
+        evidence(256, 1),
+        evidence(257, 2),
+    ];
+    for evidence in all_evidence.iter().rev() {
+        db.save_equivocation_evidence(evidence).unwrap();
+    }
+    assert_eq!(db.get_equivocation_evidence().unwrap(), all_evidence);
+
+    db.prune_equivocation_evidence(256).unwrap();
+    assert_eq!(
+        db.get_equivocation_evidence().unwrap(),
+        all_evidence[2..].to_vec()
+    );
+
+    db.prune_equivocation_evidence(0).unwrap();
+    assert_eq!(
+        db.get_equivocation_evidence().unwrap(),
+        all_evidence[2..].to_vec()
+    );
+}
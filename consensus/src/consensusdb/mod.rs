@@ -6,29 +6,34 @@
 mod consensusdb_test;
 mod schema;
 
-use crate::error::DbError;
+use crate::{equivocation_evidence::EquivocationEvidence, error::DbError};
 use anyhow::Result;
 use aptos_consensus_types::{block::Block, quorum_cert::QuorumCert};
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
 use aptos_schemadb::{
-    schema::Schema, Options, ReadOptions, SchemaBatch, DB, DEFAULT_COLUMN_FAMILY_NAME,
+    schema::Schema, ColumnFamilyName, Options, ReadOptions, SchemaBatch, DB,
+    DEFAULT_COLUMN_FAMILY_NAME,
 };
 pub use schema::{
     block::BlockSchema,
     dag::{CertifiedNodeSchema, DagVoteSchema, NodeSchema},
+    equivocation_evidence::EquivocationEvidenceSchema,
     quorum_certificate::QCSchema,
 };
 use schema::{
     single_entry::{SingleEntryKey, SingleEntrySchema},
-    BLOCK_CF_NAME, CERTIFIED_NODE_CF_NAME, DAG_VOTE_CF_NAME, NODE_CF_NAME, QC_CF_NAME,
-    SINGLE_ENTRY_CF_NAME,
+    BLOCK_CF_NAME, CERTIFIED_NODE_CF_NAME, DAG_VOTE_CF_NAME, EQUIVOCATION_EVIDENCE_CF_NAME,
+    NODE_CF_NAME, QC_CF_NAME, SINGLE_ENTRY_CF_NAME,
 };
 use std::{iter::Iterator, path::Path, time::Instant};
 
 /// The name of the consensus db file
 pub const CONSENSUS_DB_NAME: &str = "consensus_db";
 
+/// The number of epochs (including the current one) the equivocation evidence is kept for.
+pub const EQUIVOCATION_EVIDENCE_EPOCHS_TO_KEEP: u64 = 1000;
+
 /// Creates new physical DB checkpoint in directory specified by `checkpoint_path`.
 pub fn create_checkpoint<P: AsRef<Path> + Clone>(db_path: P, checkpoint_path: P) -> Result<()> {
     let start = Instant::now();
@@ -45,22 +50,40 @@ pub fn create_checkpoint<P: AsRef<Path> + Clone>(db_path: P, checkpoint_path: P)
     Ok(())
 }
 
+/// Reads the equivocation evidence from the consensus db in `db_root_path`. The db is opened
+/// read only, so this can be used while the node is running.
+pub fn read_equivocation_evidence<P: AsRef<Path>>(
+    db_root_path: P,
+) -> Result<Vec<EquivocationEvidence>> {
+    let path = db_root_path.as_ref().join(CONSENSUS_DB_NAME);
+    let db = DB::open_cf_readonly(&Options::default(), path, "consensus_ro", column_families())?;
+    let mut iter = db.iter::<EquivocationEvidenceSchema>(ReadOptions::default())?;
+    iter.seek_to_first();
+    iter.map(|item| item.map(|(_, evidence)| evidence))
+        .collect()
+}
+
+fn column_families() -> Vec<ColumnFamilyName> {
+    vec![
+        /* UNUSED CF = */ DEFAULT_COLUMN_FAMILY_NAME,
+        BLOCK_CF_NAME,
+        QC_CF_NAME,
+        SINGLE_ENTRY_CF_NAME,
+        NODE_CF_NAME,
+        CERTIFIED_NODE_CF_NAME,
+        DAG_VOTE_CF_NAME,
+        "ordered_anchor_id", // deprecated CF
+        EQUIVOCATION_EVIDENCE_CF_NAME,
+    ]
+}
+
 pub struct ConsensusDB {
     db: DB,
 }
 
 impl ConsensusDB {
     pub fn new<P: AsRef<Path> + Clone>(db_root_path: P) -> Self {
-        let column_families = vec![
-            /* UNUSED CF = */ DEFAULT_COLUMN_FAMILY_NAME,
-            BLOCK_CF_NAME,
-            QC_CF_NAME,
-            SINGLE_ENTRY_CF_NAME,
-            NODE_CF_NAME,
-            CERTIFIED_NODE_CF_NAME,
-            DAG_VOTE_CF_NAME,
-            "ordered_anchor_id", // deprecated CF
-        ];
+        let column_families = column_families();
 
         let path = db_root_path.as_ref().join(CONSENSUS_DB_NAME);
         let instant = Instant::now();
@@ -153,6 +176,51 @@ impl ConsensusDB {
         self.commit(batch)
     }
 
+    /// Persist the evidence of an equivocation. Only the first evidence of each kind is kept per
+    /// author and round.
+    pub fn save_equivocation_evidence(
+        &self,
+        evidence: &EquivocationEvidence,
+    ) -> Result<(), DbError> {
+        let key = (
+            evidence.epoch(),
+            evidence.round(),
+            evidence.author(),
+            evidence.kind().to_string(),
+        );
+        if self.db.get::<EquivocationEvidenceSchema>(&key)?.is_some() {
+            return Ok(());
+        }
+        self.put::<EquivocationEvidenceSchema>(&key, evidence)
+    }
+
+    /// Delete the equivocation evidence of all the epochs before `min_epoch`.
+    pub fn prune_equivocation_evidence(&self, min_epoch: u64) -> Result<(), DbError> {
+        let mut iter = self
+            .db
+            .iter::<EquivocationEvidenceSchema>(ReadOptions::default())?;
+        iter.seek_to_first();
+        let batch = SchemaBatch::new();
+        for item in iter {
+            let (key, _) = item?;
+            // Keys are ordered by epoch, so everything after this is kept
+            if key.0 >= min_epoch {
+                break;
+            }
+            batch.delete::<EquivocationEvidenceSchema>(&key)?;
+        }
+        self.commit(batch)
+    }
+
+    /// Get all the persisted equivocation evidence
+    pub fn get_equivocation_evidence(&self) -> Result<Vec<EquivocationEvidence>, DbError> {
+        Ok(self
+            .get_all::<EquivocationEvidenceSchema>()?
+            .into_iter()
+            .map(|(_, evidence)| evidence)
+            .collect())
+    }
+
     /// Write the whole schema batch including all data necessary to mutate the ledger
     /// state of some transaction by leveraging rocksdb atomicity support.
     fn commit(&self, batch: SchemaBatch) -> Result<(), DbError> {
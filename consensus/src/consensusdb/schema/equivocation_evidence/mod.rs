@@ -0,0 +1,78 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the equivocation evidence.
+//!
+//! There is at most one evidence of each kind per author and round. The epoch and round are
+//! encoded in big endian, so the evidence is ordered by epoch and can be pruned by epoch.
+//!
+//! ```text
+//! |<-------------key------------->|<---value--->|
+//! | epoch | round | author | kind |  evidence   |
+//! ```
+
+use crate::{define_schema, equivocation_evidence::EquivocationEvidence};
+use anyhow::{ensure, Result};
+use aptos_consensus_types::common::{Author, Round};
+use aptos_schemadb::{
+    schema::{KeyCodec, ValueCodec},
+    ColumnFamilyName,
+};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::mem::size_of;
+
+pub const EQUIVOCATION_EVIDENCE_CF_NAME: ColumnFamilyName = "equivocation_evidence";
+
+define_schema!(
+    EquivocationEvidenceSchema,
+    EquivocationEvidenceKey,
+    EquivocationEvidence,
+    EQUIVOCATION_EVIDENCE_CF_NAME
+);
+
+/// (epoch, round, author, kind)
+pub type EquivocationEvidenceKey = (u64, Round, Author, String);
+
+impl KeyCodec<EquivocationEvidenceSchema> for EquivocationEvidenceKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (epoch, round, author, kind) = self;
+
+        let mut encoded_key = Vec::with_capacity(PREFIX_LEN + kind.len());
+        encoded_key.write_u64::<BigEndian>(*epoch)?;
+        encoded_key.write_u64::<BigEndian>(*round)?;
+        encoded_key.extend_from_slice(author.as_ref());
+        encoded_key.extend_from_slice(kind.as_bytes());
+        Ok(encoded_key)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure!(
+            data.len() >= PREFIX_LEN,
+            "Unexpected data len {}, expected at least {}.",
+            data.len(),
+            PREFIX_LEN,
+        );
+
+        let epoch = (&data[..size_of::<u64>()]).read_u64::<BigEndian>()?;
+        let round = (&data[size_of::<u64>()..]).read_u64::<BigEndian>()?;
+        let author = Author::try_from(&data[size_of::<u64>() + size_of::<Round>()..PREFIX_LEN])?;
+        let kind = String::from_utf8(data[PREFIX_LEN..].to_vec())?;
+        Ok((epoch, round, author, kind))
+    }
+}
+
+/// The length of the epoch, round and author part of the key.
+const PREFIX_LEN: usize = size_of::<u64>() + size_of::<Round>() + Author::LENGTH;
+
+impl ValueCodec<EquivocationEvidenceSchema> for EquivocationEvidence {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&self)?)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(bcs::from_bytes(data)?)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,22 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use crate::dag::NodeMetadata;
+use aptos_crypto::HashValue;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+
+#[test]
+fn test_equivocation_evidence_schema() {
+    let author = Author::random();
+    let evidence = EquivocationEvidence::DagNode {
+        first: NodeMetadata::new_for_test(1, 2, author, 0, HashValue::random()),
+        second: NodeMetadata::new_for_test(1, 2, author, 0, HashValue::random()),
+    };
+    assert_encode_decode::<EquivocationEvidenceSchema>(
+        &(1, 2, author, evidence.kind().to_string()),
+        &evidence,
+    );
+}
+
+test_no_panic_decoding!(EquivocationEvidenceSchema);
@@ -9,6 +9,7 @@ use crate::{
         storage::{CommitEvent, DAGStorage},
         CertifiedNode, Node, NodeId, Vote,
     },
+    equivocation_evidence::EquivocationEvidence,
     experimental::buffer_manager::OrderedBlocks,
 };
 use anyhow::{anyhow, bail};
@@ -294,6 +295,10 @@ impl DAGStorage for StorageAdapter {
         Ok(self.consensus_db.delete::<DagVoteSchema>(node_ids)?)
     }
 
+    fn save_equivocation_evidence(&self, evidence: &EquivocationEvidence) -> anyhow::Result<()> {
+        Ok(self.consensus_db.save_equivocation_evidence(evidence)?)
+    }
+
     fn save_certified_node(&self, node: &CertifiedNode) -> anyhow::Result<()> {
         Ok(self
             .consensus_db
@@ -28,5 +28,6 @@ pub use commit_signer::DagCommitSigner;
 pub use dag_network::{RpcHandler, RpcWithFallback, TDAGNetworkSender};
 pub use storage::DAGStorage;
 pub use types::{
-    CertifiedNode, DAGMessage, DAGNetworkMessage, DAGRpcResult, Extensions, Node, NodeId, Vote,
+    CertifiedNode, DAGMessage, DAGNetworkMessage, DAGRpcResult, Extensions, Node, NodeId,
+    NodeMetadata, Vote,
 };
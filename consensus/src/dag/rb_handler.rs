@@ -1,24 +1,27 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::dag::{
-    dag_fetcher::TFetchRequester,
-    dag_network::RpcHandler,
-    dag_store::Dag,
-    errors::NodeBroadcastHandleError,
-    observability::{
-        logging::{LogEvent, LogSchema},
-        tracing::{observe_node, NodeStage},
+use crate::{
+    dag::{
+        dag_fetcher::TFetchRequester,
+        dag_network::RpcHandler,
+        dag_store::Dag,
+        errors::NodeBroadcastHandleError,
+        observability::{
+            logging::{LogEvent, LogSchema},
+            tracing::{observe_node, NodeStage},
+        },
+        storage::DAGStorage,
+        types::{Node, NodeCertificate, Vote},
+        NodeId,
     },
-    storage::DAGStorage,
-    types::{Node, NodeCertificate, Vote},
-    NodeId,
+    equivocation_evidence::EquivocationEvidence,
 };
 use anyhow::{bail, ensure};
 use aptos_config::config::DagPayloadConfig;
 use aptos_consensus_types::common::{Author, Round};
 use aptos_infallible::RwLock;
-use aptos_logger::{debug, error};
+use aptos_logger::{debug, error, SecurityEvent};
 use aptos_types::{epoch_state::EpochState, validator_signer::ValidatorSigner};
 use async_trait::async_trait;
 use std::{collections::BTreeMap, mem, sync::Arc};
@@ -182,7 +185,22 @@ impl RpcHandler for NodeBroadcastHandler {
                     .round(node.round()));
                 Ok(vote)
             },
-            Some(ack) => Ok(ack.clone()),
+            Some(ack) => {
+                if ack.metadata().digest() != node.metadata().digest() {
+                    let evidence = EquivocationEvidence::DagNode {
+                        first: ack.metadata().clone(),
+                        second: node.metadata().clone(),
+                    };
+                    error!(
+                        SecurityEvent::ConsensusEquivocatingVote,
+                        "Equivocating node {}", evidence
+                    );
+                    if let Err(e) = self.storage.save_equivocation_evidence(&evidence) {
+                        error!("Failed to save equivocation evidence {}: {}", evidence, e);
+                    }
+                }
+                Ok(ack.clone())
+            },
         }
     }
 }
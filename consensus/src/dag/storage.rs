@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{types::Vote, NodeId};
-use crate::dag::{CertifiedNode, Node};
+use crate::{
+    dag::{CertifiedNode, Node},
+    equivocation_evidence::EquivocationEvidence,
+};
 use aptos_consensus_types::common::{Author, Round};
 use aptos_crypto::HashValue;
 use aptos_types::ledger_info::LedgerInfoWithSignatures;
@@ -57,6 +60,8 @@ pub trait DAGStorage: Send + Sync {
 
     fn delete_votes(&self, node_ids: Vec<NodeId>) -> anyhow::Result<()>;
 
+    fn save_equivocation_evidence(&self, evidence: &EquivocationEvidence) -> anyhow::Result<()>;
+
     fn save_certified_node(&self, node: &CertifiedNode) -> anyhow::Result<()>;
 
     fn get_certified_nodes(&self) -> anyhow::Result<Vec<(HashValue, CertifiedNode)>>;
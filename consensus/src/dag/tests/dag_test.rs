@@ -1,12 +1,15 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::dag::{
-    dag_store::Dag,
-    storage::{CommitEvent, DAGStorage},
-    tests::helpers::{new_certified_node, TEST_DAG_WINDOW},
-    types::{CertifiedNode, DagSnapshotBitmask, Node},
-    NodeId, Vote,
+use crate::{
+    dag::{
+        dag_store::Dag,
+        storage::{CommitEvent, DAGStorage},
+        tests::helpers::{new_certified_node, TEST_DAG_WINDOW},
+        types::{CertifiedNode, DagSnapshotBitmask, Node},
+        NodeId, Vote,
+    },
+    equivocation_evidence::EquivocationEvidence,
 };
 use aptos_crypto::HashValue;
 use aptos_infallible::Mutex;
@@ -20,6 +23,7 @@ pub struct MockStorage {
     node_data: Mutex<Option<Node>>,
     vote_data: Mutex<HashMap<NodeId, Vote>>,
     certified_node_data: Mutex<HashMap<HashValue, CertifiedNode>>,
+    pub equivocation_evidence: Mutex<Vec<EquivocationEvidence>>,
     latest_ledger_info: Option<LedgerInfoWithSignatures>,
 }
 
@@ -29,6 +33,7 @@ impl MockStorage {
             node_data: Mutex::new(None),
             vote_data: Mutex::new(HashMap::new()),
             certified_node_data: Mutex::new(HashMap::new()),
+            equivocation_evidence: Mutex::new(vec![]),
             latest_ledger_info: None,
         }
     }
@@ -38,6 +43,7 @@ impl MockStorage {
             node_data: Mutex::new(None),
             vote_data: Mutex::new(HashMap::new()),
             certified_node_data: Mutex::new(HashMap::new()),
+            equivocation_evidence: Mutex::new(vec![]),
             latest_ledger_info: Some(ledger_info),
         }
     }
@@ -74,6 +80,11 @@ impl DAGStorage for MockStorage {
         Ok(())
     }
 
+    fn save_equivocation_evidence(&self, evidence: &EquivocationEvidence) -> anyhow::Result<()> {
+        self.equivocation_evidence.lock().push(evidence.clone());
+        Ok(())
+    }
+
     fn save_certified_node(&self, node: &CertifiedNode) -> anyhow::Result<()> {
         self.certified_node_data
             .lock()
@@ -1,18 +1,21 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::dag::{
-    dag_fetcher::TFetchRequester,
-    dag_store::Dag,
-    errors::NodeBroadcastHandleError,
-    rb_handler::NodeBroadcastHandler,
-    storage::DAGStorage,
-    tests::{
-        dag_test::MockStorage,
-        helpers::{new_node, TEST_DAG_WINDOW},
+use crate::{
+    dag::{
+        dag_fetcher::TFetchRequester,
+        dag_store::Dag,
+        errors::NodeBroadcastHandleError,
+        rb_handler::NodeBroadcastHandler,
+        storage::DAGStorage,
+        tests::{
+            dag_test::MockStorage,
+            helpers::{new_node, TEST_DAG_WINDOW},
+        },
+        types::NodeCertificate,
+        NodeId, RpcHandler, Vote,
     },
-    types::NodeCertificate,
-    NodeId, RpcHandler, Vote,
+    equivocation_evidence::{EquivocationEvidence, EvidenceVerification},
 };
 use aptos_config::config::DagPayloadConfig;
 use aptos_infallible::RwLock;
@@ -76,9 +79,19 @@ async fn test_node_broadcast_receiver_succeed() {
     assert_ok_eq!(rb_receiver.process(wellformed_node).await, expected_result);
     // expect the original ack for any future message from same author
     assert_ok_eq!(
-        rb_receiver.process(equivocating_node).await,
+        rb_receiver.process(equivocating_node.clone()).await,
         expected_result
     );
+    // and the equivocation to be recorded
+    let evidence = storage.equivocation_evidence.lock().clone();
+    assert_eq!(evidence, vec![EquivocationEvidence::DagNode {
+        first: expected_result.metadata().clone(),
+        second: equivocating_node.metadata().clone(),
+    }]);
+    assert_ok_eq!(
+        evidence[0].verify(&validator_verifier),
+        EvidenceVerification::Unverifiable
+    );
 }
 
 // TODO: Unit test node broad receiver with a pruned DAG store. Possibly need a validator verifier trait.
@@ -113,6 +113,10 @@ impl NodeMetadata {
         }
     }
 
+    pub fn node_id(&self) -> &NodeId {
+        &self.node_id
+    }
+
     pub fn digest(&self) -> &HashValue {
         &self.digest
     }
@@ -493,6 +497,10 @@ impl Vote {
         }
     }
 
+    pub fn metadata(&self) -> &NodeMetadata {
+        &self.metadata
+    }
+
     pub fn signature(&self) -> &Signature {
         &self.signature
     }
@@ -0,0 +1,153 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Evidence of equivocation by a validator, i.e. two conflicting messages sent by the same author
+//! in the same round. The evidence is persisted in the consensus db, so that it can be exported
+//! (through the admin service or the db tool) and checked independently with `verify`.
+
+use crate::dag::NodeMetadata;
+use anyhow::ensure;
+use aptos_consensus_types::{
+    block::Block,
+    common::{Author, Round},
+    vote::Vote,
+};
+use aptos_crypto::hash::CryptoHash;
+use aptos_types::validator_verifier::ValidatorVerifier;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum EquivocationEvidence {
+    /// Two votes of the same author for different ledger infos in the same round.
+    Vote { first: Vote, second: Vote },
+    /// Two different blocks proposed by the same author in the same round.
+    Proposal { first: Block, second: Block },
+    /// Two different DAG nodes broadcast by the same author in the same round. DAG nodes are
+    /// authenticated by the network connection rather than signed by their author, so this
+    /// evidence is only as trustworthy as the validator that recorded it.
+    DagNode {
+        first: NodeMetadata,
+        second: NodeMetadata,
+    },
+}
+
+/// The outcome of checking a piece of evidence that is consistent, see
+/// [`EquivocationEvidence::verify`].
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EvidenceVerification {
+    /// Both messages are signed by the author, so the evidence proves the equivocation.
+    Verified,
+    /// The messages are consistent and the author is a validator, but the messages are not
+    /// signed, so the evidence can't be checked independently of the validator that recorded it.
+    Unverifiable,
+}
+
+impl EquivocationEvidence {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            EquivocationEvidence::Vote { .. } => "vote",
+            EquivocationEvidence::Proposal { .. } => "proposal",
+            EquivocationEvidence::DagNode { .. } => "dag_node",
+        }
+    }
+
+    pub fn epoch(&self) -> u64 {
+        match self {
+            EquivocationEvidence::Vote { first, .. } => first.epoch(),
+            EquivocationEvidence::Proposal { first, .. } => first.epoch(),
+            EquivocationEvidence::DagNode { first, .. } => first.epoch(),
+        }
+    }
+
+    pub fn round(&self) -> Round {
+        match self {
+            EquivocationEvidence::Vote { first, .. } => first.vote_data().proposed().round(),
+            EquivocationEvidence::Proposal { first, .. } => first.round(),
+            EquivocationEvidence::DagNode { first, .. } => first.round(),
+        }
+    }
+
+    /// The equivocating author. Proposals without an author (i.e. NIL blocks) are never
+    /// recorded as evidence.
+    pub fn author(&self) -> Author {
+        match self {
+            EquivocationEvidence::Vote { first, .. } => first.author(),
+            EquivocationEvidence::Proposal { first, .. } => first.author().unwrap_or(Author::ZERO),
+            EquivocationEvidence::DagNode { first, .. } => *first.author(),
+        }
+    }
+
+    /// Checks that both messages are from the same author, epoch and round, that they conflict,
+    /// and that they are correctly signed according to the validator set of the epoch. DAG node
+    /// evidence carries no signatures, so at best it is reported as
+    /// [`EvidenceVerification::Unverifiable`].
+    pub fn verify(&self, verifier: &ValidatorVerifier) -> anyhow::Result<EvidenceVerification> {
+        match self {
+            EquivocationEvidence::Vote { first, second } => {
+                ensure!(
+                    first.author() == second.author(),
+                    "The votes have different authors"
+                );
+                ensure!(
+                    (first.epoch(), first.vote_data().proposed().round())
+                        == (second.epoch(), second.vote_data().proposed().round()),
+                    "The votes are for different rounds"
+                );
+                ensure!(
+                    first.ledger_info().hash() != second.ledger_info().hash(),
+                    "The votes are for the same ledger info"
+                );
+                first.verify(verifier)?;
+                second.verify(verifier)?;
+                Ok(EvidenceVerification::Verified)
+            },
+            EquivocationEvidence::Proposal { first, second } => {
+                ensure!(
+                    first.author().is_some() && first.author() == second.author(),
+                    "The proposals have different authors"
+                );
+                ensure!(
+                    (first.epoch(), first.round()) == (second.epoch(), second.round()),
+                    "The proposals are for different rounds"
+                );
+                ensure!(
+                    first.id() != second.id(),
+                    "The proposals are the same block"
+                );
+                first.validate_signature(verifier)?;
+                second.validate_signature(verifier)?;
+                Ok(EvidenceVerification::Verified)
+            },
+            EquivocationEvidence::DagNode { first, second } => {
+                ensure!(
+                    first.node_id() == second.node_id(),
+                    "The nodes have different ids"
+                );
+                ensure!(
+                    first.digest() != second.digest(),
+                    "The nodes have the same digest"
+                );
+                ensure!(
+                    verifier.get_voting_power(first.author()).is_some(),
+                    "The author {} is not a validator",
+                    first.author()
+                );
+                Ok(EvidenceVerification::Unverifiable)
+            },
+        }
+    }
+}
+
+impl Display for EquivocationEvidence {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "[{} equivocation by {}, epoch: {}, round: {}]",
+            self.kind(),
+            self.author(),
+            self.epoch(),
+            self.round()
+        )
+    }
+}
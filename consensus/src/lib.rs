@@ -21,6 +21,7 @@ mod block_storage;
 mod consensusdb;
 mod dag;
 mod epoch_manager;
+pub mod equivocation_evidence;
 mod error;
 mod experimental;
 mod liveness;
@@ -58,9 +59,9 @@ mod transaction_shuffler;
 mod txn_hash_and_authenticator_deduper;
 
 use aptos_metrics_core::IntGauge;
-pub use consensusdb::create_checkpoint;
 /// Required by the smoke tests
 pub use consensusdb::CONSENSUS_DB_NAME;
+pub use consensusdb::{create_checkpoint, read_equivocation_evidence};
 pub use quorum_store::quorum_store_db::QUORUM_STORE_DB_NAME;
 #[cfg(feature = "fuzzing")]
 pub use round_manager::round_manager_fuzzing;
@@ -14,10 +14,11 @@ use std::{cmp::Ordering, sync::Arc};
 // Wrapper around ProposerElection.
 //
 // Provides is_valid_proposal that remembers, and rejects if
-// the same leader proposes multiple blocks.
+// the same leader proposes multiple blocks. The first block of the latest round is kept, so that
+// it can be reported along with a conflicting proposal as evidence of equivocation.
 pub struct UnequivocalProposerElection {
     proposer_election: Arc<dyn ProposerElection + Send + Sync>,
-    already_proposed: Mutex<(Round, HashValue)>,
+    already_proposed: Mutex<(Round, Option<Block>)>,
 }
 
 impl ProposerElection for UnequivocalProposerElection {
@@ -35,7 +36,7 @@ impl UnequivocalProposerElection {
     pub fn new(proposer_election: Arc<dyn ProposerElection + Send + Sync>) -> Self {
         Self {
             proposer_election,
-            already_proposed: Mutex::new((0, HashValue::zero())),
+            already_proposed: Mutex::new((0, None)),
         }
     }
 
@@ -63,17 +64,21 @@ impl UnequivocalProposerElection {
             match block.round().cmp(&already_proposed.0) {
                 Ordering::Greater => {
                     already_proposed.0 = block.round();
-                    already_proposed.1 = block.id();
+                    already_proposed.1 = Some(block.clone());
                     true
                 },
                 Ordering::Equal => {
-                    if already_proposed.1 != block.id() {
+                    let already_proposed_id = already_proposed
+                        .1
+                        .as_ref()
+                        .map_or_else(HashValue::zero, |b| b.id());
+                    if already_proposed_id != block.id() {
                         error!(
                             SecurityEvent::InvalidConsensusProposal,
                             "Multiple proposals from {} for round {}: {} and {}",
                             author,
                             block.round(),
-                            already_proposed.1,
+                            already_proposed_id,
                             block.id()
                         );
                         false
@@ -85,4 +90,19 @@ impl UnequivocalProposerElection {
             }
         })
     }
+
+    // Return the block previously proposed by the same author in the same round, if the given
+    // block conflicts with it.
+    pub fn equivocating_proposal(&self, block: &Block) -> Option<Block> {
+        let already_proposed = self.already_proposed.lock();
+        already_proposed
+            .1
+            .as_ref()
+            .filter(|proposed| {
+                proposed.round() == block.round()
+                    && proposed.author() == block.author()
+                    && proposed.id() != block.id()
+            })
+            .cloned()
+    }
 }
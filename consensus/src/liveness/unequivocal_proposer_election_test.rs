@@ -92,7 +92,12 @@ fn test_is_valid_proposal() {
     assert!(pe.is_valid_proposal(&good_proposal));
     assert!(!pe.is_valid_proposal(&bad_author_proposal));
 
-    // another proposal from the valid proposer should fail
+    // another proposal from the valid proposer should fail, and is reported with the first one
+    assert_eq!(pe.equivocating_proposal(&good_proposal), None);
+    assert_eq!(
+        pe.equivocating_proposal(&bad_duplicate_proposal),
+        Some(good_proposal.clone())
+    );
     assert!(!pe.is_valid_proposal(&bad_duplicate_proposal));
     // good proposal still passes
     assert!(pe.is_valid_proposal(&good_proposal));
@@ -100,6 +105,10 @@ fn test_is_valid_proposal() {
     // going to the next round:
     assert!(pe.is_valid_proposal(&next_good_proposal));
     assert!(!pe.is_valid_proposal(&next_bad_duplicate_proposal));
+    assert_eq!(
+        pe.equivocating_proposal(&next_bad_duplicate_proposal),
+        Some(next_good_proposal.clone())
+    );
 
     // Proposal from previous round is not valid any more:
     assert!(!pe.is_valid_proposal(&good_proposal));
@@ -2,7 +2,12 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{consensusdb::ConsensusDB, epoch_manager::LivenessStorageData, error::DbError};
+use crate::{
+    consensusdb::{ConsensusDB, EQUIVOCATION_EVIDENCE_EPOCHS_TO_KEEP},
+    epoch_manager::LivenessStorageData,
+    equivocation_evidence::EquivocationEvidence,
+    error::DbError,
+};
 use anyhow::{format_err, Context, Result};
 use aptos_config::config::NodeConfig;
 use aptos_consensus_types::{
@@ -32,6 +37,9 @@ pub trait PersistentLivenessStorage: Send + Sync {
     /// Persist consensus' state
     fn save_vote(&self, vote: &Vote) -> Result<()>;
 
+    /// Persist the evidence of an equivocation observed by this node
+    fn save_equivocation_evidence(&self, evidence: &EquivocationEvidence) -> Result<()>;
+
     /// Construct data that can be recovered from ledger
     fn recover_from_ledger(&self) -> LedgerRecoveryData;
 
@@ -340,6 +348,10 @@ impl PersistentLivenessStorage for StorageWriteProxy {
         Ok(self.db.save_vote(bcs::to_bytes(vote)?)?)
     }
 
+    fn save_equivocation_evidence(&self, evidence: &EquivocationEvidence) -> Result<()> {
+        Ok(self.db.save_equivocation_evidence(evidence)?)
+    }
+
     fn recover_from_ledger(&self) -> LedgerRecoveryData {
         let latest_ledger_info = self
             .aptos_db
@@ -387,6 +399,13 @@ impl PersistentLivenessStorage for StorageWriteProxy {
             .aptos_db
             .get_accumulator_summary(latest_ledger_info.ledger_info().version())
             .expect("Failed to get accumulator summary.");
+        let min_evidence_epoch = latest_ledger_info
+            .ledger_info()
+            .epoch()
+            .saturating_sub(EQUIVOCATION_EVIDENCE_EPOCHS_TO_KEEP - 1);
+        if let Err(e) = self.db.prune_equivocation_evidence(min_evidence_epoch) {
+            error!(error = ?e, "Failed to prune equivocation evidence");
+        }
         let ledger_recovery_data = LedgerRecoveryData::new(latest_ledger_info);
 
         match RecoveryData::new(
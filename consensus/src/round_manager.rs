@@ -8,6 +8,7 @@ use crate::{
         BlockReader, BlockRetriever, BlockStore,
     },
     counters,
+    equivocation_evidence::EquivocationEvidence,
     error::{error_kind, VerifyError},
     liveness::{
         proposal_generator::ProposalGenerator,
@@ -635,6 +636,16 @@ impl RoundManager {
                 .max_receiving_block_bytes(self.onchain_config.quorum_store_enabled()),
         );
 
+        if let Some(first) = self.proposer_election.equivocating_proposal(&proposal) {
+            let evidence = EquivocationEvidence::Proposal {
+                first,
+                second: proposal.clone(),
+            };
+            if let Err(e) = self.storage.save_equivocation_evidence(&evidence) {
+                error!(error = ?e, "Failed to save equivocation evidence {}", evidence);
+            }
+        }
+
         ensure!(
             self.proposer_election.is_valid_proposal(&proposal),
             "[RoundManager] Proposer {} for block {} is not a valid proposer for this round or created duplicate proposal",
@@ -873,6 +884,16 @@ impl RoundManager {
             VoteReceptionResult::VoteAdded(_)
             | VoteReceptionResult::EchoTimeout(_)
             | VoteReceptionResult::DuplicateVote => Ok(()),
+            VoteReceptionResult::EquivocateVote(previous_vote) => {
+                let evidence = EquivocationEvidence::Vote {
+                    first: *previous_vote,
+                    second: vote.clone(),
+                };
+                if let Err(e) = self.storage.save_equivocation_evidence(&evidence) {
+                    error!(error = ?e, "Failed to save equivocation evidence {}", evidence);
+                }
+                Err(anyhow::anyhow!("Equivocating vote: {}", evidence))
+            },
             e => Err(anyhow::anyhow!("{:?}", e)),
         }
     }
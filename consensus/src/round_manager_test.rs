@@ -4,6 +4,7 @@
 
 use crate::{
     block_storage::{BlockReader, BlockStore},
+    equivocation_evidence::EquivocationEvidence,
     experimental::buffer_manager::OrderedBlocks,
     liveness::{
         proposal_generator::{
@@ -663,6 +664,49 @@ fn vote_on_successful_proposal() {
     });
 }
 
+#[test]
+/// A second, conflicting proposal from the same proposer in the same round is rejected, and the
+/// two proposals are persisted as evidence of the equivocation
+fn equivocating_proposal_evidence_persisted() {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let mut nodes = NodeSetup::create_nodes(&mut playground, runtime.handle().clone(), 1, None);
+    let node = &mut nodes[0];
+
+    let genesis_qc = certificate_for_genesis();
+    let proposal = |timestamp| {
+        Block::new_proposal(
+            Payload::empty(false),
+            1,
+            timestamp,
+            genesis_qc.clone(),
+            &node.signer,
+            Vec::new(),
+        )
+        .unwrap()
+    };
+    let first = proposal(1);
+    let second = proposal(2);
+    timed_block_on(&runtime, async {
+        // Start round 1 and clear the message queue
+        node.next_proposal().await;
+
+        node.round_manager
+            .process_proposal(first.clone())
+            .await
+            .unwrap();
+        assert!(node
+            .round_manager
+            .process_proposal(second.clone())
+            .await
+            .is_err());
+    });
+    assert_eq!(
+        *node.storage.shared_storage.equivocation_evidence.lock(),
+        vec![EquivocationEvidence::Proposal { first, second }]
+    );
+}
+
 #[test]
 /// In back pressure mode, verify that the proposals are processed after we get out of back pressure.
 fn delay_proposal_processing_in_sync_only() {
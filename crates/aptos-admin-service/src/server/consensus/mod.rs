@@ -38,6 +38,40 @@ pub async fn handle_dump_consensus_db_request(
     }
 }
 
+pub async fn handle_dump_equivocation_evidence_request(
+    req: Request<Body>,
+    consensus_db: Arc<dyn PersistentLivenessStorage>,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let bcs: bool = match query_pairs.get("bcs") {
+        Some(val) => match val.parse() {
+            Ok(val) => val,
+            Err(err) => return Ok(reply_with_status(StatusCode::BAD_REQUEST, err.to_string())),
+        },
+        None => false,
+    };
+
+    info!("Dumping equivocation evidence.");
+
+    match spawn_blocking(move || dump_equivocation_evidence(consensus_db.as_ref(), bcs)).await {
+        Ok(result) => {
+            info!("Finished dumping equivocation evidence.");
+            let headers: Vec<(_, HeaderValue)> =
+                vec![(CONTENT_LENGTH, HeaderValue::from(result.len()))];
+            Ok(reply_with(headers, result))
+        },
+        Err(e) => {
+            info!("Failed to dump equivocation evidence: {e:?}");
+            Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                e.to_string(),
+            ))
+        },
+    }
+}
+
 pub async fn handle_dump_quorum_store_db_request(
     req: Request<Body>,
     quorum_store_db: Arc<dyn QuorumStoreStorage>,
@@ -145,6 +179,27 @@ fn dump_consensus_db(consensus_db: &dyn PersistentLivenessStorage) -> anyhow::Re
     Ok(body)
 }
 
+fn dump_equivocation_evidence(
+    consensus_db: &dyn PersistentLivenessStorage,
+    bcs: bool,
+) -> anyhow::Result<Vec<u8>> {
+    let evidence = consensus_db.consensus_db().get_equivocation_evidence()?;
+
+    if bcs {
+        return Ok(bcs::to_bytes(&evidence)?);
+    }
+
+    let mut body = String::new();
+    for evidence in evidence {
+        body.push_str(&format!("{evidence}:\n{evidence:?}\n\n"));
+    }
+    if body.is_empty() {
+        body.push_str("No equivocation evidence is found.");
+    }
+
+    Ok(body.into_bytes())
+}
+
 fn dump_quorum_store_db(
     quorum_store_db: &dyn QuorumStoreStorage,
     digest: Option<HashValue>,
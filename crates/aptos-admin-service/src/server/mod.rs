@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::server::utils::reply_with_status;
+use crate::server::utils::{reply_with_status, spawn_blocking};
 use aptos_config::config::NodeConfig;
 use aptos_consensus::{
     persistent_liveness_storage::StorageWriteProxy, quorum_store::quorum_store_db::QuorumStoreDB,
@@ -25,9 +25,13 @@ mod consensus;
 mod profiling;
 mod utils;
 
+/// Reloads the node config, and returns the fields that were applied
+pub type ConfigReloader = Arc<dyn Fn() -> anyhow::Result<Vec<String>> + Send + Sync>;
+
 #[derive(Default)]
 pub struct Context {
     aptos_db: RwLock<Option<Arc<DbReaderWriter>>>,
+    config_reloader: RwLock<Option<ConfigReloader>>,
     consensus_db: RwLock<Option<Arc<StorageWriteProxy>>>,
     quorum_store_db: RwLock<Option<Arc<QuorumStoreDB>>>,
 }
@@ -37,6 +41,10 @@ impl Context {
         *self.aptos_db.write() = Some(aptos_db);
     }
 
+    fn set_config_reloader(&self, config_reloader: ConfigReloader) {
+        *self.config_reloader.write() = Some(config_reloader);
+    }
+
     fn set_consensus_dbs(
         &self,
         consensus_db: Arc<StorageWriteProxy>,
@@ -91,6 +99,10 @@ impl AdminService {
         self.context.set_aptos_db(aptos_db)
     }
 
+    pub fn set_config_reloader(&self, config_reloader: ConfigReloader) {
+        self.context.set_config_reloader(config_reloader)
+    }
+
     pub fn set_consensus_dbs(
         &self,
         consensus_db: Arc<StorageWriteProxy>,
@@ -143,6 +155,17 @@ impl AdminService {
                     ))
                 }
             },
+            (hyper::Method::GET, "/debug/consensus/equivocation_evidence") => {
+                let consensus_db = context.consensus_db.read().clone();
+                if let Some(consensus_db) = consensus_db {
+                    consensus::handle_dump_equivocation_evidence_request(req, consensus_db).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Consensus db is not available.",
+                    ))
+                }
+            },
             (hyper::Method::GET, "/debug/consensus/quorumstoredb") => {
                 let quorum_store_db = context.quorum_store_db.read().clone();
                 if let Some(quorum_store_db) = quorum_store_db {
@@ -171,7 +194,38 @@ impl AdminService {
                     ))
                 }
             },
+            (hyper::Method::POST, "/reload_config") => {
+                let config_reloader = context.config_reloader.read().clone();
+                if let Some(config_reloader) = config_reloader {
+                    Self::handle_reload_config_request(config_reloader).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Config reloading is not available.",
+                    ))
+                }
+            },
             _ => Ok(reply_with_status(StatusCode::NOT_FOUND, "Not found.")),
         }
     }
+
+    async fn handle_reload_config_request(
+        config_reloader: ConfigReloader,
+    ) -> hyper::Result<Response<Body>> {
+        info!("Reloading the node config.");
+
+        match spawn_blocking(move || config_reloader()).await {
+            Ok(applied_fields) => {
+                info!("Finished reloading the node config.");
+                Ok(reply_with_status(
+                    StatusCode::OK,
+                    format!("Applied config fields: {:?}", applied_fields),
+                ))
+            },
+            Err(e) => {
+                info!("Failed to reload the node config: {e:?}");
+                Ok(reply_with_status(StatusCode::BAD_REQUEST, e.to_string()))
+            },
+        }
+    }
 }
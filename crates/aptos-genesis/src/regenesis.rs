@@ -0,0 +1,117 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::config::ValidatorConfiguration;
+use anyhow::anyhow;
+use aptos_config::config::{
+    RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_db::AptosDB;
+use aptos_executor::db_bootstrapper::calculate_genesis;
+use aptos_framework::ReleaseBundle;
+use aptos_storage_interface::{
+    state_delta::StateDelta, state_view::DbStateViewAtVersion, DbReaderWriter, ExecutedTrees,
+};
+use aptos_types::{
+    chain_id::ChainId,
+    transaction::{Transaction, Version},
+    waypoint::Waypoint,
+};
+use aptos_vm::AptosVM;
+use aptos_vm_genesis::Validator;
+use std::{convert::TryInto, path::Path, sync::Arc};
+
+/// Information for a genesis on top of the state of an existing chain, which replaces its
+/// validator set and chain id, and optionally upgrades its framework.
+#[derive(Clone)]
+pub struct RegenesisInfo {
+    chain_id: ChainId,
+    validators: Vec<Validator>,
+    framework: Option<ReleaseBundle>,
+}
+
+impl RegenesisInfo {
+    pub fn new(
+        chain_id: ChainId,
+        configs: Vec<ValidatorConfiguration>,
+        framework: Option<ReleaseBundle>,
+    ) -> anyhow::Result<RegenesisInfo> {
+        let mut validators = Vec::new();
+
+        for config in configs {
+            validators.push(config.try_into()?)
+        }
+
+        Ok(RegenesisInfo {
+            chain_id,
+            validators,
+            framework,
+        })
+    }
+
+    /// Generates the genesis transaction on top of the state of the AptosDB in `db_dir` at
+    /// `version` (by default its latest version), and its waypoint. The DB is opened read only,
+    /// new nodes bootstrap from a copy of it truncated to `version`, to which they apply the
+    /// genesis transaction.
+    ///
+    /// The version has to be a state checkpoint (e.g. the last version of a block) whose state
+    /// hasn't been pruned.
+    pub fn generate(
+        &self,
+        db_dir: &Path,
+        version: Option<Version>,
+    ) -> anyhow::Result<(Transaction, Waypoint)> {
+        let aptosdb = AptosDB::open(
+            StorageDirPaths::from_path(db_dir),
+            true, /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            RocksdbConfigs::default(),
+            false,
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )?;
+        let db_rw = DbReaderWriter::new(aptosdb);
+
+        let version = match version {
+            Some(version) => version,
+            None => db_rw.reader.get_latest_version()?,
+        };
+        let executed_trees = executed_trees_at_version(&db_rw, version)?;
+
+        let state_view = db_rw.reader.state_view_at_version(Some(version))?;
+        let genesis = aptos_vm_genesis::encode_regenesis_transaction(
+            &state_view,
+            &self.validators,
+            self.chain_id,
+            self.framework.as_ref(),
+        )?;
+        let waypoint = calculate_genesis::<AptosVM>(&db_rw, executed_trees, &genesis)?.waypoint();
+        Ok((genesis, waypoint))
+    }
+}
+
+/// The executed trees of the DB as of `version`, which may be older than its latest version.
+fn executed_trees_at_version(
+    db_rw: &DbReaderWriter,
+    version: Version,
+) -> anyhow::Result<ExecutedTrees> {
+    let txn_info = db_rw
+        .reader
+        .get_transaction_info_iterator(version, 1)?
+        .next()
+        .ok_or_else(|| anyhow!("The DB has no transaction at version {}", version))??;
+    let state_root_hash = txn_info.state_checkpoint_hash().ok_or_else(|| {
+        anyhow!(
+            "Version {} is not a state checkpoint, use the last version of a block",
+            version
+        )
+    })?;
+    let state = StateDelta::new_at_checkpoint(
+        state_root_hash,
+        db_rw.reader.get_state_storage_usage(Some(version))?,
+        Some(version),
+    );
+    let transaction_accumulator = db_rw.reader.get_accumulator_summary(version)?.0;
+    Ok(ExecutedTrees::new(state, Arc::new(transaction_accumulator)))
+}
@@ -771,19 +771,31 @@ fn json_format(entry: &LogEntry) -> Result<String, fmt::Error> {
 /// Periodically rebuilds the filter and replaces the current logger filter.
 /// This is useful for dynamically changing log levels at runtime via existing
 /// environment variables such as `RUST_LOG_TELEMETRY`.
+#[derive(Clone)]
 pub struct LoggerFilterUpdater {
     logger: Arc<AptosData>,
-    logger_builder: AptosDataBuilder,
+    logger_builder: Arc<RwLock<AptosDataBuilder>>,
 }
 
 impl LoggerFilterUpdater {
     pub fn new(logger: Arc<AptosData>, logger_builder: AptosDataBuilder) -> Self {
         Self {
             logger,
-            logger_builder,
+            logger_builder: Arc::new(RwLock::new(logger_builder)),
         }
     }
 
+    /// Updates the local and telemetry log levels, and replaces the current
+    /// logger filter. Note: the log level environment variables (e.g.,
+    /// `RUST_LOG`) still take precedence over the given levels.
+    pub fn update_levels(&self, level: Level, telemetry_level: Level) {
+        self.logger_builder
+            .write()
+            .level(level)
+            .telemetry_level(telemetry_level);
+        self.update_filter();
+    }
+
     pub async fn run(self) {
         let mut interval = time::interval(FILTER_REFRESH_INTERVAL);
         loop {
@@ -795,7 +807,7 @@ impl LoggerFilterUpdater {
 
     fn update_filter(&self) {
         // TODO: check for change to env var before rebuilding filter.
-        let filter = self.logger_builder.build_filter();
+        let filter = self.logger_builder.read().build_filter();
         self.logger.set_filter(filter);
     }
 }
@@ -1035,6 +1047,23 @@ mod tests {
         (logger_builder, logger)
     }
 
+    #[test]
+    fn test_logger_filter_updater_levels() {
+        let (logger_builder, logger) = new_async_logger();
+        let debug_metadata = &Metadata::new(Level::Debug, "target", "module_path", "source_path");
+
+        assert!(!logger.filter.read().local_filter.enabled(debug_metadata));
+
+        let updater = LoggerFilterUpdater::new(logger.clone(), logger_builder);
+        updater.update_levels(Level::Debug, Level::Warn);
+
+        assert!(logger.filter.read().local_filter.enabled(debug_metadata));
+
+        updater.update_levels(Level::Info, Level::Warn);
+
+        assert!(!logger.filter.read().local_filter.enabled(debug_metadata));
+    }
+
     #[test]
     fn test_logger_filter_updater() {
         let (logger_builder, logger) = new_async_logger();
@@ -59,6 +59,38 @@ pub struct CheckEndpointArgs {
     pub handshake_args: HandshakeArgs,
 }
 
+#[derive(Clone, Debug, Deserialize, Parser, Serialize)]
+pub struct CrawlNetworkArgs {
+    /// `NetworkAddress` of the seed nodes to start crawling from
+    #[clap(long = "seed-address", value_parser = validate_address, required = true)]
+    pub seed_addresses: Vec<NetworkAddress>,
+    /// `ChainId` of the network to crawl
+    #[clap(long)]
+    pub chain_id: ChainId,
+
+    #[clap(flatten)]
+    pub handshake_args: HandshakeArgs,
+
+    /// Maximum number of nodes to crawl (the crawl stops once reached)
+    #[clap(long, default_value_t = 1000)]
+    #[serde(default = "CrawlNetworkArgs::default_max_nodes")]
+    pub max_nodes: usize,
+    /// Maximum number of nodes to crawl concurrently
+    #[clap(long, default_value_t = 16)]
+    #[serde(default = "CrawlNetworkArgs::default_max_concurrent_crawls")]
+    pub max_concurrent_crawls: usize,
+}
+
+impl CrawlNetworkArgs {
+    fn default_max_nodes() -> usize {
+        1000
+    }
+
+    fn default_max_concurrent_crawls() -> usize {
+        16
+    }
+}
+
 pub fn validate_address(address: &str) -> Result<NetworkAddress> {
     let address = NetworkAddress::from_str(address)
         .with_context(|| format!("Invalid address: {}", address))?;
@@ -9,10 +9,10 @@ use aptos_config::{
 };
 use aptos_crypto::x25519::{self, PRIVATE_KEY_SIZE};
 use aptos_network::{
-    noise::{HandshakeAuthMode, NoiseUpgrader},
+    noise::{stream::NoiseStream, HandshakeAuthMode, NoiseUpgrader},
     protocols::wire::handshake::v1::ProtocolIdSet,
     transport::{
-        resolve_and_connect, upgrade_outbound, TCPBufferCfg, TcpSocket, UpgradeContext,
+        resolve_and_connect, upgrade_outbound, Connection, TCPBufferCfg, TcpSocket, UpgradeContext,
         SUPPORTED_MESSAGING_PROTOCOL,
     },
 };
@@ -85,6 +85,20 @@ async fn check_endpoint_with_handshake(
     address: NetworkAddress,
     remote_pubkey: x25519::PublicKey,
 ) -> Result<String> {
+    let conn = connect_with_handshake(upgrade_context, address, remote_pubkey).await?;
+    let msg = format!("Successfully connected to {}", conn.metadata.addr);
+
+    // Disconnect.
+    drop(conn);
+    Ok(msg)
+}
+
+/// Connects to the address via Noise, and returns the upgraded connection
+pub(crate) async fn connect_with_handshake(
+    upgrade_context: Arc<UpgradeContext>,
+    address: NetworkAddress,
+    remote_pubkey: x25519::PublicKey,
+) -> Result<Connection<NoiseStream<TcpSocket>>> {
     // Connect to the address, this should handle DNS resolution if necessary.
     let fut_socket = async {
         resolve_and_connect(address.clone(), TCPBufferCfg::new())
@@ -108,11 +122,7 @@ async fn check_endpoint_with_handshake(
             address, error
         ))
     })?;
-    let msg = format!("Successfully connected to {}", conn.metadata.addr);
-
-    // Disconnect.
-    drop(conn);
-    Ok(msg)
+    Ok(conn)
 }
 
 const INVALID_NOISE_HEADER: &[u8; 152] = &[7; 152];
@@ -157,7 +167,7 @@ async fn check_endpoint_no_handshake(address: NetworkAddress) -> Result<String>
 }
 
 /// Builds a listener free noise connector
-fn build_upgrade_context(
+pub(crate) fn build_upgrade_context(
     chain_id: ChainId,
     network_id: NetworkId,
     peer_id: PeerId,
@@ -189,7 +199,9 @@ fn build_upgrade_context(
 
 /// Derive the peer id that we're using. This is a convenience to only have to
 /// provide a private key.
-fn private_key_to_public_info(private_key: &x25519::PrivateKey) -> (PeerId, x25519::PublicKey) {
+pub(crate) fn private_key_to_public_info(
+    private_key: &x25519::PrivateKey,
+) -> (PeerId, x25519::PublicKey) {
     let public_key = private_key.public_key();
     let peer_id = account_address::from_identity_public_key(public_key);
     (peer_id, public_key)
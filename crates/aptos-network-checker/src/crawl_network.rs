@@ -0,0 +1,535 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    args::CrawlNetworkArgs,
+    check_endpoint::{build_upgrade_context, connect_with_handshake, private_key_to_public_info},
+};
+use anyhow::{anyhow, bail, Context, Result};
+use aptos_build_info::{BUILD_COMMIT_HASH, BUILD_PKG_VERSION};
+use aptos_config::{
+    config::{PeerRole, MAX_FRAME_SIZE},
+    network_id::{NetworkId, PeerNetworkId},
+};
+use aptos_crypto::x25519::{self, PRIVATE_KEY_SIZE};
+use aptos_network::{
+    noise::stream::NoiseStream,
+    protocols::wire::{
+        handshake::v1::ProtocolId,
+        messaging::v1::{
+            MultiplexMessage, MultiplexMessageSink, MultiplexMessageStream, NetworkMessage,
+            RequestId, RpcRequest,
+        },
+    },
+    transport::{TcpSocket, UpgradeContext},
+};
+use aptos_peer_monitoring_service_types::{
+    request::{LatencyPingRequest, PeerMonitoringServiceRequest},
+    response::{
+        LatencyPingResponse, NetworkInformationResponse, NodeInformationResponse,
+        OutboundConnectionsResponse, PeerMonitoringServiceResponse, ServerProtocolVersionResponse,
+    },
+    PeerMonitoringServiceMessage,
+};
+use aptos_types::{account_address, network_address::NetworkAddress, PeerId};
+use futures::{
+    io::{ReadHalf, WriteHalf},
+    stream, AsyncReadExt, SinkExt, StreamExt,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt::Write,
+    sync::Arc,
+    time::Instant,
+};
+use tokio::time::Duration;
+
+/// The first peer monitoring service version that reports outbound connections
+const OUTBOUND_CONNECTIONS_VERSION: u64 = 2;
+
+/// A graph of the nodes and connections discovered while crawling the network
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct NetworkGraph {
+    pub nodes: BTreeMap<PeerId, NetworkNode>,
+    pub connections: BTreeSet<NetworkConnection>,
+}
+
+impl NetworkGraph {
+    /// Returns the graph in the GraphViz (DOT) format
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph aptos_network {\n");
+        for (peer_id, node) in &self.nodes {
+            let mut label = format!("{}\\n{}", peer_id.short_str_lossless(), node.role_label());
+            if let Some(node_information) = &node.node_information {
+                if let Some(version) = node_information.build_version() {
+                    let _ = write!(label, "\\n{}", version);
+                }
+            }
+            if let Some(latency_ms) = node.latency_ms {
+                let _ = write!(label, "\\n{:.1} ms", latency_ms);
+            }
+            let style = if node.crawl_error.is_some() {
+                ", style=dashed"
+            } else {
+                ""
+            };
+            let _ = writeln!(dot, "  \"{}\" [label=\"{}\"{}];", peer_id, label, style);
+        }
+        for connection in &self.connections {
+            let _ = writeln!(
+                dot,
+                "  \"{}\" -> \"{}\" [label=\"{}\"];",
+                connection.from, connection.to, connection.network_id
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// Returns the node for the given peer (inserting it if it doesn't exist)
+    fn get_or_insert_node(&mut self, peer_id: PeerId) -> &mut NetworkNode {
+        self.nodes.entry(peer_id).or_default()
+    }
+}
+
+/// A single node discovered while crawling the network
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct NetworkNode {
+    pub address: Option<NetworkAddress>, // The address the node was crawled at (if any)
+    pub role: Option<PeerRole>,          // The role of the node, as reported by its peers
+    pub distance_from_validators: Option<u64>, // The distance of the node from the validator set
+    pub latency_ms: Option<f64>,         // The latency ping round trip time (from the crawler)
+    pub node_information: Option<CrawledNodeInformation>, // The information reported by the node
+    pub crawl_error: Option<String>,     // The error encountered when crawling the node (if any)
+}
+
+impl NetworkNode {
+    fn role_label(&self) -> &'static str {
+        self.role.map(PeerRole::as_str).unwrap_or("unknown_role")
+    }
+
+    /// Updates the role of the node. Roles reported as unknown
+    /// (e.g., for inbound public connections) never override a
+    /// more specific role.
+    fn update_role(&mut self, role: PeerRole) {
+        if self.role.is_none() || self.role == Some(PeerRole::Unknown) {
+            self.role = Some(role);
+        }
+    }
+}
+
+/// The node information reported by a crawled node
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct CrawledNodeInformation {
+    pub build_information: BTreeMap<String, String>,
+    pub highest_synced_epoch: u64,
+    pub highest_synced_version: u64,
+    pub ledger_timestamp_usecs: u64,
+    pub uptime_secs: u64,
+}
+
+impl CrawledNodeInformation {
+    /// Returns the build version of the node (i.e., the package
+    /// version, or the commit hash if the version is missing).
+    pub fn build_version(&self) -> Option<&String> {
+        self.build_information
+            .get(BUILD_PKG_VERSION)
+            .or_else(|| self.build_information.get(BUILD_COMMIT_HASH))
+    }
+}
+
+impl From<NodeInformationResponse> for CrawledNodeInformation {
+    fn from(response: NodeInformationResponse) -> Self {
+        Self {
+            build_information: response.build_information,
+            highest_synced_epoch: response.highest_synced_epoch,
+            highest_synced_version: response.highest_synced_version,
+            ledger_timestamp_usecs: response.ledger_timestamp_usecs,
+            uptime_secs: response.uptime.as_secs(),
+        }
+    }
+}
+
+/// A (directed) connection between two nodes, as reported by the first node
+#[derive(Clone, Debug, Deserialize, Eq, Ord, PartialEq, PartialOrd, Serialize)]
+pub struct NetworkConnection {
+    pub from: PeerId,
+    pub to: PeerId,
+    pub network_id: NetworkId,
+}
+
+/// The responses of a successfully crawled node
+struct CrawlResponse {
+    latency: Duration,
+    network_information: NetworkInformationResponse,
+    node_information: NodeInformationResponse,
+    outbound_peers: Option<BTreeSet<PeerNetworkId>>, // Missing if the node can't report them
+}
+
+/// Crawls the network (breadth-first) starting at the given seed nodes, and
+/// returns the graph of all discovered nodes and connections. Only nodes on
+/// the crawled network (e.g., the public network) that were dialed by a crawled
+/// node are dialed, but all reported connections are included in the graph.
+///
+/// Note: the peer IDs of the seed nodes are derived from their noise keys.
+pub async fn crawl_network(
+    args: &CrawlNetworkArgs,
+    private_key: Option<x25519::PrivateKey>,
+) -> Result<NetworkGraph> {
+    if args.handshake_args.no_handshake {
+        bail!("Crawling the network requires a handshake with each node!");
+    }
+    if args.max_concurrent_crawls == 0 {
+        bail!("The maximum number of concurrent crawls must be greater than 0!");
+    }
+
+    // Build the upgrade context used to connect to each node
+    let private_key = private_key.unwrap_or_else(|| {
+        let dummy = [0; PRIVATE_KEY_SIZE];
+        x25519::PrivateKey::from(dummy)
+    });
+    let (peer_id, _) = private_key_to_public_info(&private_key);
+    let network_id = args.handshake_args.network_id;
+    let upgrade_context = build_upgrade_context(args.chain_id, network_id, peer_id, private_key);
+    let timeout = Duration::from_secs(args.handshake_args.timeout_seconds);
+
+    // Identify the seed nodes
+    let mut visited_peers = HashSet::new();
+    let mut peers_to_crawl = vec![];
+    for address in &args.seed_addresses {
+        let remote_pubkey = address.find_noise_proto().with_context(|| {
+            format!(
+                "Failed to find noise protocol in {}, /noise-ik/<pubkey> missing",
+                address
+            )
+        })?;
+        let peer_id = account_address::from_identity_public_key(remote_pubkey);
+        if visited_peers.len() < args.max_nodes && visited_peers.insert(peer_id) {
+            peers_to_crawl.push((peer_id, address.clone()));
+        }
+    }
+
+    // Crawl the network one level at a time
+    let mut network_graph = NetworkGraph::default();
+    while !peers_to_crawl.is_empty() {
+        aptos_logger::info!("Crawling {} nodes", peers_to_crawl.len());
+        let crawl_results: Vec<_> = stream::iter(peers_to_crawl.drain(..))
+            .map(|(peer_id, address)| {
+                let upgrade_context = upgrade_context.clone();
+                async move {
+                    let crawl_result = crawl_node(upgrade_context, address.clone(), timeout).await;
+                    (peer_id, address, crawl_result)
+                }
+            })
+            .buffer_unordered(args.max_concurrent_crawls)
+            .collect()
+            .await;
+
+        // Update the graph and identify the next nodes to crawl
+        for (peer_id, address, crawl_result) in crawl_results {
+            let node = network_graph.get_or_insert_node(peer_id);
+            node.address = Some(address);
+
+            let crawl_response = match crawl_result {
+                Ok(crawl_response) => crawl_response,
+                Err(error) => {
+                    aptos_logger::debug!("Failed to crawl node {}: {:#}", peer_id, error);
+                    node.crawl_error = Some(format!("{:#}", error));
+                    continue;
+                },
+            };
+            node.latency_ms = Some(crawl_response.latency.as_secs_f64() * 1000.0);
+            node.distance_from_validators =
+                Some(crawl_response.network_information.distance_from_validators);
+            node.node_information = Some(crawl_response.node_information.into());
+
+            for (peer_network_id, connection_metadata) in
+                &crawl_response.network_information.connected_peers
+            {
+                let connected_peer_id = peer_network_id.peer_id();
+                network_graph.connections.insert(NetworkConnection {
+                    from: peer_id,
+                    to: connected_peer_id,
+                    network_id: peer_network_id.network_id(),
+                });
+                network_graph
+                    .get_or_insert_node(connected_peer_id)
+                    .update_role(connection_metadata.peer_role);
+            }
+
+            // Only dial the nodes we haven't seen yet
+            for (connected_peer_id, network_address) in
+                get_dialable_peers(&crawl_response, network_id)
+            {
+                if visited_peers.len() < args.max_nodes && visited_peers.insert(connected_peer_id) {
+                    peers_to_crawl.push((connected_peer_id, network_address));
+                }
+            }
+        }
+    }
+
+    Ok(network_graph)
+}
+
+/// Returns the peers (and their addresses) to dial from the connections reported
+/// by a crawled node. Only peers on the crawled network that were dialed by the
+/// node are returned, as the addresses of inbound connections are not the addresses
+/// the peers listen on. Nodes that can't report their outbound connections (i.e.,
+/// that run an older version of the peer monitoring service) return no peers.
+fn get_dialable_peers(
+    crawl_response: &CrawlResponse,
+    network_id: NetworkId,
+) -> Vec<(PeerId, NetworkAddress)> {
+    let outbound_peers = match &crawl_response.outbound_peers {
+        Some(outbound_peers) => outbound_peers,
+        None => return vec![],
+    };
+    crawl_response
+        .network_information
+        .connected_peers
+        .iter()
+        .filter(|(peer_network_id, connection_metadata)| {
+            peer_network_id.network_id() == network_id
+                && outbound_peers.contains(peer_network_id)
+                && connection_metadata.network_address.is_aptosnet_addr()
+        })
+        .map(|(peer_network_id, connection_metadata)| {
+            (
+                peer_network_id.peer_id(),
+                connection_metadata.network_address.clone(),
+            )
+        })
+        .collect()
+}
+
+/// Connects to the node at the given address and fetches the network
+/// and node information (and outbound connections) from the node.
+async fn crawl_node(
+    upgrade_context: Arc<UpgradeContext>,
+    address: NetworkAddress,
+    timeout: Duration,
+) -> Result<CrawlResponse> {
+    let remote_pubkey = address
+        .find_noise_proto()
+        .ok_or_else(|| anyhow!("Failed to find noise protocol in {}", address))?;
+
+    tokio::time::timeout(timeout, async {
+        let connection =
+            connect_with_handshake(upgrade_context, address.clone(), remote_pubkey).await?;
+        let mut client = PeerMonitoringClient::new(connection.socket);
+
+        // Measure the latency using a latency ping
+        let ping_start_time = Instant::now();
+        let _: LatencyPingResponse = client
+            .send_request(PeerMonitoringServiceRequest::LatencyPing(
+                LatencyPingRequest { ping_counter: 0 },
+            ))
+            .await?;
+        let latency = ping_start_time.elapsed();
+
+        // Fetch the network and node information
+        let network_information = client
+            .send_request(PeerMonitoringServiceRequest::GetNetworkInformation)
+            .await?;
+        let node_information = client
+            .send_request(PeerMonitoringServiceRequest::GetNodeInformation)
+            .await?;
+
+        // Fetch the outbound connections (if the node supports the request)
+        let server_protocol_version: ServerProtocolVersionResponse = client
+            .send_request(PeerMonitoringServiceRequest::GetServerProtocolVersion)
+            .await?;
+        let outbound_peers = if server_protocol_version.version >= OUTBOUND_CONNECTIONS_VERSION {
+            let outbound_connections: OutboundConnectionsResponse = client
+                .send_request(PeerMonitoringServiceRequest::GetOutboundConnections)
+                .await?;
+            Some(outbound_connections.outbound_peers)
+        } else {
+            None
+        };
+
+        Ok(CrawlResponse {
+            latency,
+            network_information,
+            node_information,
+            outbound_peers,
+        })
+    })
+    .await
+    .with_context(|| format!("Timed out while crawling node {}", address))?
+}
+
+/// A minimal peer monitoring service client that sends
+/// RPCs directly over an upgraded (noise) connection.
+struct PeerMonitoringClient {
+    message_sink: MultiplexMessageSink<WriteHalf<NoiseStream<TcpSocket>>>,
+    message_stream: MultiplexMessageStream<ReadHalf<NoiseStream<TcpSocket>>>,
+    next_request_id: RequestId,
+}
+
+impl PeerMonitoringClient {
+    fn new(socket: NoiseStream<TcpSocket>) -> Self {
+        let (read_socket, write_socket) = socket.split();
+        Self {
+            message_sink: MultiplexMessageSink::new(write_socket, MAX_FRAME_SIZE),
+            message_stream: MultiplexMessageStream::new(read_socket, MAX_FRAME_SIZE),
+            next_request_id: 0,
+        }
+    }
+
+    /// Sends the request to the peer and waits for the corresponding response.
+    /// Any other messages sent by the peer (e.g., health checks) are ignored.
+    async fn send_request<T: TryFrom<PeerMonitoringServiceResponse>>(
+        &mut self,
+        request: PeerMonitoringServiceRequest,
+    ) -> Result<T>
+    where
+        T::Error: std::error::Error + Send + Sync + 'static,
+    {
+        // Send the request to the peer
+        let protocol_id = ProtocolId::PeerMonitoringServiceRpc;
+        let request_id = self.next_request_id;
+        self.next_request_id = self.next_request_id.wrapping_add(1);
+        let raw_request = protocol_id.to_bytes(&PeerMonitoringServiceMessage::Request(request))?;
+        let message = MultiplexMessage::Message(NetworkMessage::RpcRequest(RpcRequest {
+            protocol_id,
+            request_id,
+            priority: 0,
+            raw_request,
+        }));
+        self.message_sink.send(&message).await?;
+
+        // Wait for the response
+        while let Some(message) = self.message_stream.next().await {
+            match message? {
+                MultiplexMessage::Message(NetworkMessage::RpcResponse(response))
+                    if response.request_id == request_id =>
+                {
+                    return match protocol_id.from_bytes(&response.raw_response)? {
+                        PeerMonitoringServiceMessage::Response(Ok(response)) => {
+                            Ok(T::try_from(response)?)
+                        },
+                        PeerMonitoringServiceMessage::Response(Err(error)) => {
+                            Err(anyhow!("Peer monitoring service error: {}", error))
+                        },
+                        PeerMonitoringServiceMessage::Request(request) => Err(anyhow!(
+                            "Unexpected request received as response: {}",
+                            request.get_label()
+                        )),
+                    };
+                },
+                MultiplexMessage::Message(NetworkMessage::Error(error_code)) => {
+                    bail!("Peer returned a network error: {:?}", error_code)
+                },
+                _ => {}, // Ignore all other messages
+            }
+        }
+        bail!("The connection was closed before a response was received")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_peer_monitoring_service_types::response::ConnectionMetadata;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_network_graph_to_dot() {
+        // Create a graph with two connected nodes
+        let (crawled_peer, other_peer) = (PeerId::random(), PeerId::random());
+        let mut network_graph = NetworkGraph::default();
+        let crawled_node = network_graph.get_or_insert_node(crawled_peer);
+        crawled_node.latency_ms = Some(12.5);
+        crawled_node.node_information = Some(CrawledNodeInformation {
+            build_information: BTreeMap::from([(
+                BUILD_PKG_VERSION.to_string(),
+                "1.2.3".to_string(),
+            )]),
+            highest_synced_epoch: 10,
+            highest_synced_version: 100,
+            ledger_timestamp_usecs: 1000,
+            uptime_secs: 1,
+        });
+        network_graph
+            .get_or_insert_node(other_peer)
+            .update_role(PeerRole::Unknown);
+        network_graph
+            .get_or_insert_node(other_peer)
+            .update_role(PeerRole::ValidatorFullNode);
+        let peer_network_id = PeerNetworkId::new(NetworkId::Public, other_peer);
+        network_graph.connections.insert(NetworkConnection {
+            from: crawled_peer,
+            to: peer_network_id.peer_id(),
+            network_id: peer_network_id.network_id(),
+        });
+
+        // Verify the more specific role is kept
+        assert_eq!(
+            network_graph.nodes.get(&other_peer).unwrap().role,
+            Some(PeerRole::ValidatorFullNode)
+        );
+
+        // Verify the DOT output contains the nodes and the connection
+        let dot = network_graph.to_dot();
+        assert!(dot.starts_with("digraph aptos_network {"));
+        assert!(dot.contains("1.2.3\\n12.5 ms"));
+        assert!(dot.contains("validator_fullnode"));
+        assert!(dot.contains(&format!(
+            "\"{}\" -> \"{}\" [label=\"{}\"]",
+            crawled_peer,
+            other_peer,
+            NetworkId::Public
+        )));
+    }
+
+    #[test]
+    fn test_get_dialable_peers() {
+        // Create a crawl response with peers connected in different ways
+        let address = NetworkAddress::from_str(
+            "/ip4/34.70.116.169/tcp/6182/noise-ik/0x249f3301db104705652e0a0c471b46d13172b2baf14e31f007413f3baee46b0c/handshake/0",
+        )
+        .unwrap();
+        let outbound_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let inbound_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let vfn_peer = PeerNetworkId::new(NetworkId::Vfn, PeerId::random());
+        let connected_peers = [outbound_peer, inbound_peer, vfn_peer]
+            .into_iter()
+            .map(|peer_network_id| {
+                let connection_metadata = ConnectionMetadata::new(
+                    address.clone(),
+                    peer_network_id.peer_id(),
+                    PeerRole::Unknown,
+                );
+                (peer_network_id, connection_metadata)
+            })
+            .collect();
+        let mut crawl_response = CrawlResponse {
+            latency: Duration::from_millis(10),
+            network_information: NetworkInformationResponse {
+                connected_peers,
+                distance_from_validators: 1,
+            },
+            node_information: NodeInformationResponse {
+                build_information: BTreeMap::new(),
+                highest_synced_epoch: 0,
+                highest_synced_version: 0,
+                ledger_timestamp_usecs: 0,
+                lowest_available_version: 0,
+                uptime: Duration::from_secs(1),
+            },
+            outbound_peers: Some(BTreeSet::from([outbound_peer, vfn_peer])),
+        };
+
+        // Verify only the outbound peer on the crawled network is dialed
+        assert_eq!(
+            get_dialable_peers(&crawl_response, NetworkId::Public),
+            vec![(outbound_peer.peer_id(), address)]
+        );
+
+        // Verify no peers are dialed if the node can't report its outbound connections
+        crawl_response.outbound_peers = None;
+        assert!(get_dialable_peers(&crawl_response, NetworkId::Public).is_empty());
+    }
+}
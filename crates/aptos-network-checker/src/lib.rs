@@ -3,5 +3,7 @@
 
 pub mod args;
 pub mod check_endpoint;
+pub mod crawl_network;
 
 pub use check_endpoint::check_endpoint;
+pub use crawl_network::crawl_network;
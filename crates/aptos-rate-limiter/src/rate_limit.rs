@@ -174,6 +174,17 @@ impl<Key: Eq + Hash + Clone + Debug> TokenBucketRateLimiter<Key> {
         }
         remove
     }
+
+    /// Garbage collects every bucket that is full and not in use, returning how many were
+    /// removed. A full bucket is no different from a newly created one (or is more generous,
+    /// if new buckets don't start full), so this keeps the number of buckets bounded by the
+    /// number of recently active keys without letting any key skip its limit.
+    pub fn garbage_collect_full_buckets(&self) -> usize {
+        let mut buckets = self.buckets.write();
+        let num_buckets = buckets.len();
+        buckets.retain(|_, bucket| Arc::strong_count(bucket) > 1 || !bucket.lock().is_full());
+        num_buckets - buckets.len()
+    }
 }
 
 /// A token bucket object that keeps track of everything related to a key
@@ -291,6 +302,15 @@ impl Bucket {
         }
     }
 
+    /// Whether the bucket is refilled up to its size
+    pub fn is_full(&mut self) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        self.refill();
+        self.tokens >= self.size
+    }
+
     /// Determine if an entire batch can be passed through
     /// This is important for message based rate limiting, where the whole message has
     /// to make it through, or else it must be rejected.  A result of `None` means it cannot
@@ -404,6 +424,30 @@ mod tests {
         assert_eq!(num_keys, rate_limiters.buckets.read().len())
     }
 
+    #[test]
+    fn test_garbage_collect_full_buckets() {
+        let rate_limiter = TokenBucketRateLimiter::test(2, 1);
+
+        // Keys that used up some tokens keep their buckets
+        rate_limiter
+            .bucket("used")
+            .lock()
+            .acquire_tokens(1)
+            .unwrap();
+        rate_limiter.bucket("idle");
+        assert_num_keys(&rate_limiter, 2);
+        assert_eq!(1, rate_limiter.garbage_collect_full_buckets());
+        assert_num_keys(&rate_limiter, 1);
+
+        // Buckets in use are never removed, even once they're full
+        let bucket = rate_limiter.bucket("used");
+        sleep(Duration::from_secs(1));
+        assert_eq!(0, rate_limiter.garbage_collect_full_buckets());
+        drop(bucket);
+        assert_eq!(1, rate_limiter.garbage_collect_full_buckets());
+        assert_num_keys(&rate_limiter, 0);
+    }
+
     #[test]
     fn test_rate_limiting() {
         let bucket_size = 5;
@@ -8,16 +8,17 @@
 
 use crate::{
     common::{
-        check_network, get_block_index_from_request, handle_request, native_coin, native_coin_tag,
-        with_context,
+        check_network, find_coin_currency, get_block_index_from_request, handle_request,
+        native_coin, with_context,
     },
     error::{ApiError, ApiResult},
     types::{AccountBalanceRequest, AccountBalanceResponse, Amount, Currency, *},
     RosettaContext,
 };
 use aptos_logger::{debug, trace, warn};
+use aptos_rest_client::{aptos_api_types::AptosErrorCode, error::RestError};
 use aptos_types::{
-    account_address::AccountAddress,
+    account_address::{create_derived_object_address, AccountAddress},
     account_config::{AccountResource, CoinStoreResource},
 };
 use std::{collections::HashSet, str::FromStr};
@@ -35,6 +36,18 @@ pub fn routes(
     )
 }
 
+/// Account coins route
+pub fn coins_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::post().and(
+        warp::path!("account" / "coins")
+            .and(warp::body::json())
+            .and(with_context(server_context))
+            .and_then(handle_request(account_coins)),
+    )
+}
+
 /// Account balance command
 ///
 /// [API Spec](https://www.rosetta-api.org/docs/AccountApi.html#accountbalance)
@@ -67,6 +80,7 @@ async fn account_balance(
     let balance_version = block_info.last_version;
 
     let (sequence_number, operators, balances, lockup_expiration) = get_balances(
+        &server_context,
         &rest_client,
         request.account_identifier,
         balance_version,
@@ -85,9 +99,74 @@ async fn account_balance(
     })
 }
 
+/// Account coins command
+///
+/// Aptos is account based, so each balance of the account at the latest block is returned
+/// as a single coin.  Mempool can't be included, as pending transactions don't change balances.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/AccountApi.html#accountcoins)
+async fn account_coins(
+    request: AccountCoinsRequest,
+    server_context: RosettaContext,
+) -> ApiResult<AccountCoinsResponse> {
+    debug!("/account/coins");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "account_coins for [{}]",
+        request.account_identifier.address
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    if request.include_mempool {
+        return Err(ApiError::InvalidInput(Some(
+            "Including mempool coins is not supported".to_string(),
+        )));
+    }
+    let rest_client = server_context.rest_client()?;
+
+    // Coins are always as of the latest block
+    let block_height = get_block_index_from_request(&server_context, None).await?;
+    let block_info = server_context
+        .block_cache()?
+        .get_block_info_by_height(block_height, server_context.chain_id)
+        .await?;
+
+    let address = request.account_identifier.address.clone();
+    let (sequence_number, operators, balances, lockup_expiration) = get_balances(
+        &server_context,
+        &rest_client,
+        request.account_identifier,
+        block_info.last_version,
+        request.currencies,
+    )
+    .await?;
+
+    let coins = balances
+        .into_iter()
+        .map(|amount| Coin {
+            coin_identifier: CoinIdentifier {
+                identifier: format!("{}:{}", address, amount.currency.symbol),
+            },
+            amount,
+        })
+        .collect();
+
+    Ok(AccountCoinsResponse {
+        block_identifier: block_info.block_id,
+        coins,
+        metadata: AccountBalanceMetadata {
+            sequence_number: sequence_number.into(),
+            operators,
+            lockup_expiration_time_utc: aptos_rest_client::aptos_api_types::U64(lockup_expiration),
+        },
+    })
+}
+
 /// Retrieve the balances for an account
 #[allow(clippy::manual_retain)]
 async fn get_balances(
+    server_context: &RosettaContext,
     rest_client: &aptos_rest_client::Client,
     account: AccountIdentifier,
     version: u64,
@@ -160,10 +239,12 @@ async fn get_balances(
                         let coin_store: CoinStoreResource = bcs::from_bytes(&bytes)?;
                         if let Some(coin_type) = struct_tag.type_params.first() {
                             // Only display supported coins
-                            if coin_type == &native_coin_tag() {
+                            if let Some(currency) =
+                                find_coin_currency(&server_context.currencies, coin_type)
+                            {
                                 balances.push(Amount {
                                     value: coin_store.coin().to_string(),
-                                    currency: native_coin(),
+                                    currency,
                                 });
                             }
                         }
@@ -229,6 +310,22 @@ async fn get_balances(
             }
         }
 
+        // Fungible assets live in the primary store objects of the account, rather than in the
+        // account itself
+        if account.is_base_account() {
+            for currency in server_context.currencies.iter() {
+                if let Some(metadata) = currency.fa_address() {
+                    let balance =
+                        get_fungible_asset_balance(rest_client, owner_address, metadata, version)
+                            .await?;
+                    balances.push(Amount {
+                        value: balance.to_string(),
+                        currency: currency.clone(),
+                    });
+                }
+            }
+        }
+
         let sequence_number = if let Some(sequence_number) = maybe_sequence_number {
             sequence_number
         } else {
@@ -277,3 +374,29 @@ async fn get_balances(
         ))
     }
 }
+
+/// Retrieve the balance of a fungible asset in the primary store of an account
+///
+/// A missing primary store means the account never held the asset, so the balance is 0
+async fn get_fungible_asset_balance(
+    rest_client: &aptos_rest_client::Client,
+    owner_address: AccountAddress,
+    metadata: AccountAddress,
+    version: u64,
+) -> ApiResult<u64> {
+    let store_address = create_derived_object_address(owner_address, metadata);
+    match rest_client
+        .get_account_resource_at_version_bcs::<FungibleStore>(
+            store_address,
+            "0x1::fungible_asset::FungibleStore",
+            version,
+        )
+        .await
+    {
+        Ok(response) => Ok(response.into_inner().balance),
+        Err(RestError::Api(err)) if err.error.error_code == AptosErrorCode::ResourceNotFound => {
+            Ok(0)
+        },
+        Err(err) => Err(err.into()),
+    }
+}
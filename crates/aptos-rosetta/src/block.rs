@@ -3,14 +3,18 @@
 
 use crate::{
     common::{
-        check_network, get_block_index_from_request, get_timestamp, handle_request, with_context,
-        BlockHash, Y2K_MS,
+        check_network, get_block_index_from_request, get_timestamp, handle_request,
+        parse_transaction_hash, with_context, BlockHash, Y2K_MS,
+    },
+    error::{ApiError, ApiResult},
+    types::{
+        Block, BlockIdentifier, BlockRequest, BlockResponse, BlockTransactionRequest,
+        BlockTransactionResponse, Transaction,
     },
-    error::ApiResult,
-    types::{Block, BlockIdentifier, BlockRequest, BlockResponse, Transaction},
     RosettaContext,
 };
 use aptos_logger::{debug, trace};
+use aptos_rest_client::aptos_api_types::TransactionData;
 use aptos_types::chain_id::ChainId;
 use std::sync::Arc;
 use warp::Filter;
@@ -69,6 +73,70 @@ async fn block(request: BlockRequest, server_context: RosettaContext) -> ApiResu
     Ok(BlockResponse { block })
 }
 
+pub fn block_transaction_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("block" / "transaction")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(block_transaction))
+}
+
+/// Retrieves a single transaction in a block by its hash
+///
+/// Useful for blocks too large to return all at once.  The transaction must be in the given
+/// block, and be committed.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/BlockApi.html#blocktransaction)
+async fn block_transaction(
+    request: BlockTransactionRequest,
+    server_context: RosettaContext,
+) -> ApiResult<BlockTransactionResponse> {
+    debug!("/block/transaction");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/block/transaction",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    let rest_client = server_context.rest_client()?;
+    let hash = parse_transaction_hash(&request.transaction_identifier)?;
+
+    let txn = match rest_client
+        .get_transaction_by_hash_bcs(hash)
+        .await?
+        .into_inner()
+    {
+        TransactionData::OnChain(txn) => txn,
+        TransactionData::Pending(_) => return Err(ApiError::TransactionIsPending),
+    };
+
+    // Ensure the transaction is actually in the requested block
+    let block = server_context
+        .block_cache()?
+        .get_block_by_height(request.block_identifier.index, false)
+        .await?;
+    let block_identifier = BlockIdentifier::from_block(&block, server_context.chain_id);
+    if block_identifier.hash != request.block_identifier.hash {
+        return Err(ApiError::BlockNotFound(Some(format!(
+            "Block {} does not have hash {}",
+            request.block_identifier.index, request.block_identifier.hash
+        ))));
+    }
+    if txn.version < block.first_version || txn.version > block.last_version {
+        return Err(ApiError::TransactionNotFound(Some(format!(
+            "Transaction {} is not in block {}",
+            request.transaction_identifier.hash, request.block_identifier.index
+        ))));
+    }
+
+    Ok(BlockTransactionResponse {
+        transaction: Transaction::from_transaction(&server_context, txn).await?,
+    })
+}
+
 /// Build up the transaction, which should contain the `operations` as the change set
 async fn build_block(
     server_context: &RosettaContext,
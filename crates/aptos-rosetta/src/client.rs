@@ -4,16 +4,19 @@
 use crate::{
     common::native_coin,
     types::{
-        AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, BlockRequest,
-        BlockResponse, ConstructionCombineRequest, ConstructionCombineResponse,
+        AccountBalanceRequest, AccountBalanceResponse, AccountCoinsRequest, AccountCoinsResponse,
+        AccountIdentifier, BlockRequest, BlockResponse, BlockTransactionRequest,
+        BlockTransactionResponse, ConstructionCombineRequest, ConstructionCombineResponse,
         ConstructionDeriveRequest, ConstructionDeriveResponse, ConstructionHashRequest,
         ConstructionMetadata, ConstructionMetadataRequest, ConstructionMetadataResponse,
         ConstructionParseRequest, ConstructionParseResponse, ConstructionPayloadsRequest,
         ConstructionPayloadsResponse, ConstructionPreprocessRequest,
         ConstructionPreprocessResponse, ConstructionSubmitRequest, ConstructionSubmitResponse,
-        Error, MetadataRequest, NetworkIdentifier, NetworkListResponse, NetworkOptionsResponse,
-        NetworkRequest, NetworkStatusResponse, Operation, PreprocessMetadata, PublicKey, Signature,
-        SignatureType, TransactionIdentifier, TransactionIdentifierResponse,
+        Error, MempoolRequest, MempoolResponse, MempoolTransactionRequest,
+        MempoolTransactionResponse, MetadataRequest, NetworkIdentifier, NetworkListResponse,
+        NetworkOptionsResponse, NetworkRequest, NetworkStatusResponse, Operation,
+        PreprocessMetadata, PublicKey, SearchTransactionsRequest, SearchTransactionsResponse,
+        Signature, SignatureType, TransactionIdentifier, TransactionIdentifierResponse,
     },
 };
 use anyhow::anyhow;
@@ -49,10 +52,24 @@ impl RosettaClient {
         self.make_call("account/balance", request).await
     }
 
+    pub async fn account_coins(
+        &self,
+        request: &AccountCoinsRequest,
+    ) -> anyhow::Result<AccountCoinsResponse> {
+        self.make_call("account/coins", request).await
+    }
+
     pub async fn block(&self, request: &BlockRequest) -> anyhow::Result<BlockResponse> {
         self.make_call("block", request).await
     }
 
+    pub async fn block_transaction(
+        &self,
+        request: &BlockTransactionRequest,
+    ) -> anyhow::Result<BlockTransactionResponse> {
+        self.make_call("block/transaction", request).await
+    }
+
     pub async fn combine(
         &self,
         request: &ConstructionCombineRequest,
@@ -109,6 +126,17 @@ impl RosettaClient {
         self.make_call("construction/submit", request).await
     }
 
+    pub async fn mempool(&self, request: &MempoolRequest) -> anyhow::Result<MempoolResponse> {
+        self.make_call("mempool", request).await
+    }
+
+    pub async fn mempool_transaction(
+        &self,
+        request: &MempoolTransactionRequest,
+    ) -> anyhow::Result<MempoolTransactionResponse> {
+        self.make_call("mempool/transaction", request).await
+    }
+
     pub async fn network_list(&self) -> anyhow::Result<NetworkListResponse> {
         self.make_call("network/list", &MetadataRequest {}).await
     }
@@ -127,6 +155,13 @@ impl RosettaClient {
         self.make_call("network/status", request).await
     }
 
+    pub async fn search_transactions(
+        &self,
+        request: &SearchTransactionsRequest,
+    ) -> anyhow::Result<SearchTransactionsResponse> {
+        self.make_call("search/transactions", request).await
+    }
+
     async fn make_call<'a, I: Serialize + Debug, O: DeserializeOwned>(
         &'a self,
         path: &'static str,
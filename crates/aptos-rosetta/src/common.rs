@@ -5,11 +5,11 @@ use crate::{
     error::{ApiError, ApiResult},
     types::{
         Currency, CurrencyMetadata, MetadataRequest, NetworkIdentifier, PartialBlockIdentifier,
-        APTOS_COIN_MODULE, APTOS_COIN_RESOURCE,
+        TransactionIdentifier, APTOS_COIN_MODULE, APTOS_COIN_RESOURCE,
     },
     RosettaContext,
 };
-use aptos_crypto::{ValidCryptoMaterial, ValidCryptoMaterialStringExt};
+use aptos_crypto::{HashValue, ValidCryptoMaterial, ValidCryptoMaterialStringExt};
 use aptos_logger::debug;
 use aptos_rest_client::{Account, Response};
 use aptos_sdk::move_types::{
@@ -19,7 +19,7 @@ use aptos_sdk::move_types::{
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use futures::future::BoxFuture;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{convert::Infallible, fmt::LowerHex, future::Future, str::FromStr};
+use std::{collections::HashSet, convert::Infallible, fmt::LowerHex, future::Future, str::FromStr};
 use warp::Filter;
 
 /// The year 2000 in milliseconds, as this is the lower limit for Rosetta API implementations
@@ -119,6 +119,18 @@ pub fn strip_hex_prefix(str: &str) -> &str {
     str.strip_prefix("0x").unwrap_or(str)
 }
 
+/// Parses the hash of a transaction, with or without a `0x` prefix
+pub fn parse_transaction_hash(
+    transaction_identifier: &TransactionIdentifier,
+) -> ApiResult<HashValue> {
+    HashValue::from_hex(strip_hex_prefix(&transaction_identifier.hash)).map_err(|err| {
+        ApiError::InvalidInput(Some(format!(
+            "Invalid transaction hash {}: {}",
+            transaction_identifier.hash, err
+        )))
+    })
+}
+
 pub fn encode_bcs<T: Serialize>(obj: &T) -> ApiResult<String> {
     let bytes = bcs::to_bytes(obj)?;
     Ok(hex::encode(bytes))
@@ -144,7 +156,8 @@ pub fn native_coin() -> Currency {
         symbol: DEFAULT_COIN.to_string(),
         decimals: DEFAULT_DECIMALS,
         metadata: Some(CurrencyMetadata {
-            move_type: native_coin_tag().to_string(),
+            move_type: Some(native_coin_tag().to_string()),
+            fa_address: None,
         }),
     }
 }
@@ -158,14 +171,37 @@ pub fn native_coin_tag() -> TypeTag {
     }))
 }
 
-pub fn is_native_coin(currency: &Currency) -> ApiResult<()> {
-    if currency == &native_coin() {
+/// Checks the currency is one of the currencies supported by the server
+pub fn is_supported_currency(
+    server_context: &RosettaContext,
+    currency: &Currency,
+) -> ApiResult<()> {
+    if server_context.currencies.contains(currency) {
         Ok(())
     } else {
         Err(ApiError::UnsupportedCurrency(Some(currency.symbol.clone())))
     }
 }
 
+/// Finds the supported currency backed by the `Coin<T>` with the given `T`
+pub fn find_coin_currency(currencies: &HashSet<Currency>, coin_type: &TypeTag) -> Option<Currency> {
+    currencies
+        .iter()
+        .find(|currency| currency.coin_type().as_ref() == Some(coin_type))
+        .cloned()
+}
+
+/// Finds the supported currency backed by the fungible asset with the given metadata address
+pub fn find_fa_currency(
+    currencies: &HashSet<Currency>,
+    metadata_address: AccountAddress,
+) -> Option<Currency> {
+    currencies
+        .iter()
+        .find(|currency| currency.fa_address() == Some(metadata_address))
+        .cloned()
+}
+
 /// Determines which block to pull for the request
 pub async fn get_block_index_from_request(
     server_context: &RosettaContext,
@@ -280,23 +316,25 @@ pub fn to_hex_lower<T: LowerHex>(obj: &T) -> String {
     format!("{:x}", obj)
 }
 
-/// Retrieves the currency from the given parameters
-/// TODO: What do do about the type params?
-pub fn parse_currency(address: AccountAddress, module: &str, name: &str) -> ApiResult<Currency> {
-    match (address, module, name) {
-        (AccountAddress::ONE, APTOS_COIN_MODULE, APTOS_COIN_RESOURCE) => Ok(native_coin()),
-        _ => Err(ApiError::TransactionParseError(Some(format!(
-            "Invalid coin for transfer {}::{}::{}",
-            address, module, name
-        )))),
-    }
+/// Retrieves the currency for the coin type of a transfer
+pub fn parse_currency(server_context: &RosettaContext, coin_type: &TypeTag) -> ApiResult<Currency> {
+    find_coin_currency(&server_context.currencies, coin_type).ok_or_else(|| {
+        ApiError::TransactionParseError(Some(format!("Invalid coin for transfer {}", coin_type)))
+    })
 }
 
 #[cfg(test)]
 mod test {
-    use crate::common::BlockHash;
-    use aptos_types::chain_id::{ChainId, NamedChain};
-    use std::str::FromStr;
+    use crate::{
+        common::{find_coin_currency, find_fa_currency, native_coin, native_coin_tag, BlockHash},
+        types::{Currency, CurrencyMetadata},
+    };
+    use aptos_sdk::move_types::language_storage::TypeTag;
+    use aptos_types::{
+        account_address::AccountAddress,
+        chain_id::{ChainId, NamedChain},
+    };
+    use std::{collections::HashSet, str::FromStr};
 
     #[test]
     pub fn chain_id_height_check() {
@@ -339,4 +377,46 @@ mod test {
             BlockHash::from_str(str).expect_err("Invalid block hash");
         }
     }
+
+    #[test]
+    pub fn currency_lookup() {
+        let coin = Currency {
+            symbol: "USD".to_string(),
+            decimals: 6,
+            metadata: Some(CurrencyMetadata {
+                move_type: Some("0x1234::usd::USD".to_string()),
+                fa_address: None,
+            }),
+        };
+        let fa = Currency {
+            symbol: "EUR".to_string(),
+            decimals: 6,
+            metadata: Some(CurrencyMetadata {
+                move_type: None,
+                fa_address: Some("0x5678".to_string()),
+            }),
+        };
+        let currencies: HashSet<_> = vec![native_coin(), coin.clone(), fa.clone()]
+            .into_iter()
+            .collect();
+
+        assert_eq!(
+            find_coin_currency(&currencies, &native_coin_tag()),
+            Some(native_coin())
+        );
+        // Addresses are compared by value, not by their string representation
+        let coin_type = TypeTag::from_str(
+            "0x0000000000000000000000000000000000000000000000000000000000001234::usd::USD",
+        )
+        .unwrap();
+        assert_eq!(find_coin_currency(&currencies, &coin_type), Some(coin));
+        assert_eq!(
+            find_fa_currency(
+                &currencies,
+                AccountAddress::from_hex_literal("0x5678").unwrap()
+            ),
+            Some(fa)
+        );
+        assert_eq!(find_fa_currency(&currencies, AccountAddress::ONE), None);
+    }
 }
@@ -26,8 +26,8 @@
 
 use crate::{
     common::{
-        check_network, decode_bcs, decode_key, encode_bcs, get_account, handle_request,
-        native_coin, parse_currency, with_context,
+        check_network, decode_bcs, decode_key, encode_bcs, find_fa_currency, get_account,
+        handle_request, native_coin, parse_currency, with_context,
     },
     error::{ApiError, ApiResult},
     types::{InternalOperation, *},
@@ -39,10 +39,7 @@ use aptos_crypto::{
 };
 use aptos_global_constants::adjust_gas_headroom;
 use aptos_logger::debug;
-use aptos_sdk::{
-    move_types::language_storage::{StructTag, TypeTag},
-    transaction_builder::TransactionFactory,
-};
+use aptos_sdk::{move_types::language_storage::TypeTag, transaction_builder::TransactionFactory};
 use aptos_types::{
     account_address::AccountAddress,
     chain_id::ChainId,
@@ -528,8 +525,12 @@ async fn construction_parse(
                 module.name().as_str(),
                 function_name.as_str(),
             ) {
-                (AccountAddress::ONE, COIN_MODULE, TRANSFER_FUNCTION) => {
-                    parse_transfer_operation(sender, &type_args, &args)?
+                (AccountAddress::ONE, COIN_MODULE, TRANSFER_FUNCTION)
+                | (AccountAddress::ONE, APTOS_ACCOUNT_MODULE, TRANSFER_COINS_FUNCTION) => {
+                    parse_transfer_operation(&server_context, sender, &type_args, &args)?
+                },
+                (AccountAddress::ONE, PRIMARY_FUNGIBLE_STORE_MODULE, TRANSFER_FUNCTION) => {
+                    parse_fungible_asset_transfer_operation(&server_context, sender, &args)?
                 },
                 (AccountAddress::ONE, APTOS_ACCOUNT_MODULE, TRANSFER_FUNCTION) => {
                     parse_account_transfer_operation(sender, &type_args, &args)?
@@ -633,25 +634,17 @@ fn parse_create_account_operation(
 }
 
 fn parse_transfer_operation(
+    server_context: &RosettaContext,
     sender: AccountAddress,
     type_args: &[TypeTag],
     args: &[Vec<u8>],
 ) -> ApiResult<Vec<Operation>> {
     let mut operations = Vec::new();
 
-    // Check coin is the native coin
+    // Check coin is a supported currency
 
     let currency = match type_args.first() {
-        Some(TypeTag::Struct(struct_tag)) => {
-            let StructTag {
-                address,
-                module,
-                name,
-                ..
-            } = &**struct_tag;
-
-            parse_currency(*address, module.as_str(), name.as_str())?
-        },
+        Some(coin_type @ TypeTag::Struct(_)) => parse_currency(server_context, coin_type)?,
         _ => {
             return Err(ApiError::TransactionParseError(Some(
                 "No coin type in transfer".to_string(),
@@ -693,6 +686,59 @@ fn parse_transfer_operation(
     Ok(operations)
 }
 
+fn parse_fungible_asset_transfer_operation(
+    server_context: &RosettaContext,
+    sender: AccountAddress,
+    args: &[Vec<u8>],
+) -> ApiResult<Vec<Operation>> {
+    let metadata: AccountAddress = if let Some(metadata) = args.first() {
+        bcs::from_bytes(metadata)?
+    } else {
+        return Err(ApiError::TransactionParseError(Some(
+            "No metadata in fungible asset transfer".to_string(),
+        )));
+    };
+    let currency = find_fa_currency(&server_context.currencies, metadata).ok_or_else(|| {
+        ApiError::TransactionParseError(Some(format!(
+            "Invalid fungible asset for transfer {}",
+            metadata
+        )))
+    })?;
+
+    // The remaining arguments are the same as a coin transfer, the receiver and the amount
+    let receiver: AccountAddress = if let Some(receiver) = args.get(1) {
+        bcs::from_bytes(receiver)?
+    } else {
+        return Err(ApiError::TransactionParseError(Some(
+            "No receiver in fungible asset transfer".to_string(),
+        )));
+    };
+    let amount: u64 = if let Some(amount) = args.get(2) {
+        bcs::from_bytes(amount)?
+    } else {
+        return Err(ApiError::TransactionParseError(Some(
+            "No amount in fungible asset transfer".to_string(),
+        )));
+    };
+
+    Ok(vec![
+        Operation::withdraw(
+            0,
+            None,
+            AccountIdentifier::base_account(sender),
+            currency.clone(),
+            amount,
+        ),
+        Operation::deposit(
+            1,
+            None,
+            AccountIdentifier::base_account(receiver),
+            currency,
+            amount,
+        ),
+    ])
+}
+
 fn parse_account_transfer_operation(
     sender: AccountAddress,
     type_args: &[TypeTag],
@@ -1010,7 +1056,7 @@ async fn construction_payloads(
     check_network(request.network_identifier, &server_context)?;
 
     // Retrieve the real operation we're doing
-    let mut operation = InternalOperation::extract(&request.operations)?;
+    let mut operation = InternalOperation::extract(&server_context, &request.operations)?;
     let metadata = if let Some(ref metadata) = request.metadata {
         metadata
     } else {
@@ -1256,7 +1302,7 @@ async fn construction_preprocess(
     debug!("/construction/preprocess {:?}", request);
     check_network(request.network_identifier, &server_context)?;
 
-    let internal_operation = InternalOperation::extract(&request.operations)?;
+    let internal_operation = InternalOperation::extract(&server_context, &request.operations)?;
     let required_public_keys = vec![AccountIdentifier::base_account(internal_operation.sender())];
 
     if let Some(max_gas) = request
@@ -1363,6 +1409,7 @@ async fn construction_submit(
     let txn: SignedTransaction = decode_bcs(&request.signed_transaction, "SignedTransaction")?;
     let hash = txn.clone().committed_hash();
     rest_client.submit_bcs(&txn).await?;
+    server_context.add_submitted_transaction(hash, txn.expiration_timestamp_secs());
     Ok(ConstructionSubmitResponse {
         transaction_identifier: hash.into(),
     })
@@ -7,15 +7,21 @@
 
 use crate::{
     block::BlockRetriever,
-    common::{handle_request, with_context},
+    common::{handle_request, native_coin, with_context},
     error::{ApiError, ApiResult},
-    types::Store,
+    types::{Currency, Store},
 };
 use aptos_config::config::ApiConfig;
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
 use aptos_logger::{debug, warn};
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use aptos_warp_webserver::{logger, Error, WebServer};
-use std::{collections::BTreeMap, convert::Infallible, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    convert::Infallible,
+    sync::Arc,
+};
 use tokio::task::JoinHandle;
 use warp::{
     http::{HeaderValue, Method, StatusCode},
@@ -25,7 +31,9 @@ use warp::{
 mod account;
 mod block;
 mod construction;
+mod mempool;
 mod network;
+mod search;
 
 pub mod client;
 pub mod common;
@@ -46,6 +54,13 @@ pub struct RosettaContext {
     pub block_cache: Option<Arc<BlockRetriever>>,
     pub owner_addresses: Vec<AccountAddress>,
     pub pool_address_to_owner: BTreeMap<AccountAddress, AccountAddress>,
+    /// Currencies supported by the server, always including the native coin
+    pub currencies: Arc<HashSet<Currency>>,
+    /// Transactions submitted through this server, with their expiration time in seconds
+    ///
+    /// Fullnodes don't expose their mempool, so these are the only transactions that can be
+    /// listed as pending
+    submitted_transactions: Arc<Mutex<BTreeMap<HashValue, u64>>>,
 }
 
 impl RosettaContext {
@@ -54,7 +69,10 @@ impl RosettaContext {
         chain_id: ChainId,
         block_cache: Option<Arc<BlockRetriever>>,
         owner_addresses: Vec<AccountAddress>,
+        mut currencies: HashSet<Currency>,
     ) -> Self {
+        currencies.insert(native_coin());
+
         let mut pool_address_to_owner = BTreeMap::new();
         if let Some(ref rest_client) = rest_client {
             // We have to now fill in all of the mappings of owner to pool address
@@ -87,9 +105,31 @@ impl RosettaContext {
             block_cache,
             owner_addresses,
             pool_address_to_owner,
+            currencies: Arc::new(currencies),
+            submitted_transactions: Arc::new(Mutex::new(BTreeMap::new())),
         }
     }
 
+    /// Keeps track of a submitted transaction until it expires
+    fn add_submitted_transaction(&self, hash: HashValue, expiration_timestamp_secs: u64) {
+        self.submitted_transactions
+            .lock()
+            .insert(hash, expiration_timestamp_secs);
+    }
+
+    /// Stops tracking a submitted transaction e.g. once it's committed
+    fn remove_submitted_transaction(&self, hash: &HashValue) {
+        self.submitted_transactions.lock().remove(hash);
+    }
+
+    /// Submitted transactions which haven't expired yet, as of `now_secs`
+    fn submitted_transactions(&self, now_secs: u64) -> Vec<HashValue> {
+        let mut submitted_transactions = self.submitted_transactions.lock();
+        submitted_transactions
+            .retain(|_, expiration_timestamp_secs| *expiration_timestamp_secs > now_secs);
+        submitted_transactions.keys().cloned().collect()
+    }
+
     fn rest_client(&self) -> ApiResult<Arc<aptos_rest_client::Client>> {
         if let Some(ref client) = self.rest_client {
             Ok(client.clone())
@@ -113,6 +153,7 @@ pub fn bootstrap(
     api_config: ApiConfig,
     rest_client: Option<aptos_rest_client::Client>,
     owner_addresses: Vec<AccountAddress>,
+    currencies: HashSet<Currency>,
 ) -> anyhow::Result<tokio::runtime::Runtime> {
     let runtime = aptos_runtimes::spawn_named_runtime("rosetta".into(), None);
 
@@ -123,6 +164,7 @@ pub fn bootstrap(
         api_config,
         rest_client,
         owner_addresses,
+        currencies,
     ));
     Ok(runtime)
 }
@@ -133,6 +175,7 @@ pub async fn bootstrap_async(
     api_config: ApiConfig,
     rest_client: Option<aptos_rest_client::Client>,
     owner_addresses: Vec<AccountAddress>,
+    currencies: HashSet<Currency>,
 ) -> anyhow::Result<JoinHandle<()>> {
     debug!("Starting up Rosetta server with {:?}", api_config);
 
@@ -160,8 +203,14 @@ pub async fn bootstrap_async(
             ))
         });
 
-        let context =
-            RosettaContext::new(rest_client.clone(), chain_id, block_cache, owner_addresses).await;
+        let context = RosettaContext::new(
+            rest_client.clone(),
+            chain_id,
+            block_cache,
+            owner_addresses,
+            currencies,
+        )
+        .await;
         api.serve(routes(context)).await;
     });
     Ok(handle)
@@ -172,7 +221,9 @@ pub fn routes(
     context: RosettaContext,
 ) -> impl Filter<Extract = (impl Reply,), Error = Infallible> + Clone {
     account::routes(context.clone())
+        .or(account::coins_route(context.clone()))
         .or(block::block_route(context.clone()))
+        .or(block::block_transaction_route(context.clone()))
         .or(construction::combine_route(context.clone()))
         .or(construction::derive_route(context.clone()))
         .or(construction::hash_route(context.clone()))
@@ -181,9 +232,12 @@ pub fn routes(
         .or(construction::payloads_route(context.clone()))
         .or(construction::preprocess_route(context.clone()))
         .or(construction::submit_route(context.clone()))
+        .or(mempool::mempool_route(context.clone()))
+        .or(mempool::mempool_transaction_route(context.clone()))
         .or(network::list_route(context.clone()))
         .or(network::options_route(context.clone()))
         .or(network::status_route(context.clone()))
+        .or(search::search_transactions_route(context.clone()))
         .or(health_check_route(context))
         .with(
             warp::cors()
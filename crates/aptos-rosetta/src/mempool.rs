@@ -0,0 +1,125 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rosetta Mempool API
+//!
+//! Fullnodes don't allow listing their mempool, so only transactions submitted through this
+//! server are listed.  Any pending transaction can still be looked up by hash.
+//!
+//! See: [Mempool API Spec](https://www.rosetta-api.org/docs/MempoolApi.html)
+
+use crate::{
+    common::{check_network, handle_request, parse_transaction_hash, with_context},
+    error::{ApiError, ApiResult},
+    types::{
+        MempoolRequest, MempoolResponse, MempoolTransactionRequest, MempoolTransactionResponse,
+        Transaction,
+    },
+    RosettaContext,
+};
+use aptos_logger::{debug, trace};
+use aptos_rest_client::{
+    aptos_api_types::{AptosErrorCode, TransactionData},
+    error::RestError,
+};
+use warp::Filter;
+
+pub fn mempool_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("mempool")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(mempool))
+}
+
+pub fn mempool_transaction_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("mempool" / "transaction")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(mempool_transaction))
+}
+
+/// Lists transactions submitted through this server that are still pending
+///
+/// Transactions that have been committed, or dropped from mempool, are no longer tracked.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempool)
+async fn mempool(
+    request: MempoolRequest,
+    server_context: RosettaContext,
+) -> ApiResult<MempoolResponse> {
+    debug!("/mempool");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/mempool",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    let rest_client = server_context.rest_client()?;
+
+    let now_secs = aptos_infallible::duration_since_epoch().as_secs();
+    let mut transaction_identifiers = vec![];
+    for hash in server_context.submitted_transactions(now_secs) {
+        match rest_client.get_transaction_by_hash_bcs(hash).await {
+            Ok(response) => match response.into_inner() {
+                TransactionData::Pending(_) => transaction_identifiers.push(hash.into()),
+                TransactionData::OnChain(_) => server_context.remove_submitted_transaction(&hash),
+            },
+            Err(RestError::Api(err))
+                if err.error.error_code == AptosErrorCode::TransactionNotFound =>
+            {
+                server_context.remove_submitted_transaction(&hash)
+            },
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Ok(MempoolResponse {
+        transaction_identifiers,
+    })
+}
+
+/// Retrieves a pending transaction by hash
+///
+/// The operations are parsed from the payload, so they're only an estimate of what the
+/// transaction will do once it's executed.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/MempoolApi.html#mempooltransaction)
+async fn mempool_transaction(
+    request: MempoolTransactionRequest,
+    server_context: RosettaContext,
+) -> ApiResult<MempoolTransactionResponse> {
+    debug!("/mempool/transaction");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/mempool/transaction",
+    );
+
+    check_network(request.network_identifier, &server_context)?;
+    let rest_client = server_context.rest_client()?;
+    let hash = parse_transaction_hash(&request.transaction_identifier)?;
+
+    match rest_client
+        .get_transaction_by_hash_bcs(hash)
+        .await?
+        .into_inner()
+    {
+        TransactionData::Pending(txn) => Ok(MempoolTransactionResponse {
+            transaction: Transaction::from_pending_transaction(&server_context, *txn),
+        }),
+        TransactionData::OnChain(_) => {
+            server_context.remove_submitted_transaction(&hash);
+            Err(ApiError::TransactionNotFound(Some(format!(
+                "Transaction {} is no longer in mempool, it has been committed",
+                hash
+            ))))
+        },
+    }
+}
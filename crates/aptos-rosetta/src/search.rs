@@ -0,0 +1,246 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Rosetta Search API
+//!
+//! Transactions can be searched by hash, or by the account that sent them.  Other criteria
+//! would require an indexer, and are rejected.
+//!
+//! See: [Search API Spec](https://www.rosetta-api.org/docs/SearchApi.html)
+
+use crate::{
+    common::{check_network, handle_request, parse_transaction_hash, with_context},
+    error::{ApiError, ApiResult},
+    types::{
+        BlockIdentifier, BlockTransaction, SearchTransactionsRequest, SearchTransactionsResponse,
+        Transaction, TransactionIdentifier,
+    },
+    RosettaContext,
+};
+use aptos_logger::{debug, trace};
+use aptos_rest_client::{
+    aptos_api_types::{AptosErrorCode, TransactionData, TransactionOnChainData},
+    error::RestError,
+};
+use aptos_types::{account_address::AccountAddress, account_config::AccountResource};
+use warp::Filter;
+
+/// The only supported search operator, as only one criteria can be searched on
+const AND_OPERATOR: &str = "and";
+
+/// Number of transactions returned when searching by sender without a `limit`
+const DEFAULT_SEARCH_LIMIT: u16 = 25;
+
+/// Maximum number of transactions returned when searching by sender
+const MAX_SEARCH_LIMIT: u16 = 100;
+
+pub fn search_transactions_route(
+    server_context: RosettaContext,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    warp::path!("search" / "transactions")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(with_context(server_context))
+        .and_then(handle_request(search_transactions))
+}
+
+/// Searches committed transactions by hash or by sender
+///
+/// When searching by sender, `offset` is the sequence number of the first transaction to
+/// return, at most `limit` transactions are returned, and `next_offset` is set if there may be
+/// more transactions.  `total_count` is the number of transactions matching the search, across
+/// all pages.  If both a hash and an account are given, the transaction must be sent by the
+/// account.
+///
+/// [API Spec](https://www.rosetta-api.org/docs/SearchApi.html#searchtransactions)
+async fn search_transactions(
+    request: SearchTransactionsRequest,
+    server_context: RosettaContext,
+) -> ApiResult<SearchTransactionsResponse> {
+    debug!("/search/transactions");
+    trace!(
+        request = ?request,
+        server_context = ?server_context,
+        "/search/transactions",
+    );
+
+    check_network(request.network_identifier.clone(), &server_context)?;
+    check_search_criteria(&request)?;
+
+    let sender = if let Some(ref account) = request.account_identifier {
+        if !account.is_base_account() {
+            return Err(ApiError::InvalidInput(Some(
+                "Only base accounts can be searched on".to_string(),
+            )));
+        }
+        Some(account.account_address()?)
+    } else {
+        None
+    };
+
+    let mut next_offset = None;
+    let mut total_count = None;
+    let txns = if let Some(ref transaction_identifier) = request.transaction_identifier {
+        let txns = search_by_hash(&server_context, transaction_identifier).await?;
+        txns.into_iter()
+            .filter(|txn| sender.is_none() || txn_sender(txn) == sender)
+            .collect()
+    } else if let Some(sender) = sender {
+        let limit = request.limit.map_or(DEFAULT_SEARCH_LIMIT, |limit| {
+            limit.min(MAX_SEARCH_LIMIT as u64) as u16
+        });
+        let txns = server_context
+            .rest_client()?
+            .get_account_transactions_bcs(sender, request.offset, Some(limit))
+            .await?
+            .into_inner();
+
+        // A full page means there may be more transactions after the last one
+        if txns.len() == limit as usize {
+            next_offset = txns
+                .last()
+                .and_then(|txn| txn.transaction.try_as_signed_user_txn())
+                .map(|txn| txn.sequence_number() + 1);
+        }
+        total_count =
+            Some(count_sent_transactions(&server_context, sender, request.max_block).await?);
+        txns
+    } else {
+        return Err(ApiError::InvalidInput(Some(
+            "Either a transaction_identifier or an account_identifier must be provided".to_string(),
+        )));
+    };
+
+    let rest_client = server_context.rest_client()?;
+    let mut transactions = vec![];
+    for txn in txns {
+        let block = rest_client
+            .get_block_by_version_bcs(txn.version, false)
+            .await?
+            .into_inner();
+
+        // Transactions are in version order, so everything after is in a later block
+        if request
+            .max_block
+            .map_or(false, |max_block| block.block_height > max_block)
+        {
+            next_offset = None;
+            break;
+        }
+
+        transactions.push(BlockTransaction {
+            block_identifier: BlockIdentifier::from_block(&block, server_context.chain_id),
+            transaction: Transaction::from_transaction(&server_context, txn).await?,
+        });
+    }
+
+    Ok(SearchTransactionsResponse {
+        total_count: total_count.unwrap_or(transactions.len() as u64),
+        transactions,
+        next_offset,
+    })
+}
+
+/// Rejects criteria that can't be searched on without an indexer
+fn check_search_criteria(request: &SearchTransactionsRequest) -> ApiResult<()> {
+    if let Some(ref operator) = request.operator {
+        if operator != AND_OPERATOR {
+            return Err(ApiError::InvalidInput(Some(format!(
+                "Unsupported search operator {}, only {} is supported",
+                operator, AND_OPERATOR
+            ))));
+        }
+    }
+
+    if request.coin_identifier.is_some()
+        || request.currency.is_some()
+        || request.status.is_some()
+        || request.operation_type.is_some()
+        || request.address.is_some()
+        || request.success.is_some()
+    {
+        return Err(ApiError::InvalidInput(Some(
+            "Only transaction_identifier and account_identifier can be searched on".to_string(),
+        )));
+    }
+
+    Ok(())
+}
+
+/// Looks up a committed transaction by hash, pending and unknown transactions aren't found
+async fn search_by_hash(
+    server_context: &RosettaContext,
+    transaction_identifier: &TransactionIdentifier,
+) -> ApiResult<Vec<TransactionOnChainData>> {
+    let hash = parse_transaction_hash(transaction_identifier)?;
+    match server_context
+        .rest_client()?
+        .get_transaction_by_hash_bcs(hash)
+        .await
+    {
+        Ok(response) => match response.into_inner() {
+            TransactionData::OnChain(txn) => Ok(vec![txn]),
+            TransactionData::Pending(_) => Ok(vec![]),
+        },
+        Err(RestError::Api(err)) if err.error.error_code == AptosErrorCode::TransactionNotFound => {
+            Ok(vec![])
+        },
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Counts the transactions sent by `sender`, up to and including `max_block` if it's set
+///
+/// Every committed transaction of an account increments its sequence number, so the sequence
+/// number at the end of the block is the number of transactions it sent up to that block.
+async fn count_sent_transactions(
+    server_context: &RosettaContext,
+    sender: AccountAddress,
+    max_block: Option<u64>,
+) -> ApiResult<u64> {
+    let rest_client = server_context.rest_client()?;
+    let max_version = match max_block {
+        Some(max_block) => match rest_client.get_block_by_height_bcs(max_block, false).await {
+            Ok(response) => Some(response.into_inner().last_version),
+            // The block isn't committed yet, so every transaction so far is before it
+            Err(RestError::Api(err)) if err.error.error_code == AptosErrorCode::BlockNotFound => {
+                None
+            },
+            Err(err) => return Err(err.into()),
+        },
+        None => None,
+    };
+
+    let account = match max_version {
+        Some(version) => {
+            rest_client
+                .get_account_resource_at_version_bcs::<AccountResource>(
+                    sender,
+                    "0x1::account::Account",
+                    version,
+                )
+                .await
+        },
+        None => rest_client.get_account_bcs(sender).await,
+    };
+    match account {
+        Ok(response) => Ok(response.into_inner().sequence_number()),
+        // An account that doesn't exist hasn't sent any transactions
+        Err(RestError::Api(err))
+            if matches!(
+                err.error.error_code,
+                AptosErrorCode::AccountNotFound | AptosErrorCode::ResourceNotFound
+            ) =>
+        {
+            Ok(0)
+        },
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Sender of a transaction, only user transactions have one
+fn txn_sender(txn: &TransactionOnChainData) -> Option<AccountAddress> {
+    txn.transaction
+        .try_as_signed_user_txn()
+        .map(|txn| txn.sender())
+}
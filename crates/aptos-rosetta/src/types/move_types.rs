@@ -16,6 +16,9 @@ pub const STAKING_PROXY_MODULE: &str = "staking_proxy";
 pub const STAKING_CONTRACT_MODULE: &str = "staking_contract";
 pub const VESTING_MODULE: &str = "vesting";
 pub const DELEGATION_POOL_MODULE: &str = "delegation_pool";
+pub const OBJECT_MODULE: &str = "object";
+pub const FUNGIBLE_ASSET_MODULE: &str = "fungible_asset";
+pub const PRIMARY_FUNGIBLE_STORE_MODULE: &str = "primary_fungible_store";
 
 pub const ACCOUNT_RESOURCE: &str = "Account";
 pub const APTOS_COIN_RESOURCE: &str = "AptosCoin";
@@ -28,9 +31,15 @@ pub const STAKING_GROUP_UPDATE_COMMISSION_RESOURCE: &str = "StakingGroupUpdateCo
 pub const VESTING_RESOURCE: &str = "Vesting";
 pub const DELEGATION_POOL_RESOURCE: &str = "DelegationPool";
 pub const WITHDRAW_STAKE_EVENT: &str = "WithdrawStakeEvent";
+pub const OBJECT_GROUP_RESOURCE: &str = "ObjectGroup";
+pub const OBJECT_CORE_RESOURCE: &str = "ObjectCore";
+pub const FUNGIBLE_STORE_RESOURCE: &str = "FungibleStore";
+pub const FUNGIBLE_ASSET_EVENTS_RESOURCE: &str = "FungibleAssetEvents";
+pub const FUNGIBLE_ASSET_METADATA_RESOURCE: &str = "Metadata";
 
 pub const CREATE_ACCOUNT_FUNCTION: &str = "create_account";
 pub const TRANSFER_FUNCTION: &str = "transfer";
+pub const TRANSFER_COINS_FUNCTION: &str = "transfer_coins";
 
 // Staking Contract
 pub const RESET_LOCKUP_FUNCTION: &str = "reset_lockup";
@@ -244,3 +253,27 @@ pub struct WithdrawUndelegedEvent {
     pub delegator_address: AccountAddress,
     pub amount_withdrawn: u64,
 }
+
+// Objects and fungible assets
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ObjectCore {
+    pub guid_creation_num: u64,
+    pub owner: AccountAddress,
+    pub allow_ungated_transfer: bool,
+    pub transfer_events: EventHandle,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FungibleStore {
+    /// Address of the `Object<Metadata>` of the fungible asset
+    pub metadata: AccountAddress,
+    pub balance: u64,
+    pub frozen: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FungibleAssetEvents {
+    pub deposit_events: EventHandle,
+    pub withdraw_events: EventHandle,
+    pub frozen_events: EventHandle,
+}
@@ -6,7 +6,7 @@
 //! [Spec](https://www.rosetta-api.org/docs/api_objects.html)
 
 use crate::{
-    common::{is_native_coin, native_coin, native_coin_tag},
+    common::{find_coin_currency, find_fa_currency, is_supported_currency, native_coin},
     construction::{
         parse_create_stake_pool_operation, parse_delegation_pool_add_stake_operation,
         parse_delegation_pool_unlock_operation, parse_delegation_pool_withdraw_operation,
@@ -33,11 +33,14 @@ use aptos_types::{
     event::EventKey,
     stake_pool::{SetOperatorEvent, StakePool},
     state_store::state_key::{StateKey, StateKeyInner},
-    transaction::{EntryFunction, TransactionPayload},
+    transaction::{EntryFunction, SignedTransaction, TransactionPayload},
     write_set::{WriteOp, WriteSet},
 };
 use itertools::Itertools;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
 use serde::{Deserialize, Serialize};
 use std::{
     cmp::Ordering,
@@ -127,9 +130,29 @@ pub struct Block {
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct BlockTransaction {
     /// Block associated with transaction
-    block_identifier: BlockIdentifier,
+    pub block_identifier: BlockIdentifier,
     /// Transaction associated with block
-    transaction: Transaction,
+    pub transaction: Transaction,
+}
+
+/// An unspent balance of a [`Currency`] owned by an account
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/Coin.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct Coin {
+    /// Unique identifier of the coin
+    pub coin_identifier: CoinIdentifier,
+    /// Amount of the coin
+    pub amount: Amount,
+}
+
+/// Aptos is account based, so a coin is identified by its owner and currency as
+/// `<address>:<symbol>`
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/CoinIdentifier.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CoinIdentifier {
+    pub identifier: String,
 }
 
 /// Currency represented as atomic units including decimals
@@ -145,9 +168,34 @@ pub struct Currency {
     pub metadata: Option<CurrencyMetadata>,
 }
 
+impl Currency {
+    /// The `Coin<T>` type backing this currency, if it's a coin
+    pub fn coin_type(&self) -> Option<TypeTag> {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.move_type.as_ref())
+            .and_then(|move_type| TypeTag::from_str(move_type).ok())
+    }
+
+    /// The address of the fungible asset metadata object, if it's a fungible asset
+    pub fn fa_address(&self) -> Option<AccountAddress> {
+        self.metadata
+            .as_ref()
+            .and_then(|metadata| metadata.fa_address.as_ref())
+            .and_then(|fa_address| AccountAddress::from_str(fa_address).ok())
+    }
+}
+
+/// Identifies the on-chain asset backing a [`Currency`]
+///
+/// Exactly one of the two must be set, either the `Coin<T>` type for coins, or the address of the
+/// metadata object for fungible assets.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct CurrencyMetadata {
-    pub move_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub move_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fa_address: Option<String>,
 }
 
 /// Various signing curves supported by Rosetta.  We only use [`CurveType::Edwards25519`]
@@ -908,10 +956,12 @@ impl Transaction {
         } else {
             // Parse all failed operations from the payload
             if let Some(user_txn) = maybe_user_txn {
-                let mut ops = parse_failed_operations_from_txn_payload(
+                let mut ops = parse_operations_from_txn_payload(
+                    server_context,
                     operation_index,
                     user_txn.sender(),
                     user_txn.payload(),
+                    Some(OperationStatusType::Failure),
                 );
                 operation_index += ops.len() as u64;
                 operations.append(&mut ops);
@@ -946,16 +996,50 @@ impl Transaction {
             },
         })
     }
+
+    /// Builds a transaction that is still pending in mempool
+    ///
+    /// There are no state changes yet, so the operations are estimated from the payload and have
+    /// no status.  The version isn't known until the transaction is committed, so it's left as 0.
+    pub fn from_pending_transaction(
+        server_context: &RosettaContext,
+        txn: SignedTransaction,
+    ) -> Transaction {
+        let mut operations =
+            parse_operations_from_txn_payload(server_context, 0, txn.sender(), txn.payload(), None);
+        operations.sort();
+        for (i, operation) in operations.iter_mut().enumerate() {
+            operation.operation_identifier.index = i as u64;
+        }
+
+        Transaction {
+            transaction_identifier: txn.committed_hash().into(),
+            operations,
+            metadata: TransactionMetadata {
+                transaction_type: TransactionType::User,
+                version: 0.into(),
+                failed: false,
+                vm_status: PENDING_VM_STATUS.to_string(),
+            },
+        }
+    }
 }
 
+/// VM status of a transaction that hasn't been executed yet
+const PENDING_VM_STATUS: &str = "Pending";
+
 /// Parses operations from the transaction payload
 ///
-/// This case only occurs if the transaction failed, and that's because it's less accurate
-/// than just following the state changes
-fn parse_failed_operations_from_txn_payload(
+/// This case only occurs if the transaction failed or is still pending, and that's because it's
+/// less accurate than just following the state changes.  Pending operations have no status.
+/// Staking operations are only estimated for pending transactions; failed staking transactions
+/// keep reporting no operations.
+fn parse_operations_from_txn_payload(
+    server_context: &RosettaContext,
     operation_index: u64,
     sender: AccountAddress,
     payload: &TransactionPayload,
+    status: Option<OperationStatusType>,
 ) -> Vec<Operation> {
     let mut operations = vec![];
     if let TransactionPayload::EntryFunction(inner) = payload {
@@ -964,26 +1048,53 @@ fn parse_failed_operations_from_txn_payload(
             inner.module().name().as_str(),
             inner.function().as_str(),
         ) {
-            (AccountAddress::ONE, COIN_MODULE, TRANSFER_FUNCTION) => {
+            (AccountAddress::ONE, COIN_MODULE, TRANSFER_FUNCTION)
+            | (AccountAddress::ONE, APTOS_ACCOUNT_MODULE, TRANSFER_COINS_FUNCTION) => {
                 // Only put the transfer in if we can understand the currency
                 if let Some(type_tag) = inner.ty_args().first() {
                     // We don't want to do lookups on failures for currencies that don't exist,
-                    // so we only look up cached info not new info
-                    // TODO: If other coins are supported, this will need to be updated to handle more coins
-                    if type_tag == &native_coin_tag() {
+                    // so we only look up the configured currencies
+                    if let Some(currency) = find_coin_currency(&server_context.currencies, type_tag)
+                    {
                         operations = parse_transfer_from_txn_payload(
                             inner,
-                            native_coin(),
+                            inner.args(),
+                            currency,
                             sender,
                             operation_index,
+                            status,
                         )
                     }
                 }
             },
             (AccountAddress::ONE, APTOS_ACCOUNT_MODULE, TRANSFER_FUNCTION) => {
                 // We could add a create here as well, but we don't know if it will actually happen
-                operations =
-                    parse_transfer_from_txn_payload(inner, native_coin(), sender, operation_index)
+                operations = parse_transfer_from_txn_payload(
+                    inner,
+                    inner.args(),
+                    native_coin(),
+                    sender,
+                    operation_index,
+                    status,
+                )
+            },
+            (AccountAddress::ONE, PRIMARY_FUNGIBLE_STORE_MODULE, TRANSFER_FUNCTION) => {
+                // The first argument is the metadata object, followed by the receiver and amount
+                if let Some(currency) = inner
+                    .args()
+                    .first()
+                    .and_then(|encoded| bcs::from_bytes::<AccountAddress>(encoded).ok())
+                    .and_then(|metadata| find_fa_currency(&server_context.currencies, metadata))
+                {
+                    operations = parse_transfer_from_txn_payload(
+                        inner,
+                        &inner.args()[1..],
+                        currency,
+                        sender,
+                        operation_index,
+                        status,
+                    )
+                }
             },
             (AccountAddress::ONE, ACCOUNT_MODULE, CREATE_ACCOUNT_FUNCTION) => {
                 if let Some(Ok(address)) = inner
@@ -993,7 +1104,7 @@ fn parse_failed_operations_from_txn_payload(
                 {
                     operations.push(Operation::create_account(
                         operation_index,
-                        Some(OperationStatusType::Failure),
+                        status,
                         address,
                         sender,
                     ));
@@ -1010,7 +1121,10 @@ fn parse_failed_operations_from_txn_payload(
                     parse_set_operator_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
+                    }
+                    if status.is_none() {
+                        operations = ops;
                     }
                 } else {
                     warn!("Failed to parse set operator {:?}", inner);
@@ -1021,7 +1135,10 @@ fn parse_failed_operations_from_txn_payload(
                     parse_set_voter_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
+                    }
+                    if status.is_none() {
+                        operations = ops;
                     }
                 } else {
                     warn!("Failed to parse set voter {:?}", inner);
@@ -1032,7 +1149,10 @@ fn parse_failed_operations_from_txn_payload(
                     parse_reset_lockup_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
+                    }
+                    if status.is_none() {
+                        operations = ops;
                     }
                 } else {
                     warn!("Failed to parse reset lockup {:?}", inner);
@@ -1043,7 +1163,10 @@ fn parse_failed_operations_from_txn_payload(
                     parse_update_commission_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
+                    }
+                    if status.is_none() {
+                        operations = ops;
                     }
                 } else {
                     warn!("Failed to parse update commission {:?}", inner);
@@ -1054,7 +1177,10 @@ fn parse_failed_operations_from_txn_payload(
                     parse_create_stake_pool_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
+                    }
+                    if status.is_none() {
+                        operations = ops;
                     }
                 } else {
                     warn!("Failed to parse create staking pool {:?}", inner);
@@ -1065,7 +1191,10 @@ fn parse_failed_operations_from_txn_payload(
                     parse_unlock_stake_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
+                    }
+                    if status.is_none() {
+                        operations = ops;
                     }
                 } else {
                     warn!("Failed to parse unlock stake {:?}", inner);
@@ -1078,7 +1207,10 @@ fn parse_failed_operations_from_txn_payload(
                     inner.args(),
                 ) {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
+                    }
+                    if status.is_none() {
+                        operations = ops;
                     }
                 } else {
                     warn!("Failed to parse distribute staking rewards {:?}", inner);
@@ -1089,7 +1221,10 @@ fn parse_failed_operations_from_txn_payload(
                     parse_delegation_pool_add_stake_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
+                    }
+                    if status.is_none() {
+                        operations = ops;
                     }
                 } else {
                     warn!("Failed to parse delegation_pool::add_stake {:?}", inner);
@@ -1100,7 +1235,10 @@ fn parse_failed_operations_from_txn_payload(
                     parse_delegation_pool_withdraw_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
+                    }
+                    if status.is_none() {
+                        operations = ops;
                     }
                 } else {
                     warn!("Failed to parse delegation_pool::withdraw {:?}", inner);
@@ -1111,7 +1249,10 @@ fn parse_failed_operations_from_txn_payload(
                     parse_delegation_pool_unlock_operation(sender, inner.ty_args(), inner.args())
                 {
                     if let Some(operation) = ops.get_mut(0) {
-                        operation.status = Some(OperationStatusType::Failure.to_string());
+                        operation.status = status.map(|status| status.to_string());
+                    }
+                    if status.is_none() {
+                        operations = ops;
                     }
                 } else {
                     warn!("Failed to parse delegation_pool::unlock {:?}", inner);
@@ -1127,13 +1268,14 @@ fn parse_failed_operations_from_txn_payload(
 
 fn parse_transfer_from_txn_payload(
     payload: &EntryFunction,
+    args: &[Vec<u8>],
     currency: Currency,
     sender: AccountAddress,
     operation_index: u64,
+    status: Option<OperationStatusType>,
 ) -> Vec<Operation> {
     let mut operations = vec![];
 
-    let args = payload.args();
     let maybe_receiver = args
         .get(0)
         .map(|encoded| bcs::from_bytes::<AccountAddress>(encoded));
@@ -1142,14 +1284,14 @@ fn parse_transfer_from_txn_payload(
     if let (Some(Ok(receiver)), Some(Ok(amount))) = (maybe_receiver, maybe_amount) {
         operations.push(Operation::withdraw(
             operation_index,
-            Some(OperationStatusType::Failure),
+            status,
             AccountIdentifier::base_account(sender),
             currency.clone(),
             amount,
         ));
         operations.push(Operation::deposit(
             operation_index + 1,
-            Some(OperationStatusType::Failure),
+            status,
             AccountIdentifier::base_account(receiver),
             currency,
             amount,
@@ -1235,10 +1377,10 @@ async fn parse_operations_from_write_set(
         },
         (AccountAddress::ONE, COIN_MODULE, COIN_STORE_RESOURCE, 1) => {
             if let Some(type_tag) = struct_tag.type_params.first() {
-                // TODO: This will need to be updated to support more coins
-                if type_tag == &native_coin_tag() {
+                // Only coins with a configured currency are parsed
+                if let Some(currency) = find_coin_currency(&server_context.currencies, type_tag) {
                     parse_coinstore_changes(
-                        native_coin(),
+                        currency,
                         version,
                         address,
                         data,
@@ -1257,6 +1399,16 @@ async fn parse_operations_from_write_set(
                 Ok(vec![])
             }
         },
+        (AccountAddress::ONE, OBJECT_MODULE, OBJECT_GROUP_RESOURCE, 0) => {
+            parse_fungible_store_changes(
+                server_context,
+                version,
+                address,
+                data,
+                events,
+                operation_index,
+            )
+        },
         _ => {
             // Any unknown type will just skip the operations
             Ok(vec![])
@@ -1794,6 +1946,92 @@ async fn parse_coinstore_changes(
     Ok(operations)
 }
 
+/// Parses withdraws and deposits of a fungible store
+///
+/// Fungible stores are objects, so they live in the object's resource group along with the
+/// `ObjectCore` holding the owner of the store.  The balance changes are attributed to the owner.
+fn parse_fungible_store_changes(
+    server_context: &RosettaContext,
+    version: u64,
+    address: AccountAddress,
+    data: &[u8],
+    events: &[ContractEvent],
+    mut operation_index: u64,
+) -> ApiResult<Vec<Operation>> {
+    let group: BTreeMap<StructTag, Vec<u8>> = if let Ok(group) = bcs::from_bytes(data) {
+        group
+    } else {
+        warn!(
+            "Object group failed to parse for address {} at version {}",
+            address, version
+        );
+        return Ok(vec![]);
+    };
+
+    let mut maybe_owner = None;
+    let mut maybe_store = None;
+    let mut maybe_events = None;
+    for (struct_tag, bytes) in group.iter() {
+        match (
+            struct_tag.address,
+            struct_tag.module.as_str(),
+            struct_tag.name.as_str(),
+        ) {
+            (AccountAddress::ONE, OBJECT_MODULE, OBJECT_CORE_RESOURCE) => {
+                maybe_owner = bcs::from_bytes::<ObjectCore>(bytes)
+                    .ok()
+                    .map(|object| object.owner);
+            },
+            (AccountAddress::ONE, FUNGIBLE_ASSET_MODULE, FUNGIBLE_STORE_RESOURCE) => {
+                maybe_store = bcs::from_bytes::<FungibleStore>(bytes).ok();
+            },
+            (AccountAddress::ONE, FUNGIBLE_ASSET_MODULE, FUNGIBLE_ASSET_EVENTS_RESOURCE) => {
+                maybe_events = bcs::from_bytes::<FungibleAssetEvents>(bytes).ok();
+            },
+            _ => {},
+        }
+    }
+
+    // Not every object is a fungible store, in which case there's nothing to do
+    let (owner, store, store_events) = match (maybe_owner, maybe_store, maybe_events) {
+        (Some(owner), Some(store), Some(store_events)) => (owner, store, store_events),
+        _ => return Ok(vec![]),
+    };
+
+    // Only fungible assets with a configured currency are parsed
+    let currency = match find_fa_currency(&server_context.currencies, store.metadata) {
+        Some(currency) => currency,
+        None => return Ok(vec![]),
+    };
+
+    let mut operations = vec![];
+    let withdraw_amounts = get_amount_from_event(events, store_events.withdraw_events.key());
+    for amount in withdraw_amounts {
+        operations.push(Operation::withdraw(
+            operation_index,
+            Some(OperationStatusType::Success),
+            AccountIdentifier::base_account(owner),
+            currency.clone(),
+            amount,
+        ));
+        operation_index += 1;
+    }
+
+    let deposit_amounts = get_amount_from_event(events, store_events.deposit_events.key());
+    for amount in deposit_amounts {
+        operations.push(Operation::deposit(
+            operation_index,
+            Some(OperationStatusType::Success),
+            AccountIdentifier::base_account(owner),
+            currency.clone(),
+            amount,
+        ));
+        operation_index += 1;
+    }
+
+    Ok(operations)
+}
+
 /// Pulls the balance change from a withdraw or deposit event
 fn get_amount_from_event(events: &[ContractEvent], event_key: &EventKey) -> Vec<u64> {
     filter_events(events, event_key, |event_key, event| {
@@ -1860,7 +2098,10 @@ pub enum InternalOperation {
 
 impl InternalOperation {
     /// Pulls the [`InternalOperation`] from the set of [`Operation`]
-    pub fn extract(operations: &Vec<Operation>) -> ApiResult<InternalOperation> {
+    pub fn extract(
+        server_context: &RosettaContext,
+        operations: &Vec<Operation>,
+    ) -> ApiResult<InternalOperation> {
         match operations.len() {
             1 => {
                 if let Some(operation) = operations.first() {
@@ -2106,7 +2347,10 @@ impl InternalOperation {
                     operations
                 ))))
             },
-            2 => Ok(Self::Transfer(Transfer::extract_transfer(operations)?)),
+            2 => Ok(Self::Transfer(Transfer::extract_transfer(
+                server_context,
+                operations,
+            )?)),
             _ => Err(ApiError::InvalidOperations(Some(format!(
                 "Unrecognized operation combination {:?}",
                 operations
@@ -2140,13 +2384,7 @@ impl InternalOperation {
                 aptos_stdlib::aptos_account_create_account(create_account.new_account),
                 create_account.sender,
             ),
-            InternalOperation::Transfer(transfer) => {
-                is_native_coin(&transfer.currency)?;
-                (
-                    aptos_stdlib::aptos_account_transfer(transfer.receiver, transfer.amount.0),
-                    transfer.sender,
-                )
-            },
+            InternalOperation::Transfer(transfer) => (transfer.payload()?, transfer.sender),
             InternalOperation::SetOperator(set_operator) => {
                 if set_operator.old_operator.is_none() {
                     return Err(ApiError::InvalidInput(Some(
@@ -2252,7 +2490,10 @@ pub struct Transfer {
 }
 
 impl Transfer {
-    pub fn extract_transfer(operations: &Vec<Operation>) -> ApiResult<Transfer> {
+    pub fn extract_transfer(
+        server_context: &RosettaContext,
+        operations: &Vec<Operation>,
+    ) -> ApiResult<Transfer> {
         // Only support 1:1 P2P transfer
         // This is composed of a Deposit and a Withdraw operation
         if operations.len() != 2 {
@@ -2324,8 +2565,7 @@ impl Transfer {
         }
 
         // Check that the currency is supported
-        // TODO: in future use currency, since there's more than just 1
-        is_native_coin(&withdraw_amount.currency)?;
+        is_supported_currency(server_context, &withdraw_amount.currency)?;
 
         let withdraw_value = i128::from_str(&withdraw_amount.value)
             .map_err(|_| ApiError::InvalidTransferOperations(Some("Withdraw amount is invalid")))?;
@@ -2356,6 +2596,45 @@ impl Transfer {
             currency: deposit_amount.currency.clone(),
         })
     }
+
+    /// Builds the entry function for the transfer, based on what backs the currency
+    pub fn payload(&self) -> ApiResult<aptos_types::transaction::TransactionPayload> {
+        if self.currency == native_coin() {
+            Ok(aptos_stdlib::aptos_account_transfer(
+                self.receiver,
+                self.amount.0,
+            ))
+        } else if let Some(coin_type) = self.currency.coin_type() {
+            Ok(aptos_stdlib::aptos_account_transfer_coins(
+                coin_type,
+                self.receiver,
+                self.amount.0,
+            ))
+        } else if let Some(metadata) = self.currency.fa_address() {
+            Ok(TransactionPayload::EntryFunction(EntryFunction::new(
+                ModuleId::new(
+                    AccountAddress::ONE,
+                    Identifier::new(PRIMARY_FUNGIBLE_STORE_MODULE).unwrap(),
+                ),
+                Identifier::new(TRANSFER_FUNCTION).unwrap(),
+                vec![TypeTag::Struct(Box::new(StructTag {
+                    address: AccountAddress::ONE,
+                    module: Identifier::new(FUNGIBLE_ASSET_MODULE).unwrap(),
+                    name: Identifier::new(FUNGIBLE_ASSET_METADATA_RESOURCE).unwrap(),
+                    type_params: vec![],
+                }))],
+                vec![
+                    bcs::to_bytes(&metadata)?,
+                    bcs::to_bytes(&self.receiver)?,
+                    bcs::to_bytes(&self.amount.0)?,
+                ],
+            )))
+        } else {
+            Err(ApiError::UnsupportedCurrency(Some(
+                self.currency.symbol.clone(),
+            )))
+        }
+    }
 }
 
 /// Set operator
@@ -2430,3 +2709,112 @@ pub struct UnlockDelegatedStake {
     pub pool_address: AccountAddress,
     pub amount: u64,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, Uniform};
+    use aptos_types::{chain_id::ChainId, transaction::RawTransaction};
+    use std::collections::HashSet;
+
+    async fn test_context() -> RosettaContext {
+        RosettaContext::new(None, ChainId::test(), None, vec![], HashSet::new()).await
+    }
+
+    #[tokio::test]
+    async fn test_staking_operations_are_only_estimated_when_pending() {
+        let server_context = test_context().await;
+        let sender = AccountAddress::from_hex_literal("0xA").unwrap();
+        let payload = aptos_stdlib::staking_contract_switch_operator_with_same_commission(
+            AccountAddress::from_hex_literal("0xB").unwrap(),
+            AccountAddress::from_hex_literal("0xC").unwrap(),
+        );
+
+        let operations =
+            parse_operations_from_txn_payload(&server_context, 0, sender, &payload, None);
+        assert_eq!(1, operations.len());
+        assert_eq!(
+            OperationType::SetOperator.to_string(),
+            operations[0].operation_type
+        );
+        assert_eq!(None, operations[0].status);
+
+        // Failed staking transactions don't report any operations
+        let operations = parse_operations_from_txn_payload(
+            &server_context,
+            0,
+            sender,
+            &payload,
+            Some(OperationStatusType::Failure),
+        );
+        assert!(operations.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_operations_from_payload() {
+        let server_context = test_context().await;
+        let sender = AccountAddress::from_hex_literal("0xA").unwrap();
+        let receiver = AccountAddress::from_hex_literal("0xB").unwrap();
+        let payload = aptos_stdlib::aptos_account_transfer(receiver, 100);
+
+        for status in [None, Some(OperationStatusType::Failure)] {
+            let operations =
+                parse_operations_from_txn_payload(&server_context, 0, sender, &payload, status);
+            assert_eq!(2, operations.len());
+            assert_eq!(
+                OperationType::Withdraw.to_string(),
+                operations[0].operation_type
+            );
+            assert_eq!(
+                Some(AccountIdentifier::base_account(sender)),
+                operations[0].account
+            );
+            assert_eq!(
+                OperationType::Deposit.to_string(),
+                operations[1].operation_type
+            );
+            assert_eq!(
+                Some(AccountIdentifier::base_account(receiver)),
+                operations[1].account
+            );
+            assert!(operations
+                .iter()
+                .all(|operation| operation.status == status.map(|status| status.to_string())));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pending_transaction() {
+        let server_context = test_context().await;
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let sender = AccountAddress::from_hex_literal("0xA").unwrap();
+        let receiver = AccountAddress::from_hex_literal("0xB").unwrap();
+        let raw_txn = RawTransaction::new(
+            sender,
+            0,
+            aptos_stdlib::aptos_account_transfer(receiver, 100),
+            1000,
+            100,
+            u64::MAX,
+            ChainId::test(),
+        );
+        let txn = raw_txn
+            .sign(&private_key, private_key.public_key())
+            .unwrap();
+        let hash = txn.clone().into_inner().committed_hash();
+
+        let transaction = Transaction::from_pending_transaction(&server_context, txn.into_inner());
+        assert_eq!(
+            TransactionIdentifier::from(hash),
+            transaction.transaction_identifier
+        );
+        assert_eq!(PENDING_VM_STATUS, transaction.metadata.vm_status);
+        assert!(!transaction.metadata.failed);
+        let indices: Vec<_> = transaction
+            .operations
+            .iter()
+            .map(|operation| operation.operation_identifier.index)
+            .collect();
+        assert_eq!(vec![0, 1], indices);
+    }
+}
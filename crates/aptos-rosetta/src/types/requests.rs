@@ -3,9 +3,10 @@
 
 use crate::{
     types::{
-        AccountIdentifier, Allow, Amount, Block, BlockIdentifier, Currency, InternalOperation,
-        NetworkIdentifier, Operation, PartialBlockIdentifier, Peer, PublicKey, Signature,
-        SigningPayload, SyncStatus, Transaction, TransactionIdentifier, Version,
+        AccountIdentifier, Allow, Amount, Block, BlockIdentifier, BlockTransaction, Coin,
+        CoinIdentifier, Currency, InternalOperation, NetworkIdentifier, Operation,
+        PartialBlockIdentifier, Peer, PublicKey, Signature, SigningPayload, SyncStatus,
+        Transaction, TransactionIdentifier, Version,
     },
     AccountAddress, ApiError,
 };
@@ -58,6 +59,38 @@ pub struct AccountBalanceMetadata {
     pub operators: Option<Vec<AccountAddress>>,
     pub lockup_expiration_time_utc: U64,
 }
+
+/// Request for the coins (unspent balances) owned by an account
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/AccountCoinsRequest.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AccountCoinsRequest {
+    /// Network identifier describing the blockchain and the chain id
+    pub network_identifier: NetworkIdentifier,
+    /// Account identifier describing the account address
+    pub account_identifier: AccountIdentifier,
+    /// Whether to include coins in mempool, which is not supported as mempool can't change
+    /// balances
+    pub include_mempool: bool,
+    /// For filtering which currencies to show
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currencies: Option<Vec<Currency>>,
+}
+
+/// Response with the coins owned by the account as of the latest block
+///
+/// Aptos is account based, so there is exactly one coin per currency of the account
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/AccountCoinsResponse.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AccountCoinsResponse {
+    /// Block containing the coins
+    pub block_identifier: BlockIdentifier,
+    /// Coins of all known currencies
+    pub coins: Vec<Coin>,
+    /// Metadata of account, must have sequence number
+    pub metadata: AccountBalanceMetadata,
+}
 /// Reqyest a block (version) on the account
 ///
 /// With neither value for PartialBlockIdentifier, get the latest version
@@ -118,6 +151,28 @@ pub struct BlockResponse {
     pub block: Block,
 }
 
+/// Request for a single transaction in a block
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/BlockTransactionRequest.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockTransactionRequest {
+    /// Network identifier describing the blockchain and the chain id
+    pub network_identifier: NetworkIdentifier,
+    /// Block containing the transaction
+    pub block_identifier: BlockIdentifier,
+    /// Hash of the transaction
+    pub transaction_identifier: TransactionIdentifier,
+}
+
+/// Response with the transaction in a block
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/BlockTransactionResponse.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BlockTransactionResponse {
+    /// The transaction requested, including all of its operations
+    pub transaction: Transaction,
+}
+
 /// Request to combine signatures and an unsigned transaction for submission as a
 /// [`aptos_types::transaction::SignedTransaction`]
 ///
@@ -559,6 +614,62 @@ pub struct NetworkStatusResponse {
     pub peers: Vec<Peer>,
 }
 
+/// Request to search for transactions, by hash or by the account that sent them
+///
+/// Only a subset of the search criteria of the spec are supported, the others are rejected
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/SearchTransactionsRequest.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SearchTransactionsRequest {
+    /// Network identifier describing the blockchain and the chain id
+    pub network_identifier: NetworkIdentifier,
+    /// Find the transaction with this hash
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_identifier: Option<TransactionIdentifier>,
+    /// Find the transactions sent by this account
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account_identifier: Option<AccountIdentifier>,
+    /// Only find transactions up to and including this block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_block: Option<u64>,
+    /// For account searches, the sequence number of the first transaction to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u64>,
+    /// Maximum number of transactions to return, defaults to 25 and is capped at 100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u64>,
+    /// Only `and` is supported, as only one criteria can be set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operator: Option<String>,
+    /// Unsupported search criteria, kept to reject them rather than silently ignoring them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coin_identifier: Option<CoinIdentifier>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub currency: Option<Currency>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub operation_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub success: Option<bool>,
+}
+
+/// Response with the transactions found, and the offset to continue the search from
+///
+/// [API Spec](https://www.rosetta-api.org/docs/models/SearchTransactionsResponse.html)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SearchTransactionsResponse {
+    /// Transactions found along with their blocks
+    pub transactions: Vec<BlockTransaction>,
+    /// Number of transactions matching the search across all pages, not just this one
+    pub total_count: u64,
+    /// Offset to use for the next page, if there may be more transactions
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_offset: Option<u64>,
+}
+
 /// Response with a transaction that was hashed or submitted
 ///
 /// [API Spec](https://www.rosetta-api.org/docs/models/TransactionIdentifierResponse.html)
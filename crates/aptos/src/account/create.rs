@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::common::types::{CliCommand, CliTypedResult, TransactionOptions, TransactionSummary};
+use crate::common::{
+    types::{CliCommand, CliTypedResult, TransactionOptions, TransactionSummary},
+    utils::profile_or_submit,
+};
 use aptos_cached_packages::aptos_stdlib;
 use aptos_types::account_address::AccountAddress;
 use async_trait::async_trait;
@@ -33,9 +36,10 @@ impl CliCommand<TransactionSummary> for CreateAccount {
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
         let address = self.account;
-        self.txn_options
-            .submit_transaction(aptos_stdlib::aptos_account_create_account(address))
-            .await
-            .map(TransactionSummary::from)
+        profile_or_submit(
+            aptos_stdlib::aptos_account_create_account(address),
+            &self.txn_options,
+        )
+        .await
     }
 }
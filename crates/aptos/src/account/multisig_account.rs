@@ -6,7 +6,7 @@ use crate::common::{
         CliCommand, CliError, CliTypedResult, EntryFunctionArguments, MultisigAccount,
         MultisigAccountWithSequenceNumber, TransactionOptions, TransactionSummary,
     },
-    utils::view_json_option_str,
+    utils::{profile_or_submit, view_json_option_str},
 };
 use aptos_cached_packages::aptos_stdlib;
 use aptos_crypto::HashValue;
@@ -143,10 +143,7 @@ impl CliCommand<TransactionSummary> for CreateTransaction {
                 multisig_transaction_payload_bytes,
             )
         };
-        self.txn_options
-            .submit_transaction(transaction_payload)
-            .await
-            .map(|inner| inner.into())
+        profile_or_submit(transaction_payload, &self.txn_options).await
     }
 }
 
@@ -245,15 +242,16 @@ impl CliCommand<TransactionSummary> for Approve {
     }
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
-        self.txn_options
-            .submit_transaction(aptos_stdlib::multisig_account_approve_transaction(
+        profile_or_submit(
+            aptos_stdlib::multisig_account_approve_transaction(
                 self.multisig_account_with_sequence_number
                     .multisig_account
                     .multisig_address,
                 self.multisig_account_with_sequence_number.sequence_number,
-            ))
-            .await
-            .map(|inner| inner.into())
+            ),
+            &self.txn_options,
+        )
+        .await
     }
 }
 
@@ -277,15 +275,16 @@ impl CliCommand<TransactionSummary> for Reject {
     }
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
-        self.txn_options
-            .submit_transaction(aptos_stdlib::multisig_account_reject_transaction(
+        profile_or_submit(
+            aptos_stdlib::multisig_account_reject_transaction(
                 self.multisig_account_with_sequence_number
                     .multisig_account
                     .multisig_address,
                 self.multisig_account_with_sequence_number.sequence_number,
-            ))
-            .await
-            .map(|inner| inner.into())
+            ),
+            &self.txn_options,
+        )
+        .await
     }
 }
 
@@ -305,13 +304,14 @@ impl CliCommand<TransactionSummary> for Execute {
     }
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
-        self.txn_options
-            .submit_transaction(TransactionPayload::Multisig(Multisig {
+        profile_or_submit(
+            TransactionPayload::Multisig(Multisig {
                 multisig_address: self.multisig_account.multisig_address,
                 transaction_payload: None,
-            }))
-            .await
-            .map(|inner| inner.into())
+            }),
+            &self.txn_options,
+        )
+        .await
     }
 }
 
@@ -331,14 +331,14 @@ impl CliCommand<TransactionSummary> for ExecuteWithPayload {
     }
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
-        self.execute
-            .txn_options
-            .submit_transaction(TransactionPayload::Multisig(Multisig {
+        profile_or_submit(
+            TransactionPayload::Multisig(Multisig {
                 multisig_address: self.execute.multisig_account.multisig_address,
                 transaction_payload: Some(self.entry_function_args.try_into()?),
-            }))
-            .await
-            .map(|inner| inner.into())
+            }),
+            &self.execute.txn_options,
+        )
+        .await
     }
 }
 
@@ -361,11 +361,12 @@ impl CliCommand<TransactionSummary> for ExecuteReject {
     }
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
-        self.txn_options
-            .submit_transaction(aptos_stdlib::multisig_account_execute_rejected_transaction(
+        profile_or_submit(
+            aptos_stdlib::multisig_account_execute_rejected_transaction(
                 self.multisig_account.multisig_address,
-            ))
-            .await
-            .map(|inner| inner.into())
+            ),
+            &self.txn_options,
+        )
+        .await
     }
 }
@@ -34,14 +34,15 @@ use aptos_rest_client::{
     AptosBaseUrl, Client, Transaction,
 };
 use aptos_sdk::{
-    transaction_builder::TransactionFactory,
+    transaction_builder::{TransactionBuilder, TransactionFactory},
     types::{HardwareWalletAccount, HardwareWalletType, LocalAccount, TransactionSigner},
 };
 use aptos_types::{
     chain_id::ChainId,
     transaction::{
-        authenticator::AuthenticationKey, EntryFunction, MultisigTransactionPayload, Script,
-        SignedTransaction, TransactionArgument, TransactionPayload, TransactionStatus,
+        authenticator::{AccountAuthenticator, AuthenticationKey},
+        EntryFunction, MultisigTransactionPayload, RawTransaction, Script, SignedTransaction,
+        TransactionArgument, TransactionPayload, TransactionStatus,
     },
 };
 use async_trait::async_trait;
@@ -1257,6 +1258,9 @@ pub struct TransactionSummary {
     pub sender: Option<AccountAddress>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sequence_number: Option<u64>,
+    /// Set when the transaction was only simulated, so it isn't on chain
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub simulated: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub success: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1280,6 +1284,7 @@ impl From<&Transaction> for TransactionSummary {
                 pending: Some(true),
                 sender: Some(*txn.request.sender.inner()),
                 sequence_number: Some(txn.request.sequence_number.0),
+                simulated: None,
                 gas_used: None,
                 gas_unit_price: None,
                 success: None,
@@ -1298,6 +1303,7 @@ impl From<&Transaction> for TransactionSummary {
                 sequence_number: Some(txn.request.sequence_number.0),
                 timestamp_us: Some(txn.timestamp.0),
                 pending: None,
+                simulated: None,
             },
             Transaction::GenesisTransaction(txn) => TransactionSummary {
                 transaction_hash: txn.info.hash,
@@ -1308,6 +1314,7 @@ impl From<&Transaction> for TransactionSummary {
                 gas_used: None,
                 gas_unit_price: None,
                 pending: None,
+                simulated: None,
                 sequence_number: None,
                 timestamp_us: None,
             },
@@ -1321,6 +1328,7 @@ impl From<&Transaction> for TransactionSummary {
                 gas_used: None,
                 gas_unit_price: None,
                 pending: None,
+                simulated: None,
                 sequence_number: None,
             },
             Transaction::StateCheckpointTransaction(txn) => TransactionSummary {
@@ -1333,6 +1341,7 @@ impl From<&Transaction> for TransactionSummary {
                 gas_used: None,
                 gas_unit_price: None,
                 pending: None,
+                simulated: None,
                 sequence_number: None,
             },
         }
@@ -1487,10 +1496,136 @@ pub struct TransactionOptions {
     #[clap(flatten)]
     pub(crate) prompt_options: PromptOptions,
 
+    /// Profile of an account that pays for the gas of the transaction instead of the sender
+    ///
+    /// The profile must have a private key, and the transaction is sent as a fee payer
+    /// transaction.
+    #[clap(long)]
+    pub(crate) fee_payer_profile: Option<String>,
+
+    /// Profiles of the secondary signers of the transaction
+    ///
+    /// The profiles must have private keys, and be given in the order of the signer arguments
+    /// of the function after the sender's.
+    #[clap(long, num_args = 1..)]
+    pub(crate) secondary_signer_profiles: Vec<String>,
+
     /// If this option is set, simulate the transaction locally using the debugger and generate
     /// flamegraphs that reflect the gas usage.
     #[clap(long)]
     pub(crate) profile_gas: bool,
+
+    /// If this option is set, simulate the transaction on the network instead of submitting it
+    ///
+    /// The output is marked as `simulated`, as the transaction isn't on chain.
+    #[clap(long, conflicts_with = "profile_gas")]
+    pub(crate) simulate: bool,
+}
+
+/// Accounts, loaded from profiles, that sign a transaction alongside its sender
+struct AdditionalSigners {
+    secondary_signers: Vec<LocalAccount>,
+    fee_payer: Option<LocalAccount>,
+}
+
+impl AdditionalSigners {
+    fn load(
+        secondary_signer_profiles: &[String],
+        fee_payer_profile: Option<&str>,
+    ) -> CliTypedResult<Self> {
+        Ok(Self {
+            secondary_signers: secondary_signer_profiles
+                .iter()
+                .map(|profile| Self::load_account(profile))
+                .collect::<CliTypedResult<_>>()?,
+            fee_payer: fee_payer_profile.map(Self::load_account).transpose()?,
+        })
+    }
+
+    fn load_account(profile: &str) -> CliTypedResult<LocalAccount> {
+        let config =
+            CliConfig::load_profile(Some(profile), ConfigSearchMode::CurrentDirAndParents)?
+                .ok_or_else(|| {
+                    CliError::CommandArgumentError(format!("Profile {} does not exist", profile))
+                })?;
+        match (config.private_key, config.account) {
+            (Some(private_key), Some(address)) => Ok(LocalAccount::new(address, private_key, 0)),
+            _ => Err(CliError::CommandArgumentError(format!(
+                "Profile {} must have a private key and an account to sign transactions",
+                profile
+            ))),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.secondary_signers.is_empty() && self.fee_payer.is_none()
+    }
+
+    /// The account paying for gas, if it isn't the sender
+    fn fee_payer_address(&self) -> Option<AccountAddress> {
+        self.fee_payer.as_ref().map(LocalAccount::address)
+    }
+
+    /// Signs the transaction built by `builder` as `sender` and as all the additional signers
+    fn sign(&self, sender: &LocalAccount, builder: TransactionBuilder) -> SignedTransaction {
+        let secondary_signers = self.secondary_signers.iter().collect();
+        match &self.fee_payer {
+            Some(fee_payer) => sender.sign_fee_payer_with_transaction_builder(
+                secondary_signers,
+                fee_payer,
+                builder,
+            ),
+            None if !self.secondary_signers.is_empty() => {
+                sender.sign_multi_agent_with_transaction_builder(secondary_signers, builder)
+            },
+            None => sender.sign_with_transaction_builder(builder),
+        }
+    }
+
+    /// Creates the transaction with invalid signatures, which is what simulation requires
+    fn sign_for_simulation(
+        &self,
+        raw_txn: RawTransaction,
+        sender_public_key: Ed25519PublicKey,
+    ) -> SignedTransaction {
+        let authenticator = |public_key: Ed25519PublicKey| {
+            AccountAuthenticator::ed25519(
+                public_key,
+                Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
+            )
+        };
+        let secondary_signer_addresses = self
+            .secondary_signers
+            .iter()
+            .map(LocalAccount::address)
+            .collect();
+        let secondary_signers = self
+            .secondary_signers
+            .iter()
+            .map(|signer| authenticator(signer.public_key().clone()))
+            .collect();
+        match &self.fee_payer {
+            Some(fee_payer) => SignedTransaction::new_fee_payer(
+                raw_txn,
+                authenticator(sender_public_key),
+                secondary_signer_addresses,
+                secondary_signers,
+                fee_payer.address(),
+                authenticator(fee_payer.public_key().clone()),
+            ),
+            None if !self.secondary_signers.is_empty() => SignedTransaction::new_multi_agent(
+                raw_txn,
+                authenticator(sender_public_key),
+                secondary_signer_addresses,
+                secondary_signers,
+            ),
+            None => SignedTransaction::new(
+                raw_txn,
+                sender_public_key,
+                Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
+            ),
+        }
+    }
 }
 
 impl TransactionOptions {
@@ -1563,18 +1698,127 @@ impl TransactionOptions {
         get_sequence_number(&client, sender_address).await
     }
 
+    fn additional_signers(&self) -> CliTypedResult<AdditionalSigners> {
+        AdditionalSigners::load(
+            &self.secondary_signer_profiles,
+            self.fee_payer_profile.as_deref(),
+        )
+    }
+
     pub async fn view(&self, payload: ViewRequest) -> CliTypedResult<Vec<serde_json::Value>> {
         let client = self.rest_client()?;
         Ok(client.view(&payload, None).await?.into_inner())
     }
 
+    /// Builds the unsigned transaction, to be signed elsewhere
+    ///
+    /// Nothing is signed or simulated, so `--max-gas` is required. The sequence number and chain
+    /// id are fetched from the network unless they are given, as is the gas unit price unless
+    /// `--gas-unit-price` is set.
+    pub async fn build_raw_transaction(
+        &self,
+        payload: TransactionPayload,
+        sequence_number: Option<u64>,
+        chain_id: Option<ChainId>,
+    ) -> CliTypedResult<RawTransaction> {
+        if self.simulate || self.profile_gas {
+            return Err(CliError::CommandArgumentError(
+                "--simulate and --profile-gas can't be used when building a transaction to sign"
+                    .to_string(),
+            ));
+        }
+        if self.fee_payer_profile.is_some() || !self.secondary_signer_profiles.is_empty() {
+            return Err(CliError::CommandArgumentError(
+                "--fee-payer-profile and --secondary-signer-profiles can't be used when building \
+                a transaction to sign, give the signer addresses instead"
+                    .to_string(),
+            ));
+        }
+        let max_gas = self.gas_options.max_gas.ok_or_else(|| {
+            CliError::CommandArgumentError(
+                "--max-gas is required when building a transaction to sign".to_string(),
+            )
+        })?;
+
+        let sender_address = match self.sender_account {
+            Some(sender_address) => sender_address,
+            None => self.profile_options.account_address()?,
+        };
+        let sequence_number = match sequence_number {
+            Some(sequence_number) => sequence_number,
+            None => self.sequence_number(sender_address).await?,
+        };
+        let chain_id = match chain_id {
+            Some(chain_id) => chain_id,
+            None => ChainId::new(self.rest_client()?.get_index().await?.into_inner().chain_id),
+        };
+        let gas_unit_price = match self.gas_options.gas_unit_price {
+            Some(gas_unit_price) => gas_unit_price,
+            None => {
+                self.rest_client()?
+                    .estimate_gas_price()
+                    .await?
+                    .into_inner()
+                    .gas_estimate
+            },
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+            .as_secs();
+
+        Ok(RawTransaction::new(
+            sender_address,
+            sequence_number,
+            payload,
+            max_gas,
+            gas_unit_price,
+            now + self.gas_options.expiration_secs,
+            chain_id,
+        ))
+    }
+
     /// Submit a transaction
+    ///
+    /// `--simulate` is rejected, as the returned transaction is taken to be committed. Commands
+    /// which output a `TransactionSummary` simulate through `profile_or_submit` instead.
     pub async fn submit_transaction(
         &self,
         payload: TransactionPayload,
+    ) -> CliTypedResult<Transaction> {
+        if self.simulate {
+            return Err(CliError::CommandArgumentError(
+                "--simulate is not supported by this command".to_string(),
+            ));
+        }
+        self.submit_or_simulate(payload, false).await
+    }
+
+    /// Simulates the transaction on the network, without submitting it
+    ///
+    /// The summary is marked as simulated, and has no version or timestamp as the transaction
+    /// isn't on chain.
+    pub async fn simulate_transaction(
+        &self,
+        payload: TransactionPayload,
+    ) -> CliTypedResult<TransactionSummary> {
+        let transaction = self.submit_or_simulate(payload, true).await?;
+        Ok(TransactionSummary {
+            simulated: Some(true),
+            version: None,
+            timestamp_us: None,
+            ..TransactionSummary::from(transaction)
+        })
+    }
+
+    async fn submit_or_simulate(
+        &self,
+        payload: TransactionPayload,
+        simulate: bool,
     ) -> CliTypedResult<Transaction> {
         let client = self.rest_client()?;
         let (sender_public_key, sender_address) = self.get_public_key_and_address()?;
+        let additional_signers = self.additional_signers()?;
 
         // Ask to confirm price if the gas unit price is estimated above the lowest value when
         // it is automatically estimated
@@ -1610,6 +1854,36 @@ impl TransactionOptions {
         let chain_id = ChainId::new(state.chain_id);
         // TODO: Check auth key against current private key and provide a better message
 
+        if simulate {
+            let mut transaction_factory =
+                TransactionFactory::new(chain_id).with_gas_unit_price(gas_unit_price);
+            if let Some(max_gas) = self.gas_options.max_gas {
+                transaction_factory = transaction_factory.with_max_gas_amount(max_gas);
+            }
+            let unsigned_transaction = transaction_factory
+                .payload(payload)
+                .sender(sender_address)
+                .sequence_number(sequence_number)
+                .expiration_timestamp_secs(expiration_time_secs)
+                .build();
+            let transaction =
+                additional_signers.sign_for_simulation(unsigned_transaction, sender_public_key);
+
+            let simulated_txn = client
+                .simulate_with_gas_estimation(
+                    &transaction,
+                    self.gas_options.max_gas.is_none(),
+                    false,
+                )
+                .await?
+                .into_inner()
+                .pop()
+                .ok_or_else(|| {
+                    CliError::UnexpectedError("Simulation returned no transaction".to_string())
+                })?;
+            return Ok(Transaction::UserTransaction(Box::new(simulated_txn)));
+        }
+
         let max_gas = if let Some(max_gas) = self.gas_options.max_gas {
             // If the gas unit price was estimated ask, but otherwise you've chosen hwo much you want to spend
             if ask_to_confirm_price {
@@ -1628,11 +1902,8 @@ impl TransactionOptions {
                 .expiration_timestamp_secs(expiration_time_secs)
                 .build();
 
-            let signed_transaction = SignedTransaction::new(
-                unsigned_transaction,
-                sender_public_key.clone(),
-                Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
-            );
+            let signed_transaction = additional_signers
+                .sign_for_simulation(unsigned_transaction, sender_public_key.clone());
 
             let txns = client
                 .simulate_with_gas_estimation(&signed_transaction, true, false)
@@ -1675,8 +1946,8 @@ impl TransactionOptions {
                 let (private_key, _) = self.get_key_and_address()?;
                 let sender_account =
                     &mut LocalAccount::new(sender_address, private_key, sequence_number);
-                let transaction = sender_account
-                    .sign_with_transaction_builder(transaction_factory.payload(payload));
+                let transaction =
+                    additional_signers.sign(sender_account, transaction_factory.payload(payload));
                 let response = client
                     .submit_and_wait(&transaction)
                     .await
@@ -1685,6 +1956,12 @@ impl TransactionOptions {
                 Ok(response.into_inner())
             },
             Ok(AccountType::HardwareWallet) => {
+                if !additional_signers.is_empty() {
+                    return Err(CliError::CommandArgumentError(
+                        "A fee payer or secondary signers can't be used with a hardware wallet sender"
+                            .to_string(),
+                    ));
+                }
                 let sender_account = &mut HardwareWalletAccount::new(
                     sender_address,
                     sender_public_key,
@@ -1724,6 +2001,7 @@ impl TransactionOptions {
         const DEFAULT_MAX_GAS: u64 = 2_000_000;
 
         let (sender_key, sender_address) = self.get_key_and_address()?;
+        let additional_signers = self.additional_signers()?;
         let gas_unit_price = self
             .gas_options
             .gas_unit_price
@@ -1733,8 +2011,12 @@ impl TransactionOptions {
         let chain_id = ChainId::new(state.chain_id);
         let sequence_number = account.sequence_number;
 
+        // The gas is paid by the fee payer, if there is one
+        let gas_payer = additional_signers
+            .fee_payer_address()
+            .unwrap_or(sender_address);
         let balance = client
-            .get_account_balance_at_version(sender_address, version)
+            .get_account_balance_at_version(gas_payer, version)
             .await
             .map_err(|err| CliError::ApiError(err.to_string()))?
             .into_inner();
@@ -1754,7 +2036,7 @@ impl TransactionOptions {
             .with_transaction_expiration_time(self.gas_options.expiration_secs);
         let sender_account = &mut LocalAccount::new(sender_address, sender_key, sequence_number);
         let transaction =
-            sender_account.sign_with_transaction_builder(transaction_factory.payload(payload));
+            additional_signers.sign(sender_account, transaction_factory.payload(payload));
         let hash = transaction.clone().committed_hash();
 
         // Execute the transaction using the debugger
@@ -1802,6 +2084,7 @@ impl TransactionOptions {
             pending: None,
             sender: Some(sender_address),
             sequence_number: None, // The transaction is not comitted so there is no new sequence number.
+            simulated: Some(true),
             success,
             timestamp_us: None,
             version: Some(version), // The transaction is not comitted so there is no new version.
@@ -22,6 +22,7 @@ use aptos_crypto::{
     bls12381, ed25519::ED25519_PUBLIC_KEY_LENGTH, x25519, ValidCryptoMaterial,
     ValidCryptoMaterialStringExt,
 };
+use aptos_framework::ReleaseBundle;
 use aptos_genesis::{
     builder::GenesisConfiguration,
     config::{
@@ -29,12 +30,14 @@ use aptos_genesis::{
         StringOwnerConfiguration, ValidatorConfiguration,
     },
     mainnet::MainnetGenesisInfo,
+    regenesis::RegenesisInfo,
     GenesisInfo,
 };
 use aptos_logger::info;
 use aptos_types::{
     account_address::{AccountAddress, AccountAddressWithChecks},
     on_chain_config::{OnChainConsensusConfig, OnChainExecutionConfig},
+    transaction::Version,
 };
 use aptos_vm_genesis::{default_gas_schedule, AccountBalance, EmployeePool};
 use async_trait::async_trait;
@@ -57,6 +60,7 @@ const GENESIS_FILE: &str = "genesis.blob";
 pub enum GenesisTool {
     GenerateAdminWriteSet(keys::GenerateAdminWriteSet),
     GenerateGenesis(GenerateGenesis),
+    GenerateRegenesis(GenerateRegenesis),
     GetPoolAddresses(tools::PoolAddresses),
     GenerateKeys(keys::GenerateKeys),
     GenerateLayoutTemplate(keys::GenerateLayoutTemplate),
@@ -69,6 +73,7 @@ impl GenesisTool {
         match self {
             GenesisTool::GenerateAdminWriteSet(tool) => tool.execute_serialized_success().await,
             GenesisTool::GenerateGenesis(tool) => tool.execute_serialized().await,
+            GenesisTool::GenerateRegenesis(tool) => tool.execute_serialized().await,
             GenesisTool::GetPoolAddresses(tool) => tool.execute_serialized().await,
             GenesisTool::GenerateKeys(tool) => tool.execute_serialized().await,
             GenesisTool::GenerateLayoutTemplate(tool) => tool.execute_serialized_success().await,
@@ -301,6 +306,80 @@ pub fn fetch_genesis_info(git_options: GitOptions) -> CliTypedResult<GenesisInfo
     )?)
 }
 
+/// Generate a genesis on top of the state of an existing chain
+///
+/// This will create a genesis.blob and a waypoint.txt to start a new
+/// network from the state of an AptosDB at a given version, either a
+/// checkpoint of a node's DB or one restored from a backup with
+/// `aptos node bootstrap-db`. The validators and the chain id are taken from
+/// the git repository, as for `generate-genesis`.
+///
+/// The nodes of the new network start from a copy of the same DB, truncated
+/// to that version with `aptos-db-tool debug truncate` if it isn't the latest.
+#[derive(Parser)]
+pub struct GenerateRegenesis {
+    /// Output directory for Genesis file and waypoint
+    #[clap(long, value_parser)]
+    output_dir: Option<PathBuf>,
+    /// Directory of the AptosDB with the state of the existing chain
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+    /// Version of the state to start from
+    ///
+    /// Has to be a state checkpoint, e.g. the last version of a block, that
+    /// isn't pruned. Defaults to the latest version of the DB.
+    #[clap(long)]
+    version: Option<Version>,
+    /// Path to a framework release bundle to upgrade the framework to
+    ///
+    /// If not given, the framework of the existing chain is kept.
+    #[clap(long, value_parser)]
+    framework_bundle: Option<PathBuf>,
+
+    #[clap(flatten)]
+    prompt_options: PromptOptions,
+    #[clap(flatten)]
+    git_options: GitOptions,
+}
+
+#[async_trait]
+impl CliCommand<Vec<PathBuf>> for GenerateRegenesis {
+    fn command_name(&self) -> &'static str {
+        "GenerateRegenesis"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<PathBuf>> {
+        let output_dir = dir_default_to_current(self.output_dir.clone())?;
+        let genesis_file = output_dir.join(GENESIS_FILE);
+        let waypoint_file = output_dir.join(WAYPOINT_FILE);
+        check_if_file_exists(genesis_file.as_path(), self.prompt_options)?;
+        check_if_file_exists(waypoint_file.as_path(), self.prompt_options)?;
+
+        let framework = self.framework_bundle.map(ReleaseBundle::read).transpose()?;
+        let regenesis = fetch_regenesis_info(self.git_options, framework)?;
+        let (genesis, waypoint) = regenesis.generate(&self.db_dir, self.version)?;
+        let genesis_bytes = bcs::to_bytes(&genesis).map_err(|e| CliError::BCS(GENESIS_FILE, e))?;
+        write_to_file(genesis_file.as_path(), GENESIS_FILE, &genesis_bytes)?;
+        write_to_file(
+            waypoint_file.as_path(),
+            WAYPOINT_FILE,
+            waypoint.to_string().as_bytes(),
+        )?;
+        Ok(vec![genesis_file, waypoint_file])
+    }
+}
+
+/// Retrieves the validators and the chain id for a regenesis from the Git repository
+pub fn fetch_regenesis_info(
+    git_options: GitOptions,
+    framework: Option<ReleaseBundle>,
+) -> CliTypedResult<RegenesisInfo> {
+    let client = git_options.get_client()?;
+    let layout: Layout = client.get(Path::new(LAYOUT_FILE))?;
+    let validators = get_validator_configs(&client, &layout, false).map_err(parse_error)?;
+    Ok(RegenesisInfo::new(layout.chain_id, validators, framework)?)
+}
+
 fn parse_error(errors: Vec<String>) -> CliError {
     eprintln!(
         "Failed to parse genesis inputs:\n{}",
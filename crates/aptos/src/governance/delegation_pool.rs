@@ -153,16 +153,16 @@ impl CliCommand<Vec<TransactionSummary>> for SubmitVote {
             self.args.txn_options.prompt_options,
         )?;
         summaries.push(
-            self.args
-                .txn_options
-                .submit_transaction(aptos_stdlib::delegation_pool_vote(
+            profile_or_submit(
+                aptos_stdlib::delegation_pool_vote(
                     self.delegation_pool_address,
                     self.args.proposal_id,
                     voting_power,
                     vote,
-                ))
-                .await
-                .map(TransactionSummary::from)?,
+                ),
+                &self.args.txn_options,
+            )
+            .await?,
         );
 
         Ok(summaries)
@@ -194,12 +194,11 @@ async fn delegation_pool_governance_precheck(
     } else {
         println!("Partial governance voting for delegation pool {} hasn't been enabled yet. Enabling it now...",
                  pool_address);
-        let txn_summary = txn_options
-            .submit_transaction(
-                aptos_stdlib::delegation_pool_enable_partial_governance_voting(pool_address),
-            )
-            .await
-            .map(TransactionSummary::from)?;
+        let txn_summary = profile_or_submit(
+            aptos_stdlib::delegation_pool_enable_partial_governance_voting(pool_address),
+            &txn_options,
+        )
+        .await?;
         Ok(Some(txn_summary))
     }
 }
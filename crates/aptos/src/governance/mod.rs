@@ -12,7 +12,7 @@ use crate::{
             CliError, CliTypedResult, MovePackageDir, PoolAddressArgs, ProfileOptions,
             PromptOptions, RestOptions, TransactionOptions, TransactionSummary,
         },
-        utils::prompt_yes_with_override,
+        utils::{profile_or_submit, prompt_yes_with_override},
     },
     governance::utils::*,
     move_tool::{FrameworkPackageArgs, IncludedArtifacts},
@@ -576,15 +576,11 @@ impl SubmitVote {
             )?;
 
             summaries.push(
-                self.args
-                    .txn_options
-                    .submit_transaction(aptos_stdlib::aptos_governance_vote(
-                        *pool_address,
-                        proposal_id,
-                        vote,
-                    ))
-                    .await
-                    .map(TransactionSummary::from)?,
+                profile_or_submit(
+                    aptos_stdlib::aptos_governance_vote(*pool_address, proposal_id, vote),
+                    &self.args.txn_options,
+                )
+                .await?,
             );
         }
         Ok(summaries)
@@ -668,16 +664,16 @@ impl SubmitVote {
             )?;
 
             summaries.push(
-                self.args
-                    .txn_options
-                    .submit_transaction(aptos_stdlib::aptos_governance_partial_vote(
+                profile_or_submit(
+                    aptos_stdlib::aptos_governance_partial_vote(
                         *pool_address,
                         proposal_id,
                         voting_power,
                         vote,
-                    ))
-                    .await
-                    .map(TransactionSummary::from)?,
+                    ),
+                    &self.args.txn_options,
+                )
+                .await?,
             );
         }
         Ok(summaries)
@@ -729,13 +725,11 @@ impl CliCommand<TransactionSummary> for ApproveExecutionHash {
     }
 
     async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
-        Ok(self
-            .txn_options
-            .submit_transaction(
-                aptos_stdlib::aptos_governance_add_approved_script_hash_script(self.proposal_id),
-            )
-            .await
-            .map(TransactionSummary::from)?)
+        Ok(profile_or_submit(
+            aptos_stdlib::aptos_governance_add_approved_script_hash_script(self.proposal_id),
+            &self.txn_options,
+        )
+        .await?)
     }
 }
 
@@ -871,10 +865,7 @@ impl CliCommand<TransactionSummary> for ExecuteProposal {
         let args = vec![TransactionArgument::U64(self.proposal_id)];
         let txn = TransactionPayload::Script(Script::new(bytecode, vec![], args));
 
-        self.txn_options
-            .submit_transaction(txn)
-            .await
-            .map(TransactionSummary::from)
+        profile_or_submit(txn, &self.txn_options).await
     }
 }
 
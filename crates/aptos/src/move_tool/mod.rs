@@ -20,7 +20,7 @@ use crate::{
         },
         utils::{
             check_if_file_exists, create_dir_if_not_exist, dir_default_to_current,
-            profile_or_submit, prompt_yes_with_override, write_to_file,
+            get_feature_flag, profile_or_submit, prompt_yes_with_override, write_to_file,
         },
     },
     governance::CompileScriptFunction,
@@ -37,16 +37,19 @@ use aptos_framework::{
     BuildOptions, BuiltPackage,
 };
 use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters};
-use aptos_rest_client::aptos_api_types::{
-    EntryFunctionId, HexEncodedBytes, IdentifierWrapper, MoveModuleId,
+use aptos_rest_client::{
+    aptos_api_types::{EntryFunctionId, HexEncodedBytes, IdentifierWrapper, MoveModuleId},
+    Client,
 };
 use aptos_types::{
     account_address::{create_resource_address, AccountAddress},
+    on_chain_config::FeatureFlag,
     transaction::{TransactionArgument, TransactionPayload},
 };
 use async_trait::async_trait;
 use clap::{Parser, Subcommand, ValueEnum};
 use itertools::Itertools;
+use move_binary_format::{compatibility::Compatibility, normalized, CompiledModule};
 use move_cli::{self, base::test::UnitTestResult};
 use move_command_line_common::env::MOVE_HOME;
 use move_core_types::{identifier::Identifier, language_storage::ModuleId, u256::U256};
@@ -74,6 +77,7 @@ use tokio::task;
 #[derive(Subcommand)]
 pub enum MoveTool {
     BuildPublishPayload(BuildPublishPayload),
+    CheckUpgrade(CheckUpgrade),
     Clean(CleanPackage),
     Compile(CompilePackage),
     CompileScript(CompileScript),
@@ -100,6 +104,7 @@ impl MoveTool {
     pub async fn execute(self) -> CliResult {
         match self {
             MoveTool::BuildPublishPayload(tool) => tool.execute_serialized().await,
+            MoveTool::CheckUpgrade(tool) => tool.execute_serialized().await,
             MoveTool::Clean(tool) => tool.execute_serialized().await,
             MoveTool::Compile(tool) => tool.execute_serialized().await,
             MoveTool::CompileScript(tool) => tool.execute_serialized().await,
@@ -948,10 +953,7 @@ impl CliCommand<TransactionSummary> for CreateResourceAccountAndPublishPackage {
                 MAX_PUBLISH_PACKAGE_SIZE, size
             )));
         }
-        txn_options
-            .submit_transaction(payload)
-            .await
-            .map(TransactionSummary::from)
+        profile_or_submit(payload, &txn_options).await
     }
 }
 
@@ -1090,6 +1092,159 @@ impl CliCommand<&'static str> for VerifyPackage {
     }
 }
 
+/// Checks that a package can be published as an upgrade of the package on-chain
+///
+/// Compiles the package, and runs the checks that publishing it would run on-chain: the upgrade
+/// policy checks of `code::publish_package`, and the compatibility of each module with its
+/// published version. Every breaking change is reported, and nothing is submitted.
+#[derive(Parser)]
+pub struct CheckUpgrade {
+    /// Address of the account the package would be published to
+    ///
+    /// Defaults to the account of the profile
+    #[clap(long, value_parser = crate::common::types::load_account_arg)]
+    pub(crate) account: Option<AccountAddress>,
+
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+#[async_trait]
+impl CliCommand<&'static str> for CheckUpgrade {
+    fn command_name(&self) -> &'static str {
+        "CheckUpgrade"
+    }
+
+    async fn execute(self) -> CliTypedResult<&'static str> {
+        let account = match self.account {
+            Some(account) => account,
+            None => self.profile_options.account_address()?,
+        };
+        let build_options = IncludedArtifacts::None.build_options(
+            self.move_options.dev,
+            self.move_options.skip_fetch_latest_git_deps,
+            self.move_options.named_addresses(),
+            self.move_options.bytecode_version,
+            self.move_options.compiler_version,
+            self.move_options.skip_attribute_checks,
+            self.move_options.check_test_code,
+        );
+        let pack = BuiltPackage::build(self.move_options.get_package_path()?, build_options)
+            .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
+        let metadata = pack.extract_metadata()?;
+
+        let client = self.rest_options.client(&self.profile_options)?;
+        let mut errors = vec![];
+
+        // Upgrade policy and module names, as checked by `code::publish_package`
+        if metadata.upgrade_policy == UpgradePolicy::arbitrary() {
+            errors.push("Upgrade policy `arbitrary` is not allowed".to_string());
+        }
+        let registry = fetch_package_registry(&client, account).await?;
+        if let Some(registry) = &registry {
+            errors.extend(registry.publish_errors(&metadata));
+        }
+        for dep in &metadata.deps {
+            let dep_registry = fetch_package_registry(&client, dep.account).await?;
+            let dep_package = match &dep_registry {
+                Some(dep_registry) => dep_registry.get_package(&dep.package_name).await.ok(),
+                None => None,
+            };
+            if is_policy_exempted_address(dep.account) && dep_registry.is_some() {
+                continue;
+            }
+            match dep_package {
+                None => errors.push(format!(
+                    "Dependency `{}` is not published at {}",
+                    dep.package_name, dep.account
+                )),
+                Some(dep_package) => {
+                    if dep_package.upgrade_policy().policy < metadata.upgrade_policy.policy {
+                        errors.push(format!(
+                            "Dependency `{}` has the upgrade policy `{}`, which is weaker than `{}`",
+                            dep.package_name,
+                            dep_package.upgrade_policy(),
+                            metadata.upgrade_policy
+                        ));
+                    }
+                    if dep_package.upgrade_policy() == UpgradePolicy::arbitrary()
+                        && dep.account != account
+                    {
+                        errors.push(format!(
+                            "Dependency `{}` has the upgrade policy `arbitrary` and is not \
+                            published at the same address",
+                            dep.package_name
+                        ));
+                    }
+                },
+            }
+        }
+
+        // Compatibility of the modules with their published version, as checked by the VM
+        if registry.is_some() {
+            let treat_friend_as_private =
+                get_feature_flag(&client, FeatureFlag::TREAT_FRIEND_AS_PRIVATE).await?;
+            let compatibility = Compatibility::new(true, true, !treat_friend_as_private);
+            let published_modules = client
+                .get_account_modules_bcs(account)
+                .await?
+                .into_inner()
+                .into_iter()
+                .map(|(id, bytes)| (id.name.to_string(), bytes))
+                .collect::<BTreeMap<_, _>>();
+            for module in pack.modules() {
+                let name = module.self_id().name().to_string();
+                let bytes = match published_modules.get(&name) {
+                    Some(bytes) => bytes,
+                    None => continue,
+                };
+                let old_module = CompiledModule::deserialize(bytes).map_err(|e| {
+                    CliError::UnexpectedError(format!(
+                        "Failed to deserialize published module {}: {}",
+                        name, e
+                    ))
+                })?;
+                for change in compatibility.incompatible_changes(
+                    &normalized::Module::new(&old_module),
+                    &normalized::Module::new(module),
+                ) {
+                    errors.push(format!("Module `{}`: {}", name, change));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok("Package can be published as an upgrade")
+        } else {
+            Err(CliError::UnexpectedError(format!(
+                "Publishing package `{}` to {} would fail:\n{}",
+                pack.name(),
+                account,
+                errors.join("\n")
+            )))
+        }
+    }
+}
+
+/// Fetches the package registry at `account`, which is `None` if no package was published there
+async fn fetch_package_registry(
+    client: &Client,
+    account: AccountAddress,
+) -> CliTypedResult<Option<CachedPackageRegistry>> {
+    CachedPackageRegistry::create_if_exists(client, account)
+        .await
+        .map_err(|err| {
+            CliError::ApiError(format!(
+                "Failed to fetch the packages published at {}: {:#}",
+                account, err
+            ))
+        })
+}
+
 /// Lists information about packages and modules on-chain for an account
 #[derive(Parser)]
 pub struct ListPackage {
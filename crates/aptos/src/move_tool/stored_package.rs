@@ -6,11 +6,15 @@ use aptos_framework::{
     natives::code::{ModuleMetadata, PackageMetadata, PackageRegistry, UpgradePolicy},
     unzip_metadata_str,
 };
-use aptos_rest_client::Client;
+use aptos_rest_client::{
+    aptos_api_types::{AptosError, AptosErrorCode},
+    error::{AptosErrorResponse, RestError},
+    Client,
+};
 use aptos_types::account_address::AccountAddress;
 use move_package::compilation::package_layout::CompiledPackageLayout;
 use reqwest::Url;
-use std::{fmt, fs, path::Path};
+use std::{collections::BTreeSet, fmt, fs, path::Path};
 
 // TODO: this is a first naive implementation of the package registry. Before mainnet
 // we need to use tables for the package registry.
@@ -37,6 +41,14 @@ impl fmt::Display for CachedPackageMetadata<'_> {
     }
 }
 
+/// Whether dependencies on packages at the address are exempted from upgrade policy checks, as in
+/// `code::is_policy_exempted_address`.
+pub fn is_policy_exempted_address(address: AccountAddress) -> bool {
+    let bytes = address.into_bytes();
+    bytes[..AccountAddress::LENGTH - 1].iter().all(|b| *b == 0)
+        && (1..=10).contains(&bytes[AccountAddress::LENGTH - 1])
+}
+
 impl CachedPackageRegistry {
     /// Creates a new registry.
     pub async fn create(url: Url, addr: AccountAddress) -> anyhow::Result<Self> {
@@ -49,6 +61,74 @@ impl CachedPackageRegistry {
         Ok(Self { inner })
     }
 
+    /// Creates a new registry with the given client, or returns `None` if no package was
+    /// published at the account. Any other error fetching the registry is returned.
+    pub async fn create_if_exists(
+        client: &Client,
+        addr: AccountAddress,
+    ) -> anyhow::Result<Option<Self>> {
+        match client
+            .get_account_resource_bcs::<PackageRegistry>(addr, "0x1::code::PackageRegistry")
+            .await
+        {
+            Ok(response) => Ok(Some(Self {
+                inner: response.into_inner(),
+            })),
+            Err(RestError::Api(AptosErrorResponse {
+                error:
+                    AptosError {
+                        error_code:
+                            AptosErrorCode::ResourceNotFound | AptosErrorCode::AccountNotFound,
+                        ..
+                    },
+                ..
+            })) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Returns every reason for `code::publish_package` to reject publishing `package` to this
+    /// registry: upgrading an immutable package, weakening its upgrade policy, removing modules,
+    /// or adding modules which belong to another package.
+    pub fn publish_errors(&self, package: &PackageMetadata) -> Vec<String> {
+        let mut errors = vec![];
+        let module_names: BTreeSet<_> = package.modules.iter().map(|m| m.name.as_str()).collect();
+        for old_package in &self.inner.packages {
+            if old_package.name == package.name {
+                if old_package.upgrade_policy == UpgradePolicy::immutable() {
+                    errors.push(format!(
+                        "Package `{}` is immutable and cannot be upgraded",
+                        old_package.name
+                    ));
+                }
+                if package.upgrade_policy.policy < old_package.upgrade_policy.policy {
+                    errors.push(format!(
+                        "Upgrade policy `{}` is weaker than the policy `{}` of the published package",
+                        package.upgrade_policy, old_package.upgrade_policy
+                    ));
+                }
+                for module in &old_package.modules {
+                    if !module_names.contains(module.name.as_str()) {
+                        errors.push(format!(
+                            "Module `{}` is missing, modules cannot be removed from a package",
+                            module.name
+                        ));
+                    }
+                }
+            } else {
+                for module in &old_package.modules {
+                    if module_names.contains(module.name.as_str()) {
+                        errors.push(format!(
+                            "Module `{}` already belongs to package `{}`",
+                            module.name, old_package.name
+                        ));
+                    }
+                }
+            }
+        }
+        errors
+    }
+
     /// Returns the list of packages in this registry by name.
     pub fn package_names(&self) -> Vec<&str> {
         self.inner
@@ -213,3 +293,78 @@ impl<'a> CachedModuleMetadata<'a> {
         &self.metadata.source_map
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn package(name: &str, upgrade_policy: UpgradePolicy, modules: &[&str]) -> PackageMetadata {
+        PackageMetadata {
+            name: name.to_string(),
+            upgrade_policy,
+            upgrade_number: 0,
+            source_digest: String::new(),
+            manifest: vec![],
+            modules: modules
+                .iter()
+                .map(|name| ModuleMetadata {
+                    name: name.to_string(),
+                    source: vec![],
+                    source_map: vec![],
+                    extension: Default::default(),
+                })
+                .collect(),
+            deps: vec![],
+            extension: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_publish_errors() {
+        let registry = CachedPackageRegistry {
+            inner: PackageRegistry {
+                packages: vec![
+                    package("a", UpgradePolicy::compat(), &["m1", "m2"]),
+                    package("b", UpgradePolicy::immutable(), &["m3"]),
+                ],
+            },
+        };
+
+        assert!(registry
+            .publish_errors(&package("a", UpgradePolicy::compat(), &["m1", "m2", "m4"]))
+            .is_empty());
+        assert!(registry
+            .publish_errors(&package("c", UpgradePolicy::compat(), &["m4"]))
+            .is_empty());
+        // Removing modules and weakening the policy
+        assert_eq!(
+            registry
+                .publish_errors(&package("a", UpgradePolicy::arbitrary(), &["m1"]))
+                .len(),
+            2
+        );
+        // Upgrading an immutable package
+        assert_eq!(
+            registry
+                .publish_errors(&package("b", UpgradePolicy::immutable(), &["m3"]))
+                .len(),
+            1
+        );
+        // Module of another package
+        assert_eq!(
+            registry
+                .publish_errors(&package("c", UpgradePolicy::compat(), &["m2", "m3"]))
+                .len(),
+            2
+        );
+
+        assert!(is_policy_exempted_address(AccountAddress::ONE));
+        assert!(is_policy_exempted_address(
+            AccountAddress::from_hex_literal("0xa").unwrap()
+        ));
+        assert!(!is_policy_exempted_address(AccountAddress::ZERO));
+        assert!(!is_policy_exempted_address(
+            AccountAddress::from_hex_literal("0xb").unwrap()
+        ));
+    }
+}
@@ -0,0 +1,358 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{
+        types::{
+            load_account_arg, CliCommand, CliError, CliResult, CliTypedResult, ConfigSearchMode,
+            EntryFunctionArguments, TransactionSummary, DEFAULT_EXPIRATION_SECS,
+        },
+        utils::read_from_file,
+    },
+    config::GlobalConfig,
+};
+use aptos_api_types::MoveResource;
+use aptos_debugger::{ForkConfig, ForkedChain};
+use aptos_resource_viewer::AptosValueAnnotator;
+use aptos_types::{
+    account_address::AccountAddress,
+    chain_id::ChainId,
+    transaction::{
+        RawTransaction, SignedTransaction, TransactionOutput, TransactionPayload,
+        TransactionStatus, Version,
+    },
+};
+use aptos_vm::data_cache::AsMoveResolver;
+use async_trait::async_trait;
+use clap::{Parser, Subcommand};
+use move_core_types::language_storage::StructTag;
+use reqwest::Url;
+use serde::Serialize;
+use std::{
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const FORK_FOLDER: &str = "fork";
+const DEFAULT_FORK_MAX_GAS: u64 = 200_000;
+const DEFAULT_FORK_GAS_UNIT_PRICE: u64 = 100;
+
+/// Tool for running transactions locally on a fork of a live network
+///
+/// A fork starts at a version of a remote network, and reads its state lazily through the
+/// REST API, keeping everything it reads in an on-disk cache. Transactions run locally on
+/// top of that state, accounts can be funded, and transactions can be run as any account,
+/// without its key.
+#[derive(Subcommand)]
+pub enum ForkTool {
+    Create(CreateFork),
+    Fund(FundForkAccount),
+    RunFunction(RunForkFunction),
+    ShowResource(ShowForkResource),
+    Status(ShowForkStatus),
+    Submit(SubmitForkTransaction),
+}
+
+impl ForkTool {
+    pub async fn execute(self) -> CliResult {
+        use ForkTool::*;
+        match self {
+            Create(tool) => tool.execute_serialized().await,
+            Fund(tool) => tool.execute_serialized().await,
+            RunFunction(tool) => tool.execute_serialized().await,
+            ShowResource(tool) => tool.execute_serialized().await,
+            Status(tool) => tool.execute_serialized().await,
+            Submit(tool) => tool.execute_serialized().await,
+        }
+    }
+}
+
+#[derive(Debug, Parser)]
+pub struct ForkDirArgs {
+    /// The directory the fork is kept in
+    ///
+    /// Defaults to .aptos/fork
+    #[clap(long, value_parser)]
+    pub(crate) fork_dir: Option<PathBuf>,
+}
+
+impl ForkDirArgs {
+    fn fork_dir(&self) -> CliTypedResult<PathBuf> {
+        match &self.fork_dir {
+            Some(fork_dir) => Ok(fork_dir.clone()),
+            None => Ok(GlobalConfig::load()?
+                .get_config_location(ConfigSearchMode::CurrentDirAndParents)?
+                .join(FORK_FOLDER)),
+        }
+    }
+
+    fn open(&self) -> CliTypedResult<ForkedChain> {
+        Ok(ForkedChain::open(self.fork_dir()?)?)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ForkStatus {
+    pub rest_url: Url,
+    pub chain_id: ChainId,
+    pub fork_version: Version,
+    /// The version of the last transaction run on the fork
+    pub version: Version,
+    pub fork_dir: PathBuf,
+}
+
+impl ForkStatus {
+    fn new(fork: &ForkedChain, fork_dir: PathBuf) -> Self {
+        let ForkConfig {
+            rest_url,
+            chain_id,
+            fork_version,
+        } = fork.config().clone();
+        Self {
+            rest_url,
+            chain_id,
+            fork_version,
+            version: fork.version(),
+            fork_dir,
+        }
+    }
+}
+
+/// Create a fork of a network
+///
+/// Nothing but the version and chain id of the network is fetched when the fork is created,
+/// state is only read from the network when transactions use it.
+#[derive(Debug, Parser)]
+pub struct CreateFork {
+    /// REST endpoint of the network to fork, e.g. https://fullnode.mainnet.aptoslabs.com
+    #[clap(long)]
+    pub(crate) url: Url,
+
+    /// Version of the network to fork at
+    ///
+    /// Defaults to the latest version of the network.
+    #[clap(long)]
+    pub(crate) version: Option<Version>,
+
+    #[clap(flatten)]
+    pub(crate) fork_dir: ForkDirArgs,
+}
+
+#[async_trait]
+impl CliCommand<ForkStatus> for CreateFork {
+    fn command_name(&self) -> &'static str {
+        "CreateFork"
+    }
+
+    async fn execute(self) -> CliTypedResult<ForkStatus> {
+        let fork_dir = self.fork_dir.fork_dir()?;
+        let fork = ForkedChain::create(&fork_dir, self.url, self.version).await?;
+        Ok(ForkStatus::new(&fork, fork_dir))
+    }
+}
+
+/// Show where a fork was created from and how far it has advanced
+#[derive(Debug, Parser)]
+pub struct ShowForkStatus {
+    #[clap(flatten)]
+    pub(crate) fork_dir: ForkDirArgs,
+}
+
+#[async_trait]
+impl CliCommand<ForkStatus> for ShowForkStatus {
+    fn command_name(&self) -> &'static str {
+        "ShowForkStatus"
+    }
+
+    async fn execute(self) -> CliTypedResult<ForkStatus> {
+        let fork = self.fork_dir.open()?;
+        Ok(ForkStatus::new(&fork, self.fork_dir.fork_dir()?))
+    }
+}
+
+/// Fund an account on a fork
+///
+/// The coins are minted out of thin air, and the account is created if it doesn't exist.
+/// Returns the new balance of the account.
+#[derive(Debug, Parser)]
+pub struct FundForkAccount {
+    /// Address to fund
+    #[clap(long, value_parser = load_account_arg)]
+    pub(crate) account: AccountAddress,
+
+    /// Number of Octas to add to the account
+    #[clap(long)]
+    pub(crate) amount: u64,
+
+    #[clap(flatten)]
+    pub(crate) fork_dir: ForkDirArgs,
+}
+
+#[async_trait]
+impl CliCommand<u64> for FundForkAccount {
+    fn command_name(&self) -> &'static str {
+        "FundForkAccount"
+    }
+
+    async fn execute(self) -> CliTypedResult<u64> {
+        let mut fork = self.fork_dir.open()?;
+        Ok(fork.fund(self.account, self.amount)?)
+    }
+}
+
+/// Run a Move function on a fork as any account
+///
+/// The transaction is run on behalf of the sender without its key, but it pays for gas as
+/// usual, so the sender may need to be funded first.
+#[derive(Debug, Parser)]
+pub struct RunForkFunction {
+    /// Account to send the transaction as
+    #[clap(long, value_parser = load_account_arg)]
+    pub(crate) sender: AccountAddress,
+
+    #[clap(flatten)]
+    pub(crate) entry_function_args: EntryFunctionArguments,
+
+    /// Maximum amount of gas units to be used to send this transaction
+    #[clap(long, default_value_t = DEFAULT_FORK_MAX_GAS)]
+    pub(crate) max_gas: u64,
+
+    /// Gas multiplier per unit of gas
+    #[clap(long, default_value_t = DEFAULT_FORK_GAS_UNIT_PRICE)]
+    pub(crate) gas_unit_price: u64,
+
+    #[clap(flatten)]
+    pub(crate) fork_dir: ForkDirArgs,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for RunForkFunction {
+    fn command_name(&self) -> &'static str {
+        "RunForkFunction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let mut fork = self.fork_dir.open()?;
+        let sequence_number = fork.sequence_number(self.sender)?.ok_or_else(|| {
+            CliError::CommandArgumentError(format!(
+                "Account {} doesn't exist on the fork, fund it first",
+                self.sender
+            ))
+        })?;
+        let expiration_timestamp_secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            + DEFAULT_EXPIRATION_SECS;
+        let raw_txn = RawTransaction::new(
+            self.sender,
+            sequence_number,
+            TransactionPayload::EntryFunction(self.entry_function_args.try_into()?),
+            self.max_gas,
+            self.gas_unit_price,
+            expiration_timestamp_secs,
+            fork.config().chain_id,
+        );
+
+        let output = fork.execute_impersonated(raw_txn)?;
+        fork_transaction_summary(&fork, &output)
+    }
+}
+
+/// Submit a signed transaction to a fork
+///
+/// The transaction is read from a file of the BCS encoded `SignedTransaction`.
+#[derive(Debug, Parser)]
+pub struct SubmitForkTransaction {
+    /// Path to the BCS encoded signed transaction
+    #[clap(long, value_parser)]
+    pub(crate) transaction_file: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) fork_dir: ForkDirArgs,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for SubmitForkTransaction {
+    fn command_name(&self) -> &'static str {
+        "SubmitForkTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let txn: SignedTransaction = bcs::from_bytes(&read_from_file(&self.transaction_file)?)
+            .map_err(|err| CliError::BCS("SignedTransaction", err))?;
+        let mut fork = self.fork_dir.open()?;
+        let output = fork.execute_transaction(txn)?;
+        fork_transaction_summary(&fork, &output)
+    }
+}
+
+/// Show a resource of an account on a fork
+#[derive(Debug, Parser)]
+pub struct ShowForkResource {
+    /// Address of the account
+    #[clap(long, value_parser = load_account_arg)]
+    pub(crate) account: AccountAddress,
+
+    /// Type of the resource, e.g. `0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>`
+    #[clap(long)]
+    pub(crate) resource_type: StructTag,
+
+    #[clap(flatten)]
+    pub(crate) fork_dir: ForkDirArgs,
+}
+
+#[async_trait]
+impl CliCommand<Option<MoveResource>> for ShowForkResource {
+    fn command_name(&self) -> &'static str {
+        "ShowForkResource"
+    }
+
+    async fn execute(self) -> CliTypedResult<Option<MoveResource>> {
+        let fork = self.fork_dir.open()?;
+        let bytes = match fork.get_resource(self.account, &self.resource_type)? {
+            Some(bytes) => bytes,
+            None => return Ok(None),
+        };
+        let state_view = fork.state_view();
+        let resolver = state_view.as_move_resolver();
+        let annotated = AptosValueAnnotator::new(&resolver)
+            .view_resource(&self.resource_type, &bytes)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        Ok(Some(MoveResource::try_from(annotated)?))
+    }
+}
+
+/// Summarizes the transaction just run on the fork, which fails if it was discarded.
+fn fork_transaction_summary(
+    fork: &ForkedChain,
+    output: &TransactionOutput,
+) -> CliTypedResult<TransactionSummary> {
+    let status = match output.status() {
+        TransactionStatus::Keep(status) => status,
+        status => {
+            return Err(CliError::ApiError(format!(
+                "Transaction was discarded: {:?}",
+                status
+            )))
+        },
+    };
+    let forked_txn = fork
+        .transactions()
+        .last()
+        .ok_or_else(|| CliError::UnexpectedError("Transaction wasn't committed".to_string()))?;
+    let txn = &forked_txn.transaction;
+    Ok(TransactionSummary {
+        transaction_hash: txn.clone().committed_hash().into(),
+        gas_used: Some(output.gas_used()),
+        gas_unit_price: Some(txn.gas_unit_price()),
+        pending: None,
+        sender: Some(txn.sender()),
+        sequence_number: Some(txn.sequence_number()),
+        simulated: None,
+        success: Some(status.is_success()),
+        timestamp_us: None,
+        version: Some(forked_txn.version),
+        vm_status: Some(format!("{:?}", status)),
+    })
+}
@@ -0,0 +1,555 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    health_checker::HealthChecker, traits::ServiceManager, utils::socket_addr_to_url,
+    RunLocalTestnet,
+};
+use anyhow::{anyhow, ensure, Context, Result};
+use aptos_api_types::{
+    AccountData, Address, AptosError, AptosErrorCode, HashValue, IndexResponse, LedgerInfo,
+    MoveResource, U64, X_APTOS_BLOCK_HEIGHT, X_APTOS_CHAIN_ID, X_APTOS_EPOCH,
+    X_APTOS_LEDGER_OLDEST_VERSION, X_APTOS_LEDGER_TIMESTAMP, X_APTOS_LEDGER_VERSION,
+    X_APTOS_OLDEST_BLOCK_HEIGHT,
+};
+use aptos_config::config::RoleType;
+use aptos_debugger::{ForkConfig, ForkedChain, ForkedTransaction};
+use aptos_resource_viewer::AptosValueAnnotator;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::AccountResource,
+    on_chain_config::{ConfigurationResource, CurrentTimeMicroseconds, OnChainConfig},
+    transaction::{RawTransaction, SignedTransaction, TransactionStatus, Version},
+};
+use aptos_vm::data_cache::AsMoveResolver;
+use async_trait::async_trait;
+use clap::Parser;
+use maplit::hashset;
+use move_core_types::{language_storage::StructTag, move_resource::MoveStructType};
+use poem::{
+    get, handler,
+    http::StatusCode,
+    listener::TcpListener,
+    middleware::Tracing,
+    post,
+    web::{Data, Json, Path},
+    EndpointExt, IntoResponse, Response, Route, Server,
+};
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashSet,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+    path::PathBuf,
+    str::FromStr,
+    sync::{Arc, Mutex},
+};
+
+const FORK_FOLDER: &str = "fork";
+
+/// Args for running the local testnet as a fork of a live network, instead of as a
+/// network of its own.
+#[derive(Debug, Clone, Parser)]
+pub struct ForkArgs {
+    /// REST endpoint of a network to fork, e.g. https://fullnode.mainnet.aptoslabs.com
+    ///
+    /// Instead of running a node from its own genesis, the local testnet then serves the
+    /// state of that network at `--fork-version`, and runs the transactions submitted to it
+    /// on top of that state. State is read lazily from the network and cached in the test
+    /// dir. No faucet runs alongside a fork, accounts are funded through `POST /fork/fund`
+    /// on the API of the fork instead.
+    #[clap(long, conflicts_with = "with_indexer_api")]
+    pub fork_url: Option<Url>,
+
+    /// Version of the network to fork at
+    ///
+    /// Defaults to the latest version of the network. An existing fork in the test dir is
+    /// reused, use --force-restart to fork the network again.
+    #[clap(long, requires = "fork_url")]
+    pub fork_version: Option<Version>,
+
+    /// The port at which to expose the API of the fork.
+    #[clap(long, default_value_t = 8080)]
+    pub fork_api_port: u16,
+}
+
+/// Runs an API over a fork of a live network. It serves the parts of the node API needed
+/// to read accounts and resources and to submit transactions, plus endpoints specific to
+/// forks under `/fork`.
+#[derive(Clone, Debug)]
+pub struct ForkManager {
+    config: ForkArgs,
+    fork_url: Url,
+    fork_dir: PathBuf,
+}
+
+impl ForkManager {
+    pub fn new(args: &RunLocalTestnet, test_dir: PathBuf) -> Result<Self> {
+        let fork_url = args
+            .fork_args
+            .fork_url
+            .clone()
+            .context("No network to fork was given")?;
+        Ok(ForkManager {
+            config: args.fork_args.clone(),
+            fork_url,
+            fork_dir: test_dir.join(FORK_FOLDER),
+        })
+    }
+
+    pub fn get_api_url(&self) -> Url {
+        let address = SocketAddrV4::new(Ipv4Addr::new(127, 0, 0, 1), self.config.fork_api_port);
+        socket_addr_to_url(&SocketAddr::V4(address), "http").unwrap()
+    }
+}
+
+#[async_trait]
+impl ServiceManager for ForkManager {
+    fn get_name(&self) -> String {
+        "Fork API".to_string()
+    }
+
+    /// Creates the fork, unless there is one of the same network in the test dir already.
+    async fn pre_run(&self) -> Result<()> {
+        if !ForkedChain::exists(&self.fork_dir) {
+            ForkedChain::create(
+                &self.fork_dir,
+                self.fork_url.clone(),
+                self.config.fork_version,
+            )
+            .await?;
+            return Ok(());
+        }
+
+        let fork = ForkedChain::open(&self.fork_dir)?;
+        let ForkConfig {
+            rest_url,
+            fork_version,
+            ..
+        } = fork.config();
+        ensure!(
+            rest_url == &self.fork_url,
+            "{} holds a fork of {}, use --force-restart to fork {} instead",
+            self.fork_dir.display(),
+            rest_url,
+            self.fork_url
+        );
+        if let Some(version) = self.config.fork_version {
+            ensure!(
+                version == *fork_version,
+                "{} holds a fork at version {}, use --force-restart to fork at version {} instead",
+                self.fork_dir.display(),
+                fork_version,
+                version
+            );
+        }
+        Ok(())
+    }
+
+    fn get_health_checkers(&self) -> HashSet<HealthChecker> {
+        // The fork serves the index of the node API, so it is checked like a node.
+        hashset! {HealthChecker::NodeApi(self.get_api_url())}
+    }
+
+    fn get_prerequisite_health_checkers(&self) -> HashSet<&HealthChecker> {
+        hashset! {}
+    }
+
+    async fn run_service(self: Box<Self>) -> Result<()> {
+        run_fork_api(self.fork_dir, self.config.fork_api_port).await
+    }
+}
+
+/// This returns a future that runs a web server serving the fork in `fork_dir`.
+pub async fn run_fork_api(fork_dir: PathBuf, port: u16) -> Result<()> {
+    let fork = ForkedChain::open(&fork_dir)?;
+    let app = Route::new()
+        .at("/v1", get(index))
+        .at("/v1/", get(index))
+        .at("/v1/accounts/:address", get(account))
+        .at(
+            "/v1/accounts/:address/resource/:resource_type",
+            get(account_resource),
+        )
+        .at("/v1/transactions", post(submit_transaction))
+        .at(
+            "/v1/transactions/by_hash/:txn_hash",
+            get(transaction_by_hash),
+        )
+        .at("/fork", get(status))
+        .at("/fork/fund", post(fund))
+        .at("/fork/impersonate", post(impersonate))
+        .data(Fork(Arc::new(Mutex::new(fork))))
+        .with(Tracing);
+    Server::new(TcpListener::bind(SocketAddrV4::new(
+        Ipv4Addr::new(0, 0, 0, 0),
+        port,
+    )))
+    .name("fork-api")
+    .run(app)
+    .await?;
+    Err(anyhow!("Fork API exited unexpectedly"))
+}
+
+#[derive(Clone)]
+struct Fork(Arc<Mutex<ForkedChain>>);
+
+impl Fork {
+    /// Runs `f` on the fork along with the ledger info after it ran. Reading the state of
+    /// the fork may block on the remote network, so this runs on a blocking thread.
+    async fn run<T, F>(&self, f: F) -> Result<(T, LedgerInfo)>
+    where
+        T: Send + 'static,
+        F: FnOnce(&mut ForkedChain) -> Result<T> + Send + 'static,
+    {
+        let fork = self.0.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut fork = fork.lock().unwrap();
+            let result = f(&mut fork)?;
+            Ok((result, ledger_info(&fork)?))
+        })
+        .await?
+    }
+}
+
+/// The ledger info of the fork. A fork doesn't produce blocks, so it reports no block
+/// height, and it only has the transactions run on top of it.
+fn ledger_info(fork: &ForkedChain) -> Result<LedgerInfo> {
+    let state_view = fork.state_view();
+    let resolver = state_view.as_move_resolver();
+    let epoch = ConfigurationResource::fetch_config(&resolver)
+        .context("Failed to read the epoch of the fork")?
+        .epoch();
+    let timestamp = CurrentTimeMicroseconds::fetch_config(&resolver)
+        .context("Failed to read the timestamp of the fork")?
+        .microseconds;
+    Ok(LedgerInfo {
+        chain_id: fork.config().chain_id.id(),
+        epoch: epoch.into(),
+        ledger_version: fork.version().into(),
+        oldest_ledger_version: fork.config().fork_version.into(),
+        block_height: 0.into(),
+        oldest_block_height: 0.into(),
+        ledger_timestamp: timestamp.into(),
+    })
+}
+
+/// Responds with `body`, along with the ledger info headers clients of the node API
+/// expect on every response.
+fn ledger_response<T: Serialize + Send>(ledger_info: &LedgerInfo, body: T) -> Response {
+    Json(body)
+        .with_header(X_APTOS_CHAIN_ID, ledger_info.chain_id as u16)
+        .with_header(X_APTOS_EPOCH, ledger_info.epoch.0)
+        .with_header(X_APTOS_LEDGER_VERSION, ledger_info.ledger_version.0)
+        .with_header(
+            X_APTOS_LEDGER_OLDEST_VERSION,
+            ledger_info.oldest_ledger_version.0,
+        )
+        .with_header(X_APTOS_LEDGER_TIMESTAMP, ledger_info.ledger_timestamp.0)
+        .with_header(X_APTOS_BLOCK_HEIGHT, ledger_info.block_height.0)
+        .with_header(
+            X_APTOS_OLDEST_BLOCK_HEIGHT,
+            ledger_info.oldest_block_height.0,
+        )
+        .into_response()
+}
+
+fn error_response<E: std::fmt::Display>(
+    status: StatusCode,
+    error: E,
+    error_code: AptosErrorCode,
+) -> Response {
+    Json(AptosError::new_with_error_code(error, error_code))
+        .with_status(status)
+        .into_response()
+}
+
+fn internal_error(error: anyhow::Error) -> Response {
+    error_response(
+        StatusCode::INTERNAL_SERVER_ERROR,
+        format!("{:#}", error),
+        AptosErrorCode::InternalError,
+    )
+}
+
+fn parse_address(address: &str) -> std::result::Result<AccountAddress, Response> {
+    AccountAddress::from_str(address).map_err(|err| {
+        error_response(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid address {}: {}", address, err),
+            AptosErrorCode::InvalidInput,
+        )
+    })
+}
+
+#[handler]
+async fn index(fork: Data<&Fork>) -> Response {
+    match fork.run(|_| Ok(())).await {
+        Ok(((), ledger_info)) => ledger_response(
+            &ledger_info,
+            IndexResponse::new(ledger_info.clone(), RoleType::FullNode, None),
+        ),
+        Err(err) => internal_error(err),
+    }
+}
+
+#[handler]
+async fn account(fork: Data<&Fork>, Path(address): Path<String>) -> Response {
+    let address = match parse_address(&address) {
+        Ok(address) => address,
+        Err(response) => return response,
+    };
+    let result = fork
+        .run(move |fork| {
+            fork.get_resource(address, &AccountResource::struct_tag())?
+                .map(|bytes| bcs::from_bytes::<AccountResource>(&bytes))
+                .transpose()
+                .map_err(Into::into)
+        })
+        .await;
+    match result {
+        Ok((Some(account), ledger_info)) => {
+            ledger_response(&ledger_info, AccountData::from(account))
+        },
+        Ok((None, _)) => error_response(
+            StatusCode::NOT_FOUND,
+            format!("Account {} not found on the fork", address),
+            AptosErrorCode::AccountNotFound,
+        ),
+        Err(err) => internal_error(err),
+    }
+}
+
+#[handler]
+async fn account_resource(
+    fork: Data<&Fork>,
+    Path((address, resource_type)): Path<(String, String)>,
+) -> Response {
+    let address = match parse_address(&address) {
+        Ok(address) => address,
+        Err(response) => return response,
+    };
+    let resource_type = match StructTag::from_str(&resource_type) {
+        Ok(resource_type) => resource_type,
+        Err(err) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid resource type {}: {}", resource_type, err),
+                AptosErrorCode::InvalidInput,
+            )
+        },
+    };
+    let result = fork
+        .run(move |fork| {
+            let bytes = match fork.get_resource(address, &resource_type)? {
+                Some(bytes) => bytes,
+                None => return Ok(None),
+            };
+            let state_view = fork.state_view();
+            let resolver = state_view.as_move_resolver();
+            let annotated =
+                AptosValueAnnotator::new(&resolver).view_resource(&resource_type, &bytes)?;
+            Ok(Some(MoveResource::try_from(annotated)?))
+        })
+        .await;
+    match result {
+        Ok((Some(resource), ledger_info)) => ledger_response(&ledger_info, resource),
+        Ok((None, _)) => error_response(
+            StatusCode::NOT_FOUND,
+            format!("Resource not found for account {} on the fork", address),
+            AptosErrorCode::ResourceNotFound,
+        ),
+        Err(err) => internal_error(err),
+    }
+}
+
+/// A transaction run on the fork.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ForkTransactionResponse {
+    pub hash: HashValue,
+    pub version: U64,
+    pub sender: Address,
+    pub sequence_number: U64,
+    /// Whether the transaction was run on behalf of the sender without their signature.
+    pub impersonated: bool,
+    pub success: bool,
+    pub vm_status: String,
+    pub gas_used: U64,
+}
+
+impl From<&ForkedTransaction> for ForkTransactionResponse {
+    fn from(forked_txn: &ForkedTransaction) -> Self {
+        let txn = &forked_txn.transaction;
+        let (success, vm_status) = match forked_txn.output.status() {
+            TransactionStatus::Keep(status) => (status.is_success(), format!("{:?}", status)),
+            status => (false, format!("{:?}", status)),
+        };
+        Self {
+            hash: txn.clone().committed_hash().into(),
+            version: forked_txn.version.into(),
+            sender: txn.sender().into(),
+            sequence_number: txn.sequence_number().into(),
+            impersonated: forked_txn.impersonated,
+            success,
+            vm_status,
+            gas_used: forked_txn.output.gas_used().into(),
+        }
+    }
+}
+
+/// Responds with the transaction just run on the fork, or with an error if it was
+/// discarded rather than committed to the fork.
+fn committed_transaction_response(
+    result: Result<(Option<ForkTransactionResponse>, LedgerInfo)>,
+) -> Response {
+    match result {
+        Ok((Some(txn), ledger_info)) => ledger_response(&ledger_info, txn),
+        Ok((None, _)) => error_response(
+            StatusCode::BAD_REQUEST,
+            "Transaction was discarded",
+            AptosErrorCode::VmError,
+        ),
+        Err(err) => internal_error(err),
+    }
+}
+
+/// Runs a BCS encoded `SignedTransaction` on the fork. Unlike on a node, the transaction
+/// has run by the time this returns, so the committed transaction is returned.
+#[handler]
+async fn submit_transaction(fork: Data<&Fork>, body: Vec<u8>) -> Response {
+    let txn: SignedTransaction = match bcs::from_bytes(&body) {
+        Ok(txn) => txn,
+        Err(err) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Failed to deserialize the signed transaction: {}", err),
+                AptosErrorCode::InvalidInput,
+            )
+        },
+    };
+    let result = fork
+        .run(move |fork| {
+            let output = fork.execute_transaction(txn)?;
+            Ok(last_committed_transaction(fork, output.status()))
+        })
+        .await;
+    committed_transaction_response(result)
+}
+
+/// Runs a BCS encoded `RawTransaction` on the fork on behalf of its sender, who doesn't
+/// have to sign it.
+#[handler]
+async fn impersonate(fork: Data<&Fork>, body: Vec<u8>) -> Response {
+    let raw_txn: RawTransaction = match bcs::from_bytes(&body) {
+        Ok(raw_txn) => raw_txn,
+        Err(err) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Failed to deserialize the raw transaction: {}", err),
+                AptosErrorCode::InvalidInput,
+            )
+        },
+    };
+    let result = fork
+        .run(move |fork| {
+            let output = fork.execute_impersonated(raw_txn)?;
+            Ok(last_committed_transaction(fork, output.status()))
+        })
+        .await;
+    committed_transaction_response(result)
+}
+
+/// The transaction just run on the fork, if it was kept, i.e. committed to the fork.
+fn last_committed_transaction(
+    fork: &ForkedChain,
+    status: &TransactionStatus,
+) -> Option<ForkTransactionResponse> {
+    match status {
+        TransactionStatus::Keep(_) => fork.transactions().last().map(Into::into),
+        _ => None,
+    }
+}
+
+#[handler]
+async fn transaction_by_hash(fork: Data<&Fork>, Path(txn_hash): Path<String>) -> Response {
+    let txn_hash = match HashValue::from_str(&txn_hash) {
+        Ok(txn_hash) => txn_hash,
+        Err(err) => {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!("Invalid transaction hash {}: {}", txn_hash, err),
+                AptosErrorCode::InvalidInput,
+            )
+        },
+    };
+    let result = fork
+        .run(move |fork| {
+            Ok(fork
+                .transactions()
+                .iter()
+                .map(ForkTransactionResponse::from)
+                .find(|txn| txn.hash == txn_hash))
+        })
+        .await;
+    match result {
+        Ok((Some(txn), ledger_info)) => ledger_response(&ledger_info, txn),
+        Ok((None, _)) => error_response(
+            StatusCode::NOT_FOUND,
+            format!("Transaction {} was not run on the fork", txn_hash),
+            AptosErrorCode::TransactionNotFound,
+        ),
+        Err(err) => internal_error(err),
+    }
+}
+
+/// Where the fork was created from and how far it has advanced.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ForkStatusResponse {
+    #[serde(flatten)]
+    pub config: ForkConfig,
+    /// The version of the last transaction run on the fork
+    pub version: U64,
+}
+
+#[handler]
+async fn status(fork: Data<&Fork>) -> Response {
+    let result = fork
+        .run(|fork| {
+            Ok(ForkStatusResponse {
+                config: fork.config().clone(),
+                version: fork.version().into(),
+            })
+        })
+        .await;
+    match result {
+        Ok((status, ledger_info)) => ledger_response(&ledger_info, status),
+        Err(err) => internal_error(err),
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FundRequest {
+    pub address: Address,
+    /// Number of Octas to add to the account
+    pub amount: U64,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FundResponse {
+    /// The balance of the account after it was funded
+    pub balance: U64,
+}
+
+/// Mints coins into an account, creating it if it doesn't exist.
+#[handler]
+async fn fund(fork: Data<&Fork>, Json(request): Json<FundRequest>) -> Response {
+    let result = fork
+        .run(move |fork| fork.fund(request.address.into(), request.amount.0))
+        .await;
+    match result {
+        Ok((balance, ledger_info)) => ledger_response(&ledger_info, FundResponse {
+            balance: balance.into(),
+        }),
+        Err(err) => internal_error(err),
+    }
+}
@@ -3,6 +3,7 @@
 
 mod docker;
 mod faucet;
+mod fork;
 mod health_checker;
 mod indexer_api;
 mod logging;
@@ -15,6 +16,7 @@ mod utils;
 
 use self::{
     faucet::FaucetArgs,
+    fork::{ForkArgs, ForkManager},
     health_checker::HealthChecker,
     indexer_api::IndexerApiArgs,
     logging::ThreadNameMakeWriter,
@@ -56,6 +58,10 @@ const TESTNET_FOLDER: &str = "testnet";
 /// This local testnet will run it's own genesis and run as a single node network
 /// locally. A faucet and grpc transaction stream will run alongside the node unless
 /// you specify otherwise with --no-faucet and --no-txn-stream respectively.
+///
+/// To run on top of the state of a live network instead, fork it with --fork-url. The
+/// local testnet then serves the state of that network, and runs the transactions submitted
+/// to it locally on top of that state.
 #[derive(Parser)]
 pub struct RunLocalTestnet {
     /// The directory to save all files for the node
@@ -75,6 +81,9 @@ pub struct RunLocalTestnet {
     #[clap(flatten)]
     node_args: NodeArgs,
 
+    #[clap(flatten)]
+    fork_args: ForkArgs,
+
     #[clap(flatten)]
     faucet_args: FaucetArgs,
 
@@ -224,66 +233,74 @@ impl CliCommand<()> for RunLocalTestnet {
 
         let mut managers: Vec<Box<dyn ServiceManager>> = Vec::new();
 
-        // Build the node manager. We do this unconditionally.
-        let node_manager = NodeManager::new(&self, test_dir.clone())
-            .context("Failed to build node service manager")?;
-        let node_health_checkers = node_manager.get_health_checkers();
-
-        // If configured to do so, build the faucet manager.
-        if !self.faucet_args.no_faucet {
-            let faucet_manager = FaucetManager::new(
-                &self,
-                node_health_checkers.clone(),
-                test_dir.clone(),
-                node_manager.get_node_api_url(),
-            )
-            .context("Failed to build faucet service manager")?;
-            managers.push(Box::new(faucet_manager));
-        }
+        if self.fork_args.fork_url.is_some() {
+            // When forking a network, the fork API runs in place of the node. Accounts are
+            // funded through it, so no faucet is run either.
+            let fork_manager = ForkManager::new(&self, test_dir.clone())
+                .context("Failed to build fork service manager")?;
+            managers.push(Box::new(fork_manager));
+        } else {
+            // Build the node manager. We do this unless forking.
+            let node_manager = NodeManager::new(&self, test_dir.clone())
+                .context("Failed to build node service manager")?;
+            let node_health_checkers = node_manager.get_health_checkers();
+
+            // If configured to do so, build the faucet manager.
+            if !self.faucet_args.no_faucet {
+                let faucet_manager = FaucetManager::new(
+                    &self,
+                    node_health_checkers.clone(),
+                    test_dir.clone(),
+                    node_manager.get_node_api_url(),
+                )
+                .context("Failed to build faucet service manager")?;
+                managers.push(Box::new(faucet_manager));
+            }
 
-        if self.indexer_api_args.with_indexer_api {
-            let postgres_manager = postgres::PostgresManager::new(&self, test_dir.clone())
-                .context("Failed to build postgres service manager")?;
-            let postgres_health_checkers = postgres_manager.get_health_checkers();
-            managers.push(Box::new(postgres_manager));
+            if self.indexer_api_args.with_indexer_api {
+                let postgres_manager = postgres::PostgresManager::new(&self, test_dir.clone())
+                    .context("Failed to build postgres service manager")?;
+                let postgres_health_checkers = postgres_manager.get_health_checkers();
+                managers.push(Box::new(postgres_manager));
+
+                let processor_preqrequisite_healthcheckers =
+                    [node_health_checkers, postgres_health_checkers]
+                        .into_iter()
+                        .flatten()
+                        .collect();
+                let processor_managers = ProcessorManager::many_new(
+                    &self,
+                    processor_preqrequisite_healthcheckers,
+                    node_manager.get_data_service_url(),
+                    self.postgres_args.get_connection_string(None),
+                )
+                .context("Failed to build processor service managers")?;
+
+                let processor_health_checkers = processor_managers
+                    .iter()
+                    .flat_map(|m| m.get_health_checkers())
+                    .collect();
 
-            let processor_preqrequisite_healthcheckers =
-                [node_health_checkers, postgres_health_checkers]
+                let mut processor_managers = processor_managers
                     .into_iter()
-                    .flatten()
+                    .map(|m| Box::new(m) as Box<dyn ServiceManager>)
                     .collect();
-            let processor_managers = ProcessorManager::many_new(
-                &self,
-                processor_preqrequisite_healthcheckers,
-                node_manager.get_data_service_url(),
-                self.postgres_args.get_connection_string(None),
-            )
-            .context("Failed to build processor service managers")?;
-
-            let processor_health_checkers = processor_managers
-                .iter()
-                .flat_map(|m| m.get_health_checkers())
-                .collect();
-
-            let mut processor_managers = processor_managers
-                .into_iter()
-                .map(|m| Box::new(m) as Box<dyn ServiceManager>)
-                .collect();
-            managers.append(&mut processor_managers);
-
-            let indexer_api_manager = IndexerApiManager::new(
-                &self,
-                processor_health_checkers,
-                test_dir.clone(),
-                self.postgres_args.get_connection_string(None),
-            )
-            .context("Failed to build indexer API service manager")?;
-            managers.push(Box::new(indexer_api_manager));
-        }
+                managers.append(&mut processor_managers);
+
+                let indexer_api_manager = IndexerApiManager::new(
+                    &self,
+                    processor_health_checkers,
+                    test_dir.clone(),
+                    self.postgres_args.get_connection_string(None),
+                )
+                .context("Failed to build indexer API service manager")?;
+                managers.push(Box::new(indexer_api_manager));
+            }
 
-        // We put the node manager into managers at the end just so we have access to
-        // it before this so we can call things like `node_manager.get_node_api_url()`.
-        managers.push(Box::new(node_manager));
+            // We put the node manager into managers at the end just so we have access to
+            // it before this so we can call things like `node_manager.get_node_api_url()`.
+            managers.push(Box::new(node_manager));
+        }
 
         // Get the healthcheckers from all the managers. We'll pass to this
         // `wait_for_startup`.
@@ -2,16 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod analyze;
+pub mod fork;
 pub mod local_testnet;
 
-use self::local_testnet::RunLocalTestnet;
+use self::{fork::ForkTool, local_testnet::RunLocalTestnet};
 use crate::{
     common::{
         types::{
             CliCommand, CliError, CliResult, CliTypedResult, OptionalPoolAddressArgs,
             PoolAddressArgs, ProfileOptions, RestOptions, TransactionOptions, TransactionSummary,
         },
-        utils::read_from_file,
+        utils::{profile_or_submit, read_from_file, write_to_file},
     },
     genesis::git::from_yaml,
     node::analyze::{
@@ -29,7 +30,7 @@ use aptos_crypto::{bls12381, bls12381::PublicKey, x25519, ValidCryptoMaterialStr
 use aptos_genesis::config::{HostAndPort, OperatorConfiguration};
 use aptos_logger::Level;
 use aptos_network_checker::args::{
-    validate_address, CheckEndpointArgs, HandshakeArgs, NodeAddressArgs,
+    validate_address, CheckEndpointArgs, CrawlNetworkArgs, HandshakeArgs, NodeAddressArgs,
 };
 use aptos_rest_client::{aptos_api_types::VersionedEvent, Client, State};
 use aptos_types::{
@@ -67,6 +68,9 @@ pub enum NodeTool {
     AnalyzeValidatorPerformance(AnalyzeValidatorPerformance),
     BootstrapDb(BootstrapDb),
     CheckNetworkConnectivity(CheckNetworkConnectivity),
+    CrawlNetwork(CrawlNetwork),
+    #[clap(subcommand)]
+    Fork(ForkTool),
     GetPerformance(GetPerformance),
     GetStakePool(GetStakePool),
     InitializeValidator(InitializeValidator),
@@ -91,6 +95,8 @@ impl NodeTool {
                     .await
             },
             CheckNetworkConnectivity(tool) => tool.execute_serialized().await,
+            CrawlNetwork(tool) => tool.execute_serialized().await,
+            Fork(tool) => tool.execute().await,
             GetPerformance(tool) => tool.execute_serialized().await,
             GetStakePool(tool) => tool.execute_serialized().await,
             InitializeValidator(tool) => tool.execute_serialized().await,
@@ -637,16 +643,17 @@ impl CliCommand<TransactionSummary> for InitializeValidator {
                 },
             };
 
-        self.txn_options
-            .submit_transaction(aptos_stdlib::stake_initialize_validator(
+        profile_or_submit(
+            aptos_stdlib::stake_initialize_validator(
                 consensus_public_key.to_bytes().to_vec(),
                 consensus_proof_of_possession.to_bytes().to_vec(),
                 // BCS encode, so that we can hide the original type
                 bcs::to_bytes(&validator_network_addresses)?,
                 bcs::to_bytes(&full_node_network_addresses)?,
-            ))
-            .await
-            .map(|inner| inner.into())
+            ),
+            &self.txn_options,
+        )
+        .await
     }
 }
 
@@ -704,10 +711,11 @@ impl CliCommand<TransactionSummary> for JoinValidatorSet {
             .operator_args
             .address_fallback_to_txn(&self.txn_options)?;
 
-        self.txn_options
-            .submit_transaction(aptos_stdlib::stake_join_validator_set(address))
-            .await
-            .map(|inner| inner.into())
+        profile_or_submit(
+            aptos_stdlib::stake_join_validator_set(address),
+            &self.txn_options,
+        )
+        .await
     }
 }
 
@@ -734,10 +742,11 @@ impl CliCommand<TransactionSummary> for LeaveValidatorSet {
             .operator_args
             .address_fallback_to_txn(&self.txn_options)?;
 
-        self.txn_options
-            .submit_transaction(aptos_stdlib::stake_leave_validator_set(address))
-            .await
-            .map(|inner| inner.into())
+        profile_or_submit(
+            aptos_stdlib::stake_leave_validator_set(address),
+            &self.txn_options,
+        )
+        .await
     }
 }
 
@@ -1075,14 +1084,15 @@ impl CliCommand<TransactionSummary> for UpdateConsensusKey {
         let consensus_proof_of_possession = self
             .validator_consensus_key_args
             .get_consensus_proof_of_possession(&operator_config)?;
-        self.txn_options
-            .submit_transaction(aptos_stdlib::stake_rotate_consensus_key(
+        profile_or_submit(
+            aptos_stdlib::stake_rotate_consensus_key(
                 address,
                 consensus_public_key.to_bytes().to_vec(),
                 consensus_proof_of_possession.to_bytes().to_vec(),
-            ))
-            .await
-            .map(|inner| inner.into())
+            ),
+            &self.txn_options,
+        )
+        .await
     }
 }
 
@@ -1135,15 +1145,16 @@ impl CliCommand<TransactionSummary> for UpdateValidatorNetworkAddresses {
                 },
             };
 
-        self.txn_options
-            .submit_transaction(aptos_stdlib::stake_update_network_and_fullnode_addresses(
+        profile_or_submit(
+            aptos_stdlib::stake_update_network_and_fullnode_addresses(
                 address,
                 // BCS encode, so that we can hide the original type
                 bcs::to_bytes(&validator_network_addresses)?,
                 bcs::to_bytes(&full_node_network_addresses)?,
-            ))
-            .await
-            .map(|inner| inner.into())
+            ),
+            &self.txn_options,
+        )
+        .await
     }
 }
 
@@ -1369,6 +1380,68 @@ impl CliCommand<String> for CheckNetworkConnectivity {
     }
 }
 
+/// Crawls the network topology
+///
+/// Connects to the given seed nodes, and walks the network (breadth-first)
+/// using the peer monitoring service of each node. The discovered nodes
+/// (with their roles, versions and latencies) and connections are written
+/// to the output file as JSON or GraphViz (DOT).
+#[derive(Parser)]
+pub struct CrawlNetwork {
+    #[clap(flatten)]
+    pub crawl_network_args: CrawlNetworkArgs,
+
+    /// Output file to write the network graph to
+    #[clap(long, value_parser)]
+    pub output_file: PathBuf,
+
+    /// Format of the network graph
+    #[clap(long, value_enum, ignore_case = true, default_value_t = NetworkGraphFormat::Json)]
+    pub output_format: NetworkGraphFormat,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, clap::ValueEnum)]
+pub enum NetworkGraphFormat {
+    /// The network graph as JSON
+    Json,
+    /// The network graph in the GraphViz (DOT) format
+    Dot,
+}
+
+#[async_trait]
+impl CliCommand<String> for CrawlNetwork {
+    fn command_name(&self) -> &'static str {
+        "CrawlNetwork"
+    }
+
+    async fn execute(self) -> CliTypedResult<String> {
+        // Crawl the network
+        let network_graph = aptos_network_checker::crawl_network(&self.crawl_network_args, None)
+            .await
+            .map_err(|error| CliError::UnexpectedError(error.to_string()))?;
+
+        // Write the network graph to the output file
+        let bytes = match self.output_format {
+            NetworkGraphFormat::Json => serde_json::to_vec_pretty(&network_graph)
+                .map_err(|error| CliError::UnexpectedError(error.to_string()))?,
+            NetworkGraphFormat::Dot => network_graph.to_dot().into_bytes(),
+        };
+        write_to_file(&self.output_file, "Network graph", &bytes)?;
+
+        let num_crawled_nodes = network_graph
+            .nodes
+            .values()
+            .filter(|node| node.node_information.is_some())
+            .count();
+        Ok(format!(
+            "Discovered {} nodes ({} crawled successfully) and {} connections",
+            network_graph.nodes.len(),
+            num_crawled_nodes,
+            network_graph.connections.len()
+        ))
+    }
+}
+
 /// Show epoch information
 ///
 /// Displays the current epoch, the epoch length, and the estimated time of the next epoch
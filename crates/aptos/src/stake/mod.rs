@@ -6,7 +6,7 @@ use crate::{
         types::{
             CliCommand, CliError, CliResult, CliTypedResult, TransactionOptions, TransactionSummary,
         },
-        utils::prompt_yes_with_override,
+        utils::{profile_or_submit, prompt_yes_with_override},
     },
     node::{get_stake_pools, StakePoolType},
 };
@@ -89,21 +89,20 @@ impl CliCommand<Vec<TransactionSummary>> for AddStake {
             match stake_pool.pool_type {
                 StakePoolType::Direct => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(aptos_stdlib::stake_add_stake(amount))
-                            .await
-                            .map(|inner| inner.into())?,
+                        profile_or_submit(aptos_stdlib::stake_add_stake(amount), &self.txn_options)
+                            .await?,
                     );
                 },
                 StakePoolType::StakingContract => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(aptos_stdlib::staking_contract_add_stake(
+                        profile_or_submit(
+                            aptos_stdlib::staking_contract_add_stake(
                                 stake_pool.operator_address,
                                 amount,
-                            ))
-                            .await
-                            .map(|inner| inner.into())?,
+                            ),
+                            &self.txn_options,
+                        )
+                        .await?,
                     );
                 },
                 StakePoolType::Vesting => {
@@ -150,21 +149,20 @@ impl CliCommand<Vec<TransactionSummary>> for UnlockStake {
             match stake_pool.pool_type {
                 StakePoolType::Direct => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(aptos_stdlib::stake_unlock(amount))
-                            .await
-                            .map(|inner| inner.into())?,
+                        profile_or_submit(aptos_stdlib::stake_unlock(amount), &self.txn_options)
+                            .await?,
                     );
                 },
                 StakePoolType::StakingContract => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(aptos_stdlib::staking_contract_unlock_stake(
+                        profile_or_submit(
+                            aptos_stdlib::staking_contract_unlock_stake(
                                 stake_pool.operator_address,
                                 amount,
-                            ))
-                            .await
-                            .map(|inner| inner.into())?,
+                            ),
+                            &self.txn_options,
+                        )
+                        .await?,
                     );
                 },
                 StakePoolType::Vesting => {
@@ -214,21 +212,23 @@ impl CliCommand<Vec<TransactionSummary>> for WithdrawStake {
             match stake_pool.pool_type {
                 StakePoolType::Direct => {
                     transaction_summaries.push(
-                        self.node_op_options
-                            .submit_transaction(aptos_stdlib::stake_withdraw(amount))
-                            .await
-                            .map(|inner| inner.into())?,
+                        profile_or_submit(
+                            aptos_stdlib::stake_withdraw(amount),
+                            &self.node_op_options,
+                        )
+                        .await?,
                     );
                 },
                 StakePoolType::StakingContract => {
                     transaction_summaries.push(
-                        self.node_op_options
-                            .submit_transaction(aptos_stdlib::staking_contract_distribute(
+                        profile_or_submit(
+                            aptos_stdlib::staking_contract_distribute(
                                 owner_address,
                                 stake_pool.operator_address,
-                            ))
-                            .await
-                            .map(|inner| inner.into())?,
+                            ),
+                            &self.node_op_options,
+                        )
+                        .await?,
                     );
                 },
                 StakePoolType::Vesting => {
@@ -271,30 +271,30 @@ impl CliCommand<Vec<TransactionSummary>> for IncreaseLockup {
             match stake_pool.pool_type {
                 StakePoolType::Direct => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(aptos_stdlib::stake_increase_lockup())
-                            .await
-                            .map(|inner| inner.into())?,
+                        profile_or_submit(aptos_stdlib::stake_increase_lockup(), &self.txn_options)
+                            .await?,
                     );
                 },
                 StakePoolType::StakingContract => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(aptos_stdlib::staking_contract_reset_lockup(
+                        profile_or_submit(
+                            aptos_stdlib::staking_contract_reset_lockup(
                                 stake_pool.operator_address,
-                            ))
-                            .await
-                            .map(|inner| inner.into())?,
+                            ),
+                            &self.txn_options,
+                        )
+                        .await?,
                     );
                 },
                 StakePoolType::Vesting => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(aptos_stdlib::vesting_reset_lockup(
+                        profile_or_submit(
+                            aptos_stdlib::vesting_reset_lockup(
                                 stake_pool.vesting_contract.unwrap(),
-                            ))
-                            .await
-                            .map(|inner| inner.into())?,
+                            ),
+                            &self.txn_options,
+                        )
+                        .await?,
                     );
                 },
             }
@@ -333,14 +333,15 @@ impl CliCommand<TransactionSummary> for InitializeStakeOwner {
 
     async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
         let owner_address = self.txn_options.sender_address()?;
-        self.txn_options
-            .submit_transaction(aptos_stdlib::stake_initialize_stake_owner(
+        profile_or_submit(
+            aptos_stdlib::stake_initialize_stake_owner(
                 self.initial_stake_amount,
                 self.operator_address.unwrap_or(owner_address),
                 self.voter_address.unwrap_or(owner_address),
-            ))
-            .await
-            .map(|inner| inner.into())
+            ),
+            &self.txn_options,
+        )
+        .await
     }
 }
 
@@ -380,38 +381,35 @@ impl CliCommand<Vec<TransactionSummary>> for SetOperator {
             match stake_pool.pool_type {
                 StakePoolType::Direct => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(aptos_stdlib::stake_set_operator(
-                                new_operator_address,
-                            ))
-                            .await
-                            .map(|inner| inner.into())?,
+                        profile_or_submit(
+                            aptos_stdlib::stake_set_operator(new_operator_address),
+                            &self.txn_options,
+                        )
+                        .await?,
                     );
                 },
                 StakePoolType::StakingContract => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(
-                                aptos_stdlib::staking_contract_switch_operator_with_same_commission(
-                                    stake_pool.operator_address,
-                                    new_operator_address,
-                                ),
-                            )
-                            .await
-                            .map(|inner| inner.into())?,
+                        profile_or_submit(
+                            aptos_stdlib::staking_contract_switch_operator_with_same_commission(
+                                stake_pool.operator_address,
+                                new_operator_address,
+                            ),
+                            &self.txn_options,
+                        )
+                        .await?,
                     );
                 },
                 StakePoolType::Vesting => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(
-                                aptos_stdlib::vesting_update_operator_with_same_commission(
-                                    stake_pool.vesting_contract.unwrap(),
-                                    new_operator_address,
-                                ),
-                            )
-                            .await
-                            .map(|inner| inner.into())?,
+                        profile_or_submit(
+                            aptos_stdlib::vesting_update_operator_with_same_commission(
+                                stake_pool.vesting_contract.unwrap(),
+                                new_operator_address,
+                            ),
+                            &self.txn_options,
+                        )
+                        .await?,
                     );
                 },
             }
@@ -456,34 +454,35 @@ impl CliCommand<Vec<TransactionSummary>> for SetDelegatedVoter {
             match stake_pool.pool_type {
                 StakePoolType::Direct => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(aptos_stdlib::stake_set_delegated_voter(
-                                new_voter_address,
-                            ))
-                            .await
-                            .map(|inner| inner.into())?,
+                        profile_or_submit(
+                            aptos_stdlib::stake_set_delegated_voter(new_voter_address),
+                            &self.txn_options,
+                        )
+                        .await?,
                     );
                 },
                 StakePoolType::StakingContract => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(aptos_stdlib::staking_contract_update_voter(
+                        profile_or_submit(
+                            aptos_stdlib::staking_contract_update_voter(
                                 stake_pool.operator_address,
                                 new_voter_address,
-                            ))
-                            .await
-                            .map(|inner| inner.into())?,
+                            ),
+                            &self.txn_options,
+                        )
+                        .await?,
                     );
                 },
                 StakePoolType::Vesting => {
                     transaction_summaries.push(
-                        self.txn_options
-                            .submit_transaction(aptos_stdlib::vesting_update_voter(
+                        profile_or_submit(
+                            aptos_stdlib::vesting_update_voter(
                                 stake_pool.vesting_contract.unwrap(),
                                 new_voter_address,
-                            ))
-                            .await
-                            .map(|inner| inner.into())?,
+                            ),
+                            &self.txn_options,
+                        )
+                        .await?,
                     );
                 },
             }
@@ -536,16 +535,17 @@ impl CliCommand<TransactionSummary> for CreateStakingContract {
             self.txn_options.prompt_options,
         )?;
 
-        self.txn_options
-            .submit_transaction(aptos_stdlib::staking_contract_create_staking_contract(
+        profile_or_submit(
+            aptos_stdlib::staking_contract_create_staking_contract(
                 self.operator,
                 self.voter,
                 self.amount,
                 self.commission_percentage,
                 vec![],
-            ))
-            .await
-            .map(|inner| inner.into())
+            ),
+            &self.txn_options,
+        )
+        .await
     }
 }
 
@@ -571,10 +571,11 @@ impl CliCommand<TransactionSummary> for DistributeVestedCoins {
 
     async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
         let vesting_contract_address = create_vesting_contract_address(self.admin_address, 0, &[]);
-        self.txn_options
-            .submit_transaction(aptos_stdlib::vesting_distribute(vesting_contract_address))
-            .await
-            .map(|inner| inner.into())
+        profile_or_submit(
+            aptos_stdlib::vesting_distribute(vesting_contract_address),
+            &self.txn_options,
+        )
+        .await
     }
 }
 
@@ -604,10 +605,11 @@ impl CliCommand<TransactionSummary> for UnlockVestedCoins {
 
     async fn execute(mut self) -> CliTypedResult<TransactionSummary> {
         let vesting_contract_address = create_vesting_contract_address(self.admin_address, 0, &[]);
-        self.txn_options
-            .submit_transaction(aptos_stdlib::vesting_vest(vesting_contract_address))
-            .await
-            .map(|inner| inner.into())
+        profile_or_submit(
+            aptos_stdlib::vesting_vest(vesting_contract_address),
+            &self.txn_options,
+        )
+        .await
     }
 }
 
@@ -657,12 +659,13 @@ impl CliCommand<TransactionSummary> for RequestCommission {
         } else {
             self.owner_address
         };
-        self.txn_options
-            .submit_transaction(aptos_stdlib::staking_contract_request_commission(
+        profile_or_submit(
+            aptos_stdlib::staking_contract_request_commission(
                 staker_address,
                 self.operator_address,
-            ))
-            .await
-            .map(|inner| inner.into())
+            ),
+            &self.txn_options,
+        )
+        .await
     }
 }
@@ -200,6 +200,23 @@ impl CliTestFramework {
         Ok(index)
     }
 
+    /// Simulates creating `account`, sent by the account at `sender_index`
+    pub async fn simulate_create_account(
+        &self,
+        sender_index: usize,
+        account: AccountAddress,
+    ) -> CliTypedResult<TransactionSummary> {
+        CreateAccount {
+            txn_options: TransactionOptions {
+                simulate: true,
+                ..self.transaction_options(sender_index, None)
+            },
+            account,
+        }
+        .execute()
+        .await
+    }
+
     pub async fn create_cli_account_from_faucet(
         &mut self,
         private_key: Ed25519PrivateKey,
@@ -0,0 +1,385 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+pub mod offline;
+
+use self::offline::{MultiKeySet, OfflineTransaction, TransactionDecode};
+use crate::{
+    common::{
+        types::{
+            load_account_arg, CliCommand, CliError, CliResult, CliTypedResult, EncodingOptions,
+            EntryFunctionArguments, PrivateKeyInputOptions, ProfileOptions, PromptOptions,
+            RestOptions, ScriptFunctionArguments, TransactionOptions, TransactionSummary,
+        },
+        utils::{check_if_file_exists, prompt_yes_with_override, read_from_file, write_to_file},
+    },
+    governance::CompileScriptFunction,
+};
+use aptos_crypto::{
+    ed25519::Ed25519PublicKey, multi_ed25519::MultiEd25519PublicKey, ValidCryptoMaterialStringExt,
+};
+use aptos_types::{
+    account_address::AccountAddress,
+    chain_id::ChainId,
+    transaction::{
+        authenticator::{AnyPublicKey, AuthenticationKey, MultiKey},
+        TransactionPayload,
+    },
+};
+use async_trait::async_trait;
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+/// Tool for building, signing and submitting transactions in separate steps
+///
+/// This allows signing transactions on a machine without network access: the transaction is
+/// built into a file on an online machine, the file is signed offline by every signer, and the
+/// signed file is then submitted from an online machine.
+#[derive(Subcommand)]
+pub enum TransactionTool {
+    Build(BuildTransaction),
+    BuildScript(BuildScriptTransaction),
+    Decode(DecodeTransaction),
+    Sign(SignTransaction),
+    Submit(SubmitTransaction),
+}
+
+impl TransactionTool {
+    pub async fn execute(self) -> CliResult {
+        use TransactionTool::*;
+        match self {
+            Build(tool) => tool.execute_serialized().await,
+            BuildScript(tool) => tool.execute_serialized().await,
+            Decode(tool) => tool.execute_serialized().await,
+            Sign(tool) => tool.execute_serialized().await,
+            Submit(tool) => tool.execute_serialized().await,
+        }
+    }
+}
+
+/// Arguments shared by the commands that build an unsigned transaction into a file
+///
+/// The sequence number and chain id are fetched from the network unless they are given, so with
+/// them and `--gas-unit-price` the transaction can also be built without network access.
+#[derive(Parser)]
+pub struct OfflineTransactionArgs {
+    /// Sequence number of the transaction
+    ///
+    /// Defaults to the current sequence number of the sender
+    #[clap(long)]
+    pub(crate) sequence_number: Option<u64>,
+
+    /// Chain id of the network the transaction is for
+    ///
+    /// Defaults to the chain id of the network
+    #[clap(long)]
+    pub(crate) chain_id: Option<ChainId>,
+
+    /// Addresses of the secondary signers, for a multi-agent transaction
+    #[clap(long, num_args = 0.., value_parser = load_account_arg)]
+    pub(crate) secondary_signer_addresses: Vec<AccountAddress>,
+
+    /// Address of the account paying for gas, if it isn't the sender
+    #[clap(long, value_parser = load_account_arg)]
+    pub(crate) fee_payer_address: Option<AccountAddress>,
+
+    /// File to write the unsigned transaction to
+    #[clap(long, value_parser)]
+    pub(crate) output_file: PathBuf,
+}
+
+impl OfflineTransactionArgs {
+    /// Builds the transaction with `payload` and writes it to the output file
+    ///
+    /// The sender, gas and expiration come from `txn_options`. Leave enough time in
+    /// `--expiration-secs` for every signer to sign the transaction, it can't be submitted after
+    /// it expires.
+    async fn build(
+        self,
+        payload: TransactionPayload,
+        txn_options: &TransactionOptions,
+    ) -> CliTypedResult<TransactionDecode> {
+        check_if_file_exists(&self.output_file, txn_options.prompt_options)?;
+        let raw_txn = txn_options
+            .build_raw_transaction(payload, self.sequence_number, self.chain_id)
+            .await?;
+        let txn = OfflineTransaction::new(
+            raw_txn,
+            self.secondary_signer_addresses,
+            self.fee_payer_address,
+        );
+        write_to_file(&self.output_file, "OfflineTransaction", &txn.to_bytes()?)?;
+        Ok(txn.decode())
+    }
+}
+
+/// Build an unsigned entry function transaction into a file
+#[derive(Parser)]
+pub struct BuildTransaction {
+    #[clap(flatten)]
+    pub(crate) entry_function_args: EntryFunctionArguments,
+    #[clap(flatten)]
+    pub(crate) offline_args: OfflineTransactionArgs,
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionDecode> for BuildTransaction {
+    fn command_name(&self) -> &'static str {
+        "BuildTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionDecode> {
+        let payload = TransactionPayload::EntryFunction(self.entry_function_args.try_into()?);
+        self.offline_args.build(payload, &self.txn_options).await
+    }
+}
+
+/// Build an unsigned script transaction into a file
+#[derive(Parser)]
+pub struct BuildScriptTransaction {
+    #[clap(flatten)]
+    pub(crate) compile_script_args: CompileScriptFunction,
+    #[clap(flatten)]
+    pub(crate) script_function_args: ScriptFunctionArguments,
+    #[clap(flatten)]
+    pub(crate) offline_args: OfflineTransactionArgs,
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionDecode> for BuildScriptTransaction {
+    fn command_name(&self) -> &'static str {
+        "BuildScriptTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionDecode> {
+        let (bytecode, _script_hash) = self
+            .compile_script_args
+            .compile("BuildScriptTransaction", self.txn_options.prompt_options)?;
+        let payload = self.script_function_args.create_script_payload(bytecode)?;
+        self.offline_args.build(payload, &self.txn_options).await
+    }
+}
+
+/// Show the contents of a transaction file, and which signatures it is still missing
+#[derive(Parser)]
+pub struct DecodeTransaction {
+    /// File of the transaction, as written by `aptos transaction build`
+    #[clap(long, value_parser)]
+    pub(crate) transaction_file: PathBuf,
+}
+
+#[async_trait]
+impl CliCommand<TransactionDecode> for DecodeTransaction {
+    fn command_name(&self) -> &'static str {
+        "DecodeTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionDecode> {
+        let txn = OfflineTransaction::from_bytes(&read_from_file(&self.transaction_file)?)?;
+        Ok(txn.decode())
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum MultiKeyScheme {
+    /// A K-of-N Ed25519 account
+    MultiEd25519,
+    /// A K-of-N account with keys of any type
+    MultiKey,
+}
+
+/// Sign a transaction file, without network access
+///
+/// The signature is added to the file, so it can be passed on to the next signer. For an
+/// account with several keys (K-of-N), each key holder signs the file in turn, giving the
+/// public keys of the account with `--multi-key-public-keys`.
+#[derive(Parser)]
+pub struct SignTransaction {
+    /// File of the transaction, as written by `aptos transaction build`
+    #[clap(long, value_parser)]
+    pub(crate) transaction_file: PathBuf,
+
+    /// File to write the signed transaction to
+    ///
+    /// Defaults to updating `--transaction-file`
+    #[clap(long, value_parser)]
+    pub(crate) output_file: Option<PathBuf>,
+
+    /// Account to sign the transaction for
+    ///
+    /// Defaults to the account of the key (or of the multi-key public keys)
+    #[clap(long, value_parser = load_account_arg)]
+    pub(crate) signer_account: Option<AccountAddress>,
+
+    /// All public keys of a K-of-N signer account, in order, when signing with one of them
+    #[clap(long, num_args = 1..)]
+    pub(crate) multi_key_public_keys: Vec<String>,
+
+    /// Number of signatures a K-of-N signer account requires, i.e. K
+    #[clap(long, requires = "multi_key_public_keys")]
+    pub(crate) signatures_required: Option<u8>,
+
+    /// Type of the K-of-N signer account
+    #[clap(long, value_enum, default_value = "multi-ed25519")]
+    pub(crate) multi_key_scheme: MultiKeyScheme,
+
+    #[clap(flatten)]
+    pub(crate) private_key_options: PrivateKeyInputOptions,
+    #[clap(flatten)]
+    pub(crate) encoding_options: EncodingOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) prompt_options: PromptOptions,
+}
+
+impl SignTransaction {
+    fn multi_key_set(&self) -> CliTypedResult<Option<MultiKeySet>> {
+        if self.multi_key_public_keys.is_empty() {
+            return Ok(None);
+        }
+        let signatures_required = self.signatures_required.ok_or_else(|| {
+            CliError::CommandArgumentError(
+                "--signatures-required is required with --multi-key-public-keys".to_string(),
+            )
+        })?;
+        let public_keys = self
+            .multi_key_public_keys
+            .iter()
+            .map(|key| {
+                Ed25519PublicKey::from_encoded_string(key).map_err(|err| {
+                    CliError::UnableToParse("multi-key-public-keys", err.to_string())
+                })
+            })
+            .collect::<CliTypedResult<Vec<_>>>()?;
+
+        Ok(Some(match self.multi_key_scheme {
+            MultiKeyScheme::MultiEd25519 => MultiKeySet::MultiEd25519(
+                MultiEd25519PublicKey::new(public_keys, signatures_required)
+                    .map_err(|err| CliError::CommandArgumentError(err.to_string()))?,
+            ),
+            MultiKeyScheme::MultiKey => MultiKeySet::MultiKey(
+                MultiKey::new(
+                    public_keys.into_iter().map(AnyPublicKey::ed25519).collect(),
+                    signatures_required,
+                )
+                .map_err(|err| CliError::CommandArgumentError(err.to_string()))?,
+            ),
+        }))
+    }
+}
+
+#[async_trait]
+impl CliCommand<TransactionDecode> for SignTransaction {
+    fn command_name(&self) -> &'static str {
+        "SignTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionDecode> {
+        let mut txn = OfflineTransaction::from_bytes(&read_from_file(&self.transaction_file)?)?;
+        let encoding = self.encoding_options.encoding;
+
+        let signer = match self.multi_key_set()? {
+            Some(key_set) => {
+                let private_key = self
+                    .private_key_options
+                    .extract_private_key(encoding, &self.profile_options)?;
+                let signer = self.signer_account.unwrap_or_else(|| match &key_set {
+                    MultiKeySet::MultiEd25519(public_key) => {
+                        AuthenticationKey::multi_ed25519(public_key).account_address()
+                    },
+                    MultiKeySet::MultiKey(public_keys) => {
+                        AuthenticationKey::multi_key(public_keys.clone()).account_address()
+                    },
+                });
+                confirm_signing(&txn, signer, self.prompt_options)?;
+                txn.sign_multi_key(signer, key_set, &private_key)?;
+                signer
+            },
+            None => {
+                let (private_key, signer) =
+                    self.private_key_options.extract_private_key_and_address(
+                        encoding,
+                        &self.profile_options,
+                        self.signer_account,
+                    )?;
+                confirm_signing(&txn, signer, self.prompt_options)?;
+                txn.sign(signer, &private_key)?;
+                signer
+            },
+        };
+
+        let output_file = self.output_file.unwrap_or(self.transaction_file);
+        write_to_file(&output_file, "OfflineTransaction", &txn.to_bytes()?)?;
+        if txn.missing_signers().is_empty() {
+            eprintln!(
+                "Signed for {}, the transaction is ready to be submitted with `aptos transaction submit`",
+                signer
+            );
+        }
+        Ok(txn.decode())
+    }
+}
+
+/// Submit a fully signed transaction file
+#[derive(Parser)]
+pub struct SubmitTransaction {
+    /// File of the signed transaction, as written by `aptos transaction sign`
+    #[clap(long, value_parser)]
+    pub(crate) transaction_file: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+    #[clap(flatten)]
+    pub(crate) prompt_options: PromptOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for SubmitTransaction {
+    fn command_name(&self) -> &'static str {
+        "SubmitTransaction"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let txn = OfflineTransaction::from_bytes(&read_from_file(&self.transaction_file)?)?;
+        prompt_yes_with_override(
+            &format!(
+                "{}\nDo you want to submit this transaction?",
+                serde_json::to_string_pretty(&txn.decode())
+                    .map_err(|err| CliError::UnexpectedError(err.to_string()))?
+            ),
+            self.prompt_options,
+        )?;
+
+        let signed_txn = txn.into_signed_transaction()?;
+        let client = self.rest_options.client(&self.profile_options)?;
+        let response = client
+            .submit_and_wait(&signed_txn)
+            .await
+            .map_err(|err| CliError::ApiError(err.to_string()))?;
+        Ok(TransactionSummary::from(response.into_inner()))
+    }
+}
+
+/// Shows the transaction before it's signed, and asks to go ahead.
+fn confirm_signing(
+    txn: &OfflineTransaction,
+    signer: AccountAddress,
+    prompt_options: PromptOptions,
+) -> CliTypedResult<()> {
+    prompt_yes_with_override(
+        &format!(
+            "{}\nDo you want to sign this transaction for {}?",
+            serde_json::to_string_pretty(&txn.decode())
+                .map_err(|err| CliError::UnexpectedError(err.to_string()))?,
+            signer
+        ),
+        prompt_options,
+    )
+}
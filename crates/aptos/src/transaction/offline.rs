@@ -0,0 +1,622 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::types::{CliError, CliTypedResult};
+use aptos_crypto::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey, Ed25519Signature},
+    multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    HashValue, PrivateKey, SigningKey,
+};
+use aptos_types::{
+    account_address::AccountAddress,
+    chain_id::ChainId,
+    transaction::{
+        authenticator::{
+            AccountAuthenticator, AnyPublicKey, AnySignature, MultiKey, MultiKeyAuthenticator,
+        },
+        RawTransaction, RawTransactionWithData, SignedTransaction, TransactionPayload,
+    },
+};
+use chrono::{TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A transaction which is signed offline.
+///
+/// It is built on an online machine, passed (BCS encoded, as a file) to each machine holding
+/// a key it has to be signed with, and finally submitted from an online machine once every
+/// signer has signed it.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct OfflineTransaction {
+    pub raw_txn: RawTransaction,
+    /// Secondary signers of a multi-agent transaction
+    pub secondary_signers: Vec<AccountAddress>,
+    /// The account paying for gas, if it isn't the sender
+    pub fee_payer: Option<AccountAddress>,
+    /// The signatures collected so far, by the account they were made for
+    pub signatures: BTreeMap<AccountAddress, AccountSignatures>,
+}
+
+/// Signatures made for a single account.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum AccountSignatures {
+    Ed25519 {
+        public_key: Ed25519PublicKey,
+        signature: Ed25519Signature,
+    },
+    /// A K-of-N account, signed by some of its keys, by their index
+    MultiEd25519 {
+        public_key: MultiEd25519PublicKey,
+        signatures: BTreeMap<u8, Ed25519Signature>,
+    },
+    /// A K-of-N account of any keys, signed by some of its (Ed25519) keys, by their index
+    MultiKey {
+        public_keys: MultiKey,
+        signatures: BTreeMap<u8, Ed25519Signature>,
+    },
+}
+
+/// The keys of an account which needs signatures from several keys.
+#[derive(Clone, Debug)]
+pub enum MultiKeySet {
+    MultiEd25519(MultiEd25519PublicKey),
+    MultiKey(MultiKey),
+}
+
+impl MultiKeySet {
+    fn index_of(&self, public_key: &Ed25519PublicKey) -> Option<u8> {
+        let index = match self {
+            MultiKeySet::MultiEd25519(keys) => {
+                keys.public_keys().iter().position(|key| key == public_key)
+            },
+            MultiKeySet::MultiKey(keys) => keys.public_keys().iter().position(
+                |key| matches!(key, AnyPublicKey::Ed25519 { public_key: key } if key == public_key),
+            ),
+        };
+        index.map(|index| index as u8)
+    }
+}
+
+impl AccountSignatures {
+    fn signatures_required(&self) -> usize {
+        match self {
+            AccountSignatures::Ed25519 { .. } => 1,
+            AccountSignatures::MultiEd25519 { public_key, .. } => *public_key.threshold() as usize,
+            AccountSignatures::MultiKey { public_keys, .. } => {
+                public_keys.signatures_required() as usize
+            },
+        }
+    }
+
+    fn signature_count(&self) -> usize {
+        match self {
+            AccountSignatures::Ed25519 { .. } => 1,
+            AccountSignatures::MultiEd25519 { signatures, .. }
+            | AccountSignatures::MultiKey { signatures, .. } => signatures.len(),
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.signature_count() >= self.signatures_required()
+    }
+
+    fn authenticator(&self) -> CliTypedResult<AccountAuthenticator> {
+        Ok(match self {
+            AccountSignatures::Ed25519 {
+                public_key,
+                signature,
+            } => AccountAuthenticator::ed25519(public_key.clone(), signature.clone()),
+            AccountSignatures::MultiEd25519 {
+                public_key,
+                signatures,
+            } => AccountAuthenticator::multi_ed25519(
+                public_key.clone(),
+                MultiEd25519Signature::new(
+                    signatures
+                        .iter()
+                        .map(|(index, signature)| (signature.clone(), *index))
+                        .collect(),
+                )
+                .map_err(|err| CliError::UnexpectedError(err.to_string()))?,
+            ),
+            AccountSignatures::MultiKey {
+                public_keys,
+                signatures,
+            } => AccountAuthenticator::multi_key(MultiKeyAuthenticator::new(
+                public_keys.clone(),
+                signatures
+                    .iter()
+                    .map(|(index, signature)| (*index, AnySignature::ed25519(signature.clone())))
+                    .collect(),
+            )?),
+        })
+    }
+}
+
+impl OfflineTransaction {
+    pub fn new(
+        raw_txn: RawTransaction,
+        secondary_signers: Vec<AccountAddress>,
+        fee_payer: Option<AccountAddress>,
+    ) -> Self {
+        Self {
+            raw_txn,
+            secondary_signers,
+            fee_payer,
+            signatures: BTreeMap::new(),
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> CliTypedResult<Self> {
+        bcs::from_bytes(bytes).map_err(|err| CliError::BCS("OfflineTransaction", err))
+    }
+
+    pub fn to_bytes(&self) -> CliTypedResult<Vec<u8>> {
+        bcs::to_bytes(self).map_err(|err| CliError::BCS("OfflineTransaction", err))
+    }
+
+    /// Every account which has to sign the transaction: the sender, then the secondary signers,
+    /// then the fee payer.
+    pub fn signers(&self) -> Vec<AccountAddress> {
+        let mut signers = vec![self.raw_txn.sender()];
+        signers.extend(self.secondary_signers.iter().copied());
+        signers.extend(self.fee_payer);
+        signers
+    }
+
+    /// Signs the transaction for `account`, whose authentication key is `private_key`'s.
+    pub fn sign(
+        &mut self,
+        account: AccountAddress,
+        private_key: &Ed25519PrivateKey,
+    ) -> CliTypedResult<()> {
+        self.check_signer(account)?;
+        let signature = self.sign_message(private_key)?;
+        self.signatures.insert(account, AccountSignatures::Ed25519 {
+            public_key: private_key.public_key(),
+            signature,
+        });
+        Ok(())
+    }
+
+    /// Adds the signature of one of the keys of a K-of-N `account` to the transaction.
+    pub fn sign_multi_key(
+        &mut self,
+        account: AccountAddress,
+        keys: MultiKeySet,
+        private_key: &Ed25519PrivateKey,
+    ) -> CliTypedResult<()> {
+        self.check_signer(account)?;
+        let index = keys.index_of(&private_key.public_key()).ok_or_else(|| {
+            CliError::CommandArgumentError(
+                "The private key is not one of the keys of the account".to_string(),
+            )
+        })?;
+        let signature = self.sign_message(private_key)?;
+
+        let entry = self
+            .signatures
+            .entry(account)
+            .or_insert_with(|| match &keys {
+                MultiKeySet::MultiEd25519(public_key) => AccountSignatures::MultiEd25519 {
+                    public_key: public_key.clone(),
+                    signatures: BTreeMap::new(),
+                },
+                MultiKeySet::MultiKey(public_keys) => AccountSignatures::MultiKey {
+                    public_keys: public_keys.clone(),
+                    signatures: BTreeMap::new(),
+                },
+            });
+        match (entry, &keys) {
+            (
+                AccountSignatures::MultiEd25519 {
+                    public_key,
+                    signatures,
+                },
+                MultiKeySet::MultiEd25519(keys),
+            ) if public_key == keys => {
+                signatures.insert(index, signature);
+            },
+            (
+                AccountSignatures::MultiKey {
+                    public_keys,
+                    signatures,
+                },
+                MultiKeySet::MultiKey(keys),
+            ) if public_keys == keys => {
+                signatures.insert(index, signature);
+            },
+            _ => {
+                return Err(CliError::CommandArgumentError(format!(
+                    "Account {} was already signed for with different keys",
+                    account
+                )))
+            },
+        }
+        Ok(())
+    }
+
+    /// The accounts still missing (enough) signatures.
+    pub fn missing_signers(&self) -> Vec<AccountAddress> {
+        self.signers()
+            .into_iter()
+            .filter(|signer| {
+                !self
+                    .signatures
+                    .get(signer)
+                    .map_or(false, |signatures| signatures.is_complete())
+            })
+            .collect()
+    }
+
+    /// Builds the transaction to submit, once every signer has signed it.
+    pub fn into_signed_transaction(self) -> CliTypedResult<SignedTransaction> {
+        let missing_signers = self.missing_signers();
+        if !missing_signers.is_empty() {
+            return Err(CliError::CommandArgumentError(format!(
+                "The transaction is missing signatures for {:?}",
+                missing_signers
+            )));
+        }
+
+        let authenticator = |account: &AccountAddress| self.signatures[account].authenticator();
+        let sender = authenticator(&self.raw_txn.sender())?;
+        let secondary_signers = self
+            .secondary_signers
+            .iter()
+            .map(authenticator)
+            .collect::<CliTypedResult<Vec<_>>>()?;
+        Ok(match self.fee_payer {
+            Some(fee_payer) => SignedTransaction::new_fee_payer(
+                self.raw_txn.clone(),
+                sender,
+                self.secondary_signers.clone(),
+                secondary_signers,
+                fee_payer,
+                authenticator(&fee_payer)?,
+            ),
+            None if !self.secondary_signers.is_empty() => SignedTransaction::new_multi_agent(
+                self.raw_txn.clone(),
+                sender,
+                self.secondary_signers.clone(),
+                secondary_signers,
+            ),
+            None => match sender {
+                AccountAuthenticator::Ed25519 {
+                    public_key,
+                    signature,
+                } => SignedTransaction::new(self.raw_txn.clone(), public_key, signature),
+                AccountAuthenticator::MultiEd25519 {
+                    public_key,
+                    signature,
+                } => SignedTransaction::new_multisig(self.raw_txn.clone(), public_key, signature),
+                sender => SignedTransaction::new_single_sender(self.raw_txn.clone(), sender),
+            },
+        })
+    }
+
+    /// A human-readable description of the transaction and its signatures.
+    pub fn decode(&self) -> TransactionDecode {
+        let raw_txn = &self.raw_txn;
+        let signatures = self
+            .signers()
+            .into_iter()
+            .map(|account| {
+                let status = match self.signatures.get(&account) {
+                    None => "missing".to_string(),
+                    Some(AccountSignatures::Ed25519 { .. }) => "signed".to_string(),
+                    Some(signatures) => format!(
+                        "{} of {} signatures",
+                        signatures.signature_count(),
+                        signatures.signatures_required()
+                    ),
+                };
+                SignatureStatus { account, status }
+            })
+            .collect();
+        let transaction_hash = if self.missing_signers().is_empty() {
+            self.clone()
+                .into_signed_transaction()
+                .ok()
+                .map(|txn| txn.committed_hash())
+        } else {
+            None
+        };
+
+        TransactionDecode {
+            sender: raw_txn.sender(),
+            sequence_number: raw_txn.sequence_number(),
+            payload: PayloadDecode::new(raw_txn.payload()),
+            max_gas_amount: raw_txn.max_gas_amount(),
+            gas_unit_price: raw_txn.gas_unit_price(),
+            expiration_timestamp_secs: raw_txn.expiration_timestamp_secs(),
+            expiration_time: Utc
+                .timestamp_opt(raw_txn.expiration_timestamp_secs() as i64, 0)
+                .single()
+                .map(|time| time.to_rfc3339())
+                .unwrap_or_default(),
+            chain_id: raw_txn.chain_id(),
+            secondary_signers: self.secondary_signers.clone(),
+            fee_payer: self.fee_payer,
+            signatures,
+            transaction_hash,
+        }
+    }
+
+    fn check_signer(&self, account: AccountAddress) -> CliTypedResult<()> {
+        if self.signers().contains(&account) {
+            Ok(())
+        } else {
+            Err(CliError::CommandArgumentError(format!(
+                "Account {} is not a signer of the transaction",
+                account
+            )))
+        }
+    }
+
+    /// Multi-agent and fee payer transactions are signed together with the other signers'
+    /// addresses; every signer signs the same message.
+    fn sign_message(&self, private_key: &Ed25519PrivateKey) -> CliTypedResult<Ed25519Signature> {
+        let raw_txn = self.raw_txn.clone();
+        let result = match self.fee_payer {
+            Some(fee_payer) => private_key.sign(&RawTransactionWithData::new_fee_payer(
+                raw_txn,
+                self.secondary_signers.clone(),
+                fee_payer,
+            )),
+            None if !self.secondary_signers.is_empty() => private_key.sign(
+                &RawTransactionWithData::new_multi_agent(raw_txn, self.secondary_signers.clone()),
+            ),
+            None => private_key.sign(&raw_txn),
+        };
+        result.map_err(|err| CliError::UnexpectedError(format!("Failed to sign: {}", err)))
+    }
+}
+
+/// A human-readable description of an offline transaction.
+#[derive(Debug, Serialize)]
+pub struct TransactionDecode {
+    pub sender: AccountAddress,
+    pub sequence_number: u64,
+    pub payload: PayloadDecode,
+    pub max_gas_amount: u64,
+    pub gas_unit_price: u64,
+    pub expiration_timestamp_secs: u64,
+    pub expiration_time: String,
+    pub chain_id: ChainId,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub secondary_signers: Vec<AccountAddress>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee_payer: Option<AccountAddress>,
+    pub signatures: Vec<SignatureStatus>,
+    /// The hash the transaction will be committed with, once it's fully signed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_hash: Option<HashValue>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SignatureStatus {
+    pub account: AccountAddress,
+    pub status: String,
+}
+
+/// The payload of a transaction. Arguments are shown BCS encoded, as their types are only
+/// known on chain.
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PayloadDecode {
+    EntryFunction {
+        function: String,
+        type_arguments: Vec<String>,
+        arguments: Vec<String>,
+    },
+    Script {
+        code_hash: HashValue,
+        type_arguments: Vec<String>,
+        arguments: Vec<String>,
+    },
+    ModuleBundle {
+        modules: usize,
+    },
+    Multisig {
+        multisig_address: AccountAddress,
+        function: Option<String>,
+        type_arguments: Vec<String>,
+        arguments: Vec<String>,
+    },
+}
+
+impl PayloadDecode {
+    fn new(payload: &TransactionPayload) -> Self {
+        match payload {
+            TransactionPayload::EntryFunction(entry_function) => PayloadDecode::EntryFunction {
+                function: format!(
+                    "{}::{}",
+                    entry_function.module().short_str_lossless(),
+                    entry_function.function()
+                ),
+                type_arguments: entry_function
+                    .ty_args()
+                    .iter()
+                    .map(|ty| ty.to_string())
+                    .collect(),
+                arguments: entry_function.args().iter().map(hex_argument).collect(),
+            },
+            TransactionPayload::Script(script) => PayloadDecode::Script {
+                code_hash: HashValue::sha3_256_of(script.code()),
+                type_arguments: script.ty_args().iter().map(|ty| ty.to_string()).collect(),
+                arguments: script
+                    .args()
+                    .iter()
+                    .map(|arg| format!("{:?}", arg))
+                    .collect(),
+            },
+            TransactionPayload::ModuleBundle(modules) => PayloadDecode::ModuleBundle {
+                modules: modules.iter().count(),
+            },
+            TransactionPayload::Multisig(multisig) => {
+                let entry_function = multisig.transaction_payload.as_ref().map(|payload| {
+                    let aptos_types::transaction::MultisigTransactionPayload::EntryFunction(
+                        entry_function,
+                    ) = payload;
+                    entry_function
+                });
+                PayloadDecode::Multisig {
+                    multisig_address: multisig.multisig_address,
+                    function: entry_function.map(|entry_function| {
+                        format!(
+                            "{}::{}",
+                            entry_function.module().short_str_lossless(),
+                            entry_function.function()
+                        )
+                    }),
+                    type_arguments: entry_function
+                        .map(|entry_function| {
+                            entry_function
+                                .ty_args()
+                                .iter()
+                                .map(|ty| ty.to_string())
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    arguments: entry_function
+                        .map(|entry_function| {
+                            entry_function.args().iter().map(hex_argument).collect()
+                        })
+                        .unwrap_or_default(),
+                }
+            },
+        }
+    }
+}
+
+fn hex_argument(arg: &Vec<u8>) -> String {
+    format!("0x{}", hex::encode(arg))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_cached_packages::aptos_stdlib;
+    use aptos_keygen::KeyGen;
+    use aptos_types::transaction::{authenticator::AuthenticationKey, TransactionAuthenticator};
+
+    fn raw_txn(sender: AccountAddress) -> RawTransaction {
+        RawTransaction::new(
+            sender,
+            7,
+            aptos_stdlib::aptos_account_transfer(AccountAddress::ONE, 100),
+            2000,
+            100,
+            u64::MAX,
+            ChainId::test(),
+        )
+    }
+
+    fn generate_key() -> Ed25519PrivateKey {
+        KeyGen::from_os_rng().generate_ed25519_private_key()
+    }
+
+    #[test]
+    fn test_single_signer() {
+        let key = generate_key();
+        let sender = AuthenticationKey::ed25519(&key.public_key()).account_address();
+        let mut txn = OfflineTransaction::new(raw_txn(sender), vec![], None);
+        assert_eq!(txn.missing_signers(), vec![sender]);
+        assert!(txn.sign(AccountAddress::ONE, &key).is_err());
+
+        // The transaction survives the round trip through a file.
+        txn.sign(sender, &key).unwrap();
+        let txn = OfflineTransaction::from_bytes(&txn.to_bytes().unwrap()).unwrap();
+        assert!(txn.missing_signers().is_empty());
+        assert!(txn.decode().transaction_hash.is_some());
+
+        let signed_txn = txn.into_signed_transaction().unwrap();
+        assert!(signed_txn.verify_signature().is_ok());
+    }
+
+    #[test]
+    fn test_fee_payer_and_multi_agent() {
+        let sender_key = generate_key();
+        let secondary_key = generate_key();
+        let fee_payer_key = generate_key();
+        let address = |key: &Ed25519PrivateKey| {
+            AuthenticationKey::ed25519(&key.public_key()).account_address()
+        };
+
+        let mut txn = OfflineTransaction::new(
+            raw_txn(address(&sender_key)),
+            vec![address(&secondary_key)],
+            Some(address(&fee_payer_key)),
+        );
+        txn.sign(address(&sender_key), &sender_key).unwrap();
+        txn.sign(address(&fee_payer_key), &fee_payer_key).unwrap();
+        assert_eq!(txn.missing_signers(), vec![address(&secondary_key)]);
+        assert!(txn.clone().into_signed_transaction().is_err());
+
+        txn.sign(address(&secondary_key), &secondary_key).unwrap();
+        let signed_txn = txn.into_signed_transaction().unwrap();
+        assert!(matches!(
+            signed_txn.authenticator_ref(),
+            TransactionAuthenticator::FeePayer { .. }
+        ));
+        assert!(signed_txn.verify_signature().is_ok());
+    }
+
+    #[test]
+    fn test_multi_ed25519() {
+        let keys: Vec<_> = (0..3).map(|_| generate_key()).collect();
+        let public_key =
+            MultiEd25519PublicKey::new(keys.iter().map(|key| key.public_key()).collect(), 2)
+                .unwrap();
+        let sender = AuthenticationKey::multi_ed25519(&public_key).account_address();
+        let key_set = MultiKeySet::MultiEd25519(public_key);
+
+        let mut txn = OfflineTransaction::new(raw_txn(sender), vec![], None);
+        assert!(txn
+            .sign_multi_key(sender, key_set.clone(), &generate_key())
+            .is_err());
+        txn.sign_multi_key(sender, key_set.clone(), &keys[2])
+            .unwrap();
+        assert_eq!(txn.missing_signers(), vec![sender]);
+        assert_eq!(txn.decode().signatures[0].status, "1 of 2 signatures");
+
+        txn.sign_multi_key(sender, key_set, &keys[0]).unwrap();
+        let signed_txn = txn.into_signed_transaction().unwrap();
+        assert!(signed_txn.verify_signature().is_ok());
+    }
+
+    #[test]
+    fn test_multi_key() {
+        let keys: Vec<_> = (0..3).map(|_| generate_key()).collect();
+        let public_keys = MultiKey::new(
+            keys.iter()
+                .map(|key| AnyPublicKey::ed25519(key.public_key()))
+                .collect(),
+            2,
+        )
+        .unwrap();
+        let sender = AuthenticationKey::multi_key(public_keys.clone()).account_address();
+        let key_set = MultiKeySet::MultiKey(public_keys);
+
+        let mut txn = OfflineTransaction::new(raw_txn(sender), vec![], None);
+        assert!(txn
+            .sign_multi_key(sender, key_set.clone(), &generate_key())
+            .is_err());
+        txn.sign_multi_key(sender, key_set.clone(), &keys[1])
+            .unwrap();
+        assert_eq!(txn.missing_signers(), vec![sender]);
+        assert_eq!(txn.decode().signatures[0].status, "1 of 2 signatures");
+
+        txn.sign_multi_key(sender, key_set, &keys[2]).unwrap();
+        assert!(txn.missing_signers().is_empty());
+        let signed_txn = txn.into_signed_transaction().unwrap();
+        assert!(signed_txn.verify_signature().is_ok());
+        assert!(matches!(
+            signed_txn.authenticator(),
+            TransactionAuthenticator::SingleSender {
+                sender: AccountAuthenticator::MultiKey { .. }
+            }
+        ));
+    }
+}
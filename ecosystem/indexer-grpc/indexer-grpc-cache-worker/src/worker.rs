@@ -10,9 +10,7 @@ use aptos_indexer_grpc_utils::{
     cache_operator::CacheOperator,
     config::IndexerGrpcFileStoreConfig,
     create_grpc_client,
-    file_store_operator::{
-        FileStoreMetadata, FileStoreOperator, GcsFileStoreOperator, LocalFileStoreOperator,
-    },
+    file_store_operator::{FileStoreMetadata, FileStoreOperator},
     time_diff_since_pb_timestamp_in_secs,
     types::RedisUrl,
 };
@@ -99,19 +97,7 @@ impl Worker {
             let mut rpc_client = create_grpc_client(self.fullnode_grpc_address.clone()).await;
 
             // 1. Fetch metadata.
-            let file_store_operator: Box<dyn FileStoreOperator> = match &self.file_store {
-                IndexerGrpcFileStoreConfig::GcsFileStore(gcs_file_store) => {
-                    Box::new(GcsFileStoreOperator::new(
-                        gcs_file_store.gcs_file_store_bucket_name.clone(),
-                        gcs_file_store
-                            .gcs_file_store_service_account_key_path
-                            .clone(),
-                    ))
-                },
-                IndexerGrpcFileStoreConfig::LocalFileStore(local_file_store) => Box::new(
-                    LocalFileStoreOperator::new(local_file_store.local_file_store_path.clone()),
-                ),
-            };
+            let file_store_operator: Box<dyn FileStoreOperator> = self.file_store.create();
 
             file_store_operator.verify_storage_bucket_existence().await;
             let starting_version = file_store_operator
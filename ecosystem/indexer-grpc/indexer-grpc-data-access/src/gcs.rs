@@ -5,6 +5,7 @@ use crate::{
     get_transactions_file_name, FileMetadata, TransactionsFile,
 };
 use anyhow::Context;
+use aptos_indexer_grpc_utils::file_store_operator::StorageFormat;
 use aptos_protos::transaction::v1::Transaction;
 use google_cloud_storage::{
     client::{Client, ClientConfig},
@@ -25,6 +26,9 @@ const METADATA_FILE_MAX_STALENESS_IN_SECS: u64 = 30; // 30 seconds.
 #[serde(deny_unknown_fields)]
 pub struct GcsClientConfig {
     bucket_name: String,
+    // The format to look for the transactions files in first; files in any format can be read.
+    #[serde(default)]
+    storage_format: StorageFormat,
 }
 
 pub type GcsClient = GcsInternalClient<google_cloud_storage::client::Client>;
@@ -36,7 +40,7 @@ impl GcsClient {
             .await
             .context("Failed to create GCS client.")?;
         let client = Client::new(gcs_config);
-        GcsInternalClient::new_with_client(config.bucket_name, client).await
+        GcsInternalClient::new_with_client(config.bucket_name, config.storage_format, client).await
     }
 }
 
@@ -44,15 +48,21 @@ impl GcsClient {
 pub struct GcsInternalClient<T: GcsClientTrait> {
     // Bucket name.
     pub bucket_name: String,
+    pub storage_format: StorageFormat,
     latest_metadata: Arc<Mutex<FileMetadata>>,
     latest_metadata_timestamp: Arc<Mutex<Option<std::time::Instant>>>,
     pub gcs_client: T,
 }
 
 impl<T: GcsClientTrait + Sync + Send + Clone> GcsInternalClient<T> {
-    pub async fn new_with_client(bucket_name: String, gcs_client: T) -> anyhow::Result<Self> {
+    pub async fn new_with_client(
+        bucket_name: String,
+        storage_format: StorageFormat,
+        gcs_client: T,
+    ) -> anyhow::Result<Self> {
         let res = Self {
             bucket_name,
+            storage_format,
             latest_metadata: Arc::new(Mutex::new(FileMetadata::default())),
             latest_metadata_timestamp: Arc::new(Mutex::new(None)),
             gcs_client,
@@ -129,24 +139,33 @@ impl<T: GcsClientTrait + Sync + Send + Clone> StorageTransactionRead for GcsInte
         batch_starting_version: u64,
         _size_hint: Option<usize>,
     ) -> Result<StorageReadStatus, StorageReadError> {
-        let file_name = get_transactions_file_name(batch_starting_version);
-        let result = self
-            .gcs_client
-            .download_object(
-                &GetObjectRequest {
-                    bucket: self.bucket_name.clone(),
-                    object: file_name.clone(),
-                    ..Default::default()
+        // Files written before a storage format change are in the previous format.
+        let mut file = None;
+        for storage_format in self.storage_format.read_order() {
+            let file_name = get_transactions_file_name(batch_starting_version, storage_format);
+            let result = self
+                .gcs_client
+                .download_object(
+                    &GetObjectRequest {
+                        bucket: self.bucket_name.clone(),
+                        object: file_name.clone(),
+                        ..Default::default()
+                    },
+                    &Range::default(),
+                )
+                .await;
+            match result {
+                Err(Error::Response(e)) if e.code == 404 => continue,
+                Err(e) => Err(e)?,
+                Ok(bytes) => {
+                    file = Some(bytes);
+                    break;
                 },
-                &Range::default(),
-            )
-            .await;
-        let file = match result {
-            Err(Error::Response(e)) if e.code == 404 => {
-                return Ok(StorageReadStatus::NotAvailableYet)
-            },
-            Err(e) => Err(e)?,
-            _ => result?,
+            }
+        }
+        let file = match file {
+            Some(file) => file,
+            None => return Ok(StorageReadStatus::NotAvailableYet),
         };
         let transactions_file: TransactionsFile = TransactionsFile::from(file);
         let all_transactions: Vec<Transaction> = transactions_file.into();
@@ -272,9 +291,13 @@ mod tests {
             ],
             index: AtomicU64::new(0),
         };
-        let gcs_client = GcsInternalClient::new_with_client("test1".to_string(), mock_gcs_client)
-            .await
-            .unwrap();
+        let gcs_client = GcsInternalClient::new_with_client(
+            "test1".to_string(),
+            StorageFormat::default(),
+            mock_gcs_client,
+        )
+        .await
+        .unwrap();
 
         let get_transactions_resp = gcs_client.get_transactions(0, None).await.unwrap();
 
@@ -328,9 +351,13 @@ mod tests {
             ],
             index: AtomicU64::new(0),
         };
-        let gcs_client = GcsInternalClient::new_with_client("test2".to_string(), mock_gcs_client)
-            .await
-            .unwrap();
+        let gcs_client = GcsInternalClient::new_with_client(
+            "test2".to_string(),
+            StorageFormat::default(),
+            mock_gcs_client,
+        )
+        .await
+        .unwrap();
 
         let get_transactions_resp = gcs_client.get_transactions(500, None).await.unwrap();
         assert_eq!(
@@ -362,9 +389,13 @@ mod tests {
             }],
             index: AtomicU64::new(0),
         };
-        let gcs_client = GcsInternalClient::new_with_client("test3".to_string(), mock_gcs_client)
-            .await
-            .unwrap();
+        let gcs_client = GcsInternalClient::new_with_client(
+            "test3".to_string(),
+            StorageFormat::default(),
+            mock_gcs_client,
+        )
+        .await
+        .unwrap();
 
         let get_metadata_resp = gcs_client.get_metadata().await.unwrap();
 
@@ -1,5 +1,6 @@
 // Copyright © Aptos Foundation
 
+use aptos_indexer_grpc_utils::file_store_operator::{generate_blob_name, StorageFormat};
 use aptos_protos::transaction::v1::Transaction;
 use prost::Message;
 use serde::{Deserialize, Serialize};
@@ -59,7 +60,15 @@ struct TransactionsFile {
 
 impl From<Vec<u8>> for TransactionsFile {
     fn from(bytes: Vec<u8>) -> Self {
-        serde_json::from_slice(bytes.as_slice()).expect("Failed to deserialize Transactions file.")
+        // The file can be in any of the file store formats.
+        let file = aptos_indexer_grpc_utils::file_store_operator::TransactionsFile::from_bytes(
+            bytes.as_slice(),
+        )
+        .expect("Failed to deserialize Transactions file.");
+        Self {
+            transactions: file.transactions,
+            starting_version: file.starting_version,
+        }
     }
 }
 impl From<TransactionsFile> for Vec<Transaction> {
@@ -76,9 +85,9 @@ impl From<TransactionsFile> for Vec<Transaction> {
 }
 
 #[inline]
-fn get_transactions_file_name(version: u64) -> String {
+fn get_transactions_file_name(version: u64, storage_format: StorageFormat) -> String {
     // This assumes that the transactions are stored in file of 1000 versions.
-    format!("files/{}.json", version / 1000 * 1000)
+    generate_blob_name(version / 1000 * 1000, storage_format)
 }
 
 pub struct MockStorageClient {
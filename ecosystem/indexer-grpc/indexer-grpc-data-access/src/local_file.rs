@@ -4,6 +4,7 @@ use crate::{
     access_trait::{AccessMetadata, StorageReadError, StorageReadStatus, StorageTransactionRead},
     get_transactions_file_name, FileMetadata, TransactionsFile,
 };
+use aptos_indexer_grpc_utils::file_store_operator::StorageFormat;
 use aptos_protos::transaction::v1::Transaction;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -15,17 +16,22 @@ const LOCAL_FILE_STORAGE_NAME: &str = "Local File";
 pub struct LocalFileClientConfig {
     // The absolute path to the folder that contains the transactions files.
     path: String,
+    // The format to look for the transactions files in first; files in any format can be read.
+    #[serde(default)]
+    storage_format: StorageFormat,
 }
 
 #[derive(Clone)]
 pub struct LocalFileClient {
     pub file_path: PathBuf,
+    pub storage_format: StorageFormat,
 }
 
 impl LocalFileClient {
     pub fn new(config: LocalFileClientConfig) -> anyhow::Result<Self> {
         Ok(Self {
             file_path: PathBuf::from(config.path),
+            storage_format: config.storage_format,
         })
     }
 }
@@ -50,18 +56,20 @@ impl StorageTransactionRead for LocalFileClient {
         batch_starting_version: u64,
         _size_hint: Option<usize>,
     ) -> Result<StorageReadStatus, StorageReadError> {
-        let file_path = self
-            .file_path
-            .clone()
-            .join(get_transactions_file_name(batch_starting_version));
-        let file = match tokio::fs::read(file_path.clone()).await {
-            Ok(file) => file,
-            Err(e) => {
-                match e.kind() {
-                    std::io::ErrorKind::NotFound => {
-                        // The file is not found. This is not an error.
-                        return Ok(StorageReadStatus::NotFound);
-                    },
+        // Files written before a storage format change are in the previous format.
+        let mut file = None;
+        for storage_format in self.storage_format.read_order() {
+            let file_path = self.file_path.clone().join(get_transactions_file_name(
+                batch_starting_version,
+                storage_format,
+            ));
+            match tokio::fs::read(file_path.clone()).await {
+                Ok(bytes) => {
+                    file = Some(bytes);
+                    break;
+                },
+                Err(e) => match e.kind() {
+                    std::io::ErrorKind::NotFound => continue,
                     _ => {
                         return Err(StorageReadError::PermenantError(
                             LOCAL_FILE_STORAGE_NAME,
@@ -72,8 +80,13 @@ impl StorageTransactionRead for LocalFileClient {
                             ),
                         ));
                     },
-                }
-            },
+                },
+            }
+        }
+        let file = match file {
+            Some(file) => file,
+            // The file is not found. This is not an error.
+            None => return Ok(StorageReadStatus::NotFound),
         };
         let transactions_file = TransactionsFile::from(file);
         let all_transactions: Vec<Transaction> = transactions_file.into();
@@ -158,6 +171,7 @@ mod tests {
 
         let local_file_client = LocalFileClient::new(LocalFileClientConfig {
             path: dir.path().to_path_buf().to_str().unwrap().to_string(),
+            storage_format: StorageFormat::default(),
         })
         .unwrap();
         let transactions = local_file_client.get_transactions(0, None).await.unwrap();
@@ -200,6 +214,7 @@ mod tests {
 
         let local_file_client = LocalFileClient::new(LocalFileClientConfig {
             path: dir.path().to_path_buf().to_str().unwrap().to_string(),
+            storage_format: StorageFormat::default(),
         })
         .unwrap();
         let transactions = local_file_client.get_transactions(500, None).await.unwrap();
@@ -218,12 +233,50 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_local_file_read_compact_file_successful() {
+        // Create a temp file.
+        let dir = tempfile::tempdir().unwrap();
+        create_dir(dir.path().join("files")).unwrap();
+        let transactions_file_path = dir.path().join("files/1000.bin");
+        // Write the transactions in the compact format.
+        {
+            let transactions_file_obj = create_transactions_file(1000);
+            let bytes = aptos_indexer_grpc_utils::file_store_operator::TransactionsFile {
+                starting_version: transactions_file_obj.starting_version,
+                transactions: transactions_file_obj.transactions,
+            }
+            .to_bytes(StorageFormat::ZstdCompressedProto)
+            .unwrap();
+            File::create(&transactions_file_path)
+                .unwrap()
+                .write_all(&bytes)
+                .unwrap();
+        }
+
+        // The client looks for JSON files first, then falls back to the compact file.
+        let local_file_client = LocalFileClient::new(LocalFileClientConfig {
+            path: dir.path().to_path_buf().to_str().unwrap().to_string(),
+            storage_format: StorageFormat::JsonBase64,
+        })
+        .unwrap();
+        let transactions = local_file_client
+            .get_transactions(1000, None)
+            .await
+            .unwrap();
+        assert_eq!(
+            transactions,
+            StorageReadStatus::Ok(create_transactions(1000))
+        );
+    }
+
     #[tokio::test]
     async fn test_local_file_metadata_missing() {
         // Create a temp file.
         let dir = tempfile::tempdir().unwrap();
         let local_file_client = LocalFileClient::new(LocalFileClientConfig {
             path: dir.path().to_path_buf().to_str().unwrap().to_string(),
+            storage_format: StorageFormat::default(),
         })
         .unwrap();
         let access_metadata = local_file_client.get_metadata().await;
@@ -258,6 +311,7 @@ mod tests {
 
         let local_file_client = LocalFileClient::new(LocalFileClientConfig {
             path: dir.path().to_path_buf().to_str().unwrap().to_string(),
+            storage_format: StorageFormat::default(),
         })
         .unwrap();
         let transactions = local_file_client.get_transactions(0, None).await;
@@ -61,6 +61,7 @@ impl GrpcResponseDispatcher {
                             .map(|transactions| TransactionsResponse {
                                 transactions,
                                 chain_id: Some(metadata.chain_id),
+                                last_scanned_version: None,
                             })
                             .collect());
                     },
@@ -144,6 +145,7 @@ impl GrpcResponseDispatcher {
                     processed_responses.push(TransactionsResponse {
                         transactions: truncated_transactions,
                         chain_id: response.chain_id,
+                        last_scanned_version: None,
                     });
                     current_transaction_count += remaining_transaction_count;
                 } else {
@@ -15,7 +15,8 @@ use aptos_indexer_grpc_utils::{
     constants::{
         BLOB_STORAGE_SIZE, GRPC_AUTH_TOKEN_HEADER, GRPC_REQUEST_NAME_HEADER, MESSAGE_SIZE_LIMIT,
     },
-    file_store_operator::{FileStoreOperator, GcsFileStoreOperator, LocalFileStoreOperator},
+    file_store_operator::FileStoreOperator,
+    filter::TransactionFilters,
     time_diff_since_pb_timestamp_in_secs,
     types::RedisUrl,
     EncodedTransactionWithVersion,
@@ -109,8 +110,9 @@ impl RawData for RawDataServerWrapper {
     ///    1.2. If the data is not in cache, fetch the data from file store.
     ///    1.3. If the data is not in file store, stream connection will break.
     ///    1.4  If error happens, retry after a short sleep.
-    /// 2. Push data into channel to stream to the client.
-    ///    2.1. If the channel is full, do not fetch and retry after a short sleep.
+    /// 2. Drop the transactions that don't match the request filters, if any.
+    /// 3. Push data into channel to stream to the client.
+    ///    3.1. If the channel is full, do not fetch and retry after a short sleep.
     async fn get_transactions(
         &self,
         req: Request<GetTransactionsRequest>,
@@ -124,6 +126,15 @@ impl RawData for RawDataServerWrapper {
         let request = req.into_inner();
 
         let transactions_count = request.transactions_count;
+        let transaction_filters = match TransactionFilters::new(&request.transaction_filters) {
+            Ok(transaction_filters) => transaction_filters,
+            Err(e) => {
+                return Result::Err(Status::invalid_argument(format!(
+                    "Invalid transaction filters: {}",
+                    e
+                )));
+            },
+        };
 
         // Response channel to stream the data to the client.
         let (tx, rx) = channel(self.data_service_response_channel_size);
@@ -134,19 +145,7 @@ impl RawData for RawDataServerWrapper {
             },
         };
 
-        let file_store_operator: Box<dyn FileStoreOperator> = match &self.file_store_config {
-            IndexerGrpcFileStoreConfig::GcsFileStore(gcs_file_store) => {
-                Box::new(GcsFileStoreOperator::new(
-                    gcs_file_store.gcs_file_store_bucket_name.clone(),
-                    gcs_file_store
-                        .gcs_file_store_service_account_key_path
-                        .clone(),
-                ))
-            },
-            IndexerGrpcFileStoreConfig::LocalFileStore(local_file_store) => Box::new(
-                LocalFileStoreOperator::new(local_file_store.local_file_store_path.clone()),
-            ),
-        };
+        let file_store_operator: Box<dyn FileStoreOperator> = self.file_store_config.create();
 
         // Adds tracing context for the request.
         let serving_span = tracing::span!(
@@ -275,13 +274,25 @@ impl RawData for RawDataServerWrapper {
                             transactions_count = Some(count - transaction_data.len() as u64);
                         }
                     };
+                    let current_batch_size = transaction_data.as_slice().len();
+                    let end_of_batch_version = transaction_data.as_slice().last().unwrap().1;
+                    let transactions = decode_transactions(transaction_data);
+                    let data_latency_in_secs = transactions
+                        .last()
+                        .unwrap()
+                        .0
+                        .timestamp
+                        .as_ref()
+                        .map(time_diff_since_pb_timestamp_in_secs);
+                    // 2. Filter the data; the whole batch still counts as processed, even if nothing matches.
+                    let (transactions, encoded_sizes): (Vec<_>, Vec<_>) = transactions
+                        .into_iter()
+                        .filter(|(transaction, _)| transaction_filters.matches(transaction))
+                        .unzip();
                     // Note: this is not the actual bytes transferred to the client.
                     // This is the bytes consumed internally by the server
                     // and ready to be transferred to the client.
-                    let bytes_ready_to_transfer = transaction_data
-                        .iter()
-                        .map(|(encoded, _)| encoded.len())
-                        .sum::<usize>();
+                    let bytes_ready_to_transfer = encoded_sizes.iter().sum::<usize>();
                     BYTES_READY_TO_TRANSFER_FROM_SERVER
                         .with_label_values(&[
                             request_metadata.request_api_key_name.as_str(),
@@ -289,20 +300,14 @@ impl RawData for RawDataServerWrapper {
                             request_metadata.processor_name.as_str(),
                         ])
                         .inc_by(bytes_ready_to_transfer as u64);
-                    // 2. Push the data to the response channel, i.e. stream the data to the client.
-                    let current_batch_size = transaction_data.as_slice().len();
-                    let end_of_batch_version = transaction_data.as_slice().last().unwrap().1;
-                    let resp_items =
-                        get_transactions_responses_builder(transaction_data, chain_id as u32);
-                    let data_latency_in_secs = resp_items
-                        .last()
-                        .unwrap()
-                        .transactions
-                        .last()
-                        .unwrap()
-                        .timestamp
-                        .as_ref()
-                        .map(time_diff_since_pb_timestamp_in_secs);
+                    // 3. Push the data to the response channel, i.e. stream the data to the client.
+                    let last_scanned_version =
+                        (!transaction_filters.is_empty()).then_some(end_of_batch_version);
+                    let resp_items = get_transactions_responses_builder(
+                        transactions,
+                        chain_id as u32,
+                        last_scanned_version,
+                    );
 
                     match channel_send_multiple_with_timeout(resp_items, tx.clone()).await {
                         Ok(_) => {
@@ -355,7 +360,7 @@ impl RawData for RawDataServerWrapper {
                             break;
                         },
                     }
-                    // 3. Update the current version and record current tps.
+                    // 4. Update the current version and record current tps.
                     tps_calculator.tick_now(current_batch_size as u64);
                     current_version = end_of_batch_version + 1;
                     sample!(
@@ -392,27 +397,46 @@ impl RawData for RawDataServerWrapper {
     }
 }
 
+/// Decodes the base64 encoded protobuf transactions from the cache or file store, along with the
+/// size of their encoding.
+fn decode_transactions(data: Vec<EncodedTransactionWithVersion>) -> Vec<(Transaction, usize)> {
+    data.into_iter()
+        .map(|(encoded, _)| {
+            let decoded_transaction = base64::decode(&encoded).unwrap();
+            let transaction = Transaction::decode(&*decoded_transaction);
+            (transaction.unwrap(), encoded.len())
+        })
+        .collect()
+}
+
 /// Builds the response for the get transactions request. Partial batch is ok, i.e., a batch with transactions < 1000.
+/// For filtered streams, the last response carries the last version scanned; if no transaction
+/// of the batch matched the filters, a response without transactions is built to carry it.
 fn get_transactions_responses_builder(
-    data: Vec<EncodedTransactionWithVersion>,
+    transactions: Vec<Transaction>,
     chain_id: u32,
+    last_scanned_version: Option<u64>,
 ) -> Vec<TransactionsResponse> {
-    let transactions: Vec<Transaction> = data
-        .into_iter()
-        .map(|(encoded, _)| {
-            let decoded_transaction = base64::decode(encoded).unwrap();
-            let transaction = Transaction::decode(&*decoded_transaction);
-            transaction.unwrap()
-        })
-        .collect();
     let chunks = chunk_transactions(transactions, MESSAGE_SIZE_LIMIT);
-    chunks
+    let mut responses = chunks
         .into_iter()
         .map(|chunk| TransactionsResponse {
             chain_id: Some(chain_id as u64),
             transactions: chunk,
+            last_scanned_version: None,
         })
-        .collect::<Vec<TransactionsResponse>>()
+        .collect::<Vec<TransactionsResponse>>();
+    if last_scanned_version.is_some() {
+        match responses.last_mut() {
+            Some(response) => response.last_scanned_version = last_scanned_version,
+            None => responses.push(TransactionsResponse {
+                chain_id: Some(chain_id as u64),
+                transactions: vec![],
+                last_scanned_version,
+            }),
+        }
+    }
+    responses
 }
 
 /// Fetches data from cache or the file store. It returns the data if it is ready in the cache or file store.
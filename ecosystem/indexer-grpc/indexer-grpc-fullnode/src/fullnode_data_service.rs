@@ -37,6 +37,8 @@ impl FullnodeData for FullnodeDataService {
     /// There are 2 types of TransactionsResponse:
     /// Status - sends events back to the client, such as init stream and batch end
     /// Transaction - sends encoded transactions lightly wrapped
+    /// The stream is never filtered: it feeds the cache worker, which has to cache every
+    /// transaction. Transaction filters are applied by the `RawData` services instead.
     async fn get_transactions_from_node(
         &self,
         req: Request<GetTransactionsFromNodeRequest>,
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{stream_coordinator::IndexerStreamCoordinator, ServiceContext};
+use aptos_indexer_grpc_utils::filter::TransactionFilters;
 use aptos_logger::error;
 use aptos_protos::{
     indexer::v1::{raw_data_server::RawData, GetTransactionsRequest, TransactionsResponse},
@@ -44,6 +45,8 @@ impl RawData for LocalnetDataService {
         let output_batch_size = self.service_context.output_batch_size;
         let ledger_chain_id = context.chain_id().id();
         let transactions_count = r.transactions_count;
+        let transaction_filters = TransactionFilters::new(&r.transaction_filters)
+            .map_err(|e| Status::invalid_argument(format!("Invalid transaction filters: {}", e)))?;
         // Creates a channel to send the stream to the client
         let (tx, mut rx) = mpsc::channel(TRANSACTION_CHANNEL_SIZE);
         let (external_service_tx, external_service_rx) = mpsc::channel(TRANSACTION_CHANNEL_SIZE);
@@ -82,22 +85,36 @@ impl RawData for LocalnetDataService {
                     }
                 }
 
-                let response = response.map(|t| TransactionsResponse {
-                    chain_id: Some(ledger_chain_id as u64),
-                    transactions: match t.response.expect("Response must be set") {
-                        transactions_from_node_response::Response::Data(transaction_output) => {
-                            let mut transactions = transaction_output.transactions;
-                            let current_transactions_count = transactions.len() as u64;
-                            if let Some(count) = response_transactions_count.as_mut() {
-                                transactions =
-                                    transactions.into_iter().take(*count as usize).collect();
-                                *count = count.saturating_sub(current_transactions_count);
-                            }
-                            transactions
-                        },
-                        _ => panic!("Unexpected response type."),
+                let response = response.map(|t| match t.response.expect("Response must be set") {
+                    transactions_from_node_response::Response::Data(transaction_output) => {
+                        let mut transactions = transaction_output.transactions;
+                        let current_transactions_count = transactions.len() as u64;
+                        if let Some(count) = response_transactions_count.as_mut() {
+                            transactions = transactions.into_iter().take(*count as usize).collect();
+                            *count = count.saturating_sub(current_transactions_count);
+                        }
+                        // Filtered out transactions still count towards the requested count, and
+                        // the last version scanned is sent even if none of them matches.
+                        let last_scanned_version = if transaction_filters.is_empty() {
+                            None
+                        } else {
+                            transactions.last().map(|transaction| transaction.version)
+                        };
+                        TransactionsResponse {
+                            chain_id: Some(ledger_chain_id as u64),
+                            transactions: transaction_filters.filter(transactions),
+                            last_scanned_version,
+                        }
                     },
+                    _ => panic!("Unexpected response type."),
                 });
+                if matches!(
+                    &response,
+                    Ok(response)
+                        if response.transactions.is_empty() && response.last_scanned_version.is_none()
+                ) {
+                    continue;
+                }
                 match external_service_tx.send(response).await {
                     Ok(_) => {},
                     Err(e) => {
@@ -2,23 +2,31 @@
 
 use anyhow::{bail, Context, Result};
 use aptos_indexer_grpc_cache_worker::IndexerGrpcCacheWorkerConfig;
+use aptos_indexer_grpc_data_service::{IndexerGrpcDataServiceConfig, NonTlsConfig};
 use aptos_indexer_grpc_file_store::IndexerGrpcFileStoreWorkerConfig;
 use aptos_indexer_grpc_server_framework::{
     run_server_with_config, setup_logging, setup_panic_handler, GenericConfig, RunnableConfig,
 };
 use aptos_indexer_grpc_utils::{
     cache_operator::CacheOperator,
-    config::{IndexerGrpcFileStoreConfig, LocalFileStore},
+    config::{IndexerGrpcFileStoreConfig, LocalFileStore, S3FileStore},
     constants::BLOB_STORAGE_SIZE,
-    file_store_operator::{FileStoreOperator, LocalFileStoreOperator},
+    create_data_service_grpc_client,
+    file_store_operator::{FileStoreOperator, LocalFileStoreOperator, StorageFormat},
     types::RedisUrl,
 };
+use aptos_protos::{
+    indexer::v1::{GetTransactionsRequest, TransactionFilter},
+    transaction::v1::{transaction::TxnData, Transaction},
+};
 use aptos_transaction_emitter_lib::{emit_transactions, ClusterArgs, CoinSourceArgs, EmitArgs};
 use aptos_transaction_generator_lib::args::TransactionTypeArg;
 use aptos_types::chain_id::ChainId;
+use futures::StreamExt;
 use once_cell::sync::Lazy;
+use prost::Message;
 use regex::Regex;
-use std::{fs::File, io::Write, path::PathBuf, str::FromStr};
+use std::{fs::File, io::Write, net::SocketAddr, path::PathBuf, str::FromStr, time::Duration};
 use tempfile::TempDir;
 use tokio::task::JoinHandle;
 use tracing::info;
@@ -33,6 +41,11 @@ static REDIS_PRIMARY_URL: Lazy<RedisUrl> =
 
 static MINT_KEY_FILE_NAME: &str = "mint.key";
 
+// The MinIO instance from the indexer-grpc docker compose, with an empty bucket.
+static MINIO_ENDPOINT_URL: &str = "http://127.0.0.1:9000";
+static MINIO_BUCKET_NAME: &str = "indexer-grpc-file-store";
+static MINIO_CREDENTIALS: &str = "minioadmin";
+
 /// Get the name of docker containers that match the given regex
 /// This works around different docker compose v1 and v2 naming conventions
 fn get_container_by_name_regex(name_regex: Regex) -> Result<Vec<String>> {
@@ -230,6 +243,7 @@ async fn test_cold_start_cache_worker_progress() {
         fullnode_grpc_address: (*TESTNET_FULLNODE_GRPC_URL).clone(),
         file_store_config: IndexerGrpcFileStoreConfig::LocalFileStore(LocalFileStore {
             local_file_store_path: tmp_dir.path().to_path_buf(),
+            storage_format: StorageFormat::default(),
         }),
         redis_main_instance_address: (*REDIS_PRIMARY_URL).clone(),
     };
@@ -296,14 +310,17 @@ async fn test_cold_start_file_store_worker_progress() {
         fullnode_grpc_address: (*TESTNET_FULLNODE_GRPC_URL).clone(),
         file_store_config: IndexerGrpcFileStoreConfig::LocalFileStore(LocalFileStore {
             local_file_store_path: tmp_dir.path().to_path_buf(),
+            storage_format: StorageFormat::default(),
         }),
         redis_main_instance_address: (*REDIS_PRIMARY_URL).clone(),
     };
 
+    // The file store is written in the compact format, which the other components read regardless of their own format.
     let file_store_worker_config = IndexerGrpcFileStoreWorkerConfig {
         redis_main_instance_address: (*REDIS_PRIMARY_URL).clone(),
         file_store_config: IndexerGrpcFileStoreConfig::LocalFileStore(LocalFileStore {
             local_file_store_path: tmp_dir.path().to_path_buf(),
+            storage_format: StorageFormat::ZstdCompressedProto,
         }),
     };
 
@@ -321,7 +338,8 @@ async fn test_cold_start_file_store_worker_progress() {
             .expect("Failed to start FileStoreWorker");
 
     // wait until file store writes its first metadata
-    let file_store_operator = LocalFileStoreOperator::new(tmp_dir.path().to_path_buf());
+    let file_store_operator =
+        LocalFileStoreOperator::new(tmp_dir.path().to_path_buf(), StorageFormat::default());
     let tries = 6;
     for _ in 0..tries {
         match file_store_operator.get_file_store_metadata().await {
@@ -358,4 +376,162 @@ async fn test_cold_start_file_store_worker_progress() {
         file_store_metadata
     );
     assert!(file_store_metadata.version > 0);
+
+    // the uploaded files are compact, and readable by an operator configured with the JSON format
+    let transactions = file_store_operator
+        .get_transactions(0)
+        .await
+        .expect("Failed to read transactions file");
+    assert_eq!(transactions.len(), BLOB_STORAGE_SIZE);
+    assert!(tmp_dir.path().join("files/0.bin").exists());
+}
+
+/// Test that the data service only streams the transactions that match the request filters.
+/// Only block metadata transactions emit new block events, so no user transaction is expected.
+/// The stream still reports the last version scanned, matching or not.
+#[tokio::test]
+async fn test_data_service_transaction_filters() {
+    setup_test().await;
+
+    let tmp_dir = TempDir::new().expect("Could not create temp dir"); // start with a new file store each time
+    let file_store_config = IndexerGrpcFileStoreConfig::LocalFileStore(LocalFileStore {
+        local_file_store_path: tmp_dir.path().to_path_buf(),
+        storage_format: StorageFormat::default(),
+    });
+
+    let cache_worker_config = IndexerGrpcCacheWorkerConfig {
+        fullnode_grpc_address: (*TESTNET_FULLNODE_GRPC_URL).clone(),
+        file_store_config: file_store_config.clone(),
+        redis_main_instance_address: (*REDIS_PRIMARY_URL).clone(),
+    };
+
+    let data_service_grpc_port = aptos_config::utils::get_available_port();
+    let data_service_config = IndexerGrpcDataServiceConfig::new(
+        None,
+        Some(NonTlsConfig {
+            data_service_grpc_listen_address: SocketAddr::from((
+                [127, 0, 0, 1],
+                data_service_grpc_port,
+            )),
+        }),
+        None,
+        vec![],
+        true,
+        file_store_config,
+        (*REDIS_PRIMARY_URL).clone(),
+    );
+
+    let (_cache_worker_port, _cache_worker_handle) =
+        start_server::<IndexerGrpcCacheWorkerConfig>(cache_worker_config)
+            .await
+            .expect("Failed to start CacheWorker");
+
+    let (_data_service_port, _data_service_handle) =
+        start_server::<IndexerGrpcDataServiceConfig>(data_service_config)
+            .await
+            .expect("Failed to start DataService");
+
+    let data_service_url =
+        Url::parse(&format!("http://127.0.0.1:{}", data_service_grpc_port)).unwrap();
+    let mut client =
+        create_data_service_grpc_client(data_service_url, Some(Duration::from_secs(30)))
+            .await
+            .expect("Failed to connect to DataService");
+
+    let request = GetTransactionsRequest {
+        starting_version: Some(0),
+        transactions_count: Some(100),
+        batch_size: None,
+        transaction_filters: vec![TransactionFilter {
+            event_type: Some("0x1::block::NewBlockEvent".to_string()),
+            ..TransactionFilter::default()
+        }],
+    };
+    let mut stream = client
+        .get_transactions(request)
+        .await
+        .expect("Failed to get transactions")
+        .into_inner();
+
+    let mut filtered_transactions_count = 0;
+    let mut last_scanned_version = None;
+    while let Some(response) = stream.next().await {
+        let response = response.expect("Failed to receive transactions");
+        if response.last_scanned_version.is_some() {
+            last_scanned_version = response.last_scanned_version;
+        }
+        for transaction in response.transactions {
+            assert!(!matches!(transaction.txn_data, Some(TxnData::User(_))));
+            filtered_transactions_count += 1;
+        }
+    }
+    info!(
+        "Received {} transactions matching the filters",
+        filtered_transactions_count
+    );
+    assert!(filtered_transactions_count > 0);
+    assert!(filtered_transactions_count < 100);
+    // The whole requested range is scanned, even though the last matching transaction is earlier.
+    assert_eq!(last_scanned_version, Some(99));
+}
+
+/// Test that the S3 file store operator can write and read the file store, using MinIO as a stand-in for S3.
+#[tokio::test]
+async fn test_s3_file_store_operator() {
+    aptos_logger::Logger::init_for_testing();
+    // The operator loads the credentials from the default AWS credentials chain.
+    std::env::set_var("AWS_ACCESS_KEY_ID", MINIO_CREDENTIALS);
+    std::env::set_var("AWS_SECRET_ACCESS_KEY", MINIO_CREDENTIALS);
+
+    let file_store_config = IndexerGrpcFileStoreConfig::S3FileStore(S3FileStore {
+        s3_file_store_bucket_name: MINIO_BUCKET_NAME.to_string(),
+        s3_file_store_region: Some("us-east-1".to_string()),
+        s3_file_store_endpoint_url: Some(MINIO_ENDPOINT_URL.to_string()),
+        s3_file_store_force_path_style: true,
+        s3_file_store_max_concurrent_uploads: 2,
+        storage_format: StorageFormat::ZstdCompressedProto,
+    });
+    let mut file_store_operator = file_store_config.create();
+    file_store_operator.verify_storage_bucket_existence().await;
+
+    // Upload a few files at once, more than can be uploaded concurrently.
+    let num_transactions = 3 * BLOB_STORAGE_SIZE;
+    let transactions = (0..num_transactions as u64)
+        .map(|version| {
+            let transaction = Transaction {
+                version,
+                ..Transaction::default()
+            };
+            (base64::encode(transaction.encode_to_vec()), version)
+        })
+        .collect::<Vec<_>>();
+    file_store_operator
+        .upload_transactions(ChainId::test().id() as u64, transactions.clone())
+        .await
+        .expect("Failed to upload transactions");
+
+    // The first upload always updates the metadata.
+
+    let file_store_metadata = file_store_operator
+        .get_file_store_metadata()
+        .await
+        .expect("Failed to get file store metadata");
+    assert_eq!(file_store_metadata.version, num_transactions as u64);
+
+    // Reads start from any version within a file.
+    let read_transactions = file_store_operator
+        .get_transactions(BLOB_STORAGE_SIZE as u64 + 500)
+        .await
+        .expect("Failed to read transactions");
+    assert_eq!(
+        read_transactions,
+        transactions[BLOB_STORAGE_SIZE + 500..2 * BLOB_STORAGE_SIZE]
+            .iter()
+            .map(|(transaction, _)| transaction.clone())
+            .collect::<Vec<_>>()
+    );
+    assert!(file_store_operator
+        .get_transactions(num_transactions as u64)
+        .await
+        .is_err());
 }
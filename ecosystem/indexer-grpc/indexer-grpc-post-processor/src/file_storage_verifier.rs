@@ -5,9 +5,7 @@ use anyhow::{bail, ensure};
 use aptos_indexer_grpc_utils::{
     config::IndexerGrpcFileStoreConfig,
     constants::BLOB_STORAGE_SIZE,
-    file_store_operator::{
-        FileStoreOperator, GcsFileStoreOperator, LocalFileStoreOperator, TransactionsFile,
-    },
+    file_store_operator::{FileStoreOperator, TransactionsFile},
 };
 use aptos_protos::transaction::v1::Transaction;
 use prost::Message;
@@ -26,19 +24,7 @@ impl FileStorageVerifier {
     }
 
     pub async fn run(&self) -> anyhow::Result<()> {
-        let mut file_store_operator: Box<dyn FileStoreOperator> = match &self.file_store_config {
-            IndexerGrpcFileStoreConfig::GcsFileStore(gcs_file_store) => {
-                Box::new(GcsFileStoreOperator::new(
-                    gcs_file_store.gcs_file_store_bucket_name.clone(),
-                    gcs_file_store
-                        .gcs_file_store_service_account_key_path
-                        .clone(),
-                ))
-            },
-            IndexerGrpcFileStoreConfig::LocalFileStore(local_file_store) => Box::new(
-                LocalFileStoreOperator::new(local_file_store.local_file_store_path.clone()),
-            ),
-        };
+        let mut file_store_operator: Box<dyn FileStoreOperator> = self.file_store_config.create();
         // Verify the existence of the storage bucket.
         file_store_operator.verify_storage_bucket_existence().await;
         // Get or create verification metadata file.
@@ -177,6 +177,7 @@ async fn handle_indexer_grpc(
             starting_version: Some(current_version),
             transactions_count: None,
             batch_size: None,
+            transaction_filters: vec![],
         });
 
         request.metadata_mut().insert(
@@ -1,6 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::file_store_operator::{
+    FileStoreOperator, GcsFileStoreOperator, LocalFileStoreOperator, S3FileStoreOperator,
+    StorageFormat,
+};
 use serde::{Deserialize, Serialize};
 /// Common configuration for Indexer GRPC Store.
 use std::path::PathBuf;
@@ -10,11 +14,41 @@ pub struct GcsFileStore {
     pub gcs_file_store_bucket_name: String,
     // Required to operate on GCS.
     pub gcs_file_store_service_account_key_path: String,
+    // The format new transactions files are written in; files in any format can be read.
+    #[serde(default)]
+    pub storage_format: StorageFormat,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct S3FileStore {
+    pub s3_file_store_bucket_name: String,
+    // If not set, the region is loaded from the environment, e.g., AWS_REGION.
+    pub s3_file_store_region: Option<String>,
+    // Set to use an S3-compatible object store instead of AWS, e.g., http://localhost:9000 for MinIO.
+    pub s3_file_store_endpoint_url: Option<String>,
+    // Most S3-compatible object stores only support path-style addressing, i.e., ${endpoint}/${bucket}/${key}.
+    #[serde(default)]
+    pub s3_file_store_force_path_style: bool,
+    // The maximum number of transactions files uploaded at the same time.
+    #[serde(default = "S3FileStore::default_max_concurrent_uploads")]
+    pub s3_file_store_max_concurrent_uploads: usize,
+    // The format new transactions files are written in; files in any format can be read.
+    #[serde(default)]
+    pub storage_format: StorageFormat,
+}
+
+impl S3FileStore {
+    pub const fn default_max_concurrent_uploads() -> usize {
+        16
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct LocalFileStore {
     pub local_file_store_path: PathBuf,
+    // The format new transactions files are written in; files in any format can be read.
+    #[serde(default)]
+    pub storage_format: StorageFormat,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -22,12 +56,40 @@ pub struct LocalFileStore {
 pub enum IndexerGrpcFileStoreConfig {
     GcsFileStore(GcsFileStore),
     LocalFileStore(LocalFileStore),
+    S3FileStore(S3FileStore),
 }
 
 impl Default for IndexerGrpcFileStoreConfig {
     fn default() -> Self {
         IndexerGrpcFileStoreConfig::LocalFileStore(LocalFileStore {
             local_file_store_path: std::env::current_dir().unwrap(),
+            storage_format: StorageFormat::default(),
         })
     }
 }
+
+impl IndexerGrpcFileStoreConfig {
+    /// Creates the file store operator for this config.
+    pub fn create(&self) -> Box<dyn FileStoreOperator> {
+        match self {
+            IndexerGrpcFileStoreConfig::GcsFileStore(gcs_file_store) => {
+                Box::new(GcsFileStoreOperator::new(
+                    gcs_file_store.gcs_file_store_bucket_name.clone(),
+                    gcs_file_store
+                        .gcs_file_store_service_account_key_path
+                        .clone(),
+                    gcs_file_store.storage_format,
+                ))
+            },
+            IndexerGrpcFileStoreConfig::LocalFileStore(local_file_store) => {
+                Box::new(LocalFileStoreOperator::new(
+                    local_file_store.local_file_store_path.clone(),
+                    local_file_store.storage_format,
+                ))
+            },
+            IndexerGrpcFileStoreConfig::S3FileStore(s3_file_store) => {
+                Box::new(S3FileStoreOperator::new(s3_file_store.clone()))
+            },
+        }
+    }
+}
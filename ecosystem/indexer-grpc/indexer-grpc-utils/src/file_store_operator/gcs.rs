@@ -12,6 +12,8 @@ const SERVICE_ACCOUNT_ENV_VAR: &str = "SERVICE_ACCOUNT";
 
 pub struct GcsFileStoreOperator {
     bucket_name: String,
+    /// The format of the uploaded transactions files; files are read in any format.
+    storage_format: StorageFormat,
     /// The timestamp of the latest metadata update; this is to avoid too frequent metadata update.
     latest_metadata_update_timestamp: Option<std::time::Instant>,
 
@@ -20,10 +22,15 @@ pub struct GcsFileStoreOperator {
 }
 
 impl GcsFileStoreOperator {
-    pub fn new(bucket_name: String, service_account_path: String) -> Self {
+    pub fn new(
+        bucket_name: String,
+        service_account_path: String,
+        storage_format: StorageFormat,
+    ) -> Self {
         env::set_var(SERVICE_ACCOUNT_ENV_VAR, service_account_path);
         Self {
             bucket_name,
+            storage_format,
             latest_metadata_update_timestamp: None,
             latest_verification_metadata_update_timestamp: None,
         }
@@ -46,44 +53,38 @@ impl FileStoreOperator for GcsFileStoreOperator {
 
     /// Gets the transactions files from the file store. version has to be a multiple of BLOB_STORAGE_SIZE.
     async fn get_transactions(&self, version: u64) -> anyhow::Result<Vec<String>> {
+        let file = self.get_raw_transactions(version).await?;
+        Ok(file
+            .transactions
+            .into_iter()
+            .skip((version % BLOB_STORAGE_SIZE as u64) as usize)
+            .collect())
+    }
+
+    /// Gets the raw transactions file from the file store. Mainly for verification purpose.
+    async fn get_raw_transactions(&self, version: u64) -> anyhow::Result<TransactionsFile> {
         let batch_start_version = version / BLOB_STORAGE_SIZE as u64 * BLOB_STORAGE_SIZE as u64;
-        let current_file_name = generate_blob_name(batch_start_version);
-        match Object::download(&self.bucket_name, current_file_name.as_str()).await {
-            Ok(file) => {
-                let file: TransactionsFile =
-                    serde_json::from_slice(&file).map_err(|e| anyhow::anyhow!(e.to_string()))?;
-                Ok(file
-                    .transactions
-                    .into_iter()
-                    .skip((version % BLOB_STORAGE_SIZE as u64) as usize)
-                    .collect())
-            },
-            Err(cloud_storage::Error::Other(err)) => {
-                if err.contains("No such object: ") {
-                    anyhow::bail!("[Indexer File] Transactions file not found. Gap might happen between cache and file store. {}", err)
-                } else {
+        let mut last_err = None;
+        // Files written before a storage format change are in the previous format.
+        for storage_format in self.storage_format.read_order() {
+            let current_file_name = generate_blob_name(batch_start_version, storage_format);
+            match Object::download(&self.bucket_name, current_file_name.as_str()).await {
+                Ok(file) => return TransactionsFile::from_bytes(&file),
+                Err(cloud_storage::Error::Other(err)) if err.contains("No such object: ") => {
+                    last_err = Some(err);
+                },
+                Err(err) => {
                     anyhow::bail!(
                         "[Indexer File] Error happens when transaction file. {}",
                         err
                     );
-                }
-            },
-            Err(err) => {
-                anyhow::bail!(
-                    "[Indexer File] Error happens when transaction file. {}",
-                    err
-                );
-            },
+                },
+            }
         }
-    }
-
-    /// Gets the raw transactions file from the file store. Mainly for verification purpose.
-    async fn get_raw_transactions(&self, version: u64) -> anyhow::Result<TransactionsFile> {
-        let batch_start_version = version / BLOB_STORAGE_SIZE as u64 * BLOB_STORAGE_SIZE as u64;
-        let current_file_name = generate_blob_name(batch_start_version);
-        let bytes = Object::download(&self.bucket_name, current_file_name.as_str()).await?;
-        serde_json::from_slice(&bytes)
-            .map_err(|e| anyhow::anyhow!("Failed to deserialize transactions file: {}", e))
+        anyhow::bail!(
+            "[Indexer File] Transactions file not found. Gap might happen between cache and file store. {}",
+            last_err.unwrap_or_default()
+        )
     }
 
     /// Gets the metadata from the file store. Operator will panic if error happens when accessing the metadata file(except not found).
@@ -226,14 +227,15 @@ impl FileStoreOperator for GcsFileStoreOperator {
         // Split the transactions into batches of BLOB_STORAGE_SIZE.
         for i in transactions.chunks(BLOB_STORAGE_SIZE) {
             let bucket_name = self.bucket_name.clone();
+            let storage_format = self.storage_format;
             let current_batch = i.iter().cloned().collect_vec();
             let transactions_file = build_transactions_file(current_batch).unwrap();
             let task = tokio::spawn(async move {
                 match Object::create(
                     bucket_name.clone().as_str(),
-                    serde_json::to_vec(&transactions_file).unwrap(),
-                    generate_blob_name(transactions_file.starting_version).as_str(),
-                    JSON_FILE_TYPE,
+                    transactions_file.to_bytes(storage_format)?,
+                    generate_blob_name(transactions_file.starting_version, storage_format).as_str(),
+                    storage_format.content_type(),
                 )
                 .await
                 {
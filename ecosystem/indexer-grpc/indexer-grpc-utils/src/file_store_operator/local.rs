@@ -8,14 +8,17 @@ use tracing::info;
 
 pub struct LocalFileStoreOperator {
     path: PathBuf,
+    /// The format of the uploaded transactions files; files are read in any format.
+    storage_format: StorageFormat,
     /// The timestamp of the latest metadata update; this is to avoid too frequent metadata update.
     latest_metadata_update_timestamp: Option<std::time::Instant>,
 }
 
 impl LocalFileStoreOperator {
-    pub fn new(path: PathBuf) -> Self {
+    pub fn new(path: PathBuf, storage_format: StorageFormat) -> Self {
         Self {
             path,
+            storage_format,
             latest_metadata_update_timestamp: None,
         }
     }
@@ -34,30 +37,12 @@ impl FileStoreOperator for LocalFileStoreOperator {
     }
 
     async fn get_transactions(&self, version: u64) -> anyhow::Result<Vec<String>> {
-        let batch_start_version = version / BLOB_STORAGE_SIZE as u64 * BLOB_STORAGE_SIZE as u64;
-        let current_file_name = generate_blob_name(batch_start_version);
-        let file_path = self.path.join(current_file_name);
-        match tokio::fs::read(file_path).await {
-            Ok(file) => {
-                let file: TransactionsFile =
-                    serde_json::from_slice(&file).expect("Expected file to be valid JSON.");
-                Ok(file
-                    .transactions
-                    .into_iter()
-                    .skip((version % BLOB_STORAGE_SIZE as u64) as usize)
-                    .collect())
-            },
-            Err(err) => {
-                if err.kind() == std::io::ErrorKind::NotFound {
-                    anyhow::bail!("[Indexer File] Transactions file not found. Gap might happen between cache and file store. {}", err)
-                } else {
-                    anyhow::bail!(
-                        "[Indexer File] Error happens when transaction file. {}",
-                        err
-                    );
-                }
-            },
-        }
+        let file = self.get_raw_transactions(version).await?;
+        Ok(file
+            .transactions
+            .into_iter()
+            .skip((version % BLOB_STORAGE_SIZE as u64) as usize)
+            .collect())
     }
 
     async fn get_file_store_metadata(&self) -> Option<FileStoreMetadata> {
@@ -163,18 +148,19 @@ impl FileStoreOperator for LocalFileStoreOperator {
         for i in transactions.chunks(BLOB_STORAGE_SIZE) {
             let current_batch = i.iter().cloned().collect_vec();
             let transactions_file = build_transactions_file(current_batch).unwrap();
-            let txns_path = self
-                .path
-                .join(generate_blob_name(transactions_file.starting_version).as_str());
+            let txns_path = self.path.join(
+                generate_blob_name(transactions_file.starting_version, self.storage_format)
+                    .as_str(),
+            );
+            let storage_format = self.storage_format;
 
             tracing::debug!(
                 "Uploading transactions to {:?}",
                 txns_path.to_str().unwrap()
             );
             let task = tokio::spawn(async move {
-                match tokio::fs::write(txns_path, serde_json::to_vec(&transactions_file).unwrap())
-                    .await
-                {
+                let bytes = transactions_file.to_bytes(storage_format)?;
+                match tokio::fs::write(txns_path, bytes).await {
                     Ok(_) => Ok(()),
                     Err(err) => Err(anyhow::Error::from(err)),
                 }
@@ -225,7 +211,27 @@ impl FileStoreOperator for LocalFileStoreOperator {
         anyhow::bail!("Verification is not impelemented for local file store.")
     }
 
-    async fn get_raw_transactions(&self, _version: u64) -> anyhow::Result<TransactionsFile> {
-        anyhow::bail!("Unimplemented");
+    async fn get_raw_transactions(&self, version: u64) -> anyhow::Result<TransactionsFile> {
+        let batch_start_version = version / BLOB_STORAGE_SIZE as u64 * BLOB_STORAGE_SIZE as u64;
+        // Files written before a storage format change are in the previous format.
+        for storage_format in self.storage_format.read_order() {
+            let file_path = self
+                .path
+                .join(generate_blob_name(batch_start_version, storage_format));
+            match tokio::fs::read(file_path).await {
+                Ok(file) => return TransactionsFile::from_bytes(&file),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(err) => {
+                    anyhow::bail!(
+                        "[Indexer File] Error happens when transaction file. {}",
+                        err
+                    );
+                },
+            }
+        }
+        anyhow::bail!(
+            "[Indexer File] Transactions file not found. Gap might happen between cache and file store. {}",
+            batch_start_version
+        )
     }
 }
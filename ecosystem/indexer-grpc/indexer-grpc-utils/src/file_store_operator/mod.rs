@@ -9,6 +9,10 @@ pub mod gcs;
 pub use gcs::*;
 pub mod local;
 pub use local::*;
+pub mod s3;
+pub use s3::*;
+pub mod storage_format;
+pub use storage_format::StorageFormat;
 
 pub const FILE_FOLDER_NAME: &str = "files";
 const METADATA_FILE_NAME: &str = "metadata.json";
@@ -16,12 +20,18 @@ const VERIFICATION_FILE_NAME: &str = "verification.json";
 const FILE_STORE_UPDATE_FREQUENCY_SECS: u64 = 5;
 
 #[inline]
-pub fn generate_blob_name(starting_version: u64) -> String {
-    format!("{}/{}.json", FILE_FOLDER_NAME, starting_version)
+pub fn generate_blob_name(starting_version: u64, storage_format: StorageFormat) -> String {
+    format!(
+        "{}/{}.{}",
+        FILE_FOLDER_NAME,
+        starting_version,
+        storage_format.file_extension()
+    )
 }
 
 /// TransactionsFile is the file format for storing transactions.
-/// It's a JSON file with name: ${starting_version}.json.
+/// It's a JSON file with name: ${starting_version}.json, or a compact file with name: ${starting_version}.bin.
+/// See StorageFormat for the formats.
 #[derive(Serialize, Deserialize)]
 pub struct TransactionsFile {
     // The version of the first transaction in the file.
@@ -32,6 +42,37 @@ pub struct TransactionsFile {
     pub transactions: Vec<String>,
 }
 
+impl TransactionsFile {
+    /// Serializes the file in the given storage format.
+    pub fn to_bytes(&self, storage_format: StorageFormat) -> Result<Vec<u8>> {
+        match storage_format {
+            StorageFormat::JsonBase64 => Ok(serde_json::to_vec(self)?),
+            compact => {
+                let transactions = self
+                    .transactions
+                    .iter()
+                    .map(base64::decode)
+                    .collect::<Result<Vec<_>, _>>()?;
+                storage_format::encode_compact_file(compact, self.starting_version, &transactions)
+            },
+        }
+    }
+
+    /// Deserializes a file written in any storage format; the format is detected from the content.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        if storage_format::is_compact_file(bytes) {
+            let (starting_version, transactions) = storage_format::decode_compact_file(bytes)?;
+            Ok(Self {
+                starting_version,
+                transactions: transactions.into_iter().map(base64::encode).collect(),
+            })
+        } else {
+            serde_json::from_slice(bytes)
+                .map_err(|e| anyhow::anyhow!("Failed to deserialize transactions file: {}", e))
+        }
+    }
+}
+
 /// FileStoreMetadata is the metadata for the file store.
 /// It's a JSON file with name: metadata.json.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug)]
@@ -142,23 +183,81 @@ mod tests {
 
     #[test]
     fn verify_blob_naming() {
-        assert_eq!(super::generate_blob_name(0), "files/0.json");
+        let json = StorageFormat::JsonBase64;
+        assert_eq!(super::generate_blob_name(0, json), "files/0.json");
         assert_eq!(
-            super::generate_blob_name(100_000_000),
+            super::generate_blob_name(100_000_000, json),
             "files/100000000.json"
         );
         assert_eq!(
-            super::generate_blob_name(1_000_000_000),
+            super::generate_blob_name(1_000_000_000, json),
             "files/1000000000.json"
         );
         assert_eq!(
-            super::generate_blob_name(10_000_000_000),
+            super::generate_blob_name(10_000_000_000, json),
             "files/10000000000.json"
         );
         assert_eq!(
-            super::generate_blob_name(u64::MAX),
+            super::generate_blob_name(u64::MAX, json),
             "files/18446744073709551615.json"
         );
+        assert_eq!(
+            super::generate_blob_name(0, StorageFormat::Lz4CompressedProto),
+            "files/0.bin"
+        );
+        assert_eq!(
+            super::generate_blob_name(1_000, StorageFormat::ZstdCompressedProto),
+            "files/1000.bin"
+        );
+    }
+
+    #[test]
+    fn verify_transactions_file_formats() {
+        let transactions_file = TransactionsFile {
+            starting_version: 1_000,
+            transactions: (0..BLOB_STORAGE_SIZE)
+                .map(|i| base64::encode(format!("transaction {}", i)))
+                .collect(),
+        };
+        for storage_format in [
+            StorageFormat::JsonBase64,
+            StorageFormat::Lz4CompressedProto,
+            StorageFormat::ZstdCompressedProto,
+        ] {
+            let bytes = transactions_file.to_bytes(storage_format).unwrap();
+            let decoded = TransactionsFile::from_bytes(&bytes).unwrap();
+            assert_eq!(decoded.starting_version, transactions_file.starting_version);
+            assert_eq!(decoded.transactions, transactions_file.transactions);
+        }
+
+        // Compact files are smaller than JSON ones.
+        let json_size = transactions_file
+            .to_bytes(StorageFormat::JsonBase64)
+            .unwrap()
+            .len();
+        let zstd_size = transactions_file
+            .to_bytes(StorageFormat::ZstdCompressedProto)
+            .unwrap()
+            .len();
+        assert!(zstd_size < json_size);
+    }
+
+    #[test]
+    fn verify_corrupted_compact_file() {
+        let transactions_file = TransactionsFile {
+            starting_version: 0,
+            transactions: vec![base64::encode("transaction")],
+        };
+        let mut bytes = transactions_file
+            .to_bytes(StorageFormat::ZstdCompressedProto)
+            .unwrap();
+        // Unknown format version.
+        bytes[4] = 2;
+        assert!(TransactionsFile::from_bytes(&bytes).is_err());
+        // Truncated file.
+        bytes[4] = 1;
+        bytes.truncate(bytes.len() - 1);
+        assert!(TransactionsFile::from_bytes(&bytes).is_err());
     }
 
     #[test]
@@ -0,0 +1,364 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    config::S3FileStore, constants::BLOB_STORAGE_SIZE, file_store_operator::*,
+    EncodedTransactionWithVersion,
+};
+use aws_sdk_s3::{
+    config::{retry::RetryConfig, Region},
+    primitives::ByteStream,
+    Client,
+};
+use futures::StreamExt;
+use tokio::sync::OnceCell;
+
+const JSON_FILE_TYPE: &str = "application/json";
+// Requests are retried by the client, with exponential backoff, up to this many attempts.
+const S3_MAX_ATTEMPTS: u32 = 5;
+
+/// S3FileStoreOperator works with AWS S3 and any S3-compatible object store, e.g., MinIO.
+/// Credentials are loaded from the default AWS credentials chain, e.g., environment variables.
+pub struct S3FileStoreOperator {
+    bucket_name: String,
+    region: Option<String>,
+    endpoint_url: Option<String>,
+    force_path_style: bool,
+    max_concurrent_uploads: usize,
+    /// The format of the uploaded transactions files; files are read in any format.
+    storage_format: StorageFormat,
+    /// The client is created on first use, since loading the AWS config is async.
+    client: OnceCell<Client>,
+    /// The timestamp of the latest metadata update; this is to avoid too frequent metadata update.
+    latest_metadata_update_timestamp: Option<std::time::Instant>,
+
+    /// The timestamp of the latest verification metadata update; this is to avoid too frequent metadata update.
+    latest_verification_metadata_update_timestamp: Option<std::time::Instant>,
+}
+
+impl S3FileStoreOperator {
+    pub fn new(s3_file_store: S3FileStore) -> Self {
+        Self {
+            bucket_name: s3_file_store.s3_file_store_bucket_name,
+            region: s3_file_store.s3_file_store_region,
+            endpoint_url: s3_file_store.s3_file_store_endpoint_url,
+            force_path_style: s3_file_store.s3_file_store_force_path_style,
+            max_concurrent_uploads: s3_file_store.s3_file_store_max_concurrent_uploads.max(1),
+            storage_format: s3_file_store.storage_format,
+            client: OnceCell::new(),
+            latest_metadata_update_timestamp: None,
+            latest_verification_metadata_update_timestamp: None,
+        }
+    }
+
+    async fn client(&self) -> &Client {
+        self.client
+            .get_or_init(|| async {
+                let mut config_loader = aws_config::from_env()
+                    .retry_config(RetryConfig::standard().with_max_attempts(S3_MAX_ATTEMPTS));
+                if let Some(region) = &self.region {
+                    config_loader = config_loader.region(Region::new(region.clone()));
+                }
+                if let Some(endpoint_url) = &self.endpoint_url {
+                    config_loader = config_loader.endpoint_url(endpoint_url);
+                }
+                let sdk_config = config_loader.load().await;
+                let s3_config = aws_sdk_s3::config::Builder::from(&sdk_config)
+                    .force_path_style(self.force_path_style)
+                    .build();
+                Client::from_conf(s3_config)
+            })
+            .await
+    }
+
+    /// Downloads an object; returns None if the object doesn't exist.
+    async fn download_object(&self, key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+        match self
+            .client()
+            .await
+            .get_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(output) => Ok(Some(output.body.collect().await?.into_bytes().to_vec())),
+            Err(err) => {
+                let err = err.into_service_error();
+                if err.is_no_such_key() {
+                    Ok(None)
+                } else {
+                    Err(anyhow::Error::from(err))
+                }
+            },
+        }
+    }
+
+    async fn upload_object(&self, key: &str, bytes: Vec<u8>) -> anyhow::Result<()> {
+        put_object(
+            self.client().await,
+            &self.bucket_name,
+            key,
+            bytes,
+            JSON_FILE_TYPE,
+        )
+        .await
+    }
+}
+
+async fn put_object(
+    client: &Client,
+    bucket_name: &str,
+    key: &str,
+    bytes: Vec<u8>,
+    content_type: &str,
+) -> anyhow::Result<()> {
+    client
+        .put_object()
+        .bucket(bucket_name)
+        .key(key)
+        .content_type(content_type)
+        .body(ByteStream::from(bytes))
+        .send()
+        .await?;
+    Ok(())
+}
+
+#[async_trait::async_trait]
+impl FileStoreOperator for S3FileStoreOperator {
+    /// Bootstraps the file store operator. This is required before any other operations.
+    async fn verify_storage_bucket_existence(&self) {
+        tracing::info!(
+            bucket_name = self.bucket_name,
+            "Before file store operator starts, verify the bucket exists."
+        );
+        // Verifies the bucket exists.
+        self.client()
+            .await
+            .head_bucket()
+            .bucket(&self.bucket_name)
+            .send()
+            .await
+            .expect("Failed to read bucket.");
+    }
+
+    /// Gets the transactions files from the file store. version has to be a multiple of BLOB_STORAGE_SIZE.
+    async fn get_transactions(&self, version: u64) -> anyhow::Result<Vec<String>> {
+        let file = self.get_raw_transactions(version).await?;
+        Ok(file
+            .transactions
+            .into_iter()
+            .skip((version % BLOB_STORAGE_SIZE as u64) as usize)
+            .collect())
+    }
+
+    /// Gets the raw transactions file from the file store. Mainly for verification purpose.
+    async fn get_raw_transactions(&self, version: u64) -> anyhow::Result<TransactionsFile> {
+        let batch_start_version = version / BLOB_STORAGE_SIZE as u64 * BLOB_STORAGE_SIZE as u64;
+        // Files written before a storage format change are in the previous format.
+        for storage_format in self.storage_format.read_order() {
+            let current_file_name = generate_blob_name(batch_start_version, storage_format);
+            match self.download_object(current_file_name.as_str()).await {
+                Ok(Some(file)) => return TransactionsFile::from_bytes(&file),
+                Ok(None) => continue,
+                Err(err) => {
+                    anyhow::bail!(
+                        "[Indexer File] Error happens when transaction file. {}",
+                        err
+                    );
+                },
+            }
+        }
+        anyhow::bail!(
+            "[Indexer File] Transactions file not found. Gap might happen between cache and file store. {}",
+            batch_start_version
+        )
+    }
+
+    /// Gets the metadata from the file store. Operator will panic if error happens when accessing the metadata file(except not found).
+    async fn get_file_store_metadata(&self) -> Option<FileStoreMetadata> {
+        match self.download_object(METADATA_FILE_NAME).await {
+            Ok(Some(metadata)) => {
+                let metadata: FileStoreMetadata =
+                    serde_json::from_slice(&metadata).expect("Expected metadata to be valid JSON.");
+                Some(metadata)
+            },
+            // Metadata is not found.
+            Ok(None) => None,
+            Err(err) => {
+                panic!(
+                    "[Indexer File] Error happens when accessing metadata file. {}",
+                    err
+                );
+            },
+        }
+    }
+
+    /// If the file store is empty, the metadata will be created; otherwise, return the existing metadata.
+    async fn create_default_file_store_metadata_if_absent(
+        &mut self,
+        expected_chain_id: u64,
+    ) -> anyhow::Result<FileStoreMetadata> {
+        match self.download_object(METADATA_FILE_NAME).await? {
+            Some(metadata) => {
+                let metadata: FileStoreMetadata =
+                    serde_json::from_slice(&metadata).expect("Expected metadata to be valid JSON.");
+                anyhow::ensure!(metadata.chain_id == expected_chain_id, "Chain ID mismatch.");
+                Ok(metadata)
+            },
+            None => {
+                // If the metadata is not found, it means the file store is empty.
+                self.update_file_store_metadata(expected_chain_id, 0)
+                    .await
+                    .expect("[Indexer File] Update metadata failed.");
+                Ok(FileStoreMetadata::new(expected_chain_id, 0))
+            },
+        }
+    }
+
+    /// Updates the file store metadata. This is only performed by the operator when new file transactions are uploaded.
+    async fn update_file_store_metadata(
+        &mut self,
+        chain_id: u64,
+        version: u64,
+    ) -> anyhow::Result<()> {
+        let metadata = FileStoreMetadata::new(chain_id, version);
+        // If the metadata is not updated, the indexer will be restarted.
+        self.upload_object(METADATA_FILE_NAME, serde_json::to_vec(&metadata).unwrap())
+            .await?;
+        self.latest_metadata_update_timestamp = Some(std::time::Instant::now());
+        Ok(())
+    }
+
+    /// Updates the verification metadata file.
+    async fn update_verification_metadata(
+        &mut self,
+        chain_id: u64,
+        next_version_to_verify: u64,
+    ) -> Result<()> {
+        let verification_metadata = VerificationMetadata {
+            chain_id,
+            next_version_to_verify,
+        };
+        let time_now = std::time::Instant::now();
+        if let Some(last_update_time) = self.latest_verification_metadata_update_timestamp {
+            if time_now.duration_since(last_update_time) < std::time::Duration::from_secs(20) {
+                return Ok(());
+            }
+        }
+        self.upload_object(
+            VERIFICATION_FILE_NAME,
+            serde_json::to_vec(&verification_metadata).unwrap(),
+        )
+        .await?;
+        self.latest_verification_metadata_update_timestamp = Some(std::time::Instant::now());
+        Ok(())
+    }
+
+    /// Uploads the transactions to the file store. The transactions are grouped into batches of BLOB_STORAGE_SIZE.
+    /// At most max_concurrent_uploads files are uploaded at the same time.
+    /// Updates the file store metadata after the upload.
+    async fn upload_transactions(
+        &mut self,
+        chain_id: u64,
+        transactions: Vec<EncodedTransactionWithVersion>,
+    ) -> anyhow::Result<()> {
+        let start_version = transactions.first().unwrap().1;
+        let batch_size = transactions.len();
+        anyhow::ensure!(
+            start_version % BLOB_STORAGE_SIZE as u64 == 0,
+            "Starting version has to be a multiple of BLOB_STORAGE_SIZE."
+        );
+        anyhow::ensure!(
+            batch_size % BLOB_STORAGE_SIZE == 0,
+            "The number of transactions to upload has to be multiplier of BLOB_STORAGE_SIZE."
+        );
+
+        let client = self.client().await.clone();
+        let bucket_name = self.bucket_name.clone();
+        let storage_format = self.storage_format;
+        // Split the transactions into batches of BLOB_STORAGE_SIZE; tasks are only spawned when polled.
+        let results = futures::stream::iter(transactions.chunks(BLOB_STORAGE_SIZE))
+            .map(|batch| {
+                let client = client.clone();
+                let bucket_name = bucket_name.clone();
+                let transactions_file = build_transactions_file(batch.to_vec()).unwrap();
+                tokio::spawn(async move {
+                    put_object(
+                        &client,
+                        &bucket_name,
+                        generate_blob_name(transactions_file.starting_version, storage_format)
+                            .as_str(),
+                        transactions_file.to_bytes(storage_format)?,
+                        storage_format.content_type(),
+                    )
+                    .await
+                })
+            })
+            .buffer_unordered(self.max_concurrent_uploads)
+            .collect::<Vec<_>>()
+            .await;
+        let mut upload_failed = false;
+        for result in results {
+            match result {
+                Ok(Ok(())) => {},
+                Ok(Err(err)) => {
+                    tracing::error!("Error happens when uploading transactions. {:?}", err);
+                    upload_failed = true;
+                },
+                Err(err) => panic!("Error processing transaction batches: {:?}", err),
+            }
+        }
+        // If any uploading fails, retry.
+        if upload_failed {
+            anyhow::bail!("Uploading transactions failed.");
+        }
+
+        if let Some(ts) = self.latest_metadata_update_timestamp {
+            // a periodic metadata update
+            if ts.elapsed().as_secs() > FILE_STORE_UPDATE_FREQUENCY_SECS {
+                self.update_file_store_metadata(chain_id, start_version + batch_size as u64)
+                    .await?;
+            }
+        } else {
+            // the first metadata update
+            self.update_file_store_metadata(chain_id, start_version + batch_size as u64)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn get_or_create_verification_metadata(
+        &self,
+        chain_id: u64,
+    ) -> Result<VerificationMetadata> {
+        let file_metadata = self
+            .get_file_store_metadata()
+            .await
+            .ok_or(anyhow::anyhow!("No file store metadata found"))?;
+        anyhow::ensure!(file_metadata.chain_id == chain_id, "Chain ID mismatch");
+
+        match self.download_object(VERIFICATION_FILE_NAME).await? {
+            Some(verification_metadata) => {
+                let metadata: VerificationMetadata = serde_json::from_slice(&verification_metadata)
+                    .expect("Expected metadata to be valid JSON.");
+                anyhow::ensure!(metadata.chain_id == chain_id, "Chain ID mismatch.");
+                Ok(metadata)
+            },
+            None => {
+                // Metadata is not found.
+                let metadata = VerificationMetadata {
+                    chain_id,
+                    next_version_to_verify: 0,
+                };
+                self.upload_object(
+                    VERIFICATION_FILE_NAME,
+                    serde_json::to_vec(&metadata).unwrap(),
+                )
+                .await?;
+                Ok(metadata)
+            },
+        }
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{bail, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+
+// Compact files start with this magic, which can't be the start of a JSON file.
+const COMPACT_FILE_MAGIC: &[u8; 4] = b"APTX";
+// Bump this when the layout of compact files changes; readers reject versions they don't know.
+const COMPACT_FILE_FORMAT_VERSION: u8 = 1;
+// Magic, format version, compression, starting version and number of transactions.
+const COMPACT_FILE_HEADER_SIZE: usize = 4 + 1 + 1 + 8 + 4;
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+/// StorageFormat is the format the transactions files are written in.
+/// Files are read in any format, so the format can be changed on a live file store.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageFormat {
+    /// JSON file of base64 encoded Transaction protobufs; the original format.
+    #[default]
+    JsonBase64,
+    /// Compact file of length-prefixed Transaction protobufs, compressed with lz4.
+    Lz4CompressedProto,
+    /// Compact file of length-prefixed Transaction protobufs, compressed with zstd.
+    ZstdCompressedProto,
+}
+
+impl StorageFormat {
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            StorageFormat::JsonBase64 => "json",
+            StorageFormat::Lz4CompressedProto | StorageFormat::ZstdCompressedProto => "bin",
+        }
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            StorageFormat::JsonBase64 => "application/json",
+            StorageFormat::Lz4CompressedProto | StorageFormat::ZstdCompressedProto => {
+                "application/octet-stream"
+            },
+        }
+    }
+
+    /// The formats to look for a transactions file in, this format first.
+    /// Files written before the format was changed keep their original format, so
+    /// the file of the other extension is read if this one doesn't exist. Compact files
+    /// share an extension whatever their compression, which is read from the file itself.
+    pub fn read_order(&self) -> [StorageFormat; 2] {
+        match self {
+            StorageFormat::JsonBase64 => [
+                StorageFormat::JsonBase64,
+                StorageFormat::ZstdCompressedProto,
+            ],
+            compact => [*compact, StorageFormat::JsonBase64],
+        }
+    }
+
+    fn compression_id(&self) -> u8 {
+        match self {
+            StorageFormat::JsonBase64 => unreachable!("JSON files are not compressed."),
+            StorageFormat::Lz4CompressedProto => 1,
+            StorageFormat::ZstdCompressedProto => 2,
+        }
+    }
+
+    fn from_compression_id(compression_id: u8) -> Result<Self> {
+        match compression_id {
+            1 => Ok(StorageFormat::Lz4CompressedProto),
+            2 => Ok(StorageFormat::ZstdCompressedProto),
+            _ => bail!(
+                "Unknown compression {} in transactions file.",
+                compression_id
+            ),
+        }
+    }
+}
+
+/// Returns true if the bytes are a compact transactions file, i.e., not JSON.
+pub(crate) fn is_compact_file(bytes: &[u8]) -> bool {
+    bytes.starts_with(COMPACT_FILE_MAGIC)
+}
+
+/// Builds a compact transactions file from serialized Transaction protobufs.
+/// Layout: magic | format version (u8) | compression (u8) | starting version (u64 LE)
+/// | number of transactions (u32 LE) | compressed records, each a varint length followed by the protobuf.
+pub(crate) fn encode_compact_file(
+    storage_format: StorageFormat,
+    starting_version: u64,
+    transactions: &[Vec<u8>],
+) -> Result<Vec<u8>> {
+    let mut records = Vec::with_capacity(transactions.iter().map(|t| t.len() + 4).sum());
+    for transaction in transactions {
+        prost::encoding::encode_varint(transaction.len() as u64, &mut records);
+        records.extend_from_slice(transaction);
+    }
+    let compressed_records = match storage_format {
+        StorageFormat::JsonBase64 => bail!("JSON is not a compact storage format."),
+        StorageFormat::Lz4CompressedProto => lz4::block::compress(&records, None, true)?,
+        StorageFormat::ZstdCompressedProto => {
+            zstd::encode_all(records.as_slice(), ZSTD_COMPRESSION_LEVEL)?
+        },
+    };
+
+    let mut bytes = Vec::with_capacity(COMPACT_FILE_HEADER_SIZE + compressed_records.len());
+    bytes.extend_from_slice(COMPACT_FILE_MAGIC);
+    bytes.push(COMPACT_FILE_FORMAT_VERSION);
+    bytes.push(storage_format.compression_id());
+    bytes.extend_from_slice(&starting_version.to_le_bytes());
+    bytes.extend_from_slice(&(transactions.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&compressed_records);
+    Ok(bytes)
+}
+
+/// Parses a compact transactions file into its starting version and serialized Transaction protobufs.
+pub(crate) fn decode_compact_file(bytes: &[u8]) -> Result<(u64, Vec<Vec<u8>>)> {
+    ensure!(
+        is_compact_file(bytes) && bytes.len() >= COMPACT_FILE_HEADER_SIZE,
+        "Transactions file is not a compact file."
+    );
+    let format_version = bytes[4];
+    ensure!(
+        format_version == COMPACT_FILE_FORMAT_VERSION,
+        "Unsupported transactions file format version {}.",
+        format_version
+    );
+    let storage_format = StorageFormat::from_compression_id(bytes[5])?;
+    let starting_version = u64::from_le_bytes(bytes[6..14].try_into().unwrap());
+    let num_transactions = u32::from_le_bytes(bytes[14..18].try_into().unwrap()) as usize;
+
+    let compressed_records = &bytes[COMPACT_FILE_HEADER_SIZE..];
+    let records = match storage_format {
+        StorageFormat::Lz4CompressedProto => lz4::block::decompress(compressed_records, None)
+            .context("Failed to decompress lz4 transactions file.")?,
+        StorageFormat::ZstdCompressedProto => zstd::decode_all(compressed_records)
+            .context("Failed to decompress zstd transactions file.")?,
+        StorageFormat::JsonBase64 => unreachable!("Compact files are always compressed."),
+    };
+
+    let mut transactions = Vec::with_capacity(num_transactions);
+    let mut remaining = records.as_slice();
+    while !remaining.is_empty() {
+        let len = prost::encoding::decode_varint(&mut remaining)
+            .context("Invalid record length in transactions file.")? as usize;
+        ensure!(
+            len <= remaining.len(),
+            "Truncated record in transactions file."
+        );
+        let (transaction, rest) = remaining.split_at(len);
+        transactions.push(transaction.to_vec());
+        remaining = rest;
+    }
+    ensure!(
+        transactions.len() == num_transactions,
+        "Expected {} transactions in transactions file, found {}.",
+        num_transactions,
+        transactions.len()
+    );
+    Ok((starting_version, transactions))
+}
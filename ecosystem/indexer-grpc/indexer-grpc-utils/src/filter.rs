@@ -0,0 +1,521 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{anyhow, bail, ensure, Result};
+use aptos_protos::{
+    indexer::v1::TransactionFilter as TransactionFilterProto,
+    transaction::v1::{
+        multisig_transaction_payload, transaction::TxnData, transaction_payload,
+        write_set_change::Change, EntryFunctionId, Event, Transaction,
+    },
+};
+use move_core_types::language_storage::StructTag;
+use std::str::FromStr;
+
+// Maximum number of filters in a single request, to bound the cost of matching.
+pub const MAX_TRANSACTION_FILTERS: usize = 100;
+
+/// Filters applied to the transactions stream before it's sent to the client.
+/// A transaction is kept if it matches any of the filters; with no filters, every transaction is kept.
+#[derive(Clone, Debug, Default)]
+pub struct TransactionFilters {
+    filters: Vec<TransactionFilter>,
+}
+
+impl TransactionFilters {
+    /// Validates and normalizes the filters from a request.
+    pub fn new(filters: &[TransactionFilterProto]) -> Result<Self> {
+        ensure!(
+            filters.len() <= MAX_TRANSACTION_FILTERS,
+            "At most {} transaction filters are allowed, got {}",
+            MAX_TRANSACTION_FILTERS,
+            filters.len()
+        );
+        Ok(Self {
+            filters: filters
+                .iter()
+                .map(TransactionFilter::new)
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.filters.is_empty()
+    }
+
+    pub fn matches(&self, transaction: &Transaction) -> bool {
+        self.is_empty()
+            || self
+                .filters
+                .iter()
+                .any(|filter| filter.matches(transaction))
+    }
+
+    /// Drops the transactions that don't match, keeping the order of the rest.
+    pub fn filter(&self, transactions: Vec<Transaction>) -> Vec<Transaction> {
+        if self.is_empty() {
+            return transactions;
+        }
+        transactions
+            .into_iter()
+            .filter(|transaction| self.matches(transaction))
+            .collect()
+    }
+}
+
+/// A single filter; a transaction matches if it matches every condition set.
+#[derive(Clone, Debug)]
+struct TransactionFilter {
+    sender: Option<String>,
+    entry_function: Option<EntryFunctionFilter>,
+    event_type: Option<MoveTypeFilter>,
+    write_set_resource_type: Option<MoveTypeFilter>,
+    success: Option<bool>,
+}
+
+impl TransactionFilter {
+    fn new(filter: &TransactionFilterProto) -> Result<Self> {
+        let filter = Self {
+            sender: filter
+                .sender
+                .as_deref()
+                .map(standardize_address)
+                .transpose()?,
+            entry_function: filter
+                .entry_function
+                .as_deref()
+                .map(EntryFunctionFilter::new)
+                .transpose()?,
+            event_type: filter
+                .event_type
+                .as_deref()
+                .map(MoveTypeFilter::new)
+                .transpose()?,
+            write_set_resource_type: filter
+                .write_set_resource_type
+                .as_deref()
+                .map(MoveTypeFilter::new)
+                .transpose()?,
+            success: filter.success,
+        };
+        ensure!(
+            filter.sender.is_some()
+                || filter.entry_function.is_some()
+                || filter.event_type.is_some()
+                || filter.write_set_resource_type.is_some()
+                || filter.success.is_some(),
+            "Transaction filter must set at least one condition"
+        );
+        Ok(filter)
+    }
+
+    fn matches(&self, transaction: &Transaction) -> bool {
+        if let Some(success) = self.success {
+            let succeeded = transaction
+                .info
+                .as_ref()
+                .map(|info| info.success)
+                .unwrap_or_default();
+            if succeeded != success {
+                return false;
+            }
+        }
+
+        if let Some(sender) = &self.sender {
+            match user_transaction_sender(transaction) {
+                Some(txn_sender)
+                    if standardize_address(txn_sender).ok().as_ref() == Some(sender) => {},
+                _ => return false,
+            }
+        }
+
+        if let Some(entry_function) = &self.entry_function {
+            match user_transaction_entry_function(transaction) {
+                Some(function) if entry_function.matches(function) => {},
+                _ => return false,
+            }
+        }
+
+        if let Some(event_type) = &self.event_type {
+            if !transaction_events(transaction)
+                .iter()
+                .any(|event| event_type.matches(&event.type_str))
+            {
+                return false;
+            }
+        }
+
+        if let Some(resource_type) = &self.write_set_resource_type {
+            let changes = transaction
+                .info
+                .as_ref()
+                .map(|info| info.changes.as_slice())
+                .unwrap_or_default();
+            if !changes.iter().any(|change| match &change.change {
+                Some(Change::WriteResource(resource)) => resource_type.matches(&resource.type_str),
+                Some(Change::DeleteResource(resource)) => resource_type.matches(&resource.type_str),
+                _ => false,
+            }) {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Matches entry functions by address, and optionally module and function name.
+#[derive(Clone, Debug)]
+struct EntryFunctionFilter {
+    address: String,
+    module: Option<String>,
+    function: Option<String>,
+}
+
+impl EntryFunctionFilter {
+    fn new(entry_function: &str) -> Result<Self> {
+        let parts: Vec<&str> = entry_function.split("::").collect();
+        if parts.len() > 3 || parts.iter().any(|part| part.is_empty()) {
+            bail!(
+                "Invalid entry function filter {}, expected address::module::function",
+                entry_function
+            );
+        }
+        Ok(Self {
+            address: standardize_address(parts[0])?,
+            module: parts.get(1).map(|module| module.to_string()),
+            function: parts.get(2).map(|function| function.to_string()),
+        })
+    }
+
+    fn matches(&self, function: &EntryFunctionId) -> bool {
+        let module = match &function.module {
+            Some(module) => module,
+            None => return false,
+        };
+        standardize_address(&module.address).ok().as_ref() == Some(&self.address)
+            && self
+                .module
+                .as_ref()
+                .map_or(true, |name| name == &module.name)
+            && self
+                .function
+                .as_ref()
+                .map_or(true, |name| name == &function.name)
+    }
+}
+
+/// Matches Move struct types, e.g. `0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>`. The filter
+/// is parsed once and rendered in the canonical form used for types in transactions, so addresses
+/// may be written in any form. Without type arguments, every instantiation of a generic type matches.
+#[derive(Clone, Debug)]
+struct MoveTypeFilter {
+    move_type: String,
+    match_any_type_args: bool,
+}
+
+impl MoveTypeFilter {
+    fn new(move_type: &str) -> Result<Self> {
+        let move_type = StructTag::from_str(move_type)
+            .map_err(|err| anyhow!("Invalid type filter {}: {}", move_type, err))?;
+        Ok(Self {
+            match_any_type_args: move_type.type_params.is_empty(),
+            move_type: move_type.to_string(),
+        })
+    }
+
+    fn matches(&self, type_str: &str) -> bool {
+        match type_str.strip_prefix(self.move_type.as_str()) {
+            Some("") => true,
+            Some(type_args) => self.match_any_type_args && type_args.starts_with('<'),
+            None => false,
+        }
+    }
+}
+
+/// Standardizes an address to the short form used in transactions, e.g. `0x1`.
+fn standardize_address(address: &str) -> Result<String> {
+    let hex = address.strip_prefix("0x").unwrap_or(address);
+    ensure!(
+        !hex.is_empty() && hex.len() <= 64 && hex.chars().all(|c| c.is_ascii_hexdigit()),
+        "Invalid address {}",
+        address
+    );
+    let trimmed = hex.trim_start_matches('0');
+    Ok(format!(
+        "0x{}",
+        if trimmed.is_empty() {
+            "0".to_string()
+        } else {
+            trimmed.to_lowercase()
+        }
+    ))
+}
+
+fn user_transaction_sender(transaction: &Transaction) -> Option<&str> {
+    match &transaction.txn_data {
+        Some(TxnData::User(user_transaction)) => user_transaction
+            .request
+            .as_ref()
+            .map(|request| request.sender.as_str()),
+        _ => None,
+    }
+}
+
+/// The entry function called, including the one wrapped in a multisig transaction.
+fn user_transaction_entry_function(transaction: &Transaction) -> Option<&EntryFunctionId> {
+    let payload = match &transaction.txn_data {
+        Some(TxnData::User(user_transaction)) => user_transaction
+            .request
+            .as_ref()
+            .and_then(|request| request.payload.as_ref())
+            .and_then(|payload| payload.payload.as_ref())?,
+        _ => return None,
+    };
+    match payload {
+        transaction_payload::Payload::EntryFunctionPayload(payload) => payload.function.as_ref(),
+        transaction_payload::Payload::MultisigPayload(payload) => payload
+            .transaction_payload
+            .as_ref()
+            .and_then(|payload| payload.payload.as_ref())
+            .and_then(|payload| match payload {
+                multisig_transaction_payload::Payload::EntryFunctionPayload(payload) => {
+                    payload.function.as_ref()
+                },
+            }),
+        _ => None,
+    }
+}
+
+fn transaction_events(transaction: &Transaction) -> &[Event] {
+    match &transaction.txn_data {
+        Some(TxnData::User(user_transaction)) => &user_transaction.events,
+        Some(TxnData::BlockMetadata(block_metadata)) => &block_metadata.events,
+        Some(TxnData::Genesis(genesis)) => &genesis.events,
+        _ => &[],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::transaction::v1::{
+        EntryFunctionPayload, MoveModuleId, TransactionInfo, TransactionPayload, UserTransaction,
+        UserTransactionRequest, WriteResource, WriteSetChange,
+    };
+
+    fn user_transaction(
+        sender: &str,
+        function: &str,
+        event_type: &str,
+        resource_type: &str,
+        success: bool,
+    ) -> Transaction {
+        let (address, rest) = function.split_once("::").unwrap();
+        let (module, name) = rest.split_once("::").unwrap();
+        Transaction {
+            info: Some(TransactionInfo {
+                success,
+                changes: vec![WriteSetChange {
+                    change: Some(Change::WriteResource(WriteResource {
+                        type_str: resource_type.to_string(),
+                        ..WriteResource::default()
+                    })),
+                    ..WriteSetChange::default()
+                }],
+                ..TransactionInfo::default()
+            }),
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    sender: sender.to_string(),
+                    payload: Some(TransactionPayload {
+                        payload: Some(transaction_payload::Payload::EntryFunctionPayload(
+                            EntryFunctionPayload {
+                                function: Some(EntryFunctionId {
+                                    module: Some(MoveModuleId {
+                                        address: address.to_string(),
+                                        name: module.to_string(),
+                                    }),
+                                    name: name.to_string(),
+                                }),
+                                ..EntryFunctionPayload::default()
+                            },
+                        )),
+                        ..TransactionPayload::default()
+                    }),
+                    ..UserTransactionRequest::default()
+                }),
+                events: vec![Event {
+                    type_str: event_type.to_string(),
+                    ..Event::default()
+                }],
+            })),
+            ..Transaction::default()
+        }
+    }
+
+    fn transfer() -> Transaction {
+        user_transaction(
+            "0xa550c18",
+            "0x1::aptos_account::transfer",
+            "0x1::coin::DepositEvent",
+            "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+            true,
+        )
+    }
+
+    fn filters(filters: Vec<TransactionFilterProto>) -> TransactionFilters {
+        TransactionFilters::new(&filters).unwrap()
+    }
+
+    #[test]
+    fn test_no_filters_match_everything() {
+        assert!(filters(vec![]).matches(&transfer()));
+        assert!(filters(vec![]).matches(&Transaction::default()));
+    }
+
+    #[test]
+    fn test_filter_by_sender() {
+        let matching = filters(vec![TransactionFilterProto {
+            sender: Some(
+                "0x000000000000000000000000000000000000000000000000000000000a550C18".into(),
+            ),
+            ..TransactionFilterProto::default()
+        }]);
+        assert!(matching.matches(&transfer()));
+        assert!(!matching.matches(&Transaction::default()));
+
+        let other = filters(vec![TransactionFilterProto {
+            sender: Some("0x2".into()),
+            ..TransactionFilterProto::default()
+        }]);
+        assert!(!other.matches(&transfer()));
+    }
+
+    #[test]
+    fn test_filter_by_entry_function() {
+        for (entry_function, expected) in [
+            ("0x1", true),
+            ("0x1::aptos_account", true),
+            ("0x01::aptos_account::transfer", true),
+            ("0x1::aptos_account::transfer_coins", false),
+            ("0x1::coin", false),
+            ("0x2", false),
+        ] {
+            let filter = filters(vec![TransactionFilterProto {
+                entry_function: Some(entry_function.into()),
+                ..TransactionFilterProto::default()
+            }]);
+            assert_eq!(filter.matches(&transfer()), expected, "{}", entry_function);
+        }
+    }
+
+    #[test]
+    fn test_filter_by_types() {
+        for (event_type, expected) in [
+            ("0x1::coin::DepositEvent", true),
+            ("0x0001::coin::DepositEvent", true),
+            ("0x1::coin::WithdrawEvent", false),
+        ] {
+            let filter = filters(vec![TransactionFilterProto {
+                event_type: Some(event_type.into()),
+                ..TransactionFilterProto::default()
+            }]);
+            assert_eq!(filter.matches(&transfer()), expected, "{}", event_type);
+        }
+
+        for (resource_type, expected) in [
+            ("0x1::coin::CoinStore", true),
+            ("0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>", true),
+            ("0x1::coin::CoinStore<0x01::aptos_coin::AptosCoin>", true),
+            ("0x1::coin::CoinStore< 0x1::aptos_coin::AptosCoin >", true),
+            ("0x1::coin::CoinStore<0x1234::usd::USD>", false),
+            ("0x1::account::Account", false),
+        ] {
+            let filter = filters(vec![TransactionFilterProto {
+                write_set_resource_type: Some(resource_type.into()),
+                ..TransactionFilterProto::default()
+            }]);
+            assert_eq!(filter.matches(&transfer()), expected, "{}", resource_type);
+        }
+
+        let pair = user_transaction(
+            "0xa550c18",
+            "0x1::aptos_account::transfer",
+            "0x1::coin::DepositEvent",
+            "0x1::pair::Pair<0x1::coin::CoinStoreV2, vector<u8>>",
+            true,
+        );
+        for (resource_type, expected) in [
+            ("0x1::pair::Pair", true),
+            ("0x1::pair::Pair<0x1::coin::CoinStoreV2,vector<u8>>", true),
+            ("0x1::pair::Pair<0x1::coin::CoinStore, vector<u8>>", false),
+            ("0x1::pair::Pai", false),
+        ] {
+            let filter = filters(vec![TransactionFilterProto {
+                write_set_resource_type: Some(resource_type.into()),
+                ..TransactionFilterProto::default()
+            }]);
+            assert_eq!(filter.matches(&pair), expected, "{}", resource_type);
+        }
+    }
+
+    #[test]
+    fn test_conditions_are_and_filters_are_or() {
+        let failed_transfers = filters(vec![TransactionFilterProto {
+            entry_function: Some("0x1::aptos_account::transfer".into()),
+            success: Some(false),
+            ..TransactionFilterProto::default()
+        }]);
+        assert!(!failed_transfers.matches(&transfer()));
+
+        let either = filters(vec![
+            TransactionFilterProto {
+                sender: Some("0x2".into()),
+                ..TransactionFilterProto::default()
+            },
+            TransactionFilterProto {
+                success: Some(true),
+                ..TransactionFilterProto::default()
+            },
+        ]);
+        assert!(either.matches(&transfer()));
+        assert_eq!(
+            either.filter(vec![Transaction::default(), transfer()]),
+            vec![transfer()]
+        );
+    }
+
+    #[test]
+    fn test_invalid_filters() {
+        for filter in [
+            TransactionFilterProto::default(),
+            TransactionFilterProto {
+                sender: Some("0xgg".into()),
+                ..TransactionFilterProto::default()
+            },
+            TransactionFilterProto {
+                entry_function: Some("0x1::coin::transfer::extra".into()),
+                ..TransactionFilterProto::default()
+            },
+            TransactionFilterProto {
+                event_type: Some("0x1::coin".into()),
+                ..TransactionFilterProto::default()
+            },
+            TransactionFilterProto {
+                write_set_resource_type: Some("0x1::coin::CoinStore<0xgg::usd::USD>".into()),
+                ..TransactionFilterProto::default()
+            },
+        ] {
+            assert!(TransactionFilters::new(&[filter]).is_err());
+        }
+        assert!(TransactionFilters::new(&vec![
+            TransactionFilterProto {
+                success: Some(true),
+                ..TransactionFilterProto::default()
+            };
+            MAX_TRANSACTION_FILTERS + 1
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,197 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{CheckResult, Checker, CheckerError, CommonCheckerConfig};
+use crate::{
+    get_provider,
+    provider::{api_latency::ApiLatencyProvider, Provider, ProviderCollection},
+};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiLatencySloCheckerConfig {
+    #[serde(flatten)]
+    pub common: CommonCheckerConfig,
+
+    /// If the p50 latency across all measured routes exceeds this value, it
+    /// will fail the evaluation.
+    pub max_p50_latency_ms: u64,
+
+    /// If the p99 latency across all measured routes exceeds this value, it
+    /// will fail the evaluation.
+    pub max_p99_latency_ms: u64,
+
+    /// The number of calls, across all routes, that are allowed to be errors.
+    #[serde(default)]
+    pub num_allowed_errors: u16,
+}
+
+#[derive(Debug)]
+pub struct ApiLatencySloChecker {
+    config: ApiLatencySloCheckerConfig,
+}
+
+impl ApiLatencySloChecker {
+    pub fn new(config: ApiLatencySloCheckerConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the given percentile of the latencies using the nearest-rank method.
+    /// The latencies must be sorted. If there are none, this returns 0.
+    fn percentile_ms(sorted_latencies: &[Duration], percentile: u64) -> u64 {
+        let rank = (percentile as usize * sorted_latencies.len() + 99) / 100;
+        sorted_latencies
+            .get(rank.max(1) - 1)
+            .map_or(0, |latency| latency.as_millis() as u64)
+    }
+}
+
+#[async_trait::async_trait]
+impl Checker for ApiLatencySloChecker {
+    /// Assert that the p50 and p99 latencies across a set of representative
+    /// API routes are within the configured SLOs.
+    async fn check(
+        &self,
+        providers: &ProviderCollection,
+    ) -> Result<Vec<CheckResult>, CheckerError> {
+        let target_api_latency_provider = get_provider!(
+            providers.target_api_latency_provider,
+            self.config.common.required,
+            ApiLatencyProvider
+        );
+
+        let route_latencies = match target_api_latency_provider.provide().await {
+            Ok(route_latencies) => route_latencies,
+            Err(err) => {
+                return Ok(vec![Self::build_result(
+                    "Failed to measure API latency".to_string(),
+                    0,
+                    format!("There was an error querying your node's API: {:#}", err),
+                )]);
+            },
+        };
+
+        let errors: Vec<String> = route_latencies
+            .iter()
+            .flat_map(|r| r.errors.iter().map(move |e| format!("{}: {}", r.route, e)))
+            .collect();
+        let num_calls: usize = route_latencies
+            .iter()
+            .map(|r| r.latencies.len() + r.errors.len())
+            .sum();
+        if errors.len() > self.config.num_allowed_errors as usize || errors.len() == num_calls {
+            return Ok(vec![Self::build_result(
+                "Node returned too many errors while checking API latency".to_string(),
+                0,
+                format!(
+                    "The node returned {} errors out of {} calls while checking the latency \
+                    of a set of API routes, the tolerance was {} errors: {}",
+                    errors.len(),
+                    num_calls,
+                    self.config.num_allowed_errors,
+                    errors.join(", "),
+                ),
+            )]);
+        }
+
+        // Describe each route so operators can see which ones are slow.
+        let route_descriptions: Vec<String> = route_latencies
+            .iter()
+            .filter(|r| !r.latencies.is_empty())
+            .map(|r| {
+                let mut latencies = r.latencies.clone();
+                latencies.sort();
+                format!(
+                    "{} (p50: {}ms, p99: {}ms)",
+                    r.route,
+                    Self::percentile_ms(&latencies, 50),
+                    Self::percentile_ms(&latencies, 99)
+                )
+            })
+            .collect();
+
+        let mut latencies: Vec<Duration> = route_latencies
+            .into_iter()
+            .flat_map(|r| r.latencies)
+            .collect();
+        latencies.sort();
+        let p50 = Self::percentile_ms(&latencies, 50);
+        let p99 = Self::percentile_ms(&latencies, 99);
+
+        let exceeds_slo =
+            p50 > self.config.max_p50_latency_ms || p99 > self.config.max_p99_latency_ms;
+        let evaluation = if exceeds_slo {
+            Self::build_result(
+                "API latency exceeds the SLO".to_string(),
+                50,
+                format!(
+                    "Across {} calls, the p50 API latency was {}ms and the p99 API latency \
+                    was {}ms, but the maximum allowed latencies are {}ms and {}ms \
+                    respectively. Per route: {}.",
+                    latencies.len(),
+                    p50,
+                    p99,
+                    self.config.max_p50_latency_ms,
+                    self.config.max_p99_latency_ms,
+                    route_descriptions.join(", "),
+                ),
+            )
+        } else {
+            Self::build_result(
+                "API latency is within the SLO".to_string(),
+                100,
+                format!(
+                    "Across {} calls, the p50 API latency was {}ms and the p99 API latency \
+                    was {}ms, which is within the maximum allowed latencies of {}ms and \
+                    {}ms respectively. Per route: {}.",
+                    latencies.len(),
+                    p50,
+                    p99,
+                    self.config.max_p50_latency_ms,
+                    self.config.max_p99_latency_ms,
+                    route_descriptions.join(", "),
+                ),
+            )
+        };
+
+        Ok(vec![evaluation])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn latencies_ms(millis: impl IntoIterator<Item = u64>) -> Vec<Duration> {
+        millis.into_iter().map(Duration::from_millis).collect()
+    }
+
+    #[test]
+    fn test_percentile_ms_empty() {
+        assert_eq!(ApiLatencySloChecker::percentile_ms(&[], 50), 0);
+        assert_eq!(ApiLatencySloChecker::percentile_ms(&[], 99), 0);
+    }
+
+    #[test]
+    fn test_percentile_ms_one_sample() {
+        let latencies = latencies_ms([42]);
+        assert_eq!(ApiLatencySloChecker::percentile_ms(&latencies, 0), 42);
+        assert_eq!(ApiLatencySloChecker::percentile_ms(&latencies, 50), 42);
+        assert_eq!(ApiLatencySloChecker::percentile_ms(&latencies, 99), 42);
+    }
+
+    #[test]
+    fn test_percentile_ms_nearest_rank() {
+        let latencies = latencies_ms(1..=100);
+        assert_eq!(ApiLatencySloChecker::percentile_ms(&latencies, 50), 50);
+        assert_eq!(ApiLatencySloChecker::percentile_ms(&latencies, 99), 99);
+        assert_eq!(ApiLatencySloChecker::percentile_ms(&latencies, 100), 100);
+
+        let latencies = latencies_ms([10, 20, 30]);
+        assert_eq!(ApiLatencySloChecker::percentile_ms(&latencies, 50), 20);
+        assert_eq!(ApiLatencySloChecker::percentile_ms(&latencies, 99), 30);
+    }
+}
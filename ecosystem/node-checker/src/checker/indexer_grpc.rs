@@ -0,0 +1,237 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{CheckResult, Checker, CheckerError, CommonCheckerConfig};
+use crate::{
+    get_provider,
+    provider::{
+        api_index::ApiIndexProvider, indexer_grpc::IndexerGrpcProvider, Provider,
+        ProviderCollection,
+    },
+};
+use anyhow::Result;
+use aptos_rest_client::{aptos_api_types::TransactionOnChainData, Client as AptosRestClient};
+use serde::{Deserialize, Serialize};
+
+/// The API won't return more than this many transactions per page.
+const MAX_API_PAGE_SIZE: u64 = 100;
+
+/// How many mismatches to include in the explanation.
+const MAX_REPORTED_MISMATCHES: usize = 5;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct IndexerGrpcCheckerConfig {
+    #[serde(flatten)]
+    pub common: CommonCheckerConfig,
+}
+
+#[derive(Debug)]
+pub struct IndexerGrpcChecker {
+    config: IndexerGrpcCheckerConfig,
+}
+
+impl IndexerGrpcChecker {
+    pub fn new(config: IndexerGrpcCheckerConfig) -> Self {
+        Self { config }
+    }
+
+    /// Fetch the given range of transactions from the API, page by page.
+    async fn get_api_transactions(
+        client: &AptosRestClient,
+        starting_version: u64,
+        transactions_count: u64,
+    ) -> Result<Vec<TransactionOnChainData>> {
+        let mut transactions = Vec::with_capacity(transactions_count as usize);
+        while (transactions.len() as u64) < transactions_count {
+            let remaining = transactions_count - transactions.len() as u64;
+            let page = client
+                .get_transactions_bcs(
+                    Some(starting_version + transactions.len() as u64),
+                    Some(remaining.min(MAX_API_PAGE_SIZE) as u16),
+                )
+                .await?
+                .into_inner();
+            if page.is_empty() {
+                break;
+            }
+            transactions.extend(page);
+        }
+        Ok(transactions)
+    }
+}
+
+#[async_trait::async_trait]
+impl Checker for IndexerGrpcChecker {
+    /// Assert that the indexer gRPC fullnode service returns the same range of
+    /// transactions as the API of the same node.
+    async fn check(
+        &self,
+        providers: &ProviderCollection,
+    ) -> Result<Vec<CheckResult>, CheckerError> {
+        let target_api_index_provider = get_provider!(
+            providers.target_api_index_provider,
+            self.config.common.required,
+            ApiIndexProvider
+        );
+
+        let target_indexer_grpc_provider = get_provider!(
+            providers.target_indexer_grpc_provider,
+            self.config.common.required,
+            IndexerGrpcProvider
+        );
+
+        let output = match target_indexer_grpc_provider.provide().await {
+            Ok(output) => output,
+            Err(err) => {
+                return Ok(vec![Self::build_result(
+                    "Failed to stream transactions from indexer gRPC".to_string(),
+                    0,
+                    format!(
+                        "There was an error streaming transactions from the indexer \
+                        gRPC fullnode service of your node: {:#}",
+                        err
+                    ),
+                )]);
+            },
+        };
+
+        let api_chain_id = match target_api_index_provider.provide().await {
+            Ok(response) => response.chain_id,
+            Err(err) => {
+                return Ok(vec![Self::build_result(
+                    "Failed to determine chain ID of your node".to_string(),
+                    0,
+                    format!("There was an error querying your node's API: {:#}", err),
+                )]);
+            },
+        };
+
+        if output.chain_id != api_chain_id as u32 {
+            return Ok(vec![Self::build_result(
+                "Indexer gRPC returned the wrong chain ID".to_string(),
+                0,
+                format!(
+                    "The indexer gRPC fullnode service of your node reported chain ID {}, \
+                    but the API of your node reported chain ID {}. Make sure both are \
+                    served by the same node.",
+                    output.chain_id, api_chain_id
+                ),
+            )]);
+        }
+
+        if (output.transactions.len() as u64) < output.transactions_count {
+            return Ok(vec![Self::build_result(
+                "Indexer gRPC returned too few transactions".to_string(),
+                0,
+                format!(
+                    "We asked the indexer gRPC fullnode service of your node for {} \
+                    transactions starting at version {}, but the stream ended after {}.",
+                    output.transactions_count,
+                    output.starting_version,
+                    output.transactions.len()
+                ),
+            )]);
+        }
+
+        let api_transactions = match Self::get_api_transactions(
+            &target_api_index_provider.client,
+            output.starting_version,
+            output.transactions_count,
+        )
+        .await
+        {
+            Ok(api_transactions) => api_transactions,
+            Err(err) => {
+                return Ok(vec![Self::build_result(
+                    "Failed to fetch transactions from the API".to_string(),
+                    0,
+                    format!(
+                        "There was an error fetching transactions {} to {} from \
+                        your node's API: {:#}",
+                        output.starting_version,
+                        output.starting_version + output.transactions_count - 1,
+                        err
+                    ),
+                )]);
+            },
+        };
+
+        let mut mismatches = vec![];
+        for (index, grpc_transaction) in output.transactions.iter().enumerate() {
+            let expected_version = output.starting_version + index as u64;
+            if grpc_transaction.version != expected_version {
+                mismatches.push(format!(
+                    "expected version {} but got version {}",
+                    expected_version, grpc_transaction.version
+                ));
+                continue;
+            }
+            let api_transaction = match api_transactions.get(index) {
+                Some(api_transaction) => api_transaction,
+                None => {
+                    mismatches.push(format!(
+                        "version {} was not returned by the API",
+                        expected_version
+                    ));
+                    continue;
+                },
+            };
+            let (hash, accumulator_root_hash) = match &grpc_transaction.info {
+                Some(info) => (&info.hash, &info.accumulator_root_hash),
+                None => {
+                    mismatches.push(format!(
+                        "version {} is missing its transaction info",
+                        expected_version
+                    ));
+                    continue;
+                },
+            };
+            if *hash != api_transaction.info.transaction_hash().to_vec() {
+                mismatches.push(format!(
+                    "version {} has a different transaction hash",
+                    expected_version
+                ));
+            } else if *accumulator_root_hash != api_transaction.accumulator_root_hash.to_vec() {
+                mismatches.push(format!(
+                    "version {} has a different accumulator root hash",
+                    expected_version
+                ));
+            }
+        }
+
+        let evaluation = if mismatches.is_empty() {
+            Self::build_result(
+                "Indexer gRPC matches the API".to_string(),
+                100,
+                format!(
+                    "We streamed {} transactions starting at version {} from the indexer \
+                    gRPC fullnode service of your node and they all matched the \
+                    transactions returned by your node's API. Great!",
+                    output.transactions_count, output.starting_version
+                ),
+            )
+        } else {
+            Self::build_result(
+                "Indexer gRPC does not match the API".to_string(),
+                0,
+                format!(
+                    "We streamed {} transactions starting at version {} from the indexer \
+                    gRPC fullnode service of your node, but {} of them did not match the \
+                    transactions returned by your node's API: {}",
+                    output.transactions_count,
+                    output.starting_version,
+                    mismatches.len(),
+                    mismatches
+                        .iter()
+                        .take(MAX_REPORTED_MISMATCHES)
+                        .cloned()
+                        .collect::<Vec<_>>()
+                        .join(", "),
+                ),
+            )
+        };
+
+        Ok(vec![evaluation])
+    }
+}
@@ -1,15 +1,18 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+mod api_latency_slo;
 mod build_version;
 mod consensus_proposals;
 mod consensus_round;
 mod consensus_timeouts;
 mod handshake;
 mod hardware;
+mod indexer_grpc;
 mod latency;
 mod minimum_peers;
 mod node_identity;
+mod state_sync_catch_up;
 mod state_sync_version;
 mod tps;
 mod traits;
@@ -17,15 +20,18 @@ mod transaction_correctness;
 mod types;
 
 use self::{
+    api_latency_slo::{ApiLatencySloChecker, ApiLatencySloCheckerConfig},
     build_version::{BuildVersionChecker, BuildVersionCheckerConfig},
     consensus_proposals::{ConsensusProposalsChecker, ConsensusProposalsCheckerConfig},
     consensus_round::{ConsensusRoundChecker, ConsensusRoundCheckerConfig},
     consensus_timeouts::{ConsensusTimeoutsChecker, ConsensusTimeoutsCheckerConfig},
     handshake::{HandshakeChecker, HandshakeCheckerConfig},
     hardware::{HardwareChecker, HardwareCheckerConfig},
+    indexer_grpc::{IndexerGrpcChecker, IndexerGrpcCheckerConfig},
     latency::{LatencyChecker, LatencyCheckerConfig},
     minimum_peers::{MinimumPeersChecker, MinimumPeersCheckerConfig},
     node_identity::{NodeIdentityChecker, NodeIdentityCheckerConfig},
+    state_sync_catch_up::{StateSyncCatchUpChecker, StateSyncCatchUpCheckerConfig},
     state_sync_version::{StateSyncVersionChecker, StateSyncVersionCheckerConfig},
     tps::{TpsChecker, TpsCheckerConfig},
     transaction_correctness::{TransactionCorrectnessChecker, TransactionCorrectnessCheckerConfig},
@@ -40,15 +46,18 @@ pub use types::{CheckResult, CheckSummary};
 #[serde(tag = "type")]
 #[serde(deny_unknown_fields)]
 pub enum CheckerConfig {
+    ApiLatencySlo(ApiLatencySloCheckerConfig),
     BuildVersion(BuildVersionCheckerConfig),
     ConsensusProposals(ConsensusProposalsCheckerConfig),
     ConsensusRound(ConsensusRoundCheckerConfig),
     ConsensusTimeouts(ConsensusTimeoutsCheckerConfig),
     Handshake(HandshakeCheckerConfig),
     Hardware(HardwareCheckerConfig),
+    IndexerGrpc(IndexerGrpcCheckerConfig),
     Latency(LatencyCheckerConfig),
     MinimumPeers(MinimumPeersCheckerConfig),
     NodeIdentity(NodeIdentityCheckerConfig),
+    StateSyncCatchUp(StateSyncCatchUpCheckerConfig),
     StateSyncVersion(StateSyncVersionCheckerConfig),
     Tps(TpsCheckerConfig),
     TransactionCorrectness(TransactionCorrectnessCheckerConfig),
@@ -57,6 +66,7 @@ pub enum CheckerConfig {
 impl CheckerConfig {
     pub fn try_into_boxed_checker(self) -> Result<Box<dyn Checker>, anyhow::Error> {
         match self {
+            Self::ApiLatencySlo(config) => Ok(Box::new(ApiLatencySloChecker::new(config))),
             Self::BuildVersion(config) => Ok(Box::new(BuildVersionChecker::new(config))),
             Self::ConsensusProposals(config) => {
                 Ok(Box::new(ConsensusProposalsChecker::new(config)))
@@ -65,9 +75,11 @@ impl CheckerConfig {
             Self::ConsensusTimeouts(config) => Ok(Box::new(ConsensusTimeoutsChecker::new(config))),
             Self::Handshake(config) => Ok(Box::new(HandshakeChecker::new(config))),
             Self::Hardware(config) => Ok(Box::new(HardwareChecker::new(config))),
+            Self::IndexerGrpc(config) => Ok(Box::new(IndexerGrpcChecker::new(config))),
             Self::Latency(config) => Ok(Box::new(LatencyChecker::new(config))),
             Self::MinimumPeers(config) => Ok(Box::new(MinimumPeersChecker::new(config))),
             Self::NodeIdentity(config) => Ok(Box::new(NodeIdentityChecker::new(config))),
+            Self::StateSyncCatchUp(config) => Ok(Box::new(StateSyncCatchUpChecker::new(config))),
             Self::StateSyncVersion(config) => Ok(Box::new(StateSyncVersionChecker::new(config))),
             Self::Tps(config) => Ok(Box::new(TpsChecker::new(config)?)),
             Self::TransactionCorrectness(config) => {
@@ -0,0 +1,334 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{CheckResult, Checker, CheckerError, CommonCheckerConfig};
+use crate::{
+    get_provider,
+    provider::{api_index::ApiIndexProvider, Provider, ProviderCollection},
+};
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tokio::time::{Duration, Instant};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StateSyncCatchUpCheckerConfig {
+    #[serde(flatten)]
+    pub common: CommonCheckerConfig,
+
+    /// If the target is within this many versions of the baseline, we consider
+    /// it caught up and don't look at the catch up rate.
+    #[serde(default = "StateSyncCatchUpCheckerConfig::default_version_delta_tolerance")]
+    pub version_delta_tolerance: u64,
+
+    /// How long to measure the sync rate of both nodes for. This is longer than
+    /// the usual check delay since rates over a few seconds are noisy.
+    #[serde(default = "StateSyncCatchUpCheckerConfig::default_measurement_duration_secs")]
+    pub measurement_duration_secs: u64,
+
+    /// If, at the measured rate, the target would take longer than this to
+    /// catch up to the baseline, it will fail the evaluation.
+    #[serde(default = "StateSyncCatchUpCheckerConfig::default_max_catch_up_time_secs")]
+    pub max_catch_up_time_secs: u64,
+}
+
+impl StateSyncCatchUpCheckerConfig {
+    const fn default_version_delta_tolerance() -> u64 {
+        5000
+    }
+
+    const fn default_measurement_duration_secs() -> u64 {
+        15
+    }
+
+    const fn default_max_catch_up_time_secs() -> u64 {
+        3600
+    }
+}
+
+#[derive(Debug)]
+pub struct StateSyncCatchUpChecker {
+    config: StateSyncCatchUpCheckerConfig,
+}
+
+impl StateSyncCatchUpChecker {
+    pub fn new(config: StateSyncCatchUpCheckerConfig) -> Self {
+        Self { config }
+    }
+
+    fn build_state_sync_catch_up_check_result(
+        &self,
+        previous_target_version: u64,
+        latest_target_version: u64,
+        previous_baseline_version: u64,
+        latest_baseline_version: u64,
+        elapsed: Duration,
+    ) -> CheckResult {
+        // We convert to i64 to avoid potential overflow if somehow the ledger version went backwards
+        // or the target is ahead of the baseline.
+        let target_progress = latest_target_version as i64 - previous_target_version as i64;
+        let baseline_progress = latest_baseline_version as i64 - previous_baseline_version as i64;
+        let delta_from_baseline = latest_baseline_version as i64 - latest_target_version as i64;
+
+        if target_progress < 0 {
+            return Self::build_result(
+                "Ledger version went backwards!".to_string(),
+                0,
+                format!(
+                    "Successfully pulled ledger version from your node twice, \
+                    but the second time the ledger version went backwards! \
+                    First datapoint: {}, second datapoint: {}",
+                    previous_target_version, latest_target_version
+                ),
+            );
+        }
+
+        let elapsed_secs = elapsed.as_secs_f64();
+        let target_rate = target_progress as f64 / elapsed_secs;
+        let baseline_rate = baseline_progress as f64 / elapsed_secs;
+
+        if target_progress == 0 && baseline_progress > 0 {
+            return Self::build_result(
+                "Node is not syncing".to_string(),
+                0,
+                format!(
+                    "Over {:.1} seconds the baseline node synced {} versions but your node \
+                    stayed at version {}, so it is not making progress.",
+                    elapsed_secs, baseline_progress, latest_target_version,
+                ),
+            );
+        }
+
+        if delta_from_baseline <= self.config.version_delta_tolerance as i64 {
+            return Self::build_result(
+                "Node is caught up".to_string(),
+                100,
+                format!(
+                    "Your node is {} versions behind the baseline node, which is within \
+                    the allowed lag of {} versions. Over {:.1} seconds your node synced \
+                    {:.0} versions per second and the baseline synced {:.0} versions \
+                    per second.",
+                    delta_from_baseline.max(0),
+                    self.config.version_delta_tolerance,
+                    elapsed_secs,
+                    target_rate,
+                    baseline_rate,
+                ),
+            );
+        }
+
+        let catch_up_rate = target_rate - baseline_rate;
+        if catch_up_rate <= 0.0 {
+            return Self::build_result(
+                "Node is not catching up".to_string(),
+                0,
+                format!(
+                    "Your node is {} versions behind the baseline node, more than the \
+                    allowed lag of {} versions, and it is not closing the gap. Over {:.1} \
+                    seconds your node synced {:.0} versions per second while the baseline \
+                    synced {:.0} versions per second.",
+                    delta_from_baseline,
+                    self.config.version_delta_tolerance,
+                    elapsed_secs,
+                    target_rate,
+                    baseline_rate,
+                ),
+            );
+        }
+
+        let catch_up_time_secs = delta_from_baseline as f64 / catch_up_rate;
+        if catch_up_time_secs > self.config.max_catch_up_time_secs as f64 {
+            Self::build_result(
+                "Node is catching up too slowly".to_string(),
+                50,
+                format!(
+                    "Your node is {} versions behind the baseline node and is closing the \
+                    gap by {:.0} versions per second ({:.0} vs {:.0} versions per second), \
+                    so it would take about {:.0} seconds to catch up, more than the allowed \
+                    {} seconds.",
+                    delta_from_baseline,
+                    catch_up_rate,
+                    target_rate,
+                    baseline_rate,
+                    catch_up_time_secs,
+                    self.config.max_catch_up_time_secs,
+                ),
+            )
+        } else {
+            Self::build_result(
+                "Node is catching up".to_string(),
+                100,
+                format!(
+                    "Your node is {} versions behind the baseline node but is closing the \
+                    gap by {:.0} versions per second ({:.0} vs {:.0} versions per second), \
+                    so it should catch up in about {:.0} seconds, within the allowed {} \
+                    seconds.",
+                    delta_from_baseline,
+                    catch_up_rate,
+                    target_rate,
+                    baseline_rate,
+                    catch_up_time_secs,
+                    self.config.max_catch_up_time_secs,
+                ),
+            )
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Checker for StateSyncCatchUpChecker {
+    /// Measure how fast the target node syncs relative to the baseline node and,
+    /// if it is behind, estimate how long it will take to catch up.
+    async fn check(
+        &self,
+        providers: &ProviderCollection,
+    ) -> Result<Vec<CheckResult>, CheckerError> {
+        // Assert we have both a baseline and target API index provider.
+        let baseline_api_index_provider = get_provider!(
+            providers.baseline_api_index_provider,
+            self.config.common.required,
+            ApiIndexProvider
+        );
+
+        let target_api_index_provider = get_provider!(
+            providers.target_api_index_provider,
+            self.config.common.required,
+            ApiIndexProvider
+        );
+
+        // Get the first datapoint from both nodes. As with the other state sync
+        // checks, baseline failures are our problem, so we return an error.
+        let previous_target_version = match target_api_index_provider.provide().await {
+            Ok(response) => response.ledger_version.0,
+            Err(err) => {
+                return Ok(vec![Self::build_result(
+                    "Failed to determine state sync catch up rate".to_string(),
+                    0,
+                    format!("There was an error querying your node's API: {:#}", err),
+                )]);
+            },
+        };
+        let previous_baseline_version = baseline_api_index_provider
+            .provide()
+            .await?
+            .ledger_version
+            .0;
+        let start = Instant::now();
+
+        // Now wait.
+        tokio::time::sleep(Duration::from_secs(self.config.measurement_duration_secs)).await;
+
+        // Get the second datapoint from both nodes.
+        let latest_target_version = match target_api_index_provider.provide().await {
+            Ok(response) => response.ledger_version.0,
+            Err(err) => {
+                return Ok(vec![Self::build_result(
+                    "Failed to determine state sync catch up rate".to_string(),
+                    0,
+                    format!("There was an error querying your node's API: {:#}", err),
+                )]);
+            },
+        };
+        let latest_baseline_version = baseline_api_index_provider
+            .provide()
+            .await?
+            .ledger_version
+            .0;
+
+        // Evaluate the data, returning a check result.
+        Ok(vec![self.build_state_sync_catch_up_check_result(
+            previous_target_version,
+            latest_target_version,
+            previous_baseline_version,
+            latest_baseline_version,
+            start.elapsed(),
+        )])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn checker() -> StateSyncCatchUpChecker {
+        StateSyncCatchUpChecker::new(StateSyncCatchUpCheckerConfig {
+            common: CommonCheckerConfig::default(),
+            version_delta_tolerance: 100,
+            measurement_duration_secs: 10,
+            max_catch_up_time_secs: 60,
+        })
+    }
+
+    fn check(
+        previous_target_version: u64,
+        latest_target_version: u64,
+        previous_baseline_version: u64,
+        latest_baseline_version: u64,
+    ) -> CheckResult {
+        checker().build_state_sync_catch_up_check_result(
+            previous_target_version,
+            latest_target_version,
+            previous_baseline_version,
+            latest_baseline_version,
+            Duration::from_secs(10),
+        )
+    }
+
+    #[test]
+    fn test_caught_up() {
+        let result = check(1000, 1100, 1050, 1150);
+        assert_eq!(result.headline, "Node is caught up");
+        assert_eq!(result.score, 100);
+    }
+
+    #[test]
+    fn test_ahead_of_baseline() {
+        let result = check(2000, 2100, 1000, 1100);
+        assert_eq!(result.headline, "Node is caught up");
+        assert_eq!(result.score, 100);
+    }
+
+    #[test]
+    fn test_stalled_within_tolerance() {
+        let result = check(1000, 1000, 1000, 1050);
+        assert_eq!(result.headline, "Node is not syncing");
+        assert_eq!(result.score, 0);
+    }
+
+    #[test]
+    fn test_both_idle() {
+        let result = check(1000, 1000, 1000, 1000);
+        assert_eq!(result.headline, "Node is caught up");
+        assert_eq!(result.score, 100);
+    }
+
+    #[test]
+    fn test_went_backwards() {
+        let result = check(1000, 900, 1000, 1100);
+        assert_eq!(result.headline, "Ledger version went backwards!");
+        assert_eq!(result.score, 0);
+    }
+
+    #[test]
+    fn test_not_catching_up() {
+        let result = check(0, 100, 1000, 1100);
+        assert_eq!(result.headline, "Node is not catching up");
+        assert_eq!(result.score, 0);
+    }
+
+    #[test]
+    fn test_catching_up() {
+        // 500 versions behind, closing the gap at 50 versions per second.
+        let result = check(0, 1500, 1000, 2000);
+        assert_eq!(result.headline, "Node is catching up");
+        assert_eq!(result.score, 100);
+    }
+
+    #[test]
+    fn test_catching_up_too_slowly() {
+        // 9000 versions behind, closing the gap at 10 versions per second.
+        let result = check(0, 1100, 10000, 11000);
+        assert_eq!(result.headline, "Node is catching up too slowly");
+        assert_eq!(result.score, 50);
+    }
+}
@@ -29,6 +29,9 @@ pub struct NodeAddress {
     /// If that Checker is not enabled, this is not necessary.
     public_key: Option<x25519::PublicKey>,
 
+    /// Indexer gRPC fullnode service port.
+    indexer_grpc_port: Option<u16>,
+
     // Cookie store.
     #[serde(skip)]
     cookie_store: Arc<Jar>,
@@ -41,6 +44,7 @@ impl NodeAddress {
         metrics_port: Option<u16>,
         noise_port: Option<u16>,
         public_key: Option<x25519::PublicKey>,
+        indexer_grpc_port: Option<u16>,
     ) -> Self {
         Self {
             url,
@@ -48,6 +52,7 @@ impl NodeAddress {
             metrics_port,
             noise_port,
             public_key,
+            indexer_grpc_port,
             cookie_store: Arc::new(Jar::default()),
         }
     }
@@ -70,6 +75,11 @@ impl NodeAddress {
         self.public_key
     }
 
+    /// Do not use this to build a client, use get_indexer_grpc_url.
+    pub fn get_indexer_grpc_port(&self) -> Option<u16> {
+        self.indexer_grpc_port
+    }
+
     pub fn get_api_url(&self) -> Result<Url> {
         let mut url = self.url.clone();
         url.set_port(Some(
@@ -80,6 +90,15 @@ impl NodeAddress {
         Ok(url)
     }
 
+    pub fn get_indexer_grpc_url(&self) -> Result<Url> {
+        let mut url = self.url.clone();
+        url.set_port(Some(self.indexer_grpc_port.context(
+            "Can't build indexer gRPC URL without an indexer gRPC port",
+        )?))
+        .unwrap();
+        Ok(url)
+    }
+
     pub fn get_metrics_url(&self, path: &str) -> Result<Url> {
         let mut url = self.url.clone();
         url.set_port(Some(
@@ -104,6 +123,19 @@ impl NodeAddress {
         }
     }
 
+    /// Build a plain HTTP client for talking to the API. Prefer get_api_client
+    /// unless you need to hit arbitrary API routes.
+    pub fn get_api_http_client(&self, timeout: Duration) -> Result<reqwest::Client> {
+        match self.api_port {
+            Some(_) => Ok(reqwest::ClientBuilder::new()
+                .timeout(timeout)
+                .cookie_provider(self.cookie_store.clone())
+                .build()
+                .unwrap()),
+            None => Err(anyhow!("Cannot build API client without an API port")),
+        }
+    }
+
     pub fn get_api_client(&self, timeout: Duration) -> Result<AptosRestClient> {
         let client = reqwest::ClientBuilder::new()
             .timeout(timeout)
@@ -0,0 +1,167 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    cache::OutputCache,
+    traits::{Provider, ProviderError},
+    CommonProviderConfig,
+};
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use tokio::time::Instant;
+use url::Url;
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ApiLatencyProviderConfig {
+    #[serde(default, flatten)]
+    pub common: CommonProviderConfig,
+
+    /// The API routes to measure, including the version prefix and any query
+    /// params, e.g. /v1/accounts/0x1.
+    #[serde(default = "ApiLatencyProviderConfig::default_routes")]
+    pub routes: Vec<String>,
+
+    /// The number of times to hit each route.
+    #[serde(default = "ApiLatencyProviderConfig::default_num_samples")]
+    pub num_samples: u16,
+
+    /// The delay between each round of calls.
+    #[serde(default = "ApiLatencyProviderConfig::default_delay_between_samples_ms")]
+    pub delay_between_samples_ms: u64,
+}
+
+impl ApiLatencyProviderConfig {
+    fn default_routes() -> Vec<String> {
+        vec![
+            "/v1".to_string(),
+            "/v1/transactions?limit=25".to_string(),
+            "/v1/accounts/0x1".to_string(),
+            "/v1/accounts/0x1/resource/0x1::chain_id::ChainId".to_string(),
+            "/v1/accounts/0x1/modules?limit=10".to_string(),
+            "/v1/estimate_gas_price".to_string(),
+        ]
+    }
+
+    const fn default_num_samples() -> u16 {
+        10
+    }
+
+    const fn default_delay_between_samples_ms() -> u64 {
+        50
+    }
+}
+
+impl Default for ApiLatencyProviderConfig {
+    fn default() -> Self {
+        Self {
+            common: CommonProviderConfig::default(),
+            routes: Self::default_routes(),
+            num_samples: Self::default_num_samples(),
+            delay_between_samples_ms: Self::default_delay_between_samples_ms(),
+        }
+    }
+}
+
+/// The latencies we measured for a single route.
+#[derive(Clone, Debug)]
+pub struct RouteLatencies {
+    pub route: String,
+
+    /// The RTT of each successful call, including reading the body.
+    pub latencies: Vec<Duration>,
+
+    /// The errors returned by the calls that failed.
+    pub errors: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ApiLatencyProvider {
+    pub config: ApiLatencyProviderConfig,
+
+    pub client: reqwest::Client,
+
+    /// The base URL of the API, including the port.
+    pub api_url: Url,
+
+    // Measuring takes a while, so we don't want several Checkers to each
+    // trigger their own round of calls.
+    output_cache: Arc<OutputCache<Vec<RouteLatencies>>>,
+}
+
+impl ApiLatencyProvider {
+    pub fn new(config: ApiLatencyProviderConfig, client: reqwest::Client, api_url: Url) -> Self {
+        let output_cache = Arc::new(OutputCache::new(Duration::from_millis(
+            config.common.cache_ttl_ms,
+        )));
+        Self {
+            config,
+            client,
+            api_url,
+            output_cache,
+        }
+    }
+
+    async fn get_latency_datapoint(&self, url: Url) -> Result<Duration> {
+        let start = Instant::now();
+        self.client
+            .get(url)
+            .send()
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?;
+        Ok(start.elapsed())
+    }
+
+    async fn measure(&self) -> Result<Vec<RouteLatencies>, ProviderError> {
+        let urls = self
+            .config
+            .routes
+            .iter()
+            .map(|route| {
+                self.api_url
+                    .join(route)
+                    .with_context(|| format!("Failed to build URL for API route {}", route))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut route_latencies: Vec<_> = self
+            .config
+            .routes
+            .iter()
+            .map(|route| RouteLatencies {
+                route: route.clone(),
+                latencies: vec![],
+                errors: vec![],
+            })
+            .collect();
+
+        for _ in 0..self.config.num_samples {
+            for (url, route_latencies) in urls.iter().zip(route_latencies.iter_mut()) {
+                match self.get_latency_datapoint(url.clone()).await {
+                    Ok(latency) => route_latencies.latencies.push(latency),
+                    Err(e) => route_latencies.errors.push(format!("{:#}", e)),
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(self.config.delay_between_samples_ms)).await;
+        }
+
+        Ok(route_latencies)
+    }
+}
+
+#[async_trait]
+impl Provider for ApiLatencyProvider {
+    type Output = Vec<RouteLatencies>;
+
+    async fn provide(&self) -> Result<Self::Output, ProviderError> {
+        self.output_cache.get(self.measure()).await
+    }
+
+    fn explanation() -> &'static str {
+        "The API port was not included in the request."
+    }
+}
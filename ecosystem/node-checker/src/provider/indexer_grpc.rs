@@ -0,0 +1,191 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{
+    api_index::ApiIndexProvider,
+    cache::OutputCache,
+    traits::{Provider, ProviderError},
+    CommonProviderConfig,
+};
+use anyhow::{anyhow, Result};
+use aptos_protos::{
+    internal::fullnode::v1::{
+        fullnode_data_client::FullnodeDataClient, transactions_from_node_response::Response,
+        GetTransactionsFromNodeRequest,
+    },
+    transaction::v1::Transaction,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use url::Url;
+
+const GET_TRANSACTIONS_FROM_NODE_ENDPOINT: &str = "GetTransactionsFromNode";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct IndexerGrpcProviderConfig {
+    #[serde(default, flatten)]
+    pub common: CommonProviderConfig,
+
+    /// How many of the most recent transactions to stream from the node.
+    #[serde(default = "IndexerGrpcProviderConfig::default_num_transactions")]
+    pub num_transactions: u64,
+
+    /// How long to wait for the whole stream before giving up.
+    #[serde(default = "IndexerGrpcProviderConfig::default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl IndexerGrpcProviderConfig {
+    const fn default_num_transactions() -> u64 {
+        100
+    }
+
+    const fn default_timeout_secs() -> u64 {
+        10
+    }
+}
+
+impl Default for IndexerGrpcProviderConfig {
+    fn default() -> Self {
+        Self {
+            common: CommonProviderConfig::default(),
+            num_transactions: Self::default_num_transactions(),
+            timeout_secs: Self::default_timeout_secs(),
+        }
+    }
+}
+
+/// A range of transactions streamed from the indexer gRPC fullnode service.
+#[derive(Clone, Debug)]
+pub struct IndexerGrpcOutput {
+    /// The chain ID the node included in its responses.
+    pub chain_id: u32,
+
+    /// The version we asked the stream to start from.
+    pub starting_version: u64,
+
+    /// How many transactions we asked for.
+    pub transactions_count: u64,
+
+    /// The transactions we got back, in the order the node sent them.
+    pub transactions: Vec<Transaction>,
+}
+
+#[derive(Clone, Debug)]
+pub struct IndexerGrpcProvider {
+    pub config: IndexerGrpcProviderConfig,
+
+    /// The URL of the indexer gRPC fullnode service, including the port.
+    pub url: Url,
+
+    /// An API index provider. We use this to figure out which versions to stream.
+    pub api_index_provider: Arc<ApiIndexProvider>,
+
+    output_cache: Arc<OutputCache<IndexerGrpcOutput>>,
+}
+
+impl IndexerGrpcProvider {
+    pub fn new(
+        config: IndexerGrpcProviderConfig,
+        url: Url,
+        api_index_provider: Arc<ApiIndexProvider>,
+    ) -> Self {
+        let output_cache = Arc::new(OutputCache::new(Duration::from_millis(
+            config.common.cache_ttl_ms,
+        )));
+        Self {
+            config,
+            url,
+            api_index_provider,
+            output_cache,
+        }
+    }
+
+    async fn stream_transactions(
+        &self,
+        starting_version: u64,
+        transactions_count: u64,
+    ) -> Result<IndexerGrpcOutput, ProviderError> {
+        let mut client = FullnodeDataClient::connect(self.url.to_string())
+            .await
+            .map_err(|e| {
+                ProviderError::RetryableEndpointError(GET_TRANSACTIONS_FROM_NODE_ENDPOINT, e.into())
+            })?
+            .max_decoding_message_size(usize::MAX);
+
+        let mut stream = client
+            .get_transactions_from_node(GetTransactionsFromNodeRequest {
+                starting_version: Some(starting_version),
+                transactions_count: Some(transactions_count),
+            })
+            .await
+            .map_err(|e| {
+                ProviderError::RetryableEndpointError(GET_TRANSACTIONS_FROM_NODE_ENDPOINT, e.into())
+            })?
+            .into_inner();
+
+        let mut chain_id = None;
+        let mut transactions = vec![];
+        while let Some(response) = stream.message().await.map_err(|e| {
+            ProviderError::RetryableEndpointError(GET_TRANSACTIONS_FROM_NODE_ENDPOINT, e.into())
+        })? {
+            chain_id = Some(response.chain_id);
+            if let Some(Response::Data(output)) = response.response {
+                transactions.extend(output.transactions);
+            }
+            if transactions.len() as u64 >= transactions_count {
+                break;
+            }
+        }
+        transactions.truncate(transactions_count as usize);
+
+        Ok(IndexerGrpcOutput {
+            chain_id: chain_id.ok_or_else(|| {
+                ProviderError::NonRetryableEndpointError(
+                    GET_TRANSACTIONS_FROM_NODE_ENDPOINT,
+                    anyhow!("The stream ended without returning any responses"),
+                )
+            })?,
+            starting_version,
+            transactions_count,
+            transactions,
+        })
+    }
+}
+
+#[async_trait]
+impl Provider for IndexerGrpcProvider {
+    type Output = IndexerGrpcOutput;
+
+    async fn provide(&self) -> Result<Self::Output, ProviderError> {
+        // Stream the most recent transactions the node says it has, since those
+        // are the ones least likely to have been pruned.
+        let ledger_version = self.api_index_provider.provide().await?.ledger_version.0;
+        let starting_version = ledger_version
+            .saturating_add(1)
+            .saturating_sub(self.config.num_transactions);
+        let transactions_count = ledger_version + 1 - starting_version;
+
+        self.output_cache
+            .get(async {
+                tokio::time::timeout(
+                    Duration::from_secs(self.config.timeout_secs),
+                    self.stream_transactions(starting_version, transactions_count),
+                )
+                .await
+                .map_err(|e| {
+                    ProviderError::RetryableEndpointError(
+                        GET_TRANSACTIONS_FROM_NODE_ENDPOINT,
+                        e.into(),
+                    )
+                })?
+            })
+            .await
+    }
+
+    fn explanation() -> &'static str {
+        "The indexer gRPC port was not included in the request or the API port was not included in the request."
+    }
+}
@@ -5,8 +5,10 @@
 //! in their constructors.
 
 pub mod api_index;
+pub mod api_latency;
 mod cache;
 mod helpers;
+pub mod indexer_grpc;
 pub mod metrics;
 pub mod noise;
 mod provider_collection;
@@ -14,8 +16,9 @@ pub mod system_information;
 mod traits;
 
 use self::{
-    api_index::ApiIndexProviderConfig, metrics::MetricsProviderConfig, noise::NoiseProviderConfig,
-    system_information::SystemInformationProviderConfig,
+    api_index::ApiIndexProviderConfig, api_latency::ApiLatencyProviderConfig,
+    indexer_grpc::IndexerGrpcProviderConfig, metrics::MetricsProviderConfig,
+    noise::NoiseProviderConfig, system_information::SystemInformationProviderConfig,
 };
 pub use helpers::MISSING_PROVIDER_MESSAGE;
 pub use provider_collection::ProviderCollection;
@@ -37,6 +40,10 @@ pub struct ProviderConfigs {
     pub system_information: SystemInformationProviderConfig,
 
     pub noise: NoiseProviderConfig,
+
+    pub api_latency: ApiLatencyProviderConfig,
+
+    pub indexer_grpc: IndexerGrpcProviderConfig,
 }
 
 #[derive(Copy, Clone, Debug, Deserialize, Serialize)]
@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::{
-    api_index::ApiIndexProvider, metrics::MetricsProvider, noise::NoiseProvider,
+    api_index::ApiIndexProvider, api_latency::ApiLatencyProvider,
+    indexer_grpc::IndexerGrpcProvider, metrics::MetricsProvider, noise::NoiseProvider,
     system_information::SystemInformationProvider,
 };
 use std::sync::Arc;
@@ -50,6 +51,14 @@ pub struct ProviderCollection {
 
     /// Provider that wraps functionality for connecting to the node via noise.
     pub target_noise_provider: Option<NoiseProvider>,
+
+    /// Provider that measures the latency of a set of API routes. Only the
+    /// target node is measured, so there is no baseline variant.
+    pub target_api_latency_provider: Option<ApiLatencyProvider>,
+
+    /// Provider that streams transactions from the indexer gRPC fullnode service.
+    /// Only the target node is checked, so there is no baseline variant.
+    pub target_indexer_grpc_provider: Option<IndexerGrpcProvider>,
 }
 
 impl ProviderCollection {
@@ -63,6 +72,8 @@ impl ProviderCollection {
             target_system_information_provider: None,
             baseline_noise_provider: None,
             target_noise_provider: None,
+            target_api_latency_provider: None,
+            target_indexer_grpc_provider: None,
         }
     }
 }
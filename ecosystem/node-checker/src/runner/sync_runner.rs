@@ -6,7 +6,8 @@ use crate::{
     checker::{CheckResult, Checker, CheckerError},
     configuration::NodeAddress,
     provider::{
-        api_index::ApiIndexProvider, metrics::MetricsProvider, noise::NoiseProvider,
+        api_index::ApiIndexProvider, api_latency::ApiLatencyProvider,
+        indexer_grpc::IndexerGrpcProvider, metrics::MetricsProvider, noise::NoiseProvider,
         system_information::SystemInformationProvider, ProviderCollection, ProviderConfigs,
     },
     CheckSummary,
@@ -126,6 +127,27 @@ impl Runner for SyncRunner {
             ));
             provider_collection.target_api_index_provider = Some(api_index_provider.clone());
 
+            // Build the ApiLatencyProvider, which hits API routes beyond just /.
+            if let Ok(api_http_client) =
+                target_node_address.get_api_http_client(Duration::from_secs(4))
+            {
+                provider_collection.target_api_latency_provider = Some(ApiLatencyProvider::new(
+                    self.provider_configs.api_latency.clone(),
+                    api_http_client,
+                    target_node_address.get_api_url().unwrap(),
+                ));
+            }
+
+            // With an API provider we can also build an IndexerGrpcProvider, since
+            // it uses the API to figure out which transactions to stream.
+            if let Ok(indexer_grpc_url) = target_node_address.get_indexer_grpc_url() {
+                provider_collection.target_indexer_grpc_provider = Some(IndexerGrpcProvider::new(
+                    self.provider_configs.indexer_grpc.clone(),
+                    indexer_grpc_url,
+                    api_index_provider.clone(),
+                ));
+            }
+
             // From here, since we have an API provider, we can try to make a noise provider.
             if let (Some(_), Some(_)) = (
                 target_node_address.get_noise_port(),
@@ -42,6 +42,8 @@ impl<R: Runner> Api<R> {
         /// A public key for the node, e.g. 0x44fd1324c66371b4788af0b901c9eb8088781acb29e6b8b9c791d5d9838fbe1f.
         /// This is only necessary for certain checkers, e.g. HandshakeChecker.
         public_key: Query<Option<String>>,
+        /// If given, we will assume the indexer gRPC fullnode service is available at the given port.
+        indexer_grpc_port: Query<Option<u16>>,
     ) -> poem::Result<Json<CheckSummary>> {
         // Ensure the public key, if given, is in a valid format.
         let public_key = match public_key.0 {
@@ -84,6 +86,7 @@ impl<R: Runner> Api<R> {
             metrics_port.0,
             noise_port.0,
             public_key,
+            indexer_grpc_port.0,
         );
 
         let complete_evaluation_result = baseline_configuration
@@ -27,6 +27,7 @@ export class DefaultService {
         apiPort,
         noisePort,
         publicKey,
+        indexerGrpcPort,
     }: {
         /**
          * The ID of the baseline node configuration to use for the evaluation, e.g. devnet_fullnode
@@ -53,6 +54,10 @@ export class DefaultService {
          * This is only necessary for certain checkers, e.g. HandshakeChecker.
          */
         publicKey?: string,
+        /**
+         * If given, we will assume the indexer gRPC fullnode service is available at the given port.
+         */
+        indexerGrpcPort?: number,
     }): CancelablePromise<CheckSummary> {
         return this.httpRequest.request({
             method: 'GET',
@@ -64,6 +69,7 @@ export class DefaultService {
                 'api_port': apiPort,
                 'noise_port': noisePort,
                 'public_key': publicKey,
+                'indexer_grpc_port': indexerGrpcPort,
             },
         });
     }
@@ -28,4 +28,5 @@ export enum AptosErrorCode {
     WEB_FRAMEWORK_ERROR = 'web_framework_error',
     BCS_NOT_SUPPORTED = 'bcs_not_supported',
     API_DISABLED = 'api_disabled',
+    RATE_LIMITED = 'rate_limited',
 }
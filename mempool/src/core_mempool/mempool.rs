@@ -14,7 +14,7 @@ use crate::{
     logging::{LogEntry, LogSchema, TxnsLog},
     shared_mempool::types::MultiBucketTimelineIndexIds,
 };
-use aptos_config::config::NodeConfig;
+use aptos_config::config::{MempoolConfig, NodeConfig};
 use aptos_consensus_types::common::TransactionInProgress;
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
@@ -46,6 +46,11 @@ impl Mempool {
         }
     }
 
+    /// Updates the capacity limits of mempool (e.g., when the node config is reloaded).
+    pub fn update_capacity(&mut self, config: &MempoolConfig) {
+        self.transactions.update_capacity(config);
+    }
+
     /// This function will be called once the transaction has been stored.
     pub(crate) fn commit_transaction(&mut self, sender: &AccountAddress, sequence_number: u64) {
         self.transactions
@@ -114,6 +114,14 @@ impl TransactionStore {
         }
     }
 
+    /// Updates the capacity limits of the store. Transactions already in the
+    /// store are kept, even if they exceed the new limits.
+    pub(crate) fn update_capacity(&mut self, config: &MempoolConfig) {
+        self.capacity = config.capacity;
+        self.capacity_bytes = config.capacity_bytes;
+        self.capacity_per_user = config.capacity_per_user;
+    }
+
     #[inline]
     fn get_mempool_txn(
         &self,
@@ -17,7 +17,10 @@ use crate::{
     MempoolEventsReceiver, QuorumStoreRequest,
 };
 use aptos_bounded_executor::BoundedExecutor;
-use aptos_config::network_id::{NetworkId, PeerNetworkId};
+use aptos_config::{
+    config::MempoolConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
 use aptos_consensus_types::common::TransactionSummary;
 use aptos_event_notifications::ReconfigNotificationListener;
 use aptos_infallible::Mutex;
@@ -41,7 +44,7 @@ use std::{
     sync::Arc,
     time::{Duration, Instant, SystemTime},
 };
-use tokio::{runtime::Handle, time::interval};
+use tokio::{runtime::Handle, sync::watch, time::interval};
 use tokio_stream::wrappers::IntervalStream;
 
 /// Coordinator that handles inbound network events and outbound txn broadcasts.
@@ -385,6 +388,24 @@ pub(crate) async fn gc_coordinator(mempool: Arc<Mutex<CoreMempool>>, gc_interval
     ));
 }
 
+/// Applies the mempool config updates (e.g., from node config reloads) to core mempool.
+/// Only the capacity limits are updated, all other fields require a restart.
+pub(crate) async fn config_update_job(
+    mempool: Arc<Mutex<CoreMempool>>,
+    mut config_updates: watch::Receiver<MempoolConfig>,
+) {
+    while config_updates.changed().await.is_ok() {
+        let mempool_config = config_updates.borrow().clone();
+        info!(
+            "Updating the mempool capacity! Capacity: {}, capacity bytes: {}, capacity per user: {}",
+            mempool_config.capacity,
+            mempool_config.capacity_bytes,
+            mempool_config.capacity_per_user
+        );
+        mempool.lock().update_capacity(&mempool_config);
+    }
+}
+
 /// Periodically logs a snapshot of transactions in core mempool.
 /// In the future we may want an interactive way to directly query mempool's internal state.
 /// For now, we will rely on this periodic snapshot to observe the internal state.
@@ -522,6 +522,23 @@ fn test_capacity() {
     assert!(add_txn(&mut pool, TestTransaction::new(1, 2, 1)).is_ok());
 }
 
+#[test]
+fn test_update_capacity() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.capacity = 1;
+    let mut pool = CoreMempool::new(&config);
+
+    // Error on exceeding limit.
+    add_txn(&mut pool, TestTransaction::new(1, 0, 1)).unwrap();
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 1, 1)).is_err());
+
+    // Raise the limit and verify there's space again.
+    config.mempool.capacity = 2;
+    pool.update_capacity(&config.mempool);
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 1, 1)).is_ok());
+    assert!(add_txn(&mut pool, TestTransaction::new(1, 2, 1)).is_err());
+}
+
 #[test]
 fn test_capacity_bytes() {
     let capacity_bytes = 2_048;
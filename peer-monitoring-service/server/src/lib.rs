@@ -15,12 +15,14 @@ use aptos_config::{
     network_id::NetworkId,
 };
 use aptos_logger::prelude::*;
+use aptos_netcore::transport::ConnectionOrigin;
 use aptos_network::application::storage::PeersAndMetadata;
 use aptos_peer_monitoring_service_types::{
     request::{LatencyPingRequest, PeerMonitoringServiceRequest},
     response::{
         ConnectionMetadata, LatencyPingResponse, NetworkInformationResponse,
-        NodeInformationResponse, PeerMonitoringServiceResponse, ServerProtocolVersionResponse,
+        NodeInformationResponse, OutboundConnectionsResponse, PeerMonitoringServiceResponse,
+        ServerProtocolVersionResponse,
     },
     PeerMonitoringServiceError, Result, MAX_DISTANCE_FROM_VALIDATORS,
 };
@@ -40,7 +42,7 @@ pub mod storage;
 mod tests;
 
 /// Peer monitoring server constants
-pub const PEER_MONITORING_SERVER_VERSION: u64 = 1;
+pub const PEER_MONITORING_SERVER_VERSION: u64 = 2;
 
 /// The server-side actor for the peer monitoring service
 pub struct PeerMonitoringServiceServer<T> {
@@ -179,6 +181,7 @@ impl<T: StorageReaderInterface> Handler<T> {
             },
             PeerMonitoringServiceRequest::GetNodeInformation => self.get_node_information(),
             PeerMonitoringServiceRequest::LatencyPing(request) => self.handle_latency_ping(request),
+            PeerMonitoringServiceRequest::GetOutboundConnections => self.get_outbound_connections(),
 
             #[cfg(feature = "network-perf-test")] // Disabled by default
             PeerMonitoringServiceRequest::PerformanceMonitoringRequest(request) => {
@@ -252,6 +255,25 @@ impl<T: StorageReaderInterface> Handler<T> {
         ))
     }
 
+    fn get_outbound_connections(&self) -> Result<PeerMonitoringServiceResponse, Error> {
+        // Get the connected peers that were dialed by this node
+        let outbound_peers = self
+            .peers_and_metadata
+            .get_connected_peers_and_metadata()?
+            .into_iter()
+            .filter(|(_, metadata)| {
+                metadata.get_connection_metadata().origin == ConnectionOrigin::Outbound
+            })
+            .map(|(peer, _)| peer)
+            .collect();
+
+        // Create and return the response
+        let outbound_connections_response = OutboundConnectionsResponse { outbound_peers };
+        Ok(PeerMonitoringServiceResponse::OutboundConnections(
+            outbound_connections_response,
+        ))
+    }
+
     fn get_server_protocol_version(&self) -> Result<PeerMonitoringServiceResponse, Error> {
         let server_protocol_version_response = ServerProtocolVersionResponse {
             version: PEER_MONITORING_SERVER_VERSION,
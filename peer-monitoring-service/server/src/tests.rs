@@ -30,8 +30,8 @@ use aptos_network::{
 use aptos_peer_monitoring_service_types::{
     request::{LatencyPingRequest, PeerMonitoringServiceRequest},
     response::{
-        NetworkInformationResponse, NodeInformationResponse, PeerMonitoringServiceResponse,
-        ServerProtocolVersionResponse,
+        NetworkInformationResponse, NodeInformationResponse, OutboundConnectionsResponse,
+        PeerMonitoringServiceResponse, ServerProtocolVersionResponse,
     },
     PeerMonitoringMetadata, PeerMonitoringServiceError, PeerMonitoringServiceMessage,
 };
@@ -64,7 +64,7 @@ use maplit::btreemap;
 use mockall::mock;
 use rand::{rngs::OsRng, Rng};
 use std::{
-    collections::{BTreeMap, HashMap},
+    collections::{BTreeMap, BTreeSet, HashMap},
     str::FromStr,
     sync::Arc,
     time::Duration,
@@ -91,6 +91,40 @@ async fn test_get_server_protocol_version() {
     assert_eq!(response, expected_response);
 }
 
+#[tokio::test]
+async fn test_get_outbound_connections() {
+    // Create the peer monitoring client and server
+    let (mut mock_client, service, _, peers_and_metadata) = MockClient::new(None, None, None);
+    tokio::spawn(service.start());
+
+    // Connect an inbound peer and an outbound peer to the server
+    let inbound_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    peers_and_metadata
+        .insert_connection_metadata(
+            inbound_peer,
+            create_connection_metadata(inbound_peer.peer_id(), PeerRole::Unknown),
+        )
+        .unwrap();
+    let outbound_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    let mut outbound_connection_metadata =
+        create_connection_metadata(outbound_peer.peer_id(), PeerRole::Upstream);
+    outbound_connection_metadata.origin = ConnectionOrigin::Outbound;
+    peers_and_metadata
+        .insert_connection_metadata(outbound_peer, outbound_connection_metadata)
+        .unwrap();
+
+    // Process a request to fetch the outbound connections
+    let request = PeerMonitoringServiceRequest::GetOutboundConnections;
+    let response = mock_client.send_request(request).await.unwrap();
+
+    // Verify only the outbound peer is returned
+    let expected_response =
+        PeerMonitoringServiceResponse::OutboundConnections(OutboundConnectionsResponse {
+            outbound_peers: BTreeSet::from([outbound_peer]),
+        });
+    assert_eq!(response, expected_response);
+}
+
 #[tokio::test]
 async fn test_get_network_information_fullnode() {
     // Create the peer monitoring client and server
@@ -11,6 +11,7 @@ pub enum PeerMonitoringServiceRequest {
     GetNodeInformation,       // Returns relevant node information about the peer
     GetServerProtocolVersion, // Fetches the protocol version run by the server
     LatencyPing(LatencyPingRequest), // A simple message used by the client to ensure liveness and measure latency
+    GetOutboundConnections, // Returns the peers the server dialed (supported since protocol version 2)
 
     #[cfg(feature = "network-perf-test")] // Disabled by default
     PerformanceMonitoringRequest(PerformanceMonitoringRequest), // A request to monitor network performance
@@ -24,6 +25,7 @@ impl PeerMonitoringServiceRequest {
             Self::GetNodeInformation => "get_node_information",
             Self::GetServerProtocolVersion => "get_server_protocol_version",
             Self::LatencyPing(_) => "latency_ping",
+            Self::GetOutboundConnections => "get_outbound_connections",
 
             #[cfg(feature = "network-perf-test")] // Disabled by default
             Self::PerformanceMonitoringRequest(_) => "performance_monitoring_request",
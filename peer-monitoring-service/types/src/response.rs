@@ -5,7 +5,12 @@ use aptos_config::{config::PeerRole, network_id::PeerNetworkId};
 use aptos_types::{network_address::NetworkAddress, PeerId};
 use cfg_block::cfg_block;
 use serde::{Deserialize, Serialize};
-use std::{collections::BTreeMap, fmt, fmt::Display, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    fmt::Display,
+    time::Duration,
+};
 use thiserror::Error;
 
 /// A peer monitoring service response
@@ -16,6 +21,7 @@ pub enum PeerMonitoringServiceResponse {
     NetworkInformation(NetworkInformationResponse), // Holds the response for network information
     NodeInformation(NodeInformationResponse), // Holds the response for node information
     ServerProtocolVersion(ServerProtocolVersionResponse), // Returns the current server protocol version
+    OutboundConnections(OutboundConnectionsResponse),     // Holds the peers the server dialed
 
     #[cfg(feature = "network-perf-test")] // Disabled by default
     PerformanceMonitoring(PerformanceMonitoringResponse), // A response for performance monitoring requests
@@ -29,6 +35,7 @@ impl PeerMonitoringServiceResponse {
             Self::NetworkInformation(_) => "network_information",
             Self::NodeInformation(_) => "node_information",
             Self::ServerProtocolVersion(_) => "server_protocol_version",
+            Self::OutboundConnections(_) => "outbound_connections",
 
             #[cfg(feature = "network-perf-test")] // Disabled by default
             Self::PerformanceMonitoring(_) => "performance_monitoring_response",
@@ -91,6 +98,13 @@ impl ConnectionMetadata {
     }
 }
 
+/// A response for the outbound connections request. The addresses of these
+/// peers (in the network information) are the addresses they listen on.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct OutboundConnectionsResponse {
+    pub outbound_peers: BTreeSet<PeerNetworkId>, // The connected peers that the server dialed
+}
+
 /// A response for the server protocol version request
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 pub struct ServerProtocolVersionResponse {
@@ -170,6 +184,20 @@ impl TryFrom<PeerMonitoringServiceResponse> for NodeInformationResponse {
     }
 }
 
+impl TryFrom<PeerMonitoringServiceResponse> for OutboundConnectionsResponse {
+    type Error = UnexpectedResponseError;
+
+    fn try_from(response: PeerMonitoringServiceResponse) -> crate::Result<Self, Self::Error> {
+        match response {
+            PeerMonitoringServiceResponse::OutboundConnections(inner) => Ok(inner),
+            _ => Err(UnexpectedResponseError(format!(
+                "expected outbound_connections_response, found {}",
+                response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<PeerMonitoringServiceResponse> for ServerProtocolVersionResponse {
     type Error = UnexpectedResponseError;
 
@@ -17,7 +17,7 @@ from aptos.transaction.v1 import (
 )
 
 DESCRIPTOR = _descriptor_pool.Default().AddSerializedFile(
-    b'\n\x1f\x61ptos/indexer/v1/raw_data.proto\x12\x10\x61ptos.indexer.v1\x1a&aptos/transaction/v1/transaction.proto"\xb4\x01\n\x16GetTransactionsRequest\x12!\n\x10starting_version\x18\x01 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12#\n\x12transactions_count\x18\x02 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x12\x17\n\nbatch_size\x18\x03 \x01(\x04H\x02\x88\x01\x01\x42\x13\n\x11_starting_versionB\x15\n\x13_transactions_countB\r\n\x0b_batch_size"w\n\x14TransactionsResponse\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction\x12\x19\n\x08\x63hain_id\x18\x02 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x42\x0b\n\t_chain_id2p\n\x07RawData\x12\x65\n\x0fGetTransactions\x12(.aptos.indexer.v1.GetTransactionsRequest\x1a&.aptos.indexer.v1.TransactionsResponse0\x01\x62\x06proto3'
+    b'\n\x1f\x61ptos/indexer/v1/raw_data.proto\x12\x10\x61ptos.indexer.v1\x1a&aptos/transaction/v1/transaction.proto"\xf6\x01\n\x16GetTransactionsRequest\x12!\n\x10starting_version\x18\x01 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12#\n\x12transactions_count\x18\x02 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x12\x17\n\nbatch_size\x18\x03 \x01(\x04H\x02\x88\x01\x01\x12@\n\x13transaction_filters\x18\x04 \x03(\x0b\x32#.aptos.indexer.v1.TransactionFilterB\x13\n\x11_starting_versionB\x15\n\x13_transactions_countB\r\n\x0b_batch_size"\xef\x01\n\x11TransactionFilter\x12\x13\n\x06sender\x18\x01 \x01(\tH\x00\x88\x01\x01\x12\x1b\n\x0e\x65ntry_function\x18\x02 \x01(\tH\x01\x88\x01\x01\x12\x17\n\nevent_type\x18\x03 \x01(\tH\x02\x88\x01\x01\x12$\n\x17write_set_resource_type\x18\x04 \x01(\tH\x03\x88\x01\x01\x12\x14\n\x07success\x18\x05 \x01(\x08H\x04\x88\x01\x01\x42\t\n\x07_senderB\x11\n\x0f_entry_functionB\r\n\x0b_event_typeB\x1a\n\x18_write_set_resource_typeB\n\n\x08_success"\xb7\x01\n\x14TransactionsResponse\x12\x37\n\x0ctransactions\x18\x01 \x03(\x0b\x32!.aptos.transaction.v1.Transaction\x12\x19\n\x08\x63hain_id\x18\x02 \x01(\x04\x42\x02\x30\x01H\x00\x88\x01\x01\x12%\n\x14last_scanned_version\x18\x03 \x01(\x04\x42\x02\x30\x01H\x01\x88\x01\x01\x42\x0b\n\t_chain_idB\x17\n\x15_last_scanned_version2p\n\x07RawData\x12\x65\n\x0fGetTransactions\x12(.aptos.indexer.v1.GetTransactionsRequest\x1a&.aptos.indexer.v1.TransactionsResponse0\x01\x62\x06proto3'
 )
 
 _globals = globals()
@@ -37,10 +37,16 @@ if _descriptor._USE_C_DESCRIPTORS == False:
     ]._serialized_options = b"0\001"
     _TRANSACTIONSRESPONSE.fields_by_name["chain_id"]._options = None
     _TRANSACTIONSRESPONSE.fields_by_name["chain_id"]._serialized_options = b"0\001"
+    _TRANSACTIONSRESPONSE.fields_by_name["last_scanned_version"]._options = None
+    _TRANSACTIONSRESPONSE.fields_by_name[
+        "last_scanned_version"
+    ]._serialized_options = b"0\001"
     _globals["_GETTRANSACTIONSREQUEST"]._serialized_start = 94
-    _globals["_GETTRANSACTIONSREQUEST"]._serialized_end = 274
-    _globals["_TRANSACTIONSRESPONSE"]._serialized_start = 276
-    _globals["_TRANSACTIONSRESPONSE"]._serialized_end = 395
-    _globals["_RAWDATA"]._serialized_start = 397
-    _globals["_RAWDATA"]._serialized_end = 509
+    _globals["_GETTRANSACTIONSREQUEST"]._serialized_end = 340
+    _globals["_TRANSACTIONFILTER"]._serialized_start = 343
+    _globals["_TRANSACTIONFILTER"]._serialized_end = 582
+    _globals["_TRANSACTIONSRESPONSE"]._serialized_start = 585
+    _globals["_TRANSACTIONSRESPONSE"]._serialized_end = 768
+    _globals["_RAWDATA"]._serialized_start = 770
+    _globals["_RAWDATA"]._serialized_end = 882
 # @@protoc_insertion_point(module_scope)
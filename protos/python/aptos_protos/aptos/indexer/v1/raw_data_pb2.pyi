@@ -12,32 +12,74 @@ from google.protobuf.internal import containers as _containers
 DESCRIPTOR: _descriptor.FileDescriptor
 
 class GetTransactionsRequest(_message.Message):
-    __slots__ = ["starting_version", "transactions_count", "batch_size"]
+    __slots__ = [
+        "starting_version",
+        "transactions_count",
+        "batch_size",
+        "transaction_filters",
+    ]
     STARTING_VERSION_FIELD_NUMBER: _ClassVar[int]
     TRANSACTIONS_COUNT_FIELD_NUMBER: _ClassVar[int]
     BATCH_SIZE_FIELD_NUMBER: _ClassVar[int]
+    TRANSACTION_FILTERS_FIELD_NUMBER: _ClassVar[int]
     starting_version: int
     transactions_count: int
     batch_size: int
+    transaction_filters: _containers.RepeatedCompositeFieldContainer[
+        TransactionFilter
+    ]
     def __init__(
         self,
         starting_version: _Optional[int] = ...,
         transactions_count: _Optional[int] = ...,
         batch_size: _Optional[int] = ...,
+        transaction_filters: _Optional[
+            _Iterable[_Union[TransactionFilter, _Mapping]]
+        ] = ...,
+    ) -> None: ...
+
+class TransactionFilter(_message.Message):
+    __slots__ = [
+        "sender",
+        "entry_function",
+        "event_type",
+        "write_set_resource_type",
+        "success",
+    ]
+    SENDER_FIELD_NUMBER: _ClassVar[int]
+    ENTRY_FUNCTION_FIELD_NUMBER: _ClassVar[int]
+    EVENT_TYPE_FIELD_NUMBER: _ClassVar[int]
+    WRITE_SET_RESOURCE_TYPE_FIELD_NUMBER: _ClassVar[int]
+    SUCCESS_FIELD_NUMBER: _ClassVar[int]
+    sender: str
+    entry_function: str
+    event_type: str
+    write_set_resource_type: str
+    success: bool
+    def __init__(
+        self,
+        sender: _Optional[str] = ...,
+        entry_function: _Optional[str] = ...,
+        event_type: _Optional[str] = ...,
+        write_set_resource_type: _Optional[str] = ...,
+        success: bool = ...,
     ) -> None: ...
 
 class TransactionsResponse(_message.Message):
-    __slots__ = ["transactions", "chain_id"]
+    __slots__ = ["transactions", "chain_id", "last_scanned_version"]
     TRANSACTIONS_FIELD_NUMBER: _ClassVar[int]
     CHAIN_ID_FIELD_NUMBER: _ClassVar[int]
+    LAST_SCANNED_VERSION_FIELD_NUMBER: _ClassVar[int]
     transactions: _containers.RepeatedCompositeFieldContainer[
         _transaction_pb2.Transaction
     ]
     chain_id: int
+    last_scanned_version: int
     def __init__(
         self,
         transactions: _Optional[
             _Iterable[_Union[_transaction_pb2.Transaction, _Mapping]]
         ] = ...,
         chain_id: _Optional[int] = ...,
+        last_scanned_version: _Optional[int] = ...,
     ) -> None: ...
@@ -87,7 +87,8 @@ pub mod raw_data_client {
             self.inner = self.inner.max_encoding_message_size(limit);
             self
         }
-        /** Get transactions batch without any filtering from starting version and end if transaction count is present.
+        /** Get transactions batch from starting version and end if transaction count is present.
+ If transaction filters are present, only matching transactions are returned.
 */
         pub async fn get_transactions(
             &mut self,
@@ -129,7 +130,8 @@ pub mod raw_data_server {
             >
             + Send
             + 'static;
-        /** Get transactions batch without any filtering from starting version and end if transaction count is present.
+        /** Get transactions batch from starting version and end if transaction count is present.
+ If transaction filters are present, only matching transactions are returned.
 */
         async fn get_transactions(
             &self,
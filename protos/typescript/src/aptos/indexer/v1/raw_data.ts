@@ -28,7 +28,50 @@ export interface GetTransactionsRequest {
    * Optional; number of transactions in each `TransactionsResponse` for current stream.
    * If not present, default to 1000. If larger than 1000, request will be rejected.
    */
-  batchSize?: bigint | undefined;
+  batchSize?:
+    | bigint
+    | undefined;
+  /**
+   * Optional; only transactions matching at least one of the filters are returned.
+   * If empty, all transactions are returned. `transactions_count` still counts every
+   * transaction, whether it matches or not.
+   */
+  transactionFilters?: TransactionFilter[] | undefined;
+}
+
+/**
+ * TransactionFilter matches transactions that match all of the conditions set.
+ * Unset conditions match every transaction.
+ */
+export interface TransactionFilter {
+  /** Optional; sender of the user transaction, e.g. "0x1". */
+  sender?:
+    | string
+    | undefined;
+  /**
+   * Optional; entry function called by the user transaction, as "address::module::function".
+   * "address::module" and "address" match any entry function in the module or at the address.
+   */
+  entryFunction?:
+    | string
+    | undefined;
+  /**
+   * Optional; type of an event emitted by the transaction, e.g. "0x1::coin::DepositEvent".
+   * Without type arguments, it also matches every instantiation of a generic type.
+   */
+  eventType?:
+    | string
+    | undefined;
+  /**
+   * Optional; type of a resource written or deleted by the transaction,
+   * e.g. "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>".
+   * Without type arguments, it also matches every instantiation of a generic type.
+   */
+  writeSetResourceType?:
+    | string
+    | undefined;
+  /** Optional; whether the transaction succeeded. */
+  success?: boolean | undefined;
 }
 
 /** TransactionsResponse is a batch of transactions. */
@@ -38,11 +81,19 @@ export interface TransactionsResponse {
     | Transaction[]
     | undefined;
   /** Required; chain id. */
-  chainId?: bigint | undefined;
+  chainId?:
+    | bigint
+    | undefined;
+  /**
+   * Optional; only set when the request has transaction filters. Version of the last
+   * transaction checked against the filters, whether it matched or not, to resume the
+   * stream from. Sent even when no transaction of a batch matched.
+   */
+  lastScannedVersion?: bigint | undefined;
 }
 
 function createBaseGetTransactionsRequest(): GetTransactionsRequest {
-  return { startingVersion: undefined, transactionsCount: undefined, batchSize: undefined };
+  return { startingVersion: undefined, transactionsCount: undefined, batchSize: undefined, transactionFilters: [] };
 }
 
 export const GetTransactionsRequest = {
@@ -65,6 +116,11 @@ export const GetTransactionsRequest = {
       }
       writer.uint32(24).uint64(message.batchSize.toString());
     }
+    if (message.transactionFilters !== undefined && message.transactionFilters.length !== 0) {
+      for (const v of message.transactionFilters) {
+        TransactionFilter.encode(v!, writer.uint32(34).fork()).ldelim();
+      }
+    }
     return writer;
   },
 
@@ -96,6 +152,13 @@ export const GetTransactionsRequest = {
 
           message.batchSize = longToBigint(reader.uint64() as Long);
           continue;
+        case 4:
+          if (tag !== 34) {
+            break;
+          }
+
+          message.transactionFilters!.push(TransactionFilter.decode(reader, reader.uint32()));
+          continue;
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -144,6 +207,9 @@ export const GetTransactionsRequest = {
       startingVersion: isSet(object.startingVersion) ? BigInt(object.startingVersion) : undefined,
       transactionsCount: isSet(object.transactionsCount) ? BigInt(object.transactionsCount) : undefined,
       batchSize: isSet(object.batchSize) ? BigInt(object.batchSize) : undefined,
+      transactionFilters: globalThis.Array.isArray(object?.transactionFilters)
+        ? object.transactionFilters.map((e: any) => TransactionFilter.fromJSON(e))
+        : [],
     };
   },
 
@@ -158,6 +224,9 @@ export const GetTransactionsRequest = {
     if (message.batchSize !== undefined) {
       obj.batchSize = message.batchSize.toString();
     }
+    if (message.transactionFilters?.length) {
+      obj.transactionFilters = message.transactionFilters.map((e) => TransactionFilter.toJSON(e));
+    }
     return obj;
   },
 
@@ -169,12 +238,172 @@ export const GetTransactionsRequest = {
     message.startingVersion = object.startingVersion ?? undefined;
     message.transactionsCount = object.transactionsCount ?? undefined;
     message.batchSize = object.batchSize ?? undefined;
+    message.transactionFilters = object.transactionFilters?.map((e) => TransactionFilter.fromPartial(e)) || [];
+    return message;
+  },
+};
+
+function createBaseTransactionFilter(): TransactionFilter {
+  return {
+    sender: undefined,
+    entryFunction: undefined,
+    eventType: undefined,
+    writeSetResourceType: undefined,
+    success: undefined,
+  };
+}
+
+export const TransactionFilter = {
+  encode(message: TransactionFilter, writer: _m0.Writer = _m0.Writer.create()): _m0.Writer {
+    if (message.sender !== undefined) {
+      writer.uint32(10).string(message.sender);
+    }
+    if (message.entryFunction !== undefined) {
+      writer.uint32(18).string(message.entryFunction);
+    }
+    if (message.eventType !== undefined) {
+      writer.uint32(26).string(message.eventType);
+    }
+    if (message.writeSetResourceType !== undefined) {
+      writer.uint32(34).string(message.writeSetResourceType);
+    }
+    if (message.success !== undefined) {
+      writer.uint32(40).bool(message.success);
+    }
+    return writer;
+  },
+
+  decode(input: _m0.Reader | Uint8Array, length?: number): TransactionFilter {
+    const reader = input instanceof _m0.Reader ? input : _m0.Reader.create(input);
+    let end = length === undefined ? reader.len : reader.pos + length;
+    const message = createBaseTransactionFilter();
+    while (reader.pos < end) {
+      const tag = reader.uint32();
+      switch (tag >>> 3) {
+        case 1:
+          if (tag !== 10) {
+            break;
+          }
+
+          message.sender = reader.string();
+          continue;
+        case 2:
+          if (tag !== 18) {
+            break;
+          }
+
+          message.entryFunction = reader.string();
+          continue;
+        case 3:
+          if (tag !== 26) {
+            break;
+          }
+
+          message.eventType = reader.string();
+          continue;
+        case 4:
+          if (tag !== 34) {
+            break;
+          }
+
+          message.writeSetResourceType = reader.string();
+          continue;
+        case 5:
+          if (tag !== 40) {
+            break;
+          }
+
+          message.success = reader.bool();
+          continue;
+      }
+      if ((tag & 7) === 4 || tag === 0) {
+        break;
+      }
+      reader.skipType(tag & 7);
+    }
+    return message;
+  },
+
+  // encodeTransform encodes a source of message objects.
+  // Transform<TransactionFilter, Uint8Array>
+  async *encodeTransform(
+    source: AsyncIterable<TransactionFilter | TransactionFilter[]> | Iterable<TransactionFilter | TransactionFilter[]>,
+  ): AsyncIterable<Uint8Array> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [TransactionFilter.encode(p).finish()];
+        }
+      } else {
+        yield* [TransactionFilter.encode(pkt as any).finish()];
+      }
+    }
+  },
+
+  // decodeTransform decodes a source of encoded messages.
+  // Transform<Uint8Array, TransactionFilter>
+  async *decodeTransform(
+    source: AsyncIterable<Uint8Array | Uint8Array[]> | Iterable<Uint8Array | Uint8Array[]>,
+  ): AsyncIterable<TransactionFilter> {
+    for await (const pkt of source) {
+      if (globalThis.Array.isArray(pkt)) {
+        for (const p of (pkt as any)) {
+          yield* [TransactionFilter.decode(p)];
+        }
+      } else {
+        yield* [TransactionFilter.decode(pkt as any)];
+      }
+    }
+  },
+
+  fromJSON(object: any): TransactionFilter {
+    return {
+      sender: isSet(object.sender) ? globalThis.String(object.sender) : undefined,
+      entryFunction: isSet(object.entryFunction) ? globalThis.String(object.entryFunction) : undefined,
+      eventType: isSet(object.eventType) ? globalThis.String(object.eventType) : undefined,
+      writeSetResourceType: isSet(object.writeSetResourceType)
+        ? globalThis.String(object.writeSetResourceType)
+        : undefined,
+      success: isSet(object.success) ? globalThis.Boolean(object.success) : undefined,
+    };
+  },
+
+  toJSON(message: TransactionFilter): unknown {
+    const obj: any = {};
+    if (message.sender !== undefined) {
+      obj.sender = message.sender;
+    }
+    if (message.entryFunction !== undefined) {
+      obj.entryFunction = message.entryFunction;
+    }
+    if (message.eventType !== undefined) {
+      obj.eventType = message.eventType;
+    }
+    if (message.writeSetResourceType !== undefined) {
+      obj.writeSetResourceType = message.writeSetResourceType;
+    }
+    if (message.success !== undefined) {
+      obj.success = message.success;
+    }
+    return obj;
+  },
+
+  create(base?: DeepPartial<TransactionFilter>): TransactionFilter {
+    return TransactionFilter.fromPartial(base ?? {});
+  },
+  fromPartial(object: DeepPartial<TransactionFilter>): TransactionFilter {
+    const message = createBaseTransactionFilter();
+    message.sender = object.sender ?? undefined;
+    message.entryFunction = object.entryFunction ?? undefined;
+    message.eventType = object.eventType ?? undefined;
+    message.writeSetResourceType = object.writeSetResourceType ?? undefined;
+    message.success = object.success ?? undefined;
     return message;
   },
 };
 
 function createBaseTransactionsResponse(): TransactionsResponse {
-  return { transactions: [], chainId: undefined };
+  return { transactions: [], chainId: undefined, lastScannedVersion: undefined };
 }
 
 export const TransactionsResponse = {
@@ -190,6 +419,12 @@ export const TransactionsResponse = {
       }
       writer.uint32(16).uint64(message.chainId.toString());
     }
+    if (message.lastScannedVersion !== undefined) {
+      if (BigInt.asUintN(64, message.lastScannedVersion) !== message.lastScannedVersion) {
+        throw new Error("value provided for field message.lastScannedVersion of type uint64 too large");
+      }
+      writer.uint32(24).uint64(message.lastScannedVersion.toString());
+    }
     return writer;
   },
 
@@ -214,6 +449,13 @@ export const TransactionsResponse = {
 
           message.chainId = longToBigint(reader.uint64() as Long);
           continue;
+        case 3:
+          if (tag !== 24) {
+            break;
+          }
+
+          message.lastScannedVersion = longToBigint(reader.uint64() as Long);
+          continue;
       }
       if ((tag & 7) === 4 || tag === 0) {
         break;
@@ -263,6 +505,7 @@ export const TransactionsResponse = {
         ? object.transactions.map((e: any) => Transaction.fromJSON(e))
         : [],
       chainId: isSet(object.chainId) ? BigInt(object.chainId) : undefined,
+      lastScannedVersion: isSet(object.lastScannedVersion) ? BigInt(object.lastScannedVersion) : undefined,
     };
   },
 
@@ -274,6 +517,9 @@ export const TransactionsResponse = {
     if (message.chainId !== undefined) {
       obj.chainId = message.chainId.toString();
     }
+    if (message.lastScannedVersion !== undefined) {
+      obj.lastScannedVersion = message.lastScannedVersion.toString();
+    }
     return obj;
   },
 
@@ -284,13 +530,17 @@ export const TransactionsResponse = {
     const message = createBaseTransactionsResponse();
     message.transactions = object.transactions?.map((e) => Transaction.fromPartial(e)) || [];
     message.chainId = object.chainId ?? undefined;
+    message.lastScannedVersion = object.lastScannedVersion ?? undefined;
     return message;
   },
 };
 
 export type RawDataService = typeof RawDataService;
 export const RawDataService = {
-  /** Get transactions batch without any filtering from starting version and end if transaction count is present. */
+  /**
+   * Get transactions batch from starting version and end if transaction count is present.
+   * If transaction filters are present, only matching transactions are returned.
+   */
   getTransactions: {
     path: "/aptos.indexer.v1.RawData/GetTransactions",
     requestStream: false,
@@ -303,12 +553,18 @@ export const RawDataService = {
 } as const;
 
 export interface RawDataServer extends UntypedServiceImplementation {
-  /** Get transactions batch without any filtering from starting version and end if transaction count is present. */
+  /**
+   * Get transactions batch from starting version and end if transaction count is present.
+   * If transaction filters are present, only matching transactions are returned.
+   */
   getTransactions: handleServerStreamingCall<GetTransactionsRequest, TransactionsResponse>;
 }
 
 export interface RawDataClient extends Client {
-  /** Get transactions batch without any filtering from starting version and end if transaction count is present. */
+  /**
+   * Get transactions batch from starting version and end if transaction count is present.
+   * If transaction filters are present, only matching transactions are returned.
+   */
   getTransactions(
     request: GetTransactionsRequest,
     options?: Partial<CallOptions>,
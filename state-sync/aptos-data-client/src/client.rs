@@ -15,6 +15,7 @@ use crate::{
     },
     peer_states::{ErrorType, PeerStates},
     poller::DataSummaryPoller,
+    request_latencies::RequestLatencies,
     utils,
 };
 use aptos_config::{
@@ -43,7 +44,7 @@ use aptos_storage_service_types::{
     responses::{StorageServerSummary, StorageServiceResponse, TransactionOrOutputListWithProof},
     Epoch, StorageServiceMessage,
 };
-use aptos_time_service::TimeService;
+use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{
     epoch_change::EpochChangeProof,
     ledger_info::LedgerInfoWithSignatures,
@@ -52,6 +53,7 @@ use aptos_types::{
 };
 use arc_swap::ArcSwap;
 use async_trait::async_trait;
+use futures::future::{select, Either};
 use maplit::hashset;
 use std::{collections::HashSet, fmt, ops::Deref, sync::Arc, time::Duration};
 use tokio::runtime::Handle;
@@ -91,6 +93,8 @@ pub struct AptosDataClient {
     peer_states: Arc<PeerStates>,
     /// A cached, aggregate data summary of all unbanned peers' data summaries.
     global_summary_cache: Arc<ArcSwap<GlobalDataSummary>>,
+    /// The latencies of recent requests (used to decide when to hedge requests).
+    request_latencies: RequestLatencies,
     /// Used for generating the next request/response id.
     response_id_generator: Arc<U64IdGenerator>,
     /// Time service used for calculating peer lag
@@ -118,6 +122,7 @@ impl AptosDataClient {
             active_subscription_state: Arc::new(Mutex::new(None)),
             peer_states: Arc::new(PeerStates::new(data_client_config.clone())),
             global_summary_cache: Arc::new(ArcSwap::from(Arc::new(GlobalDataSummary::empty()))),
+            request_latencies: RequestLatencies::new(),
             response_id_generator: Arc::new(U64IdGenerator::new()),
             time_service: time_service.clone(),
         };
@@ -208,15 +213,8 @@ impl AptosDataClient {
         &self,
         request: &StorageServiceRequest,
     ) -> crate::error::Result<PeerNetworkId, Error> {
-        // All requests should be sent to prioritized peers (if possible).
-        // If none can handle the request, fall back to the regular peers.
-        let (priority_peers, regular_peers) = self.get_priority_and_regular_peers()?;
-        let priority_serviceable = self.identify_serviceable(priority_peers, request);
-        let serviceable_peers = if !priority_serviceable.is_empty() {
-            priority_serviceable
-        } else {
-            self.identify_serviceable(regular_peers, request)
-        };
+        // Identify the peers that can service the request
+        let serviceable_peers = self.get_serviceable_peers(request, None)?;
 
         // Identify the peer based on the request type
         if request.data_request.is_subscription_request() {
@@ -225,12 +223,54 @@ impl AptosDataClient {
         } else if request.data_request.is_optimistic_fetch() {
             // Choose the peer with the lowest distance and latency for the optimistic fetch
             self.choose_lowest_distance_and_latency_peer(request, serviceable_peers)
+        } else if self.data_client_config.enable_throughput_peer_selection {
+            // Choose the peer randomly weighted by throughput
+            self.choose_random_peer_by_throughput(request, serviceable_peers)
         } else {
             // Choose the peer randomly weighted by latency
             self.choose_random_peer_by_latency(request, serviceable_peers)
         }
     }
 
+    /// Chooses a second peer to race the given request against (i.e., to hedge the
+    /// request). The peer that is already servicing the request is never chosen.
+    fn choose_peer_for_hedged_request(
+        &self,
+        request: &StorageServiceRequest,
+        first_peer: PeerNetworkId,
+    ) -> crate::error::Result<PeerNetworkId, Error> {
+        let serviceable_peers = self.get_serviceable_peers(request, Some(first_peer))?;
+        if self.data_client_config.enable_throughput_peer_selection {
+            self.choose_random_peer_by_throughput(request, serviceable_peers)
+        } else {
+            self.choose_random_peer_by_latency(request, serviceable_peers)
+        }
+    }
+
+    /// Returns the peers that can service the given request (excluding the
+    /// specified peer, if any). All requests should be sent to prioritized
+    /// peers (if possible). If none can handle the request, we fall back to
+    /// the regular peers.
+    fn get_serviceable_peers(
+        &self,
+        request: &StorageServiceRequest,
+        excluded_peer: Option<PeerNetworkId>,
+    ) -> crate::error::Result<HashSet<PeerNetworkId>, Error> {
+        let (mut priority_peers, mut regular_peers) = self.get_priority_and_regular_peers()?;
+        if let Some(excluded_peer) = excluded_peer {
+            priority_peers.remove(&excluded_peer);
+            regular_peers.remove(&excluded_peer);
+        }
+
+        let priority_serviceable = self.identify_serviceable(priority_peers, request);
+        let serviceable_peers = if !priority_serviceable.is_empty() {
+            priority_serviceable
+        } else {
+            self.identify_serviceable(regular_peers, request)
+        };
+        Ok(serviceable_peers)
+    }
+
     /// Choose a peer that can service the given subscription request
     fn choose_peer_for_subscription_request(
         &self,
@@ -326,6 +366,46 @@ impl AptosDataClient {
         self.choose_random_peer(request, serviceable_peers)
     }
 
+    /// Chooses a peer randomly weighted by the observed throughput for the request
+    /// type (from the given set of serviceable peers). Peers without any observed
+    /// throughput are weighted by the average throughput (so they are still explored).
+    fn choose_random_peer_by_throughput(
+        &self,
+        request: &StorageServiceRequest,
+        serviceable_peers: HashSet<PeerNetworkId>,
+    ) -> Result<PeerNetworkId, Error> {
+        // Gather the observed throughputs for all serviceable peers
+        let peers_and_throughputs: Vec<_> = serviceable_peers
+            .iter()
+            .map(|peer| (*peer, self.peer_states.get_throughput(peer, request)))
+            .collect();
+        let observed_throughputs: Vec<f64> = peers_and_throughputs
+            .iter()
+            .filter_map(|(_, throughput)| *throughput)
+            .collect();
+
+        // If no throughputs have been observed yet, fall back to selection by latency
+        if observed_throughputs.is_empty() {
+            return self.choose_random_peer_by_latency(request, serviceable_peers);
+        }
+
+        // Choose a peer weighted by throughput
+        let average_throughput =
+            observed_throughputs.iter().sum::<f64>() / observed_throughputs.len() as f64;
+        let peers_and_weights = peers_and_throughputs
+            .into_iter()
+            .map(|(peer, throughput)| (peer, throughput.unwrap_or(average_throughput)))
+            .collect();
+        if let Ok(peer_set) = utils::choose_random_peers_by_weight(1, peers_and_weights) {
+            if let Some(peer) = peer_set.into_iter().next() {
+                return Ok(peer); // Return the peer if we found one
+            }
+        }
+
+        // Otherwise, simply select a peer at random
+        self.choose_random_peer(request, serviceable_peers)
+    }
+
     /// Identifies the peers in the given set of prospective peers
     /// that can service the specified request.
     fn identify_serviceable(
@@ -405,10 +485,121 @@ impl AptosDataClient {
             error
         })?;
         let _timer = start_request_timer(&metrics::REQUEST_LATENCIES, &request.get_label(), peer);
+
+        // Hedge the request (if possible)
+        if self.should_hedge_request(&request) {
+            return self
+                .send_hedged_request_and_decode(peer, request, request_timeout_ms)
+                .await;
+        }
+
         self.send_request_to_peer_and_decode(peer, request, request_timeout_ms)
             .await
     }
 
+    /// Returns true iff the given request should be hedged. Subscriptions and
+    /// optimistic fetches are never hedged, as they are expected to be slow.
+    fn should_hedge_request(&self, request: &StorageServiceRequest) -> bool {
+        self.data_client_config.enable_request_hedging
+            && !request.data_request.is_subscription_request()
+            && !request.data_request.is_optimistic_fetch()
+            && !request.data_request.is_storage_summary_request()
+            && !request.data_request.is_protocol_version_request()
+    }
+
+    /// Returns the delay after which the given request should be hedged. This is
+    /// the configured latency percentile of recent requests of the same type (or
+    /// the configured minimum delay, whichever is higher).
+    fn get_hedged_request_delay(&self, request: &StorageServiceRequest) -> Duration {
+        let min_delay = Duration::from_millis(self.data_client_config.min_hedged_request_delay_ms);
+        self.request_latencies
+            .get_latency_percentile(
+                request.data_request.get_label(),
+                self.data_client_config.hedged_request_latency_percentile,
+            )
+            .map_or(min_delay, |latency| latency.max(min_delay))
+    }
+
+    /// Sends a request to the given peer and, if no response is received before
+    /// the hedging delay, races the request against a second peer. The first
+    /// valid response is returned (the other request is dropped).
+    async fn send_hedged_request_and_decode<T, E>(
+        &self,
+        peer: PeerNetworkId,
+        request: StorageServiceRequest,
+        request_timeout_ms: u64,
+    ) -> crate::error::Result<Response<T>>
+    where
+        T: TryFrom<StorageServiceResponse, Error = E>,
+        E: Into<Error>,
+    {
+        let request_label = request.get_label();
+
+        // Send the request to the first peer and wait for the response or the hedging delay
+        let first_request =
+            self.send_request_to_peer_and_decode(peer, request.clone(), request_timeout_ms);
+        let hedging_delay = self
+            .time_service
+            .sleep(self.get_hedged_request_delay(&request));
+        futures::pin_mut!(first_request, hedging_delay);
+        let first_request = match select(first_request, hedging_delay).await {
+            Either::Left((result, _)) => return result,
+            Either::Right((_, first_request)) => first_request,
+        };
+
+        // The first peer is slow, so choose a second peer to race the request against
+        let second_peer = match self.choose_peer_for_hedged_request(&request, peer) {
+            Ok(second_peer) => second_peer,
+            Err(_) => {
+                // No other peer can service the request, so wait for the first peer
+                metrics::increment_hedged_request_counter(
+                    &request_label,
+                    metrics::HEDGE_UNAVAILABLE_LABEL,
+                );
+                return first_request.await;
+            },
+        };
+        debug!(
+            (LogSchema::new(LogEntry::StorageServiceRequest)
+                .event(LogEvent::HedgeRequest)
+                .request_type(&request_label)
+                .peer(&second_peer)
+                .message(&format!("Hedging slow request sent to peer: {:?}", peer)))
+        );
+        metrics::increment_hedged_request_counter(&request_label, metrics::HEDGE_SENT_LABEL);
+
+        // Return the first valid response (or the error of the first peer if both fail)
+        let second_request =
+            self.send_request_to_peer_and_decode(second_peer, request, request_timeout_ms);
+        futures::pin_mut!(second_request);
+        match select(first_request, second_request).await {
+            Either::Left((Ok(response), _)) => {
+                metrics::increment_hedged_request_counter(
+                    &request_label,
+                    metrics::HEDGE_WASTED_LABEL,
+                );
+                Ok(response)
+            },
+            Either::Right((Ok(response), _)) => {
+                metrics::increment_hedged_request_counter(&request_label, metrics::HEDGE_WON_LABEL);
+                Ok(response)
+            },
+            Either::Left((Err(first_error), second_request)) => {
+                let response = second_request.await.map_err(|_| first_error)?;
+                metrics::increment_hedged_request_counter(&request_label, metrics::HEDGE_WON_LABEL);
+                Ok(response)
+            },
+            Either::Right((Err(_), first_request)) => {
+                let response = first_request.await?;
+                metrics::increment_hedged_request_counter(
+                    &request_label,
+                    metrics::HEDGE_WASTED_LABEL,
+                );
+                Ok(response)
+            },
+        }
+    }
+
     /// Sends a request to a specific peer and decodes the response
     pub async fn send_request_to_peer_and_decode<T, E>(
         &self,
@@ -474,6 +665,7 @@ impl AptosDataClient {
         self.update_sent_request_metrics(peer, &request);
 
         // Send the request and process the result
+        let request_start_time = self.time_service.now();
         let result = self
             .storage_service_client
             .send_request(
@@ -495,6 +687,15 @@ impl AptosDataClient {
                 // Update the received response metrics
                 self.update_received_response_metrics(peer, &request);
 
+                // Update the request latencies and the peer's throughput
+                let response_time = self.time_service.now().duration_since(request_start_time);
+                self.update_request_latency_and_throughput(
+                    peer,
+                    &request,
+                    &response,
+                    response_time,
+                );
+
                 // For now, record all responses that at least pass the data
                 // client layer successfully. An alternative might also have the
                 // consumer notify both success and failure via the callback.
@@ -600,6 +801,24 @@ impl AptosDataClient {
             .increment_received_response_counter(peer, request);
     }
 
+    /// Records the latency of the request and the observed throughput of the peer
+    fn update_request_latency_and_throughput(
+        &self,
+        peer: PeerNetworkId,
+        request: &StorageServiceRequest,
+        response: &StorageServiceResponse,
+        response_time: Duration,
+    ) {
+        self.request_latencies
+            .record_latency(request.data_request.get_label(), response_time);
+        self.peer_states.update_throughput(
+            peer,
+            request,
+            get_response_size_bytes(response),
+            response_time,
+        );
+    }
+
     /// Updates the metrics for the requests sent via the data client
     fn update_sent_request_metrics(&self, peer: PeerNetworkId, request: &StorageServiceRequest) {
         // Increment the global request counter
@@ -907,3 +1126,15 @@ fn update_connected_peer_metrics(num_priority_peers: usize, num_regular_peers: u
         num_regular_peers as u64,
     );
 }
+
+/// Returns the (approximate) number of bytes in the given response
+fn get_response_size_bytes(response: &StorageServiceResponse) -> u64 {
+    match response {
+        StorageServiceResponse::CompressedResponse(_, compressed_data) => {
+            compressed_data.len() as u64
+        },
+        StorageServiceResponse::RawResponse(data_response) => {
+            bcs::serialized_size(data_response).unwrap_or_default() as u64
+        },
+    }
+}
@@ -12,6 +12,7 @@ mod logging;
 mod metrics;
 pub mod peer_states;
 pub mod poller;
+mod request_latencies;
 mod utils;
 
 #[cfg(test)]
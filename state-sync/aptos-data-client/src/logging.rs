@@ -53,6 +53,7 @@ pub enum LogEntry {
 pub enum LogEvent {
     AggregateSummary,
     CaughtUpToLatest,
+    HedgeRequest,
     NoPeersToPoll,
     PeerIgnored,
     PeerNoLongerIgnored,
@@ -9,6 +9,10 @@ use aptos_metrics_core::{
 };
 
 // Useful metric constants and labels
+pub const HEDGE_SENT_LABEL: &str = "hedge_sent";
+pub const HEDGE_UNAVAILABLE_LABEL: &str = "hedge_unavailable";
+pub const HEDGE_WASTED_LABEL: &str = "hedge_wasted";
+pub const HEDGE_WON_LABEL: &str = "hedge_won";
 pub const PRIORITIZED_PEER: &str = "prioritized_peer";
 pub const PROPOSE_TO_SEEN_LATENCY_LABEL: &str = "propose_to_seen_latency";
 pub const PROPOSE_TO_SYNC_LATENCY_LABEL: &str = "propose_to_sync_latency";
@@ -48,6 +52,17 @@ pub static ERROR_RESPONSES: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter for tracking hedged requests (i.e., requests raced against a second
+/// peer), and whether the hedge won or was wasted.
+pub static HEDGED_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_data_client_hedged_requests",
+        "Counters related to hedged requests",
+        &["request_type", "result"]
+    )
+    .unwrap()
+});
+
 // Latency buckets for network latencies (seconds)
 const REQUEST_LATENCY_BUCKETS_SECS: &[f64] = &[
     0.05, 0.1, 0.2, 0.3, 0.5, 0.75, 1.0, 1.5, 2.0, 3.0, 5.0, 7.5, 10.0, 15.0, 20.0, 30.0, 40.0,
@@ -194,6 +209,13 @@ pub fn increment_request_counter(
         .inc();
 }
 
+/// Increments the hedged request counter with the provided values
+pub fn increment_hedged_request_counter(request_label: &str, result_label: &str) {
+    HEDGED_REQUESTS
+        .with_label_values(&[request_label, result_label])
+        .inc();
+}
+
 /// Observes the value for the provided histogram and label
 pub fn observe_value_with_label(histogram: &Lazy<HistogramVec>, label: &str, value: f64) {
     histogram.with_label_values(&[label]).observe(value)
@@ -38,6 +38,8 @@ const NOT_USEFUL_MULTIPLIER: f64 = 0.95;
 const MALICIOUS_MULTIPLIER: f64 = 0.8;
 /// Ignore a peer when their score dips below this threshold.
 const IGNORE_PEER_THRESHOLD: f64 = 25.0;
+/// The weight of the latest observation in the peer's average throughput.
+const THROUGHPUT_SMOOTHING_FACTOR: f64 = 0.2;
 
 pub enum ErrorType {
     /// A response or error that's not actively malicious but also doesn't help
@@ -70,6 +72,8 @@ pub struct PeerState {
     storage_summary: Option<StorageServerSummary>,
     /// For now, a simplified port of the original state-sync v1 scoring system.
     score: f64,
+    /// The observed throughput (bytes per second) of this peer (by data request label)
+    throughput_by_type: Arc<DashMap<String, f64>>,
 }
 
 impl Default for PeerState {
@@ -79,6 +83,7 @@ impl Default for PeerState {
             sent_requests_by_type: Arc::new(DashMap::new()),
             storage_summary: None,
             score: STARTING_SCORE,
+            throughput_by_type: Arc::new(DashMap::new()),
         }
     }
 }
@@ -105,6 +110,13 @@ impl PeerState {
         self.score
     }
 
+    /// Returns the observed throughput (bytes per second) for the given label
+    pub fn get_throughput(&self, request_label: &str) -> Option<f64> {
+        self.throughput_by_type
+            .get(request_label)
+            .map(|throughput| *throughput)
+    }
+
     /// Returns the storage summary for the peer
     pub fn get_storage_summary(&self) -> Option<StorageServerSummary> {
         self.storage_summary.clone()
@@ -152,6 +164,18 @@ impl PeerState {
         self.score = f64::max(self.score * multiplier, MIN_SCORE);
     }
 
+    /// Updates the observed throughput for the given label (using an
+    /// exponential moving average of all observations).
+    fn update_throughput(&mut self, request_label: String, bytes_per_second: f64) {
+        self.throughput_by_type
+            .entry(request_label)
+            .and_modify(|throughput| {
+                *throughput = THROUGHPUT_SMOOTHING_FACTOR * bytes_per_second
+                    + (1.0 - THROUGHPUT_SMOOTHING_FACTOR) * *throughput
+            })
+            .or_insert(bytes_per_second);
+    }
+
     /// Updates the storage summary for the peer
     fn update_storage_summary(&mut self, storage_summary: StorageServerSummary) {
         self.storage_summary = Some(storage_summary);
@@ -294,6 +318,44 @@ impl PeerStates {
         }
     }
 
+    /// Returns the observed throughput (bytes per second) of the
+    /// given peer for the type of the specified request.
+    pub fn get_throughput(
+        &self,
+        peer: &PeerNetworkId,
+        request: &StorageServiceRequest,
+    ) -> Option<f64> {
+        self.peer_to_state
+            .get(peer)
+            .and_then(|peer_state| peer_state.get_throughput(request.data_request.get_label()))
+    }
+
+    /// Updates the observed throughput of the given peer for the type
+    /// of the specified request, using the size of the response and
+    /// the time it took to receive it.
+    pub fn update_throughput(
+        &self,
+        peer: PeerNetworkId,
+        request: &StorageServiceRequest,
+        num_response_bytes: u64,
+        response_time: Duration,
+    ) {
+        // Ignore responses without a measurable response time
+        let response_time_secs = response_time.as_secs_f64();
+        if response_time_secs <= 0.0 {
+            return;
+        }
+
+        // Update the peer's throughput
+        let request_label = request.data_request.get_label().into();
+        if let Some(mut entry) = self.peer_to_state.get_mut(&peer) {
+            entry.update_throughput(
+                request_label,
+                num_response_bytes as f64 / response_time_secs,
+            );
+        }
+    }
+
     /// Updates the storage summary for the given peer
     pub fn update_summary(&self, peer: PeerNetworkId, storage_summary: StorageServerSummary) {
         self.peer_to_state
@@ -30,7 +30,7 @@ use futures::StreamExt;
 use maplit::hashset;
 use rand::Rng;
 use std::{cmp, collections::HashSet, sync::Arc, time::Duration};
-use tokio::{runtime::Handle, task::JoinHandle};
+use tokio::{runtime::Handle, sync::watch, task::JoinHandle};
 
 // Useful constants
 const GLOBAL_DATA_LOG_FREQ_SECS: u64 = 10;
@@ -44,6 +44,7 @@ const POLLER_LOG_FREQ_SECS: u64 = 2;
 pub struct DataSummaryPoller {
     data_client_config: Arc<AptosDataClientConfig>, // The configuration for the data client
     data_client: AptosDataClient,                   // The data client through which to poll peers
+    data_poller_config_updates: Option<watch::Receiver<AptosDataPollerConfig>>, // Optional updates to the poller config
     in_flight_priority_polls: Arc<DashSet<PeerNetworkId>>, // The set of priority peers with in-flight polls
     in_flight_regular_polls: Arc<DashSet<PeerNetworkId>>, // The set of regular peers with in-flight polls
     peers_and_metadata: Arc<PeersAndMetadata>,            // The peers and metadata
@@ -64,6 +65,7 @@ impl DataSummaryPoller {
         Self {
             data_client_config,
             data_client,
+            data_poller_config_updates: None,
             in_flight_priority_polls: Arc::new(DashSet::new()),
             in_flight_regular_polls: Arc::new(DashSet::new()),
             peers_and_metadata,
@@ -73,6 +75,24 @@ impl DataSummaryPoller {
         }
     }
 
+    /// Applies the given data poller config updates (e.g., from node config
+    /// reloads). Note: the poll loop interval is not updated at runtime.
+    pub fn with_config_updates(
+        mut self,
+        data_poller_config_updates: watch::Receiver<AptosDataPollerConfig>,
+    ) -> Self {
+        self.data_poller_config_updates = Some(data_poller_config_updates);
+        self
+    }
+
+    /// Returns the latest data poller config
+    fn get_data_poller_config(&self) -> AptosDataPollerConfig {
+        match &self.data_poller_config_updates {
+            Some(data_poller_config_updates) => *data_poller_config_updates.borrow(),
+            None => self.data_client_config.data_poller_config,
+        }
+    }
+
     /// Returns the next set of peers to poll based on the priorities
     pub(crate) fn identify_peers_to_poll(
         &self,
@@ -102,7 +122,7 @@ impl DataSummaryPoller {
         update_in_flight_metrics(PRIORITIZED_PEER, num_in_flight_polls);
 
         // Ensure we don't go over the maximum number of in-flight polls
-        let data_poller_config = self.data_client_config.data_poller_config;
+        let data_poller_config = self.get_data_poller_config();
         let max_num_in_flight_polls = data_poller_config.max_num_in_flight_priority_polls;
         if num_in_flight_polls >= max_num_in_flight_polls {
             return hashset![];
@@ -113,7 +133,7 @@ impl DataSummaryPoller {
         let num_peers_to_poll = calculate_num_peers_to_poll(
             &all_priority_peers,
             max_num_peers_to_poll,
-            self.data_client_config.data_poller_config,
+            data_poller_config,
         );
 
         // Select a subset of the priority peers to poll
@@ -131,7 +151,7 @@ impl DataSummaryPoller {
         update_in_flight_metrics(REGULAR_PEER, num_in_flight_polls);
 
         // Ensure we don't go over the maximum number of in-flight polls
-        let data_poller_config = self.data_client_config.data_poller_config;
+        let data_poller_config = self.get_data_poller_config();
         let max_num_in_flight_polls = data_poller_config.max_num_in_flight_regular_polls;
         if num_in_flight_polls >= max_num_in_flight_polls {
             return hashset![];
@@ -142,7 +162,7 @@ impl DataSummaryPoller {
         let num_peers_to_poll = calculate_num_peers_to_poll(
             &all_regular_peers,
             max_num_peers_to_poll,
-            self.data_client_config.data_poller_config,
+            data_poller_config,
         );
 
         // Select a subset of the regular peers to poll
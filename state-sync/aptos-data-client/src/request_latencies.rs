@@ -0,0 +1,105 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_infallible::Mutex;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::Duration,
+};
+
+// Useful constants
+const MAX_LATENCY_SAMPLES_PER_TYPE: usize = 100; // The number of recent latencies to keep per request type
+const MIN_LATENCY_SAMPLES_FOR_PERCENTILE: usize = 10; // The number of latencies required to calculate a percentile
+
+/// A simple tracker for the latencies of recent successful
+/// requests (by data request label). This is used to decide
+/// when a slow request should be hedged.
+#[derive(Clone, Debug, Default)]
+pub struct RequestLatencies {
+    latencies_by_type: Arc<Mutex<HashMap<&'static str, VecDeque<Duration>>>>,
+}
+
+impl RequestLatencies {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the latency at the given percentile (of the recent
+    /// latencies for the request label). If there aren't enough
+    /// latencies to calculate the percentile, None is returned.
+    pub fn get_latency_percentile(&self, request_label: &str, percentile: u64) -> Option<Duration> {
+        // Get the recent latencies for the request label
+        let mut latencies: Vec<Duration> = self
+            .latencies_by_type
+            .lock()
+            .get(request_label)?
+            .iter()
+            .copied()
+            .collect();
+        if latencies.len() < MIN_LATENCY_SAMPLES_FOR_PERCENTILE {
+            return None;
+        }
+
+        // Calculate the percentile (using the nearest-rank method)
+        latencies.sort_unstable();
+        let rank = (percentile.min(100) as usize * latencies.len() + 99) / 100;
+        latencies.get(rank.saturating_sub(1)).copied()
+    }
+
+    /// Records the latency of a successful request with the given label
+    pub fn record_latency(&self, request_label: &'static str, latency: Duration) {
+        let mut latencies_by_type = self.latencies_by_type.lock();
+        let latencies = latencies_by_type.entry(request_label).or_default();
+        if latencies.len() >= MAX_LATENCY_SAMPLES_PER_TYPE {
+            latencies.pop_front();
+        }
+        latencies.push_back(latency);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_percentile() {
+        let request_latencies = RequestLatencies::new();
+        let request_label = "get_transactions_with_proof";
+
+        // Verify that a percentile requires enough latencies
+        for latency_ms in 1..MIN_LATENCY_SAMPLES_FOR_PERCENTILE as u64 {
+            request_latencies.record_latency(request_label, Duration::from_millis(latency_ms));
+        }
+        assert_eq!(
+            request_latencies.get_latency_percentile(request_label, 50),
+            None
+        );
+
+        // Record latencies of 1 to 100 ms and verify the percentiles
+        for latency_ms in MIN_LATENCY_SAMPLES_FOR_PERCENTILE as u64..=100 {
+            request_latencies.record_latency(request_label, Duration::from_millis(latency_ms));
+        }
+        for percentile in [1, 50, 95, 100] {
+            assert_eq!(
+                request_latencies.get_latency_percentile(request_label, percentile),
+                Some(Duration::from_millis(percentile))
+            );
+        }
+
+        // Verify that only the most recent latencies are kept
+        for _ in 0..MAX_LATENCY_SAMPLES_PER_TYPE {
+            request_latencies.record_latency(request_label, Duration::from_millis(1000));
+        }
+        assert_eq!(
+            request_latencies.get_latency_percentile(request_label, 1),
+            Some(Duration::from_millis(1000))
+        );
+
+        // Verify that other request labels are unaffected
+        assert_eq!(
+            request_latencies.get_latency_percentile("get_state_values_with_proof", 50),
+            None
+        );
+    }
+}
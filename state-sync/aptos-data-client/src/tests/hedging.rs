@@ -0,0 +1,172 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::AptosDataClient,
+    interface::AptosDataClientInterface,
+    tests::{mock::MockNetwork, utils},
+};
+use aptos_config::{
+    config::AptosDataClientConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
+use aptos_storage_service_types::requests::{
+    DataRequest, StorageServiceRequest, TransactionsWithProofRequest,
+};
+use claims::assert_ok;
+use futures::FutureExt;
+use std::{collections::HashMap, time::Duration};
+
+#[tokio::test]
+async fn hedged_request_first_peer_responds() {
+    // Create a data client config with request hedging enabled
+    let min_hedged_request_delay_ms = 1000;
+    let data_client_config = AptosDataClientConfig {
+        enable_request_hedging: true,
+        min_hedged_request_delay_ms,
+        ..Default::default()
+    };
+
+    // Create the mock network and client with two serviceable peers
+    let (mut mock_network, mut mock_time, client, _) =
+        MockNetwork::new(None, Some(data_client_config), None);
+    let (_, network_id) = add_serviceable_peers(&client, &mut mock_network);
+
+    // Send a transactions request
+    let request_timeout = data_client_config.response_timeout_ms;
+    let response = tokio::spawn(async move {
+        client
+            .get_transactions_with_proof(100, 50, 100, false, request_timeout)
+            .await
+    });
+
+    // Respond to the request before the hedging delay elapses
+    let network_request = utils::get_network_request(&mut mock_network, network_id).await;
+    utils::handle_transactions_request(network_request, true);
+
+    // Verify the response is returned
+    assert_ok!(response.await.unwrap());
+
+    // Elapse the hedging delay and verify no hedged request is sent
+    mock_time
+        .advance_async(Duration::from_millis(min_hedged_request_delay_ms))
+        .await;
+    tokio::task::yield_now().await;
+    assert!(mock_network
+        .next_request(network_id)
+        .now_or_never()
+        .is_none());
+}
+
+#[tokio::test]
+async fn hedged_request_second_peer_responds() {
+    // Create a data client config with request hedging enabled
+    let min_hedged_request_delay_ms = 1000;
+    let data_client_config = AptosDataClientConfig {
+        enable_request_hedging: true,
+        min_hedged_request_delay_ms,
+        ..Default::default()
+    };
+
+    // Create the mock network and client with two serviceable peers
+    let (mut mock_network, mut mock_time, client, _) =
+        MockNetwork::new(None, Some(data_client_config), None);
+    let (_, network_id) = add_serviceable_peers(&client, &mut mock_network);
+
+    // Send a transactions request
+    let request_timeout = data_client_config.response_timeout_ms;
+    let response = tokio::spawn(async move {
+        client
+            .get_transactions_with_proof(100, 50, 100, false, request_timeout)
+            .await
+    });
+
+    // Receive the request, but don't respond
+    let first_network_request = utils::get_network_request(&mut mock_network, network_id).await;
+
+    // Elapse the hedging delay
+    mock_time
+        .advance_async(Duration::from_millis(min_hedged_request_delay_ms))
+        .await;
+
+    // Verify the request is hedged to the other peer and respond
+    let second_network_request = utils::get_network_request(&mut mock_network, network_id).await;
+    assert_ne!(
+        first_network_request.peer_network_id,
+        second_network_request.peer_network_id
+    );
+    utils::handle_transactions_request(second_network_request, true);
+
+    // Verify the response of the second peer is returned
+    assert_ok!(response.await.unwrap());
+}
+
+#[tokio::test]
+async fn throughput_peer_selection() {
+    // Create a data client config with throughput peer selection enabled
+    let data_client_config = AptosDataClientConfig {
+        enable_throughput_peer_selection: true,
+        ..Default::default()
+    };
+
+    // Create the mock network and client with two serviceable peers
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+    let (peers, _) = add_serviceable_peers(&client, &mut mock_network);
+    let (fast_peer, slow_peer) = (peers[0], peers[1]);
+
+    // Record a high throughput for the fast peer and a low throughput for the slow peer
+    let storage_request = create_transactions_request();
+    let peer_states = client.get_peer_states();
+    peer_states.update_throughput(
+        fast_peer,
+        &storage_request,
+        1_000_000,
+        Duration::from_secs(1),
+    );
+    peer_states.update_throughput(slow_peer, &storage_request, 1_000, Duration::from_secs(1));
+
+    // Select a peer to service the request multiple times
+    let mut peers_and_selection_counts = HashMap::new();
+    for _ in 0..1000 {
+        let selected_peer = client.choose_peer_for_request(&storage_request).unwrap();
+        *peers_and_selection_counts.entry(selected_peer).or_insert(0) += 1;
+    }
+
+    // Verify the fast peer is selected much more often than the slow peer
+    let fast_peer_count = peers_and_selection_counts.get(&fast_peer).unwrap();
+    let slow_peer_count = peers_and_selection_counts
+        .get(&slow_peer)
+        .copied()
+        .unwrap_or_default();
+    assert!(*fast_peer_count > 10 * slow_peer_count);
+}
+
+/// Adds two priority peers (that can service transaction requests) to the
+/// network, and returns the peers and the network id.
+fn add_serviceable_peers(
+    client: &AptosDataClient,
+    mock_network: &mut MockNetwork,
+) -> (Vec<PeerNetworkId>, NetworkId) {
+    let mut peers = vec![];
+    let mut network_id = NetworkId::Validator;
+    for _ in 0..2 {
+        let (peer, peer_network_id) = utils::add_peer_to_network(true, mock_network);
+        client.update_peer_storage_summary(peer, utils::create_storage_summary(200));
+        peers.push(peer);
+        network_id = peer_network_id;
+    }
+    client.update_global_summary_cache().unwrap();
+
+    (peers, network_id)
+}
+
+/// Creates a transactions request that can be serviced by the test peers
+fn create_transactions_request() -> StorageServiceRequest {
+    let data_request = DataRequest::GetTransactionsWithProof(TransactionsWithProofRequest {
+        proof_version: 100,
+        start_version: 50,
+        end_version: 100,
+        include_events: false,
+    });
+    StorageServiceRequest::new(data_request, true)
+}
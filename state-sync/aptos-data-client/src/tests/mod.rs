@@ -3,6 +3,7 @@
 
 mod advertise;
 mod compression;
+mod hedging;
 pub mod mock;
 mod peers;
 mod poller;
@@ -3,7 +3,7 @@
 
 use crate::{
     get_first_seq_num_and_limit,
-    pruner::{LedgerPrunerManager, StateMerklePrunerManager},
+    pruner::{LedgerPrunerManager, LedgerRetentionFilter, StateMerklePrunerManager},
     test_helper,
     test_helper::{arb_blocks_to_commit, put_as_state_root, put_transaction_info},
     AptosDB, PrunerManager, StaleNodeIndexSchema,
@@ -26,7 +26,10 @@ use aptos_types::{
 };
 use proptest::prelude::*;
 use std::{collections::HashSet, sync::Arc};
-use test_helper::{test_save_blocks_impl, test_sync_transactions_impl};
+use test_helper::{
+    arb_blocks_to_commit_with_block_events, test_read_from_cold_ledger_db_impl,
+    test_retain_ledger_history_impl, test_save_blocks_impl, test_sync_transactions_impl,
+};
 
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(10))]
@@ -40,6 +43,16 @@ proptest! {
     fn test_sync_transactions(input in arb_blocks_to_commit(), threshold in 10..20usize) {
         test_sync_transactions_impl(input, threshold);
     }
+
+    #[test]
+    fn test_read_from_cold_ledger_db(input in arb_blocks_to_commit_with_block_events()) {
+        test_read_from_cold_ledger_db_impl(input);
+    }
+
+    #[test]
+    fn test_retain_ledger_history(input in arb_blocks_to_commit()) {
+        test_retain_ledger_history_impl(input);
+    }
 }
 
 #[test]
@@ -100,13 +113,17 @@ fn test_pruner_config() {
         assert_eq!(state_merkle_pruner.is_pruner_enabled(), enable);
         assert_eq!(state_merkle_pruner.get_prune_window(), 20);
 
-        let ledger_pruner =
-            LedgerPrunerManager::new(Arc::clone(&aptos_db.ledger_db), LedgerPrunerConfig {
+        let ledger_pruner = LedgerPrunerManager::new(
+            Arc::clone(&aptos_db.ledger_db),
+            LedgerPrunerConfig {
                 enable,
                 prune_window: 100,
                 batch_size: 1,
                 user_pruning_window_offset: 0,
-            });
+            },
+            LedgerRetentionFilter::default(),
+            None,
+        );
         assert_eq!(ledger_pruner.is_pruner_enabled(), enable);
         assert_eq!(ledger_pruner.get_prune_window(), 100);
     }
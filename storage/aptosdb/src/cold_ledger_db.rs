@@ -0,0 +1,155 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The cold ledger db keeps the ledger data that has left the prune window of the ledger pruner.
+//! It's an append-only RocksDB instance with heavy compression, the ledger pruner moves the data
+//! of every version into it before pruning the version from the ledger db.
+
+use crate::{
+    db_options::{cold_ledger_db_column_families, gen_cold_ledger_cfds},
+    metrics::{COLD_LEDGER_ARCHIVE_PROGRESS, COLD_LEDGER_READ_LATENCY_SECONDS},
+    schema::{
+        cold_block_event::ColdBlockEventSchema,
+        cold_transaction_data::ColdTransactionDataSchema,
+        db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+    },
+    utils::iterators::ExpectContinuousVersions,
+};
+use anyhow::Result;
+use aptos_config::config::RocksdbConfig;
+use aptos_logger::prelude::info;
+use aptos_rocksdb_options::gen_rocksdb_options;
+use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
+use aptos_types::{
+    account_config::NewBlockEvent,
+    contract_event::ContractEvent,
+    transaction::{Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
+};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const COLD_LEDGER_DB_NAME: &str = "cold_ledger_db";
+
+/// Everything the ledger db has for a single version, stored together in the cold ledger db.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+pub(crate) struct ColdTransactionData {
+    pub transaction: Transaction,
+    pub info: TransactionInfo,
+    pub events: Vec<ContractEvent>,
+    pub write_set: WriteSet,
+}
+
+#[derive(Debug)]
+pub(crate) struct ColdLedgerDb {
+    db: DB,
+}
+
+impl ColdLedgerDb {
+    pub(crate) fn new<P: AsRef<Path>>(
+        path: P,
+        rocksdb_config: &RocksdbConfig,
+        readonly: bool,
+    ) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let db = if readonly {
+            DB::open_cf_readonly(
+                &gen_rocksdb_options(rocksdb_config, true),
+                path.clone(),
+                COLD_LEDGER_DB_NAME,
+                cold_ledger_db_column_families(),
+            )?
+        } else {
+            DB::open_cf(
+                &gen_rocksdb_options(rocksdb_config, false),
+                path.clone(),
+                COLD_LEDGER_DB_NAME,
+                gen_cold_ledger_cfds(rocksdb_config),
+            )?
+        };
+
+        info!("Opened {COLD_LEDGER_DB_NAME} at {path:?}!");
+
+        let cold_ledger_db = Self { db };
+        COLD_LEDGER_ARCHIVE_PROGRESS.set(cold_ledger_db.progress()? as i64);
+        Ok(cold_ledger_db)
+    }
+
+    /// The version up to which the ledger data has been moved to the cold ledger db. Versions that
+    /// were already pruned when the cold ledger db was enabled are missing from it.
+    pub(crate) fn progress(&self) -> Result<Version> {
+        Ok(self
+            .db
+            .get::<DbMetadataSchema>(&DbMetadataKey::ColdLedgerArchiveProgress)?
+            .map_or(0, |v| v.expect_version()))
+    }
+
+    pub(crate) fn get_transaction_data(
+        &self,
+        version: Version,
+    ) -> Result<Option<ColdTransactionData>> {
+        let _timer = COLD_LEDGER_READ_LATENCY_SECONDS
+            .with_label_values(&["transaction_data"])
+            .start_timer();
+        self.db.get::<ColdTransactionDataSchema>(&version)
+    }
+
+    /// Returns an iterator over the data of `num_versions` versions starting at `start_version`,
+    /// which errors out if any of the versions is missing.
+    pub(crate) fn get_transaction_data_iter(
+        &self,
+        start_version: Version,
+        num_versions: usize,
+    ) -> Result<impl Iterator<Item = Result<ColdTransactionData>> + '_> {
+        let mut iter = self
+            .db
+            .iter::<ColdTransactionDataSchema>(ReadOptions::default())?;
+        iter.seek(&start_version)?;
+        iter.expect_continuous_versions(start_version, num_versions)
+    }
+
+    /// Returns the first version of the block the version belongs to, and its `NewBlockEvent`.
+    pub(crate) fn get_block_event(
+        &self,
+        version: Version,
+    ) -> Result<Option<(Version, NewBlockEvent)>> {
+        let _timer = COLD_LEDGER_READ_LATENCY_SECONDS
+            .with_label_values(&["block_event"])
+            .start_timer();
+        let mut iter = self
+            .db
+            .iter::<ColdBlockEventSchema>(ReadOptions::default())?;
+        iter.seek_for_prev(&version)?;
+        iter.next()
+            .transpose()?
+            .map(|(first_version, event)| Ok((first_version, bcs::from_bytes(event.event_data())?)))
+            .transpose()
+    }
+
+    /// Adds the data of a version to the batch, along with its block event if it starts a block.
+    pub(crate) fn put_transaction_data(
+        &self,
+        version: Version,
+        data: &ColdTransactionData,
+        block_event: Option<&ContractEvent>,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        batch.put::<ColdTransactionDataSchema>(&version, data)?;
+        if let Some(block_event) = block_event {
+            batch.put::<ColdBlockEventSchema>(&version, block_event)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the batch along with the new progress.
+    pub(crate) fn commit(&self, progress: Version, batch: SchemaBatch) -> Result<()> {
+        batch.put::<DbMetadataSchema>(
+            &DbMetadataKey::ColdLedgerArchiveProgress,
+            &DbMetadataValue::Version(progress),
+        )?;
+        self.db.write_schemas(batch)?;
+        COLD_LEDGER_ARCHIVE_PROGRESS.set(progress as i64);
+        Ok(())
+    }
+}
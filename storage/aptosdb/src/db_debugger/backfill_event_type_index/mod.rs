@@ -0,0 +1,82 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::ShardingConfig, ledger_db::LedgerDb, schema::event::EventSchema,
+    utils::truncation_helper::get_ledger_commit_progress, EventStore,
+};
+use anyhow::Result;
+use aptos_config::config::RocksdbConfigs;
+use aptos_schemadb::{ReadOptions, SchemaBatch};
+use aptos_types::transaction::Version;
+use clap::Parser;
+use std::path::PathBuf;
+
+#[derive(Parser)]
+#[clap(
+    about = "Backfill the event by type index for the module events committed before the index \
+    existed. The node must be stopped."
+)]
+pub struct Cmd {
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+
+    /// The first version to backfill. Events that have been pruned are skipped, but the events
+    /// kept by the ledger retention policy below the pruner progress are backfilled.
+    #[clap(long, default_value_t = 0)]
+    start_version: Version,
+
+    /// The number of events indexed per write batch.
+    #[clap(long, default_value_t = 10000)]
+    batch_size: usize,
+
+    #[clap(flatten)]
+    sharding_config: ShardingConfig,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let ledger_db = LedgerDb::new(
+            self.db_dir.as_path(),
+            RocksdbConfigs {
+                enable_storage_sharding: self.sharding_config.enable_storage_sharding,
+                ..Default::default()
+            },
+            /*readonly=*/ false,
+        )?;
+        let ledger_commit_progress = get_ledger_commit_progress(ledger_db.metadata_db())?
+            .expect("Current version of ledger db must exist.");
+
+        println!(
+            "Backfilling the event by type index from version {} to version {}.",
+            self.start_version, ledger_commit_progress
+        );
+
+        // Go through the stored events rather than the versions, so that pruned versions are
+        // skipped while events retained below the pruner progress are still indexed.
+        let mut iter = ledger_db
+            .event_db()
+            .iter::<EventSchema>(ReadOptions::default())?;
+        iter.seek(&self.start_version)?;
+        let mut batch = SchemaBatch::new();
+        let mut num_events = 0;
+        for res in iter {
+            let ((version, idx), event) = res?;
+            if version > ledger_commit_progress {
+                break;
+            }
+            EventStore::put_event_by_type_index(version, idx, &event, &batch)?;
+            num_events += 1;
+            if num_events % self.batch_size == 0 {
+                ledger_db
+                    .event_db()
+                    .write_schemas(std::mem::replace(&mut batch, SchemaBatch::new()))?;
+                println!("Backfilled until version {}.", version);
+            }
+        }
+        ledger_db.event_db().write_schemas(batch)?;
+        println!("Done.");
+
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+pub mod backfill_event_type_index;
 pub mod checkpoint;
 mod common;
 mod examine;
@@ -31,6 +32,8 @@ pub enum Cmd {
 
     #[clap(subcommand)]
     Examine(examine::Cmd),
+
+    BackfillEventTypeIndex(backfill_event_type_index::Cmd),
 }
 
 impl Cmd {
@@ -41,6 +44,7 @@ impl Cmd {
             Cmd::Ledger(cmd) => cmd.run(),
             Cmd::Truncate(cmd) => cmd.run(),
             Cmd::Examine(cmd) => cmd.run(),
+            Cmd::BackfillEventTypeIndex(cmd) => cmd.run(),
         }
     }
 }
@@ -14,11 +14,14 @@ const VERSION_SIZE: usize = std::mem::size_of::<Version>();
 pub(super) fn ledger_db_column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
+        ACCOUNT_INVOLVEMENT_CF_NAME,
         EPOCH_BY_VERSION_CF_NAME,
         EVENT_ACCUMULATOR_CF_NAME,
         EVENT_BY_KEY_CF_NAME,
+        EVENT_BY_TYPE_CF_NAME,
         EVENT_BY_VERSION_CF_NAME,
         EVENT_CF_NAME,
+        INVOLVED_ACCOUNTS_BY_VERSION_CF_NAME,
         LEDGER_INFO_CF_NAME,
         STALE_STATE_VALUE_INDEX_CF_NAME,
         STATE_VALUE_CF_NAME,
@@ -39,6 +42,7 @@ pub(super) fn event_db_column_families() -> Vec<ColumnFamilyName> {
         DB_METADATA_CF_NAME,
         EVENT_ACCUMULATOR_CF_NAME,
         EVENT_BY_KEY_CF_NAME,
+        EVENT_BY_TYPE_CF_NAME,
         EVENT_BY_VERSION_CF_NAME,
         EVENT_CF_NAME,
     ]
@@ -56,6 +60,8 @@ pub(super) fn transaction_db_column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
         DB_METADATA_CF_NAME,
+        ACCOUNT_INVOLVEMENT_CF_NAME,
+        INVOLVED_ACCOUNTS_BY_VERSION_CF_NAME,
         TRANSACTION_CF_NAME,
         TRANSACTION_BY_ACCOUNT_CF_NAME,
         TRANSACTION_BY_HASH_CF_NAME,
@@ -108,6 +114,15 @@ pub(super) fn state_kv_db_column_families() -> Vec<ColumnFamilyName> {
     ]
 }
 
+pub(super) fn cold_ledger_db_column_families() -> Vec<ColumnFamilyName> {
+    vec![
+        /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
+        COLD_BLOCK_EVENT_CF_NAME,
+        COLD_TRANSACTION_DATA_CF_NAME,
+        DB_METADATA_CF_NAME,
+    ]
+}
+
 fn gen_cfds<F>(
     rocksdb_config: &RocksdbConfig,
     cfs: Vec<ColumnFamilyName>,
@@ -191,6 +206,15 @@ pub(super) fn gen_state_kv_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFam
     gen_cfds(rocksdb_config, cfs, with_state_key_extractor_processor)
 }
 
+pub(super) fn gen_cold_ledger_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+    let cfs = cold_ledger_db_column_families();
+    // Cold data is written once and rarely read, so trade CPU for disk space.
+    gen_cfds(rocksdb_config, cfs, |_, cf_opts| {
+        cf_opts.set_compression_type(DBCompressionType::Zstd);
+        cf_opts.set_bottommost_compression_type(DBCompressionType::Zstd);
+    })
+}
+
 fn state_key_extractor(state_value_raw_key: &[u8]) -> &[u8] {
     &state_value_raw_key[..(state_value_raw_key.len() - VERSION_SIZE)]
 }
@@ -9,9 +9,11 @@
 use super::AptosDB;
 use crate::{
     errors::AptosDbError,
+    pruner::RetainedVersions,
     schema::{
         event::EventSchema, event_accumulator::EventAccumulatorSchema,
-        event_by_key::EventByKeySchema, event_by_version::EventByVersionSchema,
+        event_by_key::EventByKeySchema, event_by_type::EventByTypeSchema,
+        event_by_version::EventByVersionSchema,
     },
     utils::iterators::EventsByVersionIter,
 };
@@ -22,6 +24,7 @@ use aptos_crypto::{
     HashValue,
 };
 use aptos_schemadb::{iterator::SchemaIterator, schema::ValueCodec, ReadOptions, SchemaBatch, DB};
+use aptos_storage_interface::Order;
 use aptos_types::{
     account_address::AccountAddress,
     account_config::{new_block_event_key, NewBlockEvent},
@@ -30,6 +33,7 @@ use aptos_types::{
     proof::position::Position,
     transaction::Version,
 };
+use move_core_types::language_storage::TypeTag;
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     convert::{TryFrom, TryInto},
@@ -190,7 +194,53 @@ impl EventStore {
         Ok(result)
     }
 
-    fn lookup_event_by_key(
+    /// Given `type_tag`, returns the module events of that type identified by transaction version
+    /// and index among all events emitted by the same transaction. The events are read from the
+    /// `(version, index)` position `start` on, in the given order. Result won't contain records
+    /// with a transaction version > `ledger_version`.
+    pub fn lookup_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start: (Version, u64),
+        order: Order,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(Version, u64)>> {
+        let iter = match order {
+            Order::Ascending => {
+                let mut iter = self
+                    .event_db
+                    .iter::<EventByTypeSchema>(ReadOptions::default())?;
+                iter.seek(&(type_tag.clone(), start.0, start.1))?;
+                iter
+            },
+            Order::Descending => {
+                let start = if start.0 > ledger_version {
+                    (ledger_version, u64::MAX)
+                } else {
+                    start
+                };
+                let mut iter = self
+                    .event_db
+                    .rev_iter::<EventByTypeSchema>(ReadOptions::default())?;
+                iter.seek_for_prev(&(type_tag.clone(), start.0, start.1))?;
+                iter
+            },
+        };
+
+        let mut result = Vec::new();
+        for res in iter.take(limit as usize) {
+            let ((event_type, ver, idx), ()) = res?;
+            if event_type != *type_tag || ver > ledger_version {
+                break;
+            }
+            result.push((ver, idx));
+        }
+
+        Ok(result)
+    }
+
+    pub fn lookup_event_by_key(
         &self,
         event_key: &EventKey,
         seq_num: u64,
@@ -317,16 +367,24 @@ impl EventStore {
             .iter()
             .enumerate()
             .try_for_each::<_, Result<_>>(|(idx, event)| {
-                if let ContractEvent::V1(v1) = event {
-                    if !skip_index {
-                        batch.put::<EventByKeySchema>(
-                            &(*v1.key(), v1.sequence_number()),
-                            &(version, idx as u64),
-                        )?;
-                        batch.put::<EventByVersionSchema>(
-                            &(*v1.key(), version, v1.sequence_number()),
-                            &(idx as u64),
-                        )?;
+                if !skip_index {
+                    match event {
+                        ContractEvent::V1(v1) => {
+                            batch.put::<EventByKeySchema>(
+                                &(*v1.key(), v1.sequence_number()),
+                                &(version, idx as u64),
+                            )?;
+                            batch.put::<EventByVersionSchema>(
+                                &(*v1.key(), version, v1.sequence_number()),
+                                &(idx as u64),
+                            )?;
+                        },
+                        ContractEvent::V2(v2) => {
+                            batch.put::<EventByTypeSchema>(
+                                &(v2.type_tag().clone(), version, idx as u64),
+                                &(),
+                            )?;
+                        },
                     }
                 }
                 batch.put::<EventSchema>(&(version, idx as u64), event)
@@ -350,6 +408,20 @@ impl EventStore {
         Ok(())
     }
 
+    /// Adds the event by type index of the event at `idx` of the transaction at `version`, if
+    /// it's a module event. Used to backfill the index for versions committed before it existed.
+    pub(crate) fn put_event_by_type_index(
+        version: Version,
+        idx: u64,
+        event: &ContractEvent,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        if let ContractEvent::V2(v2) = event {
+            batch.put::<EventByTypeSchema>(&(v2.type_tag().clone(), version, idx), &())?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn put_events_multiple_versions(
         &self,
         first_version: u64,
@@ -482,14 +554,7 @@ impl EventStore {
         let mut current_version = start;
         for events in self.get_events_by_version_iter(start, (end - start) as usize)? {
             for (idx, event) in (events?).into_iter().enumerate() {
-                if let ContractEvent::V1(v1) = event {
-                    db_batch.delete::<EventByVersionSchema>(&(
-                        *v1.key(),
-                        current_version,
-                        v1.sequence_number(),
-                    ))?;
-                    db_batch.delete::<EventByKeySchema>(&(*v1.key(), v1.sequence_number()))?;
-                }
+                Self::delete_event_indices(current_version, idx as u64, &event, db_batch)?;
                 db_batch.delete::<EventSchema>(&(current_version, idx as u64))?;
             }
             current_version += 1;
@@ -497,6 +562,62 @@ impl EventStore {
         self.prune_event_accumulator(start, end, db_batch)?;
         Ok(())
     }
+
+    /// Prune the events in the range of version in [begin, end) and all related indices, except
+    /// for the data kept by the ledger retention policy. The retained versions keep all their
+    /// events, but only the retained events keep their indices. The retained events of other
+    /// versions keep everything.
+    pub fn prune_events_with_retention(
+        &self,
+        start: Version,
+        end: Version,
+        retained_versions: &RetainedVersions,
+        db_batch: &SchemaBatch,
+    ) -> anyhow::Result<()> {
+        let mut current_version = start;
+        for events in self.get_events_by_version_iter(start, (end - start) as usize)? {
+            let is_version_retained = retained_versions.contains(current_version);
+            for (idx, event) in (events?).into_iter().enumerate() {
+                if retained_versions.retains_event(&event) {
+                    continue;
+                }
+                // The indices point into the cold ledger db if it's enabled.
+                if !retained_versions.keeps_indices() {
+                    Self::delete_event_indices(current_version, idx as u64, &event, db_batch)?;
+                }
+                if !is_version_retained {
+                    db_batch.delete::<EventSchema>(&(current_version, idx as u64))?;
+                }
+            }
+            current_version += 1;
+        }
+        for range in retained_versions.pruned_ranges() {
+            self.prune_event_accumulator(range.start, range.end, db_batch)?;
+        }
+        Ok(())
+    }
+
+    /// Deletes the indices of the event at the given version and index.
+    fn delete_event_indices(
+        version: Version,
+        idx: u64,
+        event: &ContractEvent,
+        db_batch: &SchemaBatch,
+    ) -> Result<()> {
+        match event {
+            ContractEvent::V1(v1) => {
+                db_batch.delete::<EventByVersionSchema>(&(
+                    *v1.key(),
+                    version,
+                    v1.sequence_number(),
+                ))?;
+                db_batch.delete::<EventByKeySchema>(&(*v1.key(), v1.sequence_number()))
+            },
+            ContractEvent::V2(v2) => {
+                db_batch.delete::<EventByTypeSchema>(&(v2.type_tag().clone(), version, idx))
+            },
+        }
+    }
 }
 
 struct EventHashReader<'a> {
@@ -359,3 +359,80 @@ proptest! {
         test_get_last_version_before_timestamp_impl(new_block_events)
     }
 }
+
+#[test]
+fn test_lookup_events_by_type() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = &db.event_store;
+
+    // Save module events of two types, along with a v1 event of the first type
+    let type_a = TypeTag::U64;
+    let type_b = TypeTag::Bool;
+    let batch = SchemaBatch::new();
+    for version in 0..10 {
+        let events = vec![
+            ContractEvent::new_v2(type_a.clone(), vec![version as u8]),
+            ContractEvent::new_v1(EventKey::random(), version, type_a.clone(), vec![]),
+            ContractEvent::new_v2(type_b.clone(), vec![]),
+            ContractEvent::new_v2(type_a.clone(), vec![version as u8]),
+        ];
+        store
+            .put_events(version, &events, /*skip_index=*/ false, &batch)
+            .unwrap();
+    }
+    store.event_db.write_schemas(batch).unwrap();
+
+    // Only the module events are indexed, in order of version and index
+    assert_eq!(
+        store
+            .lookup_events_by_type(&type_a, (2, 0), Order::Ascending, 5, 9)
+            .unwrap(),
+        vec![(2, 0), (2, 3), (3, 0), (3, 3), (4, 0)]
+    );
+    assert_eq!(
+        store
+            .lookup_events_by_type(&type_b, (7, u64::MAX), Order::Descending, 3, 9)
+            .unwrap(),
+        vec![(7, 2), (6, 2), (5, 2)]
+    );
+
+    // Lookups can start in the middle of the events of a version
+    assert_eq!(
+        store
+            .lookup_events_by_type(&type_a, (2, 1), Order::Ascending, 2, 9)
+            .unwrap(),
+        vec![(2, 3), (3, 0)]
+    );
+    assert_eq!(
+        store
+            .lookup_events_by_type(&type_a, (3, 2), Order::Descending, 2, 9)
+            .unwrap(),
+        vec![(3, 0), (2, 3)]
+    );
+
+    // Events after the ledger version are not returned
+    assert_eq!(
+        store
+            .lookup_events_by_type(&type_b, (8, 0), Order::Ascending, 5, 8)
+            .unwrap(),
+        vec![(8, 2)]
+    );
+    assert_eq!(
+        store
+            .lookup_events_by_type(&type_b, (u64::MAX, u64::MAX), Order::Descending, 1, 8)
+            .unwrap(),
+        vec![(8, 2)]
+    );
+
+    // Pruning the events also prunes the index
+    let batch = SchemaBatch::new();
+    store.prune_events(0, 5, &batch).unwrap();
+    store.event_db.write_schemas(batch).unwrap();
+    assert_eq!(
+        store
+            .lookup_events_by_type(&type_b, (0, 0), Order::Ascending, 2, 9)
+            .unwrap(),
+        vec![(5, 2), (6, 2)]
+    );
+}
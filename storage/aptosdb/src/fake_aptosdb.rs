@@ -585,6 +585,18 @@ impl DbReader for FakeAptosDB {
             .get_events(event_key, start, order, limit, ledger_version)
     }
 
+    fn get_events_by_type(
+        &self,
+        event_type: &move_core_types::language_storage::TypeTag,
+        start: (Version, u64),
+        order: aptos_storage_interface::Order,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(u64, EventWithVersion)>> {
+        self.inner
+            .get_events_by_type(event_type, start, order, limit, ledger_version)
+    }
+
     fn get_block_timestamp(&self, version: Version) -> Result<u64> {
         gauged_api("get_block_timestamp", || {
             ensure!(version <= self.get_latest_version()?);
@@ -680,6 +692,25 @@ impl DbReader for FakeAptosDB {
             .get_account_transactions(address, seq_num, limit, include_events, ledger_version)
     }
 
+    fn get_account_involved_transactions(
+        &self,
+        address: aptos_types::PeerId,
+        start: Version,
+        order: aptos_storage_interface::Order,
+        limit: u64,
+        include_events: bool,
+        ledger_version: Version,
+    ) -> Result<aptos_types::transaction::AccountTransactionsWithProof> {
+        self.inner.get_account_involved_transactions(
+            address,
+            start,
+            order,
+            limit,
+            include_events,
+            ledger_version,
+        )
+    }
+
     fn get_state_proof_with_ledger_info(
         &self,
         known_version: u64,
@@ -837,6 +868,10 @@ impl DbReader for FakeAptosDB {
         self.inner.get_ledger_prune_window()
     }
 
+    fn is_pruned_transaction_kept(&self, version: Version) -> Result<bool> {
+        self.inner.is_pruned_transaction_kept(version)
+    }
+
     fn get_table_info(&self, handle: table::TableHandle) -> Result<table::TableInfo> {
         self.inner.get_table_info(handle)
     }
@@ -42,12 +42,15 @@ impl FastSyncStorageWrapper {
     /// If the db is empty and configured to do fast sync, we return a FastSyncStorageWrapper
     /// Otherwise, we returns AptosDB directly and the FastSyncStorageWrapper is None
     pub fn initialize_dbs(config: &NodeConfig) -> Result<Either<AptosDB, Self>> {
-        let db_main = AptosDB::open(
+        let db_main = AptosDB::open_with_ledger_history(
             config.storage.get_dir_paths(),
             /*readonly=*/ false,
             config.storage.storage_pruner_config,
+            &config.storage.ledger_retention,
+            &config.storage.ledger_cold_storage,
             config.storage.rocksdb_configs,
             config.storage.enable_indexer,
+            config.storage.enable_account_involvement_index,
             config.storage.buffered_state_target_items,
             config.storage.max_num_nodes_per_lru_cache_shard,
         )
@@ -23,6 +23,7 @@ pub mod schema;
 pub mod state_restore;
 pub mod utils;
 
+mod cold_ledger_db;
 mod db_options;
 mod event_store;
 mod ledger_db;
@@ -46,6 +47,7 @@ pub mod fast_sync_storage_wrapper;
 use crate::state_store::buffered_state::BufferedState;
 use crate::{
     backup::{backup_handler::BackupHandler, restore_handler::RestoreHandler, restore_utils},
+    cold_ledger_db::{ColdLedgerDb, ColdTransactionData, COLD_LEDGER_DB_NAME},
     db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
     db_options::{
         event_db_column_families, ledger_db_column_families, ledger_metadata_db_column_families,
@@ -61,7 +63,10 @@ use crate::{
         API_LATENCY_SECONDS, COMMITTED_TXNS, LATEST_TXN_VERSION, LEDGER_VERSION, NEXT_BLOCK_EPOCH,
         OTHER_TIMERS_SECONDS, ROCKSDB_PROPERTIES,
     },
-    pruner::{LedgerPrunerManager, PrunerManager, StateKvPrunerManager, StateMerklePrunerManager},
+    pruner::{
+        LedgerPrunerManager, LedgerRetentionFilter, PrunerManager, StateKvPrunerManager,
+        StateMerklePrunerManager,
+    },
     schema::*,
     stale_node_index::StaleNodeIndexSchema,
     stale_node_index_cross_epoch::StaleNodeIndexCrossEpochSchema,
@@ -72,7 +77,8 @@ use crate::{
 };
 use anyhow::{bail, ensure, Result};
 use aptos_config::config::{
-    PrunerConfig, RocksdbConfig, RocksdbConfigs, StorageDirPaths, NO_OP_STORAGE_PRUNER_CONFIG,
+    LedgerColdStorageConfig, LedgerRetentionConfig, PrunerConfig, RocksdbConfig, RocksdbConfigs,
+    StorageDirPaths, NO_OP_STORAGE_PRUNER_CONFIG,
 };
 #[cfg(any(test, feature = "fuzzing"))]
 use aptos_config::config::{
@@ -101,7 +107,7 @@ use aptos_types::{
     proof::{
         accumulator::InMemoryAccumulator, AccumulatorConsistencyProof, SparseMerkleProofExt,
         TransactionAccumulatorRangeProof, TransactionAccumulatorSummary,
-        TransactionInfoListWithProof,
+        TransactionInfoListWithProof, TransactionInfoWithProof,
     },
     state_proof::StateProof,
     state_store::{
@@ -121,13 +127,14 @@ use aptos_types::{
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use arr_macro::arr;
+use move_core_types::language_storage::TypeTag;
 use move_resource_viewer::MoveValueAnnotator;
 use once_cell::sync::Lazy;
 use rayon::prelude::*;
 #[cfg(any(test, feature = "fuzzing"))]
 use std::default::Default;
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fmt::{Debug, Formatter},
     iter::Iterator,
     path::Path,
@@ -337,10 +344,13 @@ pub struct AptosDB {
     pub(crate) state_store: Arc<StateStore>,
     pub(crate) transaction_store: Arc<TransactionStore>,
     ledger_pruner: LedgerPrunerManager,
+    /// Keeps the ledger data pruned from `ledger_db`, if enabled.
+    cold_ledger_db: Option<Arc<ColdLedgerDb>>,
     _rocksdb_property_reporter: RocksdbPropertyReporter,
     ledger_commit_lock: std::sync::Mutex<()>,
     indexer: Option<Indexer>,
     skip_index_and_usage: bool,
+    enable_account_involvement_index: bool,
 }
 
 impl AptosDB {
@@ -349,14 +359,18 @@ impl AptosDB {
         state_merkle_db: StateMerkleDb,
         state_kv_db: StateKvDb,
         pruner_config: PrunerConfig,
+        ledger_retention_filter: LedgerRetentionFilter,
+        cold_ledger_db: Option<ColdLedgerDb>,
         buffered_state_target_items: usize,
         hack_for_tests: bool,
         empty_buffered_state_for_restore: bool,
         skip_index_and_usage: bool,
+        enable_account_involvement_index: bool,
     ) -> Self {
         let ledger_db = Arc::new(ledger_db);
         let state_merkle_db = Arc::new(state_merkle_db);
         let state_kv_db = Arc::new(state_kv_db);
+        let cold_ledger_db = cold_ledger_db.map(Arc::new);
         let state_merkle_pruner = StateMerklePrunerManager::new(
             Arc::clone(&state_merkle_db),
             pruner_config.state_merkle_pruner_config,
@@ -380,8 +394,12 @@ impl AptosDB {
             skip_index_and_usage,
         ));
 
-        let ledger_pruner =
-            LedgerPrunerManager::new(Arc::clone(&ledger_db), pruner_config.ledger_pruner_config);
+        let ledger_pruner = LedgerPrunerManager::new(
+            Arc::clone(&ledger_db),
+            pruner_config.ledger_pruner_config,
+            ledger_retention_filter,
+            cold_ledger_db.clone(),
+        );
 
         AptosDB {
             ledger_db: Arc::clone(&ledger_db),
@@ -391,6 +409,7 @@ impl AptosDB {
             state_store,
             transaction_store: Arc::new(TransactionStore::new(Arc::clone(&ledger_db))),
             ledger_pruner,
+            cold_ledger_db,
             _rocksdb_property_reporter: RocksdbPropertyReporter::new(
                 ledger_db,
                 state_merkle_db,
@@ -399,6 +418,7 @@ impl AptosDB {
             ledger_commit_lock: std::sync::Mutex::new(()),
             indexer: None,
             skip_index_and_usage,
+            enable_account_involvement_index,
         }
     }
 
@@ -406,8 +426,11 @@ impl AptosDB {
         db_paths: &StorageDirPaths,
         readonly: bool,
         pruner_config: PrunerConfig,
+        ledger_retention_config: &LedgerRetentionConfig,
+        ledger_cold_storage_config: &LedgerColdStorageConfig,
         rocksdb_configs: RocksdbConfigs,
         enable_indexer: bool,
+        enable_account_involvement_index: bool,
         buffered_state_target_items: usize,
         max_num_nodes_per_lru_cache_shard: usize,
         empty_buffered_state_for_restore: bool,
@@ -416,6 +439,7 @@ impl AptosDB {
             pruner_config.eq(&NO_OP_STORAGE_PRUNER_CONFIG) || !readonly,
             "Do not set prune_window when opening readonly.",
         );
+        let ledger_retention_filter = LedgerRetentionFilter::new(ledger_retention_config)?;
 
         let (ledger_db, state_merkle_db, state_kv_db) = Self::open_dbs(
             db_paths,
@@ -423,16 +447,32 @@ impl AptosDB {
             readonly,
             max_num_nodes_per_lru_cache_shard,
         )?;
+        let cold_ledger_db = if ledger_cold_storage_config.enable {
+            let path = ledger_cold_storage_config
+                .dir
+                .clone()
+                .unwrap_or_else(|| db_paths.default_root_path().join(COLD_LEDGER_DB_NAME));
+            Some(ColdLedgerDb::new(
+                path,
+                &ledger_cold_storage_config.rocksdb_config,
+                readonly,
+            )?)
+        } else {
+            None
+        };
 
         let mut myself = Self::new_with_dbs(
             ledger_db,
             state_merkle_db,
             state_kv_db,
             pruner_config,
+            ledger_retention_filter,
+            cold_ledger_db,
             buffered_state_target_items,
             readonly,
             empty_buffered_state_for_restore,
             rocksdb_configs.enable_storage_sharding,
+            enable_account_involvement_index,
         );
 
         if !readonly && enable_indexer {
@@ -453,13 +493,47 @@ impl AptosDB {
         enable_indexer: bool,
         buffered_state_target_items: usize,
         max_num_nodes_per_lru_cache_shard: usize,
+    ) -> Result<Self> {
+        Self::open_with_ledger_history(
+            db_paths,
+            readonly,
+            pruner_config,
+            &LedgerRetentionConfig::default(),
+            &LedgerColdStorageConfig::default(),
+            rocksdb_configs,
+            enable_indexer,
+            false, /* enable_account_involvement_index */
+            buffered_state_target_items,
+            max_num_nodes_per_lru_cache_shard,
+        )
+    }
+
+    /// Like `open`, but the ledger pruner keeps the history of the accounts and event types in
+    /// `ledger_retention_config` past its prune window, and moves the pruned ledger data to the
+    /// cold ledger db if `ledger_cold_storage_config` enables it. If
+    /// `enable_account_involvement_index` is set, the committed transactions are also indexed by
+    /// the accounts involved in them.
+    pub fn open_with_ledger_history(
+        db_paths: StorageDirPaths,
+        readonly: bool,
+        pruner_config: PrunerConfig,
+        ledger_retention_config: &LedgerRetentionConfig,
+        ledger_cold_storage_config: &LedgerColdStorageConfig,
+        rocksdb_configs: RocksdbConfigs,
+        enable_indexer: bool,
+        enable_account_involvement_index: bool,
+        buffered_state_target_items: usize,
+        max_num_nodes_per_lru_cache_shard: usize,
     ) -> Result<Self> {
         Self::open_internal(
             &db_paths,
             readonly,
             pruner_config,
+            ledger_retention_config,
+            ledger_cold_storage_config,
             rocksdb_configs,
             enable_indexer,
+            enable_account_involvement_index,
             buffered_state_target_items,
             max_num_nodes_per_lru_cache_shard,
             false,
@@ -479,8 +553,11 @@ impl AptosDB {
             &db_paths,
             readonly,
             pruner_config,
+            &LedgerRetentionConfig::default(),
+            &LedgerColdStorageConfig::default(),
             rocksdb_configs,
             enable_indexer,
+            false, /* enable_account_involvement_index */
             buffered_state_target_items,
             max_num_nodes_per_lru_cache_shard,
             true,
@@ -733,7 +810,21 @@ impl AptosDB {
         ledger_version: Version,
         fetch_events: bool,
     ) -> Result<TransactionWithProof> {
-        self.error_if_ledger_pruned("Transaction", version)?;
+        if let Some(data) = self
+            .error_if_ledger_pruned_and_not_retained("Transaction", version, 1)?
+            .remove(&version)
+        {
+            return Ok(TransactionWithProof {
+                version,
+                transaction: data.transaction,
+                events: fetch_events.then_some(data.events),
+                proof: TransactionInfoWithProof::new(
+                    self.ledger_store
+                        .get_transaction_proof(version, ledger_version)?,
+                    data.info,
+                ),
+            });
+        }
 
         let proof = self
             .ledger_store
@@ -839,10 +930,13 @@ impl AptosDB {
             }
         }
 
+        let events = self.get_events_by_version_and_index(
+            event_indices.iter().map(|(_seq, ver, idx)| (*ver, *idx)),
+        )?;
         let mut events_with_version = event_indices
             .into_iter()
-            .map(|(seq, ver, idx)| {
-                let event = self.event_store.get_event_by_version_and_index(ver, idx)?;
+            .zip(events)
+            .map(|((seq, ver, _idx), event)| {
                 let v0 = match &event {
                     ContractEvent::V1(event) => event,
                     ContractEvent::V2(_) => bail!("Unexpected module event"),
@@ -863,6 +957,39 @@ impl AptosDB {
         Ok(events_with_version)
     }
 
+    fn get_events_by_event_type(
+        &self,
+        event_type: &TypeTag,
+        start: (Version, u64),
+        order: Order,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(u64, EventWithVersion)>> {
+        error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+
+        let event_indices = self.event_store.lookup_events_by_type(
+            event_type,
+            start,
+            order,
+            limit,
+            ledger_version,
+        )?;
+        let events = self.get_events_by_version_and_index(event_indices.iter().copied())?;
+        event_indices
+            .into_iter()
+            .zip(events)
+            .map(|((ver, idx), event)| {
+                ensure!(
+                    event.type_tag() == event_type,
+                    "Index broken, expected type:{}, actual:{}",
+                    event_type,
+                    event.type_tag()
+                );
+                Ok((idx, EventWithVersion::new(ver, event)))
+            })
+            .collect()
+    }
+
     fn get_table_info_option(&self, handle: TableHandle) -> Result<Option<TableInfo>> {
         match &self.indexer {
             Some(indexer) => indexer.get_table_info(handle),
@@ -1105,12 +1232,20 @@ impl AptosDB {
                 let chunk_first_version = first_version + (chunk_size * chunk_index) as u64;
                 txns_in_chunk.iter().enumerate().try_for_each(
                     |(i, txn_to_commit)| -> Result<()> {
+                        let version = chunk_first_version + i as u64;
                         self.transaction_store.put_transaction(
-                            chunk_first_version + i as u64,
+                            version,
                             txn_to_commit.transaction(),
                             skip_index,
                             &batch,
                         )?;
+                        if self.enable_account_involvement_index && !skip_index {
+                            self.transaction_store.put_account_involvement(
+                                version,
+                                txn_to_commit,
+                                &batch,
+                            )?;
+                        }
 
                         Ok(())
                     },
@@ -1315,6 +1450,158 @@ impl AptosDB {
         Ok(())
     }
 
+    /// Like `error_if_ledger_pruned`, but lets through the pruned versions that were kept by the
+    /// ledger retention policy or moved to the cold ledger db. Returns the data of the versions
+    /// read from the cold ledger db, so that callers don't have to read it again.
+    fn error_if_ledger_pruned_and_not_retained(
+        &self,
+        data_type: &str,
+        start_version: Version,
+        num_versions: u64,
+    ) -> Result<BTreeMap<Version, ColdTransactionData>> {
+        let mut cold_data = BTreeMap::new();
+        if !self.ledger_pruner.retains_ledger_history() && self.cold_ledger_db.is_none() {
+            self.error_if_ledger_pruned(data_type, start_version)?;
+            return Ok(cold_data);
+        }
+
+        let min_readable_version = self.ledger_pruner.get_min_readable_version();
+        let end_version = std::cmp::min(start_version + num_versions, min_readable_version);
+        for version in start_version..end_version {
+            if let Some(data) = self.get_cold_transaction_data(version)? {
+                cold_data.insert(version, data);
+                continue;
+            }
+            ensure!(
+                self.is_pruned_version_in_ledger_db(version)?,
+                "{} at version {} is pruned, min available version is {}. Only the history of the \
+                 accounts and event types in the ledger retention config, and the versions in the \
+                 cold ledger db are kept past the prune window.",
+                data_type,
+                version,
+                min_readable_version
+            );
+        }
+        Ok(cold_data)
+    }
+
+    /// Whether the ledger data of a version is readable, i.e. it's not pruned yet, it was kept by
+    /// the ledger retention policy, or it was moved to the cold ledger db.
+    fn is_ledger_version_kept(&self, version: Version) -> Result<bool> {
+        Ok(version >= self.ledger_pruner.get_min_readable_version()
+            || self.is_pruned_version_in_ledger_db(version)?
+            || self.get_cold_transaction_data(version)?.is_some())
+    }
+
+    /// Whether a version below the min readable version can still be read from the ledger db,
+    /// i.e. it was kept by the ledger retention policy, or the cold ledger db is enabled and the
+    /// version is yet to be moved to it.
+    fn is_pruned_version_in_ledger_db(&self, version: Version) -> Result<bool> {
+        if self.cold_ledger_db.is_some() && self.ledger_pruner.is_version_pending_prune(version)? {
+            return Ok(true);
+        }
+        self.ledger_pruner.is_version_retained(version)
+    }
+
+    /// The block events are all kept if the ledger pruner retains any history, so that the block
+    /// of a retained transaction can be looked up.
+    fn error_if_block_event_pruned(&self, version: Version) -> Result<()> {
+        if self.ledger_pruner.retains_ledger_history() {
+            return Ok(());
+        }
+        self.error_if_ledger_pruned("NewBlockEvent", version)
+    }
+
+    /// Returns the data of a pruned version from the cold ledger db, if it's enabled and has the
+    /// version. Versions being pruned might not have been moved yet, they are still in the ledger
+    /// db then, see `is_pruned_version_in_ledger_db`.
+    fn get_cold_transaction_data(&self, version: Version) -> Result<Option<ColdTransactionData>> {
+        match &self.cold_ledger_db {
+            Some(cold_ledger_db) if version < self.ledger_pruner.get_min_readable_version() => {
+                cold_ledger_db.get_transaction_data(version)
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// Reads the events at the given versions and indices, from the cold ledger db for the
+    /// versions that were moved there. The events of a version are read from the cold ledger db
+    /// only once as long as the indices of the same version are next to each other.
+    fn get_events_by_version_and_index(
+        &self,
+        indices: impl Iterator<Item = (Version, u64)>,
+    ) -> Result<Vec<ContractEvent>> {
+        let mut cold_events: Option<(Version, Option<Vec<ContractEvent>>)> = None;
+        indices
+            .map(|(version, index)| {
+                if cold_events.as_ref().map(|(v, _)| *v) != Some(version) {
+                    let events = self
+                        .get_cold_transaction_data(version)?
+                        .map(|data| data.events);
+                    cold_events = Some((version, events));
+                }
+                match cold_events.as_ref().and_then(|(_, events)| events.as_ref()) {
+                    Some(events) => events.get(index as usize).cloned().ok_or_else(|| {
+                        AptosDbError::NotFound(format!("Event {} of Txn {}", index, version)).into()
+                    }),
+                    None => self
+                        .event_store
+                        .get_event_by_version_and_index(version, index),
+                }
+            })
+            .collect()
+    }
+
+    /// Like `EventStore::get_block_metadata`, but the block event is read from the cold ledger db
+    /// if it was moved there, e.g. when the block started before the min readable version.
+    fn get_block_metadata(&self, version: Version) -> Result<(Version, NewBlockEvent)> {
+        let (first_version, event_index, _seq_num) = self
+            .event_store
+            .lookup_event_before_or_at_version(&new_block_event_key(), version)?
+            .ok_or_else(|| AptosDbError::NotFound("NewBlockEvent".to_string()))?;
+        let new_block_event = self
+            .get_events_by_version_and_index(std::iter::once((first_version, event_index)))?
+            .pop()
+            .expect("One event is read.");
+        Ok((
+            first_version,
+            bcs::from_bytes(new_block_event.event_data())?,
+        ))
+    }
+
+    /// Chains the data of the versions moved to the cold ledger db, if any of the requested
+    /// versions were, with the iterator over the rest of the versions in the ledger db.
+    fn get_ledger_data_iter<'a, T: 'a, F>(
+        &'a self,
+        data_type: &str,
+        start_version: Version,
+        limit: u64,
+        from_cold_data: fn(ColdTransactionData) -> T,
+        ledger_db_iter: F,
+    ) -> Result<Box<dyn Iterator<Item = Result<T>> + 'a>>
+    where
+        F: FnOnce(Version, usize) -> Result<Box<dyn Iterator<Item = Result<T>> + 'a>>,
+    {
+        error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+
+        let min_readable_version = self.ledger_pruner.get_min_readable_version();
+        match &self.cold_ledger_db {
+            Some(cold_ledger_db) if start_version < min_readable_version => {
+                let num_cold_versions = std::cmp::min(limit, min_readable_version - start_version);
+                let cold_iter = cold_ledger_db
+                    .get_transaction_data_iter(start_version, num_cold_versions as usize)?
+                    .map(move |data| data.map(from_cold_data));
+                let iter =
+                    ledger_db_iter(min_readable_version, (limit - num_cold_versions) as usize)?;
+                Ok(Box::new(cold_iter.chain(iter)))
+            },
+            _ => {
+                self.error_if_ledger_pruned(data_type, start_version)?;
+                ledger_db_iter(start_version, limit as usize)
+            },
+        }
+    }
+
     fn error_if_state_merkle_pruned(&self, data_type: &str, version: Version) -> Result<()> {
         let min_readable_version = self
             .state_store
@@ -1444,6 +1731,35 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_account_involved_transactions(
+        &self,
+        address: AccountAddress,
+        start: Version,
+        order: Order,
+        limit: u64,
+        include_events: bool,
+        ledger_version: Version,
+    ) -> Result<AccountTransactionsWithProof> {
+        gauged_api("get_account_involved_transactions", || {
+            ensure!(
+                self.enable_account_involvement_index,
+                "The account involvement index is not enabled."
+            );
+            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+
+            let txns_with_proofs = self
+                .transaction_store
+                .get_account_involved_versions(address, start, order, limit, ledger_version)?
+                .into_iter()
+                .map(|version| {
+                    self.get_transaction_with_proof(version, ledger_version, include_events)
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            Ok(AccountTransactionsWithProof::new(txns_with_proofs))
+        })
+    }
+
     /// This API is best-effort in that it CANNOT provide absence proof.
     fn get_transaction_by_hash(
         &self,
@@ -1492,25 +1808,29 @@ impl DbReader for AptosDB {
             if start_version > ledger_version || limit == 0 {
                 return Ok(TransactionListWithProof::new_empty());
             }
-            self.error_if_ledger_pruned("Transaction", start_version)?;
-
             let limit = std::cmp::min(limit, ledger_version - start_version + 1);
-
-            let txns = (start_version..start_version + limit)
-                .map(|version| self.transaction_store.get_transaction(version))
-                .collect::<Result<Vec<_>>>()?;
-            let txn_infos = (start_version..start_version + limit)
-                .map(|version| self.ledger_store.get_transaction_info(version))
-                .collect::<Result<Vec<_>>>()?;
-            let events = if fetch_events {
-                Some(
-                    (start_version..start_version + limit)
-                        .map(|version| self.event_store.get_events_by_version(version))
-                        .collect::<Result<Vec<_>>>()?,
-                )
-            } else {
-                None
-            };
+            let mut cold_data =
+                self.error_if_ledger_pruned_and_not_retained("Transaction", start_version, limit)?;
+
+            let mut txns = Vec::with_capacity(limit as usize);
+            let mut txn_infos = Vec::with_capacity(limit as usize);
+            let mut events = Vec::with_capacity(limit as usize);
+            for version in start_version..start_version + limit {
+                if let Some(data) = cold_data.remove(&version) {
+                    txns.push(data.transaction);
+                    txn_infos.push(data.info);
+                    if fetch_events {
+                        events.push(data.events);
+                    }
+                    continue;
+                }
+                txns.push(self.transaction_store.get_transaction(version)?);
+                txn_infos.push(self.ledger_store.get_transaction_info(version)?);
+                if fetch_events {
+                    events.push(self.event_store.get_events_by_version(version)?);
+                }
+            }
+            let events = fetch_events.then_some(events);
             let proof = TransactionInfoListWithProof::new(
                 self.ledger_store.get_transaction_range_proof(
                     Some(start_version),
@@ -1569,12 +1889,22 @@ impl DbReader for AptosDB {
                 return Ok(TransactionOutputListWithProof::new_empty());
             }
 
-            self.error_if_ledger_pruned("Transaction", start_version)?;
-
             let limit = std::cmp::min(limit, ledger_version - start_version + 1);
+            let mut cold_data =
+                self.error_if_ledger_pruned_and_not_retained("Transaction", start_version, limit)?;
 
             let (txn_infos, txns_and_outputs) = (start_version..start_version + limit)
                 .map(|version| {
+                    if let Some(data) = cold_data.remove(&version) {
+                        let txn_output = TransactionOutput::new(
+                            data.write_set,
+                            data.events,
+                            data.info.gas_used(),
+                            data.info.status().clone().into(),
+                        );
+                        return Ok((data.info, (data.transaction, txn_output)));
+                    }
+
                     let txn_info = self.ledger_store.get_transaction_info(version)?;
                     let events = self.event_store.get_events_by_version(version)?;
                     let write_set = self.transaction_store.get_write_set(version)?;
@@ -1620,19 +1950,37 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_events_by_type(
+        &self,
+        event_type: &TypeTag,
+        start: (Version, u64),
+        order: Order,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(u64, EventWithVersion)>> {
+        gauged_api("get_events_by_type", || {
+            self.get_events_by_event_type(event_type, start, order, limit, ledger_version)
+        })
+    }
+
     fn get_transaction_iterator(
         &self,
         start_version: Version,
         limit: u64,
     ) -> Result<Box<dyn Iterator<Item = Result<Transaction>> + '_>> {
         gauged_api("get_transaction_iterator", || {
-            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
-            self.error_if_ledger_pruned("Transaction", start_version)?;
-
-            let iter = self
-                .transaction_store
-                .get_transaction_iter(start_version, limit as usize)?;
-            Ok(Box::new(iter) as Box<dyn Iterator<Item = Result<Transaction>> + '_>)
+            self.get_ledger_data_iter(
+                "Transaction",
+                start_version,
+                limit,
+                |data| data.transaction,
+                |start_version, limit| {
+                    let iter = self
+                        .transaction_store
+                        .get_transaction_iter(start_version, limit)?;
+                    Ok(Box::new(iter) as Box<dyn Iterator<Item = Result<Transaction>> + '_>)
+                },
+            )
         })
     }
 
@@ -1642,13 +1990,18 @@ impl DbReader for AptosDB {
         limit: u64,
     ) -> Result<Box<dyn Iterator<Item = Result<TransactionInfo>> + '_>> {
         gauged_api("get_transaction_info_iterator", || {
-            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
-            self.error_if_ledger_pruned("Transaction", start_version)?;
-
-            let iter = self
-                .ledger_store
-                .get_transaction_info_iter(start_version, limit as usize)?;
-            Ok(Box::new(iter) as Box<dyn Iterator<Item = Result<TransactionInfo>> + '_>)
+            self.get_ledger_data_iter(
+                "Transaction",
+                start_version,
+                limit,
+                |data| data.info,
+                |start_version, limit| {
+                    let iter = self
+                        .ledger_store
+                        .get_transaction_info_iter(start_version, limit)?;
+                    Ok(Box::new(iter) as Box<dyn Iterator<Item = Result<TransactionInfo>> + '_>)
+                },
+            )
         })
     }
 
@@ -1658,16 +2011,21 @@ impl DbReader for AptosDB {
         limit: u64,
     ) -> Result<Box<dyn Iterator<Item = Result<Vec<ContractEvent>>> + '_>> {
         gauged_api("get_events_iterator", || {
-            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
-            self.error_if_ledger_pruned("Transaction", start_version)?;
-
-            let iter = self
-                .event_store
-                .get_events_by_version_iter(start_version, limit as usize)?;
-            Ok(Box::new(iter)
-                as Box<
-                    dyn Iterator<Item = Result<Vec<ContractEvent>>> + '_,
-                >)
+            self.get_ledger_data_iter(
+                "Transaction",
+                start_version,
+                limit,
+                |data| data.events,
+                |start_version, limit| {
+                    let iter = self
+                        .event_store
+                        .get_events_by_version_iter(start_version, limit)?;
+                    Ok(Box::new(iter)
+                        as Box<
+                            dyn Iterator<Item = Result<Vec<ContractEvent>>> + '_,
+                        >)
+                },
+            )
         })
     }
 
@@ -1677,13 +2035,18 @@ impl DbReader for AptosDB {
         limit: u64,
     ) -> Result<Box<dyn Iterator<Item = Result<WriteSet>> + '_>> {
         gauged_api("get_write_set_iterator", || {
-            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
-            self.error_if_ledger_pruned("Transaction", start_version)?;
-
-            let iter = self
-                .transaction_store
-                .get_write_set_iter(start_version, limit as usize)?;
-            Ok(Box::new(iter) as Box<dyn Iterator<Item = Result<WriteSet>> + '_>)
+            self.get_ledger_data_iter(
+                "Transaction",
+                start_version,
+                limit,
+                |data| data.write_set,
+                |start_version, limit| {
+                    let iter = self
+                        .transaction_store
+                        .get_write_set_iter(start_version, limit)?;
+                    Ok(Box::new(iter) as Box<dyn Iterator<Item = Result<WriteSet>> + '_>)
+                },
+            )
         })
     }
 
@@ -1694,7 +2057,10 @@ impl DbReader for AptosDB {
         ledger_version: Version,
     ) -> Result<TransactionAccumulatorRangeProof> {
         gauged_api("get_transaction_accumulator_range_proof", || {
-            self.error_if_ledger_pruned("Transaction", first_version)?;
+            // The transaction accumulator isn't pruned if the cold ledger db is enabled.
+            if self.cold_ledger_db.is_none() {
+                self.error_if_ledger_pruned("Transaction", first_version)?;
+            }
 
             self.ledger_store.get_transaction_range_proof(
                 Some(first_version),
@@ -1837,17 +2203,26 @@ impl DbReader for AptosDB {
 
     fn get_block_timestamp(&self, version: u64) -> Result<u64> {
         gauged_api("get_block_timestamp", || {
-            self.error_if_ledger_pruned("NewBlockEvent", version)?;
+            if let Some(cold_ledger_db) = &self.cold_ledger_db {
+                if version < self.ledger_pruner.get_min_readable_version() {
+                    if let Some((_first_version, new_block_event)) =
+                        cold_ledger_db.get_block_event(version)?
+                    {
+                        return Ok(new_block_event.proposed_time());
+                    }
+                }
+            }
+            self.error_if_block_event_pruned(version)?;
             ensure!(version <= self.get_latest_version()?);
 
-            let (_first_version, new_block_event) = self.event_store.get_block_metadata(version)?;
+            let (_first_version, new_block_event) = self.get_block_metadata(version)?;
             Ok(new_block_event.proposed_time())
         })
     }
 
     fn get_next_block_event(&self, version: Version) -> Result<(Version, NewBlockEvent)> {
         gauged_api("get_next_block_event", || {
-            self.error_if_ledger_pruned("NewBlockEvent", version)?;
+            self.error_if_block_event_pruned(version)?;
             if let Some((block_version, _, _)) = self
                 .event_store
                 .lookup_event_at_or_after_version(&new_block_event_key(), version)?
@@ -1867,7 +2242,7 @@ impl DbReader for AptosDB {
         version: Version,
     ) -> Result<(Version, Version, NewBlockEvent)> {
         gauged_api("get_block_info", || {
-            self.error_if_ledger_pruned("NewBlockEvent", version)?;
+            self.error_if_block_event_pruned(version)?;
 
             let latest_li = self.get_latest_ledger_info()?;
             let committed_version = latest_li.ledger_info().version();
@@ -1878,7 +2253,7 @@ impl DbReader for AptosDB {
                 committed_version
             );
 
-            let (first_version, new_block_event) = self.event_store.get_block_metadata(version)?;
+            let (first_version, new_block_event) = self.get_block_metadata(version)?;
 
             let last_version = self
                 .event_store
@@ -1895,9 +2270,13 @@ impl DbReader for AptosDB {
             let committed_version = latest_li.ledger_info().version();
 
             let event_key = new_block_event_key();
-            let (first_version, new_block_event) =
+            let (first_version, index) =
                 self.event_store
-                    .get_event_by_key(&event_key, height, committed_version)?;
+                    .lookup_event_by_key(&event_key, height, committed_version)?;
+            let new_block_event = self
+                .get_events_by_version_and_index(std::iter::once((first_version, index)))?
+                .pop()
+                .expect("One event is read.");
             let last_version = self
                 .event_store
                 .lookup_event_after_version(&event_key, first_version)?
@@ -1944,7 +2323,7 @@ impl DbReader for AptosDB {
 
     fn get_accumulator_root_hash(&self, version: Version) -> Result<HashValue> {
         gauged_api("get_accumulator_root_hash", || {
-            self.error_if_ledger_pruned("Transaction accumulator", version)?;
+            self.error_if_ledger_pruned_and_not_retained("Transaction accumulator", version, 1)?;
             self.ledger_store.get_root_hash(version)
         })
     }
@@ -2025,6 +2404,12 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn is_pruned_transaction_kept(&self, version: Version) -> Result<bool> {
+        gauged_api("is_pruned_transaction_kept", || {
+            self.is_ledger_version_kept(version)
+        })
+    }
+
     fn get_table_info(&self, handle: TableHandle) -> Result<TableInfo> {
         gauged_api("get_table_info", || {
             self.get_table_info_option(handle)?
@@ -142,6 +142,27 @@ pub static NODE_CACHE_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static COLD_LEDGER_READ_LATENCY_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        // metric name
+        "aptos_storage_cold_ledger_read_latency_seconds",
+        // metric description
+        "Latency of reads of pruned ledger data from the cold ledger db",
+        // metric labels (dimensions)
+        &["name"],
+        exponential_buckets(/*start=*/ 1e-6, /*factor=*/ 2.0, /*count=*/ 22).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static COLD_LEDGER_ARCHIVE_PROGRESS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_storage_cold_ledger_archive_progress",
+        "The version up to which the pruned ledger data has been moved to the cold ledger db."
+    )
+    .unwrap()
+});
+
 /// Rocksdb metrics
 pub static ROCKSDB_PROPERTIES: Lazy<IntGaugeVec> = Lazy::new(|| {
     register_int_gauge_vec!(
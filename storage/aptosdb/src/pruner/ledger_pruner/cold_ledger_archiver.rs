@@ -0,0 +1,92 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    cold_ledger_db::{ColdLedgerDb, ColdTransactionData},
+    errors::AptosDbError,
+    ledger_db::LedgerDb,
+    schema::{
+        transaction::TransactionSchema, transaction_info::TransactionInfoSchema,
+        write_set::WriteSetSchema,
+    },
+    EventStore,
+};
+use anyhow::Result;
+use aptos_logger::info;
+use aptos_schemadb::SchemaBatch;
+use aptos_types::{account_config::new_block_event_key, transaction::Version};
+use std::{cmp::max, sync::Arc};
+
+/// Moves the ledger data about to be pruned to the cold ledger db.
+#[derive(Debug)]
+pub(crate) struct ColdLedgerArchiver {
+    ledger_db: Arc<LedgerDb>,
+    event_store: EventStore,
+    cold_ledger_db: Arc<ColdLedgerDb>,
+}
+
+impl ColdLedgerArchiver {
+    pub fn new(ledger_db: Arc<LedgerDb>, cold_ledger_db: Arc<ColdLedgerDb>) -> Self {
+        Self {
+            event_store: EventStore::new(ledger_db.event_db_arc()),
+            ledger_db,
+            cold_ledger_db,
+        }
+    }
+
+    /// Copies the data of the versions in [begin, end) to the cold ledger db. It must be called
+    /// before any data in the range is pruned.
+    pub fn archive(&self, begin: Version, end: Version) -> Result<()> {
+        let begin = max(begin, self.cold_ledger_db.progress()?);
+        if begin >= end {
+            return Ok(());
+        }
+
+        info!(begin = begin, end = end, "Archiving ledger data.");
+
+        let batch = SchemaBatch::new();
+        for version in begin..end {
+            // Everything from the pruner progress onwards is still in the ledger db, so missing
+            // data means the ledger db is corrupted, and archiving must not skip over it.
+            let transaction = self
+                .ledger_db
+                .transaction_db()
+                .get::<TransactionSchema>(&version)?
+                .ok_or_else(|| {
+                    AptosDbError::NotFound(format!("Transaction {} to archive", version))
+                })?;
+            let info = self
+                .ledger_db
+                .transaction_info_db()
+                .get::<TransactionInfoSchema>(&version)?
+                .ok_or_else(|| {
+                    AptosDbError::NotFound(format!("TransactionInfo {} to archive", version))
+                })?;
+            let write_set = self
+                .ledger_db
+                .write_set_db()
+                .get::<WriteSetSchema>(&version)?
+                .ok_or_else(|| {
+                    AptosDbError::NotFound(format!("WriteSet {} to archive", version))
+                })?;
+            let events = self.event_store.get_events_by_version(version)?;
+            let block_event = events
+                .iter()
+                .find(|event| event.event_key() == Some(&new_block_event_key()))
+                .cloned();
+
+            self.cold_ledger_db.put_transaction_data(
+                version,
+                &ColdTransactionData {
+                    transaction,
+                    info,
+                    events,
+                    write_set,
+                },
+                block_event.as_ref(),
+                &batch,
+            )?;
+        }
+        self.cold_ledger_db.commit(end, batch)
+    }
+}
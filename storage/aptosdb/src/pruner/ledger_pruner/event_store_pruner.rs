@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    pruner::{db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress},
+    pruner::{
+        db_sub_pruner::DBSubPruner, ledger_pruner::ledger_retention::LedgerRetention,
+        pruner_utils::get_or_initialize_subpruner_progress,
+    },
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
     EventStore,
 };
@@ -16,6 +19,7 @@ use std::sync::Arc;
 pub struct EventStorePruner {
     event_store: Arc<EventStore>,
     event_db: Arc<DB>,
+    retention: Arc<LedgerRetention>,
 }
 
 impl DBSubPruner for EventStorePruner {
@@ -25,8 +29,15 @@ impl DBSubPruner for EventStorePruner {
 
     fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
         let batch = SchemaBatch::new();
-        self.event_store
-            .prune_events(current_progress, target_version, &batch)?;
+        let retained_versions = self
+            .retention
+            .retained_versions(current_progress, target_version)?;
+        self.event_store.prune_events_with_retention(
+            current_progress,
+            target_version,
+            &retained_versions,
+            &batch,
+        )?;
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::EventPrunerProgress,
             &DbMetadataValue::Version(target_version),
@@ -39,6 +50,7 @@ impl EventStorePruner {
     pub(in crate::pruner) fn new(
         event_store: Arc<EventStore>,
         event_db: Arc<DB>,
+        retention: Arc<LedgerRetention>,
         metadata_progress: Version,
     ) -> Result<Self> {
         let progress = get_or_initialize_subpruner_progress(
@@ -50,6 +62,7 @@ impl EventStorePruner {
         let myself = EventStorePruner {
             event_store,
             event_db,
+            retention,
         };
 
         info!(
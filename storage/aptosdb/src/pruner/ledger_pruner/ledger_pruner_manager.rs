@@ -2,12 +2,16 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    cold_ledger_db::ColdLedgerDb,
     ledger_db::LedgerDb,
     metrics::{PRUNER_BATCH_SIZE, PRUNER_VERSIONS, PRUNER_WINDOW},
     pruner::{
-        ledger_pruner::LedgerPruner, pruner_manager::PrunerManager, pruner_utils,
+        ledger_pruner::{ledger_retention::LedgerRetentionFilter, LedgerPruner},
+        pruner_manager::PrunerManager,
+        pruner_utils,
         pruner_worker::PrunerWorker,
     },
+    EventStore,
 };
 use anyhow::Result;
 use aptos_config::config::LedgerPrunerConfig;
@@ -31,6 +35,9 @@ pub(crate) struct LedgerPrunerManager {
     user_pruning_window_offset: u64,
     /// The minimal readable version for the ledger data.
     min_readable_version: AtomicVersion,
+    /// Decides which ledger history of the accounts and event types is kept past the prune window.
+    retention_filter: Arc<LedgerRetentionFilter>,
+    event_store: EventStore,
 }
 
 impl PrunerManager for LedgerPrunerManager {
@@ -104,11 +111,19 @@ impl PrunerManager for LedgerPrunerManager {
 
 impl LedgerPrunerManager {
     /// Creates a worker thread that waits on a channel for pruning commands.
-    pub fn new(ledger_db: Arc<LedgerDb>, ledger_pruner_config: LedgerPrunerConfig) -> Self {
+    pub fn new(
+        ledger_db: Arc<LedgerDb>,
+        ledger_pruner_config: LedgerPrunerConfig,
+        retention_filter: LedgerRetentionFilter,
+        cold_ledger_db: Option<Arc<ColdLedgerDb>>,
+    ) -> Self {
+        let retention_filter = Arc::new(retention_filter);
         let pruner_worker = if ledger_pruner_config.enable {
             Some(Self::init_pruner(
                 Arc::clone(&ledger_db),
                 ledger_pruner_config,
+                Arc::clone(&retention_filter),
+                cold_ledger_db,
             ))
         } else {
             None
@@ -122,6 +137,7 @@ impl LedgerPrunerManager {
             .set(min_readable_version as i64);
 
         Self {
+            event_store: EventStore::new(ledger_db.event_db_arc()),
             ledger_db,
             prune_window: ledger_pruner_config.prune_window,
             pruner_worker,
@@ -129,15 +145,40 @@ impl LedgerPrunerManager {
             latest_version: Arc::new(Mutex::new(min_readable_version)),
             user_pruning_window_offset: ledger_pruner_config.user_pruning_window_offset,
             min_readable_version: AtomicVersion::new(min_readable_version),
+            retention_filter,
         }
     }
 
+    /// Whether the pruner keeps the ledger history of some accounts and event types past the prune
+    /// window. The block events are all kept then, as well as the transaction accumulator.
+    pub fn retains_ledger_history(&self) -> bool {
+        !self.retention_filter.is_empty()
+    }
+
+    /// Whether the transaction at a version that's below the min readable version was kept by the
+    /// ledger retention policy. The policy is evaluated rather than checking whether the data is
+    /// still there, because the versions below the min readable version might not be pruned yet.
+    pub fn is_version_retained(&self, version: Version) -> Result<bool> {
+        self.retention_filter
+            .retains_version(&self.ledger_db, &self.event_store, version)
+    }
+
+    /// Whether the pruner hasn't got to a version below the min readable version yet, which moves
+    /// ahead as soon as the pruner is woken up.
+    pub fn is_version_pending_prune(&self, version: Version) -> Result<bool> {
+        Ok(version >= pruner_utils::get_ledger_pruner_progress(&self.ledger_db)?)
+    }
+
     fn init_pruner(
         ledger_db: Arc<LedgerDb>,
         ledger_pruner_config: LedgerPrunerConfig,
+        retention_filter: Arc<LedgerRetentionFilter>,
+        cold_ledger_db: Option<Arc<ColdLedgerDb>>,
     ) -> PrunerWorker {
-        let pruner =
-            Arc::new(LedgerPruner::new(ledger_db).expect("Failed to create ledger pruner."));
+        let pruner = Arc::new(
+            LedgerPruner::new(ledger_db, retention_filter, cold_ledger_db)
+                .expect("Failed to create ledger pruner."),
+        );
 
         PRUNER_WINDOW
             .with_label_values(&["ledger_pruner"])
@@ -0,0 +1,268 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ledger_db::LedgerDb,
+    schema::{transaction::TransactionSchema, write_set::WriteSetSchema},
+    EventStore,
+};
+use anyhow::{Context, Result};
+use aptos_config::config::LedgerRetentionConfig;
+use aptos_infallible::Mutex;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::new_block_event_key,
+    contract_event::ContractEvent,
+    state_store::state_key::StateKeyInner,
+    transaction::{Transaction, Version},
+    write_set::WriteSet,
+};
+use move_core_types::language_storage::TypeTag;
+use std::{collections::HashSet, ops::Range, str::FromStr, sync::Arc};
+
+/// Decides which ledger data is kept past the prune window, per the `LedgerRetentionConfig`.
+#[derive(Debug, Default)]
+pub(crate) struct LedgerRetentionFilter {
+    accounts: HashSet<AccountAddress>,
+    event_types: HashSet<TypeTag>,
+}
+
+impl LedgerRetentionFilter {
+    pub fn new(config: &LedgerRetentionConfig) -> Result<Self> {
+        let event_types = config
+            .event_types
+            .iter()
+            .map(|event_type| {
+                TypeTag::from_str(event_type)
+                    .with_context(|| format!("Invalid retained event type {}", event_type))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            accounts: config.accounts.iter().cloned().collect(),
+            event_types,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty() && self.event_types.is_empty()
+    }
+
+    /// Whether the transaction at a version is retained, along with its events and write set. The
+    /// criteria are checked from the cheapest to the most expensive one, so that the events and
+    /// the write set are only read when needed. A version whose transaction is already pruned
+    /// wasn't retained.
+    pub fn retains_version(
+        &self,
+        ledger_db: &LedgerDb,
+        event_store: &EventStore,
+        version: Version,
+    ) -> Result<bool> {
+        if self.is_empty() {
+            return Ok(false);
+        }
+        let txn = match ledger_db
+            .transaction_db()
+            .get::<TransactionSchema>(&version)?
+        {
+            Some(txn) => txn,
+            None => return Ok(false),
+        };
+        if self.retains_sender(&txn) {
+            return Ok(true);
+        }
+        if self.retains_any_event(&event_store.get_events_by_version(version)?) {
+            return Ok(true);
+        }
+        if self.accounts.is_empty() {
+            return Ok(false);
+        }
+        Ok(ledger_db
+            .write_set_db()
+            .get::<WriteSetSchema>(&version)?
+            .map_or(false, |write_set| {
+                self.writes_to_retained_account(&write_set)
+            }))
+    }
+
+    fn retains_sender(&self, txn: &Transaction) -> bool {
+        txn.try_as_signed_user_txn()
+            .map_or(false, |txn| self.accounts.contains(&txn.sender()))
+    }
+
+    fn retains_any_event(&self, events: &[ContractEvent]) -> bool {
+        events.iter().any(|event| {
+            self.event_types.contains(event.type_tag())
+                || event.event_key().map_or(false, |key| {
+                    self.accounts.contains(&key.get_creator_address())
+                })
+        })
+    }
+
+    fn writes_to_retained_account(&self, write_set: &WriteSet) -> bool {
+        write_set
+            .iter()
+            .any(|(state_key, _)| match state_key.inner() {
+                StateKeyInner::AccessPath(access_path) => {
+                    self.accounts.contains(&access_path.address)
+                },
+                _ => false,
+            })
+    }
+
+    /// Whether an event is kept in the event indices. Besides the events of the retained
+    /// accounts and event types, the block events are kept as well, so that the block of a
+    /// retained transaction can still be looked up.
+    fn retains_event(&self, event: &ContractEvent) -> bool {
+        if self.event_types.contains(event.type_tag()) {
+            return true;
+        }
+        event.event_key().map_or(false, |key| {
+            *key == new_block_event_key() || self.accounts.contains(&key.get_creator_address())
+        })
+    }
+}
+
+/// The versions kept by the ledger retention policy in a range of versions being pruned.
+#[derive(Debug)]
+pub(crate) struct RetainedVersions {
+    filter: Arc<LedgerRetentionFilter>,
+    range: Range<Version>,
+    versions: HashSet<Version>,
+    keeps_indices: bool,
+}
+
+impl RetainedVersions {
+    pub fn contains(&self, version: Version) -> bool {
+        self.versions.contains(&version)
+    }
+
+    /// Whether the account index entry of a retained user transaction is kept.
+    pub fn retains_account_index(&self, sender: &AccountAddress) -> bool {
+        self.filter.accounts.contains(sender)
+    }
+
+    /// Whether an event is kept along with its indices, see `LedgerRetentionFilter::retains_event`.
+    pub fn retains_event(&self, event: &ContractEvent) -> bool {
+        !self.filter.is_empty() && self.filter.retains_event(event)
+    }
+
+    /// Whether the transaction and event indices of all versions are kept, see
+    /// `LedgerRetention::new`.
+    pub fn keeps_indices(&self) -> bool {
+        self.keeps_indices
+    }
+
+    /// The ranges of versions that are not retained, and can be pruned entirely.
+    pub fn pruned_ranges(&self) -> Vec<Range<Version>> {
+        let mut ranges = vec![];
+        let mut begin = self.range.start;
+        for version in self.range.clone() {
+            if self.contains(version) {
+                if begin < version {
+                    ranges.push(begin..version);
+                }
+                begin = version + 1;
+            }
+        }
+        if begin < self.range.end {
+            ranges.push(begin..self.range.end);
+        }
+        ranges
+    }
+}
+
+/// Computes the versions kept by the ledger retention policy for the ledger sub pruners.
+#[derive(Debug)]
+pub(crate) struct LedgerRetention {
+    filter: Arc<LedgerRetentionFilter>,
+    ledger_db: Arc<LedgerDb>,
+    event_store: EventStore,
+    /// The sub pruners prune the same range of versions in parallel, so the retained versions of
+    /// the last range are cached to compute them only once.
+    last_retained_versions: Mutex<Option<Arc<RetainedVersions>>>,
+    keeps_indices: bool,
+}
+
+impl LedgerRetention {
+    /// `keeps_indices` is set when the cold ledger db is enabled. The cold ledger db only has the
+    /// data by version, so the indices by hash, account and event are kept in the ledger db to
+    /// look up the versions in it.
+    pub fn new(
+        filter: Arc<LedgerRetentionFilter>,
+        ledger_db: Arc<LedgerDb>,
+        keeps_indices: bool,
+    ) -> Self {
+        Self {
+            filter,
+            event_store: EventStore::new(ledger_db.event_db_arc()),
+            ledger_db,
+            last_retained_versions: Mutex::new(None),
+            keeps_indices,
+        }
+    }
+
+    /// Whether anything is retained at all, in which case the transaction accumulator must be
+    /// kept to prove the retained transactions.
+    pub fn retains_anything(&self) -> bool {
+        !self.filter.is_empty()
+    }
+
+    /// Returns the retained versions in [begin, end). It must be called before any data in the
+    /// range is pruned. As data is only pruned if its version is not retained, and nothing in a
+    /// retained version is pruned, a sub pruner catching up on a range other sub pruners have
+    /// already pruned comes to the same decision for every version.
+    pub fn retained_versions(&self, begin: Version, end: Version) -> Result<Arc<RetainedVersions>> {
+        let mut last_retained_versions = self.last_retained_versions.lock();
+        if let Some(retained_versions) = last_retained_versions.as_ref() {
+            if retained_versions.range == (begin..end) {
+                return Ok(Arc::clone(retained_versions));
+            }
+        }
+
+        let mut versions = HashSet::new();
+        if !self.filter.is_empty() {
+            for version in begin..end {
+                if self
+                    .filter
+                    .retains_version(&self.ledger_db, &self.event_store, version)?
+                {
+                    versions.insert(version);
+                }
+            }
+        }
+
+        let retained_versions = Arc::new(RetainedVersions {
+            filter: Arc::clone(&self.filter),
+            range: begin..end,
+            versions,
+            keeps_indices: self.keeps_indices,
+        });
+        *last_retained_versions = Some(Arc::clone(&retained_versions));
+        Ok(retained_versions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pruned_ranges() {
+        let retained_versions = RetainedVersions {
+            filter: Arc::new(LedgerRetentionFilter::default()),
+            range: 10..20,
+            versions: [10, 13, 14, 19].into_iter().collect(),
+            keeps_indices: false,
+        };
+        assert_eq!(retained_versions.pruned_ranges(), vec![11..13, 15..19]);
+
+        let retained_versions = RetainedVersions {
+            filter: Arc::new(LedgerRetentionFilter::default()),
+            range: 10..20,
+            versions: HashSet::new(),
+            keeps_indices: false,
+        };
+        assert_eq!(retained_versions.pruned_ranges(), vec![10..20]);
+    }
+}
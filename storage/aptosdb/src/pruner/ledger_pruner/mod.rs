@@ -1,24 +1,31 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+mod cold_ledger_archiver;
 mod event_store_pruner;
 mod ledger_metadata_pruner;
 pub(crate) mod ledger_pruner_manager;
+pub(crate) mod ledger_retention;
 mod transaction_accumulator_pruner;
 mod transaction_info_pruner;
 mod transaction_pruner;
 mod write_set_pruner;
 
 use crate::{
+    cold_ledger_db::ColdLedgerDb,
     ledger_db::LedgerDb,
     metrics::PRUNER_VERSIONS,
     pruner::{
         db_pruner::DBPruner,
         db_sub_pruner::DBSubPruner,
         ledger_pruner::{
-            event_store_pruner::EventStorePruner, ledger_metadata_pruner::LedgerMetadataPruner,
+            cold_ledger_archiver::ColdLedgerArchiver,
+            event_store_pruner::EventStorePruner,
+            ledger_metadata_pruner::LedgerMetadataPruner,
+            ledger_retention::{LedgerRetention, LedgerRetentionFilter},
             transaction_accumulator_pruner::TransactionAccumulatorPruner,
-            transaction_info_pruner::TransactionInfoPruner, transaction_pruner::TransactionPruner,
+            transaction_info_pruner::TransactionInfoPruner,
+            transaction_pruner::TransactionPruner,
             write_set_pruner::WriteSetPruner,
         },
     },
@@ -45,6 +52,9 @@ pub(crate) struct LedgerPruner {
 
     ledger_metadata_pruner: Box<LedgerMetadataPruner>,
 
+    /// Moves the data to the cold ledger db before it's pruned, if the cold ledger db is enabled.
+    cold_ledger_archiver: Option<ColdLedgerArchiver>,
+
     sub_pruners: Vec<Box<dyn DBSubPruner + Send + Sync>>,
 }
 
@@ -66,6 +76,9 @@ impl DBPruner for LedgerPruner {
                 target_version = current_batch_target_version,
                 "Pruning ledger data."
             );
+            if let Some(cold_ledger_archiver) = &self.cold_ledger_archiver {
+                cold_ledger_archiver.archive(progress, current_batch_target_version)?;
+            }
             self.ledger_metadata_pruner
                 .prune(progress, current_batch_target_version)?;
 
@@ -109,7 +122,11 @@ impl DBPruner for LedgerPruner {
 }
 
 impl LedgerPruner {
-    pub fn new(ledger_db: Arc<LedgerDb>) -> Result<Self> {
+    pub fn new(
+        ledger_db: Arc<LedgerDb>,
+        retention_filter: Arc<LedgerRetentionFilter>,
+        cold_ledger_db: Option<Arc<ColdLedgerDb>>,
+    ) -> Result<Self> {
         info!(name = LEDGER_PRUNER_NAME, "Initializing...");
 
         let ledger_metadata_pruner = Box::new(
@@ -124,31 +141,43 @@ impl LedgerPruner {
             "Created ledger metadata pruner, start catching up all sub pruners."
         );
 
+        let cold_ledger_archiver = cold_ledger_db
+            .map(|cold_ledger_db| ColdLedgerArchiver::new(Arc::clone(&ledger_db), cold_ledger_db));
         let transaction_store = Arc::new(TransactionStore::new(Arc::clone(&ledger_db)));
+        let retention = Arc::new(LedgerRetention::new(
+            retention_filter,
+            Arc::clone(&ledger_db),
+            cold_ledger_archiver.is_some(),
+        ));
 
         let event_store_pruner = Box::new(EventStorePruner::new(
             Arc::new(EventStore::new(ledger_db.event_db_arc())),
             ledger_db.event_db_arc(),
+            Arc::clone(&retention),
             metadata_progress,
         )?);
         let transaction_accumulator_pruner = Box::new(TransactionAccumulatorPruner::new(
             Arc::clone(&transaction_store),
             ledger_db.transaction_accumulator_db_arc(),
+            retention.retains_anything() || cold_ledger_archiver.is_some(),
             metadata_progress,
         )?);
         let transaction_info_pruner = Box::new(TransactionInfoPruner::new(
             Arc::clone(&transaction_store),
             ledger_db.transaction_info_db_arc(),
+            Arc::clone(&retention),
             metadata_progress,
         )?);
         let transaction_pruner = Box::new(TransactionPruner::new(
             Arc::clone(&transaction_store),
             ledger_db.transaction_db_arc(),
+            Arc::clone(&retention),
             metadata_progress,
         )?);
         let write_set_pruner = Box::new(WriteSetPruner::new(
             Arc::clone(&transaction_store),
             ledger_db.write_set_db_arc(),
+            Arc::clone(&retention),
             metadata_progress,
         )?);
 
@@ -156,6 +185,7 @@ impl LedgerPruner {
             target_version: AtomicVersion::new(metadata_progress),
             progress: AtomicVersion::new(metadata_progress),
             ledger_metadata_pruner,
+            cold_ledger_archiver,
             sub_pruners: vec![
                 event_store_pruner,
                 transaction_accumulator_pruner,
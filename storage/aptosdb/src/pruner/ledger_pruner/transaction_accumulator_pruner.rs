@@ -16,6 +16,9 @@ use std::sync::Arc;
 pub struct TransactionAccumulatorPruner {
     transaction_store: Arc<TransactionStore>,
     transaction_accumulator_db: Arc<DB>,
+    /// The accumulator is needed to prove the transactions kept past the prune window, by the
+    /// ledger retention policy or in the cold ledger db, so it isn't pruned if any are kept.
+    keep_accumulator: bool,
 }
 
 impl DBSubPruner for TransactionAccumulatorPruner {
@@ -25,11 +28,13 @@ impl DBSubPruner for TransactionAccumulatorPruner {
 
     fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
         let batch = SchemaBatch::new();
-        self.transaction_store.prune_transaction_accumulator(
-            current_progress,
-            target_version,
-            &batch,
-        )?;
+        if !self.keep_accumulator {
+            self.transaction_store.prune_transaction_accumulator(
+                current_progress,
+                target_version,
+                &batch,
+            )?;
+        }
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::TransactionAccumulatorPrunerProgress,
             &DbMetadataValue::Version(target_version),
@@ -42,6 +47,7 @@ impl TransactionAccumulatorPruner {
     pub(in crate::pruner) fn new(
         transaction_store: Arc<TransactionStore>,
         transaction_accumulator_db: Arc<DB>,
+        keep_accumulator: bool,
         metadata_progress: Version,
     ) -> Result<Self> {
         let progress = get_or_initialize_subpruner_progress(
@@ -53,6 +59,7 @@ impl TransactionAccumulatorPruner {
         let myself = TransactionAccumulatorPruner {
             transaction_store,
             transaction_accumulator_db,
+            keep_accumulator,
         };
 
         info!(
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    pruner::{db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress},
+    pruner::{
+        db_sub_pruner::DBSubPruner, ledger_pruner::ledger_retention::LedgerRetention,
+        pruner_utils::get_or_initialize_subpruner_progress,
+    },
     schema::{
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         transaction::TransactionSchema,
@@ -19,6 +22,7 @@ use std::sync::Arc;
 pub struct TransactionPruner {
     transaction_store: Arc<TransactionStore>,
     transaction_db: Arc<DB>,
+    retention: Arc<LedgerRetention>,
 }
 
 impl DBSubPruner for TransactionPruner {
@@ -28,17 +32,42 @@ impl DBSubPruner for TransactionPruner {
 
     fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
         let batch = SchemaBatch::new();
+        let retained_versions = self
+            .retention
+            .retained_versions(current_progress, target_version)?;
         let candidate_transactions =
             self.get_pruning_candidate_transactions(current_progress, target_version)?;
-        self.transaction_store
-            .prune_transaction_by_hash(&candidate_transactions, &batch)?;
-        self.transaction_store
-            .prune_transaction_by_account(&candidate_transactions, &batch)?;
-        self.transaction_store.prune_transaction_schema(
-            current_progress,
-            target_version,
-            &batch,
-        )?;
+        let mut pruned_transactions = vec![];
+        // Retained transactions keep their account index entry only if the sender is retained,
+        // otherwise the sequence numbers in the account index wouldn't be continuous.
+        let mut pruned_account_index = vec![];
+        for (version, txn) in candidate_transactions {
+            if !retained_versions.contains(version) {
+                pruned_transactions.push(txn);
+            } else if txn.try_as_signed_user_txn().map_or(false, |txn| {
+                !retained_versions.retains_account_index(&txn.sender())
+            }) {
+                pruned_account_index.push(txn);
+            }
+        }
+        // The indices point into the cold ledger db if it's enabled.
+        let keeps_indices = retained_versions.keeps_indices();
+        if !keeps_indices {
+            self.transaction_store
+                .prune_transaction_by_hash(&pruned_transactions, &batch)?;
+            self.transaction_store
+                .prune_transaction_by_account(&pruned_transactions, &batch)?;
+            self.transaction_store
+                .prune_transaction_by_account(&pruned_account_index, &batch)?;
+        }
+        for range in retained_versions.pruned_ranges() {
+            self.transaction_store
+                .prune_transaction_schema(range.start, range.end, &batch)?;
+            if !keeps_indices {
+                self.transaction_store
+                    .prune_account_involvement(range.start, range.end, &batch)?;
+            }
+        }
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::TransactionPrunerProgress,
             &DbMetadataValue::Version(target_version),
@@ -51,6 +80,7 @@ impl TransactionPruner {
     pub(in crate::pruner) fn new(
         transaction_store: Arc<TransactionStore>,
         transaction_db: Arc<DB>,
+        retention: Arc<LedgerRetention>,
         metadata_progress: Version,
     ) -> Result<Self> {
         let progress = get_or_initialize_subpruner_progress(
@@ -62,6 +92,7 @@ impl TransactionPruner {
         let myself = TransactionPruner {
             transaction_store,
             transaction_db,
+            retention,
         };
 
         info!(
@@ -78,7 +109,7 @@ impl TransactionPruner {
         &self,
         start: Version,
         end: Version,
-    ) -> Result<Vec<Transaction>> {
+    ) -> Result<Vec<(Version, Transaction)>> {
         ensure!(end >= start);
 
         let mut iter = self
@@ -94,7 +125,7 @@ impl TransactionPruner {
             if version >= end {
                 break;
             }
-            txns.push(txn);
+            txns.push((version, txn));
         }
 
         Ok(txns)
@@ -2,7 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    pruner::{db_sub_pruner::DBSubPruner, pruner_utils::get_or_initialize_subpruner_progress},
+    pruner::{
+        db_sub_pruner::DBSubPruner, ledger_pruner::ledger_retention::LedgerRetention,
+        pruner_utils::get_or_initialize_subpruner_progress,
+    },
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
     TransactionStore,
 };
@@ -16,6 +19,7 @@ use std::sync::Arc;
 pub struct WriteSetPruner {
     transaction_store: Arc<TransactionStore>,
     write_set_db: Arc<DB>,
+    retention: Arc<LedgerRetention>,
 }
 
 impl DBSubPruner for WriteSetPruner {
@@ -25,8 +29,13 @@ impl DBSubPruner for WriteSetPruner {
 
     fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
         let batch = SchemaBatch::new();
-        self.transaction_store
-            .prune_write_set(current_progress, target_version, &batch)?;
+        let retained_versions = self
+            .retention
+            .retained_versions(current_progress, target_version)?;
+        for range in retained_versions.pruned_ranges() {
+            self.transaction_store
+                .prune_write_set(range.start, range.end, &batch)?;
+        }
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::WriteSetPrunerProgress,
             &DbMetadataValue::Version(target_version),
@@ -39,6 +48,7 @@ impl WriteSetPruner {
     pub(in crate::pruner) fn new(
         transaction_store: Arc<TransactionStore>,
         write_set_db: Arc<DB>,
+        retention: Arc<LedgerRetention>,
         metadata_progress: Version,
     ) -> Result<Self> {
         let progress = get_or_initialize_subpruner_progress(
@@ -50,6 +60,7 @@ impl WriteSetPruner {
         let myself = WriteSetPruner {
             transaction_store,
             write_set_db,
+            retention,
         };
 
         info!(
@@ -11,7 +11,10 @@ mod pruner_worker;
 mod state_kv_pruner;
 mod state_merkle_pruner;
 
-pub(crate) use ledger_pruner::ledger_pruner_manager::LedgerPrunerManager;
+pub(crate) use ledger_pruner::{
+    ledger_pruner_manager::LedgerPrunerManager,
+    ledger_retention::{LedgerRetentionFilter, RetainedVersions},
+};
 pub(crate) use pruner_manager::PrunerManager;
 pub(crate) use state_kv_pruner::state_kv_pruner_manager::StateKvPrunerManager;
 pub(crate) use state_merkle_pruner::state_merkle_pruner_manager::StateMerklePrunerManager;
@@ -0,0 +1,55 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the block events moved to the cold ledger db,
+//! so that the block of a version in cold storage can be looked up.
+//!
+//! The `NewBlockEvent` of each block is identified by the first version of the block.
+//! ```text
+//! |<--key-->|<---value--->|
+//! | version | event bytes |
+//! ```
+//!
+//! `Version` is serialized in big endian so that records in RocksDB will be in order of it's
+//! numeric value.
+
+use crate::schema::{ensure_slice_len_eq, COLD_BLOCK_EVENT_CF_NAME};
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::{contract_event::ContractEvent, transaction::Version};
+use byteorder::{BigEndian, ReadBytesExt};
+use std::mem::size_of;
+
+define_schema!(
+    ColdBlockEventSchema,
+    Version,
+    ContractEvent,
+    COLD_BLOCK_EVENT_CF_NAME
+);
+
+impl KeyCodec<ColdBlockEventSchema> for Version {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_key(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Version>())?;
+        Ok(data.read_u64::<BigEndian>()?)
+    }
+}
+
+impl ValueCodec<ColdBlockEventSchema> for ContractEvent {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        bcs::to_bytes(self).map_err(Into::into)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        bcs::from_bytes(data).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,58 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the ledger data moved to the cold ledger db.
+//!
+//! The transaction, transaction info, events and write set of a version are stored together,
+//! identified by version.
+//! ```text
+//! |<--key-->|<-------value------->|
+//! | version | cold_txn_data bytes |
+//! ```
+//!
+//! `Version` is serialized in big endian so that records in RocksDB will be in order of it's
+//! numeric value.
+
+use crate::{
+    cold_ledger_db::ColdTransactionData,
+    schema::{ensure_slice_len_eq, COLD_TRANSACTION_DATA_CF_NAME},
+};
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt};
+use std::mem::size_of;
+
+define_schema!(
+    ColdTransactionDataSchema,
+    Version,
+    ColdTransactionData,
+    COLD_TRANSACTION_DATA_CF_NAME
+);
+
+impl KeyCodec<ColdTransactionDataSchema> for Version {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_key(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Version>())?;
+        Ok(data.read_u64::<BigEndian>()?)
+    }
+}
+
+impl ValueCodec<ColdTransactionDataSchema> for ColdTransactionData {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        bcs::to_bytes(self).map_err(Into::into)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        bcs::from_bytes(data).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,56 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the accounts involved in the transaction at
+//! a version, i.e. the reverse of `AccountInvolvementSchema`. It's used to delete the account
+//! involvement index of a version without having to read the transaction, write set and events
+//! again when the version is pruned.
+//!
+//! ```text
+//! |<--key-->|<-------value------->|
+//! | version | bcs(Vec<address>)   |
+//! ```
+//!
+//! `Version` is serialized in big endian so that records in RocksDB will be in order of it's
+//! numeric value.
+
+use crate::schema::{ensure_slice_len_eq, INVOLVED_ACCOUNTS_BY_VERSION_CF_NAME};
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::{account_address::AccountAddress, transaction::Version};
+use byteorder::{BigEndian, ReadBytesExt};
+use std::mem::size_of;
+
+define_schema!(
+    InvolvedAccountsByVersionSchema,
+    Version,
+    Vec<AccountAddress>,
+    INVOLVED_ACCOUNTS_BY_VERSION_CF_NAME
+);
+
+impl KeyCodec<InvolvedAccountsByVersionSchema> for Version {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_key(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Version>())?;
+        Ok(data.read_u64::<BigEndian>()?)
+    }
+}
+
+impl ValueCodec<InvolvedAccountsByVersionSchema> for Vec<AccountAddress> {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        bcs::to_bytes(self).map_err(Into::into)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        bcs::from_bytes(data).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,18 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::{collection::vec, prelude::*};
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        version in any::<Version>(),
+        addresses in vec(any::<AccountAddress>(), 0..10),
+    ) {
+        assert_encode_decode::<InvolvedAccountsByVersionSchema>(&version, &addresses);
+    }
+}
+
+test_no_panic_decoding!(InvolvedAccountsByVersionSchema);
@@ -6,12 +6,17 @@
 //!
 //! All schemas are `pub(crate)` so not shown in rustdoc, refer to the source code to see details.
 
+pub(crate) mod account_involvement;
+pub(crate) mod cold_block_event;
+pub(crate) mod cold_transaction_data;
 pub(crate) mod db_metadata;
 pub(crate) mod epoch_by_version;
 pub(crate) mod event;
 pub(crate) mod event_accumulator;
 pub(crate) mod event_by_key;
+pub(crate) mod event_by_type;
 pub(crate) mod event_by_version;
+pub(crate) mod involved_accounts_by_version;
 pub(crate) mod jellyfish_merkle_node;
 pub(crate) mod ledger_info;
 pub(crate) mod stale_node_index;
@@ -30,12 +35,17 @@ pub(crate) mod write_set;
 use anyhow::{ensure, Result};
 use aptos_schemadb::ColumnFamilyName;
 
+pub const ACCOUNT_INVOLVEMENT_CF_NAME: ColumnFamilyName = "account_involvement";
+pub const COLD_BLOCK_EVENT_CF_NAME: ColumnFamilyName = "cold_block_event";
+pub const COLD_TRANSACTION_DATA_CF_NAME: ColumnFamilyName = "cold_transaction_data";
 pub const DB_METADATA_CF_NAME: ColumnFamilyName = "db_metadata";
 pub const EPOCH_BY_VERSION_CF_NAME: ColumnFamilyName = "epoch_by_version";
 pub const EVENT_ACCUMULATOR_CF_NAME: ColumnFamilyName = "event_accumulator";
 pub const EVENT_BY_KEY_CF_NAME: ColumnFamilyName = "event_by_key";
+pub const EVENT_BY_TYPE_CF_NAME: ColumnFamilyName = "event_by_type";
 pub const EVENT_BY_VERSION_CF_NAME: ColumnFamilyName = "event_by_version";
 pub const EVENT_CF_NAME: ColumnFamilyName = "event";
+pub const INVOLVED_ACCOUNTS_BY_VERSION_CF_NAME: ColumnFamilyName = "involved_accounts_by_version";
 pub const JELLYFISH_MERKLE_NODE_CF_NAME: ColumnFamilyName = "jellyfish_merkle_node";
 pub const LEDGER_INFO_CF_NAME: ColumnFamilyName = "ledger_info";
 pub const STALE_NODE_INDEX_CF_NAME: ColumnFamilyName = "stale_node_index";
@@ -78,11 +88,20 @@ pub mod fuzzing {
     pub fn fuzz_decode(data: &[u8]) {
         #[allow(unused_must_use)]
         {
+            assert_no_panic_decoding::<super::account_involvement::AccountInvolvementSchema>(data);
+            assert_no_panic_decoding::<super::cold_block_event::ColdBlockEventSchema>(data);
+            assert_no_panic_decoding::<super::cold_transaction_data::ColdTransactionDataSchema>(
+                data,
+            );
             assert_no_panic_decoding::<super::epoch_by_version::EpochByVersionSchema>(data);
             assert_no_panic_decoding::<super::event::EventSchema>(data);
             assert_no_panic_decoding::<super::event_accumulator::EventAccumulatorSchema>(data);
             assert_no_panic_decoding::<super::event_by_key::EventByKeySchema>(data);
+            assert_no_panic_decoding::<super::event_by_type::EventByTypeSchema>(data);
             assert_no_panic_decoding::<super::event_by_version::EventByVersionSchema>(data);
+            assert_no_panic_decoding::<
+                super::involved_accounts_by_version::InvolvedAccountsByVersionSchema,
+            >(data);
             assert_no_panic_decoding::<super::jellyfish_merkle_node::JellyfishMerkleNodeSchema>(
                 data,
             );
@@ -7,6 +7,7 @@ use super::*;
 use crate::{
     jellyfish_merkle_node::JellyfishMerkleNodeSchema, schema::state_value::StateValueSchema,
 };
+use aptos_config::config::LedgerPrunerConfig;
 use aptos_crypto::hash::CryptoHash;
 use aptos_executor_types::ProofReader;
 use aptos_jellyfish_merkle::node_type::{Node, NodeKey};
@@ -18,6 +19,7 @@ use aptos_types::{
     proptest_types::{AccountInfoUniverse, BlockGen},
     state_store::create_empty_sharded_state_updates,
 };
+use move_core_types::move_resource::MoveStructType;
 use proptest::{collection::vec, prelude::*, sample::Index};
 use std::fmt::Debug;
 
@@ -156,6 +158,7 @@ prop_compose! {
         max_user_txns_per_block: usize,
         min_blocks: usize,
         max_blocks: usize,
+        with_block_events: bool,
     )(
         mut universe in any_with::<AccountInfoUniverse>(num_accounts).no_shrink(),
         block_gens in vec(any_with::<BlockGen>(max_user_txns_per_block), min_blocks..=max_blocks),
@@ -166,8 +169,15 @@ prop_compose! {
         let mut in_memory_state = StateDelta::new_empty();
         let _ancester = in_memory_state.current.clone();
 
-        for block_gen in block_gens {
+        for (height, block_gen) in block_gens.into_iter().enumerate() {
             let (mut txns_to_commit, mut ledger_info) = block_gen.materialize(&mut universe);
+            if with_block_events {
+                txns_to_commit[0] = add_new_block_event(
+                    &txns_to_commit[0],
+                    height as u64,
+                    ledger_info.timestamp_usecs(),
+                );
+            }
             update_in_memory_state(&mut in_memory_state, &txns_to_commit);
             let state_checkpoint_root_hash = in_memory_state.root_hash();
 
@@ -212,10 +222,55 @@ prop_compose! {
 pub fn arb_blocks_to_commit(
 ) -> impl Strategy<Value = Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>> {
     arb_blocks_to_commit_impl(
-        5,  /* num_accounts */
-        2,  /* max_user_txn_per_block */
-        1,  /* min_blocks */
-        10, /* max_blocks */
+        5,     /* num_accounts */
+        2,     /* max_user_txn_per_block */
+        1,     /* min_blocks */
+        10,    /* max_blocks */
+        false, /* with_block_events */
+    )
+}
+
+/// Like `arb_blocks_to_commit`, but the first transaction of every block emits a `NewBlockEvent`.
+pub fn arb_blocks_to_commit_with_block_events(
+) -> impl Strategy<Value = Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>> {
+    arb_blocks_to_commit_impl(
+        5,    /* num_accounts */
+        2,    /* max_user_txn_per_block */
+        1,    /* min_blocks */
+        10,   /* max_blocks */
+        true, /* with_block_events */
+    )
+}
+
+fn add_new_block_event(
+    txn_to_commit: &TransactionToCommit,
+    height: u64,
+    timestamp: u64,
+) -> TransactionToCommit {
+    let new_block_event = NewBlockEvent::new(
+        AccountAddress::ZERO,
+        0,      // epoch
+        height, // round
+        height,
+        vec![],               // prev block voters
+        AccountAddress::ZERO, // proposer
+        Vec::new(),           // failed_proposers
+        timestamp,
+    );
+    let mut events = txn_to_commit.events().to_vec();
+    events.push(ContractEvent::new_v1(
+        new_block_event_key(),
+        height,
+        TypeTag::Struct(Box::new(NewBlockEvent::struct_tag())),
+        bcs::to_bytes(&new_block_event).unwrap(),
+    ));
+    TransactionToCommit::new(
+        txn_to_commit.transaction().clone(),
+        txn_to_commit.transaction_info().clone(),
+        txn_to_commit.state_updates().clone(),
+        txn_to_commit.write_set().clone(),
+        events,
+        txn_to_commit.is_reconfig(),
     )
 }
 
@@ -232,7 +287,7 @@ pub fn arb_blocks_to_commit_with_block_nums(
         arb_blocks_to_commit_impl(
             5, /* num_accounts */
             2, /* max_user_txn_per_block */
-            min_blocks, max_blocks,
+            min_blocks, max_blocks, false, /* with_block_events */
         ),
         proptest::bool::ANY,
     )
@@ -432,6 +487,239 @@ pub fn test_save_blocks_impl(
     );
 }
 
+/// Commits the blocks, then prunes everything but the latest version. Returns the first version
+/// of every block.
+fn save_blocks_and_prune(
+    db: &AptosDB,
+    input: &[(Vec<TransactionToCommit>, LedgerInfoWithSignatures)],
+) -> Vec<Version> {
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut cur_ver: Version = 0;
+    let mut block_first_versions = vec![];
+    for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+        update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions_for_test(
+            txns_to_commit,
+            cur_ver,                /* first_version */
+            cur_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            false, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        block_first_versions.push(cur_ver);
+        cur_ver += txns_to_commit.len() as u64;
+    }
+
+    let latest_version = cur_ver - 1;
+    db.ledger_pruner
+        .wake_and_wait_pruner(latest_version)
+        .unwrap();
+    assert_eq!(db.ledger_pruner.get_min_readable_version(), latest_version);
+    block_first_versions
+}
+
+/// Commits the blocks with the ledger history of the sender of the first user transaction
+/// retained, prunes everything but the latest version, and verifies that the transactions of the
+/// sender survive the prune, unlike the state checkpoints.
+pub fn test_retain_ledger_history_impl(
+    input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
+) {
+    let txns_to_commit: Vec<_> = input
+        .iter()
+        .flat_map(|(txns_to_commit, _)| txns_to_commit.iter().cloned())
+        .collect();
+    let retained_account = match txns_to_commit
+        .iter()
+        .find_map(|txn_to_commit| txn_to_commit.transaction().try_as_signed_user_txn())
+    {
+        Some(txn) => txn.sender(),
+        None => return,
+    };
+
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::open_with_ledger_history(
+        StorageDirPaths::from_path(&tmp_dir),
+        false, /* readonly */
+        PrunerConfig {
+            ledger_pruner_config: LedgerPrunerConfig {
+                enable: true,
+                prune_window: 0,
+                batch_size: 1,
+                user_pruning_window_offset: 0,
+            },
+            ..NO_OP_STORAGE_PRUNER_CONFIG
+        },
+        &LedgerRetentionConfig {
+            accounts: vec![retained_account],
+            event_types: vec![],
+        },
+        &LedgerColdStorageConfig::default(),
+        RocksdbConfigs::default(),
+        false, /* enable_indexer */
+        false, /* enable_account_involvement_index */
+        BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    )
+    .unwrap();
+
+    save_blocks_and_prune(&db, &input);
+    let latest_version = txns_to_commit.len() as Version - 1;
+    let ledger_info = input.last().unwrap().1.ledger_info();
+
+    for (version, txn_to_commit) in txns_to_commit
+        .iter()
+        .enumerate()
+        .take(latest_version as usize)
+    {
+        let version = version as Version;
+        match txn_to_commit.transaction() {
+            Transaction::UserTransaction(txn) if txn.sender() == retained_account => {
+                assert!(db.is_pruned_transaction_kept(version).unwrap());
+                let txn_with_proof = db
+                    .get_transaction_by_version(version, latest_version, true)
+                    .unwrap();
+                txn_with_proof
+                    .verify_user_txn(ledger_info, version, txn.sender(), txn.sequence_number())
+                    .unwrap();
+                assert_eq!(
+                    txn_with_proof.events.as_deref(),
+                    Some(txn_to_commit.events())
+                );
+            },
+            Transaction::StateCheckpoint(_) => {
+                assert!(!db.is_pruned_transaction_kept(version).unwrap());
+                assert!(db
+                    .get_transaction_by_version(version, latest_version, false)
+                    .is_err());
+                assert!(db.transaction_store.get_transaction(version).is_err());
+            },
+            _ => (),
+        }
+    }
+
+    let mut txns_by_account = group_txns_by_account(&txns_to_commit);
+    txns_by_account.retain(|account, _| *account == retained_account);
+    verify_account_txns(&db, txns_by_account, ledger_info);
+}
+
+/// Commits the blocks with the cold ledger db enabled, prunes everything but the latest version,
+/// and verifies that the pruned data is read from the cold ledger db.
+pub fn test_read_from_cold_ledger_db_impl(
+    input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
+) {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::open_with_ledger_history(
+        StorageDirPaths::from_path(&tmp_dir),
+        false, /* readonly */
+        PrunerConfig {
+            ledger_pruner_config: LedgerPrunerConfig {
+                enable: true,
+                prune_window: 0,
+                batch_size: 1,
+                user_pruning_window_offset: 0,
+            },
+            ..NO_OP_STORAGE_PRUNER_CONFIG
+        },
+        &LedgerRetentionConfig::default(),
+        &LedgerColdStorageConfig {
+            enable: true,
+            ..Default::default()
+        },
+        RocksdbConfigs::default(),
+        false, /* enable_indexer */
+        false, /* enable_account_involvement_index */
+        BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    )
+    .unwrap();
+
+    let block_first_versions = save_blocks_and_prune(&db, &input);
+    let txns_to_commit: Vec<_> = input
+        .iter()
+        .flat_map(|(txns_to_commit, _)| txns_to_commit.iter().cloned())
+        .collect();
+    let latest_version = txns_to_commit.len() as Version - 1;
+    if latest_version > 0 {
+        assert!(db.transaction_store.get_transaction(0).is_err());
+    }
+    let latest_ledger_info_with_sigs = &input.last().unwrap().1;
+    let ledger_info = latest_ledger_info_with_sigs.ledger_info();
+
+    for (version, txn_to_commit) in txns_to_commit.iter().enumerate() {
+        let version = version as Version;
+        assert!(db.is_pruned_transaction_kept(version).unwrap());
+
+        let txn_with_proof = db
+            .get_transaction_by_version(version, latest_version, true)
+            .unwrap();
+        assert_eq!(&txn_with_proof.transaction, txn_to_commit.transaction());
+        assert_eq!(
+            txn_with_proof.events.as_deref(),
+            Some(txn_to_commit.events())
+        );
+        txn_with_proof.proof.verify(ledger_info, version).unwrap();
+
+        if txn_to_commit
+            .transaction()
+            .try_as_signed_user_txn()
+            .is_some()
+        {
+            let txn_with_proof = db
+                .get_transaction_by_hash(txn_to_commit.transaction().hash(), latest_version, true)
+                .unwrap()
+                .expect("Should exist.");
+            assert_eq!(txn_with_proof.version, version);
+        }
+
+        let txn_list_with_proof = db
+            .get_transactions(version, 1, latest_version, true /* fetch_events */)
+            .unwrap();
+        txn_list_with_proof
+            .verify(ledger_info, Some(version))
+            .unwrap();
+        assert_eq!(txn_list_with_proof.transactions.len(), 1);
+
+        let txn_output_list_with_proof = db
+            .get_transaction_outputs(version, 1, latest_version)
+            .unwrap();
+        txn_output_list_with_proof
+            .verify(ledger_info, Some(version))
+            .unwrap();
+        assert_eq!(txn_output_list_with_proof.transactions_and_outputs.len(), 1);
+    }
+
+    // The iterators span both the cold ledger db and the ledger db.
+    verify_ledger_iterators(&db, &txns_to_commit, 0, latest_ledger_info_with_sigs);
+    verify_events_by_event_key(
+        &db,
+        group_events_by_event_key(0, &txns_to_commit),
+        ledger_info,
+        true, /* is_latest */
+    );
+    verify_account_txns(&db, group_txns_by_account(&txns_to_commit), ledger_info);
+
+    for (height, first_version) in block_first_versions.iter().enumerate() {
+        let last_version = block_first_versions
+            .get(height + 1)
+            .map_or(latest_version, |v| v - 1);
+        let (actual_first_version, actual_last_version, new_block_event) =
+            db.get_block_info_by_height(height as u64).unwrap();
+        assert_eq!(actual_first_version, *first_version);
+        assert_eq!(actual_last_version, last_version);
+        assert_eq!(new_block_event.height(), height as u64);
+        assert_eq!(
+            db.get_block_timestamp(last_version).unwrap(),
+            input[height].1.ledger_info().timestamp_usecs()
+        );
+    }
+}
+
 fn verify_snapshots(
     db: &AptosDB,
     start_version: Version,
@@ -8,6 +8,8 @@ use crate::{
     errors::AptosDbError,
     ledger_db::LedgerDb,
     schema::{
+        account_involvement::AccountInvolvementSchema,
+        involved_accounts_by_version::InvolvedAccountsByVersionSchema,
         transaction::TransactionSchema, transaction_by_account::TransactionByAccountSchema,
         transaction_by_hash::TransactionByHashSchema, write_set::WriteSetSchema,
     },
@@ -18,13 +20,24 @@ use crate::{
 use anyhow::{ensure, format_err, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_schemadb::{ReadOptions, SchemaBatch};
+use aptos_storage_interface::Order;
 use aptos_types::{
+    access_path::AccessPath,
     account_address::AccountAddress,
+    account_config::{ObjectCoreResource, ObjectGroupResource, TransferEvent},
     proof::position::Position,
-    transaction::{Transaction, Version},
+    state_store::state_key::StateKeyInner,
+    transaction::{Transaction, TransactionToCommit, Version},
     write_set::WriteSet,
 };
-use std::sync::Arc;
+use move_core_types::{
+    language_storage::{StructTag, TypeTag},
+    move_resource::MoveStructType,
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::Arc,
+};
 
 #[cfg(test)]
 mod test;
@@ -105,6 +118,50 @@ impl TransactionStore {
         ))
     }
 
+    /// Gets the versions of the transactions involving `address` (see `involved_accounts`), at
+    /// most `limit` of them, starting from `start_version` in the given `order` and with
+    /// `version <= ledger_version`.
+    ///
+    /// N.b. the account involvement index is only populated if it's enabled in the storage config.
+    pub fn get_account_involved_versions(
+        &self,
+        address: AccountAddress,
+        start_version: Version,
+        order: Order,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<Version>> {
+        let iter = match order {
+            Order::Ascending => {
+                let mut iter = self
+                    .ledger_db
+                    .transaction_db()
+                    .iter::<AccountInvolvementSchema>(ReadOptions::default())?;
+                iter.seek(&(address, start_version))?;
+                iter
+            },
+            Order::Descending => {
+                let mut iter = self
+                    .ledger_db
+                    .transaction_db()
+                    .rev_iter::<AccountInvolvementSchema>(ReadOptions::default())?;
+                iter.seek_for_prev(&(address, std::cmp::min(start_version, ledger_version)))?;
+                iter
+            },
+        };
+
+        let mut versions = Vec::new();
+        for res in iter.take(limit as usize) {
+            let ((account, version), ()) = res?;
+            if account != address || version > ledger_version {
+                break;
+            }
+            versions.push(version);
+        }
+
+        Ok(versions)
+    }
+
     /// Get signed transaction given `version`
     pub fn get_transaction(&self, version: Version) -> Result<Transaction> {
         self.ledger_db
@@ -163,6 +220,23 @@ impl TransactionStore {
         Ok(())
     }
 
+    /// Save the account involvement index of the transaction at `version`
+    pub fn put_account_involvement(
+        &self,
+        version: Version,
+        txn_to_commit: &TransactionToCommit,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        let accounts = involved_accounts(txn_to_commit);
+        if accounts.is_empty() {
+            return Ok(());
+        }
+        for account in &accounts {
+            batch.put::<AccountInvolvementSchema>(&(*account, version), &())?;
+        }
+        batch.put::<InvolvedAccountsByVersionSchema>(&version, &accounts.into_iter().collect())
+    }
+
     /// Get executed transaction vm output given `version`
     pub fn get_write_set(&self, version: Version) -> Result<WriteSet> {
         self.ledger_db
@@ -252,6 +326,31 @@ impl TransactionStore {
         Ok(())
     }
 
+    /// Prune the account involvement index between a range of version in [begin, end)
+    pub fn prune_account_involvement(
+        &self,
+        begin: Version,
+        end: Version,
+        db_batch: &SchemaBatch,
+    ) -> Result<()> {
+        let mut iter = self
+            .ledger_db
+            .transaction_db()
+            .iter::<InvolvedAccountsByVersionSchema>(ReadOptions::default())?;
+        iter.seek(&begin)?;
+        for item in iter {
+            let (version, accounts) = item?;
+            if version >= end {
+                break;
+            }
+            for account in accounts {
+                db_batch.delete::<AccountInvolvementSchema>(&(account, version))?;
+            }
+            db_batch.delete::<InvolvedAccountsByVersionSchema>(&version)?;
+        }
+        Ok(())
+    }
+
     /// Prune the transaction schema store between a range of version in [begin, end)
     pub fn prune_transaction_schema(
         &self,
@@ -350,3 +449,56 @@ impl TransactionStore {
         Ok(())
     }
 }
+
+/// Returns the accounts involved in a transaction: its signers (the sender, the secondary signers
+/// and the fee payer), the owners of the resources and modules in its write set, the creators of
+/// the event handles it emits events on, the owners of the objects it writes (e.g. the owner of a
+/// fungible store receiving a deposit), and the previous and new owners of the objects it
+/// transfers. Special addresses (e.g. `0x1`) are left out, because almost every transaction
+/// involves them.
+pub(crate) fn involved_accounts(txn_to_commit: &TransactionToCommit) -> BTreeSet<AccountAddress> {
+    let mut accounts = BTreeSet::new();
+
+    if let Some(txn) = txn_to_commit.transaction().try_as_signed_user_txn() {
+        let authenticator = txn.authenticator_ref();
+        accounts.insert(txn.sender());
+        accounts.extend(authenticator.secondary_signer_addresses());
+        accounts.extend(authenticator.fee_payer_address());
+    }
+
+    let object_group_path = AccessPath::resource_group_path_vec(ObjectGroupResource::struct_tag());
+    for (state_key, write_op) in txn_to_commit.write_set() {
+        if let StateKeyInner::AccessPath(access_path) = state_key.inner() {
+            accounts.insert(access_path.address);
+            // The object, e.g. a fungible store, is usually owned by another account, which is
+            // involved as well.
+            if access_path.path == object_group_path {
+                accounts.extend(write_op.bytes().and_then(|bytes| object_owner(bytes)));
+            }
+        }
+    }
+
+    let transfer_event_type = TypeTag::Struct(Box::new(TransferEvent::struct_tag()));
+    for event in txn_to_commit.events() {
+        if let Some(event_key) = event.event_key() {
+            accounts.insert(event_key.get_creator_address());
+        }
+        if event.type_tag() == &transfer_event_type {
+            if let Ok(transfer) = TransferEvent::try_from_bytes(event.event_data()) {
+                accounts.extend([transfer.object(), transfer.from(), transfer.to()]);
+            }
+        }
+    }
+
+    accounts.retain(|account| !account.is_special());
+    accounts
+}
+
+/// Returns the owner recorded in the `ObjectCore` of a serialized object group, if any.
+fn object_owner(object_group: &[u8]) -> Option<AccountAddress> {
+    let group: BTreeMap<StructTag, Vec<u8>> = bcs::from_bytes(object_group).ok()?;
+    let object_core = group.get(&ObjectCoreResource::struct_tag())?;
+    bcs::from_bytes::<ObjectCoreResource>(object_core)
+        .ok()
+        .map(|object_core| object_core.owner())
+}
@@ -9,8 +9,11 @@ use crate::AptosDB;
 use aptos_proptest_helpers::Index;
 use aptos_temppath::TempPath;
 use aptos_types::{
+    event::EventHandle,
     proptest_types::{AccountInfoUniverse, SignatureCheckedTransactionGen},
-    transaction::Transaction,
+    state_store::{create_empty_sharded_state_updates, state_key::StateKey},
+    transaction::{ExecutionStatus, Transaction, TransactionInfo, TransactionToCommit},
+    write_set::{WriteOp, WriteSetMut},
 };
 use proptest::{collection::vec, prelude::*};
 use std::collections::BTreeMap;
@@ -199,6 +202,134 @@ proptest! {
 
         prop_assert_eq!(&actual_scan, &expected_scan);
     }
+
+    #[test]
+    fn test_account_involvement(
+        txns_to_commit in vec(any::<TransactionToCommit>(), 1..10),
+        num_pruned in 0_usize..10,
+    ) {
+        let tmp_dir = TempPath::new();
+        let db = AptosDB::new_for_test(&tmp_dir);
+        let store = &db.transaction_store;
+
+        let batch = SchemaBatch::new();
+        let mut expected = BTreeMap::<AccountAddress, Vec<Version>>::new();
+        for (ver, txn_to_commit) in txns_to_commit.iter().enumerate() {
+            store
+                .put_account_involvement(ver as Version, txn_to_commit, &batch)
+                .unwrap();
+            let accounts = involved_accounts(txn_to_commit);
+            let sender = txn_to_commit
+                .transaction()
+                .try_as_signed_user_txn()
+                .expect("All should be user transactions here.")
+                .sender();
+            prop_assert!(sender.is_special() || accounts.contains(&sender));
+            for account in accounts {
+                expected.entry(account).or_default().push(ver as Version);
+            }
+        }
+        store
+            .ledger_db
+            .transaction_db()
+            .write_schemas(batch)
+            .unwrap();
+
+        let ledger_version = txns_to_commit.len() as Version - 1;
+        for (address, versions) in &expected {
+            prop_assert_eq!(
+                &store
+                    .get_account_involved_versions(
+                        *address,
+                        0,
+                        Order::Ascending,
+                        100,
+                        ledger_version,
+                    )
+                    .unwrap(),
+                versions
+            );
+            prop_assert_eq!(
+                store
+                    .get_account_involved_versions(
+                        *address,
+                        Version::MAX,
+                        Order::Descending,
+                        100,
+                        ledger_version,
+                    )
+                    .unwrap(),
+                versions.iter().rev().cloned().collect::<Vec<_>>()
+            );
+            prop_assert_eq!(
+                &store
+                    .get_account_involved_versions(*address, 0, Order::Ascending, 1, ledger_version)
+                    .unwrap(),
+                &versions[..1]
+            );
+        }
+
+        // Prune the index of the first `num_pruned` versions
+        let num_pruned = std::cmp::min(num_pruned, txns_to_commit.len()) as Version;
+        let batch = SchemaBatch::new();
+        store.prune_account_involvement(0, num_pruned, &batch).unwrap();
+        store
+            .ledger_db
+            .transaction_db()
+            .write_schemas(batch)
+            .unwrap();
+        for (address, versions) in &expected {
+            prop_assert_eq!(
+                store
+                    .get_account_involved_versions(
+                        *address,
+                        0,
+                        Order::Ascending,
+                        100,
+                        ledger_version,
+                    )
+                    .unwrap(),
+                versions
+                    .iter()
+                    .filter(|version| **version >= num_pruned)
+                    .cloned()
+                    .collect::<Vec<_>>()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_involved_accounts_include_object_owners() {
+    let store_address = AccountAddress::from_hex_literal("0xabc").unwrap();
+    let owner = AccountAddress::from_hex_literal("0xdef").unwrap();
+    let object_core = ObjectCoreResource::new(0, owner, false, EventHandle::random(0));
+    let object_group = BTreeMap::from([(
+        ObjectCoreResource::struct_tag(),
+        bcs::to_bytes(&object_core).unwrap(),
+    )]);
+    let write_set = WriteSetMut::new(vec![(
+        StateKey::access_path(AccessPath::resource_group_access_path(
+            store_address,
+            ObjectGroupResource::struct_tag(),
+        )),
+        WriteOp::Modification(bcs::to_bytes(&object_group).unwrap().into()),
+    )])
+    .freeze()
+    .unwrap();
+    let txn_to_commit = TransactionToCommit::new(
+        Transaction::StateCheckpoint(HashValue::zero()),
+        TransactionInfo::new_placeholder(0, None, ExecutionStatus::Success),
+        create_empty_sharded_state_updates(),
+        write_set,
+        vec![],
+        false,
+    );
+
+    assert_eq!(
+        involved_accounts(&txn_to_commit),
+        BTreeSet::from([store_address, owner])
+    );
 }
 
 fn init_store(
@@ -293,6 +293,11 @@ fn delete_transaction_index_data(
         );
         transaction_store.prune_transaction_by_account(&transactions, batch)?;
         transaction_store.prune_transaction_by_hash(&transactions, batch)?;
+        transaction_store.prune_account_involvement(
+            start_version,
+            start_version + num_txns as u64,
+            batch,
+        )?;
     }
 
     Ok(())
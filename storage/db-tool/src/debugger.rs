@@ -1,11 +1,13 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::equivocation_evidence;
 use anyhow::Result;
 use aptos_db::db_debugger::{checkpoint, ledger, state_tree, truncate};
 use clap::Parser;
 
-/// List snapshots, print nodes, make DB checkpoints and validate ledger hash
+/// List snapshots, print nodes, make DB checkpoints, validate ledger hash and export
+/// equivocation evidence
 #[derive(Parser)]
 pub enum Command {
     #[clap(subcommand)]
@@ -14,6 +16,7 @@ pub enum Command {
     #[clap(subcommand)]
     Ledger(ledger::Cmd),
     Truncate(truncate::Cmd),
+    EquivocationEvidence(equivocation_evidence::Cmd),
 }
 
 impl Command {
@@ -23,6 +26,7 @@ impl Command {
             Command::Checkpoint(cmd) => cmd.run(),
             Command::Ledger(cmd) => cmd.run(),
             Command::Truncate(cmd) => cmd.run(),
+            Command::EquivocationEvidence(cmd) => cmd.run(),
         }
     }
 }
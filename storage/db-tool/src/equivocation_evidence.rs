@@ -0,0 +1,103 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use aptos_config::config::{
+    RocksdbConfigs, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_consensus::{
+    equivocation_evidence::{EquivocationEvidence, EvidenceVerification},
+    read_equivocation_evidence,
+};
+use aptos_db::AptosDB;
+use aptos_storage_interface::DbReader;
+use aptos_types::validator_verifier::ValidatorVerifier;
+use clap::Parser;
+use std::{collections::HashMap, path::PathBuf};
+
+/// Export the equivocation evidence recorded in a consensus db, optionally verifying it against
+/// the validator sets found in an AptosDB.
+#[derive(Parser)]
+pub struct Cmd {
+    /// The directory containing the consensus db.
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+
+    /// Write the evidence, bcs encoded, to this file instead of printing it.
+    #[clap(long, value_parser)]
+    output: Option<PathBuf>,
+
+    /// The directory of an AptosDB to read the validator set of each epoch from. If set, every
+    /// piece of evidence is verified.
+    #[clap(long, value_parser)]
+    aptos_db_dir: Option<PathBuf>,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let evidence = read_equivocation_evidence(&self.db_dir)?;
+        println!(
+            "Found {} piece(s) of equivocation evidence.",
+            evidence.len()
+        );
+
+        if let Some(aptos_db_dir) = &self.aptos_db_dir {
+            let db = AptosDB::open(
+                StorageDirPaths::from_path(aptos_db_dir),
+                true, /* read_only */
+                NO_OP_STORAGE_PRUNER_CONFIG,
+                RocksdbConfigs::default(),
+                false, /* indexer */
+                BUFFERED_STATE_TARGET_ITEMS,
+                DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+            )?;
+            let mut verifiers = HashMap::new();
+            for e in &evidence {
+                if !verifiers.contains_key(&e.epoch()) {
+                    verifiers.insert(e.epoch(), get_verifier(&db, e.epoch())?);
+                }
+                match e.verify(&verifiers[&e.epoch()]) {
+                    Ok(EvidenceVerification::Verified) => println!("Verified {}", e),
+                    Ok(EvidenceVerification::Unverifiable) => println!(
+                        "Unable to verify {}: the messages are not signed by their author",
+                        e
+                    ),
+                    Err(err) => println!("Failed to verify {}: {}", e, err),
+                }
+            }
+        }
+
+        match &self.output {
+            Some(output) => {
+                std::fs::write(output, bcs::to_bytes(&evidence)?)?;
+                println!("Written to {}.", output.display());
+            },
+            None => print_evidence(&evidence),
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns the validator set of the given epoch, as announced by the ledger info ending the
+/// previous epoch.
+fn get_verifier(db: &AptosDB, epoch: u64) -> Result<ValidatorVerifier> {
+    ensure!(epoch > 0, "Epoch 0 has no validator set to verify against.");
+    let proof = db.get_epoch_ending_ledger_infos(epoch - 1, epoch)?;
+    let ledger_info = proof
+        .ledger_info_with_sigs
+        .first()
+        .ok_or_else(|| format_err!("Epoch {} is not found in the db.", epoch - 1))?;
+    let epoch_state = ledger_info
+        .ledger_info()
+        .next_epoch_state()
+        .ok_or_else(|| format_err!("Epoch {} has no ending ledger info.", epoch - 1))?;
+    Ok(epoch_state.verifier.clone())
+}
+
+fn print_evidence(evidence: &[EquivocationEvidence]) {
+    for e in evidence {
+        println!("{}:\n{:?}\n", e, e);
+    }
+}
@@ -6,6 +6,7 @@ extern crate core;
 mod backup;
 mod backup_maintenance;
 mod debugger;
+mod equivocation_evidence;
 mod replay_verify;
 pub mod restore;
 #[cfg(test)]
@@ -35,6 +35,7 @@ use aptos_types::{
     },
     write_set::WriteSet,
 };
+use move_core_types::language_storage::TypeTag;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
 use thiserror::Error;
@@ -200,6 +201,17 @@ pub trait DbReader: Send + Sync {
             ledger_version: Version,
         ) -> Result<Vec<EventWithVersion>>;
 
+        /// Returns module events by given event type, along with the index of each event among
+        /// the events of its transaction. `start` is a (transaction version, event index) pair.
+        fn get_events_by_type(
+            &self,
+            event_type: &TypeTag,
+            start: (Version, u64),
+            order: Order,
+            limit: u64,
+            ledger_version: Version,
+        ) -> Result<Vec<(u64, EventWithVersion)>>;
+
         fn get_transaction_iterator(
             &self,
             start_version: Version,
@@ -313,6 +325,21 @@ pub trait DbReader: Send + Sync {
             ledger_version: Version,
         ) -> Result<AccountTransactionsWithProof>;
 
+        /// Returns the list of transactions involving the account with `address` (signed by it,
+        /// writing to its resources, or emitting events referencing it) starting at version
+        /// `start`, in the given `order`. Will return no more than `limit` transactions. Will
+        /// ignore transactions with `txn.version > ledger_version`. Fails if the account
+        /// involvement index isn't enabled.
+        fn get_account_involved_transactions(
+            &self,
+            address: AccountAddress,
+            start: Version,
+            order: Order,
+            limit: u64,
+            include_events: bool,
+            ledger_version: Version,
+        ) -> Result<AccountTransactionsWithProof>;
+
         /// Returns proof of new state for a given ledger info with signatures relative to version known
         /// to client
         fn get_state_proof_with_ledger_info(
@@ -437,6 +464,10 @@ pub trait DbReader: Send + Sync {
         /// Get the ledger prune window config value.
         fn get_ledger_prune_window(&self) -> Result<usize>;
 
+        /// Returns if the transaction at a version below the ledger prune window is still kept,
+        /// because the ledger retention policy kept it.
+        fn is_pruned_transaction_kept(&self, version: Version) -> Result<bool>;
+
         /// Get table info from the internal indexer.
         fn get_table_info(&self, handle: TableHandle) -> Result<TableInfo>;
 
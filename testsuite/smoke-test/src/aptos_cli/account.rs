@@ -5,6 +5,7 @@ use crate::smoke_test_environment::SwarmBuilder;
 use aptos::{account::create::DEFAULT_FUNDED_COINS, common::types::GasOptions};
 use aptos_crypto::{PrivateKey, ValidCryptoMaterialStringExt};
 use aptos_keygen::KeyGen;
+use aptos_types::account_address::from_public_key;
 
 #[tokio::test]
 async fn test_account_flow() {
@@ -91,6 +92,30 @@ async fn test_account_flow() {
     assert!(cli.account_balance_now(2).await.unwrap() <= new_expected_balance);
 }
 
+#[tokio::test]
+async fn test_simulate_transaction() {
+    let (_swarm, mut cli, _faucet) = SwarmBuilder::new_local(1)
+        .with_aptos()
+        .build_with_cli(1)
+        .await;
+    let private_key = KeyGen::from_os_rng().generate_ed25519_private_key();
+    let address = from_public_key(&private_key.public_key());
+
+    let summary = cli.simulate_create_account(0, address).await.unwrap();
+    assert_eq!(summary.simulated, Some(true));
+    assert_eq!(summary.success, Some(true));
+    assert!(summary.gas_used.unwrap() > 0);
+    // The transaction isn't on chain, so it has no version
+    assert_eq!(summary.version, None);
+    assert_eq!(serde_json::to_value(&summary).unwrap()["simulated"], true);
+
+    // Nothing was submitted, so no gas was charged and the account can still be created
+    cli.assert_account_balance_now(0, DEFAULT_FUNDED_COINS)
+        .await;
+    let index = cli.create_cli_account(private_key, 0).await.unwrap();
+    assert_eq!(cli.account_id(index), address);
+}
+
 #[tokio::test]
 async fn test_account_key_rotation() {
     let (_swarm, mut cli, _faucet) = SwarmBuilder::new_local(1)
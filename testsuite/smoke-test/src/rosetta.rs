@@ -26,10 +26,12 @@ use aptos_rosetta::{
     client::RosettaClient,
     common::{native_coin, BlockHash, BLOCKCHAIN, Y2K_MS},
     types::{
-        AccountBalanceRequest, AccountBalanceResponse, AccountIdentifier, BlockIdentifier,
-        BlockRequest, BlockResponse, NetworkIdentifier, NetworkRequest, Operation,
-        OperationStatusType, OperationType, PartialBlockIdentifier, TransactionIdentifier,
-        TransactionType, STAKING_CONTRACT_MODULE, SWITCH_OPERATOR_WITH_SAME_COMMISSION_FUNCTION,
+        AccountBalanceRequest, AccountBalanceResponse, AccountCoinsRequest, AccountIdentifier,
+        BlockIdentifier, BlockRequest, BlockResponse, BlockTransactionRequest, MempoolRequest,
+        MempoolTransactionRequest, NetworkIdentifier, NetworkRequest, Operation,
+        OperationStatusType, OperationType, PartialBlockIdentifier, SearchTransactionsRequest,
+        TransactionIdentifier, TransactionType, STAKING_CONTRACT_MODULE,
+        SWITCH_OPERATOR_WITH_SAME_COMMISSION_FUNCTION,
     },
     ROSETTA_VERSION,
 };
@@ -110,6 +112,7 @@ async fn setup_test(
             validator.rest_api_endpoint(),
         )),
         cli.addresses(),
+        HashSet::new(),
     )
     .await
     .unwrap();
@@ -1889,6 +1892,214 @@ async fn test_invalid_transaction_gas_charged() {
     );
 }
 
+#[tokio::test]
+async fn test_block_transaction_search_and_mempool() {
+    let (mut swarm, cli, _faucet, rosetta_client) = setup_simple_test(1).await;
+    let chain_id = swarm.chain_id();
+    let client = swarm.aptos_public_info().client().clone();
+    let sender = cli.account_id(0);
+    let sender_private_key = cli.private_key(0);
+    let receiver = AccountAddress::from_hex_literal("0xBEEF").unwrap();
+    let network = NetworkIdentifier::from(chain_id);
+    let node_clients = NodeClients {
+        rosetta_client: &rosetta_client,
+        rest_client: &client,
+        network: &network,
+    };
+    wait_for_rosetta_block(&node_clients, 2).await;
+
+    let txn = simple_transfer_and_wait(
+        &node_clients,
+        sender_private_key,
+        receiver,
+        DEFAULT_TRANSFER_AMOUNT,
+    )
+    .await
+    .unwrap();
+    let transaction_identifier = TransactionIdentifier {
+        hash: format!("{:x}", txn.info.hash),
+    };
+    let block_height = client
+        .get_block_by_version_bcs(txn.info.version.0, false)
+        .await
+        .unwrap()
+        .into_inner()
+        .block_height;
+    let block_identifier = try_until_ok_default(|| {
+        rosetta_client.block(&BlockRequest::by_index(chain_id, block_height))
+    })
+    .await
+    .unwrap()
+    .block
+    .block_identifier;
+
+    // The transaction can be retrieved from its block
+    let request = BlockTransactionRequest {
+        network_identifier: network.clone(),
+        block_identifier: block_identifier.clone(),
+        transaction_identifier: transaction_identifier.clone(),
+    };
+    let response = rosetta_client.block_transaction(&request).await.unwrap();
+    assert_eq!(
+        transaction_identifier,
+        response.transaction.transaction_identifier
+    );
+    assert_eq!(txn.info.version.0, response.transaction.metadata.version.0);
+    assert!(!response.transaction.metadata.failed);
+
+    // But not from a different block
+    let request = BlockTransactionRequest {
+        block_identifier: BlockIdentifier {
+            index: block_height + 1,
+            hash: BlockHash::new(chain_id, block_height + 1).to_string(),
+        },
+        ..request
+    };
+    rosetta_client
+        .block_transaction(&request)
+        .await
+        .expect_err("Transaction should not be found in another block");
+
+    // Search by hash
+    let response = rosetta_client
+        .search_transactions(&search_request(
+            &network,
+            Some(transaction_identifier.clone()),
+            None,
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(1, response.total_count);
+    assert_eq!(None, response.next_offset);
+    assert_eq!(1, response.transactions.len());
+    assert_eq!(block_identifier, response.transactions[0].block_identifier);
+    assert_eq!(
+        transaction_identifier,
+        response.transactions[0].transaction.transaction_identifier
+    );
+
+    // Search by hash, filtered to another sender
+    let response = rosetta_client
+        .search_transactions(&search_request(
+            &network,
+            Some(transaction_identifier.clone()),
+            Some(AccountIdentifier::base_account(receiver)),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert!(response.transactions.is_empty());
+
+    // Search by sender
+    let response = rosetta_client
+        .search_transactions(&search_request(
+            &network,
+            None,
+            Some(AccountIdentifier::base_account(sender)),
+            None,
+        ))
+        .await
+        .unwrap();
+    assert_eq!(1, response.total_count);
+    assert_eq!(1, response.transactions.len());
+    assert_eq!(
+        transaction_identifier,
+        response.transactions[0].transaction.transaction_identifier
+    );
+
+    // Search by sender, before the transaction was committed
+    let response = rosetta_client
+        .search_transactions(&search_request(
+            &network,
+            None,
+            Some(AccountIdentifier::base_account(sender)),
+            Some(block_height - 1),
+        ))
+        .await
+        .unwrap();
+    assert!(response.transactions.is_empty());
+    assert_eq!(None, response.next_offset);
+
+    // Searching without any criteria isn't supported
+    rosetta_client
+        .search_transactions(&search_request(&network, None, None, None))
+        .await
+        .expect_err("Search should require a hash or an account");
+
+    // The receiver has exactly one coin, of the amount transferred
+    let request = AccountCoinsRequest {
+        network_identifier: network.clone(),
+        account_identifier: AccountIdentifier::base_account(receiver),
+        include_mempool: false,
+        currencies: Some(vec![native_coin()]),
+    };
+    let response = rosetta_client.account_coins(&request).await.unwrap();
+    assert!(response.block_identifier.index >= block_height);
+    assert_eq!(1, response.coins.len());
+    assert_eq!(
+        format!(
+            "{}:{}",
+            request.account_identifier.address,
+            native_coin().symbol
+        ),
+        response.coins[0].coin_identifier.identifier
+    );
+    assert_eq!(native_coin(), response.coins[0].amount.currency);
+    assert_eq!(
+        DEFAULT_TRANSFER_AMOUNT.to_string(),
+        response.coins[0].amount.value
+    );
+    rosetta_client
+        .account_coins(&AccountCoinsRequest {
+            include_mempool: true,
+            ..request
+        })
+        .await
+        .expect_err("Mempool coins are not supported");
+
+    // Committed transactions are no longer in mempool
+    let response = rosetta_client
+        .mempool(&MempoolRequest {
+            network_identifier: network.clone(),
+        })
+        .await
+        .unwrap();
+    assert!(!response
+        .transaction_identifiers
+        .contains(&transaction_identifier));
+    rosetta_client
+        .mempool_transaction(&MempoolTransactionRequest {
+            network_identifier: network.clone(),
+            transaction_identifier,
+        })
+        .await
+        .expect_err("Committed transaction should not be found in mempool");
+}
+
+fn search_request(
+    network: &NetworkIdentifier,
+    transaction_identifier: Option<TransactionIdentifier>,
+    account_identifier: Option<AccountIdentifier>,
+    max_block: Option<u64>,
+) -> SearchTransactionsRequest {
+    SearchTransactionsRequest {
+        network_identifier: network.clone(),
+        transaction_identifier,
+        account_identifier,
+        max_block,
+        offset: None,
+        limit: None,
+        operator: None,
+        coin_identifier: None,
+        currency: None,
+        status: None,
+        operation_type: None,
+        address: None,
+        success: None,
+    }
+}
+
 fn assert_failed_transfer_transaction(
     sender: AccountAddress,
     receiver: AccountAddress,
@@ -16,6 +16,7 @@ use aptos_release_builder::{
         gas::generate_gas_upgrade_proposal,
         ExecutionMode, Proposal, ProposalMetadata,
     },
+    dry_run::{dry_run_release, DryRunSource},
     ReleaseEntry,
 };
 use aptos_temppath::TempPath;
@@ -286,3 +287,50 @@ async fn test_release_validate_tool_multi_step() {
 
     check_create_mint_transfer(&mut env).await;
 }
+
+#[tokio::test]
+/// This test dry runs a multi-step governance proposal against the state of a live network,
+/// i.e: the proposal is voted through and resolved without anything being committed.
+async fn test_release_dry_run() {
+    let mut env = SwarmBuilder::new_local(1).with_aptos().build().await;
+    let rest_api_url = url::Url::parse(&env.chain_info().rest_api_url).unwrap();
+
+    let config = aptos_release_builder::ReleaseConfig {
+        name: "DryRun".to_string(),
+        remote_endpoint: None,
+        proposals: vec![Proposal {
+            execution_mode: ExecutionMode::MultiStep,
+            name: "feature_flags".to_string(),
+            metadata: ProposalMetadata::default(),
+            update_sequence: vec![ReleaseEntry::FeatureFlag(Features {
+                enabled: vec![],
+                disabled: vec![FeatureFlag::MultisigAccounts],
+            })],
+        }],
+    };
+
+    let report = dry_run_release(&config, DryRunSource::Rest(rest_api_url), None)
+        .await
+        .unwrap();
+    assert!(!report.steps.is_empty());
+    assert!(report.first_abort().is_none(), "{}", report);
+    assert!(report.steps.last().unwrap().reconfigured);
+    assert_eq!(report.epoch_after, report.epoch_before + 1);
+    assert_eq!(report.disabled_features, vec![
+        FeatureFlag::MultisigAccounts
+    ]);
+    assert!(report.enabled_features.is_empty());
+
+    // Nothing was committed, so the feature is still enabled on chain.
+    let features = env
+        .aptos_public_info()
+        .client()
+        .get_account_resource_bcs::<aptos_types::on_chain_config::Features>(
+            aptos_types::account_config::CORE_CODE_ADDRESS,
+            "0x1::features::Features",
+        )
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(features.is_enabled(aptos_types::on_chain_config::FeatureFlag::MULTISIG_ACCOUNTS));
+}
@@ -8,8 +8,8 @@ use crate::{
     file_format_common::VERSION_5,
     normalized::Module,
 };
-use move_core_types::vm_status::StatusCode;
-use std::collections::BTreeSet;
+use move_core_types::{identifier::Identifier, language_storage::ModuleId, vm_status::StatusCode};
+use std::{collections::BTreeSet, fmt};
 
 /// The result of a linking and layout compatibility check. Here is what the different combinations. NOTE that if `check_struct_layout` is false, type safety over a series of upgrades cannot be guaranteed.
 /// mean:
@@ -71,135 +71,28 @@ impl Compatibility {
 
     /// Check compatibility for `new_module` relative to old module `old_module`.
     pub fn check(&self, old_module: &Module, new_module: &Module) -> PartialVMResult<()> {
-        let mut struct_and_pub_function_linking = true;
-        let mut struct_layout = true;
-        let mut friend_linking = true;
+        let changes = changes(old_module, new_module);
 
-        // module's name and address are unchanged
-        if old_module.address != new_module.address || old_module.name != new_module.name {
-            struct_and_pub_function_linking = false;
-        }
-
-        // old module's structs are a subset of the new module's structs
-        for (name, old_struct) in &old_module.structs {
-            let new_struct = match new_module.structs.get(name) {
-                Some(new_struct) => new_struct,
-                None => {
-                    // Struct not present in new . Existing modules that depend on this struct will fail to link with the new version of the module.
-                    // Also, struct layout cannot be guaranteed transitively, because after
-                    // removing the struct, it could be re-added later with a different layout.
-                    struct_and_pub_function_linking = false;
-                    struct_layout = false;
-                    break;
-                },
-            };
-
-            if !struct_abilities_compatibile(old_struct.abilities, new_struct.abilities)
-                || !struct_type_parameters_compatibile(
-                    &old_struct.type_parameters,
-                    &new_struct.type_parameters,
-                )
-            {
-                struct_and_pub_function_linking = false;
-            }
-            if new_struct.fields != old_struct.fields {
-                // Fields changed. Code in this module will fail at runtime if it tries to
-                // read a previously published struct value
-                // TODO: this is a stricter definition than required. We could in principle
-                // choose that changing the name (but not position or type) of a field is
-                // compatible. The VM does not care about the name of a field
-                // (it's purely informational), but clients presumably do.
-                struct_layout = false
-            }
-        }
-
-        // The modules are considered as compatible function-wise when all the conditions are met:
-        //
-        // - old module's public functions are a subset of the new module's public functions
-        //   (i.e. we cannot remove or change public functions)
-        // - old module's script functions are a subset of the new module's script functions
-        //   (i.e. we cannot remove or change script functions)
-        // - for any friend function that is removed or changed in the old module
-        //   - if the function visibility is upgraded to public, it is OK
-        //   - otherwise, it is considered as incompatible.
-        //
-        // NOTE: it is possible to relax the compatibility checking for a friend function, i.e.,
-        // we can remove/change a friend function if the function is not used by any module in the
-        // friend list. But for simplicity, we decided to go to the more restrictive form now and
-        // we may revisit this in the future.
-        for (name, old_func) in &old_module.exposed_functions {
-            let new_func = match new_module.exposed_functions.get(name) {
-                Some(new_func) => new_func,
-                None => {
-                    if matches!(old_func.visibility, Visibility::Friend) {
-                        friend_linking = false;
-                    } else {
-                        struct_and_pub_function_linking = false;
-                    }
-                    continue;
-                },
-            };
-            let is_vis_compatible = match (old_func.visibility, new_func.visibility) {
-                // public must remain public
-                (Visibility::Public, Visibility::Public) => true,
-                (Visibility::Public, _) => false,
-                // friend can become public or remain friend
-                (Visibility::Friend, Visibility::Public)
-                | (Visibility::Friend, Visibility::Friend) => true,
-                (Visibility::Friend, _) => false,
-                // private can become public or friend, or stay private
-                (Visibility::Private, _) => true,
-            };
-            let is_entry_compatible = if old_module.file_format_version < VERSION_5
-                && new_module.file_format_version < VERSION_5
-            {
-                // if it was public(script), it must remain pubic(script)
-                // if it was not public(script), it _cannot_ become public(script)
-                old_func.is_entry == new_func.is_entry
-            } else {
-                // If it was an entry function, it must remain one.
-                // If it was not an entry function, it is allowed to become one.
-                !old_func.is_entry || new_func.is_entry
-            };
-            if !is_vis_compatible
-                || !is_entry_compatible
-                || old_func.parameters != new_func.parameters
-                || old_func.return_ != new_func.return_
-                || !fun_type_parameters_compatibile(
-                    &old_func.type_parameters,
-                    &new_func.type_parameters,
-                )
-            {
-                if matches!(old_func.visibility, Visibility::Friend) {
-                    friend_linking = false;
-                } else {
-                    struct_and_pub_function_linking = false;
-                }
-            }
-        }
-
-        // check friend declarations compatibility
-        //
-        // - additions to the list are allowed
-        // - removals are not allowed
-        //
-        let old_friend_module_ids: BTreeSet<_> = old_module.friends.iter().cloned().collect();
-        let new_friend_module_ids: BTreeSet<_> = new_module.friends.iter().cloned().collect();
-        if !old_friend_module_ids.is_subset(&new_friend_module_ids) {
-            friend_linking = false;
-        }
-
-        if self.check_struct_and_pub_function_linking && !struct_and_pub_function_linking {
+        if self.check_struct_and_pub_function_linking
+            && changes
+                .iter()
+                .any(IncompatibleChange::breaks_struct_and_pub_function_linking)
+        {
             return Err(PartialVMError::new(
                 StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
             ).with_message(format!("Module Update Failure: Public function/struct signature of new module differs from existing module in {:?}::{}", old_module.address, old_module.name)));
         }
-        if self.check_struct_layout && !struct_layout {
+        if self.check_struct_layout && changes.iter().any(IncompatibleChange::breaks_struct_layout)
+        {
             return Err(PartialVMError::new(
                 StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
             ).with_message(format!("Module Update Failure: Struct layout of new module differs from existing modul in {:?}::{}", old_module.address, old_module.name)));
         }
-        if self.check_friend_linking && !friend_linking {
+        if self.check_friend_linking
+            && changes
+                .iter()
+                .any(IncompatibleChange::breaks_friend_linking)
+        {
             return Err(PartialVMError::new(
                 StatusCode::BACKWARD_INCOMPATIBLE_MODULE_UPDATE,
             ).with_message(format!("Module Update Failure: Friend signature of new module differs from existing module in {:?}::{}", old_module.address, old_module.name)));
@@ -207,6 +100,238 @@ impl Compatibility {
 
         Ok(())
     }
+
+    /// Returns all the changes of `new_module` relative to `old_module` which fail the
+    /// compatibility check, whereas `check` only reports the first kind of failure.
+    pub fn incompatible_changes(
+        &self,
+        old_module: &Module,
+        new_module: &Module,
+    ) -> Vec<IncompatibleChange> {
+        changes(old_module, new_module)
+            .into_iter()
+            .filter(|change| {
+                (self.check_struct_and_pub_function_linking
+                    && change.breaks_struct_and_pub_function_linking())
+                    || (self.check_struct_layout && change.breaks_struct_layout())
+                    || (self.check_friend_linking && change.breaks_friend_linking())
+            })
+            .collect()
+    }
+}
+
+/// A change between two versions of a module which may break compatibility, depending on the
+/// checks that are enabled.
+#[derive(PartialEq, Eq, Debug, Clone)]
+pub enum IncompatibleChange {
+    /// The address or the name of the module changed.
+    ModuleIdChanged,
+    StructRemoved(Identifier),
+    /// Abilities were removed from the struct, or its type parameters changed.
+    StructSignatureChanged(Identifier),
+    StructLayoutChanged(Identifier),
+    /// A function which is public, friend or entry was removed.
+    FunctionRemoved(Identifier, Visibility),
+    /// The visibility, entry modifier, parameters, return types or type parameters of a function
+    /// which is public, friend or entry changed.
+    FunctionSignatureChanged(Identifier, Visibility),
+    FriendRemoved(ModuleId),
+}
+
+impl IncompatibleChange {
+    /// Whether dependent modules that reference functions or types in the module may not link.
+    pub fn breaks_struct_and_pub_function_linking(&self) -> bool {
+        match self {
+            IncompatibleChange::ModuleIdChanged
+            | IncompatibleChange::StructRemoved(_)
+            | IncompatibleChange::StructSignatureChanged(_) => true,
+            IncompatibleChange::FunctionRemoved(_, visibility)
+            | IncompatibleChange::FunctionSignatureChanged(_, visibility) => {
+                !matches!(visibility, Visibility::Friend)
+            },
+            IncompatibleChange::StructLayoutChanged(_) | IncompatibleChange::FriendRemoved(_) => {
+                false
+            },
+        }
+    }
+
+    /// Whether reading structs previously published by the module may fail.
+    pub fn breaks_struct_layout(&self) -> bool {
+        matches!(
+            self,
+            IncompatibleChange::StructRemoved(_) | IncompatibleChange::StructLayoutChanged(_)
+        )
+    }
+
+    /// Whether friend modules may not link.
+    pub fn breaks_friend_linking(&self) -> bool {
+        match self {
+            IncompatibleChange::FunctionRemoved(_, visibility)
+            | IncompatibleChange::FunctionSignatureChanged(_, visibility) => {
+                matches!(visibility, Visibility::Friend)
+            },
+            IncompatibleChange::FriendRemoved(_) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for IncompatibleChange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let quote_visibility = |visibility: &Visibility| match visibility {
+            Visibility::Public => "public",
+            Visibility::Friend => "friend",
+            // Private functions are only exposed if they are entry functions
+            Visibility::Private => "entry",
+        };
+        match self {
+            IncompatibleChange::ModuleIdChanged => write!(f, "address or name of module changed"),
+            IncompatibleChange::StructRemoved(name) => write!(f, "struct `{}` was removed", name),
+            IncompatibleChange::StructSignatureChanged(name) => write!(
+                f,
+                "abilities or type parameters of struct `{}` changed",
+                name
+            ),
+            IncompatibleChange::StructLayoutChanged(name) => {
+                write!(f, "fields of struct `{}` changed", name)
+            },
+            IncompatibleChange::FunctionRemoved(name, visibility) => write!(
+                f,
+                "{} function `{}` was removed",
+                quote_visibility(visibility),
+                name
+            ),
+            IncompatibleChange::FunctionSignatureChanged(name, visibility) => write!(
+                f,
+                "signature of {} function `{}` changed",
+                quote_visibility(visibility),
+                name
+            ),
+            IncompatibleChange::FriendRemoved(module_id) => {
+                write!(f, "friend `{}` was removed", module_id)
+            },
+        }
+    }
+}
+
+/// Returns the changes of `new_module` relative to `old_module` which may break compatibility.
+fn changes(old_module: &Module, new_module: &Module) -> Vec<IncompatibleChange> {
+    let mut changes = vec![];
+
+    // module's name and address are unchanged
+    if old_module.address != new_module.address || old_module.name != new_module.name {
+        changes.push(IncompatibleChange::ModuleIdChanged);
+    }
+
+    // old module's structs are a subset of the new module's structs
+    for (name, old_struct) in &old_module.structs {
+        let new_struct = match new_module.structs.get(name) {
+            Some(new_struct) => new_struct,
+            None => {
+                // Struct not present in new . Existing modules that depend on this struct will fail to link with the new version of the module.
+                // Also, struct layout cannot be guaranteed transitively, because after
+                // removing the struct, it could be re-added later with a different layout.
+                changes.push(IncompatibleChange::StructRemoved(name.clone()));
+                continue;
+            },
+        };
+
+        if !struct_abilities_compatibile(old_struct.abilities, new_struct.abilities)
+            || !struct_type_parameters_compatibile(
+                &old_struct.type_parameters,
+                &new_struct.type_parameters,
+            )
+        {
+            changes.push(IncompatibleChange::StructSignatureChanged(name.clone()));
+        }
+        if new_struct.fields != old_struct.fields {
+            // Fields changed. Code in this module will fail at runtime if it tries to
+            // read a previously published struct value
+            // TODO: this is a stricter definition than required. We could in principle
+            // choose that changing the name (but not position or type) of a field is
+            // compatible. The VM does not care about the name of a field
+            // (it's purely informational), but clients presumably do.
+            changes.push(IncompatibleChange::StructLayoutChanged(name.clone()));
+        }
+    }
+
+    // The modules are considered as compatible function-wise when all the conditions are met:
+    //
+    // - old module's public functions are a subset of the new module's public functions
+    //   (i.e. we cannot remove or change public functions)
+    // - old module's script functions are a subset of the new module's script functions
+    //   (i.e. we cannot remove or change script functions)
+    // - for any friend function that is removed or changed in the old module
+    //   - if the function visibility is upgraded to public, it is OK
+    //   - otherwise, it is considered as incompatible.
+    //
+    // NOTE: it is possible to relax the compatibility checking for a friend function, i.e.,
+    // we can remove/change a friend function if the function is not used by any module in the
+    // friend list. But for simplicity, we decided to go to the more restrictive form now and
+    // we may revisit this in the future.
+    for (name, old_func) in &old_module.exposed_functions {
+        let new_func = match new_module.exposed_functions.get(name) {
+            Some(new_func) => new_func,
+            None => {
+                changes.push(IncompatibleChange::FunctionRemoved(
+                    name.clone(),
+                    old_func.visibility,
+                ));
+                continue;
+            },
+        };
+        let is_vis_compatible = match (old_func.visibility, new_func.visibility) {
+            // public must remain public
+            (Visibility::Public, Visibility::Public) => true,
+            (Visibility::Public, _) => false,
+            // friend can become public or remain friend
+            (Visibility::Friend, Visibility::Public) | (Visibility::Friend, Visibility::Friend) => {
+                true
+            },
+            (Visibility::Friend, _) => false,
+            // private can become public or friend, or stay private
+            (Visibility::Private, _) => true,
+        };
+        let is_entry_compatible = if old_module.file_format_version < VERSION_5
+            && new_module.file_format_version < VERSION_5
+        {
+            // if it was public(script), it must remain pubic(script)
+            // if it was not public(script), it _cannot_ become public(script)
+            old_func.is_entry == new_func.is_entry
+        } else {
+            // If it was an entry function, it must remain one.
+            // If it was not an entry function, it is allowed to become one.
+            !old_func.is_entry || new_func.is_entry
+        };
+        if !is_vis_compatible
+            || !is_entry_compatible
+            || old_func.parameters != new_func.parameters
+            || old_func.return_ != new_func.return_
+            || !fun_type_parameters_compatibile(
+                &old_func.type_parameters,
+                &new_func.type_parameters,
+            )
+        {
+            changes.push(IncompatibleChange::FunctionSignatureChanged(
+                name.clone(),
+                old_func.visibility,
+            ));
+        }
+    }
+
+    // check friend declarations compatibility
+    //
+    // - additions to the list are allowed
+    // - removals are not allowed
+    //
+    let new_friend_module_ids: BTreeSet<_> = new_module.friends.iter().collect();
+    for friend in &old_module.friends {
+        if !new_friend_module_ids.contains(friend) {
+            changes.push(IncompatibleChange::FriendRemoved(friend.clone()));
+        }
+    }
+
+    changes
 }
 
 // When upgrading, the new abilities must be a superset of the old abilities.
@@ -1,7 +1,11 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{compatibility::Compatibility, file_format::*, normalized};
+use crate::{
+    compatibility::{Compatibility, IncompatibleChange},
+    file_format::*,
+    normalized,
+};
 use move_core_types::{account_address::AccountAddress, identifier::Identifier};
 use std::convert::TryFrom;
 
@@ -114,3 +118,45 @@ fn deprecated_add_script_visibility() {
         .check(&friend_module, &script_module)
         .is_err());
 }
+
+#[test]
+fn incompatible_changes() {
+    let public_module = mk_module(Visibility::Public as u8);
+    let friend_module = mk_module(Visibility::Friend as u8);
+    let private_module = mk_module(Visibility::Private as u8);
+    let fn_name = Identifier::new("fn").unwrap();
+
+    assert_eq!(
+        Compatibility::full_check().incompatible_changes(&public_module, &friend_module),
+        vec![IncompatibleChange::FunctionSignatureChanged(
+            fn_name.clone(),
+            Visibility::Public
+        )]
+    );
+    // Private functions which are not entry functions are not exposed
+    assert_eq!(
+        Compatibility::full_check().incompatible_changes(&public_module, &private_module),
+        vec![IncompatibleChange::FunctionRemoved(
+            fn_name.clone(),
+            Visibility::Public
+        )]
+    );
+    // Changes of friend functions are only incompatible if friends are checked
+    assert_eq!(
+        Compatibility::full_check().incompatible_changes(&friend_module, &private_module),
+        vec![IncompatibleChange::FunctionRemoved(
+            fn_name,
+            Visibility::Friend
+        )]
+    );
+    assert!(Compatibility::new(true, true, false)
+        .incompatible_changes(&friend_module, &private_module)
+        .is_empty());
+    assert!(Compatibility::new(true, true, false)
+        .check(&friend_module, &private_module)
+        .is_ok());
+    // Adding a function is compatible
+    assert!(Compatibility::full_check()
+        .incompatible_changes(&private_module, &public_module)
+        .is_empty());
+}
@@ -5,9 +5,11 @@
 pub mod deposit;
 pub mod new_block;
 pub mod new_epoch;
+pub mod transfer;
 pub mod withdraw;
 
 pub use deposit::*;
 pub use new_block::*;
 pub use new_epoch::*;
+pub use transfer::*;
 pub use withdraw::*;
@@ -0,0 +1,42 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use move_core_types::{
+    account_address::AccountAddress, ident_str, identifier::IdentStr, move_resource::MoveStructType,
+};
+use serde::{Deserialize, Serialize};
+
+/// Struct that represents an object TransferEvent.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TransferEvent {
+    object: AccountAddress,
+    from: AccountAddress,
+    to: AccountAddress,
+}
+
+impl TransferEvent {
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self> {
+        bcs::from_bytes(bytes).map_err(Into::into)
+    }
+
+    /// Get the address of the transferred object
+    pub fn object(&self) -> AccountAddress {
+        self.object
+    }
+
+    /// Get the previous owner of the object
+    pub fn from(&self) -> AccountAddress {
+        self.from
+    }
+
+    /// Get the new owner of the object
+    pub fn to(&self) -> AccountAddress {
+        self.to
+    }
+}
+
+impl MoveStructType for TransferEvent {
+    const MODULE_NAME: &'static IdentStr = ident_str!("object");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("TransferEvent");
+}
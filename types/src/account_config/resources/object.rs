@@ -1,7 +1,9 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::event::EventHandle;
 use move_core_types::{
+    account_address::AccountAddress,
     ident_str,
     identifier::IdentStr,
     move_resource::{MoveResource, MoveStructType},
@@ -21,3 +23,41 @@ impl MoveStructType for ObjectGroupResource {
 }
 
 impl MoveResource for ObjectGroupResource {}
+
+/// A Rust representation of ObjectCore, which every object has in its ObjectGroup.
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct ObjectCoreResource {
+    guid_creation_num: u64,
+    owner: AccountAddress,
+    allow_ungated_transfer: bool,
+    transfer_events: EventHandle,
+}
+
+impl ObjectCoreResource {
+    pub fn new(
+        guid_creation_num: u64,
+        owner: AccountAddress,
+        allow_ungated_transfer: bool,
+        transfer_events: EventHandle,
+    ) -> Self {
+        Self {
+            guid_creation_num,
+            owner,
+            allow_ungated_transfer,
+            transfer_events,
+        }
+    }
+
+    /// Get the address of the owner of the object
+    pub fn owner(&self) -> AccountAddress {
+        self.owner
+    }
+}
+
+impl MoveStructType for ObjectCoreResource {
+    const MODULE_NAME: &'static IdentStr = ident_str!("object");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("ObjectCore");
+}
+
+impl MoveResource for ObjectCoreResource {}